@@ -1,20 +1,54 @@
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::PathBuf;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Instant;
 
 use anyhow::{anyhow, Context, Result};
-use clap::{Parser, Subcommand, ValueEnum};
-use serde_json::Value;
+use clap::{Arg, Command, CommandFactory, Parser, Subcommand, ValueEnum};
+use clap_complete::Shell;
+use serde::Serialize;
+use serde_json::{json, Value};
+use solana_dex_parser::core::program_tables::SharedProgramTables;
+use solana_dex_parser::format::to_json_value;
+use solana_dex_parser::io::{self as dex_io, NdjsonWriter};
+use solana_dex_parser::jobs::daily_rollup::DailyRollup;
+use solana_dex_parser::protocols::registry;
 use solana_dex_parser::rpc;
+use solana_dex_parser::service::{AdminServer, ServiceStats};
+use solana_dex_parser::stream::{self, OverflowPolicy};
 use solana_dex_parser::types::FromJsonValue;
-use solana_dex_parser::{DexParser, ParseConfig, SolanaBlock, SolanaTransaction};
+use solana_dex_parser::{
+    BlockParseResult, DexParser, JsonFormat, MemeEvent, ParseConfig, ParseResult, ParserPool,
+    PoolEvent, SolanaBlock, SolanaTransaction, TradeAlert, TradeInfo, TransferData, UiAmountFormat,
+    WatchList, WatchSpec,
+};
 
 const DEFAULT_RPC_URL: &str = "https://api.mainnet-beta.solana.com";
+const DEFAULT_ADMIN_ADDR: &str = "127.0.0.1:9464";
 
 #[derive(Parser)]
 #[command(author, version, about = "Parse Solana DEX transactions", long_about = None)]
+#[command(arg_required_else_help = true)]
 struct Cli {
+    /// Print a machine-readable description of all subcommands and flags
+    #[arg(long)]
+    help_json: bool,
+    /// Render UI token amounts as JSON numbers or exact decimal strings
+    #[arg(long, global = true, value_enum, default_value = "number")]
+    ui_amounts: UiAmounts,
+    /// Emit canonical JSON (sorted keys, events ordered by idx) for byte-identical output
+    #[arg(long, global = true)]
+    canonical: bool,
+    /// JSON or .toml file naming extra programs and their discriminators; `serve` reloads
+    /// it on SIGHUP
+    #[arg(long, global = true)]
+    program_tables: Option<PathBuf>,
     #[command(subcommand)]
-    command: Commands,
+    command: Option<Commands>,
 }
 
 #[derive(Subcommand)]
@@ -36,6 +70,60 @@ enum Commands {
         /// Block parsing mode
         #[arg(long, value_enum, default_value = "parsed")]
         mode: BlockMode,
+        /// Write one JSON line per transaction result here instead of stdout (.gz/.zst compress)
+        #[arg(long)]
+        output: Option<PathBuf>,
+        /// Parse only this fraction of the transactions, picked by signature hash
+        #[arg(long)]
+        sample_rate: Option<f64>,
+    },
+    /// Parse every block/transaction JSON dump under a directory concurrently
+    ParseDir {
+        /// Directory to walk recursively
+        #[arg(long)]
+        dir: PathBuf,
+        /// Number of worker threads
+        #[arg(long, default_value_t = default_jobs())]
+        jobs: usize,
+        /// Write all results to this NDJSON sink instead of `<name>.parsed.json` next to each input
+        #[arg(long)]
+        output: Option<PathBuf>,
+        /// Parse only this fraction of each block's transactions, picked by signature hash
+        #[arg(long)]
+        sample_rate: Option<f64>,
+    },
+    /// Roll parse results up into per-day, per-venue, per-token CSV statistics
+    Rollup {
+        /// NDJSON or JSON files of parse results or block results (.gz/.zst accepted)
+        #[arg(required = true)]
+        inputs: Vec<PathBuf>,
+        /// Write the CSV here (.gz/.zst compress, .parquet for Parquet) instead of stdout
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+    /// Parse NDJSON transactions as a long-running service with an HTTP admin endpoint
+    Serve {
+        /// NDJSON file of transactions to read (.gz/.zst accepted); stdin when omitted
+        #[arg(long)]
+        input: Option<PathBuf>,
+        /// Write one JSON line per result here instead of stdout (.gz/.zst compress)
+        #[arg(long)]
+        output: Option<PathBuf>,
+        /// Address of the /healthz, /readyz and /status endpoint
+        #[arg(long, default_value = DEFAULT_ADMIN_ADDR)]
+        admin_addr: String,
+        /// Number of pooled parsers
+        #[arg(long, default_value_t = default_jobs())]
+        jobs: usize,
+        /// Capacity of the input and result queues
+        #[arg(long, default_value_t = 1024)]
+        channel_capacity: usize,
+        /// What to do with new input while the input queue is full
+        #[arg(long, value_enum, default_value = "park")]
+        overflow: Overflow,
+        /// JSON array of watches ({name, wallets, mints, webhook}) to push matching trades to
+        #[arg(long)]
+        watch: Option<PathBuf>,
     },
     /// Fetch a transaction by signature via RPC
     ParseSig {
@@ -49,6 +137,36 @@ enum Commands {
         #[arg(long, value_enum, default_value = "all")]
         mode: TxMode,
     },
+    /// Convert a transaction between RPC JSON and the normalized parser input
+    Convert {
+        /// Path to a JSON file containing a transaction
+        #[arg(long)]
+        file: PathBuf,
+        /// Format of the input file
+        #[arg(long, value_enum, default_value = "rpc-json")]
+        from: TxFormat,
+        /// Format to print
+        #[arg(long, value_enum, default_value = "normalized")]
+        to: TxFormat,
+    },
+    /// Print the JSON schema of a parser result type
+    Schema {
+        /// Result type to describe
+        #[arg(long = "type", value_enum, default_value = "parse-result")]
+        target: SchemaTarget,
+    },
+    /// Dump the known discriminator registry
+    Discriminators {
+        /// Only list discriminators of this program id
+        #[arg(long)]
+        program: Option<String>,
+    },
+    /// Generate a shell completion script
+    Completions {
+        /// Target shell
+        #[arg(value_enum)]
+        shell: Shell,
+    },
 }
 
 #[derive(Clone, ValueEnum)]
@@ -65,67 +183,683 @@ enum BlockMode {
     Parsed,
 }
 
+#[derive(Clone, Copy, ValueEnum)]
+enum UiAmounts {
+    Number,
+    String,
+}
+
+impl From<UiAmounts> for UiAmountFormat {
+    fn from(value: UiAmounts) -> Self {
+        match value {
+            UiAmounts::Number => Self::Number,
+            UiAmounts::String => Self::String,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Overflow {
+    Park,
+    DropNewest,
+    DropOldest,
+}
+
+impl From<Overflow> for OverflowPolicy {
+    fn from(value: Overflow) -> Self {
+        match value {
+            Overflow::Park => Self::Park,
+            Overflow::DropNewest => Self::DropNewest,
+            Overflow::DropOldest => Self::DropOldest,
+        }
+    }
+}
+
+#[derive(Clone, ValueEnum)]
+enum TxFormat {
+    /// `getTransaction` response with JSON encoding
+    RpcJson,
+    /// SolanaTransaction as consumed by the parser
+    Normalized,
+}
+
+#[derive(Clone, ValueEnum)]
+enum SchemaTarget {
+    ParseResult,
+    BlockParseResult,
+    Trade,
+    Liquidity,
+    Transfer,
+    MemeEvent,
+    Config,
+}
+
+/// Aggregated outcome of `parse-dir`.
+#[derive(Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DirSummary {
+    files: usize,
+    failed: usize,
+    transactions: usize,
+    trades: usize,
+    liquidities: usize,
+    transfers: usize,
+    meme_events: usize,
+    elapsed_ms: u128,
+    errors: Vec<Value>,
+}
+
+impl DirSummary {
+    fn record(&mut self, path: &Path, outcome: Result<Vec<ParseResult>>) {
+        self.files += 1;
+        match outcome {
+            Ok(results) => {
+                self.transactions += results.len();
+                for result in &results {
+                    self.trades += result.trades.len();
+                    self.liquidities += result.liquidities.len();
+                    self.transfers += result.transfers.len();
+                    self.meme_events += result.meme_events.len();
+                }
+            }
+            Err(err) => {
+                self.failed += 1;
+                self.errors.push(json!({
+                    "file": path.display().to_string(),
+                    "error": format!("{err:#}"),
+                }));
+            }
+        }
+    }
+}
+
+/// NDJSON sink of `parse-dir` that writes each file's results in file order, whichever
+/// worker finishes first, so the output does not depend on thread scheduling.
+struct OrderedSink {
+    writer: NdjsonWriter,
+    next: usize,
+    pending: BTreeMap<usize, Vec<ParseResult>>,
+}
+
+impl OrderedSink {
+    fn new(writer: NdjsonWriter) -> Self {
+        Self {
+            writer,
+            next: 0,
+            pending: BTreeMap::new(),
+        }
+    }
+
+    /// Hand over the results of the file at `index`; failed files pass no results.
+    fn write(&mut self, index: usize, results: &[ParseResult]) -> Result<()> {
+        if index != self.next {
+            self.pending.insert(index, results.to_vec());
+            return Ok(());
+        }
+        self.write_records(results)?;
+        self.next += 1;
+        while let Some(results) = self.pending.remove(&self.next) {
+            self.write_records(&results)?;
+            self.next += 1;
+        }
+        Ok(())
+    }
+
+    fn write_records(&mut self, results: &[ParseResult]) -> Result<()> {
+        results
+            .iter()
+            .try_for_each(|result| self.writer.write_record(result))
+    }
+
+    fn finish(self) -> Result<()> {
+        self.writer.finish()
+    }
+}
+
+fn default_jobs() -> usize {
+    thread::available_parallelism().map_or(1, |jobs| jobs.get())
+}
+
 fn read_json(file: &PathBuf) -> Result<Value> {
-    let data = fs::read_to_string(file).with_context(|| format!("failed to read {:?}", file))?;
-    serde_json::from_str(&data).with_context(|| format!("failed to parse JSON in {:?}", file))
+    dex_io::read_json(file)
+}
+
+fn print_block(
+    result: &BlockParseResult,
+    output: Option<&PathBuf>,
+    format: JsonFormat,
+) -> Result<()> {
+    let Some(path) = output else {
+        let result = to_json_value(result, format)?;
+        println!("{}", serde_json::to_string_pretty(&result)?);
+        return Ok(());
+    };
+    let mut writer = NdjsonWriter::create(path)?.with_json_format(format);
+    for tx in &result.transactions {
+        writer.write_record(tx)?;
+    }
+    writer
+        .finish()
+        .with_context(|| format!("failed to write {:?}", path))
 }
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
+    if cli.help_json {
+        let description = describe_command(&Cli::command());
+        println!("{}", serde_json::to_string_pretty(&description)?);
+        return Ok(());
+    }
+    let Some(command) = cli.command else {
+        Cli::command().print_help()?;
+        return Ok(());
+    };
+
+    let mut config = ParseConfig::default();
+    if let Some(path) = &cli.program_tables {
+        config.program_tables = SharedProgramTables::from_file(path)
+            .with_context(|| format!("failed to load program tables {}", path.display()))?;
+    }
+
     let parser = DexParser::new();
-    let config = ParseConfig::default();
+    let format = JsonFormat {
+        ui_amounts: cli.ui_amounts.into(),
+        canonical: cli.canonical,
+    };
 
-    match cli.command {
+    match command {
         Commands::ParseTx { file, mode } => {
             let value = read_json(&file)?;
             let tx =
                 SolanaTransaction::from_value(&value, &config).map_err(|err| anyhow!("{err}"))?;
-            let output = parse_with_mode(&parser, tx, mode, &config)?;
+            let output = parse_with_mode(&parser, tx, mode, &config, format)?;
             println!("{}", serde_json::to_string_pretty(&output)?);
         }
-        Commands::ParseBlock { file, mode } => {
+        Commands::ParseBlock {
+            file,
+            mode,
+            output,
+            sample_rate,
+        } => {
+            let config = sampled(config, sample_rate)?;
             let value = read_json(&file)?;
-            match mode {
+            let result = match mode {
                 BlockMode::Raw => {
                     let txs: Vec<Value> = serde_json::from_value(value)?;
-                    let result = parser.parse_block_raw(&txs, Some(config))?;
-                    println!("{}", serde_json::to_string_pretty(&result)?);
+                    parser.parse_block_raw(&txs, Some(config))?
                 }
                 BlockMode::Parsed => {
                     let block: SolanaBlock = serde_json::from_value(value)?;
-                    let result = parser.parse_block_parsed(&block, Some(config));
-                    println!("{}", serde_json::to_string_pretty(&result)?);
+                    parser.parse_block_parsed(&block, Some(config))
+                }
+            };
+            print_block(&result, output.as_ref(), format)?;
+        }
+        Commands::ParseDir {
+            dir,
+            jobs,
+            output,
+            sample_rate,
+        } => {
+            let config = sampled(config, sample_rate)?;
+            let summary = parse_dir(&parser, &config, &dir, jobs, output.as_ref(), format)?;
+            println!("{}", serde_json::to_string_pretty(&summary)?);
+        }
+        Commands::Rollup { inputs, output } => {
+            let mut rollup = DailyRollup::new().with_config(config);
+            rollup.observe_files(&inputs)?;
+            match output {
+                Some(path) if path.extension().is_some_and(|ext| ext == "parquet") => {
+                    rollup.write_parquet(fs::File::create(&path)?)?;
+                }
+                Some(path) => {
+                    let mut writer = dex_io::CompressedWriter::create(&path)?;
+                    rollup.write_csv(&mut writer)?;
+                    writer.finish()?;
                 }
+                None => rollup.write_csv(io::stdout().lock())?,
             }
         }
+        Commands::Serve {
+            input,
+            output,
+            admin_addr,
+            jobs,
+            channel_capacity,
+            overflow,
+            watch,
+        } => {
+            let pool = ParserPool::new(jobs).with_config(config);
+            let watch = watch
+                .map(|path| {
+                    let specs: Vec<WatchSpec> = serde_json::from_value(read_json(&path)?)
+                        .with_context(|| format!("invalid watch list {}", path.display()))?;
+                    Ok::<_, anyhow::Error>(WatchList::from_specs(specs))
+                })
+                .transpose()?;
+            let tables = pool.config().program_tables;
+            let _reload = cli
+                .program_tables
+                .clone()
+                .map(|path| reload_on_sighup(path, tables))
+                .transpose()?;
+            serve(
+                &pool,
+                input.as_ref(),
+                output.as_ref(),
+                &admin_addr,
+                format,
+                channel_capacity,
+                overflow.into(),
+                watch.as_ref(),
+            )?;
+        }
         Commands::ParseSig {
             signature,
             rpc_url,
             mode,
         } => {
             let tx = rpc::fetch_transaction(&rpc_url, &signature)?;
-            let output = parse_with_mode(&parser, tx, mode, &config)?;
+            let output = parse_with_mode(&parser, tx, mode, &config, format)?;
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        }
+        Commands::Convert { file, from, to } => {
+            let value = read_json(&file)?;
+            let tx = match from {
+                TxFormat::RpcJson => rpc::transaction_from_rpc_json(value)?,
+                TxFormat::Normalized => SolanaTransaction::from_value(&value, &config)
+                    .map_err(|err| anyhow!("{err}"))?,
+            };
+            let output = match to {
+                TxFormat::RpcJson => serde_json::to_value(rpc::transaction_to_rpc(&tx)?)?,
+                TxFormat::Normalized => serde_json::to_value(&tx)?,
+            };
             println!("{}", serde_json::to_string_pretty(&output)?);
         }
+        Commands::Schema { target } => {
+            let schema = match target {
+                SchemaTarget::ParseResult => schemars::schema_for!(ParseResult),
+                SchemaTarget::BlockParseResult => schemars::schema_for!(BlockParseResult),
+                SchemaTarget::Trade => schemars::schema_for!(TradeInfo),
+                SchemaTarget::Liquidity => schemars::schema_for!(PoolEvent),
+                SchemaTarget::Transfer => schemars::schema_for!(TransferData),
+                SchemaTarget::MemeEvent => schemars::schema_for!(MemeEvent),
+                SchemaTarget::Config => schemars::schema_for!(ParseConfig),
+            };
+            println!("{}", serde_json::to_string_pretty(&schema)?);
+        }
+        Commands::Discriminators { program } => {
+            let mut entries = registry::discriminators_with(&config.program_tables.tables());
+            if let Some(program_id) = program.as_deref() {
+                entries.retain(|entry| entry.program_id == program_id);
+            }
+            println!("{}", serde_json::to_string_pretty(&entries)?);
+        }
+        Commands::Completions { shell } => {
+            let mut command = Cli::command();
+            let name = command.get_name().to_string();
+            clap_complete::generate(shell, &mut command, name, &mut std::io::stdout());
+        }
     }
 
     Ok(())
 }
 
+fn sampled(config: ParseConfig, sample_rate: Option<f64>) -> Result<ParseConfig> {
+    match sample_rate {
+        Some(rate) if !(0.0..=1.0).contains(&rate) => Err(anyhow!(
+            "--sample-rate must be between 0.0 and 1.0, got {rate}"
+        )),
+        Some(rate) => Ok(config.with_sample_rate(rate)),
+        None => Ok(config),
+    }
+}
+
+fn parse_dir(
+    parser: &DexParser,
+    config: &ParseConfig,
+    dir: &Path,
+    jobs: usize,
+    output: Option<&PathBuf>,
+    format: JsonFormat,
+) -> Result<DirSummary> {
+    let started = Instant::now();
+    let mut files = Vec::new();
+    collect_dump_files(dir, &mut files)?;
+    files.sort();
+
+    let sink = output
+        .map(|path| Ok::<_, anyhow::Error>(NdjsonWriter::create(path)?.with_json_format(format)))
+        .transpose()?
+        .map(|writer| Mutex::new(OrderedSink::new(writer)));
+    let summary = Mutex::new(DirSummary::default());
+    let next = AtomicUsize::new(0);
+    thread::scope(|scope| {
+        for _ in 0..jobs.max(1) {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(path) = files.get(index) else {
+                    break;
+                };
+                let mut outcome = parse_dump(parser, config, path, sink.is_none(), format);
+                if let Some(sink) = &sink {
+                    let results = outcome.as_deref().unwrap_or_default();
+                    let written = sink
+                        .lock()
+                        .expect("sink lock poisoned")
+                        .write(index, results);
+                    if let Err(err) = written {
+                        outcome = Err(err);
+                    }
+                }
+                summary
+                    .lock()
+                    .expect("summary lock poisoned")
+                    .record(path, outcome);
+            });
+        }
+    });
+
+    if let Some(sink) = sink {
+        let sink = sink.into_inner().expect("sink lock poisoned");
+        sink.finish()?;
+    }
+    let mut summary = summary.into_inner().expect("summary lock poisoned");
+    summary
+        .errors
+        .sort_by(|a, b| a["file"].as_str().cmp(&b["file"].as_str()));
+    summary.elapsed_ms = started.elapsed().as_millis();
+    Ok(summary)
+}
+
+/// Stops reloading program tables when dropped.
+struct SighupReload(signal_hook::iterator::Handle);
+
+impl Drop for SighupReload {
+    fn drop(&mut self) {
+        self.0.close();
+    }
+}
+
+/// Reload `tables` from `path` whenever the process receives SIGHUP. A file that fails to
+/// load keeps the previous tables in place.
+fn reload_on_sighup(path: PathBuf, tables: SharedProgramTables) -> Result<SighupReload> {
+    let mut signals = signal_hook::iterator::Signals::new([signal_hook::consts::SIGHUP])
+        .context("failed to listen for SIGHUP")?;
+    let handle = signals.handle();
+    thread::spawn(move || {
+        for _ in signals.forever() {
+            match tables.reload(&path) {
+                Ok(()) => tracing::info!("reloaded program tables from {}", path.display()),
+                Err(err) => tracing::warn!("keeping previous program tables: {err}"),
+            }
+        }
+    });
+    Ok(SighupReload(handle))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn serve(
+    pool: &ParserPool,
+    input: Option<&PathBuf>,
+    output: Option<&PathBuf>,
+    admin_addr: &str,
+    format: JsonFormat,
+    capacity: usize,
+    overflow: OverflowPolicy,
+    watch: Option<&WatchList>,
+) -> Result<()> {
+    let stats = Arc::new(ServiceStats::new());
+    let admin = AdminServer::start(admin_addr, stats.clone())
+        .with_context(|| format!("failed to bind admin endpoint on {admin_addr}"))?;
+    eprintln!("admin endpoint listening on http://{}", admin.local_addr());
+
+    let (tx_sender, tx_receiver) = stream::bounded::<SolanaTransaction>(capacity, overflow);
+    // Results always park: a slow sink stalls the workers, which fills the input queue,
+    // and that is where `overflow` decides between waiting and shedding.
+    let (result_sender, result_receiver) =
+        stream::bounded::<ParseResult>(capacity, OverflowPolicy::Park);
+    stats.register_channel("input", tx_sender.counters());
+    stats.register_channel("results", result_sender.counters());
+    // A slow webhook must not hold up parsing, so alerts shed the oldest instead of parking.
+    let (alert_sender, alert_receiver) =
+        stream::bounded::<TradeAlert>(capacity, OverflowPolicy::DropOldest);
+    if watch.is_some() {
+        stats.register_channel("alerts", alert_sender.counters());
+    }
+    stats.set_cache_size("priceCache", pool.price_cache_len());
+    stats.set_ready(true);
+
+    let config = pool.config();
+    let stats = &*stats;
+    let outcome = thread::scope(|scope| {
+        let reader = scope.spawn(|| read_transactions(input, &config, stats, tx_sender));
+        if let Some(watch) = watch {
+            scope.spawn(move || {
+                for alert in alert_receiver {
+                    if let Err(err) = watch.deliver(&alert) {
+                        tracing::warn!("failed to deliver alert for watch {}: {err}", alert.watch);
+                    }
+                }
+            });
+        }
+        for _ in 0..pool.size() {
+            let transactions = tx_receiver.clone();
+            let results = result_sender.clone();
+            let alerts = alert_sender.clone();
+            scope.spawn(move || {
+                for tx in transactions {
+                    let result = pool.parse(tx);
+                    stats.record(&result);
+                    stats.set_cache_size("priceCache", pool.price_cache_len());
+                    for alert in watch.map(|watch| watch.alerts(&result)).unwrap_or_default() {
+                        let _ = alerts.send(alert);
+                    }
+                    if results.send(result).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop((tx_receiver, result_sender, alert_sender));
+
+        // Returning drops the receiver, which unblocks the workers if writing failed.
+        let written = write_results(result_receiver, output, format);
+        let read = reader.join().expect("reader thread panicked");
+        written.and(read)
+    });
+    stats.set_ready(false);
+    admin.shutdown();
+    outcome
+}
+
+/// Feed NDJSON transactions into the pipeline until the input ends or the workers are gone.
+fn read_transactions(
+    input: Option<&PathBuf>,
+    config: &ParseConfig,
+    stats: &ServiceStats,
+    sender: stream::Sender<SolanaTransaction>,
+) -> Result<()> {
+    let reader: Box<dyn BufRead> = match input {
+        Some(path) => dex_io::open_reader(path)?,
+        None => Box::new(io::stdin().lock()),
+    };
+    for line in reader.lines() {
+        let line = line.context("failed to read input")?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let tx = serde_json::from_str::<Value>(&line)
+            .map_err(|err| anyhow!("{err}"))
+            .and_then(|value| {
+                SolanaTransaction::from_value(&value, config).map_err(|err| anyhow!("{err}"))
+            });
+        match tx {
+            Ok(tx) => {
+                if sender.send(tx).is_err() {
+                    break;
+                }
+            }
+            Err(err) => {
+                tracing::warn!("skipping malformed input line: {err}");
+                stats.record_failure();
+            }
+        }
+    }
+    Ok(())
+}
+
+fn write_results(
+    results: stream::Receiver<ParseResult>,
+    output: Option<&PathBuf>,
+    format: JsonFormat,
+) -> Result<()> {
+    let mut sink = output
+        .map(|path| Ok::<_, anyhow::Error>(NdjsonWriter::create(path)?.with_json_format(format)))
+        .transpose()?;
+    let mut stdout = io::stdout().lock();
+    for result in results {
+        match sink.as_mut() {
+            Some(sink) => sink.write_record(&result)?,
+            None => {
+                serde_json::to_writer(&mut stdout, &to_json_value(&result, format)?)?;
+                stdout.write_all(b"\n")?;
+            }
+        }
+    }
+    if let Some(sink) = sink {
+        sink.finish()?;
+    }
+    stdout.flush()?;
+    Ok(())
+}
+
+fn parse_dump(
+    parser: &DexParser,
+    config: &ParseConfig,
+    path: &Path,
+    write_parsed: bool,
+    format: JsonFormat,
+) -> Result<Vec<ParseResult>> {
+    let value: Value = dex_io::read_json(path)?;
+    let results = match value {
+        Value::Array(txs) => {
+            parser
+                .parse_block_raw(&txs, Some(config.clone()))?
+                .transactions
+        }
+        Value::Object(ref object) if object.contains_key("transactions") => {
+            let block: SolanaBlock = serde_json::from_value(value)?;
+            parser
+                .parse_block_parsed(&block, Some(config.clone()))
+                .transactions
+        }
+        Value::Object(ref object)
+            if object.contains_key("jsonrpc") || object.contains_key("transaction") =>
+        {
+            let tx = rpc::transaction_from_rpc_json(value)?;
+            vec![parser.parse_all(tx, Some(config.clone()))]
+        }
+        _ => {
+            let tx =
+                SolanaTransaction::from_value(&value, config).map_err(|err| anyhow!("{err}"))?;
+            vec![parser.parse_all(tx, Some(config.clone()))]
+        }
+    };
+
+    if write_parsed {
+        let target = parsed_path(path);
+        let output = to_json_value(&results, format)?;
+        fs::write(&target, serde_json::to_string_pretty(&output)?)
+            .with_context(|| format!("failed to write {:?}", target))?;
+    }
+    Ok(results)
+}
+
+fn collect_dump_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in fs::read_dir(dir).with_context(|| format!("failed to read {:?}", dir))? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_dump_files(&path, files)?;
+        } else if dump_stem(&path).is_some_and(|stem| !stem.ends_with(".parsed")) {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// File name without the `.json[.gz|.zst]` suffix, if it is a JSON dump at all.
+fn dump_stem(path: &Path) -> Option<&str> {
+    let name = path.file_name()?.to_str()?;
+    let name = [".gz", ".zst"]
+        .iter()
+        .find_map(|ext| name.strip_suffix(ext))
+        .unwrap_or(name);
+    name.strip_suffix(".json")
+}
+
+fn parsed_path(path: &Path) -> PathBuf {
+    let stem = dump_stem(path).unwrap_or_default();
+    path.with_file_name(format!("{stem}.parsed.json"))
+}
+
+fn describe_command(command: &Command) -> Value {
+    let args: Vec<Value> = command
+        .get_arguments()
+        .filter(|arg| !arg.is_hide_set())
+        .map(describe_arg)
+        .collect();
+    let subcommands: Vec<Value> = command
+        .get_subcommands()
+        .filter(|sub| !sub.is_hide_set())
+        .map(describe_command)
+        .collect();
+    json!({
+        "name": command.get_name(),
+        "about": command.get_about().map(|about| about.to_string()),
+        "args": args,
+        "subcommands": subcommands,
+    })
+}
+
+fn describe_arg(arg: &Arg) -> Value {
+    let takes_value = arg.get_num_args().is_some_and(|range| range.takes_values());
+    let possible_values: Vec<String> = arg
+        .get_possible_values()
+        .iter()
+        .map(|value| value.get_name().to_string())
+        .collect();
+    let default_values: Vec<String> = arg
+        .get_default_values()
+        .iter()
+        .map(|value| value.to_string_lossy().into_owned())
+        .collect();
+    json!({
+        "id": arg.get_id().as_str(),
+        "long": arg.get_long(),
+        "short": arg.get_short(),
+        "help": arg.get_help().map(|help| help.to_string()),
+        "required": arg.is_required_set(),
+        "positional": arg.is_positional(),
+        "takesValue": takes_value,
+        "possibleValues": possible_values,
+        "defaultValues": default_values,
+        "env": arg.get_env().map(|env| env.to_string_lossy().into_owned()),
+    })
+}
+
 fn parse_with_mode(
     parser: &DexParser,
     tx: SolanaTransaction,
     mode: TxMode,
     config: &ParseConfig,
+    format: JsonFormat,
 ) -> Result<Value> {
+    let config = Some(config.clone());
     Ok(match mode {
-        TxMode::All => serde_json::to_value(parser.parse_all(tx, Some(config.clone())))?,
-        TxMode::Trades => serde_json::to_value(parser.parse_trades(tx, Some(config.clone())))?,
-        TxMode::Liquidity => {
-            serde_json::to_value(parser.parse_liquidity(tx, Some(config.clone())))?
-        }
-        TxMode::Transfers => {
-            serde_json::to_value(parser.parse_transfers(tx, Some(config.clone())))?
-        }
+        TxMode::All => to_json_value(&parser.parse_all(tx, config), format)?,
+        TxMode::Trades => to_json_value(&parser.parse_trades(tx, config), format)?,
+        TxMode::Liquidity => to_json_value(&parser.parse_liquidity(tx, config), format)?,
+        TxMode::Transfers => to_json_value(&parser.parse_transfers(tx, config), format)?,
     })
 }
@@ -1,7 +1,13 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
+use crate::core::constants::{devnet_programs, dex_program_names};
+use crate::core::program_tables::SharedProgramTables;
+
 /// Configuration for the parser mirroring the TypeScript structure.
-#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ParseConfig {
     #[serde(
@@ -17,6 +23,155 @@ pub struct ParseConfig {
     pub throw_error: bool,
     #[serde(default = "ParseConfig::default_aggregate_trades")]
     pub aggregate_trades: bool,
+    /// Embed the owner-level SOL and token balance-change maps in `ParseResult`.
+    #[serde(default)]
+    pub include_balance_maps: bool,
+    /// Embed the owner and mint of every token account the adapter resolved in `ParseResult`.
+    #[serde(default)]
+    pub include_token_account_owners: bool,
+    /// Embed the net SOL and token movements of every wallet the transaction touched in
+    /// `ParseResult::wallet_flows`.
+    #[serde(default)]
+    pub include_wallet_flows: bool,
+    /// Label the transaction as likely bot or human order flow in `ParseResult::flow_label`.
+    #[serde(default)]
+    pub classify_flow: bool,
+    /// Flag trades in `TradeInfo::suspected_wash` that an opposite trade of the same pool
+    /// undoes for the same wallet, or for a wallet funded by the same parent; block parsing
+    /// checks across the whole block.
+    #[serde(default)]
+    pub detect_wash_trades: bool,
+    /// Report inner CPIs the logs show as failed and rolled back in `ParseResult::failed_cpis`.
+    #[serde(default)]
+    pub include_failed_cpis: bool,
+    /// Keep the single transaction `fee` in `ParseResult` next to the `fees` breakdown, for
+    /// consumers of the TypeScript-compatible output.
+    #[serde(default = "ParseConfig::default_include_legacy_fee")]
+    pub include_legacy_fee: bool,
+    /// Record how long the parse took, per stage, in `ParseResult::timings`.
+    #[serde(default)]
+    pub record_timings: bool,
+    /// Rebuild Pump.fun, PumpSwap and Jupiter events from `Program data:` log lines for
+    /// outer instructions whose inner instructions the provider stripped.
+    #[serde(default)]
+    pub use_logs_fallback: bool,
+    /// Decode the outer Pump.fun, PumpSwap and Jupiter instructions of failed transactions
+    /// into attempted trades with `TradeStatus::Failed`.
+    #[serde(default)]
+    pub parse_failed: bool,
+    /// Parse only about this fraction (0.0 to 1.0) of each block's transactions, picked by a
+    /// hash of their signature so reruns sample the same ones. Values outside the range are
+    /// clamped when parsing, so a negative rate samples nothing, and NaN samples nothing;
+    /// deserialization rejects them.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_sample_rate",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub sample_rate: Option<f64>,
+    /// Per-program adjustments keyed by program id.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub protocol_overrides: HashMap<String, ProtocolConfig>,
+    /// Forked program ids mapped to the canonical program whose decoders parse them.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub program_aliases: HashMap<String, String>,
+    /// Launchpad platform configs mapped to the name reported in `MemeEvent::protocol`,
+    /// consulted before the built-in table.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub launchpad_platforms: HashMap<String, String>,
+    /// Trading bots whose fees are reported in `TradeInfo::fees`, consulted before the
+    /// built-in table.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub trading_bots: Vec<TradingBot>,
+    /// Cluster whose program deployments the transactions come from.
+    #[serde(default)]
+    pub network: NetworkProfile,
+    /// Runtime program tables naming venues without a bundled decoder; clones of the
+    /// config share them, so storing new tables reloads every parser using it.
+    #[serde(skip)]
+    pub program_tables: SharedProgramTables,
+}
+
+/// Program-id table of the cluster being parsed.
+///
+/// Deployments listed by a profile are parsed as their mainnet program and keep their own
+/// id in the output, exactly like `ParseConfig::program_aliases`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum NetworkProfile {
+    #[default]
+    Mainnet,
+    Devnet,
+    /// A local validator with programs loaded at their mainnet addresses.
+    Localnet,
+    /// Deployed program ids mapped to the mainnet program they clone.
+    Custom(HashMap<String, String>),
+}
+
+impl NetworkProfile {
+    /// Deployed program ids of this cluster mapped to their mainnet program ids.
+    pub fn program_ids(&self) -> HashMap<String, String> {
+        match self {
+            Self::Mainnet | Self::Localnet => HashMap::new(),
+            Self::Devnet => devnet_programs::ALL
+                .iter()
+                .map(|(devnet, mainnet)| (devnet.to_string(), mainnet.to_string()))
+                .collect(),
+            Self::Custom(program_ids) => program_ids.clone(),
+        }
+    }
+}
+
+/// Trading bot listed in `ParseConfig::trading_bots`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct TradingBot {
+    /// Name reported as the `dex` of its fees.
+    pub name: String,
+    /// Router programs the bot wraps swaps in; they carry no trades of their own.
+    #[serde(default)]
+    pub program_ids: Vec<String>,
+    /// Wallets the bot's fee is paid to.
+    #[serde(default)]
+    pub fee_wallets: Vec<String>,
+}
+
+/// Overrides applied to a single program through `ParseConfig::protocol_overrides`.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ProtocolConfig {
+    /// Emit trades for the program.
+    #[serde(default = "ProtocolConfig::default_enabled")]
+    pub trades: bool,
+    /// Emit liquidity events for the program.
+    #[serde(default = "ProtocolConfig::default_enabled")]
+    pub liquidity: bool,
+    /// Emit meme events for the program.
+    #[serde(default = "ProtocolConfig::default_enabled")]
+    pub meme_events: bool,
+    /// Recipient reported on protocol fees, for forks that collect fees elsewhere.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_recipient: Option<String>,
+}
+
+impl Default for ProtocolConfig {
+    fn default() -> Self {
+        Self {
+            trades: Self::default_enabled(),
+            liquidity: Self::default_enabled(),
+            meme_events: Self::default_enabled(),
+            fee_recipient: None,
+        }
+    }
+}
+
+impl ProtocolConfig {
+    const fn default_enabled() -> bool {
+        true
+    }
 }
 
 impl Default for ParseConfig {
@@ -27,6 +182,23 @@ impl Default for ParseConfig {
             ignore_program_ids: None,
             throw_error: Self::default_throw_error(),
             aggregate_trades: Self::default_aggregate_trades(),
+            include_balance_maps: false,
+            include_token_account_owners: false,
+            include_wallet_flows: false,
+            classify_flow: false,
+            detect_wash_trades: false,
+            include_failed_cpis: false,
+            include_legacy_fee: Self::default_include_legacy_fee(),
+            record_timings: false,
+            use_logs_fallback: false,
+            parse_failed: false,
+            sample_rate: None,
+            protocol_overrides: HashMap::new(),
+            program_aliases: HashMap::new(),
+            launchpad_platforms: HashMap::new(),
+            trading_bots: Vec::new(),
+            network: NetworkProfile::default(),
+            program_tables: SharedProgramTables::default(),
         }
     }
 }
@@ -43,4 +215,46 @@ impl ParseConfig {
     const fn default_aggregate_trades() -> bool {
         true
     }
+
+    const fn default_include_legacy_fee() -> bool {
+        true
+    }
+
+    /// [`ParseConfig::sample_rate`] set to `rate`, clamped to 0.0..=1.0.
+    pub fn with_sample_rate(mut self, rate: f64) -> Self {
+        self.sample_rate = Some(if rate.is_nan() {
+            0.0
+        } else {
+            rate.clamp(0.0, 1.0)
+        });
+        self
+    }
+
+    /// Name of a bundled program, else the one the program tables list for it.
+    pub fn program_name(&self, program_id: &str) -> String {
+        match dex_program_names::name(program_id) {
+            "Unknown DEX" => self
+                .program_tables
+                .program_name(program_id)
+                .unwrap_or_else(|| "Unknown DEX".to_string()),
+            name => name.to_string(),
+        }
+    }
+
+    /// Overrides configured for `program_id`, if any.
+    pub fn protocol(&self, program_id: &str) -> Option<&ProtocolConfig> {
+        self.protocol_overrides.get(program_id)
+    }
+}
+
+fn deserialize_sample_rate<'de, D>(deserializer: D) -> Result<Option<f64>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    match Option::<f64>::deserialize(deserializer)? {
+        Some(rate) if !(0.0..=1.0).contains(&rate) => Err(serde::de::Error::custom(format!(
+            "sampleRate must be between 0.0 and 1.0, got {rate}"
+        ))),
+        rate => Ok(rate),
+    }
 }
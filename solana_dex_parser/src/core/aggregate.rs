@@ -0,0 +1,103 @@
+//! Net trade of a transaction, reported in `ParseResult::aggregate_trade`.
+
+use std::collections::HashSet;
+
+use crate::core::amounts::convert_to_ui_amount;
+use crate::core::flow::is_router_trade;
+use crate::core::util::get_trade_type;
+use crate::types::{TokenInfo, TradeInfo};
+
+/// Collapse the trades of a transaction into the single trade the signer executed.
+///
+/// A router trade (Jupiter, OKX) already describes the whole route and is returned as is.
+/// Otherwise hops are chained in `idx` order: a hop continues the route when it sells the
+/// route's input mint (a split leg) or a mint an earlier hop bought. The last such route
+/// becomes one trade from its input mint to the mint its final hop bought, with the legs on
+/// either end summed, `amms` listing every hop and `route` naming the router, or the hop
+/// venues when no router was involved. Account fields are those of the first leg on each
+/// side. `trades` must be sorted by `idx`.
+pub fn aggregate_route(trades: &[TradeInfo]) -> Option<TradeInfo> {
+    if let Some(routed) = trades.iter().rev().find(|trade| is_router_trade(trade)) {
+        return Some(routed.clone());
+    }
+    let hops = last_route(trades);
+    match hops {
+        [] => None,
+        [hop] => Some(hop.clone()),
+        _ => Some(net_trade(hops)),
+    }
+}
+
+/// Trailing run of hops linked by the mints they pass on.
+fn last_route(trades: &[TradeInfo]) -> &[TradeInfo] {
+    let mut start = 0;
+    let mut reachable: HashSet<&str> = HashSet::new();
+    for (index, hop) in trades.iter().enumerate() {
+        if index > start && !reachable.contains(hop.input_token.mint.as_str()) {
+            start = index;
+            reachable.clear();
+        }
+        if index == start {
+            reachable.insert(&hop.input_token.mint);
+        }
+        reachable.insert(&hop.output_token.mint);
+    }
+    &trades[start..]
+}
+
+fn net_trade(hops: &[TradeInfo]) -> TradeInfo {
+    let first = &hops[0];
+    let last = &hops[hops.len() - 1];
+    let input_mint = first.input_token.mint.as_str();
+    let output_mint = last.output_token.mint.as_str();
+    let entry_legs: Vec<&TradeInfo> = hops
+        .iter()
+        .filter(|hop| hop.input_token.mint == input_mint)
+        .collect();
+
+    let mut pool: Vec<String> = Vec::new();
+    for address in hops.iter().flat_map(|hop| &hop.pool) {
+        if !pool.contains(address) {
+            pool.push(address.clone());
+        }
+    }
+    let amms: Vec<String> = hops.iter().filter_map(|hop| hop.amm.clone()).collect();
+    let route = hops
+        .iter()
+        .find_map(|hop| hop.route.clone().filter(|route| !route.is_empty()))
+        .or_else(|| (!amms.is_empty()).then(|| amms.join(" -> ")));
+
+    TradeInfo {
+        trade_type: get_trade_type(input_mint, output_mint),
+        pool,
+        input_token: summed(entry_legs.iter().map(|hop| &hop.input_token)),
+        output_token: summed(
+            hops.iter()
+                .map(|hop| &hop.output_token)
+                .filter(|token| token.mint == output_mint),
+        ),
+        fees: hops.iter().flat_map(|hop| hop.fees.clone()).collect(),
+        user: first.user.clone(),
+        program_id: first.program_id.clone(),
+        amm: first.amm.clone(),
+        amms: Some(amms),
+        route,
+        slot: first.slot,
+        timestamp: first.timestamp,
+        signature: first.signature.clone(),
+        idx: first.idx.clone(),
+        signer: first.signer.clone(),
+        volume_sol: entry_legs.iter().map(|hop| hop.volume_sol).sum(),
+        ..TradeInfo::default()
+    }
+}
+
+/// First token of `legs` carrying the raw amounts of all of them.
+fn summed<'a>(mut legs: impl Iterator<Item = &'a TokenInfo>) -> TokenInfo {
+    let mut token = legs.next().cloned().unwrap_or_default();
+    let raw = |token: &TokenInfo| token.amount_raw.parse::<u128>().unwrap_or(0);
+    let total = legs.fold(raw(&token), |total, leg| total + raw(leg));
+    token.amount_raw = total.to_string();
+    token.amount = convert_to_ui_amount(total, token.decimals);
+    token
+}
@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+
+use crate::config::ParseConfig;
+use crate::types::{ParseResult, SolanaTransaction};
+
+/// Rewrite forked program ids to the canonical ids listed in `ParseConfig::program_aliases`
+/// and in the program-id table of `ParseConfig::network`.
+///
+/// Instructions, inner instructions, transfers and the program filters of `config` are
+/// rewritten so the existing decoders pick the fork up. Returns the `canonical -> alias`
+/// pairs to hand back to [`restore_program_ids`]; a canonical program that is also invoked
+/// directly in the transaction keeps its own id in the output.
+pub fn canonicalize_program_ids(
+    tx: &mut SolanaTransaction,
+    config: &mut ParseConfig,
+) -> HashMap<String, String> {
+    let mut aliases = config.network.program_ids();
+    aliases.extend(
+        config
+            .program_aliases
+            .iter()
+            .map(|(alias, canonical)| (alias.clone(), canonical.clone())),
+    );
+    if aliases.is_empty() {
+        return HashMap::new();
+    }
+
+    let mut invoked: Vec<&mut String> = tx
+        .instructions
+        .iter_mut()
+        .chain(
+            tx.inner_instructions
+                .iter_mut()
+                .flat_map(|inner| inner.instructions.iter_mut()),
+        )
+        .map(|instruction| &mut instruction.program_id)
+        .collect();
+    let native: Vec<String> = invoked
+        .iter()
+        .filter(|program_id| !aliases.contains_key(program_id.as_str()))
+        .map(|program_id| program_id.to_string())
+        .collect();
+
+    let mut restored = HashMap::new();
+    for program_id in invoked.iter_mut() {
+        if let Some(canonical) = aliases.get(program_id.as_str()) {
+            if !native.contains(canonical) {
+                restored.insert(canonical.clone(), program_id.to_string());
+            }
+            **program_id = canonical.clone();
+        }
+    }
+    for transfer in &mut tx.transfers {
+        if let Some(canonical) = aliases.get(&transfer.program_id) {
+            transfer.program_id = canonical.clone();
+        }
+    }
+
+    for filter in [&mut config.program_ids, &mut config.ignore_program_ids]
+        .into_iter()
+        .flatten()
+    {
+        for program_id in filter.iter_mut() {
+            if let Some(canonical) = aliases.get(program_id) {
+                *program_id = canonical.clone();
+            }
+        }
+    }
+    restored
+}
+
+/// Put the forked program ids returned by [`canonicalize_program_ids`] back into `result`.
+pub fn restore_program_ids(result: &mut ParseResult, restored: &HashMap<String, String>) {
+    if restored.is_empty() {
+        return;
+    }
+    let restore = |program_id: &mut String| {
+        if let Some(alias) = restored.get(program_id.as_str()) {
+            *program_id = alias.clone();
+        }
+    };
+
+    result
+        .trades
+        .iter_mut()
+        .chain(result.aggregate_trade.as_mut())
+        .filter_map(|trade| trade.program_id.as_mut())
+        .for_each(restore);
+    result
+        .liquidities
+        .iter_mut()
+        .filter_map(|pool| pool.program_id.as_mut())
+        .for_each(restore);
+    result
+        .transfers
+        .iter_mut()
+        .map(|transfer| &mut transfer.program_id)
+        .for_each(restore);
+    result
+        .programs_invoked
+        .iter_mut()
+        .map(|invocation| &mut invocation.program_id)
+        .for_each(restore);
+    result
+        .migrations
+        .iter_mut()
+        .filter_map(|migration| migration.destination_program.as_mut())
+        .for_each(restore);
+    result
+        .bridges
+        .iter_mut()
+        .map(|bridge| &mut bridge.program_id)
+        .for_each(restore);
+}
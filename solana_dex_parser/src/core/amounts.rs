@@ -0,0 +1,57 @@
+//! Raw token amounts and their UI values.
+
+use crate::types::{FeeInfo, TokenInfo};
+
+/// Raw amount of a token as carried in `amountRaw`, `None` when it is not a number.
+pub(crate) fn raw_amount(amount: &str) -> Option<u128> {
+    amount.parse().ok()
+}
+
+/// `amount` raw units of a token with `decimals` decimals, as a UI amount.
+pub fn convert_to_ui_amount(amount: impl Into<u128>, decimals: u8) -> f64 {
+    let value: u128 = amount.into();
+    if decimals == 0 {
+        return value as f64;
+    }
+    let scale = 10u128.pow(decimals as u32);
+    (value as f64) / (scale as f64)
+}
+
+/// `amount` raw units of `mint` as a token leg.
+pub fn build_token_info(
+    mint: &str,
+    amount: u128,
+    decimals: u8,
+    _owner: Option<String>,
+) -> TokenInfo {
+    TokenInfo {
+        mint: mint.to_string(),
+        amount: convert_to_ui_amount(amount, decimals),
+        amount_raw: amount.to_string(),
+        decimals,
+        authority: None,
+        destination: None,
+        destination_owner: None,
+        destination_balance: None,
+        destination_pre_balance: None,
+        source: None,
+        source_balance: None,
+        source_pre_balance: None,
+        destination_balance_change: None,
+        source_balance_change: None,
+        balance_change: None,
+    }
+}
+
+/// `amount` raw units of `mint` as a fee, charged by `dex` when known.
+pub fn build_fee_info(mint: &str, amount: u128, decimals: u8, dex: Option<String>) -> FeeInfo {
+    FeeInfo {
+        mint: mint.to_string(),
+        amount: convert_to_ui_amount(amount, decimals),
+        amount_raw: amount.to_string(),
+        decimals,
+        dex,
+        fee_type: None,
+        recipient: None,
+    }
+}
@@ -0,0 +1,94 @@
+//! Sanity checks that flag implausible trades as `suspect` instead of passing them on silently.
+
+use std::collections::HashMap;
+
+use crate::types::{ParseResult, PoolState, TokenInfo, TradeInfo};
+
+/// Same-pool trades a block needs before its median price is trusted.
+const MIN_PRICED_TRADES: usize = 3;
+
+/// Result position, trade position and pair price of the priced trades of a pool.
+type PricedTrades = Vec<(usize, usize, f64)>;
+
+/// Raw reserve of `mint` in `state`.
+fn reserve(state: &PoolState, mint: &str) -> Option<u128> {
+    let reserve = if state.mint_a == mint {
+        &state.reserve_a
+    } else if state.mint_b == mint {
+        &state.reserve_b
+    } else {
+        return None;
+    };
+    reserve.parse().ok()
+}
+
+/// Whether the trade moved more than its pool holds.
+///
+/// The attached state is taken after the trade, so the pool must still hold the input, and an
+/// output larger than what is left means the trade drained over half of the pool.
+fn exceeds_reserves(trade: &TradeInfo) -> bool {
+    let Some(state) = trade.pool_state.as_ref() else {
+        return false;
+    };
+    let exceeds = |token: &TokenInfo| {
+        let amount = token.amount_raw.parse::<u128>().ok();
+        matches!((amount, reserve(state, &token.mint)), (Some(amount), Some(reserve)) if amount > reserve)
+    };
+    exceeds(&trade.input_token) || exceeds(&trade.output_token)
+}
+
+/// Price of the trade's pair, oriented by mint so both directions of a pool compare.
+fn pair_price(trade: &TradeInfo) -> Option<f64> {
+    let (input, output) = (&trade.input_token, &trade.output_token);
+    if input.amount <= 0.0 || output.amount <= 0.0 {
+        return None;
+    }
+    Some(if input.mint < output.mint {
+        output.amount / input.amount
+    } else {
+        input.amount / output.amount
+    })
+}
+
+/// Flag trades of a block as `suspect` when they move more than the pool's reserves or
+/// when their price is more than `max_price_deviation` (0.1 for 10%) away from the median
+/// price of the other trades on the same pool in the same slot.
+///
+/// The price check needs at least three priced trades on the pool, otherwise there is no
+/// telling which of two disagreeing trades is wrong.
+pub fn flag_suspect_trades(results: &mut [ParseResult], max_price_deviation: f64) {
+    let mut prices: HashMap<(String, u64), PricedTrades> = HashMap::new();
+    for (position, result) in results.iter_mut().enumerate() {
+        for (index, trade) in result.trades.iter_mut().enumerate() {
+            if exceeds_reserves(trade) {
+                trade.suspect = true;
+            }
+            let (Some(pool), Some(price)) = (trade.pool.first(), pair_price(trade)) else {
+                continue;
+            };
+            prices
+                .entry((pool.clone(), trade.slot))
+                .or_default()
+                .push((position, index, price));
+        }
+    }
+
+    for trades in prices.into_values() {
+        if trades.len() < MIN_PRICED_TRADES {
+            continue;
+        }
+        let mut sorted: Vec<f64> = trades.iter().map(|(_, _, price)| *price).collect();
+        sorted.sort_by(f64::total_cmp);
+        let middle = sorted.len() / 2;
+        let median = if sorted.len().is_multiple_of(2) {
+            (sorted[middle - 1] + sorted[middle]) / 2.0
+        } else {
+            sorted[middle]
+        };
+        for (position, index, price) in trades {
+            if (price / median - 1.0).abs() > max_price_deviation {
+                results[position].trades[index].suspect = true;
+            }
+        }
+    }
+}
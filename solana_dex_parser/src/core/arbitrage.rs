@@ -0,0 +1,72 @@
+//! Atomic arbitrage: swap cycles that end a transaction with more of the starting mint.
+
+use std::collections::HashSet;
+
+use crate::core::amounts::{build_token_info, raw_amount};
+use crate::core::flow::is_router_trade;
+use crate::types::{ArbitrageInfo, ArbitrageLeg, TradeInfo};
+
+fn leg(trade: &TradeInfo) -> ArbitrageLeg {
+    ArbitrageLeg {
+        amm: trade.amm.clone(),
+        program_id: trade.program_id.clone(),
+        pool: trade.pool.first().cloned(),
+        input_mint: trade.input_token.mint.clone(),
+        output_mint: trade.output_token.mint.clone(),
+        idx: trade.idx.clone(),
+    }
+}
+
+/// First profitable swap cycle among `trades` (sorted by `idx`), run by `wallet`.
+///
+/// A cycle starts at a swap and follows, in execution order, the next swap selling what
+/// the previous one bought until a swap buys back the starting mint. It needs at least
+/// two distinct pools and must return more than the first swap spent. Route-level trades
+/// of aggregators are skipped in favour of their hops.
+pub fn detect_arbitrage(wallet: String, trades: &[TradeInfo]) -> Option<ArbitrageInfo> {
+    let hops: Vec<&TradeInfo> = trades
+        .iter()
+        .filter(|trade| !is_router_trade(trade))
+        .collect();
+
+    for (start, first) in hops.iter().enumerate() {
+        let mut cycle = vec![*first];
+        for trade in &hops[start + 1..] {
+            let last = cycle[cycle.len() - 1];
+            if trade.input_token.mint != last.output_token.mint {
+                continue;
+            }
+            cycle.push(trade);
+            if trade.output_token.mint == first.input_token.mint {
+                break;
+            }
+        }
+        let last = cycle[cycle.len() - 1];
+        if cycle.len() < 2 || last.output_token.mint != first.input_token.mint {
+            continue;
+        }
+        let pools: HashSet<_> = cycle
+            .iter()
+            .filter_map(|trade| trade.pool.first())
+            .collect();
+        if pools.len() < 2 {
+            continue;
+        }
+        let (Some(spent), Some(returned)) = (
+            raw_amount(&first.input_token.amount_raw),
+            raw_amount(&last.output_token.amount_raw),
+        ) else {
+            continue;
+        };
+        if returned <= spent {
+            continue;
+        }
+        let input = &first.input_token;
+        return Some(ArbitrageInfo {
+            wallet,
+            profit: build_token_info(&input.mint, returned - spent, input.decimals, None),
+            legs: cycle.into_iter().map(leg).collect(),
+        });
+    }
+    None
+}
@@ -0,0 +1,230 @@
+//! Trades failed transactions attempted.
+//!
+//! A failed transaction rolls back its inner instructions and balance changes, so the regular
+//! parsers find nothing to read. Its outer instructions still carry what the trader asked for:
+//! with `ParseConfig::parse_failed` the Pump.fun, PumpSwap and Jupiter ones are decoded into
+//! trades with `TradeStatus::Failed`, for slippage-failure analytics and sniper monitoring.
+
+use crate::core::amounts::build_token_info;
+use crate::core::amounts::convert_to_ui_amount;
+use crate::core::constants::{dex_programs, tokens};
+use crate::core::transaction_adapter::TransactionAdapter;
+use crate::core::util::{decode_instruction_data, get_trade_type};
+use crate::protocols::jupiter::constants::discriminators::jupiter_instructions;
+use crate::protocols::jupiter::jupiter_parser::{
+    JupiterParser, ROUTE_USER_ACCOUNT, SHARED_ACCOUNTS_ROUTE_USER_ACCOUNT,
+};
+use crate::protocols::pumpfun::binary_reader::BinaryReader;
+use crate::protocols::pumpfun::constants::discriminators::{
+    pumpfun_instructions, pumpswap_instructions,
+};
+use crate::types::{
+    SolanaInstruction, TokenInfo, TradeInfo, TradeLimit, TradeLimitKind, TradeStatus,
+    TransactionStatus,
+};
+
+/// Decimals of the tokens Pump.fun launches.
+const PUMP_TOKEN_DECIMALS: u8 = 6;
+
+/// Mint, decimals (when known) and raw amount of one side of an attempted trade.
+type Leg = (String, Option<u8>, u64);
+
+/// What an attempted trade's instruction asked for.
+struct Attempt<'a> {
+    pool: Option<&'a String>,
+    user: Option<&'a String>,
+    input: Leg,
+    output: Leg,
+    limit: Option<TradeLimitKind>,
+    slippage_bps: Option<u64>,
+}
+
+/// Attempted trades of the outer instructions of a failed transaction, in instruction order;
+/// nothing for transactions that did not fail.
+///
+/// Pump.fun and PumpSwap trades carry the amount the instruction fixed; the other side's
+/// amount is left at 0 and its bound goes to `TradeInfo::limit` (maximum in for buys,
+/// minimum out for sells). Jupiter routes carry the input amount, the quoted output and the
+/// slippage tolerance. UI amounts of mints whose decimals the transaction does not reveal are
+/// left at 0, with the raw amounts set.
+pub fn attempted_trades(adapter: &TransactionAdapter) -> Vec<TradeInfo> {
+    if adapter.tx_status() != TransactionStatus::Failed {
+        return Vec::new();
+    }
+    adapter
+        .instructions()
+        .iter()
+        .enumerate()
+        .filter_map(|(index, instruction)| attempted_trade(adapter, index, instruction))
+        .collect()
+}
+
+fn attempted_trade(
+    adapter: &TransactionAdapter,
+    index: usize,
+    instruction: &SolanaInstruction,
+) -> Option<TradeInfo> {
+    let data = decode_instruction_data(&instruction.data);
+    if data.len() < 8 {
+        return None;
+    }
+    let (discriminator, payload) = data.split_at(8);
+    let accounts = &instruction.accounts;
+    let attempt = match instruction.program_id.as_str() {
+        dex_programs::PUMP_FUN => {
+            let mint = accounts.get(2)?;
+            let mut reader = BinaryReader::new(payload.to_vec());
+            let token_amount = reader.read_u64().ok()?;
+            let sol_limit = reader.read_u64().ok()?;
+            let token = (
+                mint.clone(),
+                Some(decimals(adapter, mint).unwrap_or(PUMP_TOKEN_DECIMALS)),
+                token_amount,
+            );
+            let sol = (
+                tokens::SOL.to_string(),
+                Some(tokens::SOL_DECIMALS),
+                sol_limit,
+            );
+            let (input, output, limit) = if discriminator == pumpfun_instructions::BUY {
+                (sol, token, TradeLimitKind::MaxInput)
+            } else if discriminator == pumpfun_instructions::SELL {
+                (token, sol, TradeLimitKind::MinOutput)
+            } else {
+                return None;
+            };
+            Attempt {
+                pool: accounts.get(3),
+                user: accounts.get(6),
+                input,
+                output,
+                limit: Some(limit),
+                slippage_bps: None,
+            }
+        }
+        dex_programs::PUMP_SWAP => {
+            let (base_mint, quote_mint) = (accounts.get(3)?, accounts.get(4)?);
+            let mut reader = BinaryReader::new(payload.to_vec());
+            let base_amount = reader.read_u64().ok()?;
+            let quote_limit = reader.read_u64().ok()?;
+            let base = (
+                base_mint.clone(),
+                Some(decimals(adapter, base_mint).unwrap_or(PUMP_TOKEN_DECIMALS)),
+                base_amount,
+            );
+            let quote = (
+                quote_mint.clone(),
+                decimals(adapter, quote_mint),
+                quote_limit,
+            );
+            let (input, output, limit) = if discriminator == pumpswap_instructions::BUY {
+                (quote, base, TradeLimitKind::MaxInput)
+            } else if discriminator == pumpswap_instructions::SELL {
+                (base, quote, TradeLimitKind::MinOutput)
+            } else {
+                return None;
+            };
+            Attempt {
+                pool: accounts.first(),
+                user: accounts.get(1),
+                input,
+                output,
+                limit: Some(limit),
+                slippage_bps: None,
+            }
+        }
+        dex_programs::JUPITER => {
+            // `route` names only the output mint; the input one comes from the source account.
+            let (user_account, input_mint, output_mint) =
+                if discriminator == jupiter_instructions::ROUTE {
+                    let source = accounts.get(2)?;
+                    let mint = adapter.token_account_info(source)?.mint.clone();
+                    (ROUTE_USER_ACCOUNT, mint, accounts.get(5)?.clone())
+                } else if discriminator == jupiter_instructions::SHARED_ACCOUNTS_ROUTE {
+                    (
+                        SHARED_ACCOUNTS_ROUTE_USER_ACCOUNT,
+                        accounts.get(7)?.clone(),
+                        accounts.get(8)?.clone(),
+                    )
+                } else {
+                    return None;
+                };
+            let user = accounts.get(user_account).cloned().unwrap_or_default();
+            let route = JupiterParser::decode_route(user, payload).ok()?;
+            let input_decimals = decimals(adapter, &input_mint);
+            let output_decimals = decimals(adapter, &output_mint);
+            Attempt {
+                pool: None,
+                user: accounts.get(user_account),
+                input: (input_mint, input_decimals, route.in_amount),
+                output: (output_mint, output_decimals, route.quoted_out_amount),
+                limit: None,
+                slippage_bps: Some(route.slippage_bps as u64),
+            }
+        }
+        _ => return None,
+    };
+    Some(trade(adapter, index, &instruction.program_id, attempt))
+}
+
+fn trade(
+    adapter: &TransactionAdapter,
+    index: usize,
+    program_id: &str,
+    attempt: Attempt,
+) -> TradeInfo {
+    let (input_mint, input_decimals, mut input_amount) = attempt.input;
+    let (output_mint, output_decimals, mut output_amount) = attempt.output;
+    let limit = attempt.limit.map(|kind| {
+        let (amount, decimals) = match kind {
+            TradeLimitKind::MaxInput => (std::mem::take(&mut input_amount), input_decimals),
+            TradeLimitKind::MinOutput => (std::mem::take(&mut output_amount), output_decimals),
+        };
+        TradeLimit {
+            kind,
+            amount: decimals.map_or(0.0, |decimals| {
+                convert_to_ui_amount(amount as u128, decimals)
+            }),
+            amount_raw: amount.to_string(),
+        }
+    });
+    TradeInfo {
+        trade_type: get_trade_type(&input_mint, &output_mint),
+        pool: attempt.pool.cloned().into_iter().collect(),
+        input_token: token_info(&input_mint, input_amount, input_decimals),
+        output_token: token_info(&output_mint, output_amount, output_decimals),
+        slippage_bps: attempt.slippage_bps,
+        user: attempt.user.cloned(),
+        program_id: Some(program_id.to_string()),
+        amm: Some(adapter.config().program_name(program_id)),
+        slot: adapter.slot(),
+        timestamp: adapter.block_time(),
+        signature: adapter.shared_signature(),
+        idx: format!("{index}-0"),
+        signer: Some(adapter.signers().to_vec()),
+        status: TradeStatus::Failed,
+        limit,
+        ..TradeInfo::default()
+    }
+}
+
+/// Token side of an attempted trade; without decimals only the raw amount is set.
+fn token_info(mint: &str, amount: u64, decimals: Option<u8>) -> TokenInfo {
+    match decimals {
+        Some(decimals) => build_token_info(mint, amount as u128, decimals, None),
+        None => TokenInfo {
+            amount_raw: amount.to_string(),
+            ..build_token_info(mint, 0, 0, None)
+        },
+    }
+}
+
+/// Decimals of `mint`: SOL and the dollar stablecoins are known, other mints only when the
+/// transaction's token balances report them.
+fn decimals(adapter: &TransactionAdapter, mint: &str) -> Option<u8> {
+    match mint {
+        tokens::SOL => Some(tokens::SOL_DECIMALS),
+        tokens::USDC | tokens::USDT => Some(6),
+        _ => adapter.token_decimals(mint),
+    }
+}
@@ -0,0 +1,90 @@
+//! Volume and pool statistics over the transactions of a block.
+
+use std::collections::HashSet;
+
+use crate::config::ParseConfig;
+use crate::core::constants::dex_programs;
+use crate::core::flow::is_router_trade;
+use crate::core::wash::trader;
+use crate::types::{BlockStats, ParseResult, TradeInfo, TradeType, VolumeStats};
+
+impl VolumeStats {
+    fn add(&mut self, trade: &TradeInfo) {
+        self.trades += 1;
+        self.volume_sol += trade.volume_sol.unwrap_or(0.0);
+        self.volume_usd += trade.volume_usd.unwrap_or(0.0);
+    }
+}
+
+/// Sum the trades, traders, volume and new pools of a block's transactions.
+///
+/// Router trades (Jupiter, OKX) are left out: the hops they route through are reported as
+/// trades of their own, so counting the route as well would count its volume twice. Trades
+/// without `volumeSol` or `volumeUsd` are counted with no volume in that unit. Trades are
+/// keyed by their first pool and by venue, their AMM or else their program as `config` names
+/// it, as in the daily rollup.
+pub fn block_stats(results: &[ParseResult], config: &ParseConfig) -> BlockStats {
+    let mut stats = BlockStats {
+        transactions: results.len(),
+        ..BlockStats::default()
+    };
+    let mut wallets: HashSet<&str> = HashSet::new();
+    let mut ordered: Vec<&ParseResult> = results.iter().collect();
+    ordered.sort_by_key(|result| result.block_position());
+
+    for result in ordered {
+        for trade in result.trades.iter().filter(|trade| !is_router_trade(trade)) {
+            stats.trades += 1;
+            stats.volume_sol += trade.volume_sol.unwrap_or(0.0);
+            stats.volume_usd += trade.volume_usd.unwrap_or(0.0);
+            stats
+                .volume_by_amm
+                .entry(venue(trade, config))
+                .or_default()
+                .add(trade);
+            if let Some(pool) = trade.pool.first() {
+                stats
+                    .volume_by_pool
+                    .entry(pool.clone())
+                    .or_default()
+                    .add(trade);
+            }
+            if let Some(wallet) = trader(result, trade) {
+                wallets.insert(wallet);
+            }
+        }
+
+        let created = result
+            .liquidities
+            .iter()
+            .filter(|event| event.event_type == TradeType::Create)
+            .map(|event| &event.pool_id)
+            .chain(
+                result
+                    .migrations
+                    .iter()
+                    .filter_map(|event| event.pool.as_ref()),
+            );
+        for pool in created {
+            if !pool.is_empty() && !stats.new_pools.contains(pool) {
+                stats.new_pools.push(pool.clone());
+            }
+        }
+    }
+    stats.unique_wallets = wallets.len();
+    stats
+}
+
+/// Venue a trade is attributed to: its AMM, else the name of its program.
+pub(crate) fn venue(trade: &TradeInfo, config: &ParseConfig) -> String {
+    trade
+        .amm
+        .clone()
+        .or_else(|| {
+            trade
+                .program_id
+                .as_deref()
+                .map(|program| config.program_name(program))
+        })
+        .unwrap_or_else(|| config.program_name(dex_programs::UNKNOWN))
+}
@@ -0,0 +1,163 @@
+//! Fees skimmed by trading bots that wrap DEX swaps.
+
+use crate::config::{ParseConfig, TradingBot};
+use crate::core::amounts::build_fee_info;
+use crate::core::constants::{system_programs, tokens, trading_bots};
+use crate::core::transaction_adapter::TransactionAdapter;
+use crate::core::util::{compare_idx, decode_instruction_data};
+use crate::types::{FeeInfo, SolanaInstruction, TradeInfo};
+
+/// `SystemInstruction::Transfer` tag, followed by the lamports as a `u64`.
+const SYSTEM_TRANSFER_TAG: [u8; 4] = [2, 0, 0, 0];
+
+/// Bots of `config` followed by the built-in table.
+fn registry(config: &ParseConfig) -> Vec<TradingBot> {
+    let built_in = trading_bots::ALL
+        .iter()
+        .map(|(name, program_ids, fee_wallets)| TradingBot {
+            name: name.to_string(),
+            program_ids: program_ids.iter().map(|id| id.to_string()).collect(),
+            fee_wallets: fee_wallets
+                .iter()
+                .map(|wallet| wallet.to_string())
+                .collect(),
+        });
+    config
+        .trading_bots
+        .iter()
+        .cloned()
+        .chain(built_in)
+        .collect()
+}
+
+/// Whether `program_id` is the router of a known trading bot.
+pub fn is_bot_program(config: &ParseConfig, program_id: &str) -> bool {
+    trading_bots::ALL
+        .iter()
+        .any(|(_, program_ids, _)| program_ids.contains(&program_id))
+        || config
+            .trading_bots
+            .iter()
+            .any(|bot| bot.program_ids.iter().any(|id| id == program_id))
+}
+
+struct BotFee {
+    idx: String,
+    fee: FeeInfo,
+}
+
+fn outer_index(idx: &str) -> Option<usize> {
+    idx.split('-').next()?.parse().ok()
+}
+
+/// Lamports and recipient of a system-program transfer.
+pub(crate) fn system_transfer(instruction: &SolanaInstruction) -> Option<(u64, &str)> {
+    if instruction.program_id != system_programs::SYSTEM {
+        return None;
+    }
+    let data = decode_instruction_data(&instruction.data);
+    if data.len() < 12 || data[..4] != SYSTEM_TRANSFER_TAG {
+        return None;
+    }
+    let lamports = u64::from_le_bytes(data[4..12].try_into().ok()?);
+    Some((lamports, instruction.accounts.get(1)?.as_str()))
+}
+
+/// Native SOL and token transfers into a bot fee wallet, keyed by instruction position.
+fn bot_fees(adapter: &TransactionAdapter, bots: &[TradingBot]) -> Vec<BotFee> {
+    let bot_of = |wallet: &str| {
+        bots.iter().find(|bot| {
+            bot.fee_wallets
+                .iter()
+                .any(|fee_wallet| fee_wallet == wallet)
+        })
+    };
+    let fee = |bot: &TradingBot, wallet: &str, mint: &str, amount: u128, decimals: u8| FeeInfo {
+        fee_type: Some("bot".to_string()),
+        recipient: Some(wallet.to_string()),
+        ..build_fee_info(mint, amount, decimals, Some(bot.name.clone()))
+    };
+
+    let mut fees = Vec::new();
+    let outer = adapter
+        .instructions()
+        .iter()
+        .enumerate()
+        .map(|(index, instruction)| (index.to_string(), instruction));
+    let inner = adapter.inner_instructions().iter().flat_map(|set| {
+        set.instructions
+            .iter()
+            .enumerate()
+            .map(move |(index, instruction)| (format!("{}-{index}", set.index), instruction))
+    });
+    for (idx, instruction) in outer.chain(inner) {
+        let Some((lamports, recipient)) = system_transfer(instruction) else {
+            continue;
+        };
+        if let Some(bot) = bot_of(recipient) {
+            fees.push(BotFee {
+                idx,
+                fee: fee(
+                    bot,
+                    recipient,
+                    tokens::SOL,
+                    lamports as u128,
+                    tokens::SOL_DECIMALS,
+                ),
+            });
+        }
+    }
+
+    for transfer in adapter.transfers() {
+        if fees.iter().any(|fee| fee.idx == transfer.idx) {
+            continue;
+        }
+        let info = &transfer.info;
+        let owner = info
+            .destination_owner
+            .clone()
+            .or_else(|| adapter.get_token_account_owner(&info.destination));
+        let Some((bot, wallet)) = [Some(info.destination.clone()), owner]
+            .into_iter()
+            .flatten()
+            .find_map(|wallet| bot_of(&wallet).map(|bot| (bot, wallet)))
+        else {
+            continue;
+        };
+        fees.push(BotFee {
+            idx: transfer.idx.clone(),
+            fee: fee(
+                bot,
+                &wallet,
+                &info.mint,
+                info.token_amount.amount.parse().unwrap_or(0),
+                info.token_amount.decimals,
+            ),
+        });
+    }
+    fees
+}
+
+/// Add bot fees to `trades` (sorted by `idx`) with `type` `"bot"`.
+///
+/// A fee goes to the trade of its own outer instruction, else to the last trade executed
+/// before it (bots often pay themselves in a separate instruction), else to the first trade.
+pub fn attach_bot_fees(adapter: &TransactionAdapter, trades: &mut [TradeInfo]) {
+    if trades.is_empty() {
+        return;
+    }
+    let bots = registry(adapter.config());
+    for BotFee { idx, fee } in bot_fees(adapter, &bots) {
+        let outer = outer_index(&idx);
+        let target = trades
+            .iter()
+            .position(|trade| outer_index(&trade.idx) == outer)
+            .or_else(|| {
+                trades
+                    .iter()
+                    .rposition(|trade| compare_idx(&trade.idx, &idx).is_lt())
+            })
+            .unwrap_or(0);
+        trades[target].fees.push(fee);
+    }
+}
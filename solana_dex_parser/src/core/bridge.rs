@@ -0,0 +1,280 @@
+//! Cross-chain bridge transfers, reported as bridge events rather than plain transfers.
+
+use crate::core::amounts::build_token_info;
+use crate::core::constants::{bridge_instructions, bridge_programs};
+use crate::core::cpi::InvocationScope;
+use crate::core::instruction_classifier::InstructionClassifier;
+use crate::core::transaction_adapter::TransactionAdapter;
+use crate::core::util::{compare_idx, decode_instruction_data};
+use crate::protocols::pumpfun::binary_reader::{BinaryReader, BinaryReaderError};
+use crate::types::{
+    BridgeDirection, BridgeEvent, ClassifiedInstruction, TransferData, TransferMap,
+};
+
+/// Recipient account of the Wormhole `Complete*` instructions.
+const WORMHOLE_COMPLETE_TO_ACCOUNT: usize = 5;
+
+/// What an outbound instruction says it sends, before the token decimals are known.
+struct Outbound {
+    user: String,
+    mint: String,
+    amount: u64,
+    target_chain: u64,
+    recipient: Vec<u8>,
+}
+
+fn hex(bytes: &[u8]) -> String {
+    let digits: String = bytes.iter().map(|byte| format!("{byte:02x}")).collect();
+    format!("0x{digits}")
+}
+
+/// Chain ids wider than 64 bits do not exist in practice; keep the low bytes.
+fn chain_id(be_bytes: &[u8]) -> u64 {
+    be_bytes
+        .iter()
+        .rev()
+        .take(8)
+        .rev()
+        .fold(0, |id, byte| (id << 8) | u64::from(*byte))
+}
+
+fn account(classified: &ClassifiedInstruction, index: usize) -> String {
+    classified
+        .data
+        .accounts
+        .get(index)
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// `TransferNative`/`TransferWrapped` and their `WithPayload` variants: nonce, amount, the
+/// relayer fee (without payload only), target address and target chain.
+fn wormhole_outbound(
+    classified: &ClassifiedInstruction,
+    tag: u8,
+    payload: &[u8],
+) -> Result<Outbound, BinaryReaderError> {
+    use bridge_instructions::*;
+
+    let wrapped = matches!(
+        tag,
+        WORMHOLE_TRANSFER_WRAPPED | WORMHOLE_TRANSFER_WRAPPED_WITH_PAYLOAD
+    );
+    // Wrapped transfers burn from an owner-signed account; native ones lock into custody.
+    let (user, mint) = if wrapped {
+        (account(classified, 3), account(classified, 4))
+    } else {
+        (account(classified, 0), account(classified, 3))
+    };
+    let mut reader = BinaryReader::new(payload.to_vec());
+    reader.read_u32()?;
+    let amount = reader.read_u64()?;
+    if matches!(tag, WORMHOLE_TRANSFER_NATIVE | WORMHOLE_TRANSFER_WRAPPED) {
+        reader.read_u64()?;
+    }
+    let recipient = reader.read_fixed_array(32)?;
+    let target_chain = u64::from(reader.read_u16()?);
+    Ok(Outbound {
+        user,
+        mint,
+        amount,
+        target_chain,
+        recipient,
+    })
+}
+
+/// DLN `create_order`: the give amount, then the take offer's chain id, token and amount,
+/// then the receiver on the take chain.
+fn debridge_outbound(
+    classified: &ClassifiedInstruction,
+    payload: &[u8],
+) -> Result<Outbound, BinaryReaderError> {
+    let mut reader = BinaryReader::new(payload.to_vec());
+    let amount = reader.read_u64()?;
+    let target_chain = chain_id(&reader.read_fixed_array(32)?);
+    let token_len = reader.read_u32()? as usize;
+    reader.read_fixed_array(token_len)?;
+    reader.read_fixed_array(32)?;
+    let receiver_len = reader.read_u32()? as usize;
+    let recipient = reader.read_fixed_array(receiver_len)?;
+    Ok(Outbound {
+        user: account(classified, 0),
+        mint: account(classified, 2),
+        amount,
+        target_chain,
+        recipient,
+    })
+}
+
+/// Wormhole token bridge lockups and burns, DLN orders, and the releases of both.
+pub struct BridgeFlows {
+    events: Vec<BridgeEvent>,
+    scopes: Vec<InvocationScope>,
+}
+
+impl BridgeFlows {
+    pub fn new(
+        adapter: &TransactionAdapter,
+        classifier: &InstructionClassifier,
+        transfer_actions: &TransferMap,
+    ) -> Self {
+        let transfers: Vec<&TransferData> = transfer_actions.values().flatten().collect();
+        let mut flows = Self {
+            events: Vec::new(),
+            scopes: Vec::new(),
+        };
+        for program_id in bridge_programs::ALL {
+            for classified in classifier.get_instructions(program_id) {
+                let scope =
+                    InvocationScope::new(adapter, classified.outer_index, classified.inner_index);
+                let moved: Vec<&TransferData> = transfers
+                    .iter()
+                    .copied()
+                    .filter(|transfer| scope.contains(&transfer.idx))
+                    .collect();
+                let Some(event) = Self::build_event(adapter, &classified, &moved) else {
+                    continue;
+                };
+                flows.events.push(BridgeEvent {
+                    program_id: program_id.to_string(),
+                    protocol: adapter.config().program_name(program_id),
+                    slot: adapter.slot(),
+                    timestamp: adapter.block_time(),
+                    signature: adapter.shared_signature(),
+                    idx: format!(
+                        "{}-{}",
+                        classified.outer_index,
+                        classified.inner_index.unwrap_or(0)
+                    ),
+                    ..event
+                });
+                flows.scopes.push(scope);
+            }
+        }
+        flows.events.sort_by(|a, b| compare_idx(&a.idx, &b.idx));
+        flows
+    }
+
+    /// Whether `transfer` was made by a bridge instruction.
+    pub fn contains(&self, transfer: &TransferData) -> bool {
+        self.scopes
+            .iter()
+            .any(|scope| scope.contains(&transfer.idx))
+    }
+
+    pub fn into_events(self) -> Vec<BridgeEvent> {
+        self.events
+    }
+
+    fn build_event(
+        adapter: &TransactionAdapter,
+        classified: &ClassifiedInstruction,
+        moved: &[&TransferData],
+    ) -> Option<BridgeEvent> {
+        use bridge_instructions::*;
+
+        let data = decode_instruction_data(&classified.data.data);
+        let outbound = match classified.program_id.as_str() {
+            bridge_programs::WORMHOLE_TOKEN_BRIDGE => match data.first().copied()? {
+                tag @ (WORMHOLE_TRANSFER_NATIVE
+                | WORMHOLE_TRANSFER_WRAPPED
+                | WORMHOLE_TRANSFER_NATIVE_WITH_PAYLOAD
+                | WORMHOLE_TRANSFER_WRAPPED_WITH_PAYLOAD) => {
+                    wormhole_outbound(classified, tag, &data[1..])
+                }
+                WORMHOLE_COMPLETE_NATIVE
+                | WORMHOLE_COMPLETE_WRAPPED
+                | WORMHOLE_COMPLETE_NATIVE_WITH_PAYLOAD
+                | WORMHOLE_COMPLETE_WRAPPED_WITH_PAYLOAD => {
+                    let to = account(classified, WORMHOLE_COMPLETE_TO_ACCOUNT);
+                    return Self::inbound(adapter, moved, Some(&to));
+                }
+                _ => return None,
+            },
+            bridge_programs::DEBRIDGE_DLN_SOURCE
+                if data.len() >= 8
+                    && (data[..8] == DEBRIDGE_CREATE_ORDER
+                        || data[..8] == DEBRIDGE_CREATE_ORDER_WITH_NONCE) =>
+            {
+                debridge_outbound(classified, &data[8..])
+            }
+            bridge_programs::DEBRIDGE_DLN_DESTINATION
+                if data.len() >= 8 && data[..8] == DEBRIDGE_FULFILL_ORDER =>
+            {
+                return Self::inbound(adapter, moved, None);
+            }
+            _ => return None,
+        };
+        match outbound {
+            Ok(outbound) => Some(Self::outbound(adapter, outbound, moved)),
+            Err(err) => {
+                tracing::error!("failed to parse bridge instruction: {err}");
+                None
+            }
+        }
+    }
+
+    fn outbound(
+        adapter: &TransactionAdapter,
+        outbound: Outbound,
+        moved: &[&TransferData],
+    ) -> BridgeEvent {
+        let decimals = moved
+            .iter()
+            .find(|transfer| transfer.info.mint == outbound.mint)
+            .map(|transfer| transfer.info.token_amount.decimals)
+            .or_else(|| adapter.spl_decimals_map().get(&outbound.mint).copied())
+            .unwrap_or(0);
+        BridgeEvent {
+            direction: BridgeDirection::Outbound,
+            user: outbound.user,
+            token: build_token_info(&outbound.mint, outbound.amount as u128, decimals, None),
+            target_chain: Some(outbound.target_chain),
+            recipient: Some(hex(&outbound.recipient)),
+            ..BridgeEvent::default()
+        }
+    }
+
+    /// Releases carry their amounts in the attested message, so read them off the payout
+    /// transfers: those into `to` when the recipient account is known, else the first one.
+    fn inbound(
+        adapter: &TransactionAdapter,
+        moved: &[&TransferData],
+        to: Option<&str>,
+    ) -> Option<BridgeEvent> {
+        let first = moved
+            .iter()
+            .find(|transfer| to.is_none_or(|to| transfer.info.destination == to))?;
+        let payouts = moved.iter().filter(|transfer| {
+            transfer.info.destination == first.info.destination
+                && transfer.info.mint == first.info.mint
+        });
+        let amount: u128 = payouts
+            .map(|transfer| {
+                transfer
+                    .info
+                    .token_amount
+                    .amount
+                    .parse::<u128>()
+                    .unwrap_or(0)
+            })
+            .sum();
+        let user = first
+            .info
+            .destination_owner
+            .clone()
+            .or_else(|| adapter.get_token_account_owner(&first.info.destination))
+            .unwrap_or_else(|| first.info.destination.clone());
+        Some(BridgeEvent {
+            direction: BridgeDirection::Inbound,
+            user,
+            token: build_token_info(
+                &first.info.mint,
+                amount,
+                first.info.token_amount.decimals,
+                None,
+            ),
+            ..BridgeEvent::default()
+        })
+    }
+}
@@ -0,0 +1,95 @@
+//! Jito bundles found among the transactions of a block.
+
+use std::collections::HashSet;
+
+use crate::types::ParseResult;
+
+/// Most transactions a Jito bundle can carry.
+const MAX_BUNDLE_LEN: usize = 5;
+
+/// Signers and pools a bundle's transactions touch, used to link the ones before its tip.
+#[derive(Default)]
+struct Members<'a> {
+    signers: HashSet<&'a str>,
+    pools: HashSet<&'a str>,
+}
+
+impl<'a> Members<'a> {
+    fn add(&mut self, result: &'a ParseResult) {
+        self.signers
+            .extend(result.signer.iter().map(String::as_str));
+        self.pools.extend(
+            result
+                .trades
+                .iter()
+                .filter_map(|trade| trade.pool.first().map(String::as_str)),
+        );
+    }
+
+    fn links(&self, result: &ParseResult) -> bool {
+        result
+            .signer
+            .iter()
+            .any(|signer| self.signers.contains(signer.as_str()))
+            || result.trades.iter().any(|trade| {
+                trade
+                    .pool
+                    .first()
+                    .is_some_and(|pool| self.pools.contains(pool.as_str()))
+            })
+    }
+}
+
+/// Whether `later` directly follows `earlier` in the block. Results without a block context
+/// are taken as consecutive.
+fn adjacent(earlier: &ParseResult, later: &ParseResult) -> bool {
+    match (&earlier.block_context, &later.block_context) {
+        (Some(earlier), Some(later)) => earlier.transaction_index + 1 == later.transaction_index,
+        _ => true,
+    }
+}
+
+/// Set `bundle_id` and `bundle_index` on the transactions of the Jito bundles in a block.
+///
+/// A bundle is closed by the transaction paying its Jito tip and runs back over up to four
+/// directly preceding transactions without a tip of their own that share a signer or a
+/// traded pool with the bundle, which takes in a sandwich's victim. Its id is the signature
+/// of the tipping transaction. Transactions are taken in [`ParseResult::block_position`]
+/// order.
+pub fn group_bundles(results: &mut [ParseResult]) {
+    let mut ordered: Vec<usize> = (0..results.len()).collect();
+    ordered.sort_by_key(|&index| results[index].block_position());
+
+    let mut bundles: Vec<Vec<usize>> = Vec::new();
+    for (position, &index) in ordered.iter().enumerate() {
+        if results[index].tip.is_none() {
+            continue;
+        }
+        let mut members = Members::default();
+        members.add(&results[index]);
+        let mut bundle = vec![index];
+        for &previous in ordered[..position].iter().rev() {
+            let candidate = &results[previous];
+            let next = &results[bundle[bundle.len() - 1]];
+            if bundle.len() == MAX_BUNDLE_LEN
+                || candidate.tip.is_some()
+                || !adjacent(candidate, next)
+                || !members.links(candidate)
+            {
+                break;
+            }
+            members.add(candidate);
+            bundle.push(previous);
+        }
+        bundle.reverse();
+        bundles.push(bundle);
+    }
+
+    for bundle in bundles {
+        let id = results[bundle[bundle.len() - 1]].signature.clone();
+        for (bundle_index, index) in bundle.into_iter().enumerate() {
+            results[index].bundle_id = Some(id.clone());
+            results[index].bundle_index = Some(bundle_index);
+        }
+    }
+}
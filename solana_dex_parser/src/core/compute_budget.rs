@@ -0,0 +1,78 @@
+//! Compute budget requests and the priority fee they pay.
+
+use crate::core::amounts::convert_to_ui_amount;
+use crate::core::constants::system_programs;
+use crate::core::constants::tokens::SOL_DECIMALS;
+use crate::core::transaction_adapter::TransactionAdapter;
+use crate::types::{ComputeBudget, SolanaInstruction, TokenAmount};
+
+/// `SetComputeUnitLimit` and `SetComputeUnitPrice` instruction tags.
+const SET_COMPUTE_UNIT_LIMIT: u8 = 2;
+const SET_COMPUTE_UNIT_PRICE: u8 = 3;
+
+/// Limit granted per non-compute-budget instruction when none is requested, and the cap.
+const DEFAULT_INSTRUCTION_UNIT_LIMIT: u64 = 200_000;
+const MAX_UNIT_LIMIT: u64 = 1_400_000;
+const MICRO_LAMPORTS_PER_LAMPORT: u128 = 1_000_000;
+
+/// Requested compute unit limit and price from the top-level compute budget instructions,
+/// with the priority fee they add; `None` when the transaction has no such instruction.
+///
+/// The fee is the unit price times the limit, rounded up to whole lamports as the runtime
+/// charges it. Without a requested limit the runtime default applies: 200k units per
+/// instruction that is not a compute budget one, up to 1.4M.
+pub fn compute_budget(adapter: &TransactionAdapter) -> Option<ComputeBudget> {
+    instructions_budget(adapter.instructions())
+}
+
+/// [`compute_budget`] of a list of top-level instructions.
+pub(crate) fn instructions_budget(instructions: &[SolanaInstruction]) -> Option<ComputeBudget> {
+    let mut budget_instructions = 0;
+    let mut unit_limit = None;
+    let mut unit_price = None;
+    for instruction in instructions {
+        if instruction.program_id != system_programs::COMPUTE_BUDGET {
+            continue;
+        }
+        budget_instructions += 1;
+        let Ok(data) = bs58::decode(&instruction.data).into_vec() else {
+            continue;
+        };
+        match data.split_first() {
+            Some((&SET_COMPUTE_UNIT_LIMIT, rest)) if rest.len() >= 4 => {
+                unit_limit = Some(u32::from_le_bytes([rest[0], rest[1], rest[2], rest[3]]));
+            }
+            Some((&SET_COMPUTE_UNIT_PRICE, rest)) if rest.len() >= 8 => {
+                let mut bytes = [0u8; 8];
+                bytes.copy_from_slice(&rest[..8]);
+                unit_price = Some(u64::from_le_bytes(bytes));
+            }
+            _ => {}
+        }
+    }
+    if budget_instructions == 0 {
+        return None;
+    }
+
+    let priority_fee = unit_price.map(|price| {
+        let limit = unit_limit.map_or_else(
+            || {
+                let instructions = (instructions.len() - budget_instructions) as u64;
+                (instructions * DEFAULT_INSTRUCTION_UNIT_LIMIT).min(MAX_UNIT_LIMIT)
+            },
+            u64::from,
+        );
+        let lamports =
+            (u128::from(price) * u128::from(limit)).div_ceil(MICRO_LAMPORTS_PER_LAMPORT) as u64;
+        TokenAmount::new(
+            lamports.to_string(),
+            SOL_DECIMALS,
+            Some(convert_to_ui_amount(lamports, SOL_DECIMALS)),
+        )
+    });
+    Some(ComputeBudget {
+        unit_limit,
+        unit_price,
+        priority_fee,
+    })
+}
@@ -1,29 +1,259 @@
 pub mod dex_programs {
     pub const JUPITER: &str = "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4";
+    pub const JUPITER_LIMIT_ORDER: &str = "jupoNjAxXgZ4rjzxzPMP4oxduvQsQtZzyknqvzYNrNu";
+    pub const JUPITER_TRIGGER: &str = "j1o2qRpjcyUwEvwtcfhEQefh773ZgjxcVRry7LDqg5X";
+    pub const JUPITER_DCA: &str = "DCA265Vj8a9CEuX1eb1LWRnDT7uK6q1xMipnNyatn23M";
     pub const RAYDIUM: &str = "675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8";
+    pub const RAYDIUM_CLMM: &str = "CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK";
+    pub const RAYDIUM_CPMM: &str = "CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C";
+    pub const RAYDIUM_LAUNCHLAB: &str = "LanMV9sAd7wArD4vJFi2qDdfnVhFxYSUg6eADduJ3uj";
     pub const PUMP_FUN: &str = "6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P";
     pub const PUMP_SWAP: &str = "pAMMBay6oceH9fJKBRHGP5D4bD4sWpmSwMn52FMfXEA";
     pub const ORCA: &str = "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc";
     pub const METEORA: &str = "LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo";
+    pub const METEORA_DBC: &str = "dbcij3LWUppWqq96dh6gJWwBifmcGfLSB5D4DuSMaqN";
+    pub const METEORA_DAMM: &str = "Eo7WjKq67rjJQSZxS6z3YkapzY3eMj6Xy8X5EQVn5UaB";
+    pub const METEORA_DAMM_V2: &str = "cpamdpZCGKUy5JxQXB4dcpGPiikHawvSWAd6mEn1sGG";
+    pub const OPENBOOK_V2: &str = "opnb2LAfJYbRMAHHvqjCwQxanZn7ReEHp1k81EohpZb";
+    pub const SABER: &str = "SSwpkEEcbUqx4vtoEByFjSkhKdCT5PRxhsJq6Nf1ppq";
+    pub const FLUXBEAM: &str = "FLUXubRmkEi2q6K3Y9kBPg9248ggaZVsoSFhtJHSrm1X";
+    pub const SOLFI: &str = "SoLFiHG9TfgtdUXUjWAxi3LtvYuFyDLVhBWxdMZxyCe";
+    pub const ZEROFI: &str = "ZERor4xhbUycZ6gb9ntrhqscUcZmAbQDjEAtCf4hbZY";
+    pub const OBRIC_V2: &str = "obriQD1zbpyLz95G5n7nJe6a4DPjpFwa5XYPoNm113y";
+    pub const HEAVEN: &str = "HEAVENoP2qxoeuF8Dj2oT1GHEnu49U5mJYkdeC8BAX2o";
+    pub const HUMIDIFI: &str = "9H6tua7jkLhdm3w8BvgpTn5LZNU7g4ZynDmCiNN3q6Rp";
+    pub const VIRTUALS: &str = "5U3EU2ubXtK84QcRjWVmYt9RaDyA8gKxdUrPFXmZyaki";
+    pub const OKX: &str = "6m2CDdhRgxpH4WjvdzxAYbGxwdGUz5MziiL5jek2kBma";
+    pub const BAGS: &str = "BAGSB9TpGrZxQbEsrEznv5jXXdwyP6AXerN8aVRiAmcv";
+    pub const SPL_TOKEN_SWAP: &str = "SwaPpA9LAaLfeLi3a68M4DjnLqgtticKg6CnyNwgAC8";
+    pub const STEP: &str = "SSwpMgqNDsyV7mAgN9ady4bDVu5ySjmmXejXvy2vLt1";
+    pub const PENGUIN: &str = "PSwapMdSai8tjrEXcxFeQth87xC4rRsa4VA5mhGhXkP";
+    pub const SAROS: &str = "SSwapUtytfBdBn1b9NUGG6foMVPtcWgpRU32HToDUZr";
+    pub const MOONSHOT: &str = "MoonCVVNZFSYkqNXP6bxHLPL6QQJiMagDL3qcqUQTrG";
     pub const UNKNOWN: &str = "UNKNOWN";
 }
 
-pub mod dex_program_names {
+/// Devnet deployments whose program id differs from mainnet; the rest share mainnet ids.
+pub mod devnet_programs {
     use super::dex_programs;
+
+    pub const RAYDIUM: &str = "HWy1jotHpo6UqeQxx49dpYYdQB8wj9Qk9MdxwjLvDHB8";
+    pub const RAYDIUM_CLMM: &str = "devi51mZmdwUJGU9hjN27vEz64Gps7uUefqxg27EAtH";
+    pub const RAYDIUM_CPMM: &str = "CPMDWBwJDtYax9qW7AyRuVC19Cc4L4Vcy4n2BHAbHkCW";
+
+    /// `(devnet, mainnet)` program id pairs.
+    pub const ALL: [(&str, &str); 3] = [
+        (RAYDIUM, dex_programs::RAYDIUM),
+        (RAYDIUM_CLMM, dex_programs::RAYDIUM_CLMM),
+        (RAYDIUM_CPMM, dex_programs::RAYDIUM_CPMM),
+    ];
+}
+
+/// Launchpad instructions that graduate a bonding curve, for programs without a parser module.
+pub mod migration_instructions {
+    pub const MOONSHOT_MIGRATE_FUNDS: [u8; 8] = [42, 229, 10, 231, 189, 62, 193, 174];
+}
+
+/// Platform configs of launchpads that run on a shared curve program, with the launchpad
+/// name reported in `MemeEvent::protocol`.
+pub mod launchpad_platforms {
+    /// Meteora DBC pool config of Believe launches.
+    pub const BELIEVE: &str = "5qWya6UjwWnGVhdSBL3hyZ7B45jbk6Byt1hwd7ohEGXE";
+    /// Raydium LaunchLab platform config of LetsBonk (bonk.fun) launches.
+    pub const LETS_BONK: &str = "FfYek5vEz23cMkWsdJwG2oa6EphsvXSHrGpdALN4g6W1";
+
+    pub const ALL: [(&str, &str); 2] = [(BELIEVE, "Believe"), (LETS_BONK, "LetsBonk")];
+
+    pub fn name(platform_config: &str) -> Option<&'static str> {
+        ALL.iter()
+            .find(|(config, _)| *config == platform_config)
+            .map(|(_, name)| *name)
+    }
+}
+
+/// Trading bots that wrap DEX swaps and skim a fee to their own wallets, as
+/// `(name, router program ids, fee wallets)`.
+pub mod trading_bots {
+    pub const PHOTON_ROUTER: &str = "BSfD6SHZigAfDWSjzD5Q41jw8LmKwtmjskPH9XW1mrRW";
+    pub const PHOTON_FEE_WALLET: &str = "AVUCZyuT35YSuj4RH7fwiyPu82Djn2Hfg7y2ND2XcnZH";
+    pub const BULLX_FEE_WALLET: &str = "F4hJ3Ee3c5UuaorKAMfELBjYCjiiLH75haZTKqTywRP3";
+    pub const TROJAN_FEE_WALLET: &str = "9yMwSPk9mrXSN7yDHUuZurAh1sjbJsfpUqjZ7SvVtdco";
+    pub const AXIOM_FEE_WALLET: &str = "7LCZckF6XXGQ1hDY6HFXBKWAtiUgL9QY5vj1C4Bn1Qjj";
+
+    pub const ALL: [(&str, &[&str], &[&str]); 4] = [
+        ("Photon", &[PHOTON_ROUTER], &[PHOTON_FEE_WALLET]),
+        ("BullX", &[], &[BULLX_FEE_WALLET]),
+        ("Trojan", &[], &[TROJAN_FEE_WALLET]),
+        ("Axiom", &[], &[AXIOM_FEE_WALLET]),
+    ];
+}
+
+/// Hot wallets of centralized exchanges, which fund unrelated users with their withdrawals.
+pub mod exchange_wallets {
+    pub const BINANCE_1: &str = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM";
+    pub const BINANCE_2: &str = "5tzFkiKscXHK5ZXCGbXZxdw7gTjjD1mBwuoFbhUvuAi9";
+    pub const COINBASE_1: &str = "H8sMJSCQxfKiFTCfDR3DUMLPwcRbM61LGFJ8N4dK3WjS";
+    pub const COINBASE_2: &str = "GJRs4FwHtemZ5ZE9x3FNvJ8TMwitKTh21yxdRPqn7npE";
+    pub const KRAKEN: &str = "FWznbcNXWQuHTawe9RxvQ2LdCENssh12dsznf4RiouN5";
+    pub const OKX: &str = "5VCwKtCXgCJ6kit5FybXjvriW3xELsFDhYrPSqtJNmcD";
+    pub const BYBIT: &str = "AC5RDfQFmDS1deWZos921JfqscXdByf8BKHs5ACWjtW2";
+
+    pub const ALL: [&str; 7] = [
+        BINANCE_1, BINANCE_2, COINBASE_1, COINBASE_2, KRAKEN, OKX, BYBIT,
+    ];
+}
+
+pub mod token_programs {
+    pub const TOKEN: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+    pub const TOKEN_2022: &str = "TokenzQdBNbLqP5VKv7iGkhyyddDpRdmHgDBNXn7Dp1";
+}
+
+/// Programs that custody LP tokens on behalf of the pool creator.
+pub mod lock_programs {
+    pub const RAYDIUM_LP_LOCK: &str = "LockrWmn6K5twhz3y9w1dQERbmgSaRkfnTeTKbpofwE";
+    pub const STREAMFLOW: &str = "strmRqUCoQUgGUan5YhzUZa6KqdzwX5L6FpUxfmKg5m";
+
+    pub const ALL: [&str; 2] = [RAYDIUM_LP_LOCK, STREAMFLOW];
+}
+
+/// Programs that move tokens around a DEX swap without being a venue themselves.
+pub mod helper_programs {
+    pub const SABER_DECIMAL_WRAPPER: &str = "DecZY86MU5Gj7kppfUCEmd4LbXXuyZH1yHaP2NTqdiZB";
+
+    pub const ALL: [&str; 1] = [SABER_DECIMAL_WRAPPER];
+}
+
+pub mod tokens {
+    pub const SOL: &str = "So11111111111111111111111111111111111111112";
+    /// Decimals of SOL and wrapped SOL: 1 SOL is 10^9 lamports.
+    pub const SOL_DECIMALS: u8 = 9;
+    pub const USDC: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+    pub const USDT: &str = "Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB";
+}
+
+pub mod system_programs {
+    use super::token_programs;
+
+    pub const SYSTEM: &str = "11111111111111111111111111111111";
+    pub const COMPUTE_BUDGET: &str = "ComputeBudget111111111111111111111111111111";
+    pub const ASSOCIATED_TOKEN: &str = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL";
+    pub const VOTE: &str = "Vote111111111111111111111111111111111111111";
+
+    /// Runtime and token programs, never parsed as a venue.
+    pub const ALL: [&str; 5] = [
+        COMPUTE_BUDGET,
+        SYSTEM,
+        token_programs::TOKEN,
+        token_programs::TOKEN_2022,
+        ASSOCIATED_TOKEN,
+    ];
+}
+
+/// Programs invoked next to a DEX that carry no trades of their own.
+pub mod skip_programs {
+    pub const PUMPSWAP_FEE: &str = "pfeeUxB6jkeY1Hxd7CsFCAjcbHA9rWtchMGdZ6VojVZ";
+
+    pub const ALL: [&str; 1] = [PUMPSWAP_FEE];
+}
+
+/// Serum-style order books that are not parsed as venues; their cranks and settlements
+/// move funds without trading.
+pub mod orderbook_programs {
+    pub const SERUM_V3: &str = "9xQeWvG816bUx9EPjHmaT23yvVM2ZWbrrpZb9PusVFin";
+    pub const OPENBOOK_V1: &str = "srmqPvymJeFKQ4zGQed1GFppgkRHL9kaELCbyksJtPX";
+
+    pub const ALL: [&str; 2] = [SERUM_V3, OPENBOOK_V1];
+}
+
+/// Cross-chain bridges whose lockups and releases are reported as bridge events.
+pub mod bridge_programs {
+    pub const WORMHOLE_TOKEN_BRIDGE: &str = "wormDTUJ6AWPNvk59vGQbDvGJmqbDTdgWgAqcLBCgUb";
+    pub const DEBRIDGE_DLN_SOURCE: &str = "src5qyZHqTqecJV4aY6Cb6zDZLMDzrDKKezs22MPHr4";
+    pub const DEBRIDGE_DLN_DESTINATION: &str = "dst5MGcFPoBeREFAA5E3tU5ij8m5uVYwkzkSAbsLbNo";
+
+    pub const ALL: [&str; 3] = [
+        WORMHOLE_TOKEN_BRIDGE,
+        DEBRIDGE_DLN_SOURCE,
+        DEBRIDGE_DLN_DESTINATION,
+    ];
+}
+
+/// Bridge instructions that move tokens across chains.
+pub mod bridge_instructions {
+    /// Wormhole Token Bridge instruction tags (first data byte).
+    pub const WORMHOLE_COMPLETE_NATIVE: u8 = 2;
+    pub const WORMHOLE_COMPLETE_WRAPPED: u8 = 3;
+    pub const WORMHOLE_TRANSFER_WRAPPED: u8 = 4;
+    pub const WORMHOLE_TRANSFER_NATIVE: u8 = 5;
+    pub const WORMHOLE_COMPLETE_NATIVE_WITH_PAYLOAD: u8 = 9;
+    pub const WORMHOLE_COMPLETE_WRAPPED_WITH_PAYLOAD: u8 = 10;
+    pub const WORMHOLE_TRANSFER_WRAPPED_WITH_PAYLOAD: u8 = 11;
+    pub const WORMHOLE_TRANSFER_NATIVE_WITH_PAYLOAD: u8 = 12;
+
+    pub const DEBRIDGE_CREATE_ORDER: [u8; 8] = [141, 54, 37, 207, 237, 210, 250, 215];
+    pub const DEBRIDGE_CREATE_ORDER_WITH_NONCE: [u8; 8] = [130, 131, 98, 190, 40, 206, 68, 50];
+    pub const DEBRIDGE_FULFILL_ORDER: [u8; 8] = [61, 214, 39, 248, 65, 212, 153, 36];
+}
+
+/// Accounts that receive Jito bundle tips.
+pub mod jito_tip_accounts {
+    pub const ALL: [&str; 8] = [
+        "96gYZGLnJYVFmbjzopPSU6QiEV5fGqZNyN9nmNhvrZU5",
+        "HFqU5x63VTqvQss8hp11i4wVV8bD44PvwucfZ2bU7gRe",
+        "Cw8CFyM9FkoMi7K7Crf6HNQqf4uEMzpKw6QNghXLvLkY",
+        "ADaUMid9yfUytqMBgopwjb2DTLSokTSzL1zt6iGPaS49",
+        "DfXygSm4jCyNCybVYYK6DwvWqjKee8pbDmJGcLWNDXjh",
+        "ADuUkR4vqLUMWXxW9gh6D6L8pMSawimctcNZ5pGwDcEt",
+        "DttWaMuVvTiduZRnguLF7jNxTgiMBZ1hyAumKUiL2KRL",
+        "3AVi9Tg9Uo68tJfuvoKvqKNWKkC5wPdSSdeBnizKZ6jT",
+    ];
+}
+
+pub mod dex_program_names {
+    use super::{bridge_programs, dex_programs};
     use once_cell::sync::Lazy;
     use std::collections::HashMap;
 
     static PROGRAM_NAME: Lazy<HashMap<&'static str, &'static str>> = Lazy::new(|| {
         let mut map = HashMap::new();
         map.insert(dex_programs::JUPITER, "Jupiter");
+        map.insert(dex_programs::JUPITER_LIMIT_ORDER, "JupiterLimit");
+        map.insert(dex_programs::JUPITER_TRIGGER, "JupiterTrigger");
+        map.insert(dex_programs::JUPITER_DCA, "JupiterDCA");
         map.insert(dex_programs::RAYDIUM, "Raydium");
+        map.insert(dex_programs::RAYDIUM_CLMM, "RaydiumCLMM");
+        map.insert(dex_programs::RAYDIUM_CPMM, "RaydiumCPMM");
+        map.insert(dex_programs::RAYDIUM_LAUNCHLAB, "RaydiumLaunchLab");
         map.insert(dex_programs::PUMP_FUN, "Pumpfun");
         map.insert(dex_programs::PUMP_SWAP, "Pumpswap");
         map.insert(dex_programs::ORCA, "Orca");
         map.insert(dex_programs::METEORA, "Meteora");
+        map.insert(dex_programs::METEORA_DBC, "MeteoraDBC");
+        map.insert(dex_programs::METEORA_DAMM, "MeteoraDAMM");
+        map.insert(dex_programs::METEORA_DAMM_V2, "MeteoraDAMMV2");
+        map.insert(dex_programs::OPENBOOK_V2, "OpenBookV2");
+        map.insert(dex_programs::SABER, "Saber");
+        map.insert(dex_programs::FLUXBEAM, "FluxBeam");
+        map.insert(dex_programs::SOLFI, "SolFi");
+        map.insert(dex_programs::ZEROFI, "ZeroFi");
+        map.insert(dex_programs::OBRIC_V2, "ObricV2");
+        map.insert(dex_programs::HEAVEN, "Heaven");
+        map.insert(dex_programs::HUMIDIFI, "HumidiFi");
+        map.insert(dex_programs::VIRTUALS, "Virtuals");
+        map.insert(dex_programs::OKX, "OKX");
+        map.insert(dex_programs::BAGS, "Bags");
+        map.insert(dex_programs::SPL_TOKEN_SWAP, "SplTokenSwap");
+        map.insert(dex_programs::STEP, "Step");
+        map.insert(dex_programs::PENGUIN, "Penguin");
+        map.insert(dex_programs::SAROS, "Saros");
+        map.insert(dex_programs::MOONSHOT, "Moonshot");
+        map.insert(bridge_programs::WORMHOLE_TOKEN_BRIDGE, "Wormhole");
+        map.insert(bridge_programs::DEBRIDGE_DLN_SOURCE, "deBridge");
+        map.insert(bridge_programs::DEBRIDGE_DLN_DESTINATION, "deBridge");
         map
     });
 
+    /// Name of a bundled program; see `ParseConfig::program_name` for runtime program tables.
     pub fn name(program_id: &str) -> &'static str {
         PROGRAM_NAME
             .get(program_id)
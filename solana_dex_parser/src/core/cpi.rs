@@ -0,0 +1,233 @@
+use std::collections::HashMap;
+
+use base64::{engine::general_purpose::STANDARD as BASE64_STANDARD, Engine as _};
+
+use crate::core::constants::token_programs;
+use crate::core::transaction_adapter::TransactionAdapter;
+use crate::types::{FailedCpi, SolanaInstruction, TransactionStatus};
+
+/// Open invocation frame while walking the program logs.
+struct Frame {
+    program_id: String,
+    /// Position of the invoking instruction in execution order.
+    start: usize,
+}
+
+/// Inner CPIs that failed without failing the transaction, read from the program logs.
+///
+/// Each `Program <id> invoke [n]` line is matched to the next instruction in execution order
+/// (an outer instruction followed by its inner instructions); a frame closed by
+/// `Program <id> failed: ...` reverts the instruction and every CPI it made. Returns nothing
+/// for failed transactions, when logs are missing, or when the invocations in the logs do
+/// not line up with the recorded instructions. Truncated logs are read up to the cut.
+pub fn failed_cpis(adapter: &TransactionAdapter) -> Vec<FailedCpi> {
+    if adapter.log_messages().is_empty() || adapter.tx_status() == TransactionStatus::Failed {
+        return Vec::new();
+    }
+
+    let executed = execution_order(adapter);
+    let mut stack: Vec<Frame> = Vec::new();
+    let mut next = 0;
+    let mut failed = Vec::new();
+    for line in adapter.log_messages() {
+        let Some(rest) = line.strip_prefix("Program ") else {
+            continue;
+        };
+        let Some((program_id, event)) = rest.split_once(' ') else {
+            continue;
+        };
+        if event.starts_with("invoke [") {
+            match executed.get(next) {
+                Some(expected) if expected.program_id == program_id => {}
+                _ => return Vec::new(),
+            }
+            stack.push(Frame {
+                program_id: program_id.to_string(),
+                start: next,
+            });
+            next += 1;
+        } else if event == "success" {
+            if stack
+                .pop()
+                .is_none_or(|frame| frame.program_id != program_id)
+            {
+                return Vec::new();
+            }
+        } else if let Some(error) = event.strip_prefix("failed") {
+            let Some(frame) = stack.pop().filter(|frame| frame.program_id == program_id) else {
+                return Vec::new();
+            };
+            // A failing outer instruction fails the whole transaction.
+            if executed[frame.start].inner_index.is_some() {
+                let error = error.trim_start_matches(':').trim();
+                failed.push(FailedCpi {
+                    program_id: frame.program_id,
+                    idx: executed[frame.start].idx(),
+                    error: (!error.is_empty()).then(|| error.to_string()),
+                    reverted: executed[frame.start..next]
+                        .iter()
+                        .map(Executed::idx)
+                        .collect(),
+                });
+            }
+        }
+    }
+    failed
+}
+
+/// `Program data:` payloads each instruction of `program_id` logged itself, keyed by
+/// `(outer_index, inner_index)`; data logged by the programs it invokes stays with them.
+///
+/// Walks the invocations the same way as [`failed_cpis`] and returns nothing when the logs
+/// are missing or do not line up with the recorded instructions.
+pub fn program_data(
+    adapter: &TransactionAdapter,
+    program_id: &str,
+) -> HashMap<(usize, Option<usize>), Vec<Vec<u8>>> {
+    let executed = execution_order(adapter);
+    let mut stack: Vec<usize> = Vec::new();
+    let mut next = 0;
+    let mut data: HashMap<(usize, Option<usize>), Vec<Vec<u8>>> = HashMap::new();
+    for line in adapter.log_messages() {
+        if let Some(payload) = line.strip_prefix("Program data: ") {
+            let Some(&current) = stack.last() else {
+                continue;
+            };
+            let instruction = &executed[current];
+            if instruction.program_id != program_id {
+                continue;
+            }
+            if let Ok(bytes) = BASE64_STANDARD.decode(payload.trim()) {
+                data.entry((instruction.outer_index, instruction.inner_index))
+                    .or_default()
+                    .push(bytes);
+            }
+            continue;
+        }
+        let Some(rest) = line.strip_prefix("Program ") else {
+            continue;
+        };
+        let Some((invoked, event)) = rest.split_once(' ') else {
+            continue;
+        };
+        if event.starts_with("invoke [") {
+            match executed.get(next) {
+                Some(expected) if expected.program_id == invoked => {}
+                _ => return HashMap::new(),
+            }
+            stack.push(next);
+            next += 1;
+        } else if event == "success" || event.starts_with("failed") {
+            stack.pop();
+        }
+    }
+    data
+}
+
+/// Instruction at one position of the execution order.
+struct Executed {
+    program_id: String,
+    outer_index: usize,
+    inner_index: Option<usize>,
+}
+
+impl Executed {
+    /// Outer instructions keep a bare index so they cannot be confused with their first
+    /// inner instruction.
+    fn idx(&self) -> String {
+        match self.inner_index {
+            Some(inner_index) => format!("{}-{inner_index}", self.outer_index),
+            None => self.outer_index.to_string(),
+        }
+    }
+}
+
+/// Every instruction in execution order: each outer instruction followed by its inner ones.
+fn execution_order(adapter: &TransactionAdapter) -> Vec<Executed> {
+    let mut executed = Vec::new();
+    for (outer_index, instruction) in adapter.instructions().iter().enumerate() {
+        executed.push(Executed {
+            program_id: instruction.program_id.clone(),
+            outer_index,
+            inner_index: None,
+        });
+        let inner = adapter
+            .inner_instructions()
+            .iter()
+            .filter(|set| set.index == outer_index)
+            .flat_map(|set| &set.instructions);
+        for (inner_index, instruction) in inner.enumerate() {
+            executed.push(Executed {
+                program_id: instruction.program_id.clone(),
+                outer_index,
+                inner_index: Some(inner_index),
+            });
+        }
+    }
+    executed
+}
+
+/// Instruction positions covered by one instruction and the CPIs it made, for telling which
+/// transfers it executed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct InvocationScope {
+    outer_index: usize,
+    /// Inner positions `start..end` of the CPIs made by an inner instruction.
+    inner: Option<(usize, usize)>,
+}
+
+impl InvocationScope {
+    /// Scope of the instruction at `(outer_index, inner_index)`.
+    ///
+    /// An outer instruction covers its whole outer position. An inner one covers the
+    /// instructions it invoked: the deeper ones by stack height or, without stack heights,
+    /// the token-program instructions that directly follow it.
+    pub fn new(
+        adapter: &TransactionAdapter,
+        outer_index: usize,
+        inner_index: Option<usize>,
+    ) -> Self {
+        let Some(inner_index) = inner_index else {
+            return Self {
+                outer_index,
+                inner: None,
+            };
+        };
+        let instructions: Vec<&SolanaInstruction> = adapter
+            .inner_instructions()
+            .iter()
+            .filter(|set| set.index == outer_index)
+            .flat_map(|set| &set.instructions)
+            .collect();
+        let height = instructions
+            .get(inner_index)
+            .and_then(|instruction| instruction.stack_height);
+        let start = inner_index + 1;
+        let end = instructions
+            .iter()
+            .skip(start)
+            .position(|next| match (height, next.stack_height) {
+                (Some(height), Some(next_height)) => next_height <= height,
+                _ => ![token_programs::TOKEN, token_programs::TOKEN_2022]
+                    .contains(&next.program_id.as_str()),
+            })
+            .map_or(instructions.len().max(start), |offset| start + offset);
+        Self {
+            outer_index,
+            inner: Some((start, end)),
+        }
+    }
+
+    /// Whether the instruction at `idx` (`"outer"` or `"outer-inner"`) ran in this scope.
+    pub fn contains(&self, idx: &str) -> bool {
+        let mut parts = idx.split('-').map(str::parse::<usize>);
+        if parts.next().and_then(Result::ok) != Some(self.outer_index) {
+            return false;
+        }
+        match (self.inner, parts.next().and_then(Result::ok)) {
+            (None, _) => true,
+            (Some((start, end)), Some(inner_index)) => (start..end).contains(&inner_index),
+            (Some(_), None) => false,
+        }
+    }
+}
@@ -1,24 +1,87 @@
 use std::collections::{HashMap, HashSet};
+#[cfg(feature = "rpc")]
+use std::ops::Range;
+use std::sync::Arc;
+
+#[cfg(feature = "rpc")]
+use futures::stream::{self, Stream, StreamExt};
 
 use crate::config::ParseConfig;
-use crate::core::constants::{dex_program_names, dex_programs};
+use crate::core::aggregate::aggregate_route;
+use crate::core::alias::{canonicalize_program_ids, restore_program_ids};
+use crate::core::anomaly::flag_suspect_trades;
+use crate::core::arbitrage::detect_arbitrage;
+use crate::core::attempts::attempted_trades;
+use crate::core::bots::{attach_bot_fees, is_bot_program};
+use crate::core::bridge::BridgeFlows;
+use crate::core::bundles::group_bundles;
+use crate::core::compute_budget::compute_budget;
+use crate::core::constants::{bridge_programs, dex_programs, helper_programs};
+use crate::core::cpi::failed_cpis;
 use crate::core::error::ParserError;
+use crate::core::failure::error_reason;
+use crate::core::fees::fee_breakdown;
+use crate::core::flow::classify_flow;
 use crate::core::instruction_classifier::InstructionClassifier;
+use crate::core::launchpad::attribute_launchpads;
+use crate::core::migration::detect_migrations;
+use crate::core::pool_state::{attach_pool_states, PoolStateProvider};
+use crate::core::price::{fill_usd_values, fill_volume_sol, PriceProvider, StablePairPrice};
+use crate::core::routing::routing_improvement_bps;
+use crate::core::settlement::SettlementFlows;
+use crate::core::timing::StageTimer;
+use crate::core::tips::{is_tip_transfer, jito_tip};
 use crate::core::transaction_adapter::TransactionAdapter;
 use crate::core::transaction_utils::TransactionUtils;
+use crate::core::util::compare_idx;
+use crate::core::wash::flag_wash_trades;
+use crate::protocols::bags::{build_bags_meme_parser, build_bags_trade_parser};
+use crate::protocols::fluxbeam::{build_fluxbeam_liquidity_parser, build_fluxbeam_trade_parser};
+use crate::protocols::heaven::build_heaven_trade_parser;
+use crate::protocols::humidifi::build_humidifi_trade_parser;
+use crate::protocols::jupiter::{
+    build_jupiter_dca_trade_parser, build_jupiter_limit_order_trade_parser,
+    build_jupiter_trade_parser,
+};
+use crate::protocols::meteora::build_meteora_dbc_meme_parser;
+use crate::protocols::obric::build_obric_v2_trade_parser;
+use crate::protocols::okx::build_okx_trade_parser;
+use crate::protocols::openbook::{build_openbook_liquidity_parser, build_openbook_trade_parser};
+use crate::protocols::orca::{
+    build_orca_whirlpool_liquidity_parser, build_orca_whirlpool_trade_parser,
+};
 use crate::protocols::pumpfun::{
     build_pumpfun_meme_parser, build_pumpfun_trade_parser, build_pumpswap_liquidity_parser,
-    build_pumpswap_trade_parser, build_pumpswap_transfer_parser,
+    build_pumpswap_meme_parser, build_pumpswap_trade_parser, build_pumpswap_transfer_parser,
 };
+use crate::protocols::raydium::{
+    build_raydium_clmm_liquidity_parser, build_raydium_clmm_trade_parser,
+    build_raydium_cpmm_liquidity_parser, build_raydium_cpmm_trade_parser,
+    build_raydium_launchlab_meme_parser,
+};
+use crate::protocols::saber::{build_saber_liquidity_parser, build_saber_trade_parser};
 use crate::protocols::simple::{
     LiquidityParser, MemeEventParser, SimpleLiquidityParser, SimpleMemeParser, SimpleTradeParser,
     SimpleTransferParser, TradeParser, TransferParser,
 };
+use crate::protocols::solfi::build_solfi_trade_parser;
+use crate::protocols::token_swap::constants::TOKEN_SWAP_PROGRAM_IDS;
+use crate::protocols::token_swap::{
+    build_token_swap_liquidity_parser, build_token_swap_trade_parser,
+};
+use crate::protocols::virtuals::{build_virtuals_meme_parser, build_virtuals_trade_parser};
+use crate::protocols::zerofi::build_zerofi_trade_parser;
+use crate::rpc::{self, SimulationContext};
+#[cfg(feature = "rpc")]
+use crate::rpc::{BackfillOptions, RetryPolicy};
 use crate::types::{
-    BlockInput, BlockParseResult, ClassifiedInstruction, DexInfo, FromJsonValue, ParseResult,
-    PoolEvent, SolanaBlock, SolanaTransaction, TradeInfo, TransferData, TransferMap,
+    BlockContext, BlockInput, BlockParseResult, ClassifiedInstruction, DexInfo, FromJsonValue,
+    ParseResult, PoolEvent, ProgramInvocation, SolanaBlock, SolanaTransaction, TokenAccountOwner,
+    TradeInfo, TransferData, TransferMap,
 };
 use serde_json::Value;
+use solana_client::rpc_response::RpcSimulateTransactionResult;
+use solana_sdk::transaction::VersionedTransaction;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum ParseType {
@@ -61,11 +124,25 @@ type TransferParserBuilder = fn(
 
 type MemeParserBuilder = fn(TransactionAdapter, TransferMap) -> Box<dyn MemeEventParser>;
 
+/// Fix-up applied to every transaction before it is parsed, e.g. to patch a vendor's
+/// field quirks, inject missing decimals or redact accounts. Closures may capture state,
+/// such as a table of accounts to redact.
+pub type NormalizeMiddleware = Arc<dyn Fn(&mut SolanaTransaction) + Send + Sync>;
+
+/// Hook applied to every result after parsing, e.g. for custom labels or account tags;
+/// closures may capture their own lookup tables.
+pub type PostProcessor = Arc<dyn Fn(&mut ParseResult) + Send + Sync>;
+
 pub struct DexParser {
     trade_parsers: HashMap<String, TradeParserBuilder>,
     liquidity_parsers: HashMap<String, LiquidityParserBuilder>,
     transfer_parsers: HashMap<String, TransferParserBuilder>,
     meme_parsers: HashMap<String, MemeParserBuilder>,
+    price_provider: Option<Arc<dyn PriceProvider>>,
+    pool_state_provider: Option<Arc<dyn PoolStateProvider>>,
+    middlewares: Vec<NormalizeMiddleware>,
+    post_processors: Vec<PostProcessor>,
+    max_price_deviation: Option<f64>,
 }
 
 impl Default for DexParser {
@@ -95,6 +172,20 @@ impl DexParser {
             meme_parsers.insert(program.to_string(), SimpleMemeParser::boxed);
         }
 
+        trade_parsers.insert(
+            dex_programs::JUPITER.to_string(),
+            build_jupiter_trade_parser,
+        );
+        for program in [
+            dex_programs::JUPITER_LIMIT_ORDER,
+            dex_programs::JUPITER_TRIGGER,
+        ] {
+            trade_parsers.insert(program.to_string(), build_jupiter_limit_order_trade_parser);
+        }
+        trade_parsers.insert(
+            dex_programs::JUPITER_DCA.to_string(),
+            build_jupiter_dca_trade_parser,
+        );
         trade_parsers.insert(
             dex_programs::PUMP_FUN.to_string(),
             build_pumpfun_trade_parser,
@@ -103,10 +194,76 @@ impl DexParser {
             dex_programs::PUMP_SWAP.to_string(),
             build_pumpswap_trade_parser,
         );
+        trade_parsers.insert(
+            dex_programs::ORCA.to_string(),
+            build_orca_whirlpool_trade_parser,
+        );
+        trade_parsers.insert(
+            dex_programs::RAYDIUM_CLMM.to_string(),
+            build_raydium_clmm_trade_parser,
+        );
+        trade_parsers.insert(
+            dex_programs::RAYDIUM_CPMM.to_string(),
+            build_raydium_cpmm_trade_parser,
+        );
+        trade_parsers.insert(
+            dex_programs::OPENBOOK_V2.to_string(),
+            build_openbook_trade_parser,
+        );
+        trade_parsers.insert(dex_programs::SABER.to_string(), build_saber_trade_parser);
+        trade_parsers.insert(
+            dex_programs::FLUXBEAM.to_string(),
+            build_fluxbeam_trade_parser,
+        );
+        trade_parsers.insert(dex_programs::SOLFI.to_string(), build_solfi_trade_parser);
+        trade_parsers.insert(dex_programs::ZEROFI.to_string(), build_zerofi_trade_parser);
+        trade_parsers.insert(
+            dex_programs::OBRIC_V2.to_string(),
+            build_obric_v2_trade_parser,
+        );
+        trade_parsers.insert(dex_programs::HEAVEN.to_string(), build_heaven_trade_parser);
+        trade_parsers.insert(
+            dex_programs::HUMIDIFI.to_string(),
+            build_humidifi_trade_parser,
+        );
+        trade_parsers.insert(
+            dex_programs::VIRTUALS.to_string(),
+            build_virtuals_trade_parser,
+        );
+        trade_parsers.insert(dex_programs::BAGS.to_string(), build_bags_trade_parser);
+        trade_parsers.insert(dex_programs::OKX.to_string(), build_okx_trade_parser);
         liquidity_parsers.insert(
             dex_programs::PUMP_SWAP.to_string(),
             build_pumpswap_liquidity_parser,
         );
+        liquidity_parsers.insert(
+            dex_programs::ORCA.to_string(),
+            build_orca_whirlpool_liquidity_parser,
+        );
+        liquidity_parsers.insert(
+            dex_programs::RAYDIUM_CLMM.to_string(),
+            build_raydium_clmm_liquidity_parser,
+        );
+        liquidity_parsers.insert(
+            dex_programs::RAYDIUM_CPMM.to_string(),
+            build_raydium_cpmm_liquidity_parser,
+        );
+        liquidity_parsers.insert(
+            dex_programs::OPENBOOK_V2.to_string(),
+            build_openbook_liquidity_parser,
+        );
+        liquidity_parsers.insert(
+            dex_programs::SABER.to_string(),
+            build_saber_liquidity_parser,
+        );
+        liquidity_parsers.insert(
+            dex_programs::FLUXBEAM.to_string(),
+            build_fluxbeam_liquidity_parser,
+        );
+        for program_id in TOKEN_SWAP_PROGRAM_IDS {
+            trade_parsers.insert(program_id.to_string(), build_token_swap_trade_parser);
+            liquidity_parsers.insert(program_id.to_string(), build_token_swap_liquidity_parser);
+        }
         transfer_parsers.insert(
             dex_programs::PUMP_SWAP.to_string(),
             build_pumpswap_transfer_parser,
@@ -115,12 +272,113 @@ impl DexParser {
             dex_programs::PUMP_FUN.to_string(),
             build_pumpfun_meme_parser,
         );
+        meme_parsers.insert(
+            dex_programs::PUMP_SWAP.to_string(),
+            build_pumpswap_meme_parser,
+        );
+        meme_parsers.insert(
+            dex_programs::METEORA_DBC.to_string(),
+            build_meteora_dbc_meme_parser,
+        );
+        meme_parsers.insert(
+            dex_programs::RAYDIUM_LAUNCHLAB.to_string(),
+            build_raydium_launchlab_meme_parser,
+        );
+        meme_parsers.insert(
+            dex_programs::VIRTUALS.to_string(),
+            build_virtuals_meme_parser,
+        );
+        meme_parsers.insert(dex_programs::BAGS.to_string(), build_bags_meme_parser);
 
         Self {
             trade_parsers,
             liquidity_parsers,
             transfer_parsers,
             meme_parsers,
+            price_provider: None,
+            pool_state_provider: None,
+            middlewares: Vec::new(),
+            post_processors: Vec::new(),
+            max_price_deviation: None,
+        }
+    }
+
+    /// Use `provider` to value trades without a SOL leg in `volume_sol`.
+    pub fn with_price_provider(mut self, provider: Arc<dyn PriceProvider>) -> Self {
+        self.price_provider = Some(provider);
+        self
+    }
+
+    /// Use `provider` to attach post-trade pool reserves when parsing blocks.
+    pub fn with_pool_state_provider(mut self, provider: Arc<dyn PoolStateProvider>) -> Self {
+        self.pool_state_provider = Some(provider);
+        self
+    }
+
+    /// Run `middleware` on each transaction before parsing, after those already added.
+    pub fn with_middleware(mut self, middleware: NormalizeMiddleware) -> Self {
+        self.middlewares.push(middleware);
+        self
+    }
+
+    /// Run `post_processor` on each result, failed parses included, after those already added.
+    pub fn with_post_processor(mut self, post_processor: PostProcessor) -> Self {
+        self.post_processors.push(post_processor);
+        self
+    }
+
+    /// Flag implausible trades of parsed blocks as `suspect`: trades moving more than their
+    /// pool holds (with a pool state provider) and trades priced more than
+    /// `max_price_deviation` (0.1 for 10%) away from the other trades on their pool in the slot.
+    pub fn with_anomaly_checks(mut self, max_price_deviation: f64) -> Self {
+        self.max_price_deviation = Some(max_price_deviation);
+        self
+    }
+
+    /// Whether `config.sample_rate` keeps the transaction; skipped ones are left out of the
+    /// block result.
+    fn in_sample(config: &ParseConfig, signature: &str) -> bool {
+        let Some(rate) = config.sample_rate else {
+            return true;
+        };
+        // Struct literals skip `with_sample_rate`, so clamp here too; NaN samples nothing.
+        if rate.is_nan() {
+            return false;
+        }
+        let rate = rate.clamp(0.0, 1.0);
+        if rate >= 1.0 {
+            return true;
+        }
+        // FNV-1a, spelled out so the sample does not move with the std hasher.
+        let hash = signature
+            .bytes()
+            .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+                (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+            });
+        (hash as f64 / u64::MAX as f64) < rate
+    }
+
+    /// Block-wide passes over the parsed transactions of a block.
+    fn annotate_block(&self, results: &mut [ParseResult], config: &ParseConfig) {
+        if let Some(provider) = self.pool_state_provider.as_deref() {
+            attach_pool_states(results, provider);
+        }
+        if let Some(max_price_deviation) = self.max_price_deviation {
+            flag_suspect_trades(results, max_price_deviation);
+        }
+        if config.detect_wash_trades {
+            flag_wash_trades(results);
+        }
+        group_bundles(results);
+        // Transactions without a stable pair of their own are priced off the rest of the block.
+        let block_price = StablePairPrice::from_trades(results.iter().flat_map(|r| &r.trades));
+        if let Some(sol_usd) = block_price.sol_usd() {
+            for result in results.iter_mut() {
+                fill_usd_values(&mut result.trades, &mut result.meme_events, Some(sol_usd));
+                if let Some(aggregate) = result.aggregate_trade.as_mut() {
+                    fill_usd_values(std::slice::from_mut(aggregate), &mut [], Some(sol_usd));
+                }
+            }
         }
     }
 
@@ -129,13 +387,18 @@ impl DexParser {
         tx: SolanaTransaction,
         config: ParseConfig,
         parse_type: ParseType,
+        timer: &mut StageTimer,
     ) -> Result<ParseResult, ParserError> {
         let adapter = TransactionAdapter::new(tx.clone(), config.clone());
         let utils = TransactionUtils::new(adapter.clone());
         let classifier = InstructionClassifier::new(&adapter);
         let dex_info = utils.get_dex_info(&classifier);
         let transfer_actions = utils.get_transfer_actions();
+        // Order-book cranks and settlements move funds without trading, which the
+        // unknown-DEX heuristic would otherwise read as swaps.
+        let swap_transfers = SettlementFlows::new(&adapter).exclude(&transfer_actions);
         let all_program_ids = classifier.get_all_program_ids();
+        let failed_cpis = failed_cpis(&adapter);
 
         let mut result = ParseResult::new();
         result.slot = adapter.slot();
@@ -143,8 +406,14 @@ impl DexParser {
         result.signature = adapter.signature().to_string();
         result.signer = adapter.signers().to_vec();
         result.compute_units = adapter.compute_units();
+        result.compute_budget = compute_budget(&adapter);
         result.tx_status = adapter.tx_status();
-        result.fee = adapter.fee();
+        result.error_reason = error_reason(&adapter);
+        if config.include_legacy_fee {
+            result.fee = adapter.fee();
+        } else {
+            result.fee.amount.clear();
+        }
 
         if let Some(change) = adapter.signer_sol_balance_change().cloned() {
             result.sol_balance_change = Some(change);
@@ -152,6 +421,48 @@ impl DexParser {
         if let Some(token_change) = adapter.signer_token_balance_changes() {
             result.token_balance_change = token_change.clone();
         }
+        if config.include_balance_maps {
+            result.sol_balance_changes = Some(adapter.get_account_sol_balance_changes(true));
+            result.token_balance_changes = Some(adapter.get_account_token_balance_changes(true));
+        }
+        let signer_flow = adapter.get_wallet_flow(&adapter.signer());
+        result.signer_sol_change = signer_flow.sol.map_or(0, |sol| sol.change);
+        result.signer_token_changes = signer_flow
+            .tokens
+            .into_iter()
+            .map(|(mint, change)| (mint, change.change))
+            .collect();
+        if config.include_wallet_flows {
+            result.wallet_flows = Some(adapter.get_wallet_flows());
+        }
+        if config.include_token_account_owners {
+            result.token_account_owners = Some(
+                adapter
+                    .spl_token_map()
+                    .iter()
+                    .map(|(account, info)| {
+                        let owner = TokenAccountOwner {
+                            owner: adapter.get_token_account_owner(account),
+                            mint: info.mint.clone(),
+                        };
+                        (account.clone(), owner)
+                    })
+                    .collect(),
+            );
+        }
+
+        result.programs_invoked = all_program_ids
+            .iter()
+            .map(|program_id| {
+                let (outer_count, inner_count) = classifier.count_instructions(program_id);
+                ProgramInvocation {
+                    program_id: program_id.clone(),
+                    name: config.program_name(program_id),
+                    outer_count,
+                    inner_count,
+                }
+            })
+            .collect();
 
         if let Some(program_filter) = config.program_ids.as_ref() {
             if !program_filter.iter().any(|id| all_program_ids.contains(id)) {
@@ -160,6 +471,8 @@ impl DexParser {
             }
         }
 
+        timer.lap("prepare");
+
         if parse_type.includes_trades() {
             for program_id in &all_program_ids {
                 if let Some(filter) = config.program_ids.as_ref() {
@@ -173,12 +486,19 @@ impl DexParser {
                     }
                 }
 
+                if config
+                    .protocol(program_id)
+                    .is_some_and(|overrides| !overrides.trades)
+                {
+                    continue;
+                }
+
                 let classified_instructions = classifier.get_instructions(program_id);
                 if let Some(builder) = self.trade_parsers.get(program_id) {
                     let mut program_info = dex_info.clone();
                     program_info.program_id = Some(program_id.clone());
                     if program_info.amm.is_none() {
-                        program_info.amm = Some(dex_program_names::name(program_id).to_string());
+                        program_info.amm = Some(config.program_name(program_id));
                     }
                     let mut parser = builder(
                         adapter.clone(),
@@ -187,28 +507,61 @@ impl DexParser {
                         classified_instructions.clone(),
                     );
                     result.trades.extend(parser.process_trades());
-                } else if config.try_unknown_dex {
-                    if let Some(transfers) = transfer_actions.get(program_id) {
+                } else if config.try_unknown_dex
+                    && !helper_programs::ALL.contains(&program_id.as_str())
+                    && !bridge_programs::ALL.contains(&program_id.as_str())
+                    && !is_bot_program(&config, program_id)
+                {
+                    if let Some(transfers) = swap_transfers.get(program_id) {
                         if transfers.len() >= 2
                             && transfers
                                 .iter()
-                                .any(|transfer| adapter.is_supported_token(transfer))
+                                .any(|transfer| adapter.is_supported_token(&transfer.info.mint))
                         {
                             let mut program_info = dex_info.clone();
                             program_info.program_id = Some(program_id.clone());
                             if program_info.amm.is_none() {
-                                program_info.amm =
-                                    Some(dex_program_names::name(program_id).to_string());
+                                program_info.amm = Some(config.program_name(program_id));
                             }
-                            if let Some(trade) = utils.process_swap_data(transfers, &program_info) {
+                            if let Some(trade) =
+                                utils.process_swap_data(&swap_transfers, program_id, &program_info)
+                            {
                                 let trade =
-                                    utils.attach_token_transfer_info(trade, &transfer_actions);
+                                    utils.attach_token_transfer_info(trade, &swap_transfers);
                                 result.trades.push(trade);
                             }
                         }
                     }
                 }
             }
+            // Swaps decoded from rolled-back CPIs (router fallback paths) never executed.
+            let reverted: HashSet<&str> = failed_cpis
+                .iter()
+                .flat_map(|cpi| &cpi.reverted)
+                .map(String::as_str)
+                .collect();
+            result
+                .trades
+                .retain(|trade| !reverted.contains(trade.idx.as_str()));
+            if config.parse_failed {
+                result
+                    .trades
+                    .extend(attempted_trades(&adapter).into_iter().filter(|trade| {
+                        let program_id = trade.program_id.as_deref().unwrap_or_default();
+                        config
+                            .program_ids
+                            .as_ref()
+                            .is_none_or(|filter| filter.iter().any(|id| id == program_id))
+                            && !config
+                                .ignore_program_ids
+                                .as_ref()
+                                .is_some_and(|ignore| ignore.iter().any(|id| id == program_id))
+                            && config
+                                .protocol(program_id)
+                                .is_none_or(|overrides| overrides.trades)
+                    }));
+            }
+            timer.lap("trades");
         }
 
         if parse_type.includes_liquidity() {
@@ -223,6 +576,12 @@ impl DexParser {
                         continue;
                     }
                 }
+                if config
+                    .protocol(program_id)
+                    .is_some_and(|overrides| !overrides.liquidity)
+                {
+                    continue;
+                }
                 if let Some(builder) = self.liquidity_parsers.get(program_id) {
                     let classified_instructions = classifier.get_instructions(program_id);
                     let mut parser = builder(
@@ -233,6 +592,9 @@ impl DexParser {
                     result.liquidities.extend(parser.process_liquidity());
                 }
             }
+            result.liquidities =
+                utils.attach_lp_safety_flags(std::mem::take(&mut result.liquidities));
+            timer.lap("liquidity");
         }
 
         if parse_type == ParseType::All {
@@ -247,13 +609,33 @@ impl DexParser {
                         continue;
                     }
                 }
+                if config
+                    .protocol(program_id)
+                    .is_some_and(|overrides| !overrides.meme_events)
+                {
+                    continue;
+                }
                 if let Some(builder) = self.meme_parsers.get(program_id) {
                     let mut parser = builder(adapter.clone(), transfer_actions.clone());
                     result.meme_events.extend(parser.process_events());
                 }
             }
+            result.meme_events =
+                utils.attach_authority_renounce_flags(std::mem::take(&mut result.meme_events));
+            result.meme_events = attribute_launchpads(
+                std::mem::take(&mut result.meme_events),
+                &config.launchpad_platforms,
+            );
+            result.migrations = detect_migrations(
+                &adapter,
+                &classifier,
+                &result.meme_events,
+                &result.liquidities,
+            );
+            timer.lap("memeEvents");
         }
 
+        let bridges = BridgeFlows::new(&adapter, &classifier, &transfer_actions);
         if result.trades.is_empty()
             && result.liquidities.is_empty()
             && parse_type.includes_transfer()
@@ -271,25 +653,66 @@ impl DexParser {
                 }
             }
             if result.transfers.is_empty() {
-                result
-                    .transfers
-                    .extend(transfer_actions.values().flatten().cloned());
+                result.transfers.extend(
+                    transfer_actions
+                        .values()
+                        .flatten()
+                        .filter(|transfer| !bridges.contains(transfer))
+                        .cloned(),
+                );
             }
+            result
+                .transfers
+                .retain(|transfer| !is_tip_transfer(transfer));
         }
+        result.tip = jito_tip(&adapter);
+        if parse_type.includes_transfer() {
+            result.bridges = bridges.into_events();
+        }
+        timer.lap("transfers");
 
         if !result.trades.is_empty() {
             let mut seen = HashSet::new();
             result
                 .trades
                 .retain(|trade| seen.insert((trade.signature.clone(), trade.idx.clone())));
-            result.trades.sort_by(|a, b| a.idx.cmp(&b.idx));
+            // Parsers run per program, so order the merged trades by instruction position;
+            // a plain string sort would put "10-0" before "2-0".
+            result.trades.sort_by(|a, b| compare_idx(&a.idx, &b.idx));
+            utils.attach_routes(&classifier, &mut result.trades);
+            attach_bot_fees(&adapter, &mut result.trades);
+            result.arbitrage = detect_arbitrage(adapter.signer(), &result.trades);
+            fill_volume_sol(&mut result.trades, self.price_provider.as_deref());
+            let sol_usd = StablePairPrice::from_trades(&result.trades)
+                .sol_usd()
+                .or_else(|| {
+                    let provider = self.price_provider.as_deref()?;
+                    provider.sol_price_usd(result.timestamp)
+                });
+            fill_usd_values(&mut result.trades, &mut result.meme_events, sol_usd);
             if adapter.config().aggregate_trades {
-                if let Some(last_trade) = result.trades.last().cloned() {
-                    result.aggregate_trade = Some(utils.attach_trade_fee(last_trade));
+                if let Some(net_trade) = aggregate_route(&result.trades) {
+                    let mut aggregate = utils.attach_trade_fee(net_trade);
+                    fill_usd_values(std::slice::from_mut(&mut aggregate), &mut [], sol_usd);
+                    aggregate.routing_improvement_bps =
+                        routing_improvement_bps(&adapter, &result.trades);
+                    result.aggregate_trade = Some(aggregate);
                 }
             }
         }
 
+        if config.classify_flow {
+            result.flow_label = classify_flow(&adapter, &result.trades, &result.meme_events);
+        }
+        if config.detect_wash_trades {
+            flag_wash_trades(std::slice::from_mut(&mut result));
+        }
+        if config.include_failed_cpis {
+            result.failed_cpis = failed_cpis;
+        }
+        result.fees = fee_breakdown(&adapter, &result);
+        timer.lap("enrich");
+
         Ok(result)
     }
 
@@ -299,9 +722,19 @@ impl DexParser {
         config: Option<ParseConfig>,
         parse_type: ParseType,
     ) -> ParseResult {
-        let config = config.unwrap_or_default();
-        match self.try_parse(tx, config.clone(), parse_type) {
-            Ok(result) => result,
+        let mut config = config.unwrap_or_default();
+        let mut timer = StageTimer::new(config.record_timings);
+        let mut tx = tx;
+        for middleware in &self.middlewares {
+            middleware(&mut tx);
+        }
+        let restored = canonicalize_program_ids(&mut tx, &mut config);
+        timer.lap("normalize");
+        let mut result = match self.try_parse(tx, config.clone(), parse_type, &mut timer) {
+            Ok(mut result) => {
+                restore_program_ids(&mut result, &restored);
+                result
+            }
             Err(err) => {
                 if config.throw_error {
                     tracing::error!("parser error: {err}");
@@ -311,7 +744,13 @@ impl DexParser {
                 result.msg = Some(err.to_string());
                 result
             }
+        };
+        for post_processor in &self.post_processors {
+            post_processor(&mut result);
         }
+        timer.lap("postProcess");
+        result.timings = timer.finish();
+        result
     }
 
     pub fn parse_trades(
@@ -345,6 +784,98 @@ impl DexParser {
         self.parse_with_classifier(tx, config, ParseType::All)
     }
 
+    /// Parse the `simulateTransaction` result of `transaction`, predicting its trades and
+    /// fees before it is sent; see [`rpc::transaction_from_simulation`] for what the
+    /// simulation and `context` must provide.
+    pub fn parse_simulation(
+        &self,
+        transaction: VersionedTransaction,
+        simulation: &RpcSimulateTransactionResult,
+        context: &SimulationContext,
+        config: Option<ParseConfig>,
+    ) -> anyhow::Result<ParseResult> {
+        let tx = rpc::transaction_from_simulation(transaction, simulation, context)?;
+        Ok(self.parse_all(tx, config))
+    }
+
+    /// Fetch a transaction by signature and parse it, retrying failed requests with the
+    /// default [`RetryPolicy`].
+    #[cfg(feature = "rpc")]
+    pub async fn parse_signature(
+        &self,
+        rpc_url: &str,
+        signature: &str,
+    ) -> anyhow::Result<ParseResult> {
+        self.parse_signature_with(rpc_url, signature, None, &RetryPolicy::default())
+            .await
+    }
+
+    /// [`DexParser::parse_signature`] with a parse config and retry policy.
+    #[cfg(feature = "rpc")]
+    pub async fn parse_signature_with(
+        &self,
+        rpc_url: &str,
+        signature: &str,
+        config: Option<ParseConfig>,
+        retry: &RetryPolicy,
+    ) -> anyhow::Result<ParseResult> {
+        let tx = rpc::fetch_transaction_with_retry(rpc_url, signature, retry).await?;
+        Ok(self.parse_all(tx, config))
+    }
+
+    /// Fetch the block at `slot` with `getBlock` and parse it, vote transactions aside,
+    /// retrying failed requests with the default [`RetryPolicy`]. `None` when the slot was
+    /// skipped.
+    #[cfg(feature = "rpc")]
+    pub async fn fetch_and_parse_block(
+        &self,
+        rpc_url: &str,
+        slot: u64,
+    ) -> anyhow::Result<Option<BlockParseResult>> {
+        self.fetch_and_parse_block_with(rpc_url, slot, None, &RetryPolicy::default())
+            .await
+    }
+
+    /// [`DexParser::fetch_and_parse_block`] with a parse config and retry policy.
+    #[cfg(feature = "rpc")]
+    pub async fn fetch_and_parse_block_with(
+        &self,
+        rpc_url: &str,
+        slot: u64,
+        config: Option<ParseConfig>,
+        retry: &RetryPolicy,
+    ) -> anyhow::Result<Option<BlockParseResult>> {
+        let Some(block) = rpc::fetch_block_with_retry(rpc_url, slot, retry).await? else {
+            return Ok(None);
+        };
+        Ok(Some(self.parse_block_filtered(&block, config, |tx| {
+            !rpc::is_vote_transaction(tx)
+        })))
+    }
+
+    /// Fetch and parse the blocks of `slots` as a stream in slot order, with up to
+    /// `options.concurrency` blocks in flight. Skipped slots are left out; a block that
+    /// cannot be fetched yields its error and the stream goes on with the next slot.
+    #[cfg(feature = "rpc")]
+    pub fn backfill<'a>(
+        &'a self,
+        rpc_url: &'a str,
+        slots: Range<u64>,
+        options: &'a BackfillOptions,
+    ) -> impl Stream<Item = anyhow::Result<BlockParseResult>> + 'a {
+        stream::iter(slots)
+            .map(move |slot| {
+                self.fetch_and_parse_block_with(
+                    rpc_url,
+                    slot,
+                    options.config.clone(),
+                    &options.retry,
+                )
+            })
+            .buffered(options.concurrency.max(1))
+            .filter_map(|result| async move { result.transpose() })
+    }
+
     pub fn parse_block_raw(
         &self,
         transactions: &[Value],
@@ -352,11 +883,21 @@ impl DexParser {
     ) -> Result<BlockParseResult, ParserError> {
         let cfg = config.unwrap_or_default();
         let mut results = Vec::with_capacity(transactions.len());
-        for tx_value in transactions {
+        for (transaction_index, tx_value) in transactions.iter().enumerate() {
+            let signature = tx_value.get("signature").and_then(Value::as_str);
+            if !Self::in_sample(&cfg, signature.unwrap_or_default()) {
+                continue;
+            }
             let tx = SolanaTransaction::from_value(tx_value, &cfg)
                 .map_err(|err| ParserError::generic(err.to_string()))?;
-            results.push(self.parse_all(tx, Some(cfg.clone())));
+            let mut result = self.parse_all(tx, Some(cfg.clone()));
+            result.block_context = Some(BlockContext {
+                transaction_index,
+                ..BlockContext::default()
+            });
+            results.push(result);
         }
+        self.annotate_block(&mut results, &cfg);
         Ok(BlockParseResult {
             slot: 0,
             timestamp: None,
@@ -368,12 +909,33 @@ impl DexParser {
         &self,
         block: &SolanaBlock,
         config: Option<ParseConfig>,
+    ) -> BlockParseResult {
+        self.parse_block_filtered(block, config, |_| true)
+    }
+
+    /// Parse the transactions of `block` that `keep` accepts, with their position in the
+    /// whole block.
+    fn parse_block_filtered(
+        &self,
+        block: &SolanaBlock,
+        config: Option<ParseConfig>,
+        keep: impl Fn(&SolanaTransaction) -> bool,
     ) -> BlockParseResult {
         let cfg = config.unwrap_or_default();
         let mut results = Vec::with_capacity(block.transactions.len());
-        for tx in &block.transactions {
-            results.push(self.parse_all(tx.clone(), Some(cfg.clone())));
+        for (transaction_index, tx) in block.transactions.iter().enumerate() {
+            if !keep(tx) || !Self::in_sample(&cfg, &tx.signature) {
+                continue;
+            }
+            let mut result = self.parse_all(tx.clone(), Some(cfg.clone()));
+            result.block_context = Some(BlockContext {
+                epoch: block.epoch,
+                leader: block.leader.clone(),
+                transaction_index,
+            });
+            results.push(result);
         }
+        self.annotate_block(&mut results, &cfg);
         BlockParseResult {
             slot: block.slot,
             timestamp: block.block_time,
@@ -398,11 +960,11 @@ mod tests {
     use std::collections::HashMap;
 
     use super::*;
-    use crate::config::ParseConfig;
+    use crate::config::{NetworkProfile, ParseConfig};
     use crate::core::constants::dex_programs;
     use crate::types::{
-        BalanceChange, SolanaInstruction, TokenAmount, TransactionMeta, TransactionStatus,
-        TransferData,
+        BalanceChange, InnerInstruction, SolanaInstruction, TokenAmount, TokenBalance,
+        TransactionMeta, TransactionStatus, TransferData,
     };
 
     fn sample_transaction() -> SolanaTransaction {
@@ -445,6 +1007,7 @@ mod tests {
                 program_id: dex_programs::JUPITER.to_string(),
                 accounts: vec!["BASE".to_string(), "QUOTE".to_string()],
                 data: "swap".to_string(),
+                stack_height: None,
             }],
             inner_instructions: Vec::new(),
             transfers: vec![
@@ -458,6 +1021,7 @@ mod tests {
                         mint: "BASE".to_string(),
                         source: "user-token".to_string(),
                         token_amount: TokenAmount::new("1000000", 6, Some(1.0)),
+                        fee_amount: None,
                         source_balance: None,
                         source_pre_balance: None,
                         destination_balance: None,
@@ -466,7 +1030,7 @@ mod tests {
                     },
                     idx: "0-0".to_string(),
                     timestamp: 1_234_567,
-                    signature: "sample-signature".to_string(),
+                    signature: "sample-signature".into(),
                     is_fee: false,
                 },
                 TransferData {
@@ -479,6 +1043,7 @@ mod tests {
                         mint: "QUOTE".to_string(),
                         source: "pool-token".to_string(),
                         token_amount: TokenAmount::new("2000000", 6, Some(2.0)),
+                        fee_amount: None,
                         source_balance: None,
                         source_pre_balance: None,
                         destination_balance: None,
@@ -487,7 +1052,7 @@ mod tests {
                     },
                     idx: "0-1".to_string(),
                     timestamp: 1_234_567,
-                    signature: "sample-signature".to_string(),
+                    signature: "sample-signature".into(),
                     is_fee: false,
                 },
             ],
@@ -499,6 +1064,8 @@ mod tests {
                 status: TransactionStatus::Success,
                 sol_balance_changes: sol_changes,
                 token_balance_changes: token_changes,
+                log_messages: Vec::new(),
+                err: None,
             },
         }
     }
@@ -519,6 +1086,164 @@ mod tests {
         assert!(result.sol_balance_change.is_some());
     }
 
+    #[test]
+    fn events_share_one_signature_allocation() {
+        let result = DexParser::new().parse_all(sample_transaction(), None);
+
+        let trade = &result.trades[0];
+        let aggregate = result.aggregate_trade.as_ref().expect("aggregate trade");
+        assert!(Arc::ptr_eq(&trade.signature, &aggregate.signature));
+        let json = serde_json::to_value(trade).expect("serialize trade");
+        assert_eq!(json["signature"], "sample-signature");
+    }
+
+    struct FixedPrice;
+
+    impl PriceProvider for FixedPrice {
+        fn price_in_sol(&self, mint: &str, _timestamp: u64) -> Option<f64> {
+            (mint == "QUOTE").then_some(0.5)
+        }
+
+        fn sol_price_usd(&self, _timestamp: u64) -> Option<f64> {
+            Some(150.0)
+        }
+    }
+
+    #[test]
+    fn values_non_sol_trades_with_price_provider() {
+        let parser = DexParser::new();
+        assert_eq!(
+            parser.parse_trades(sample_transaction(), None)[0].volume_sol,
+            None
+        );
+
+        let parser = DexParser::new().with_price_provider(Arc::new(FixedPrice));
+        let result = parser.parse_all(sample_transaction(), None);
+        assert_eq!(result.trades[0].volume_sol, Some(1.0));
+        assert_eq!(result.trades[0].volume_usd, Some(150.0));
+        // Two QUOTE tokens bought for 150 USD.
+        assert_eq!(result.trades[0].price_usd, Some(75.0));
+        let aggregate = result.aggregate_trade.expect("aggregate trade");
+        assert_eq!(aggregate.volume_sol, Some(1.0));
+        assert_eq!(aggregate.volume_usd, Some(150.0));
+    }
+
+    #[test]
+    fn embeds_balance_maps_when_enabled() {
+        let parser = DexParser::new();
+        let result = parser.parse_all(sample_transaction(), None);
+        assert!(result.sol_balance_changes.is_none());
+        assert!(result.token_balance_changes.is_none());
+
+        let config = ParseConfig {
+            include_balance_maps: true,
+            ..ParseConfig::default()
+        };
+        let result = parser.parse_all(sample_transaction(), Some(config));
+        let sol_changes = result.sol_balance_changes.expect("sol balance map");
+        assert_eq!(sol_changes["user"].change, -5_000);
+        assert!(result.token_balance_changes.is_some());
+    }
+
+    #[test]
+    fn protocol_overrides_disable_program_trades() {
+        let config: ParseConfig = serde_json::from_value(serde_json::json!({
+            "protocolOverrides": { (dex_programs::JUPITER): { "trades": false } },
+        }))
+        .expect("config");
+        let overrides = config.protocol(dex_programs::JUPITER).expect("overrides");
+        assert!(overrides.liquidity && overrides.meme_events);
+
+        let result = DexParser::new().parse_all(sample_transaction(), Some(config));
+        assert!(result.trades.is_empty());
+        assert!(result.aggregate_trade.is_none());
+    }
+
+    #[test]
+    fn parses_forked_program_through_alias() {
+        let mut tx = sample_transaction();
+        tx.instructions[0].program_id = "FORK".to_string();
+        tx.transfers.iter_mut().for_each(|transfer| {
+            transfer.program_id = "FORK".to_string();
+        });
+        let mut config = ParseConfig {
+            program_ids: Some(vec!["FORK".to_string()]),
+            ..ParseConfig::default()
+        };
+        config
+            .program_aliases
+            .insert("FORK".to_string(), dex_programs::JUPITER.to_string());
+
+        let result = DexParser::new().parse_all(tx, Some(config));
+        assert_eq!(result.trades.len(), 1);
+        assert_eq!(result.trades[0].program_id.as_deref(), Some("FORK"));
+        assert_eq!(result.trades[0].amm.as_deref(), Some("Jupiter"));
+        assert_eq!(result.programs_invoked[0].program_id, "FORK");
+    }
+
+    #[test]
+    fn summarises_invoked_programs() {
+        let mut tx = sample_transaction();
+        let mut inner = tx.instructions[0].clone();
+        inner.program_id = dex_programs::RAYDIUM.to_string();
+        tx.inner_instructions.push(InnerInstruction {
+            index: 0,
+            instructions: vec![inner.clone(), inner],
+        });
+
+        let result = DexParser::new().parse_all(tx, None);
+        assert_eq!(
+            result.programs_invoked,
+            vec![
+                ProgramInvocation {
+                    program_id: dex_programs::JUPITER.to_string(),
+                    name: "Jupiter".to_string(),
+                    outer_count: 1,
+                    inner_count: 0,
+                },
+                ProgramInvocation {
+                    program_id: dex_programs::RAYDIUM.to_string(),
+                    name: "Raydium".to_string(),
+                    outer_count: 0,
+                    inner_count: 2,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn embeds_token_account_owners_when_enabled() {
+        let mut tx = sample_transaction();
+        tx.post_token_balances.push(TokenBalance {
+            account: "user-token".to_string(),
+            mint: "BASE".to_string(),
+            owner: Some("user".to_string()),
+            ui_token_amount: TokenAmount::new("0", 6, Some(0.0)),
+        });
+
+        let parser = DexParser::new();
+        assert!(parser
+            .parse_all(tx.clone(), None)
+            .token_account_owners
+            .is_none());
+
+        let config = ParseConfig {
+            include_token_account_owners: true,
+            ..ParseConfig::default()
+        };
+        let owners = parser
+            .parse_all(tx, Some(config))
+            .token_account_owners
+            .expect("token account owners");
+        assert_eq!(
+            owners["user-token"],
+            TokenAccountOwner {
+                owner: Some("user".to_string()),
+                mint: "BASE".to_string(),
+            }
+        );
+    }
+
     #[test]
     fn falls_back_to_transfers_when_no_trade() {
         let mut tx = sample_transaction();
@@ -534,6 +1259,23 @@ mod tests {
             ignore_program_ids: None,
             aggregate_trades: false,
             throw_error: false,
+            include_balance_maps: false,
+            include_token_account_owners: false,
+            include_wallet_flows: false,
+            classify_flow: false,
+            detect_wash_trades: false,
+            include_failed_cpis: false,
+            include_legacy_fee: true,
+            record_timings: false,
+            use_logs_fallback: false,
+            parse_failed: false,
+            sample_rate: None,
+            launchpad_platforms: HashMap::new(),
+            trading_bots: Vec::new(),
+            protocol_overrides: HashMap::new(),
+            program_aliases: HashMap::new(),
+            network: NetworkProfile::Mainnet,
+            program_tables: Default::default(),
         };
         let transfers = parser.parse_transfers(tx.clone(), Some(config.clone()));
         assert_eq!(transfers.len(), 2);
@@ -0,0 +1,140 @@
+//! Human-readable reasons of failed transactions.
+
+use solana_sdk::instruction::InstructionError;
+use solana_sdk::transaction::TransactionError;
+
+use crate::config::ParseConfig;
+use crate::core::constants::{dex_programs, system_programs, token_programs};
+use crate::core::transaction_adapter::TransactionAdapter;
+use crate::types::TransactionStatus;
+
+/// Custom error codes of supported programs that matter to traders, with their messages.
+const KNOWN_ERRORS: [(&str, u32, &str); 12] = [
+    (dex_programs::JUPITER, 6000, "Empty route"),
+    (dex_programs::JUPITER, 6001, "Slippage tolerance exceeded"),
+    (
+        dex_programs::PUMP_FUN,
+        6002,
+        "Slippage: too much SOL required to buy the given amount of tokens",
+    ),
+    (
+        dex_programs::PUMP_FUN,
+        6003,
+        "Slippage: too little SOL received to sell the given amount of tokens",
+    ),
+    (
+        dex_programs::PUMP_FUN,
+        6005,
+        "Bonding curve complete, liquidity migrated",
+    ),
+    (dex_programs::PUMP_SWAP, 6004, "Exceeded slippage"),
+    (dex_programs::RAYDIUM, 30, "Exceeds desired slippage limit"),
+    (system_programs::SYSTEM, 0, "Account already in use"),
+    (system_programs::SYSTEM, 1, "Insufficient SOL"),
+    (token_programs::TOKEN, 1, "Insufficient funds"),
+    (token_programs::TOKEN_2022, 1, "Insufficient funds"),
+    (token_programs::TOKEN, 17, "Account is frozen"),
+];
+
+/// Why a failed transaction failed; `None` for transactions that did not fail.
+///
+/// Instruction errors name the failing instruction. A custom error code belongs to the
+/// program whose invocation failed first in the logs, which may be a CPI of the instruction;
+/// it is looked up among the known codes of supported programs, then in the `AnchorError`
+/// line that program logged, and is reported as is otherwise.
+pub fn error_reason(adapter: &TransactionAdapter) -> Option<String> {
+    if adapter.tx_status() != TransactionStatus::Failed {
+        return None;
+    }
+    let Some(err) = adapter.tx_error() else {
+        // No structured error from the source: the logs may still tell.
+        return failed_program(adapter.log_messages())
+            .and_then(|program_id| anchor_message(adapter.log_messages(), program_id, None));
+    };
+    let TransactionError::InstructionError(index, error) = err else {
+        return Some(err.to_string());
+    };
+    let reason = match error {
+        InstructionError::Custom(code) => {
+            let program_id = failed_program(adapter.log_messages()).or_else(|| {
+                adapter
+                    .instructions()
+                    .get(*index as usize)
+                    .map(|instruction| instruction.program_id.as_str())
+            });
+            custom_error(adapter, program_id, *code)
+        }
+        other => other.to_string(),
+    };
+    Some(format!("instruction {index} failed: {reason}"))
+}
+
+fn custom_error(adapter: &TransactionAdapter, program_id: Option<&str>, code: u32) -> String {
+    let Some(program_id) = program_id else {
+        return format!("custom program error {code:#x}");
+    };
+    let message = KNOWN_ERRORS
+        .iter()
+        .find(|(program, known, _)| *program == program_id && *known == code)
+        .map(|(_, _, message)| message.to_string())
+        .or_else(|| anchor_message(adapter.log_messages(), program_id, Some(code)));
+    let program = program_name(adapter.config(), program_id);
+    match message {
+        Some(message) => format!("{program}: {message} (error {code})"),
+        None => format!("{program}: custom program error {code:#x}"),
+    }
+}
+
+/// Program of the first `Program <id> failed` line, the innermost failing invocation.
+fn failed_program(logs: &[String]) -> Option<&str> {
+    logs.iter().find_map(|line| {
+        let (program_id, event) = line.strip_prefix("Program ")?.split_once(' ')?;
+        event.starts_with("failed").then_some(program_id)
+    })
+}
+
+/// Message of the `AnchorError` `program_id` logged, for error number `code` when given.
+///
+/// Anchor logs `AnchorError ... Error Code: <name>. Error Number: <code>. Error Message:
+/// <message>.` from within the failing invocation.
+fn anchor_message(logs: &[String], program_id: &str, code: Option<u32>) -> Option<String> {
+    let mut stack: Vec<&str> = Vec::new();
+    for line in logs {
+        if let Some(log) = line.strip_prefix("Program log: AnchorError") {
+            if stack.last() != Some(&program_id) {
+                continue;
+            }
+            if let Some(code) = code {
+                if !log.contains(&format!("Error Number: {code}.")) {
+                    continue;
+                }
+            }
+            let (_, message) = log.split_once("Error Message: ")?;
+            return Some(message.trim_end_matches('.').to_string());
+        }
+        let Some((invoked, event)) = line
+            .strip_prefix("Program ")
+            .and_then(|rest| rest.split_once(' '))
+        else {
+            continue;
+        };
+        if event.starts_with("invoke [") {
+            stack.push(invoked);
+        } else if event == "success" || event.starts_with("failed") {
+            stack.pop();
+        }
+    }
+    None
+}
+
+fn program_name(config: &ParseConfig, program_id: &str) -> String {
+    match program_id {
+        system_programs::SYSTEM => "System".to_string(),
+        token_programs::TOKEN => "Token".to_string(),
+        token_programs::TOKEN_2022 => "Token2022".to_string(),
+        _ => match config.program_name(program_id) {
+            name if name == "Unknown DEX" => program_id.to_string(),
+            name => name,
+        },
+    }
+}
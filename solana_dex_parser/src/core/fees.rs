@@ -0,0 +1,80 @@
+//! Breakdown of the fees a transaction paid.
+
+use crate::core::amounts::convert_to_ui_amount;
+use crate::core::constants::tokens::SOL_DECIMALS;
+use crate::core::transaction_adapter::TransactionAdapter;
+use crate::types::{FeeBreakdown, FeeInfo, ParseResult, TokenAmount, TradeInfo};
+
+fn lamports(amount: Option<&TokenAmount>) -> u64 {
+    amount
+        .and_then(|amount| amount.amount.parse().ok())
+        .unwrap_or(0)
+}
+
+fn sol(lamports: u64) -> TokenAmount {
+    TokenAmount::new(
+        lamports.to_string(),
+        SOL_DECIMALS,
+        Some(convert_to_ui_amount(lamports, SOL_DECIMALS)),
+    )
+}
+
+/// Itemized fees of a trade, or its single fee when the venue does not itemize.
+fn itemized(trade: &TradeInfo) -> &[FeeInfo] {
+    if trade.fees.is_empty() {
+        trade.fee.as_slice()
+    } else {
+        &trade.fees
+    }
+}
+
+/// Fees of `trades` summed per fee type and mint, in order of first appearance.
+pub(crate) fn trade_fees(trades: &[TradeInfo]) -> Vec<FeeInfo> {
+    let mut totals: Vec<(FeeInfo, u128)> = Vec::new();
+    for fee in trades.iter().flat_map(itemized) {
+        let raw = fee.amount_raw.parse::<u128>().unwrap_or(0);
+        match totals
+            .iter_mut()
+            .find(|(total, _)| total.fee_type == fee.fee_type && total.mint == fee.mint)
+        {
+            Some((_, sum)) => *sum += raw,
+            None => totals.push((
+                FeeInfo {
+                    dex: None,
+                    recipient: None,
+                    ..fee.clone()
+                },
+                raw,
+            )),
+        }
+    }
+    totals
+        .into_iter()
+        .map(|(fee, raw)| FeeInfo {
+            amount: convert_to_ui_amount(raw, fee.decimals),
+            amount_raw: raw.to_string(),
+            ..fee
+        })
+        .collect()
+}
+
+/// Split the transaction fee into its base and priority parts, next to the Jito tip and the
+/// fees of the trades in `result`.
+///
+/// The transaction fee already includes the priority fee, so the base fee is what is left of
+/// it; the tip and the trade fees are paid on top.
+pub fn fee_breakdown(adapter: &TransactionAdapter, result: &ParseResult) -> FeeBreakdown {
+    let total = lamports(Some(&adapter.fee()));
+    let priority = lamports(
+        result
+            .compute_budget
+            .as_ref()
+            .and_then(|budget| budget.priority_fee.as_ref()),
+    );
+    FeeBreakdown {
+        base: sol(total.saturating_sub(priority)),
+        priority: sol(priority),
+        tip: sol(lamports(result.tip.as_ref().map(|tip| &tip.amount))),
+        trade_fees: trade_fees(&result.trades),
+    }
+}
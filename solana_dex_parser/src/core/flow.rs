@@ -0,0 +1,142 @@
+use std::collections::HashSet;
+
+use crate::core::compute_budget::compute_budget;
+use crate::core::constants::{dex_programs, jito_tip_accounts, system_programs};
+use crate::core::transaction_adapter::TransactionAdapter;
+use crate::types::{FlowLabel, MemeEvent, TradeInfo, TradeType};
+
+/// Aggregators whose swaps are usually submitted from a wallet UI.
+pub(crate) const ROUTER_PROGRAMS: [&str; 2] = [dex_programs::JUPITER, dex_programs::OKX];
+
+/// Route-level trade of an aggregator, whose hops are reported as trades of their own.
+pub(crate) fn is_router_trade(trade: &TradeInfo) -> bool {
+    trade
+        .program_id
+        .as_deref()
+        .is_some_and(|program| ROUTER_PROGRAMS.contains(&program))
+}
+
+/// Compute unit price (micro-lamports) above which the priority fee counts as aggressive.
+const HIGH_PRIORITY_PRICE: u64 = 1_000_000;
+/// Share of the requested compute limit actually consumed that marks a tuned budget.
+const TIGHT_LIMIT_RATIO: f64 = 0.9;
+/// Score from which a transaction is labelled as bot flow.
+const BOT_SCORE_THRESHOLD: i32 = 3;
+
+/// Label a trading transaction as likely bot or likely human order flow.
+///
+/// Signals that push towards bot flow: an aggressive priority fee, a compute limit tuned to
+/// the units consumed, a Jito tip, swaps executed through an unknown program, round trips
+/// or multi-venue swaps of the same mint, and buys landing in the token's create
+/// transaction. Routing through a known aggregator counts towards human flow.
+/// Returns `None` when the transaction carries no trades.
+pub fn classify_flow(
+    adapter: &TransactionAdapter,
+    trades: &[TradeInfo],
+    meme_events: &[MemeEvent],
+) -> Option<FlowLabel> {
+    let meme_trades = meme_events
+        .iter()
+        .filter(|event| matches!(event.event_type, TradeType::Buy | TradeType::Sell))
+        .count();
+    if trades.is_empty() && meme_trades == 0 {
+        return None;
+    }
+
+    let instructions = adapter.instructions();
+    let mut score = 0;
+
+    let budget = compute_budget(adapter).unwrap_or_default();
+    let (unit_limit, unit_price) = (budget.unit_limit, budget.unit_price);
+    if unit_price.is_some_and(|price| price >= HIGH_PRIORITY_PRICE) {
+        score += 2;
+    }
+    if let Some(limit) = unit_limit.filter(|limit| *limit > 0) {
+        if adapter.compute_units() as f64 / limit as f64 >= TIGHT_LIMIT_RATIO {
+            score += 1;
+        }
+    }
+
+    let tipped = instructions
+        .iter()
+        .chain(
+            adapter
+                .inner_instructions()
+                .iter()
+                .flat_map(|inner| inner.instructions.iter()),
+        )
+        .any(|instruction| {
+            instruction
+                .accounts
+                .iter()
+                .any(|account| jito_tip_accounts::ALL.contains(&account.as_str()))
+        });
+    if tipped {
+        score += 1;
+    }
+
+    let routed = instructions
+        .iter()
+        .any(|instruction| ROUTER_PROGRAMS.contains(&instruction.program_id.as_str()));
+    if routed {
+        score -= 2;
+    }
+
+    let via_unknown_program = trades.iter().any(|trade| {
+        outer_program(adapter, &trade.idx).is_some_and(|program| {
+            program != system_programs::COMPUTE_BUDGET
+                && adapter.config().program_name(program) == "Unknown DEX"
+        })
+    });
+    if via_unknown_program {
+        score += 2;
+    }
+
+    if !routed && (has_round_trip(trades) || venue_count(trades) > 1) {
+        score += 2;
+    }
+
+    let created: HashSet<&str> = meme_events
+        .iter()
+        .filter(|event| event.event_type == TradeType::Create)
+        .map(|event| event.base_mint.as_str())
+        .collect();
+    let sniped = meme_events.iter().any(|event| {
+        event.event_type == TradeType::Buy && created.contains(event.base_mint.as_str())
+    });
+    if sniped {
+        score += 2;
+    }
+
+    Some(if score >= BOT_SCORE_THRESHOLD {
+        FlowLabel::LikelyBot
+    } else {
+        FlowLabel::LikelyHuman
+    })
+}
+
+fn outer_program<'a>(adapter: &'a TransactionAdapter, idx: &str) -> Option<&'a str> {
+    let outer_index = idx.split('-').next()?.parse::<usize>().ok()?;
+    adapter
+        .instructions()
+        .get(outer_index)
+        .map(|instruction| instruction.program_id.as_str())
+}
+
+/// Whether a mint is both bought and sold within the transaction.
+fn has_round_trip(trades: &[TradeInfo]) -> bool {
+    trades.iter().any(|trade| {
+        trades.iter().any(|other| {
+            other.input_token.mint == trade.output_token.mint
+                && other.output_token.mint == trade.input_token.mint
+        })
+    })
+}
+
+fn venue_count(trades: &[TradeInfo]) -> usize {
+    trades
+        .iter()
+        .filter_map(|trade| trade.program_id.as_deref())
+        .collect::<HashSet<_>>()
+        .len()
+}
@@ -1,14 +1,66 @@
 use std::collections::{HashMap, HashSet};
 
+use crate::core::constants::{skip_programs, system_programs};
 use crate::core::transaction_adapter::TransactionAdapter;
+use crate::core::util::decode_instruction_data;
 use crate::types::ClassifiedInstruction;
 
-// ← Подправь путь при необходимости
-use crate::constants::{SKIP_PROGRAM_IDS, SYSTEM_PROGRAMS};
-// Утилита, аналогичная TS getInstructionData(instruction)
-// Должна вернуть байты data инструкции.
-// Подправь путь/сигнатуру при необходимости.
-use crate::core::utils::get_instruction_data;
+/// Узел дерева вызовов: инструкция и CPI, которые она сделала
+#[derive(Clone, Debug, PartialEq)]
+pub struct InvocationNode {
+    pub instruction: ClassifiedInstruction,
+    pub children: Vec<InvocationNode>,
+}
+
+impl InvocationNode {
+    /// Всё поддерево (без самого узла) в порядке исполнения
+    pub fn descendants(&self) -> Vec<ClassifiedInstruction> {
+        let mut out = Vec::new();
+        for child in &self.children {
+            out.push(child.instruction.clone());
+            out.extend(child.descendants());
+        }
+        out
+    }
+
+    /// Узел инструкции `(outer_index, inner_index)` в этом поддереве
+    pub fn find(&self, outer_index: usize, inner_index: Option<usize>) -> Option<&InvocationNode> {
+        if self.instruction.outer_index != outer_index {
+            return None;
+        }
+        if self.instruction.inner_index == inner_index {
+            return Some(self);
+        }
+        self.children
+            .iter()
+            .find_map(|child| child.find(outer_index, inner_index))
+    }
+
+    /// Выполнялась ли инструкция `idx` (`"outer"` или `"outer-inner"`) внутри этого узла.
+    /// Outer-узлу принадлежат все inner его позиции
+    pub fn contains(&self, idx: &str) -> bool {
+        let mut parts = idx.split('-').map(str::parse::<usize>);
+        if parts.next().and_then(Result::ok) != Some(self.instruction.outer_index) {
+            return false;
+        }
+        match (
+            self.instruction.inner_index,
+            parts.next().and_then(Result::ok),
+        ) {
+            (None, _) => true,
+            (Some(_), Some(inner_index)) => self.covers_inner(inner_index),
+            (Some(_), None) => false,
+        }
+    }
+
+    fn covers_inner(&self, inner_index: usize) -> bool {
+        self.instruction.inner_index == Some(inner_index)
+            || self
+                .children
+                .iter()
+                .any(|child| child.covers_inner(inner_index))
+    }
+}
 
 #[derive(Clone, Debug)]
 pub struct InstructionClassifier {
@@ -78,11 +130,11 @@ impl InstructionClassifier {
     pub fn get_all_program_ids(&self) -> Vec<String> {
         self.order
             .iter()
-            .cloned()
             .filter(|pid| {
                 let pid_str = pid.as_str();
-                !SYSTEM_PROGRAMS.contains(&pid_str) && !SKIP_PROGRAM_IDS.contains(&pid_str)
+                !system_programs::ALL.contains(&pid_str) && !skip_programs::ALL.contains(&pid_str)
             })
+            .cloned()
             .collect()
     }
 
@@ -94,6 +146,20 @@ impl InstructionClassifier {
             .unwrap_or_default()
     }
 
+    /// Число внешних и вложенных вызовов program_id: `(outer, inner)`
+    pub fn count_instructions(&self, program_id: &str) -> (usize, usize) {
+        self.instruction_map
+            .get(program_id)
+            .map(|instructions| {
+                let inner = instructions
+                    .iter()
+                    .filter(|ci| ci.inner_index.is_some())
+                    .count();
+                (instructions.len() - inner, inner)
+            })
+            .unwrap_or_default()
+    }
+
     /// Инструкции по нескольким program_id (flatten), как getMultiInstructions в TS
     pub fn get_multi_instructions<S: AsRef<str>>(
         &self,
@@ -117,9 +183,8 @@ impl InstructionClassifier {
     ) -> Option<ClassifiedInstruction> {
         for instructions in self.instruction_map.values() {
             for ci in instructions {
-                // get_instruction_data должен вернуть &[u8] / Vec<u8> с реальными байтами data
-                let data = get_instruction_data(&ci.data);
-                if data.len() >= slice && &data[..slice] == discriminator {
+                let data = decode_instruction_data(&ci.data.data);
+                if data.len() >= slice && data[..slice] == *discriminator {
                     return Some(ci.clone());
                 }
             }
@@ -127,6 +192,108 @@ impl InstructionClassifier {
         None
     }
 
+    /// Инструкции program_id, чьи данные начинаются с `discriminator`,
+    /// в порядке исполнения (outer, затем его inner)
+    pub fn find_instructions(
+        &self,
+        program_id: &str,
+        discriminator: &[u8],
+    ) -> Vec<ClassifiedInstruction> {
+        let mut found: Vec<ClassifiedInstruction> = self
+            .instruction_map
+            .get(program_id)
+            .into_iter()
+            .flatten()
+            .filter(|ci| decode_instruction_data(&ci.data.data).starts_with(discriminator))
+            .cloned()
+            .collect();
+        Self::sort_by_execution(&mut found);
+        found
+    }
+
+    /// Все инструкции (любых программ), в аккаунтах которых есть `pubkey`,
+    /// в порядке исполнения
+    pub fn find_by_account(&self, pubkey: &str) -> Vec<ClassifiedInstruction> {
+        let mut found: Vec<ClassifiedInstruction> = self
+            .instruction_map
+            .values()
+            .flatten()
+            .filter(|ci| ci.data.accounts.iter().any(|account| account == pubkey))
+            .cloned()
+            .collect();
+        Self::sort_by_execution(&mut found);
+        found
+    }
+
+    /// Дерево вызовов: по корню на outer-инструкцию, inner вложены под вызвавший
+    /// их CPI по stack height. Без stack height inner считаются прямыми потомками outer
+    pub fn invocation_tree(&self) -> Vec<InvocationNode> {
+        let mut all = self.flatten();
+        Self::sort_by_execution(&mut all);
+        all.chunk_by(|a, b| a.outer_index == b.outer_index)
+            .flat_map(|group| Self::build_level(group, &mut 0, 0))
+            .collect()
+    }
+
+    /// Узел `instruction` с CPI, которые она сделала
+    pub fn invocation_of(&self, instruction: &ClassifiedInstruction) -> Option<InvocationNode> {
+        self.invocation_tree().into_iter().find_map(|root| {
+            root.find(instruction.outer_index, instruction.inner_index)
+                .cloned()
+        })
+    }
+
+    /// Прямые CPI outer-инструкции `outer_index` вместе с их поддеревьями
+    pub fn children_of(&self, outer_index: usize) -> Vec<InvocationNode> {
+        let mut group: Vec<ClassifiedInstruction> = self
+            .instruction_map
+            .values()
+            .flatten()
+            .filter(|ci| ci.outer_index == outer_index)
+            .cloned()
+            .collect();
+        Self::sort_by_execution(&mut group);
+        let mut roots = Self::build_level(&group, &mut 0, 0);
+        match roots.first() {
+            Some(root) if root.instruction.inner_index.is_none() => roots.swap_remove(0).children,
+            // outer отфильтрован (пустой program_id) — отдаём inner как есть
+            _ => roots,
+        }
+    }
+
+    /// Забирает из `group` подряд идущие инструкции глубже `parent_height`
+    fn build_level(
+        group: &[ClassifiedInstruction],
+        pos: &mut usize,
+        parent_height: u32,
+    ) -> Vec<InvocationNode> {
+        let mut nodes = Vec::new();
+        while let Some(instruction) = group.get(*pos) {
+            let height = Self::stack_height(instruction);
+            if height <= parent_height {
+                break;
+            }
+            *pos += 1;
+            let children = Self::build_level(group, pos, height);
+            nodes.push(InvocationNode {
+                instruction: instruction.clone(),
+                children,
+            });
+        }
+        nodes
+    }
+
+    fn stack_height(ci: &ClassifiedInstruction) -> u32 {
+        ci.data
+            .stack_height
+            .unwrap_or(if ci.inner_index.is_some() { 2 } else { 1 })
+    }
+
+    /// Outer-инструкция идёт перед своими inner: `(outer_index, inner_index + 1)`
+    fn sort_by_execution(instructions: &mut [ClassifiedInstruction]) {
+        instructions.sort_by_key(|ci| (ci.outer_index, ci.inner_index.map_or(0, |i| i + 1)));
+    }
+
     /// Опционально оставил (в TS нет, но вдруг пригодится)
     pub fn flatten(&self) -> Vec<ClassifiedInstruction> {
         self.instruction_map.values().flatten().cloned().collect()
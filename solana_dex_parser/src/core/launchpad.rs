@@ -0,0 +1,40 @@
+use std::collections::HashMap;
+
+use crate::core::constants::launchpad_platforms;
+use crate::types::MemeEvent;
+
+/// Attribute meme events to the launchpad that created their curve.
+///
+/// Events missing a platform config borrow it from another event of the same curve in the
+/// transaction. Known configs, from `platforms` first and then the built-in table, replace
+/// the curve program in `protocol` with the launchpad name.
+pub fn attribute_launchpads(
+    mut events: Vec<MemeEvent>,
+    platforms: &HashMap<String, String>,
+) -> Vec<MemeEvent> {
+    let configs: HashMap<String, String> = events
+        .iter()
+        .filter_map(|event| Some((curve(event)?.clone(), event.platform_config.clone()?)))
+        .collect();
+
+    for event in &mut events {
+        if event.platform_config.is_none() {
+            event.platform_config = curve(event).and_then(|curve| configs.get(curve)).cloned();
+        }
+        let Some(config) = event.platform_config.as_deref() else {
+            continue;
+        };
+        let name = platforms
+            .get(config)
+            .map(String::as_str)
+            .or_else(|| launchpad_platforms::name(config));
+        if let Some(name) = name {
+            event.protocol = Some(name.to_string());
+        }
+    }
+    events
+}
+
+fn curve(event: &MemeEvent) -> Option<&String> {
+    event.bonding_curve.as_ref().or(event.pool.as_ref())
+}
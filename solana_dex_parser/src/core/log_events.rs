@@ -0,0 +1,118 @@
+//! Anchor events recovered from `Program data:` log lines.
+//!
+//! Pump.fun, PumpSwap and Jupiter log their events besides emitting them through a
+//! self-invoked inner instruction. Some providers strip inner instructions, which leaves the
+//! event decoders nothing to read; with `ParseConfig::use_logs_fallback` the logged events
+//! are turned back into the inner instructions `emit_cpi!` records.
+
+use base64::{engine::general_purpose::STANDARD as BASE64_STANDARD, Engine as _};
+
+use crate::core::constants::dex_programs;
+use crate::protocols::jupiter::constants::discriminators::jupiter_events;
+use crate::protocols::pumpfun::constants::discriminators::{pumpfun_events, pumpswap_events};
+use crate::types::{InnerInstruction, SolanaInstruction, SolanaTransaction};
+
+/// Events read from the logs of each program, as the 16-byte discriminators of their
+/// inner instructions: the `emit_cpi!` tag followed by the event discriminator.
+const LOGGED_EVENTS: [(&str, &[[u8; 16]]); 3] = [
+    (
+        dex_programs::PUMP_FUN,
+        &[
+            pumpfun_events::TRADE,
+            pumpfun_events::CREATE,
+            pumpfun_events::COMPLETE,
+            pumpfun_events::MIGRATE,
+            pumpfun_events::SET_PARAMS,
+            pumpfun_events::SET_CREATOR,
+            pumpfun_events::ADMIN_SET_CREATOR,
+            pumpfun_events::UPDATE_GLOBAL_AUTHORITY,
+            pumpfun_events::COLLECT_CREATOR_FEE,
+        ],
+    ),
+    (
+        dex_programs::PUMP_SWAP,
+        &[
+            pumpswap_events::CREATE_POOL,
+            pumpswap_events::ADD_LIQUIDITY,
+            pumpswap_events::REMOVE_LIQUIDITY,
+            pumpswap_events::BUY,
+            pumpswap_events::SELL,
+            pumpswap_events::COLLECT_COIN_CREATOR_FEE,
+        ],
+    ),
+    (dex_programs::JUPITER, &[jupiter_events::SWAP]),
+];
+
+/// Inner instructions rebuilt from the events logged under each outer instruction that has
+/// none recorded, in log order.
+///
+/// An event becomes a self-invocation of the program that logged it, one level below it and
+/// without accounts. Nothing is rebuilt when the invocations in the logs do not line up with
+/// the outer instructions.
+pub fn inner_instructions_from_logs(tx: &SolanaTransaction) -> Vec<InnerInstruction> {
+    let mut rebuilt: Vec<InnerInstruction> = Vec::new();
+    let mut stack: Vec<&str> = Vec::new();
+    let mut outer_index: Option<usize> = None;
+    for line in &tx.meta.log_messages {
+        if let Some(payload) = line.strip_prefix("Program data: ") {
+            let (Some(&program_id), Some(index)) = (stack.last(), outer_index) else {
+                continue;
+            };
+            let recorded = tx
+                .inner_instructions
+                .iter()
+                .any(|set| set.index == index && !set.instructions.is_empty());
+            if recorded {
+                continue;
+            }
+            let Some(data) = event_data(program_id, payload) else {
+                continue;
+            };
+            let instruction = SolanaInstruction {
+                program_id: program_id.to_string(),
+                accounts: Vec::new(),
+                data: bs58::encode(data).into_string(),
+                stack_height: Some(stack.len() as u32 + 1),
+            };
+            match rebuilt.last_mut() {
+                Some(set) if set.index == index => set.instructions.push(instruction),
+                _ => rebuilt.push(InnerInstruction {
+                    index,
+                    instructions: vec![instruction],
+                }),
+            }
+            continue;
+        }
+        let Some(rest) = line.strip_prefix("Program ") else {
+            continue;
+        };
+        let Some((invoked, event)) = rest.split_once(' ') else {
+            continue;
+        };
+        if event == "invoke [1]" {
+            let index = outer_index.map_or(0, |index| index + 1);
+            match tx.instructions.get(index) {
+                Some(instruction) if instruction.program_id == invoked => {}
+                _ => return Vec::new(),
+            }
+            outer_index = Some(index);
+            stack.clear();
+            stack.push(invoked);
+        } else if event.starts_with("invoke [") {
+            stack.push(invoked);
+        } else if event == "success" || event.starts_with("failed") {
+            stack.pop();
+        }
+    }
+    rebuilt
+}
+
+/// Inner instruction data of a payload `program_id` logged, when it is one of its events.
+fn event_data(program_id: &str, payload: &str) -> Option<Vec<u8>> {
+    let (_, events) = LOGGED_EVENTS.iter().find(|(id, _)| *id == program_id)?;
+    let bytes = BASE64_STANDARD.decode(payload.trim()).ok()?;
+    let event = events
+        .iter()
+        .find(|event| bytes.len() >= 8 && bytes[..8] == event[8..])?;
+    Some([&event[..8], bytes.as_slice()].concat())
+}
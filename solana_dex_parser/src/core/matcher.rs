@@ -0,0 +1,273 @@
+use crate::core::instruction_classifier::InvocationNode;
+use crate::types::{TokenInfo, TradeInfo, TransferData, TransferMap};
+
+/// Largest gap, in basis points of the decoded amount, between a trade leg and the transfer
+/// that settled it. Covers protocol, creator and LP fees deducted on either side of the
+/// amount a program reports.
+pub const DEFAULT_FEE_TOLERANCE_BPS: u64 = 200;
+
+/// Transfers that settled the input and output legs of a trade.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct MatchedLegs<'a> {
+    pub input: Option<&'a TransferData>,
+    pub output: Option<&'a TransferData>,
+}
+
+/// Accounts, mint and amount a decoded instruction says one of its transfers moved; unset
+/// fields match any transfer.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Leg<'s> {
+    pub source: Option<&'s str>,
+    pub destination: Option<&'s str>,
+    pub authority: Option<&'s str>,
+    pub destination_owner: Option<&'s str>,
+    pub mint: Option<&'s str>,
+    pub amount: Option<u128>,
+}
+
+impl<'s> Leg<'s> {
+    /// Transfer from token account `source` to token account `destination`.
+    pub fn between(source: &'s str, destination: &'s str) -> Self {
+        Self {
+            source: Some(source),
+            destination: Some(destination),
+            ..Self::default()
+        }
+    }
+
+    /// Transfer of `mint` of any accounts.
+    pub fn of(mint: &'s str) -> Self {
+        Self {
+            mint: Some(mint),
+            ..Self::default()
+        }
+    }
+
+    pub fn from(mut self, source: &'s str) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    pub fn to(mut self, destination: &'s str) -> Self {
+        self.destination = Some(destination);
+        self
+    }
+
+    pub fn mint(mut self, mint: &'s str) -> Self {
+        self.mint = Some(mint);
+        self
+    }
+
+    /// Amount decoded from the instruction, matched within the fee tolerance.
+    pub fn amount(mut self, amount: u128) -> Self {
+        self.amount = Some(amount);
+        self
+    }
+
+    /// Transfer signed by `authority`, e.g. the user paying in.
+    pub fn from_owner(mut self, authority: &'s str) -> Self {
+        self.authority = Some(authority);
+        self
+    }
+
+    /// Transfer into an account owned by `owner`, e.g. the user being paid out.
+    pub fn to_owner(mut self, owner: &'s str) -> Self {
+        self.destination_owner = Some(owner);
+        self
+    }
+
+    fn accepts(&self, transfer: &TransferData) -> bool {
+        let info = &transfer.info;
+        self.source.is_none_or(|source| info.source == source)
+            && self
+                .destination
+                .is_none_or(|destination| info.destination == destination)
+            && self
+                .authority
+                .is_none_or(|authority| info.authority.as_deref() == Some(authority))
+            && self
+                .destination_owner
+                .is_none_or(|owner| info.destination_owner.as_deref() == Some(owner))
+            && self.mint.is_none_or(|mint| info.mint == mint)
+    }
+}
+
+/// Pairs decoded trades and instructions with their transfer legs. Candidates must come from
+/// the same program invocation and agree on accounts and mint; the amount, exact before
+/// fee-adjusted, only decides between those.
+pub struct TransferMatcher<'a> {
+    transfers: &'a TransferMap,
+    tolerance_bps: u64,
+}
+
+impl<'a> TransferMatcher<'a> {
+    pub fn new(transfers: &'a TransferMap) -> Self {
+        Self {
+            transfers,
+            tolerance_bps: DEFAULT_FEE_TOLERANCE_BPS,
+        }
+    }
+
+    pub fn with_tolerance_bps(mut self, tolerance_bps: u64) -> Self {
+        self.tolerance_bps = tolerance_bps;
+        self
+    }
+
+    /// Transfers executed under the outer instruction at `outer_index`, of any program, in
+    /// execution order.
+    pub fn instruction_transfers(&self, outer_index: usize) -> Vec<&'a TransferData> {
+        let mut transfers: Vec<&'a TransferData> = self
+            .transfers
+            .values()
+            .flatten()
+            .filter(|transfer| outer_index_of(&transfer.idx) == Some(outer_index))
+            .collect();
+        transfers.sort_by_key(|transfer| execution_order(&transfer.idx));
+        transfers
+    }
+
+    /// Transfer of `leg` under the outer instruction at `outer_index`: the accounts and mint
+    /// must agree and a decoded amount must be within the fee tolerance; the closest amount
+    /// wins, then the earliest transfer.
+    pub fn find_leg(&self, outer_index: usize, leg: &Leg) -> Option<&'a TransferData> {
+        self.instruction_transfers(outer_index)
+            .into_iter()
+            .filter(|transfer| leg.accepts(transfer))
+            .filter_map(|transfer| Some((self.amount_gap(transfer, leg.amount)?, transfer)))
+            .min_by_key(|(gap, _)| *gap)
+            .map(|(_, transfer)| transfer)
+    }
+
+    /// Every transfer of `leg` under the outer instruction at `outer_index`, in execution order.
+    pub fn find_legs(&self, outer_index: usize, leg: &Leg) -> Vec<&'a TransferData> {
+        self.legs_among(self.instruction_transfers(outer_index), leg)
+    }
+
+    /// Transfers executed by `node` and the CPIs it made, in execution order; a router's hop
+    /// gets only what its venue moved.
+    pub fn invocation_transfers(&self, node: &InvocationNode) -> Vec<&'a TransferData> {
+        self.instruction_transfers(node.instruction.outer_index)
+            .into_iter()
+            .filter(|transfer| node.contains(&transfer.idx))
+            .collect()
+    }
+
+    /// Every transfer of `leg` executed by `node` and the CPIs it made, in execution order.
+    pub fn find_legs_in(&self, node: &InvocationNode, leg: &Leg) -> Vec<&'a TransferData> {
+        self.legs_among(self.invocation_transfers(node), leg)
+    }
+
+    fn legs_among(&self, transfers: Vec<&'a TransferData>, leg: &Leg) -> Vec<&'a TransferData> {
+        transfers
+            .into_iter()
+            .filter(|transfer| leg.accepts(transfer))
+            .filter(|transfer| self.amount_gap(transfer, leg.amount).is_some())
+            .collect()
+    }
+
+    /// Legs of a swap known only from the transfers of `program_id`: the first transfer pays
+    /// in, and the payout is the transfer to the payer's accounts in the same invocation,
+    /// preferring another mint, then execution order.
+    pub fn swap_legs(&self, program_id: &str) -> Option<(&'a TransferData, &'a TransferData)> {
+        let transfers = self.transfers.get(program_id)?;
+        let input = transfers.first()?;
+        let user = input
+            .info
+            .authority
+            .as_deref()
+            .unwrap_or(&input.info.source);
+        let outer = outer_index_of(&input.idx);
+        let output = transfers
+            .iter()
+            .skip(1)
+            .enumerate()
+            .filter(|(_, transfer)| outer_index_of(&transfer.idx) == outer)
+            .min_by_key(|(position, transfer)| {
+                (
+                    transfer.info.destination_owner.as_deref() != Some(user)
+                        && transfer.info.destination != user,
+                    transfer.info.mint == input.info.mint,
+                    *position,
+                )
+            })
+            .map(|(_, transfer)| transfer)?;
+        Some((input, output))
+    }
+
+    /// Distance of `transfer` from `expected`, `None` outside the fee tolerance.
+    fn amount_gap(&self, transfer: &TransferData, expected: Option<u128>) -> Option<u128> {
+        let Some(expected) = expected else {
+            return Some(0);
+        };
+        let amount: u128 = transfer.info.token_amount.amount.parse().ok()?;
+        let gap = amount.abs_diff(expected);
+        (gap * 10_000 <= expected * self.tolerance_bps as u128).then_some(gap)
+    }
+
+    /// Legs of `trade` among the transfers of its program under the trade's outer instruction;
+    /// input and output never share a transfer. Token accounts the trade already names must match, and transfers moved
+    /// by or to the trade's user outrank a closer amount.
+    pub fn match_trade(&self, trade: &TradeInfo) -> MatchedLegs<'a> {
+        let Some(candidates) = trade
+            .program_id
+            .as_ref()
+            .and_then(|program_id| self.transfers.get(program_id))
+        else {
+            return MatchedLegs::default();
+        };
+        let outer = outer_index_of(&trade.idx);
+        let user = trade.user.as_deref();
+
+        let input = self.best_leg(candidates, &trade.input_token, outer, None, |transfer| {
+            user.is_some_and(|user| {
+                transfer.info.authority.as_deref() == Some(user) || transfer.info.source == user
+            })
+        });
+        let output = self.best_leg(candidates, &trade.output_token, outer, input, |transfer| {
+            user.is_some_and(|user| {
+                transfer.info.destination_owner.as_deref() == Some(user)
+                    || transfer.info.destination == user
+            })
+        });
+        MatchedLegs { input, output }
+    }
+
+    fn best_leg(
+        &self,
+        candidates: &'a [TransferData],
+        token: &TokenInfo,
+        outer: Option<usize>,
+        taken: Option<&TransferData>,
+        owned_by_user: impl Fn(&TransferData) -> bool,
+    ) -> Option<&'a TransferData> {
+        let expected: u128 = token.amount_raw.parse().ok()?;
+        let leg = Leg {
+            source: token.source.as_deref(),
+            destination: token.destination.as_deref(),
+            ..Leg::of(&token.mint)
+        };
+        candidates
+            .iter()
+            .filter(|transfer| leg.accepts(transfer))
+            .filter(|transfer| !taken.is_some_and(|taken| std::ptr::eq(taken, *transfer)))
+            .filter(|transfer| outer.is_none() || outer_index_of(&transfer.idx) == outer)
+            .filter_map(|transfer| {
+                let gap = self.amount_gap(transfer, Some(expected))?;
+                Some(((!owned_by_user(transfer), gap), transfer))
+            })
+            .min_by_key(|(rank, _)| *rank)
+            .map(|(_, transfer)| transfer)
+    }
+}
+
+fn outer_index_of(idx: &str) -> Option<usize> {
+    idx.split('-').next()?.parse().ok()
+}
+
+/// `(outer, inner + 1)` of an `"outer-inner"` index, so an outer transfer sorts first.
+fn execution_order(idx: &str) -> (usize, usize) {
+    let mut parts = idx.split('-').map(|part| part.parse::<usize>().ok());
+    let outer = parts.next().flatten().unwrap_or(usize::MAX);
+    let inner = parts.next().flatten().map_or(0, |inner| inner + 1);
+    (outer, inner)
+}
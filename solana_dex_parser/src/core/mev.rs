@@ -0,0 +1,130 @@
+//! MEV found across the transactions of a block.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::core::amounts::{build_token_info, raw_amount};
+use crate::core::wash::trader;
+use crate::types::{MevEvent, MevType, ParseResult, TradeInfo};
+
+/// Trade of a block, with the transaction it came from.
+struct BlockTrade<'a> {
+    /// Index of the transaction in block order.
+    position: usize,
+    signature: &'a str,
+    trader: &'a str,
+    timestamp: u64,
+    trade: &'a TradeInfo,
+}
+
+impl BlockTrade<'_> {
+    fn same_direction(&self, other: &BlockTrade) -> bool {
+        self.trade.input_token.mint == other.trade.input_token.mint
+            && self.trade.output_token.mint == other.trade.output_token.mint
+    }
+
+    fn reverses(&self, other: &BlockTrade) -> bool {
+        self.trade.input_token.mint == other.trade.output_token.mint
+            && self.trade.output_token.mint == other.trade.input_token.mint
+    }
+}
+
+/// Flag victim/frontrun/backrun triples on the same pool within a slot.
+///
+/// A sandwich is a trade (the frontrun) followed, in a later transaction of the same pool
+/// and slot, by the reverse trade of the same trader (the backrun) that gets back more of
+/// the token the frontrun spent; every trade in between from another trader in the
+/// frontrun's direction is a victim. Transactions are taken in
+/// [`ParseResult::block_position`] order; results without a block context keep the order
+/// they are given in.
+pub fn detect_sandwiches(results: &[ParseResult]) -> Vec<MevEvent> {
+    let mut ordered: Vec<&ParseResult> = results.iter().collect();
+    ordered.sort_by_key(|result| result.block_position());
+
+    let mut pools: HashMap<(&str, u64), Vec<BlockTrade>> = HashMap::new();
+    let mut pool_order = Vec::new();
+    for (position, result) in ordered.into_iter().enumerate() {
+        for trade in &result.trades {
+            let Some(pool) = trade.pool.first() else {
+                continue;
+            };
+            let Some(trader) = trader(result, trade) else {
+                continue;
+            };
+            let key = (pool.as_str(), trade.slot);
+            if !pools.contains_key(&key) {
+                pool_order.push(key);
+            }
+            pools.entry(key).or_default().push(BlockTrade {
+                position,
+                signature: &result.signature,
+                trader,
+                timestamp: result.timestamp,
+                trade,
+            });
+        }
+    }
+
+    let mut events = Vec::new();
+    for key in pool_order {
+        let trades = &pools[&key];
+        let mut used = HashSet::new();
+        for (front_index, front) in trades.iter().enumerate() {
+            if used.contains(&front_index) {
+                continue;
+            }
+            let back =
+                trades
+                    .iter()
+                    .enumerate()
+                    .skip(front_index + 1)
+                    .find(|(back_index, back)| {
+                        !used.contains(back_index)
+                            && back.position > front.position
+                            && back.trader == front.trader
+                            && back.reverses(front)
+                    });
+            let Some((back_index, back)) = back else {
+                continue;
+            };
+            let (Some(spent), Some(returned)) = (
+                raw_amount(&front.trade.input_token.amount_raw),
+                raw_amount(&back.trade.output_token.amount_raw),
+            ) else {
+                continue;
+            };
+            if returned <= spent {
+                continue;
+            }
+            let victims: Vec<&BlockTrade> = trades[front_index + 1..back_index]
+                .iter()
+                .filter(|victim| {
+                    victim.position > front.position
+                        && victim.position < back.position
+                        && victim.trader != front.trader
+                        && victim.same_direction(front)
+                })
+                .collect();
+            if victims.is_empty() {
+                continue;
+            }
+            used.extend([front_index, back_index]);
+            let input = &front.trade.input_token;
+            let extracted_value =
+                build_token_info(&input.mint, returned - spent, input.decimals, None);
+            events.extend(victims.into_iter().map(|victim| MevEvent {
+                mev_type: MevType::Sandwich,
+                pool: key.0.to_string(),
+                amm: victim.trade.amm.clone(),
+                attacker: front.trader.to_string(),
+                victim: victim.trader.to_string(),
+                frontrun_signature: front.signature.to_string(),
+                victim_signature: victim.signature.to_string(),
+                backrun_signature: back.signature.to_string(),
+                extracted_value: extracted_value.clone(),
+                slot: key.1,
+                timestamp: victim.timestamp,
+            }));
+        }
+    }
+    events
+}
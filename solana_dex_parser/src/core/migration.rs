@@ -0,0 +1,319 @@
+use crate::config::ParseConfig;
+use crate::core::constants::{dex_programs, migration_instructions};
+use crate::core::instruction_classifier::InstructionClassifier;
+use crate::core::transaction_adapter::TransactionAdapter;
+use crate::core::util::compare_idx;
+use crate::protocols::meteora::constants::discriminators::dbc_instructions;
+use crate::protocols::pumpfun::constants::{discriminators::pumpfun_instructions, SOL_MINT};
+use crate::protocols::raydium::launchlab::constants::discriminators::launchlab_instructions;
+use crate::protocols::virtuals::constants::discriminators::virtuals_instructions;
+use crate::types::{MemeEvent, MigrationEvent, ParseResult, PoolEvent, TradeType};
+
+/// Launchpad instruction that graduates a bonding curve into an AMM pool.
+struct MigrationRoute {
+    source_program: &'static str,
+    discriminator: [u8; 8],
+    curve_account: usize,
+    mint_account: usize,
+    /// Destination pool account when the instruction creates the pool itself.
+    pool_account: Option<usize>,
+    destinations: &'static [&'static str],
+}
+
+const MIGRATION_ROUTES: [MigrationRoute; 7] = [
+    MigrationRoute {
+        source_program: dex_programs::PUMP_FUN,
+        discriminator: pumpfun_instructions::MIGRATE,
+        curve_account: 3,
+        mint_account: 2,
+        pool_account: Some(9),
+        destinations: &[dex_programs::PUMP_SWAP],
+    },
+    // The Raydium/Meteora pool is initialised by a sibling instruction.
+    MigrationRoute {
+        source_program: dex_programs::MOONSHOT,
+        discriminator: migration_instructions::MOONSHOT_MIGRATE_FUNDS,
+        curve_account: 2,
+        mint_account: 5,
+        pool_account: None,
+        destinations: &[dex_programs::RAYDIUM, dex_programs::METEORA_DAMM],
+    },
+    MigrationRoute {
+        source_program: dex_programs::METEORA_DBC,
+        discriminator: dbc_instructions::MIGRATE_METEORA_DAMM,
+        curve_account: 0,
+        mint_account: 7,
+        pool_account: Some(4),
+        destinations: &[dex_programs::METEORA_DAMM],
+    },
+    MigrationRoute {
+        source_program: dex_programs::METEORA_DBC,
+        discriminator: dbc_instructions::MIGRATION_DAMM_V2,
+        curve_account: 0,
+        mint_account: 13,
+        pool_account: Some(4),
+        destinations: &[dex_programs::METEORA_DAMM_V2],
+    },
+    MigrationRoute {
+        source_program: dex_programs::RAYDIUM_LAUNCHLAB,
+        discriminator: launchlab_instructions::MIGRATE_TO_AMM,
+        curve_account: 22,
+        mint_account: 1,
+        pool_account: Some(13),
+        destinations: &[dex_programs::RAYDIUM],
+    },
+    MigrationRoute {
+        source_program: dex_programs::RAYDIUM_LAUNCHLAB,
+        discriminator: launchlab_instructions::MIGRATE_TO_CPSWAP,
+        curve_account: 17,
+        mint_account: 1,
+        pool_account: Some(5),
+        destinations: &[dex_programs::RAYDIUM_CPMM],
+    },
+    MigrationRoute {
+        source_program: dex_programs::VIRTUALS,
+        discriminator: virtuals_instructions::GRADUATE,
+        curve_account: 3,
+        mint_account: 1,
+        pool_account: Some(4),
+        destinations: &[dex_programs::METEORA_DAMM_V2],
+    },
+];
+
+/// Detect launchpad migrations executed by a single transaction.
+///
+/// The destination pool is read from the migration instruction when it creates the pool,
+/// otherwise from a pool `Create` event or Pumpfun migrate event for the same mint.
+pub fn detect_migrations(
+    adapter: &TransactionAdapter,
+    classifier: &InstructionClassifier,
+    meme_events: &[MemeEvent],
+    liquidities: &[PoolEvent],
+) -> Vec<MigrationEvent> {
+    let program_ids = classifier.get_all_program_ids();
+    let mut migrations = Vec::new();
+
+    for route in &MIGRATION_ROUTES {
+        for classified in classifier.get_instructions(route.source_program) {
+            let Ok(data) = bs58::decode(&classified.data.data).into_vec() else {
+                continue;
+            };
+            if !data.starts_with(&route.discriminator) {
+                continue;
+            }
+            let accounts = &classified.data.accounts;
+            let mint = accounts
+                .get(route.mint_account)
+                .cloned()
+                .unwrap_or_default();
+            let created_pool = created_pool(route, &mint, liquidities);
+
+            let destination_program = created_pool
+                .and_then(|pool| pool.program_id.clone())
+                .or_else(|| {
+                    route
+                        .destinations
+                        .iter()
+                        .find(|program| program_ids.iter().any(|id| id == *program))
+                        .map(|program| program.to_string())
+                });
+            let pool = route
+                .pool_account
+                .and_then(|index| accounts.get(index).cloned())
+                .or_else(|| created_pool.map(|pool| pool.pool_id.clone()))
+                .or_else(|| migrated_pool(&mint, meme_events));
+
+            let seed = created_pool
+                .map(|pool| PoolSeed::of_pool(pool, &mint))
+                .or_else(|| PoolSeed::of_migrate_event(&mint, meme_events))
+                .unwrap_or_default();
+
+            migrations.push(MigrationEvent {
+                source_program: route.source_program.to_string(),
+                source_protocol: adapter.config().program_name(route.source_program),
+                bonding_curve: accounts
+                    .get(route.curve_account)
+                    .cloned()
+                    .unwrap_or_default(),
+                mint,
+                destination_protocol: destination_program
+                    .as_deref()
+                    .map(|program| adapter.config().program_name(program)),
+                destination_program,
+                pool,
+                pool_signature: None,
+                quote_mint: seed.quote_mint,
+                initial_base_reserve: seed.base_reserve,
+                initial_quote_reserve: seed.quote_reserve,
+                slot: adapter.slot(),
+                timestamp: adapter.block_time(),
+                signature: adapter.shared_signature(),
+                idx: format!(
+                    "{}-{}",
+                    classified.outer_index,
+                    classified.inner_index.unwrap_or(0)
+                ),
+            });
+        }
+    }
+
+    migrations.sort_by(|a, b| compare_idx(&a.idx, &b.idx));
+    migrations
+}
+
+/// What the destination pool was seeded with, oriented so the graduated mint is the base.
+#[derive(Default)]
+struct PoolSeed {
+    quote_mint: Option<String>,
+    base_reserve: Option<f64>,
+    quote_reserve: Option<f64>,
+}
+
+impl PoolSeed {
+    fn of_pool(pool: &PoolEvent, mint: &str) -> Self {
+        if pool.token0_mint.as_deref() == Some(mint) {
+            Self {
+                quote_mint: pool.token1_mint.clone(),
+                base_reserve: pool.token0_amount,
+                quote_reserve: pool.token1_amount,
+            }
+        } else {
+            Self {
+                quote_mint: pool.token0_mint.clone(),
+                base_reserve: pool.token1_amount,
+                quote_reserve: pool.token0_amount,
+            }
+        }
+    }
+
+    /// Pumpfun logs the deposit into its Pumpswap pool on the migrate event.
+    fn of_migrate_event(mint: &str, meme_events: &[MemeEvent]) -> Option<Self> {
+        let event = meme_events.iter().find(|event| {
+            event.event_type == TradeType::Migrate
+                && event.base_mint == mint
+                && event.pool_a_reserve.is_some()
+        })?;
+        Some(Self {
+            quote_mint: Some(SOL_MINT.to_string()),
+            base_reserve: event.pool_a_reserve,
+            quote_reserve: event.pool_b_reserve,
+        })
+    }
+
+    fn apply(self, migration: &mut MigrationEvent) {
+        migration.quote_mint = self.quote_mint;
+        migration.initial_base_reserve = self.base_reserve;
+        migration.initial_quote_reserve = self.quote_reserve;
+    }
+}
+
+fn created_pool<'a>(
+    route: &MigrationRoute,
+    mint: &str,
+    liquidities: &'a [PoolEvent],
+) -> Option<&'a PoolEvent> {
+    liquidities.iter().find(|pool| {
+        pool.event_type == TradeType::Create
+            && pool
+                .program_id
+                .as_deref()
+                .is_some_and(|program| route.destinations.contains(&program))
+            && pool_has_mint(pool, mint)
+    })
+}
+
+fn migrated_pool(mint: &str, meme_events: &[MemeEvent]) -> Option<String> {
+    meme_events
+        .iter()
+        .find(|event| event.event_type == TradeType::Migrate && event.base_mint == mint)
+        .and_then(|event| event.pool.clone())
+}
+
+fn pool_has_mint(pool: &PoolEvent, mint: &str) -> bool {
+    pool.token0_mint.as_deref() == Some(mint) || pool.token1_mint.as_deref() == Some(mint)
+}
+
+/// Links migrations whose destination pool is created by a later transaction.
+///
+/// Feed parse results in slot order; migrations without a pool are held for
+/// `window_slots` and completed by the first matching pool `Create` event, then dropped.
+#[derive(Clone, Debug)]
+pub struct MigrationTracker {
+    window_slots: u64,
+    config: ParseConfig,
+    pending: Vec<MigrationEvent>,
+}
+
+impl MigrationTracker {
+    pub fn new(window_slots: u64) -> Self {
+        Self {
+            window_slots,
+            config: ParseConfig::default(),
+            pending: Vec::new(),
+        }
+    }
+
+    /// Name linked destination pools with `config`, so venues of its program tables are known.
+    pub fn with_config(mut self, config: ParseConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Record one transaction and return the migrations that are now fully linked.
+    pub fn observe(&mut self, result: &ParseResult) -> Vec<MigrationEvent> {
+        let window_slots = self.window_slots;
+        self.pending
+            .retain(|pending| result.slot.saturating_sub(pending.slot) <= window_slots);
+
+        let mut linked = Vec::new();
+        for pool in &result.liquidities {
+            if pool.event_type != TradeType::Create {
+                continue;
+            }
+            let Some(pos) = self.pending.iter().position(|pending| {
+                pool_has_mint(pool, &pending.mint)
+                    && pool.program_id.as_deref().is_none_or(|program| {
+                        destinations_of(&pending.source_program).contains(&program)
+                    })
+            }) else {
+                continue;
+            };
+            let mut migration = self.pending.remove(pos);
+            migration.pool = Some(pool.pool_id.clone());
+            migration.pool_signature = Some(result.signature.clone());
+            PoolSeed::of_pool(pool, &migration.mint).apply(&mut migration);
+            if let Some(program) = pool.program_id.clone() {
+                migration.destination_protocol = Some(self.config.program_name(&program));
+                migration.destination_program = Some(program);
+            }
+            linked.push(migration);
+        }
+
+        for migration in &result.migrations {
+            if migration.pool.is_some() {
+                linked.push(migration.clone());
+            } else {
+                self.pending.push(migration.clone());
+            }
+        }
+        linked
+    }
+
+    /// Migrations still waiting for their destination pool.
+    pub fn pending(&self) -> &[MigrationEvent] {
+        &self.pending
+    }
+
+    /// Drain the tracker, returning migrations that were never linked to a pool.
+    pub fn finish(self) -> Vec<MigrationEvent> {
+        self.pending
+    }
+}
+
+fn destinations_of(source_program: &str) -> Vec<&'static str> {
+    MIGRATION_ROUTES
+        .iter()
+        .filter(|route| route.source_program == source_program)
+        .flat_map(|route| route.destinations.iter().copied())
+        .collect()
+}
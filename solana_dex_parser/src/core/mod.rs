@@ -1,6 +1,39 @@
+pub mod aggregate;
+pub mod alias;
+pub mod amounts;
+pub mod anomaly;
+pub mod arbitrage;
+pub mod attempts;
+pub mod block_stats;
+pub mod bots;
+pub mod bridge;
+pub mod bundles;
+pub mod compute_budget;
 pub mod constants;
+pub mod cpi;
 pub mod dex_parser;
 pub mod error;
+pub mod failure;
+pub mod fees;
+pub mod flow;
 pub mod instruction_classifier;
+pub mod launchpad;
+pub mod log_events;
+pub mod matcher;
+pub mod mev;
+pub mod migration;
+pub mod pnl;
+pub mod pool;
+pub mod pool_state;
+pub mod pool_tracker;
+pub mod price;
+pub mod program_tables;
+pub mod reserves;
+pub mod routing;
+pub mod settlement;
+pub mod timing;
+pub mod tips;
 pub mod transaction_adapter;
 pub mod transaction_utils;
+pub mod util;
+pub mod wash;
@@ -0,0 +1,261 @@
+//! Average-cost profit and loss of one wallet, in SOL, across parsed transactions.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::amounts::convert_to_ui_amount;
+use crate::core::constants::tokens;
+use crate::core::flow::is_router_trade;
+use crate::core::wash::trader;
+use crate::types::{ParseResult, TokenAmount, TokenInfo, TradeInfo, TradeType};
+
+/// Holding of one mint, in UI units, with the SOL it cost.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Position {
+    pub mint: String,
+    pub amount: f64,
+    /// SOL paid for `amount`, fees included.
+    pub cost_sol: f64,
+    pub realized_pnl_sol: f64,
+}
+
+impl Position {
+    /// SOL paid per token held; `None` when nothing is held.
+    pub fn average_cost(&self) -> Option<f64> {
+        (self.amount > 0.0).then(|| self.cost_sol / self.amount)
+    }
+}
+
+/// Effect of one trade of the tracked wallet on its position in `mint`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PnlEvent {
+    pub signature: String,
+    pub idx: String,
+    pub mint: String,
+    /// `Buy` when the trade added to the position, `Sell` when it reduced it.
+    #[serde(rename = "type")]
+    pub trade_type: TradeType,
+    pub amount: f64,
+    /// SOL value of the trade, before fees.
+    pub value_sol: f64,
+    /// Trade fees paid in SOL and the trade's share of the transaction fee and tip.
+    pub fee_sol: f64,
+    /// Position after the trade.
+    pub position_amount: f64,
+    pub average_cost: Option<f64>,
+    /// Profit of a sale over the average cost of what it sold, net of fees; zero for buys.
+    pub realized_pnl_sol: f64,
+    /// Remaining position marked at this trade's price, less what it cost.
+    pub unrealized_pnl_sol: f64,
+}
+
+/// Tracks the positions of one wallet from its trades.
+///
+/// Feed parse results in execution order. SOL, native or wrapped, is the quote: buying a
+/// token adds its SOL value and fees to the position's cost, and selling realizes the
+/// proceeds less fees over the average cost of the amount sold. Swaps between two tokens
+/// sell one and buy the other at the trade's `volumeSol`; trades without a SOL value are
+/// skipped. Router trades (Jupiter, OKX) stand for their whole route, so a transaction with
+/// one only counts its router trades. Transaction fees and tips count when the wallet paid
+/// them, split evenly across its trades in the transaction.
+#[derive(Clone, Debug)]
+pub struct PnlTracker {
+    wallet: String,
+    positions: HashMap<String, Position>,
+    /// Last SOL price per token seen in any trade, for unrealized PnL.
+    marks: HashMap<String, f64>,
+}
+
+impl PnlTracker {
+    pub fn new(wallet: impl Into<String>) -> Self {
+        Self {
+            wallet: wallet.into(),
+            positions: HashMap::new(),
+            marks: HashMap::new(),
+        }
+    }
+
+    pub fn wallet(&self) -> &str {
+        &self.wallet
+    }
+
+    pub fn position(&self, mint: &str) -> Option<&Position> {
+        self.positions.get(mint)
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = &Position> {
+        self.positions.values()
+    }
+
+    pub fn realized_pnl(&self) -> f64 {
+        self.positions
+            .values()
+            .map(|position| position.realized_pnl_sol)
+            .sum()
+    }
+
+    /// Open positions marked at the last price seen for their mint, less what they cost.
+    pub fn unrealized_pnl(&self) -> f64 {
+        self.positions
+            .values()
+            .filter_map(|position| {
+                let mark = self.marks.get(&position.mint)?;
+                Some(position.amount * mark - position.cost_sol)
+            })
+            .sum()
+    }
+
+    /// Record one transaction and return the PnL of the wallet's trades in it.
+    pub fn observe(&mut self, result: &ParseResult) -> Vec<PnlEvent> {
+        for trade in &result.trades {
+            if let Some((mint, price)) = sol_price(trade) {
+                self.marks.insert(mint.to_string(), price);
+            }
+        }
+
+        let mut trades: Vec<&TradeInfo> = result
+            .trades
+            .iter()
+            .filter(|trade| trader(result, trade) == Some(self.wallet.as_str()))
+            .collect();
+        if trades.iter().any(|trade| is_router_trade(trade)) {
+            trades.retain(|trade| is_router_trade(trade));
+        }
+        if trades.is_empty() {
+            return Vec::new();
+        }
+
+        let network_fee = if result.signer.first() == Some(&self.wallet) {
+            [&result.fees.base, &result.fees.priority, &result.fees.tip]
+                .into_iter()
+                .map(ui_amount)
+                .sum::<f64>()
+                / trades.len() as f64
+        } else {
+            0.0
+        };
+
+        let mut events = Vec::new();
+        for trade in trades {
+            let Some(value_sol) = trade_value(trade) else {
+                continue;
+            };
+            let fee_sol = network_fee
+                + trade
+                    .fee
+                    .as_ref()
+                    .filter(|fee| is_sol(&fee.mint))
+                    .map_or(0.0, |fee| fee.amount);
+            let legs = [
+                (&trade.input_token, TradeType::Sell),
+                (&trade.output_token, TradeType::Buy),
+            ];
+            let legs: Vec<_> = legs
+                .into_iter()
+                .filter(|(token, _)| !is_sol(&token.mint))
+                .collect();
+            // A token-to-token swap pays its fees once, on the buy.
+            let legs_count = legs.len();
+            for (token, side) in legs {
+                let fee = if side == TradeType::Buy || legs_count == 1 {
+                    fee_sol
+                } else {
+                    0.0
+                };
+                events.push(self.apply(result, trade, token, side, value_sol, fee));
+            }
+        }
+        events
+    }
+
+    fn apply(
+        &mut self,
+        result: &ParseResult,
+        trade: &TradeInfo,
+        token: &TokenInfo,
+        side: TradeType,
+        value_sol: f64,
+        fee_sol: f64,
+    ) -> PnlEvent {
+        let position = self
+            .positions
+            .entry(token.mint.clone())
+            .or_insert_with(|| Position {
+                mint: token.mint.clone(),
+                ..Position::default()
+            });
+        let mut realized = 0.0;
+        if side == TradeType::Buy {
+            position.amount += token.amount;
+            position.cost_sol += value_sol + fee_sol;
+        } else {
+            // Only the tracked part of a sale is realized: tokens that came from outside the
+            // tracked trades have no known cost.
+            let sold = token.amount.min(position.amount);
+            let cost = position
+                .average_cost()
+                .map_or(0.0, |average| average * sold);
+            let proceeds = if token.amount > 0.0 {
+                value_sol * sold / token.amount
+            } else {
+                0.0
+            };
+            realized = proceeds - fee_sol - cost;
+            position.amount -= sold;
+            position.cost_sol -= cost;
+            position.realized_pnl_sol += realized;
+        }
+
+        let price = (token.amount > 0.0).then(|| value_sol / token.amount);
+        PnlEvent {
+            signature: result.signature.clone(),
+            idx: trade.idx.clone(),
+            mint: token.mint.clone(),
+            trade_type: side,
+            amount: token.amount,
+            value_sol,
+            fee_sol,
+            position_amount: position.amount,
+            average_cost: position.average_cost(),
+            realized_pnl_sol: realized,
+            unrealized_pnl_sol: price
+                .map_or(0.0, |price| position.amount * price - position.cost_sol),
+        }
+    }
+}
+
+fn is_sol(mint: &str) -> bool {
+    mint == tokens::SOL
+}
+
+fn ui_amount(amount: &TokenAmount) -> f64 {
+    amount.ui_amount.unwrap_or_else(|| {
+        convert_to_ui_amount(amount.amount.parse::<u128>().unwrap_or(0), amount.decimals)
+    })
+}
+
+/// SOL side of the trade, else its `volumeSol`.
+fn trade_value(trade: &TradeInfo) -> Option<f64> {
+    if is_sol(&trade.input_token.mint) {
+        Some(trade.input_token.amount)
+    } else if is_sol(&trade.output_token.mint) {
+        Some(trade.output_token.amount)
+    } else {
+        trade.volume_sol
+    }
+}
+
+/// Token and SOL price per token of a trade against SOL.
+fn sol_price(trade: &TradeInfo) -> Option<(&str, f64)> {
+    let (sol, token) = if is_sol(&trade.input_token.mint) {
+        (&trade.input_token, &trade.output_token)
+    } else if is_sol(&trade.output_token.mint) {
+        (&trade.output_token, &trade.input_token)
+    } else {
+        return None;
+    };
+    (token.amount > 0.0).then(|| (token.mint.as_str(), sol.amount / token.amount))
+}
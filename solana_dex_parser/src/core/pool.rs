@@ -0,0 +1,135 @@
+use std::ops::Deref;
+use std::sync::{Arc, Condvar, Mutex, PoisonError, RwLock};
+
+use crate::config::ParseConfig;
+use crate::core::dex_parser::{DexParser, NormalizeMiddleware, PostProcessor};
+use crate::core::price::{CachedPriceProvider, PriceProvider};
+use crate::types::{ParseResult, SolanaTransaction};
+
+/// Fixed set of pre-built parsers shared between threads.
+///
+/// Each call checks out an idle parser and blocks while all of them are busy. The parse
+/// config and the price cache are shared by every parser in the pool.
+pub struct ParserPool {
+    idle: Mutex<Vec<DexParser>>,
+    returned: Condvar,
+    size: usize,
+    config: RwLock<ParseConfig>,
+    price_cache: Option<Arc<CachedPriceProvider>>,
+}
+
+impl ParserPool {
+    /// Build `size` parsers up front; a pool always holds at least one.
+    pub fn new(size: usize) -> Self {
+        let size = size.max(1);
+        Self {
+            idle: Mutex::new((0..size).map(|_| DexParser::new()).collect()),
+            returned: Condvar::new(),
+            size,
+            config: RwLock::new(ParseConfig::default()),
+            price_cache: None,
+        }
+    }
+
+    /// Value trades through `provider`, caching its answers across all pooled parsers.
+    pub fn with_price_provider(mut self, provider: Arc<dyn PriceProvider>) -> Self {
+        let cache = Arc::new(CachedPriceProvider::new(provider));
+        let cached: Arc<dyn PriceProvider> = cache.clone();
+        self.rebuild(|parser| parser.with_price_provider(cached.clone()));
+        self.price_cache = Some(cache);
+        self
+    }
+
+    /// Run `middleware` on every transaction parsed by the pool.
+    pub fn with_middleware(mut self, middleware: NormalizeMiddleware) -> Self {
+        self.rebuild(|parser| parser.with_middleware(middleware.clone()));
+        self
+    }
+
+    /// Run `post_processor` on every result produced by the pool.
+    pub fn with_post_processor(mut self, post_processor: PostProcessor) -> Self {
+        self.rebuild(|parser| parser.with_post_processor(post_processor.clone()));
+        self
+    }
+
+    fn rebuild(&mut self, mut build: impl FnMut(DexParser) -> DexParser) {
+        let idle = self.idle.get_mut().unwrap_or_else(PoisonError::into_inner);
+        *idle = std::mem::take(idle).into_iter().map(&mut build).collect();
+    }
+
+    pub fn with_config(self, config: ParseConfig) -> Self {
+        self.set_config(config);
+        self
+    }
+
+    /// Replace the config used by subsequent [`ParserPool::parse`] calls.
+    pub fn set_config(&self, config: ParseConfig) {
+        *self.config.write().unwrap_or_else(PoisonError::into_inner) = config;
+    }
+
+    pub fn config(&self) -> ParseConfig {
+        self.config
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Entries in the shared price cache; zero without a price provider.
+    pub fn price_cache_len(&self) -> usize {
+        self.price_cache.as_ref().map_or(0, |cache| cache.len())
+    }
+
+    /// Parse `tx` with the shared config on the next idle parser.
+    pub fn parse(&self, tx: SolanaTransaction) -> ParseResult {
+        let config = self.config();
+        self.checkout().parse_all(tx, Some(config))
+    }
+
+    /// Borrow an idle parser, waiting for one to be returned if all are in use.
+    pub fn checkout(&self) -> PooledParser<'_> {
+        let mut idle = self.idle.lock().unwrap_or_else(PoisonError::into_inner);
+        loop {
+            if let Some(parser) = idle.pop() {
+                return PooledParser {
+                    pool: self,
+                    parser: Some(parser),
+                };
+            }
+            idle = self
+                .returned
+                .wait(idle)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+    }
+}
+
+/// Parser checked out of a [`ParserPool`]; it goes back to the pool when dropped.
+pub struct PooledParser<'a> {
+    pool: &'a ParserPool,
+    parser: Option<DexParser>,
+}
+
+impl Deref for PooledParser<'_> {
+    type Target = DexParser;
+
+    fn deref(&self) -> &DexParser {
+        self.parser.as_ref().expect("parser is held until drop")
+    }
+}
+
+impl Drop for PooledParser<'_> {
+    fn drop(&mut self) {
+        if let Some(parser) = self.parser.take() {
+            self.pool
+                .idle
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .push(parser);
+            self.pool.returned.notify_one();
+        }
+    }
+}
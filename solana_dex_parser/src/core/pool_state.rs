@@ -0,0 +1,74 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::types::{ParseResult, PoolState};
+
+/// Source of pool account state, e.g. an RPC client or an account snapshot.
+pub trait PoolStateProvider: Send + Sync {
+    /// Reserves and liquidity of `pool` at the end of `slot`, if known.
+    fn pool_state(&self, pool: &str, slot: u64) -> Option<PoolState>;
+}
+
+/// Pool states fetched ahead of parsing, keyed by pool address and slot.
+#[derive(Clone, Debug, Default)]
+pub struct PrefetchedPoolStates {
+    states: HashMap<(String, u64), PoolState>,
+}
+
+impl PrefetchedPoolStates {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, pool: impl Into<String>, state: PoolState) {
+        self.states.insert((pool.into(), state.slot), state);
+    }
+
+    pub fn len(&self) -> usize {
+        self.states.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.states.is_empty()
+    }
+}
+
+impl<P: Into<String>> FromIterator<(P, PoolState)> for PrefetchedPoolStates {
+    fn from_iter<I: IntoIterator<Item = (P, PoolState)>>(iter: I) -> Self {
+        let mut states = Self::new();
+        for (pool, state) in iter {
+            states.insert(pool, state);
+        }
+        states
+    }
+}
+
+impl PoolStateProvider for PrefetchedPoolStates {
+    fn pool_state(&self, pool: &str, slot: u64) -> Option<PoolState> {
+        self.states.get(&(pool.to_string(), slot)).cloned()
+    }
+}
+
+/// Attach `pool_state` to the trades of a block.
+///
+/// Account state is only observable at slot boundaries, so the state of a pool at the end
+/// of a slot is the post-trade state of the last trade on that pool in the slot; earlier
+/// trades on the same pool are left without one. Each pool is looked up once per slot.
+///
+/// Results are taken in [`ParseResult::block_position`] order, so "last" follows the
+/// transaction index within the block rather than the order of `results`.
+pub fn attach_pool_states(results: &mut [ParseResult], provider: &dyn PoolStateProvider) {
+    let mut order: Vec<usize> = (0..results.len()).collect();
+    order.sort_by_key(|&position| results[position].block_position());
+    let mut seen = HashSet::new();
+    for position in order.into_iter().rev() {
+        for trade in results[position].trades.iter_mut().rev() {
+            let Some(pool) = trade.pool.first() else {
+                continue;
+            };
+            if !seen.insert((pool.clone(), trade.slot)) {
+                continue;
+            }
+            trade.pool_state = provider.pool_state(pool, trade.slot);
+        }
+    }
+}
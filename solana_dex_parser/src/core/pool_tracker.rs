@@ -0,0 +1,248 @@
+//! Running reserves of the pools seen in a stream of parsed blocks.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::amounts::convert_to_ui_amount;
+use crate::core::constants::tokens;
+use crate::core::flow::is_router_trade;
+use crate::types::{
+    BlockParseResult, ParseResult, PoolEvent, PoolReserves, TokenInfo, TradeInfo, TradeType,
+};
+
+/// Last known state of one pool.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TrackedPool {
+    pub pool: String,
+    pub program_id: Option<String>,
+    pub reserves: PoolReserves,
+    /// Quote tokens per whole base token after the last update: the trade's `priceAfter`,
+    /// else the reserve ratio.
+    pub price: Option<f64>,
+    pub slot: u64,
+    pub signature: String,
+}
+
+impl TrackedPool {
+    pub fn base_reserve(&self) -> f64 {
+        ui(&self.reserves.base_reserve, self.reserves.base_decimals)
+    }
+
+    pub fn quote_reserve(&self) -> f64 {
+        ui(&self.reserves.quote_reserve, self.reserves.quote_decimals)
+    }
+
+    /// Value of both reserves in the quote token, at `price`.
+    pub fn tvl(&self) -> Option<f64> {
+        Some(self.quote_reserve() + self.base_reserve() * self.price?)
+    }
+
+    /// Value of both reserves in SOL, for pools with a SOL side.
+    pub fn tvl_sol(&self) -> Option<f64> {
+        if self.reserves.quote_mint == tokens::SOL {
+            self.tvl()
+        } else if self.reserves.base_mint == tokens::SOL {
+            let price = self.price.filter(|price| *price > 0.0)?;
+            Some(self.base_reserve() + self.quote_reserve() / price)
+        } else {
+            None
+        }
+    }
+
+    fn reserve_mut(&mut self, mint: &str) -> Option<&mut String> {
+        if self.reserves.base_mint == mint {
+            Some(&mut self.reserves.base_reserve)
+        } else if self.reserves.quote_mint == mint {
+            Some(&mut self.reserves.quote_reserve)
+        } else {
+            None
+        }
+    }
+
+    /// Move `amount` raw units of `mint` into the pool, or out of it when `inflow` is unset.
+    fn shift(&mut self, mint: &str, amount: &str, inflow: bool) {
+        let Ok(amount) = amount.parse::<u128>() else {
+            return;
+        };
+        let Some(reserve) = self.reserve_mut(mint) else {
+            return;
+        };
+        let current = reserve.parse::<u128>().unwrap_or(0);
+        let next = if inflow {
+            current.saturating_add(amount)
+        } else {
+            current.saturating_sub(amount)
+        };
+        *reserve = next.to_string();
+    }
+}
+
+/// Keeps the reserves of every pool seen in the parse results fed to it.
+///
+/// Feed results in execution order, block by block with [`PoolStateTracker::observe_block`]
+/// or one transaction at a time. A pool is tracked from its first `Create` event or from the
+/// first trade that reports its reserves (`poolReserves`). Reported reserves replace the
+/// tracked ones; liquidity adds and removes, and trades without reported reserves, move the
+/// tracked reserves by their amounts until the next report. Within a transaction, liquidity
+/// events apply before trades. Router trades are skipped, their hops carry the pool moves.
+#[derive(Clone, Debug, Default)]
+pub struct PoolStateTracker {
+    pools: HashMap<String, TrackedPool>,
+}
+
+impl PoolStateTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn pool(&self, pool: &str) -> Option<&TrackedPool> {
+        self.pools.get(pool)
+    }
+
+    pub fn pools(&self) -> impl Iterator<Item = &TrackedPool> {
+        self.pools.values()
+    }
+
+    pub fn len(&self) -> usize {
+        self.pools.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pools.is_empty()
+    }
+
+    /// Record the transactions of a block, in [`ParseResult::block_position`] order.
+    pub fn observe_block(&mut self, block: &BlockParseResult) {
+        let mut transactions: Vec<&ParseResult> = block.transactions.iter().collect();
+        transactions.sort_by_key(|result| result.block_position());
+        for result in transactions {
+            self.observe(result);
+        }
+    }
+
+    /// Record one transaction.
+    pub fn observe(&mut self, result: &ParseResult) {
+        for event in &result.liquidities {
+            self.apply_liquidity(result, event);
+        }
+        for trade in &result.trades {
+            if !is_router_trade(trade) {
+                self.apply_trade(result, trade);
+            }
+        }
+    }
+
+    fn apply_liquidity(&mut self, result: &ParseResult, event: &PoolEvent) {
+        if event.event_type == TradeType::Create {
+            let pool = self
+                .pools
+                .entry(event.pool_id.clone())
+                .or_insert_with(|| TrackedPool {
+                    pool: event.pool_id.clone(),
+                    reserves: created_reserves(event),
+                    ..TrackedPool::default()
+                });
+            pool.program_id = event.program_id.clone().or(pool.program_id.take());
+            pool.price = pool.reserves.price();
+            pool.slot = result.slot;
+            pool.signature = result.signature.clone();
+            return;
+        }
+
+        let inflow = match event.event_type {
+            TradeType::Add => true,
+            TradeType::Remove => false,
+            _ => return,
+        };
+        let Some(pool) = self.pools.get_mut(&event.pool_id) else {
+            return;
+        };
+        let legs = [
+            (&event.token0_mint, &event.token0_amount_raw),
+            (&event.token1_mint, &event.token1_amount_raw),
+        ];
+        for (mint, amount) in legs {
+            if let (Some(mint), Some(amount)) = (mint, amount) {
+                pool.shift(mint, amount, inflow);
+            }
+        }
+        pool.slot = result.slot;
+        pool.signature = result.signature.clone();
+    }
+
+    fn apply_trade(&mut self, result: &ParseResult, trade: &TradeInfo) {
+        let Some(pool_id) = trade.pool.first() else {
+            return;
+        };
+        let pool = match (&trade.pool_reserves, self.pools.get_mut(pool_id)) {
+            (Some(reserves), Some(pool)) => {
+                pool.reserves = reserves.clone();
+                pool
+            }
+            (Some(reserves), None) => {
+                self.pools
+                    .entry(pool_id.clone())
+                    .or_insert_with(|| TrackedPool {
+                        pool: pool_id.clone(),
+                        reserves: reserves.clone(),
+                        ..TrackedPool::default()
+                    })
+            }
+            (None, Some(pool)) => {
+                shift_by(pool, &trade.input_token, true);
+                shift_by(pool, &trade.output_token, false);
+                pool
+            }
+            (None, None) => return,
+        };
+        if pool.program_id.is_none() {
+            pool.program_id = trade.program_id.clone();
+        }
+        pool.price = trade.price_after.or_else(|| pool.reserves.price());
+        pool.slot = result.slot;
+        pool.signature = result.signature.clone();
+    }
+}
+
+fn shift_by(pool: &mut TrackedPool, token: &TokenInfo, inflow: bool) {
+    pool.shift(&token.mint, &token.amount_raw, inflow);
+}
+
+/// Initial reserves of a created pool, SOL taken as the quote when it is one of the sides.
+fn created_reserves(event: &PoolEvent) -> PoolReserves {
+    let token0 = (
+        event.token0_mint.clone().unwrap_or_default(),
+        event
+            .token0_amount_raw
+            .clone()
+            .unwrap_or_else(|| "0".into()),
+        event.token0_decimals.unwrap_or_default(),
+    );
+    let token1 = (
+        event.token1_mint.clone().unwrap_or_default(),
+        event
+            .token1_amount_raw
+            .clone()
+            .unwrap_or_else(|| "0".into()),
+        event.token1_decimals.unwrap_or_default(),
+    );
+    let (base, quote) = if token0.0 == tokens::SOL {
+        (token1, token0)
+    } else {
+        (token0, token1)
+    };
+    PoolReserves {
+        base_mint: base.0,
+        base_reserve: base.1,
+        base_decimals: base.2,
+        quote_mint: quote.0,
+        quote_reserve: quote.1,
+        quote_decimals: quote.2,
+    }
+}
+
+fn ui(raw: &str, decimals: u8) -> f64 {
+    convert_to_ui_amount(raw.parse::<u128>().unwrap_or(0), decimals)
+}
@@ -0,0 +1,249 @@
+use std::collections::HashMap;
+use std::sync::{Arc, PoisonError, RwLock};
+
+use crate::core::constants::tokens;
+use crate::types::{MemeEvent, TokenInfo, TradeInfo};
+
+/// External source of token prices quoted in SOL.
+pub trait PriceProvider: Send + Sync {
+    /// Price of one whole `mint` token in SOL around `timestamp`, if known.
+    fn price_in_sol(&self, mint: &str, timestamp: u64) -> Option<f64>;
+
+    /// Price of one SOL in USD around `timestamp`, if known.
+    fn sol_price_usd(&self, _timestamp: u64) -> Option<f64> {
+        None
+    }
+}
+
+/// Lookups kept by [`CachedPriceProvider`] before the cache is reset.
+const PRICE_CACHE_CAPACITY: usize = 65_536;
+
+/// Memoises another provider's answers, including misses, per mint and timestamp.
+pub struct CachedPriceProvider {
+    inner: Arc<dyn PriceProvider>,
+    cache: RwLock<HashMap<(String, u64), Option<f64>>>,
+    sol_usd_cache: RwLock<HashMap<u64, Option<f64>>>,
+}
+
+impl CachedPriceProvider {
+    pub fn new(inner: Arc<dyn PriceProvider>) -> Self {
+        Self {
+            inner,
+            cache: RwLock::new(HashMap::new()),
+            sol_usd_cache: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Number of cached lookups, misses included.
+    pub fn len(&self) -> usize {
+        self.cache
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl PriceProvider for CachedPriceProvider {
+    fn price_in_sol(&self, mint: &str, timestamp: u64) -> Option<f64> {
+        let key = (mint.to_string(), timestamp);
+        if let Some(price) = self
+            .cache
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&key)
+        {
+            return *price;
+        }
+
+        let price = self.inner.price_in_sol(mint, timestamp);
+        let mut cache = self.cache.write().unwrap_or_else(PoisonError::into_inner);
+        if cache.len() >= PRICE_CACHE_CAPACITY {
+            cache.clear();
+        }
+        cache.insert(key, price);
+        price
+    }
+
+    fn sol_price_usd(&self, timestamp: u64) -> Option<f64> {
+        if let Some(price) = self
+            .sol_usd_cache
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&timestamp)
+        {
+            return *price;
+        }
+
+        let price = self.inner.sol_price_usd(timestamp);
+        let mut cache = self
+            .sol_usd_cache
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
+        if cache.len() >= PRICE_CACHE_CAPACITY {
+            cache.clear();
+        }
+        cache.insert(timestamp, price);
+        price
+    }
+}
+
+/// SOL price in USD implied by SOL swaps against USDC or USDT, weighted by the SOL traded.
+///
+/// Built from the trades of one transaction or one block, it prices the rest of them without
+/// an external feed; as a [`PriceProvider`] it quotes SOL and the stablecoins.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct StablePairPrice {
+    sol_usd: Option<f64>,
+}
+
+impl StablePairPrice {
+    pub fn from_trades<'a>(trades: impl IntoIterator<Item = &'a TradeInfo>) -> Self {
+        let (sol, usd) = trades
+            .into_iter()
+            .filter_map(sol_stable_legs)
+            .fold((0.0, 0.0), |(sol, usd), (leg_sol, leg_usd)| {
+                (sol + leg_sol, usd + leg_usd)
+            });
+        Self {
+            sol_usd: (sol > 0.0 && usd > 0.0).then(|| usd / sol),
+        }
+    }
+
+    /// Price of one SOL in USD, when a stable pair traded.
+    pub fn sol_usd(&self) -> Option<f64> {
+        self.sol_usd
+    }
+}
+
+impl PriceProvider for StablePairPrice {
+    fn price_in_sol(&self, mint: &str, _timestamp: u64) -> Option<f64> {
+        if is_stablecoin(mint) {
+            self.sol_usd.map(|price| 1.0 / price)
+        } else {
+            None
+        }
+    }
+
+    fn sol_price_usd(&self, _timestamp: u64) -> Option<f64> {
+        self.sol_usd
+    }
+}
+
+fn is_stablecoin(mint: &str) -> bool {
+    mint == tokens::USDC || mint == tokens::USDT
+}
+
+/// `(SOL, USD)` amounts of a trade between SOL and a stablecoin.
+fn sol_stable_legs(trade: &TradeInfo) -> Option<(f64, f64)> {
+    let (input, output) = (&trade.input_token, &trade.output_token);
+    if input.mint == tokens::SOL && is_stablecoin(&output.mint) {
+        Some((input.amount, output.amount))
+    } else if output.mint == tokens::SOL && is_stablecoin(&input.mint) {
+        Some((output.amount, input.amount))
+    } else {
+        None
+    }
+}
+
+/// Populate `volume_usd` and `price_usd` on the trades and meme events that lack them.
+///
+/// A stablecoin leg is taken at face value; otherwise the SOL leg, or `volume_sol` for
+/// trades, is converted at `sol_usd`. The price is the USD value per whole token of the leg
+/// that is neither SOL nor a stablecoin, or of the SOL leg for stable pairs.
+pub fn fill_usd_values(
+    trades: &mut [TradeInfo],
+    meme_events: &mut [MemeEvent],
+    sol_usd: Option<f64>,
+) {
+    for trade in trades.iter_mut().filter(|trade| trade.volume_usd.is_none()) {
+        let legs = [&trade.input_token, &trade.output_token];
+        let volume_usd = stable_leg(legs).or_else(|| Some(trade.volume_sol? * sol_usd?));
+        trade.volume_usd = volume_usd;
+        trade.price_usd = volume_usd.and_then(|volume| unit_price(volume, priced_leg(legs)?));
+    }
+    for event in meme_events
+        .iter_mut()
+        .filter(|event| event.volume_usd.is_none())
+    {
+        let legs: Vec<&TokenInfo> = [&event.input_token, &event.output_token]
+            .into_iter()
+            .flatten()
+            .collect();
+        let volume_usd = stable_leg(legs.iter().copied()).or_else(|| {
+            let sol = legs.iter().find(|token| token.mint == tokens::SOL)?;
+            Some(sol.amount * sol_usd?)
+        });
+        event.volume_usd = volume_usd;
+        event.price_usd = volume_usd.and_then(|volume| {
+            let base = legs.iter().find(|token| token.mint == event.base_mint)?;
+            unit_price(volume, base)
+        });
+    }
+}
+
+fn stable_leg<'a>(legs: impl IntoIterator<Item = &'a TokenInfo>) -> Option<f64> {
+    legs.into_iter()
+        .find(|token| is_stablecoin(&token.mint))
+        .map(|token| token.amount)
+}
+
+/// Leg whose token the trade's USD price is quoted for.
+fn priced_leg(legs: [&TokenInfo; 2]) -> Option<&TokenInfo> {
+    legs.into_iter()
+        .rev()
+        .find(|token| token.mint != tokens::SOL && !is_stablecoin(&token.mint))
+        .or_else(|| legs.into_iter().find(|token| token.mint == tokens::SOL))
+}
+
+fn unit_price(volume_usd: f64, token: &TokenInfo) -> Option<f64> {
+    (token.amount > 0.0).then(|| volume_usd / token.amount)
+}
+
+/// Populate `volume_sol` on every trade.
+///
+/// Trades with a SOL leg use it directly. Otherwise a price implied by another SOL-paired
+/// trade in the same transaction is preferred, falling back to the provider.
+pub fn fill_volume_sol(trades: &mut [TradeInfo], provider: Option<&dyn PriceProvider>) {
+    let implied = implied_sol_prices(trades);
+    for trade in trades.iter_mut() {
+        let price_of = |token: &TokenInfo| {
+            implied.get(&token.mint).copied().or_else(|| {
+                provider.and_then(|provider| provider.price_in_sol(&token.mint, trade.timestamp))
+            })
+        };
+        trade.volume_sol = sol_leg(trade)
+            .or_else(|| price_of(&trade.input_token).map(|price| price * trade.input_token.amount))
+            .or_else(|| {
+                price_of(&trade.output_token).map(|price| price * trade.output_token.amount)
+            });
+    }
+}
+
+fn sol_leg(trade: &TradeInfo) -> Option<f64> {
+    [&trade.input_token, &trade.output_token]
+        .into_iter()
+        .find(|token| token.mint == tokens::SOL)
+        .map(|token| token.amount)
+}
+
+/// SOL price per token implied by trades that swap the token directly against SOL.
+fn implied_sol_prices(trades: &[TradeInfo]) -> HashMap<String, f64> {
+    let mut prices = HashMap::new();
+    for trade in trades {
+        let (sol, token) = if trade.input_token.mint == tokens::SOL {
+            (&trade.input_token, &trade.output_token)
+        } else if trade.output_token.mint == tokens::SOL {
+            (&trade.output_token, &trade.input_token)
+        } else {
+            continue;
+        };
+        if token.amount > 0.0 {
+            prices.insert(token.mint.clone(), sol.amount / token.amount);
+        }
+    }
+    prices
+}
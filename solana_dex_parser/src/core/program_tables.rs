@@ -0,0 +1,141 @@
+//! Program names and discriminators loaded at runtime, so venues without a bundled decoder
+//! can at least be named and counted.
+//!
+//! Tables are set per config through [`ParseConfig::program_tables`](crate::ParseConfig) and
+//! extend the built-in ones: [`ParseConfig::program_name`](crate::ParseConfig::program_name)
+//! and [`registry::discriminators_with`](crate::protocols::registry::discriminators_with)
+//! consult them after their own entries.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+use std::sync::Arc;
+
+use arc_swap::ArcSwap;
+use serde::{Deserialize, Serialize};
+
+use crate::core::error::ParserError;
+use crate::protocols::registry::DiscriminatorKind;
+
+/// Discriminator of an external program, as listed in a program table file.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExternalDiscriminator {
+    pub kind: DiscriminatorKind,
+    pub name: String,
+    pub discriminator: Vec<u8>,
+}
+
+/// Program of a program table file, with the name it is reported under.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExternalProgram {
+    pub program_id: String,
+    pub name: String,
+    #[serde(default)]
+    pub discriminators: Vec<ExternalDiscriminator>,
+}
+
+/// Contents of a program table file:
+/// `{"programs": [{"programId", "name", "discriminators": [{"kind", "name", "discriminator"}]}]}`
+/// in JSON, or the same layout in TOML with `[[programs]]` tables.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProgramTables {
+    #[serde(default)]
+    pub programs: Vec<ExternalProgram>,
+}
+
+impl ProgramTables {
+    pub fn from_json_str(text: &str) -> Result<Self, ParserError> {
+        serde_json::from_str(text)
+            .map_err(|err| ParserError::generic(format!("invalid program table: {err}")))
+    }
+
+    pub fn from_toml_str(text: &str) -> Result<Self, ParserError> {
+        toml::from_str(text)
+            .map_err(|err| ParserError::generic(format!("invalid program table: {err}")))
+    }
+
+    /// Read a `.toml` file as TOML and anything else as JSON.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ParserError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).map_err(|err| {
+            ParserError::generic(format!("failed to read {}: {err}", path.display()))
+        })?;
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("toml") => Self::from_toml_str(&text),
+            _ => Self::from_json_str(&text),
+        }
+    }
+}
+
+#[derive(Default)]
+struct Tables {
+    tables: ProgramTables,
+    names: HashMap<String, String>,
+}
+
+impl From<ProgramTables> for Tables {
+    fn from(tables: ProgramTables) -> Self {
+        let names = tables
+            .programs
+            .iter()
+            .map(|program| (program.program_id.clone(), program.name.clone()))
+            .collect();
+        Self { tables, names }
+    }
+}
+
+/// Program tables shared by every config cloned from the one they were set on.
+///
+/// Storing new tables swaps them in for all holders at once without blocking parses in
+/// flight, which is how a reload works.
+#[derive(Clone, Default)]
+pub struct SharedProgramTables(Arc<ArcSwap<Tables>>);
+
+impl SharedProgramTables {
+    pub fn new(tables: ProgramTables) -> Self {
+        Self(Arc::new(ArcSwap::from_pointee(tables.into())))
+    }
+
+    /// Load the tables of `path` into a new handle.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ParserError> {
+        Ok(Self::new(ProgramTables::from_file(path)?))
+    }
+
+    /// Replace the tables with `tables`.
+    pub fn store(&self, tables: ProgramTables) {
+        self.0.store(Arc::new(tables.into()));
+    }
+
+    /// Replace the tables with those of `path`, keeping the current ones when it fails to load.
+    pub fn reload(&self, path: impl AsRef<Path>) -> Result<(), ParserError> {
+        self.store(ProgramTables::from_file(path)?);
+        Ok(())
+    }
+
+    /// Name listed for `program_id`.
+    pub fn program_name(&self, program_id: &str) -> Option<String> {
+        self.0.load().names.get(program_id).cloned()
+    }
+
+    /// Tables currently in place.
+    pub fn tables(&self) -> ProgramTables {
+        self.0.load().tables.clone()
+    }
+}
+
+impl PartialEq for SharedProgramTables {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl fmt::Debug for SharedProgramTables {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SharedProgramTables")
+            .field(&self.0.load().tables)
+            .finish()
+    }
+}
@@ -0,0 +1,35 @@
+//! Pool reserves and prices right after a trade, reported on `TradeInfo`.
+
+use crate::core::transaction_adapter::TransactionAdapter;
+use crate::types::PoolReserves;
+
+/// Price of token A in token B from a Q64.64 square-root price, as concentrated-liquidity
+/// pools (Raydium CLMM, Orca Whirlpool) store it.
+pub fn sqrt_price_x64_to_price(sqrt_price_x64: u128, decimals_a: u8, decimals_b: u8) -> f64 {
+    let sqrt_price = sqrt_price_x64 as f64 / 2f64.powi(64);
+    sqrt_price * sqrt_price * 10f64.powi(i32::from(decimals_a) - i32::from(decimals_b))
+}
+
+/// Balances of a pool's two vaults at the end of the transaction.
+pub fn vault_reserves(
+    adapter: &TransactionAdapter,
+    base_vault: &str,
+    quote_vault: &str,
+) -> Option<PoolReserves> {
+    let balance = |vault: &str| {
+        adapter
+            .post_token_balances()
+            .iter()
+            .find(|balance| balance.account == vault)
+    };
+    let base = balance(base_vault)?;
+    let quote = balance(quote_vault)?;
+    Some(PoolReserves {
+        base_mint: base.mint.clone(),
+        base_reserve: base.ui_token_amount.amount.clone(),
+        base_decimals: base.ui_token_amount.decimals,
+        quote_mint: quote.mint.clone(),
+        quote_reserve: quote.ui_token_amount.amount.clone(),
+        quote_decimals: quote.ui_token_amount.decimals,
+    })
+}
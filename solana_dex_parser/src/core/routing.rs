@@ -0,0 +1,98 @@
+use crate::core::flow::is_router_trade;
+use crate::core::transaction_adapter::TransactionAdapter;
+use crate::types::TradeInfo;
+
+/// Improvement of a routed trade over the best single venue, in basis points.
+///
+/// The route runs from the mint that hops only sell to the mint they only buy. Every hop
+/// must expose its pool vaults (`input_token.destination` and
+/// `output_token.source`) with pre-transaction balances. A single-venue execution of the
+/// whole route input is then estimated on every hop pool that trades the route's pair
+/// directly, using the constant-product curve on its pre-trade reserves scaled by the
+/// efficiency the pool actually delivered on its own hop, which accounts for its fee.
+/// Returns `None` for single-hop trades, when a hop lacks reserves, or when no pool trades
+/// the pair directly.
+pub fn routing_improvement_bps(adapter: &TransactionAdapter, trades: &[TradeInfo]) -> Option<i64> {
+    let hops = trades
+        .iter()
+        .filter(|trade| !is_router_trade(trade))
+        .map(|trade| pool_reserves(adapter, trade).map(|reserves| (trade, reserves)))
+        .collect::<Option<Vec<_>>>()?;
+    if hops.len() < 2 {
+        return None;
+    }
+
+    let input_mint = hops
+        .iter()
+        .map(|(trade, _)| trade.input_token.mint.as_str())
+        .find(|mint| {
+            hops.iter()
+                .all(|(trade, _)| trade.output_token.mint != *mint)
+        })?;
+    let output_mint = hops
+        .iter()
+        .map(|(trade, _)| trade.output_token.mint.as_str())
+        .find(|mint| {
+            hops.iter()
+                .all(|(trade, _)| trade.input_token.mint != *mint)
+        })?;
+    let amount_in: f64 = hops
+        .iter()
+        .filter(|(trade, _)| trade.input_token.mint == input_mint)
+        .map(|(trade, _)| raw_amount(&trade.input_token.amount_raw))
+        .sum();
+    let amount_out: f64 = hops
+        .iter()
+        .filter(|(trade, _)| trade.output_token.mint == output_mint)
+        .map(|(trade, _)| raw_amount(&trade.output_token.amount_raw))
+        .sum();
+
+    let best_single = hops
+        .iter()
+        .filter(|(trade, _)| {
+            trade.input_token.mint == input_mint && trade.output_token.mint == output_mint
+        })
+        .filter_map(|(trade, (reserve_in, reserve_out))| {
+            let hop_in = raw_amount(&trade.input_token.amount_raw);
+            let quoted = constant_product_out(*reserve_in, *reserve_out, hop_in);
+            if quoted <= 0.0 {
+                return None;
+            }
+            let efficiency = raw_amount(&trade.output_token.amount_raw) / quoted;
+            Some(constant_product_out(*reserve_in, *reserve_out, amount_in) * efficiency)
+        })
+        .fold(None, |best: Option<f64>, out| {
+            Some(best.map_or(out, |best| best.max(out)))
+        })?;
+    if best_single <= 0.0 {
+        return None;
+    }
+
+    Some(((amount_out - best_single) / best_single * 10_000.0).round() as i64)
+}
+
+/// Pre-trade `(input, output)` reserves of the pool a hop traded against.
+fn pool_reserves(adapter: &TransactionAdapter, trade: &TradeInfo) -> Option<(f64, f64)> {
+    let balances = adapter.pre_token_balances();
+    let reserve = |vault: Option<&String>, mint: &str| {
+        let vault = vault?;
+        balances
+            .iter()
+            .find(|balance| &balance.account == vault && balance.mint == mint)
+            .map(|balance| raw_amount(&balance.ui_token_amount.amount))
+    };
+    let reserve_in = reserve(
+        trade.input_token.destination.as_ref(),
+        &trade.input_token.mint,
+    )?;
+    let reserve_out = reserve(trade.output_token.source.as_ref(), &trade.output_token.mint)?;
+    (reserve_in > 0.0 && reserve_out > 0.0).then_some((reserve_in, reserve_out))
+}
+
+fn constant_product_out(reserve_in: f64, reserve_out: f64, amount_in: f64) -> f64 {
+    reserve_out * amount_in / (reserve_in + amount_in)
+}
+
+fn raw_amount(amount: &str) -> f64 {
+    amount.parse::<u128>().map_or(0.0, |amount| amount as f64)
+}
@@ -0,0 +1,93 @@
+//! Order-book crank and settlement flows, whose token transfers are not swaps.
+
+use crate::core::constants::{dex_programs, orderbook_programs};
+use crate::core::cpi::InvocationScope;
+use crate::core::transaction_adapter::TransactionAdapter;
+use crate::core::util::decode_instruction_data;
+use crate::protocols::openbook::constants::discriminators::openbook_instructions;
+use crate::types::{SolanaInstruction, TransferData, TransferMap};
+
+/// `MarketInstruction` tags of the Serum-style programs (version byte, then a `u32` tag):
+/// `MatchOrders`, `ConsumeEvents`, `SettleFunds` and `ConsumeEventsPermissioned`.
+const SERUM_SETTLEMENT_TAGS: [u32; 4] = [2, 3, 5, 17];
+
+const OPENBOOK_V2_SETTLEMENTS: [[u8; 8]; 3] = [
+    openbook_instructions::SETTLE_FUNDS,
+    openbook_instructions::CONSUME_EVENTS,
+    openbook_instructions::CONSUME_GIVEN_EVENTS,
+];
+
+/// Whether `instruction` cranks an order book or settles funds out of it.
+pub fn is_settlement_instruction(instruction: &SolanaInstruction) -> bool {
+    let program = instruction.program_id.as_str();
+    if !orderbook_programs::ALL.contains(&program) && program != dex_programs::OPENBOOK_V2 {
+        return false;
+    }
+    let data = decode_instruction_data(&instruction.data);
+    if program == dex_programs::OPENBOOK_V2 {
+        return data.len() >= 8 && OPENBOOK_V2_SETTLEMENTS.iter().any(|d| data[..8] == *d);
+    }
+    data.len() >= 5
+        && data[0] == 0
+        && SERUM_SETTLEMENT_TAGS.contains(&u32::from_le_bytes([data[1], data[2], data[3], data[4]]))
+}
+
+/// Cranks and settlements of a transaction, whether outer or reached by CPI, as market
+/// makers and AMMs settling their open orders do.
+pub struct SettlementFlows {
+    scopes: Vec<InvocationScope>,
+}
+
+impl SettlementFlows {
+    pub fn new(adapter: &TransactionAdapter) -> Self {
+        let mut scopes: Vec<InvocationScope> = adapter
+            .instructions()
+            .iter()
+            .enumerate()
+            .filter(|(_, instruction)| is_settlement_instruction(instruction))
+            .map(|(index, _)| InvocationScope::new(adapter, index, None))
+            .collect();
+        for set in adapter.inner_instructions() {
+            scopes.extend(
+                set.instructions
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, instruction)| is_settlement_instruction(instruction))
+                    .map(|(inner_index, _)| {
+                        InvocationScope::new(adapter, set.index, Some(inner_index))
+                    }),
+            );
+        }
+        Self { scopes }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.scopes.is_empty()
+    }
+
+    /// Whether `transfer` was executed by a crank or settlement.
+    pub fn contains(&self, transfer: &TransferData) -> bool {
+        self.scopes
+            .iter()
+            .any(|scope| scope.contains(&transfer.idx))
+    }
+
+    /// `transfer_actions` without the transfers of cranks and settlements.
+    pub fn exclude(&self, transfer_actions: &TransferMap) -> TransferMap {
+        if self.is_empty() {
+            return transfer_actions.clone();
+        }
+        transfer_actions
+            .iter()
+            .map(|(program_id, transfers)| {
+                let kept = transfers
+                    .iter()
+                    .filter(|transfer| !self.contains(transfer))
+                    .cloned()
+                    .collect::<Vec<_>>();
+                (program_id.clone(), kept)
+            })
+            .filter(|(_, transfers)| !transfers.is_empty())
+            .collect()
+    }
+}
@@ -0,0 +1,48 @@
+//! Per-stage wall-clock timing of a parse, reported in `ParseResult::timings`.
+
+use std::time::Instant;
+
+use crate::types::{ParseTimings, StageTiming};
+
+/// Stopwatch split into laps, one per parse stage; a no-op unless enabled.
+pub(crate) struct StageTimer {
+    started: Option<Instant>,
+    lap_started: Option<Instant>,
+    stages: Vec<StageTiming>,
+}
+
+impl StageTimer {
+    pub(crate) fn new(enabled: bool) -> Self {
+        let now = enabled.then(Instant::now);
+        Self {
+            started: now,
+            lap_started: now,
+            stages: Vec::new(),
+        }
+    }
+
+    /// Close the running lap as `stage` and start the next one.
+    pub(crate) fn lap(&mut self, stage: &str) {
+        let Some(lap_started) = self.lap_started else {
+            return;
+        };
+        let now = Instant::now();
+        self.stages.push(StageTiming {
+            stage: stage.to_string(),
+            micros: micros(now - lap_started),
+        });
+        self.lap_started = Some(now);
+    }
+
+    pub(crate) fn finish(self) -> Option<ParseTimings> {
+        let started = self.started?;
+        Some(ParseTimings {
+            total_micros: micros(started.elapsed()),
+            stages: self.stages,
+        })
+    }
+}
+
+fn micros(duration: std::time::Duration) -> u64 {
+    u64::try_from(duration.as_micros()).unwrap_or(u64::MAX)
+}
@@ -0,0 +1,58 @@
+//! Jito bundle tips, reported apart from the transfers they would otherwise pollute.
+
+use crate::core::amounts::convert_to_ui_amount;
+use crate::core::bots::system_transfer;
+use crate::core::constants::jito_tip_accounts;
+use crate::core::constants::tokens::SOL_DECIMALS;
+use crate::core::transaction_adapter::TransactionAdapter;
+use crate::types::{JitoTip, TokenAmount, TransferData};
+
+fn is_tip_account(account: &str) -> bool {
+    jito_tip_accounts::ALL.contains(&account)
+}
+
+/// Whether `transfer` pays one of the Jito tip accounts.
+pub fn is_tip_transfer(transfer: &TransferData) -> bool {
+    is_tip_account(&transfer.info.destination)
+}
+
+/// Native SOL sent to the Jito tip accounts, outer and inner instructions alike.
+///
+/// Several tips in one transaction are summed; recipient and `idx` are those of the first.
+pub fn jito_tip(adapter: &TransactionAdapter) -> Option<JitoTip> {
+    let outer = adapter
+        .instructions()
+        .iter()
+        .enumerate()
+        .map(|(index, instruction)| (index.to_string(), instruction));
+    let inner = adapter.inner_instructions().iter().flat_map(|set| {
+        set.instructions
+            .iter()
+            .enumerate()
+            .map(move |(index, instruction)| (format!("{}-{index}", set.index), instruction))
+    });
+
+    let mut tip: Option<(String, String, u64)> = None;
+    for (idx, instruction) in outer.chain(inner) {
+        let Some((lamports, recipient)) = system_transfer(instruction) else {
+            continue;
+        };
+        if !is_tip_account(recipient) {
+            continue;
+        }
+        match tip.as_mut() {
+            Some((_, _, total)) => *total += lamports,
+            None => tip = Some((recipient.to_string(), idx, lamports)),
+        }
+    }
+
+    tip.map(|(recipient, idx, lamports)| JitoTip {
+        recipient,
+        amount: TokenAmount::new(
+            lamports.to_string(),
+            SOL_DECIMALS,
+            Some(convert_to_ui_amount(lamports as u128, SOL_DECIMALS)),
+        ),
+        idx,
+    })
+}
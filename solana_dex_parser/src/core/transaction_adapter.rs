@@ -1,19 +1,42 @@
-use std::collections::{HashMap, HashSet};
-
-use crate::constants::{SPL_TOKEN_INSTRUCTION_TYPES, TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID, TOKENS};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use solana_sdk::transaction::TransactionError;
+use solana_transaction_status::TransactionStatusMeta;
+
+use crate::config::ParseConfig;
+use crate::core::amounts::convert_to_ui_amount;
+use crate::core::constants::token_programs;
+use crate::core::constants::tokens::{self, SOL_DECIMALS};
+use crate::core::log_events::inner_instructions_from_logs;
+use crate::core::util::decode_instruction_data;
+use crate::rpc;
 use crate::types::{
-    BalanceChange, ParseConfig, PoolEventType, SolanaInstruction, SolanaTransaction, TokenAmount,
-    TokenBalance, TokenInfo, TransactionStatus,
+    BalanceChange, InnerInstruction, SolanaInstruction, SolanaTransaction, TokenAmount,
+    TokenBalance, TokenInfo, TransactionStatus, TransferData, TransferMap, WalletFlow,
 };
-use crate::utils::{decode_instruction_data, get_instruction_data, get_program_name};
+
+/// Номера инструкций SPL Token, из которых извлекаются mint/decimals токен-аккаунтов
+mod spl_token_instructions {
+    pub const TRANSFER: u8 = 3;
+    pub const MINT_TO: u8 = 7;
+    pub const BURN: u8 = 8;
+    pub const CLOSE_ACCOUNT: u8 = 9;
+    pub const TRANSFER_CHECKED: u8 = 12;
+    pub const MINT_TO_CHECKED: u8 = 14;
+    pub const BURN_CHECKED: u8 = 15;
+}
 
 /// Унифицированный адаптер доступа к данным транзакции (аналог TS TransactionAdapter)
 #[derive(Clone, Debug)]
 pub struct TransactionAdapter {
     tx: SolanaTransaction,
-    config: Option<ParseConfig>,
+    config: ParseConfig,
+
+    /// Подпись транзакции, общая для всех событий (одна аллокация на транзакцию)
+    signature: Arc<str>,
 
-    /// Аналог TS: accountKeys[]
+    /// Аналог TS: accountKeys[] — все адреса транзакции в порядке первого появления
     account_keys: Vec<String>,
 
     /// Аналог TS: splTokenMap (карта: токен-аккаунт → инфо о токене)
@@ -24,9 +47,18 @@ pub struct TransactionAdapter {
 }
 
 impl TransactionAdapter {
-    pub fn new(tx: SolanaTransaction, config: Option<ParseConfig>) -> Self {
+    pub fn new(mut tx: SolanaTransaction, config: ParseConfig) -> Self {
+        if config.use_logs_fallback {
+            // провайдер мог вырезать inner-инструкции: восстанавливаем события из логов
+            let rebuilt = inner_instructions_from_logs(&tx);
+            if !rebuilt.is_empty() {
+                tx.inner_instructions.extend(rebuilt);
+                tx.inner_instructions.sort_by_key(|set| set.index);
+            }
+        }
         let account_keys = Self::extract_account_keys(&tx);
         let mut adapter = Self {
+            signature: Arc::from(tx.signature.as_str()),
             tx,
             config,
             account_keys,
@@ -38,14 +70,24 @@ impl TransactionAdapter {
         adapter
     }
 
-    // ===== Соответствие геттерам из TS =====
-
-    pub fn tx_message(&self) -> &crate::types::TxMessage {
-        &self.tx.message
+    /// Адаптер из бинарного `VersionedTransaction` и его status meta, без RPC JSON;
+    /// адреса из lookup-таблиц берутся из `meta.loaded_addresses`
+    pub fn from_wire(
+        bytes: &[u8],
+        meta: TransactionStatusMeta,
+        slot: u64,
+        block_time: Option<i64>,
+        config: ParseConfig,
+    ) -> anyhow::Result<Self> {
+        let tx = rpc::transaction_from_wire(bytes, meta, slot, block_time)?;
+        Ok(Self::new(tx, config))
     }
 
-    pub fn is_message_v0(&self) -> bool {
-        self.tx.message.is_v0()
+    // ===== Соответствие геттерам из TS =====
+
+    /// config (настройки парсинга, включая protocolOverrides)
+    pub fn config(&self) -> &ParseConfig {
+        &self.config
     }
 
     /// slot
@@ -53,14 +95,9 @@ impl TransactionAdapter {
         self.tx.slot
     }
 
-    /// version
-    pub fn version(&self) -> crate::types::Version {
-        self.tx.version
-    }
-
     /// blockTime
     pub fn block_time(&self) -> u64 {
-        self.tx.block_time.unwrap_or(0)
+        self.tx.block_time
     }
 
     /// signature
@@ -68,176 +105,117 @@ impl TransactionAdapter {
         &self.tx.signature
     }
 
+    /// signature для событий: клонирует общий `Arc`, а не строку
+    pub fn shared_signature(&self) -> Arc<str> {
+        Arc::clone(&self.signature)
+    }
+
     /// instructions (outer)
     pub fn instructions(&self) -> &[SolanaInstruction] {
         &self.tx.instructions
     }
 
     /// innerInstructions
-    pub fn inner_instructions(&self) -> &[crate::types::InnerInstruction] {
+    pub fn inner_instructions(&self) -> &[InnerInstruction] {
         &self.tx.inner_instructions
     }
 
-    /// preBalances
-    pub fn pre_balances(&self) -> Option<&[u64]> {
-        self.tx.meta.pre_balances.as_deref()
-    }
-
-    /// postBalances
-    pub fn post_balances(&self) -> Option<&[u64]> {
-        self.tx.meta.post_balances.as_deref()
-    }
-
     /// preTokenBalances
-    pub fn pre_token_balances(&self) -> Option<&[TokenBalance]> {
-        self.tx.pre_token_balances.as_deref()
+    pub fn pre_token_balances(&self) -> &[TokenBalance] {
+        &self.tx.pre_token_balances
     }
 
     /// postTokenBalances
-    pub fn post_token_balances(&self) -> Option<&[TokenBalance]> {
-        self.tx.post_token_balances.as_deref()
+    pub fn post_token_balances(&self) -> &[TokenBalance] {
+        &self.tx.post_token_balances
+    }
+
+    /// Переводы токенов, уже разобранные нормализатором
+    pub fn transfers(&self) -> &[TransferData] {
+        &self.tx.transfers
     }
 
-    /// первый подписант
+    /// первый подписант (fee payer)
     pub fn signer(&self) -> String {
-        self.signers().get(0).cloned().unwrap_or_else(|| "".to_string())
+        self.tx.signers.first().cloned().unwrap_or_default()
     }
 
     /// signers[]
-    pub fn signers(&self) -> Vec<String> {
-        // В TS берётся из message.header.numRequiredSignatures.
-        // В нормализованных типах обычно уже есть signers. Если их нет — берём из self.account_keys[0..n]
-        if let Some(n) = self.tx.message.num_required_signatures() {
-            return self.account_keys.iter().take(n as usize).cloned().collect();
-        }
-        self.tx.signers.clone().or_else(|| {
-            if !self.account_keys.is_empty() {
-                Some(vec![self.account_keys[0].clone()])
-            } else {
-                Some(vec![])
-            }
-        }).unwrap_or_default()
+    pub fn signers(&self) -> &[String] {
+        &self.tx.signers
     }
 
     /// fee()
     pub fn fee(&self) -> TokenAmount {
-        let fee = self.tx.meta.fee.unwrap_or(0);
-        TokenAmount {
-            amount: fee.to_string(),
-            ui_amount: Some(Self::convert_to_ui_amount(&fee.to_string(), 9)),
-            decimals: 9,
-        }
+        let fee = self.tx.meta.fee;
+        TokenAmount::new(
+            fee.to_string(),
+            SOL_DECIMALS,
+            Some(convert_to_ui_amount(fee, SOL_DECIMALS)),
+        )
     }
 
     /// computeUnits
     pub fn compute_units(&self) -> u64 {
-        self.tx.meta.compute_units_consumed.unwrap_or(0)
+        self.tx.meta.compute_units
     }
 
-    /// txStatus: success/failed/unknown
+    /// txStatus
     pub fn tx_status(&self) -> TransactionStatus {
-        if self.tx.meta.err.is_none() {
-            if self.tx.meta.pre_balances.is_some() || self.tx.meta.post_balances.is_some() {
-                TransactionStatus::Success
-            } else {
-                TransactionStatus::Unknown
-            }
-        } else {
-            TransactionStatus::Failed
-        }
+        self.tx.meta.status
     }
 
-    // ===== Account keys (аналог TS extractAccountKeys) =====
-
-    fn extract_account_keys(tx: &SolanaTransaction) -> Vec<String> {
-        // Пытаемся собрать уникальные ключи: из message (статические) + из loaded addresses,
-        // плюс все аккаунты встречающиеся в инструкциях/inner-инструкциях (на случай отсутствия адрес-таблиц)
-        let mut set: HashSet<String> = HashSet::new();
-
-        // из message (v0/legacy)
-        for k in tx.message.static_account_keys() {
-            set.insert(k.clone());
-        }
-        for k in tx.message.loaded_writable() {
-            set.insert(k.clone());
-        }
-        for k in tx.message.loaded_readonly() {
-            set.insert(k.clone());
-        }
-
-        // из outer instructions (индексы уже должны быть резолвнуты до баз58 строк в нормализаторе)
-        for ix in &tx.instructions {
-            for acc in &ix.accounts {
-                set.insert(acc.clone());
-            }
-            set.insert(ix.program_id.clone());
-        }
-
-        // из inner
-        for inner in &tx.inner_instructions {
-            for ix in &inner.instructions {
-                for acc in &ix.accounts {
-                    set.insert(acc.clone());
-                }
-                set.insert(ix.program_id.clone());
-            }
-        }
-
-        let mut out: Vec<String> = set.into_iter().collect();
-        out.sort();
-        out
+    /// logMessages
+    pub fn log_messages(&self) -> &[String] {
+        &self.tx.meta.log_messages
     }
 
-    pub fn address_table_lookups(&self) -> &[crate::types::AddressTableLookup] {
-        self.tx.message.address_table_lookups()
+    /// meta.err (ошибка упавшей транзакции, если источник её отдаёт)
+    pub fn tx_error(&self) -> Option<&TransactionError> {
+        self.tx.meta.err.as_ref()
     }
 
-    pub fn address_table_lookup_keys(&self) -> Vec<String> {
-        self.address_table_lookups()
-            .iter()
-            .map(|l| l.account_key.clone())
-            .collect()
-    }
+    // ===== Account keys (аналог TS extractAccountKeys) =====
 
-    // ===== Унификация одной инструкции (аналог getInstruction) =====
+    /// Инструкции уже содержат адреса строками, поэтому ключи собираются из подписантов,
+    /// инструкций и токен-балансов в порядке первого появления
+    fn extract_account_keys(tx: &SolanaTransaction) -> Vec<String> {
+        let instructions = tx.instructions.iter().chain(
+            tx.inner_instructions
+                .iter()
+                .flat_map(|inner| &inner.instructions),
+        );
+        let keys = tx
+            .signers
+            .iter()
+            .chain(instructions.flat_map(|ix| ix.accounts.iter().chain([&ix.program_id])))
+            .chain(
+                tx.pre_token_balances
+                    .iter()
+                    .chain(&tx.post_token_balances)
+                    .map(|balance| &balance.account),
+            );
 
-    /// Вернёт унифицированную инструкцию с декодированным data (как TS getInstruction)
-    pub fn get_instruction(&self, instruction: &SolanaInstruction) -> SolanaInstruction {
-        let mut ix = instruction.clone();
-        // Если исходный нормализатор не заполнял data как bytes — можно декодировать здесь
-        // (в проекте, вероятно, уже bytes). Оставлю вызов на случай совместимости:
-        if ix.data.is_empty() && !ix.encoded_data.is_empty() {
-            ix.data = decode_instruction_data(&ix.encoded_data);
+        let mut out: Vec<String> = Vec::new();
+        for key in keys {
+            if !key.is_empty() && !out.contains(key) {
+                out.push(key.clone());
+            }
         }
-        ix
+        out
     }
 
-    pub fn get_inner_instruction(&self, outer_index: usize, inner_index: usize) -> Option<&SolanaInstruction> {
+    pub fn get_inner_instruction(
+        &self,
+        outer_index: usize,
+        inner_index: usize,
+    ) -> Option<&SolanaInstruction> {
         self.inner_instructions()
             .iter()
             .find(|s| s.index == outer_index)
             .and_then(|s| s.instructions.get(inner_index))
     }
 
-    /// Возвращает список аккаунтов инструкции
-    pub fn get_instruction_accounts<'a>(&self, instruction: &'a SolanaInstruction) -> &'a [String] {
-        &instruction.accounts
-    }
-
-    /// Compiled или Parsed (в TS это проверка по наличию programIdIndex/parsed)
-    pub fn is_compiled_instruction(&self, instruction: &SolanaInstruction) -> bool {
-        instruction.parsed.is_none()
-    }
-
-    /// Тип инструкции: если parsed → name, иначе первый байт data как строка (аналог TS getInstructionType)
-    pub fn get_instruction_type(&self, instruction: &SolanaInstruction) -> Option<String> {
-        if let Some(parsed) = &instruction.parsed {
-            return parsed.type_name.clone();
-        }
-        let data = get_instruction_data(instruction);
-        data.first().map(|b| b.to_string())
-    }
-
     /// programId (аналог TS getInstructionProgramId)
     pub fn get_instruction_program_id<'a>(&self, instruction: &'a SolanaInstruction) -> &'a str {
         &instruction.program_id
@@ -256,77 +234,35 @@ impl TransactionAdapter {
     // ===== Владелец токен-аккаунта (аналог TS getTokenAccountOwner) =====
 
     pub fn get_token_account_owner(&self, account_key: &str) -> Option<String> {
-        if let Some(post) = self.post_token_balances() {
-            if let Some(b) = post.iter().find(|b| b.account == account_key) {
-                return b.owner.clone();
-            }
-        }
-        if let Some(pre) = self.pre_token_balances() {
-            if let Some(b) = pre.iter().find(|b| b.account == account_key) {
-                return b.owner.clone();
-            }
-        }
-        None
-    }
-
-    // ===== Балансы (SOL / токены) — как в TS =====
-
-    pub fn get_account_balance(&self, account_keys: &[String]) -> Vec<Option<TokenAmount>> {
-        account_keys
+        self.post_token_balances()
             .iter()
-            .map(|key| {
-                let idx = self.get_account_index(key)?;
-                let post = self.post_balances()?.get(idx).copied().unwrap_or(0);
-                Some(TokenAmount {
-                    amount: post.to_string(),
-                    ui_amount: Some(Self::convert_to_ui_amount(&post.to_string(), 9)),
-                    decimals: 9,
-                })
-            })
-            .collect()
+            .chain(self.pre_token_balances())
+            .find(|b| b.account == account_key)
+            .and_then(|b| b.owner.clone())
     }
 
-    pub fn get_account_pre_balance(&self, account_keys: &[String]) -> Vec<Option<TokenAmount>> {
-        account_keys
-            .iter()
-            .map(|key| {
-                let idx = self.get_account_index(key)?;
-                let pre = self.pre_balances()?.get(idx).copied().unwrap_or(0);
-                Some(TokenAmount {
-                    amount: pre.to_string(),
-                    ui_amount: Some(Self::convert_to_ui_amount(&pre.to_string(), 9)),
-                    decimals: 9,
-                })
-            })
-            .collect()
-    }
+    // ===== Балансы токенов — как в TS =====
 
     pub fn get_token_account_balance(&self, account_keys: &[String]) -> Vec<Option<TokenAmount>> {
-        let post = match self.post_token_balances() {
-            Some(v) => v,
-            None => return vec![None; account_keys.len()],
-        };
-
-        account_keys
-            .iter()
-            .map(|key| {
-                post.iter()
-                    .find(|b| &b.account == key)
-                    .map(|b| b.ui_token_amount.clone())
-            })
-            .collect()
+        Self::token_account_amounts(self.post_token_balances(), account_keys)
     }
 
-    pub fn get_token_account_pre_balance(&self, account_keys: &[String]) -> Vec<Option<TokenAmount>> {
-        let pre = match self.pre_token_balances() {
-            Some(v) => v,
-            None => return vec![None; account_keys.len()],
-        };
+    pub fn get_token_account_pre_balance(
+        &self,
+        account_keys: &[String],
+    ) -> Vec<Option<TokenAmount>> {
+        Self::token_account_amounts(self.pre_token_balances(), account_keys)
+    }
 
+    fn token_account_amounts(
+        balances: &[TokenBalance],
+        account_keys: &[String],
+    ) -> Vec<Option<TokenAmount>> {
         account_keys
             .iter()
             .map(|key| {
-                pre.iter()
+                balances
+                    .iter()
                     .find(|b| &b.account == key)
                     .map(|b| b.ui_token_amount.clone())
             })
@@ -335,228 +271,251 @@ impl TransactionAdapter {
 
     // ===== Карты токенов (mint/decimals) — как в TS =====
 
+    /// SOL и стейблкоины — токены, которыми котируются сделки
     pub fn is_supported_token(&self, mint: &str) -> bool {
-        TOKENS.values().any(|m| m == &mint)
+        [tokens::SOL, tokens::USDC, tokens::USDT].contains(&mint)
     }
 
-    pub fn get_instruction_program_id_string(&self, instruction: &SolanaInstruction) -> String {
-        self.get_instruction_program_id(instruction).to_string()
+    /// Инфо о токене по адресу токен-аккаунта
+    pub fn token_account_info(&self, account: &str) -> Option<&TokenInfo> {
+        self.spl_token_map.get(account)
     }
 
+    /// Decimals mint-а, если он встречался в транзакции
+    pub fn token_decimals(&self, mint: &str) -> Option<u8> {
+        self.spl_decimals_map.get(mint).copied()
+    }
+
+    /// Decimals mint-а; 0, если mint не встречался (как TS getTokenDecimals)
     pub fn get_token_decimals(&self, mint: &str) -> u8 {
-        *self.spl_decimals_map.get(mint).unwrap_or(&0)
-    }
-
-    pub fn get_pool_event_base(&self, r#type: PoolEventType, program_id: &str) -> crate::types::PoolEventBase {
-        crate::types::PoolEventBase {
-            user: self.signer(),
-            r#type,
-            program_id: program_id.to_string(),
-            amm: get_program_name(program_id),
-            slot: self.slot(),
-            timestamp: self.block_time(),
-            signature: self.signature().to_string(),
+        self.token_decimals(mint).unwrap_or(0)
+    }
+
+    // ===== Переводы и изменения балансов =====
+
+    /// Переводы, сгруппированные по программе, от имени которой они сделаны
+    pub fn get_transfer_actions(&self) -> TransferMap {
+        let mut actions = TransferMap::new();
+        for transfer in self.transfers() {
+            actions
+                .entry(transfer.program_id.clone())
+                .or_default()
+                .push(transfer.clone());
         }
+        actions
     }
 
-    // ===== Баланс-дифы по всем аккаунтам (как TS getAccountSolBalanceChanges / getAccountTokenBalanceChanges) =====
+    /// Изменение SOL-баланса первого подписанта
+    pub fn signer_sol_balance_change(&self) -> Option<&BalanceChange> {
+        self.tx.meta.sol_balance_changes.get(&self.signer())
+    }
 
-    pub fn get_account_sol_balance_changes(&self, is_owner: bool) -> HashMap<String, BalanceChange> {
-        let mut changes: HashMap<String, BalanceChange> = HashMap::new();
-        let Some(pre) = self.pre_balances() else { return changes; };
-        let Some(post) = self.post_balances() else { return changes; };
+    /// Изменения токен-балансов первого подписанта (mint → изменение)
+    pub fn signer_token_balance_changes(&self) -> Option<&HashMap<String, BalanceChange>> {
+        self.tx.meta.token_balance_changes.get(&self.signer())
+    }
 
-        for (index, key) in self.account_keys.iter().enumerate() {
+    /// Изменения SOL по всем аккаунтам; `is_owner` сводит токен-аккаунты к владельцу
+    pub fn get_account_sol_balance_changes(
+        &self,
+        is_owner: bool,
+    ) -> HashMap<String, BalanceChange> {
+        let mut changes: HashMap<String, BalanceChange> = HashMap::new();
+        for (key, change) in &self.tx.meta.sol_balance_changes {
+            if change.change == 0 {
+                continue;
+            }
             let account_key = if is_owner {
-                self.get_token_account_owner(key).unwrap_or_else(|| key.clone())
+                self.get_token_account_owner(key)
+                    .unwrap_or_else(|| key.clone())
             } else {
                 key.clone()
             };
+            let entry = changes.entry(account_key).or_default();
+            entry.pre += change.pre;
+            entry.post += change.post;
+            entry.change += change.change;
+        }
+        changes
+    }
 
-            let pre_balance = pre.get(index).copied().unwrap_or(0);
-            let post_balance = post.get(index).copied().unwrap_or(0);
-            let change = (post_balance as i128) - (pre_balance as i128);
-            if change == 0 {
-                continue;
+    /// Изменения токенов по всем аккаунтам (аккаунт → mint → изменение) из pre/post
+    /// балансов; без балансов берутся готовые изменения нормализатора
+    pub fn get_account_token_balance_changes(
+        &self,
+        is_owner: bool,
+    ) -> HashMap<String, HashMap<String, BalanceChange>> {
+        if self.pre_token_balances().is_empty() && self.post_token_balances().is_empty() {
+            return self.tx.meta.token_balance_changes.clone();
+        }
+
+        let mut changes: HashMap<String, HashMap<String, BalanceChange>> = HashMap::new();
+        let mut record = |balance: &TokenBalance, is_post: bool| {
+            if balance.mint.is_empty() {
+                return;
             }
-            let to_amount = |lamports: u64| TokenAmount {
-                amount: lamports.to_string(),
-                ui_amount: Some(Self::convert_to_ui_amount(&lamports.to_string(), 9)),
-                decimals: 9,
+            let account_key = if is_owner {
+                self.get_token_account_owner(&balance.account)
+                    .unwrap_or_else(|| balance.account.clone())
+            } else {
+                balance.account.clone()
             };
+            let amount = balance.ui_token_amount.amount.parse::<i128>().unwrap_or(0);
+            let entry = changes
+                .entry(account_key)
+                .or_default()
+                .entry(balance.mint.clone())
+                .or_default();
+            if is_post {
+                entry.post += amount;
+                entry.change += amount;
+            } else {
+                entry.pre += amount;
+                entry.change -= amount;
+            }
+        };
+        self.pre_token_balances()
+            .iter()
+            .for_each(|balance| record(balance, false));
+        self.post_token_balances()
+            .iter()
+            .for_each(|balance| record(balance, true));
 
-            changes.insert(
-                account_key,
-                BalanceChange {
-                    pre: to_amount(pre_balance),
-                    post: to_amount(post_balance),
-                    change: TokenAmount {
-                        amount: change.to_string(),
-                        ui_amount: Some(change as f64 / 1e9),
-                        decimals: 9,
-                    },
-                },
-            );
-        }
+        // почистим нулевые
+        changes
+            .values_mut()
+            .for_each(|m| m.retain(|_, c| c.change != 0));
+        changes.retain(|_, m| !m.is_empty());
         changes
     }
 
-    pub fn get_account_token_balance_changes(&self, is_owner: bool) -> HashMap<String, HashMap<String, BalanceChange>> {
-        let mut changes: HashMap<String, HashMap<String, BalanceChange>> = HashMap::new();
-
-        // pre
-        if let Some(pre) = self.pre_token_balances() {
-            for b in pre {
-                if b.mint.is_empty() {
-                    continue;
-                }
-                let key = &b.account;
-                let account_key = if is_owner {
-                    self.get_token_account_owner(key).unwrap_or_else(|| key.clone())
-                } else {
-                    key.clone()
-                };
-
-                let entry = changes.entry(account_key).or_default();
-                entry.entry(b.mint.clone()).or_insert(BalanceChange {
-                    pre: b.ui_token_amount.clone(),
-                    post: TokenAmount {
-                        amount: "0".into(),
-                        ui_amount: Some(0.0),
-                        decimals: b.ui_token_amount.decimals,
-                    },
-                    change: TokenAmount {
-                        amount: "0".into(),
-                        ui_amount: Some(0.0),
-                        decimals: b.ui_token_amount.decimals,
-                    },
-                });
+    /// Чистые движения SOL и токенов по кошелькам: токен-аккаунты сводятся к владельцу,
+    /// wrapped SOL не дублируется — его баланс уже учтён в лампортах аккаунта
+    pub fn get_wallet_flows(&self) -> HashMap<String, WalletFlow> {
+        let mut flows: HashMap<String, WalletFlow> = HashMap::new();
+        for (owner, change) in self.get_account_sol_balance_changes(true) {
+            if change.change != 0 {
+                flows.entry(owner).or_default().sol = Some(change);
             }
         }
-
-        // post + diff
-        if let Some(post) = self.post_token_balances() {
-            for b in post {
-                if b.mint.is_empty() {
-                    continue;
-                }
-                let key = &b.account;
-                let account_key = if is_owner {
-                    self.get_token_account_owner(key).unwrap_or_else(|| key.clone())
-                } else {
-                    key.clone()
-                };
-
-                let entry = changes.entry(account_key).or_default();
-                if let Some(ch) = entry.get_mut(&b.mint) {
-                    // считаем дельту raw amounts
-                    let pre_raw = ch.pre.amount.parse::<i128>().unwrap_or(0);
-                    let post_raw = b.ui_token_amount.amount.parse::<i128>().unwrap_or(0);
-                    let diff = post_raw - pre_raw;
-
-                    ch.post = b.ui_token_amount.clone();
-                    ch.change = TokenAmount {
-                        amount: diff.to_string(),
-                        ui_amount: Some(
-                            b.ui_token_amount.ui_amount.unwrap_or(0.0) - ch.pre.ui_amount.unwrap_or(0.0),
-                        ),
-                        decimals: b.ui_token_amount.decimals,
-                    };
-
-                    if diff == 0 {
-                        entry.remove(&b.mint);
-                    }
-                } else {
-                    // pre не было, считаем pre = 0
-                    entry.insert(
-                        b.mint.clone(),
-                        BalanceChange {
-                            pre: TokenAmount {
-                                amount: "0".into(),
-                                ui_amount: Some(0.0),
-                                decimals: b.ui_token_amount.decimals,
-                            },
-                            post: b.ui_token_amount.clone(),
-                            change: b.ui_token_amount.clone(),
-                        },
-                    );
-                }
+        for (owner, mut changes) in self.get_account_token_balance_changes(true) {
+            changes.remove(tokens::SOL);
+            if !changes.is_empty() {
+                flows.entry(owner).or_default().tokens = changes;
             }
         }
+        flows
+    }
 
-        // почистим пустые
-        changes.retain(|_, m| !m.is_empty());
-        changes
+    /// Чистые движения одного кошелька, как его запись в `get_wallet_flows`
+    pub fn get_wallet_flow(&self, wallet: &str) -> WalletFlow {
+        let sol = self
+            .get_account_sol_balance_changes(true)
+            .remove(wallet)
+            .filter(|change| change.change != 0);
+        let mut tokens = self
+            .get_account_token_balance_changes(true)
+            .remove(wallet)
+            .unwrap_or_default();
+        tokens.remove(tokens::SOL);
+        WalletFlow { sol, tokens }
     }
 
     // ===== Внутренняя логика извлечения токенов (как в TS extractTokenInfo) =====
 
     fn extract_token_info(&mut self) {
         self.extract_token_balances();
+        self.extract_token_from_transfers();
         self.extract_token_from_instructions();
 
         // Добавляем SOL, если нет
-        if !self.spl_token_map.contains_key(TOKENS.SOL) {
-            self.spl_token_map.insert(
-                TOKENS.SOL.to_string(),
-                TokenInfo {
-                    mint: TOKENS.SOL.to_string(),
-                    amount: 0.0,
-                    amount_raw: "0".into(),
-                    decimals: 9,
-                    ..TokenInfo::default()
-                },
-            );
-        }
-        if !self.spl_decimals_map.contains_key(TOKENS.SOL) {
-            self.spl_decimals_map.insert(TOKENS.SOL.to_string(), 9);
-        }
+        self.spl_token_map
+            .entry(tokens::SOL.to_string())
+            .or_insert_with(|| TokenInfo {
+                mint: tokens::SOL.to_string(),
+                amount: 0.0,
+                amount_raw: "0".into(),
+                decimals: SOL_DECIMALS,
+                ..TokenInfo::default()
+            });
+        self.spl_decimals_map
+            .entry(tokens::SOL.to_string())
+            .or_insert(SOL_DECIMALS);
     }
 
     /// Аналог TS extractTokenBalances()
     fn extract_token_balances(&mut self) {
-        if let Some(post) = self.post_token_balances() {
-            for balance in post {
-                if balance.mint.is_empty() {
-                    continue;
-                }
-                let account_key = &balance.account;
-                if !self.spl_token_map.contains_key(account_key) {
-                    let token_info = TokenInfo {
-                        mint: balance.mint.clone(),
-                        amount: balance.ui_token_amount.ui_amount.unwrap_or(0.0),
-                        amount_raw: balance.ui_token_amount.amount.clone(),
-                        decimals: balance.ui_token_amount.decimals,
-                        ..TokenInfo::default()
-                    };
-                    self.spl_token_map.insert(account_key.clone(), token_info);
-                }
-                self.spl_decimals_map
-                    .entry(balance.mint.clone())
-                    .or_insert(balance.ui_token_amount.decimals);
+        for balance in self
+            .tx
+            .post_token_balances
+            .iter()
+            .chain(&self.tx.pre_token_balances)
+        {
+            if balance.mint.is_empty() {
+                continue;
             }
+            self.spl_token_map
+                .entry(balance.account.clone())
+                .or_insert_with(|| TokenInfo {
+                    mint: balance.mint.clone(),
+                    amount: balance.ui_token_amount.ui_amount.unwrap_or(0.0),
+                    amount_raw: balance.ui_token_amount.amount.clone(),
+                    decimals: balance.ui_token_amount.decimals,
+                    ..TokenInfo::default()
+                });
+            self.spl_decimals_map
+                .entry(balance.mint.clone())
+                .or_insert(balance.ui_token_amount.decimals);
+        }
+    }
+
+    /// Токен-аккаунты из переводов нормализатора
+    fn extract_token_from_transfers(&mut self) {
+        let accounts: Vec<(String, String, String, u8)> = self
+            .tx
+            .transfers
+            .iter()
+            .filter(|transfer| !transfer.info.mint.is_empty())
+            .map(|transfer| {
+                let info = &transfer.info;
+                (
+                    info.source.clone(),
+                    info.destination.clone(),
+                    info.mint.clone(),
+                    info.token_amount.decimals,
+                )
+            })
+            .collect();
+        for (source, destination, mint, decimals) in accounts {
+            self.set_token_info(
+                Some(&source),
+                Some(&destination),
+                Some(&mint),
+                Some(decimals),
+            );
         }
     }
 
     /// Аналог TS extractTokenFromInstructions()
     fn extract_token_from_instructions(&mut self) {
-        // outer
-        for ix in self.instructions() {
-            if self.is_compiled_instruction(ix) {
-                self.extract_from_compiled_transfer(ix);
-            } else {
-                self.extract_from_parsed_transfer(ix);
-            }
-        }
-        // inner
-        for inner in self.inner_instructions() {
-            for ix in &inner.instructions {
-                if self.is_compiled_instruction(ix) {
-                    self.extract_from_compiled_transfer(ix);
-                } else {
-                    self.extract_from_parsed_transfer(ix);
-                }
-            }
+        let instructions: Vec<SolanaInstruction> = self
+            .tx
+            .instructions
+            .iter()
+            .chain(
+                self.tx
+                    .inner_instructions
+                    .iter()
+                    .flat_map(|inner| &inner.instructions),
+            )
+            .filter(|ix| {
+                ix.program_id == token_programs::TOKEN
+                    || ix.program_id == token_programs::TOKEN_2022
+            })
+            .cloned()
+            .collect();
+        for ix in &instructions {
+            self.extract_from_compiled_transfer(ix);
         }
     }
 
@@ -568,55 +527,35 @@ impl TransactionAdapter {
         mint: Option<&str>,
         decimals: Option<u8>,
     ) {
-        if let Some(src) = source {
-            if self.spl_token_map.contains_key(src) && mint.is_some() && decimals.is_some() {
-                self.spl_token_map.insert(
-                    src.to_string(),
-                    TokenInfo {
-                        mint: mint.unwrap().to_string(),
-                        amount: 0.0,
-                        amount_raw: "0".into(),
-                        decimals: decimals.unwrap(),
-                        ..TokenInfo::default()
-                    },
-                );
-            } else if !self.spl_token_map.contains_key(src) {
-                self.spl_token_map.insert(
-                    src.to_string(),
-                    TokenInfo {
-                        mint: mint.unwrap_or(TOKENS.SOL).to_string(),
-                        amount: 0.0,
-                        amount_raw: "0".into(),
-                        decimals: decimals.unwrap_or(9),
-                        ..TokenInfo::default()
-                    },
-                );
+        for account in [source, destination].into_iter().flatten() {
+            if account.is_empty() {
+                continue;
             }
-        }
-
-        if let Some(dst) = destination {
-            if self.spl_token_map.contains_key(dst) && mint.is_some() && decimals.is_some() {
-                self.spl_token_map.insert(
-                    dst.to_string(),
-                    TokenInfo {
-                        mint: mint.unwrap().to_string(),
-                        amount: 0.0,
-                        amount_raw: "0".into(),
-                        decimals: decimals.unwrap(),
-                        ..TokenInfo::default()
-                    },
-                );
-            } else if !self.spl_token_map.contains_key(dst) {
-                self.spl_token_map.insert(
-                    dst.to_string(),
-                    TokenInfo {
-                        mint: mint.unwrap_or(TOKENS.SOL).to_string(),
-                        amount: 0.0,
-                        amount_raw: "0".into(),
-                        decimals: decimals.unwrap_or(9),
-                        ..TokenInfo::default()
-                    },
-                );
+            match (mint, decimals) {
+                // известны mint и decimals — перезаписываем заглушку
+                (Some(mint), Some(decimals)) if !self.has_balance(account) => {
+                    self.spl_token_map.insert(
+                        account.to_string(),
+                        TokenInfo {
+                            mint: mint.to_string(),
+                            amount: 0.0,
+                            amount_raw: "0".into(),
+                            decimals,
+                            ..TokenInfo::default()
+                        },
+                    );
+                }
+                _ => {
+                    self.spl_token_map
+                        .entry(account.to_string())
+                        .or_insert_with(|| TokenInfo {
+                            mint: mint.unwrap_or(tokens::SOL).to_string(),
+                            amount: 0.0,
+                            amount_raw: "0".into(),
+                            decimals: decimals.unwrap_or(SOL_DECIMALS),
+                            ..TokenInfo::default()
+                        });
+                }
             }
         }
 
@@ -625,134 +564,46 @@ impl TransactionAdapter {
         }
     }
 
-    /// Аналог TS extractFromParsedTransfer()
-    fn extract_from_parsed_transfer(&mut self, ix: &SolanaInstruction) {
-        if ix.parsed.is_none() || ix.program_id.is_empty() {
-            return;
-        }
-        let pid = &ix.program_id;
-        if pid != TOKEN_PROGRAM_ID && pid != TOKEN_2022_PROGRAM_ID {
-            return;
-        }
-        let info = match &ix.parsed {
-            Some(p) => &p.info,
-            None => return,
-        };
-
-        let source = info.source.as_deref();
-        let destination = info.destination.as_deref();
-        let mint = info.mint.as_deref();
-        let decimals = info.decimals;
-
-        if source.is_none() && destination.is_none() {
-            return;
-        }
-        self.set_token_info(source, destination, mint, decimals);
+    /// Аккаунт есть в токен-балансах: его инфо точнее любой выведенной из инструкций
+    fn has_balance(&self, account: &str) -> bool {
+        self.tx
+            .post_token_balances
+            .iter()
+            .chain(&self.tx.pre_token_balances)
+            .any(|balance| balance.account == account)
     }
 
     /// Аналог TS extractFromCompiledTransfer()
     fn extract_from_compiled_transfer(&mut self, ix: &SolanaInstruction) {
-        // bytes (у нас уже Vec<u8>)
-        let decoded = get_instruction_data(ix);
-        if decoded.is_empty() {
-            return;
-        }
-        let program_id = &ix.program_id;
-        if program_id != TOKEN_PROGRAM_ID && program_id != TOKEN_2022_PROGRAM_ID {
-            return;
-        }
+        use spl_token_instructions::*;
 
+        let decoded = decode_instruction_data(&ix.data);
+        let Some(&instruction_type) = decoded.first() else {
+            return;
+        };
         let accounts = &ix.accounts;
-        if accounts.is_empty() {
+        let account = |index: usize| accounts.get(index).map(String::as_str);
+
+        // NOTE: в TS accounts — индексы в accountKeys; в наших нормализованных типах уже
+        // строки-адреса, поэтому берём их как есть по позициям.
+        let (source, destination, mint, decimals) = match instruction_type {
+            TRANSFER => (account(0), account(1), None, None),
+            // byte offset 9 как в TS
+            TRANSFER_CHECKED => (account(0), account(2), account(1), decoded.get(9).copied()),
+            MINT_TO => (None, account(1), account(0), None),
+            MINT_TO_CHECKED => (None, account(1), account(0), decoded.get(9).copied()),
+            BURN => (account(0), None, account(1), None),
+            BURN_CHECKED => (account(0), None, account(1), decoded.get(9).copied()),
+            CLOSE_ACCOUNT => (account(0), account(1), None, None),
+            _ => return,
+        };
+        if source.is_none() && destination.is_none() {
             return;
         }
 
-        // NOTE: в TS accounts — индексы в accountKeys; в наших нормализованных типах уже строки-адреса.
-        // Поэтому берём их как есть по позициям.
-        let mut source: Option<&str> = None;
-        let mut destination: Option<&str> = None;
-        let mut mint: Option<&str> = None;
-        let mut decimals: Option<u8> = None;
-
-        match decoded[0] {
-            x if x == SPL_TOKEN_INSTRUCTION_TYPES.Transfer => {
-                if accounts.len() < 2 {
-                    return;
-                }
-                source = Some(&accounts[0]);
-                destination = Some(&accounts[1]);
-            }
-            x if x == SPL_TOKEN_INSTRUCTION_TYPES.TransferChecked => {
-                if accounts.len() < 3 {
-                    return;
-                }
-                source = Some(&accounts[0]);
-                mint = Some(&accounts[1]);
-                destination = Some(&accounts[2]);
-                // byte offset 9 как в TS
-                decimals = decoded.get(9).copied();
-            }
-            x if x == SPL_TOKEN_INSTRUCTION_TYPES.InitializeMint => {
-                if accounts.len() < 2 {
-                    return;
-                }
-                mint = Some(&accounts[0]);
-                destination = Some(&accounts[1]);
-            }
-            x if x == SPL_TOKEN_INSTRUCTION_TYPES.MintTo => {
-                if accounts.len() < 2 {
-                    return;
-                }
-                mint = Some(&accounts[0]);
-                destination = Some(&accounts[1]);
-            }
-            x if x == SPL_TOKEN_INSTRUCTION_TYPES.MintToChecked => {
-                if accounts.len() < 2 {
-                    return;
-                }
-                mint = Some(&accounts[0]);
-                destination = Some(&accounts[1]);
-                decimals = decoded.get(9).copied();
-            }
-            x if x == SPL_TOKEN_INSTRUCTION_TYPES.Burn => {
-                if accounts.len() < 2 {
-                    return;
-                }
-                source = Some(&accounts[0]);
-                mint = Some(&accounts[1]);
-            }
-            x if x == SPL_TOKEN_INSTRUCTION_TYPES.BurnChecked => {
-                if accounts.len() < 2 {
-                    return;
-                }
-                source = Some(&accounts[0]);
-                mint = Some(&accounts[1]);
-                decimals = decoded.get(9).copied();
-            }
-            x if x == SPL_TOKEN_INSTRUCTION_TYPES.CloseAccount => {
-                if accounts.len() < 2 {
-                    return;
-                }
-                source = Some(&accounts[0]);
-                destination = Some(&accounts[1]);
-            }
-            _ => {}
-        }
-
         self.set_token_info(source, destination, mint, decimals);
     }
 
-    // ===== Вспомогательные =====
-
-    fn convert_to_ui_amount(raw: &str, decimals: u8) -> f64 {
-        let val = raw.parse::<f64>().unwrap_or(0.0);
-        if decimals == 0 {
-            return val;
-        }
-        let scale = 10f64.powi(decimals as i32);
-        val / scale
-    }
-
     // Публичный доступ к картам, если нужно
     pub fn spl_token_map(&self) -> &HashMap<String, TokenInfo> {
         &self.spl_token_map
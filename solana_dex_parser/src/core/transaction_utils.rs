@@ -1,7 +1,24 @@
-use crate::core::constants::dex_program_names;
-use crate::core::instruction_classifier::InstructionClassifier;
+use std::collections::HashSet;
+
+use serde_json::Value;
+
+use crate::core::amounts::build_fee_info;
+use crate::core::amounts::convert_to_ui_amount;
+use crate::core::constants::{lock_programs, token_programs};
+use crate::core::flow::ROUTER_PROGRAMS;
+use crate::core::instruction_classifier::{InstructionClassifier, InvocationNode};
+use crate::core::matcher::TransferMatcher;
 use crate::core::transaction_adapter::TransactionAdapter;
-use crate::types::{DexInfo, FeeInfo, PoolEvent, TradeInfo, TradeType, TransferData, TransferMap};
+use crate::types::{
+    DexInfo, FeeInfo, MemeEvent, PoolEvent, SolanaInstruction, TradeInfo, TradeStatus, TradeType,
+    TransferData, TransferMap,
+};
+
+const SPL_SET_AUTHORITY: u8 = 6;
+const SPL_BURN: u8 = 8;
+const SPL_BURN_CHECKED: u8 = 15;
+const AUTHORITY_MINT_TOKENS: u8 = 0;
+const AUTHORITY_FREEZE_ACCOUNT: u8 = 1;
 
 #[derive(Clone, Debug)]
 pub struct TransactionUtils {
@@ -17,7 +34,7 @@ impl TransactionUtils {
         let program_id = classifier.get_all_program_ids().into_iter().next();
         let amm = program_id
             .as_ref()
-            .map(|id| dex_program_names::name(id).to_string());
+            .map(|id| self.adapter.config().program_name(id));
         DexInfo {
             program_id,
             amm,
@@ -25,21 +42,54 @@ impl TransactionUtils {
         }
     }
 
+    /// Name each hop after the innermost aggregator invocation that executed it. Trades
+    /// outside every router call, and the router's own trades, keep their route.
+    pub fn attach_routes(&self, classifier: &InstructionClassifier, trades: &mut [TradeInfo]) {
+        fn routers(node: &InvocationNode, found: &mut Vec<InvocationNode>) {
+            if ROUTER_PROGRAMS.contains(&node.instruction.program_id.as_str()) {
+                found.push(node.clone());
+            }
+            for child in &node.children {
+                routers(child, found);
+            }
+        }
+        let mut found = Vec::new();
+        for root in classifier.invocation_tree() {
+            routers(&root, &mut found);
+        }
+        if found.is_empty() {
+            return;
+        }
+        for trade in trades
+            .iter_mut()
+            .filter(|trade| trade.route.as_deref().is_none_or(str::is_empty))
+        {
+            // Execution order puts nested routers after the ones that invoked them.
+            let router = found.iter().rev().find(|router| {
+                trade.program_id.as_deref() != Some(router.instruction.program_id.as_str())
+                    && router.contains(&trade.idx)
+            });
+            if let Some(router) = router {
+                trade.route = Some(
+                    self.adapter
+                        .config()
+                        .program_name(&router.instruction.program_id),
+                );
+            }
+        }
+    }
+
     pub fn get_transfer_actions(&self) -> TransferMap {
         self.adapter.get_transfer_actions()
     }
 
     pub fn process_swap_data(
         &self,
-        transfers: &[TransferData],
+        transfer_actions: &TransferMap,
+        program_id: &str,
         dex_info: &DexInfo,
     ) -> Option<TradeInfo> {
-        if transfers.len() < 2 {
-            return None;
-        }
-
-        let input = transfers.first()?;
-        let output = transfers.get(1)?;
+        let (input, output) = TransferMatcher::new(transfer_actions).swap_legs(program_id)?;
         let program_id = dex_info
             .program_id
             .clone()
@@ -47,7 +97,7 @@ impl TransactionUtils {
         let amm = dex_info
             .amm
             .clone()
-            .unwrap_or_else(|| dex_program_names::name(&program_id).to_string());
+            .unwrap_or_else(|| self.adapter.config().program_name(&program_id));
 
         let input_token = Self::transfer_to_token_info(input);
         let output_token = Self::transfer_to_token_info(output);
@@ -67,9 +117,25 @@ impl TransactionUtils {
             route: dex_info.route.clone(),
             slot: self.adapter.slot(),
             timestamp: self.adapter.block_time(),
-            signature: self.adapter.signature().to_string(),
+            signature: self.adapter.shared_signature(),
             idx: input.idx.clone(),
             signer: Some(self.adapter.signers().to_vec()),
+            volume_sol: None,
+            price_usd: None,
+            volume_usd: None,
+            routing_improvement_bps: None,
+            maker: None,
+            order: None,
+            dca: None,
+            pool_reserves: None,
+            price_after: None,
+            pool_state: None,
+            oracle_price: None,
+            ticks_crossed: None,
+            suspect: false,
+            suspected_wash: false,
+            status: TradeStatus::Success,
+            limit: None,
         })
     }
 
@@ -98,23 +164,156 @@ impl TransactionUtils {
         trade
     }
 
+    /// Flag created pools whose LP mint is burned or handed to a locker in the same transaction.
+    pub fn attach_lp_safety_flags(&self, pools: Vec<PoolEvent>) -> Vec<PoolEvent> {
+        if !pools
+            .iter()
+            .any(|pool| pool.event_type == TradeType::Create && pool.pool_lp_mint.is_some())
+        {
+            return pools;
+        }
+
+        let mut burned: HashSet<String> = HashSet::new();
+        let mut locked: HashSet<&str> = HashSet::new();
+        for instruction in self.all_instructions() {
+            if let Some(mint) = Self::burned_mint(instruction) {
+                burned.insert(mint);
+            }
+            if lock_programs::ALL.contains(&instruction.program_id.as_str()) {
+                locked.extend(instruction.accounts.iter().map(String::as_str));
+            }
+        }
+
+        pools
+            .into_iter()
+            .map(|mut pool| {
+                if pool.event_type == TradeType::Create {
+                    if let Some(lp_mint) = pool.pool_lp_mint.as_deref() {
+                        pool.lp_burned = Some(burned.contains(lp_mint));
+                        pool.lp_locked = Some(locked.contains(lp_mint));
+                    }
+                }
+                pool
+            })
+            .collect()
+    }
+
+    /// Flag create events whose mint/freeze authority is set to `None` in the same transaction.
+    pub fn attach_authority_renounce_flags(&self, events: Vec<MemeEvent>) -> Vec<MemeEvent> {
+        if !events
+            .iter()
+            .any(|event| event.event_type == TradeType::Create)
+        {
+            return events;
+        }
+
+        let mut mint_renounced: HashSet<String> = HashSet::new();
+        let mut freeze_renounced: HashSet<String> = HashSet::new();
+        for instruction in self.all_instructions() {
+            match Self::renounced_authority(instruction) {
+                Some((mint, AUTHORITY_MINT_TOKENS)) => {
+                    mint_renounced.insert(mint);
+                }
+                Some((mint, AUTHORITY_FREEZE_ACCOUNT)) => {
+                    freeze_renounced.insert(mint);
+                }
+                _ => {}
+            }
+        }
+
+        events
+            .into_iter()
+            .map(|mut event| {
+                if event.event_type == TradeType::Create {
+                    event.mint_authority_renounced =
+                        Some(mint_renounced.contains(&event.base_mint));
+                    event.freeze_authority_renounced =
+                        Some(freeze_renounced.contains(&event.base_mint));
+                }
+                event
+            })
+            .collect()
+    }
+
     pub fn attach_user_balance_to_lps(&self, pools: Vec<PoolEvent>) -> Vec<PoolEvent> {
-        if let Some(signer) = self.adapter.signer() {
-            pools
-                .into_iter()
-                .map(|mut pool| {
-                    pool.idx = format!("{}-{}", signer, pool.idx);
-                    pool
-                })
-                .collect()
-        } else {
-            pools
+        let signer = self.adapter.signer();
+        if signer.is_empty() {
+            return pools;
         }
+        pools
+            .into_iter()
+            .map(|mut pool| {
+                pool.idx = format!("{}-{}", signer, pool.idx);
+                pool
+            })
+            .collect()
     }
 }
 
 impl TransactionUtils {
-    fn transfer_to_token_info(transfer: &TransferData) -> crate::types::TokenInfo {
+    fn all_instructions(&self) -> impl Iterator<Item = &SolanaInstruction> {
+        self.adapter.instructions().iter().chain(
+            self.adapter
+                .inner_instructions()
+                .iter()
+                .flat_map(|set| set.instructions.iter()),
+        )
+    }
+
+    /// `(mint, authority type)` of an SPL Token `SetAuthority` that clears the authority.
+    fn renounced_authority(instruction: &SolanaInstruction) -> Option<(String, u8)> {
+        if !Self::is_token_program(&instruction.program_id) {
+            return None;
+        }
+        if instruction.accounts.is_empty() {
+            let parsed: Value = serde_json::from_str(&instruction.data).ok()?;
+            if parsed.get("type")?.as_str()? != "setAuthority" {
+                return None;
+            }
+            let info = parsed.get("info")?;
+            if !info.get("newAuthority").is_none_or(Value::is_null) {
+                return None;
+            }
+            let authority_type = match info.get("authorityType")?.as_str()? {
+                "mintTokens" => AUTHORITY_MINT_TOKENS,
+                "freezeAccount" => AUTHORITY_FREEZE_ACCOUNT,
+                _ => return None,
+            };
+            return Some((info.get("mint")?.as_str()?.to_string(), authority_type));
+        }
+        let data = bs58::decode(&instruction.data).into_vec().ok()?;
+        match data.as_slice() {
+            [SPL_SET_AUTHORITY, authority_type, 0, ..] => {
+                Some((instruction.accounts.first()?.clone(), *authority_type))
+            }
+            _ => None,
+        }
+    }
+
+    fn is_token_program(program_id: &str) -> bool {
+        program_id == token_programs::TOKEN || program_id == token_programs::TOKEN_2022
+    }
+
+    /// Mint burned by an SPL Token `Burn`/`BurnChecked`, compiled or jsonParsed.
+    fn burned_mint(instruction: &SolanaInstruction) -> Option<String> {
+        if !Self::is_token_program(&instruction.program_id) {
+            return None;
+        }
+        if instruction.accounts.is_empty() {
+            let parsed: Value = serde_json::from_str(&instruction.data).ok()?;
+            return match parsed.get("type")?.as_str()? {
+                "burn" | "burnChecked" => Some(parsed["info"]["mint"].as_str()?.to_string()),
+                _ => None,
+            };
+        }
+        let data = bs58::decode(&instruction.data).into_vec().ok()?;
+        match data.first()? {
+            &SPL_BURN | &SPL_BURN_CHECKED => instruction.accounts.get(1).cloned(),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn transfer_to_token_info(transfer: &TransferData) -> crate::types::TokenInfo {
         let amount = transfer.info.token_amount.ui_amount.unwrap_or_else(|| {
             transfer
                 .info
@@ -142,4 +341,46 @@ impl TransactionUtils {
             balance_change: transfer.info.sol_balance_change.clone(),
         }
     }
+
+    /// Raw token-2022 transfer fee withheld from a transfer, zero without the extension.
+    pub(crate) fn transfer_fee_raw(transfer: &TransferData) -> u128 {
+        transfer
+            .info
+            .fee_amount
+            .as_ref()
+            .and_then(|fee| fee.amount.parse().ok())
+            .unwrap_or(0)
+    }
+
+    /// Token info of the amount the destination was credited, i.e. net of the transfer fee.
+    pub(crate) fn transfer_to_received_token_info(
+        transfer: &TransferData,
+    ) -> crate::types::TokenInfo {
+        let token = Self::transfer_to_token_info(transfer);
+        let fee = Self::transfer_fee_raw(transfer);
+        if fee == 0 {
+            return token;
+        }
+        let sent: u128 = token.amount_raw.parse().unwrap_or(0);
+        let received = sent.saturating_sub(fee);
+        crate::types::TokenInfo {
+            amount: convert_to_ui_amount(received, token.decimals),
+            amount_raw: received.to_string(),
+            ..token
+        }
+    }
+
+    /// The withheld transfer fee as a trade fee of type `transferFee`, if any.
+    pub(crate) fn transfer_fee_info(transfer: &TransferData, dex: &str) -> Option<FeeInfo> {
+        let fee = Self::transfer_fee_raw(transfer);
+        (fee > 0).then(|| FeeInfo {
+            fee_type: Some("transferFee".to_string()),
+            ..build_fee_info(
+                &transfer.info.mint,
+                fee,
+                transfer.info.token_amount.decimals,
+                Some(dex.to_string()),
+            )
+        })
+    }
 }
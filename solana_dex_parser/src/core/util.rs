@@ -0,0 +1,65 @@
+//! Helpers shared by the core and the protocol parsers: instruction indexes, instruction data
+//! and trade direction.
+
+use base64::{engine::general_purpose::STANDARD as BASE64_STANDARD, Engine as _};
+use bs58::decode as bs58_decode;
+
+use crate::core::constants::tokens;
+use crate::types::TradeType;
+
+/// Buy when SOL is paid in, sell when SOL is paid out, a swap otherwise.
+pub fn get_trade_type(input_mint: &str, output_mint: &str) -> TradeType {
+    if input_mint == tokens::SOL {
+        TradeType::Buy
+    } else if output_mint == tokens::SOL {
+        TradeType::Sell
+    } else {
+        TradeType::Swap
+    }
+}
+
+pub fn sort_by_idx<T>(mut items: Vec<T>) -> Vec<T>
+where
+    T: HasIdx,
+{
+    items.sort_by(|a, b| compare_idx(a.idx(), b.idx()));
+    items
+}
+
+/// Execution order of two `"outer-inner"` indexes.
+pub fn compare_idx(a: &str, b: &str) -> std::cmp::Ordering {
+    let (a_main, a_sub) = parse_idx(a);
+    let (b_main, b_sub) = parse_idx(b);
+    a_main.cmp(&b_main).then_with(|| a_sub.cmp(&b_sub))
+}
+
+fn parse_idx(value: &str) -> (u64, u64) {
+    let mut parts = value.split('-');
+    let main = parts
+        .next()
+        .and_then(|p| p.parse::<u64>().ok())
+        .unwrap_or_default();
+    let sub = parts
+        .next()
+        .and_then(|p| p.parse::<u64>().ok())
+        .unwrap_or_default();
+    (main, sub)
+}
+
+pub trait HasIdx {
+    fn idx(&self) -> &str;
+}
+
+/// Bytes of instruction data as base58, else base64, else the raw string.
+pub fn decode_instruction_data(data: &str) -> Vec<u8> {
+    if data.is_empty() {
+        return Vec::new();
+    }
+    if let Ok(decoded) = bs58_decode(data).into_vec() {
+        return decoded;
+    }
+    if let Ok(decoded) = BASE64_STANDARD.decode(data) {
+        return decoded;
+    }
+    data.as_bytes().to_vec()
+}
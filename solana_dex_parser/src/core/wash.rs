@@ -0,0 +1,100 @@
+//! Wash-trading heuristic: trades that buy and sell the same pool for one beneficial owner.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use solana_sdk::pubkey::Pubkey;
+
+use crate::core::constants::{exchange_wallets, system_programs};
+use crate::types::{ParseResult, TradeInfo};
+
+/// Result and trade positions of an owner's trades on a pool, by input and output mint.
+type OwnerTrades<'a> = HashMap<(&'a str, &'a str), Vec<(usize, usize)>>;
+
+/// Wallet a trade is attributed to: its user, else its signer or its transaction's.
+pub(crate) fn trader<'a>(result: &'a ParseResult, trade: &'a TradeInfo) -> Option<&'a str> {
+    trade
+        .user
+        .as_deref()
+        .or_else(|| trade.signer.as_ref()?.first().map(String::as_str))
+        .or(result.signer.first().map(String::as_str))
+}
+
+/// Wallet that first sent native SOL to each wallet in `results`, with the position of the
+/// funding result.
+///
+/// Only System program transfers count: token transfers of wrapped SOL come out of pool
+/// vaults whose authority is shared by every pool of a program. Program-derived addresses
+/// and exchange hot wallets fund unrelated users and are no parent either.
+fn funders(results: &[ParseResult]) -> HashMap<&str, (&str, usize)> {
+    let mut funders = HashMap::new();
+    for (result_index, result) in results.iter().enumerate() {
+        for transfer in &result.transfers {
+            if transfer.program_id != system_programs::SYSTEM
+                || transfer.transfer_type != "transfer"
+            {
+                continue;
+            }
+            let (funder, funded) = (&transfer.info.source, &transfer.info.destination);
+            if funder == funded || !is_parent(funder) {
+                continue;
+            }
+            funders
+                .entry(funded.as_str())
+                .or_insert((funder.as_str(), result_index));
+        }
+    }
+    funders
+}
+
+/// Whether `wallet` can stand for the owner of the wallets it funds.
+fn is_parent(wallet: &str) -> bool {
+    !exchange_wallets::ALL.contains(&wallet)
+        && Pubkey::from_str(wallet).map_or(true, |key| key.is_on_curve())
+}
+
+/// Set `suspected_wash` on trades that meet an opposite trade of the same pool whose wallet
+/// is theirs, or was funded with SOL by the same parent in an earlier transaction of
+/// `results`.
+///
+/// Funding is read from the SOL transfers the results report, so it is only seen when the
+/// funding transaction is part of `results`. A sandwich's own frontrun and backrun are a
+/// round trip of one wallet and are flagged as well.
+pub fn flag_wash_trades(results: &mut [ParseResult]) {
+    let funders = funders(results);
+    // Trades grouped by pool and owner, so no pair of trades is compared.
+    let mut owners: HashMap<(&str, &str), OwnerTrades> = HashMap::new();
+    for (result_index, result) in results.iter().enumerate() {
+        for (trade_index, trade) in result.trades.iter().enumerate() {
+            let (Some(pool), Some(wallet)) = (trade.pool.first(), trader(result, trade)) else {
+                continue;
+            };
+            let owner = match funders.get(wallet) {
+                Some(&(funder, funded_at)) if funded_at < result_index => funder,
+                _ => wallet,
+            };
+            let direction = (
+                trade.input_token.mint.as_str(),
+                trade.output_token.mint.as_str(),
+            );
+            owners
+                .entry((pool.as_str(), owner))
+                .or_default()
+                .entry(direction)
+                .or_default()
+                .push((result_index, trade_index));
+        }
+    }
+
+    let mut flagged = Vec::new();
+    for directions in owners.values() {
+        for (&(input, output), trades) in directions {
+            if directions.contains_key(&(output, input)) {
+                flagged.extend_from_slice(trades);
+            }
+        }
+    }
+    for (result_index, trade_index) in flagged {
+        results[result_index].trades[trade_index].suspected_wash = true;
+    }
+}
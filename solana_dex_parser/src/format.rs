@@ -0,0 +1,314 @@
+//! JSON rendering options and token amount formatting for parser results.
+
+use std::cmp::Ordering;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::core::util::compare_idx;
+
+/// How UI (decimal-adjusted) token amounts are written to JSON.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum UiAmountFormat {
+    /// JSON numbers, as the `f64` fields serialize natively.
+    #[default]
+    Number,
+    /// Exact decimal strings rebuilt from the raw amount and decimals where the value
+    /// carries them, for sinks that load decimals without going through a float.
+    String,
+}
+
+/// JSON rendering of parser results.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct JsonFormat {
+    pub ui_amounts: UiAmountFormat,
+    /// Render byte-identical output for identical input: object keys (hash map entries
+    /// included) are sorted and event lists are ordered by `idx`, so the output can be
+    /// content-hashed or compared against golden files.
+    pub canonical: bool,
+}
+
+impl JsonFormat {
+    pub fn canonical() -> Self {
+        Self {
+            canonical: true,
+            ..Self::default()
+        }
+    }
+}
+
+impl From<UiAmountFormat> for JsonFormat {
+    fn from(ui_amounts: UiAmountFormat) -> Self {
+        Self {
+            ui_amounts,
+            ..Self::default()
+        }
+    }
+}
+
+/// UI amounts next to the raw amount and decimals they were derived from: `(ui, raw, decimals)`.
+const UI_AMOUNTS_WITH_RAW: [(&str, &str, &str); 4] = [
+    ("amount", "amountRaw", "decimals"),
+    ("uiAmount", "amount", "decimals"),
+    ("token0Amount", "token0AmountRaw", "token0Decimals"),
+    ("token1Amount", "token1AmountRaw", "token1Decimals"),
+];
+
+/// UI amounts serialized without their raw counterpart; rendered from the float.
+const UI_AMOUNTS: [&str; 8] = [
+    "lpAmount",
+    "fee",
+    "protocolFee",
+    "platformFee",
+    "shareFee",
+    "creatorFee",
+    "poolAReserve",
+    "poolBReserve",
+];
+
+/// Serialize `value` as `format`. Works for every result type, since the amounts and events
+/// are recognised by their field names.
+pub fn to_json_value<T: Serialize>(
+    value: &T,
+    format: impl Into<JsonFormat>,
+) -> serde_json::Result<Value> {
+    let format = format.into();
+    let mut json = serde_json::to_value(value)?;
+    apply_ui_amount_format(&mut json, format.ui_amounts);
+    if format.canonical {
+        canonicalize(&mut json);
+    }
+    Ok(json)
+}
+
+/// Serialize `value` to canonical JSON: see [`JsonFormat::canonical`].
+pub fn to_canonical_string<T: Serialize>(value: &T) -> serde_json::Result<String> {
+    serde_json::to_string(&to_json_value(value, JsonFormat::canonical())?)
+}
+
+/// Sort object keys and order arrays of events by `idx`, recursively.
+///
+/// Keys are re-inserted in sorted order rather than relying on the map type, so the result
+/// holds even when `serde_json/preserve_order` is enabled elsewhere in the build.
+pub fn canonicalize(value: &mut Value) {
+    match value {
+        Value::Object(object) => {
+            let mut entries: Vec<_> = std::mem::take(object).into_iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            for (key, mut value) in entries {
+                canonicalize(&mut value);
+                object.insert(key, value);
+            }
+        }
+        Value::Array(values) => {
+            values.iter_mut().for_each(canonicalize);
+            if values.iter().all(|value| event_idx(value).is_some()) {
+                values.sort_by(compare_events);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn event_idx(value: &Value) -> Option<&str> {
+    value.get("idx").and_then(Value::as_str)
+}
+
+/// Order by `idx`, breaking ties on the rendered event so that parsers emitting several
+/// events at one instruction cannot reorder them between runs.
+fn compare_events(a: &Value, b: &Value) -> Ordering {
+    let (a_idx, b_idx) = (event_idx(a).unwrap_or(""), event_idx(b).unwrap_or(""));
+    compare_idx(a_idx, b_idx).then_with(|| a.to_string().cmp(&b.to_string()))
+}
+
+/// Rewrite the UI amounts of an already serialized result in place.
+pub fn apply_ui_amount_format(value: &mut Value, format: UiAmountFormat) {
+    if format == UiAmountFormat::Number {
+        return;
+    }
+    match value {
+        Value::Object(object) => {
+            stringify_ui_amounts(object);
+            object
+                .values_mut()
+                .for_each(|value| apply_ui_amount_format(value, format));
+        }
+        Value::Array(values) => values
+            .iter_mut()
+            .for_each(|value| apply_ui_amount_format(value, format)),
+        _ => {}
+    }
+}
+
+fn stringify_ui_amounts(object: &mut Map<String, Value>) {
+    for (ui, raw, decimals) in UI_AMOUNTS_WITH_RAW {
+        if !object.get(ui).is_some_and(Value::is_number) {
+            continue;
+        }
+        let exact = object
+            .get(raw)
+            .and_then(Value::as_str)
+            .zip(object.get(decimals).and_then(Value::as_u64))
+            .and_then(|(raw, decimals)| raw_to_decimal(raw, decimals as usize));
+        if let Some(exact) = exact {
+            object.insert(ui.to_string(), Value::String(exact));
+        } else {
+            stringify_number(object, ui);
+        }
+    }
+    for ui in UI_AMOUNTS {
+        stringify_number(object, ui);
+    }
+}
+
+fn stringify_number(object: &mut Map<String, Value>, key: &str) {
+    if let Some(value @ Value::Number(_)) = object.get_mut(key) {
+        *value = Value::String(value.to_string());
+    }
+}
+
+/// How [`format_amount_with`] treats digits beyond the requested decimal places.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum Rounding {
+    /// Drop the extra digits, so a balance is never shown larger than it is.
+    #[default]
+    Truncate,
+    /// Round half away from zero.
+    HalfUp,
+    /// Round half to even (banker's rounding), for totals of many rounded amounts.
+    HalfEven,
+}
+
+/// Sign, whole digits and exactly `decimals` fraction digits of `raw / 10^decimals`.
+fn split_raw(raw: &str, decimals: usize) -> Option<(&'static str, String, String)> {
+    let (sign, digits) = match raw.strip_prefix('-') {
+        Some(digits) => ("-", digits),
+        None => ("", raw),
+    };
+    if digits.is_empty() || !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    let digits = format!("{digits:0>width$}", width = decimals + 1);
+    let (whole, fraction) = digits.split_at(digits.len() - decimals);
+    let whole = whole.trim_start_matches('0');
+    let whole = if whole.is_empty() { "0" } else { whole };
+    Some((sign, whole.to_string(), fraction.to_string()))
+}
+
+/// Add one to the last digit of a decimal digit string, carrying to the left.
+fn increment(digits: &str) -> String {
+    let mut bytes = digits.as_bytes().to_vec();
+    for byte in bytes.iter_mut().rev() {
+        if *byte == b'9' {
+            *byte = b'0';
+        } else {
+            *byte += 1;
+            return String::from_utf8(bytes).expect("ascii digits");
+        }
+    }
+    format!("1{}", String::from_utf8(bytes).expect("ascii digits"))
+}
+
+/// `raw / 10^decimals` with exactly `dp` decimal places, truncating extra digits:
+/// `format_amount("1234567", 6, 2)` is `"1.23"`. `None` when `raw` is not an integer.
+///
+/// Works on the digits of the raw amount, so large amounts keep their precision where a
+/// detour through `f64` would not.
+pub fn format_amount(raw: &str, decimals: u8, dp: usize) -> Option<String> {
+    format_amount_with(raw, decimals, dp, Rounding::Truncate)
+}
+
+/// [`format_amount`] with an explicit [`Rounding`] mode.
+pub fn format_amount_with(
+    raw: &str,
+    decimals: u8,
+    dp: usize,
+    rounding: Rounding,
+) -> Option<String> {
+    let (sign, whole, fraction) = split_raw(raw, decimals as usize)?;
+    let mut fraction = fraction;
+    let dropped = if dp < fraction.len() {
+        fraction.split_off(dp)
+    } else {
+        fraction.extend(std::iter::repeat_n('0', dp - fraction.len()));
+        String::new()
+    };
+    let digits = format!("{whole}{fraction}");
+    let round_up = match (rounding, dropped.as_bytes().first()) {
+        (Rounding::Truncate, _) | (_, None) => false,
+        (Rounding::HalfUp, Some(first)) => *first >= b'5',
+        (Rounding::HalfEven, Some(first)) => match first.cmp(&b'5') {
+            Ordering::Greater => true,
+            Ordering::Less => false,
+            Ordering::Equal => {
+                dropped[1..].bytes().any(|byte| byte != b'0')
+                    || digits
+                        .bytes()
+                        .last()
+                        .is_some_and(|last| (last - b'0') % 2 == 1)
+            }
+        },
+    };
+    let digits = if round_up { increment(&digits) } else { digits };
+    let (whole, fraction) = digits.split_at(digits.len() - dp);
+    let sign = if digits.bytes().all(|byte| byte == b'0') {
+        ""
+    } else {
+        sign
+    };
+    Some(match fraction {
+        "" => format!("{sign}{whole}"),
+        fraction => format!("{sign}{whole}.{fraction}"),
+    })
+}
+
+/// `raw / 10^decimals` as a plain decimal string without trailing zeros.
+pub(crate) fn raw_to_decimal(raw: &str, decimals: usize) -> Option<String> {
+    let (sign, whole, fraction) = split_raw(raw, decimals)?;
+    Some(match fraction.trim_end_matches('0') {
+        "" => format!("{sign}{whole}"),
+        fraction => format!("{sign}{whole}.{fraction}"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn raw_amounts_render_as_exact_decimals() {
+        assert_eq!(raw_to_decimal("1500000", 6).as_deref(), Some("1.5"));
+        assert_eq!(raw_to_decimal("42", 9).as_deref(), Some("0.000000042"));
+        assert_eq!(raw_to_decimal("-5000", 3).as_deref(), Some("-5"));
+        assert_eq!(raw_to_decimal("0", 6).as_deref(), Some("0"));
+        assert_eq!(raw_to_decimal("12", 0).as_deref(), Some("12"));
+        assert_eq!(raw_to_decimal("1e5", 2), None);
+    }
+
+    #[test]
+    fn amounts_format_to_fixed_decimal_places() {
+        assert_eq!(format_amount("1234567", 6, 2).as_deref(), Some("1.23"));
+        assert_eq!(format_amount("1500000", 6, 0).as_deref(), Some("1"));
+        assert_eq!(format_amount("15", 1, 3).as_deref(), Some("1.500"));
+        assert_eq!(format_amount("42", 9, 4).as_deref(), Some("0.0000"));
+        assert_eq!(format_amount("-1239", 3, 2).as_deref(), Some("-1.23"));
+        assert_eq!(format_amount("-1", 3, 2).as_deref(), Some("0.00"));
+        assert_eq!(format_amount("12.5", 2, 2), None);
+    }
+
+    #[test]
+    fn rounding_modes_differ_on_the_dropped_digits() {
+        let round = |raw, dp, rounding| format_amount_with(raw, 3, dp, rounding).unwrap();
+        assert_eq!(round("1235", 2, Rounding::Truncate), "1.23");
+        assert_eq!(round("1235", 2, Rounding::HalfUp), "1.24");
+        assert_eq!(round("1225", 2, Rounding::HalfEven), "1.22");
+        assert_eq!(round("1235", 2, Rounding::HalfEven), "1.24");
+        assert_eq!(round("1225", 1, Rounding::HalfEven), "1.2");
+        assert_eq!(round("9995", 2, Rounding::HalfUp), "10.00");
+        assert_eq!(round("-1995", 0, Rounding::HalfUp), "-2");
+        assert_eq!(round("499", 0, Rounding::HalfUp), "0");
+    }
+}
@@ -0,0 +1,227 @@
+//! Yellowstone gRPC (Geyser) transaction updates, behind the `grpc` feature.
+//!
+//! Streams from a Yellowstone endpoint carry transactions as protobuf messages with raw
+//! pubkeys and instruction data; the conversion here builds the parser's input from them
+//! directly, without going through the RPC JSON encoding.
+
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Context, Result};
+use yellowstone_grpc_proto::geyser::{SubscribeUpdateTransaction, SubscribeUpdateTransactionInfo};
+use yellowstone_grpc_proto::solana::storage::confirmed_block::{
+    self, CompiledInstruction, InnerInstructions, TransactionStatusMeta,
+};
+
+use crate::types::{
+    BalanceChange, InnerInstruction, SolanaInstruction, SolanaTransaction, TokenAmount,
+    TokenBalance, TransactionMeta, TransactionStatus,
+};
+
+/// Convert a `SubscribeUpdateTransaction` into the internal SolanaTransaction type.
+///
+/// Account keys are the message keys followed by the writable and readonly addresses loaded
+/// from lookup tables, as in RPC payloads. Updates carry no block time, so `block_time` is
+/// left at 0 for the caller to fill in from the block or slot stream.
+pub fn transaction_from_grpc(update: &SubscribeUpdateTransaction) -> Result<SolanaTransaction> {
+    let info = update
+        .transaction
+        .as_ref()
+        .context("update has no transaction")?;
+    transaction_from_grpc_info(update.slot, info)
+}
+
+/// Convert the transaction of an update whose slot is known separately, e.g. one taken from
+/// a `SubscribeUpdateBlock`.
+pub fn transaction_from_grpc_info(
+    slot: u64,
+    info: &SubscribeUpdateTransactionInfo,
+) -> Result<SolanaTransaction> {
+    let transaction = info
+        .transaction
+        .as_ref()
+        .context("update has no transaction")?;
+    let message = transaction
+        .message
+        .as_ref()
+        .context("transaction missing message")?;
+    let meta = info
+        .meta
+        .as_ref()
+        .context("transaction missing status meta")?;
+
+    let signature = if info.signature.is_empty() {
+        transaction
+            .signatures
+            .first()
+            .ok_or_else(|| anyhow!("transaction missing signature"))?
+    } else {
+        &info.signature
+    };
+    let num_signers = message
+        .header
+        .as_ref()
+        .map_or(0, |header| header.num_required_signatures as usize);
+
+    let mut account_keys: Vec<String> = message.account_keys.iter().map(encode).collect();
+    let signers = account_keys.iter().take(num_signers).cloned().collect();
+    account_keys.extend(meta.loaded_writable_addresses.iter().map(encode));
+    account_keys.extend(meta.loaded_readonly_addresses.iter().map(encode));
+
+    let instructions = message
+        .instructions
+        .iter()
+        .map(|ix| convert_compiled_instruction(ix, &account_keys))
+        .collect::<Result<_>>()?;
+
+    Ok(SolanaTransaction {
+        slot,
+        signature: encode(signature),
+        block_time: 0,
+        signers,
+        instructions,
+        inner_instructions: convert_inner_instructions(&meta.inner_instructions, &account_keys)?,
+        transfers: Vec::new(),
+        pre_token_balances: convert_token_balances(&meta.pre_token_balances, &account_keys),
+        post_token_balances: convert_token_balances(&meta.post_token_balances, &account_keys),
+        meta: TransactionMeta {
+            fee: meta.fee,
+            compute_units: meta.compute_units_consumed.unwrap_or(0),
+            status: if meta.err.is_some() {
+                TransactionStatus::Failed
+            } else {
+                TransactionStatus::Success
+            },
+            sol_balance_changes: collect_sol_balance_changes(meta, &account_keys),
+            token_balance_changes: HashMap::new(),
+            log_messages: meta.log_messages.clone(),
+            // Geyser carries the error bincode-encoded, as the ledger stores it.
+            err: meta
+                .err
+                .as_ref()
+                .and_then(|err| bincode::deserialize(&err.err).ok()),
+        },
+    })
+}
+
+fn encode(bytes: impl AsRef<[u8]>) -> String {
+    bs58::encode(bytes).into_string()
+}
+
+/// Program and accounts of an instruction, resolved against the account keys. Parsers read
+/// accounts by position, so an index past the known keys is an error.
+fn resolve(
+    program_id_index: u32,
+    accounts: &[u8],
+    account_keys: &[String],
+) -> Result<(String, Vec<String>)> {
+    let key = |index: usize| {
+        account_keys.get(index).cloned().ok_or_else(|| {
+            anyhow!(
+                "account index {index} is past the {} resolved account keys",
+                account_keys.len()
+            )
+        })
+    };
+    Ok((
+        key(program_id_index as usize)?,
+        accounts
+            .iter()
+            .map(|index| key(*index as usize))
+            .collect::<Result<_>>()?,
+    ))
+}
+
+fn convert_compiled_instruction(
+    instruction: &CompiledInstruction,
+    account_keys: &[String],
+) -> Result<SolanaInstruction> {
+    let (program_id, accounts) = resolve(
+        instruction.program_id_index,
+        &instruction.accounts,
+        account_keys,
+    )?;
+    Ok(SolanaInstruction {
+        program_id,
+        accounts,
+        data: encode(&instruction.data),
+        stack_height: None,
+    })
+}
+
+fn convert_inner_instructions(
+    sets: &[InnerInstructions],
+    account_keys: &[String],
+) -> Result<Vec<InnerInstruction>> {
+    sets.iter()
+        .map(|set| {
+            Ok(InnerInstruction {
+                index: set.index as usize,
+                instructions: set
+                    .instructions
+                    .iter()
+                    .map(|ix| {
+                        let (program_id, accounts) =
+                            resolve(ix.program_id_index, &ix.accounts, account_keys)?;
+                        Ok(SolanaInstruction {
+                            program_id,
+                            accounts,
+                            data: encode(&ix.data),
+                            stack_height: ix.stack_height,
+                        })
+                    })
+                    .collect::<Result<_>>()?,
+            })
+        })
+        .collect()
+}
+
+fn convert_token_balances(
+    balances: &[confirmed_block::TokenBalance],
+    account_keys: &[String],
+) -> Vec<TokenBalance> {
+    balances
+        .iter()
+        .filter_map(|balance| {
+            let account = account_keys.get(balance.account_index as usize)?.clone();
+            let amount = balance.ui_token_amount.as_ref();
+            Some(TokenBalance {
+                account,
+                mint: balance.mint.clone(),
+                owner: (!balance.owner.is_empty()).then(|| balance.owner.clone()),
+                ui_token_amount: TokenAmount {
+                    amount: amount
+                        .map(|amount| amount.amount.clone())
+                        .unwrap_or_default(),
+                    // Protobuf has no null; zero stands for the `None` RPC reports on empty
+                    // accounts, as in the Solana storage conversion.
+                    ui_amount: amount
+                        .map(|amount| amount.ui_amount)
+                        .filter(|ui_amount| *ui_amount != 0.0),
+                    decimals: amount.map_or(0, |amount| amount.decimals as u8),
+                },
+            })
+        })
+        .collect()
+}
+
+fn collect_sol_balance_changes(
+    meta: &TransactionStatusMeta,
+    account_keys: &[String],
+) -> HashMap<String, BalanceChange> {
+    let mut changes = HashMap::new();
+    for (idx, key) in account_keys.iter().enumerate() {
+        if let (Some(pre), Some(post)) = (meta.pre_balances.get(idx), meta.post_balances.get(idx)) {
+            if pre != post {
+                changes.insert(
+                    key.clone(),
+                    BalanceChange {
+                        pre: *pre as i128,
+                        post: *post as i128,
+                        change: *post as i128 - *pre as i128,
+                    },
+                );
+            }
+        }
+    }
+    changes
+}
@@ -0,0 +1,239 @@
+//! Conversions between parser results and solana-sdk types, behind the `sdk-interop` feature.
+//!
+//! Crate types keep addresses and signatures as base58 strings so they serialize as-is; the
+//! conversions here parse them once for callers that already work with `Pubkey`/`Signature`.
+
+use std::str::FromStr;
+
+use solana_account_decoder::parse_token::UiTokenAmount;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use thiserror::Error;
+
+use crate::format::raw_to_decimal;
+use crate::protocols::jupiter::constants::discriminators::{
+    dca_events, jupiter_events, limit_order_events,
+};
+use crate::protocols::jupiter::dca_parser::{DcaFilledEvent, JupiterDcaParser};
+use crate::protocols::jupiter::jupiter_parser::{JupiterParser, JupiterSwapEvent};
+use crate::protocols::jupiter::limit_order_parser::{
+    JupiterLimitOrderParser, LimitOrderTradeEvent,
+};
+use crate::protocols::orca::constants::discriminators::whirlpool_events;
+use crate::protocols::orca::whirlpool_parser::{OrcaWhirlpoolParser, WhirlpoolTradedEvent};
+use crate::types::{
+    MemeEvent, ParseResult, PoolEvent, TokenAmount, TokenInfo, TradeInfo, TransferData,
+};
+
+#[derive(Debug, Error)]
+pub enum InteropError {
+    #[error("invalid pubkey in {field}: {value:?}")]
+    InvalidPubkey { field: &'static str, value: String },
+    #[error("invalid signature: {0:?}")]
+    InvalidSignature(String),
+    #[error("data is not a {0} event")]
+    Discriminator(&'static str),
+    #[error("failed to decode {event} event: {message}")]
+    Decode {
+        event: &'static str,
+        message: String,
+    },
+}
+
+/// Parse a base58 address, naming the field it came from on failure.
+pub fn parse_pubkey(field: &'static str, value: &str) -> Result<Pubkey, InteropError> {
+    Pubkey::from_str(value).map_err(|_| InteropError::InvalidPubkey {
+        field,
+        value: value.to_string(),
+    })
+}
+
+pub fn parse_signature(value: &str) -> Result<Signature, InteropError> {
+    Signature::from_str(value).map_err(|_| InteropError::InvalidSignature(value.to_string()))
+}
+
+impl From<UiTokenAmount> for TokenAmount {
+    fn from(amount: UiTokenAmount) -> Self {
+        Self {
+            amount: amount.amount,
+            ui_amount: amount.ui_amount,
+            decimals: amount.decimals,
+        }
+    }
+}
+
+impl From<&TokenAmount> for UiTokenAmount {
+    fn from(amount: &TokenAmount) -> Self {
+        let ui_amount_string = raw_to_decimal(&amount.amount, amount.decimals as usize)
+            .or_else(|| amount.ui_amount.map(|ui_amount| ui_amount.to_string()))
+            .unwrap_or_default();
+        Self {
+            ui_amount: amount.ui_amount,
+            decimals: amount.decimals,
+            amount: amount.amount.clone(),
+            ui_amount_string,
+        }
+    }
+}
+
+impl From<TokenAmount> for UiTokenAmount {
+    fn from(amount: TokenAmount) -> Self {
+        Self::from(&amount)
+    }
+}
+
+impl TryFrom<&ParseResult> for Signature {
+    type Error = InteropError;
+
+    fn try_from(result: &ParseResult) -> Result<Self, Self::Error> {
+        parse_signature(&result.signature)
+    }
+}
+
+impl TryFrom<&TradeInfo> for Signature {
+    type Error = InteropError;
+
+    fn try_from(trade: &TradeInfo) -> Result<Self, Self::Error> {
+        parse_signature(&trade.signature)
+    }
+}
+
+impl TryFrom<&PoolEvent> for Signature {
+    type Error = InteropError;
+
+    fn try_from(event: &PoolEvent) -> Result<Self, Self::Error> {
+        parse_signature(&event.signature)
+    }
+}
+
+impl TryFrom<&MemeEvent> for Signature {
+    type Error = InteropError;
+
+    fn try_from(event: &MemeEvent) -> Result<Self, Self::Error> {
+        parse_signature(&event.signature)
+    }
+}
+
+impl TryFrom<&TransferData> for Signature {
+    type Error = InteropError;
+
+    fn try_from(transfer: &TransferData) -> Result<Self, Self::Error> {
+        parse_signature(&transfer.signature)
+    }
+}
+
+impl From<&TokenInfo> for UiTokenAmount {
+    fn from(token: &TokenInfo) -> Self {
+        Self::from(&TokenAmount::new(
+            token.amount_raw.clone(),
+            token.decimals,
+            Some(token.amount),
+        ))
+    }
+}
+
+impl TokenInfo {
+    pub fn mint_pubkey(&self) -> Result<Pubkey, InteropError> {
+        parse_pubkey("mint", &self.mint)
+    }
+}
+
+impl TradeInfo {
+    pub fn pool_pubkeys(&self) -> Result<Vec<Pubkey>, InteropError> {
+        self.pool
+            .iter()
+            .map(|pool| parse_pubkey("pool", pool))
+            .collect()
+    }
+
+    pub fn user_pubkey(&self) -> Result<Option<Pubkey>, InteropError> {
+        self.user
+            .as_deref()
+            .map(|user| parse_pubkey("user", user))
+            .transpose()
+    }
+}
+
+impl PoolEvent {
+    pub fn pool_pubkey(&self) -> Result<Pubkey, InteropError> {
+        parse_pubkey("poolId", &self.pool_id)
+    }
+}
+
+impl TransferData {
+    pub fn source_pubkey(&self) -> Result<Pubkey, InteropError> {
+        parse_pubkey("source", &self.info.source)
+    }
+
+    pub fn destination_pubkey(&self) -> Result<Pubkey, InteropError> {
+        parse_pubkey("destination", &self.info.destination)
+    }
+
+    pub fn mint_pubkey(&self) -> Result<Pubkey, InteropError> {
+        parse_pubkey("mint", &self.info.mint)
+    }
+}
+
+/// Body of an Anchor event, accepting both the `emit_cpi!` instruction data (event CPI tag
+/// followed by the event discriminator) and the `emit!` log payload (discriminator only).
+fn anchor_event_body<'a>(
+    data: &'a [u8],
+    discriminator: &[u8; 16],
+    event: &'static str,
+) -> Result<&'a [u8], InteropError> {
+    if data.starts_with(discriminator) {
+        Ok(&data[16..])
+    } else if data.starts_with(&discriminator[8..]) {
+        Ok(&data[8..])
+    } else {
+        Err(InteropError::Discriminator(event))
+    }
+}
+
+impl TryFrom<&[u8]> for WhirlpoolTradedEvent {
+    type Error = InteropError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        let body = anchor_event_body(data, &whirlpool_events::TRADED, "Traded")?;
+        OrcaWhirlpoolParser::decode_traded_event(body).map_err(|err| InteropError::Decode {
+            event: "Traded",
+            message: err.to_string(),
+        })
+    }
+}
+
+impl TryFrom<&[u8]> for JupiterSwapEvent {
+    type Error = InteropError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        let body = anchor_event_body(data, &jupiter_events::SWAP, "SwapEvent")?;
+        JupiterParser::decode_swap_event(body).map_err(|err| InteropError::Decode {
+            event: "SwapEvent",
+            message: err.to_string(),
+        })
+    }
+}
+
+impl TryFrom<&[u8]> for DcaFilledEvent {
+    type Error = InteropError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        let body = anchor_event_body(data, &dca_events::FILLED, "Filled")?;
+        JupiterDcaParser::decode_filled_event(body).map_err(|err| InteropError::Decode {
+            event: "Filled",
+            message: err.to_string(),
+        })
+    }
+}
+
+impl TryFrom<&[u8]> for LimitOrderTradeEvent {
+    type Error = InteropError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        let body = anchor_event_body(data, &limit_order_events::TRADE, "TradeEvent")?;
+        JupiterLimitOrderParser::decode_trade_event(body).map_err(|err| InteropError::Decode {
+            event: "TradeEvent",
+            message: err.to_string(),
+        })
+    }
+}
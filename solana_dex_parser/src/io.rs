@@ -0,0 +1,173 @@
+//! File helpers that transparently handle gzip/zstd compressed dumps.
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use flate2::read::MultiGzDecoder;
+use flate2::write::GzEncoder;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::format::{to_json_value, JsonFormat, UiAmountFormat};
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+/// Compression applied to a file, detected from content on read and from the extension on write.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Compression {
+    None,
+    Gzip,
+    Zstd,
+}
+
+impl Compression {
+    /// Pick the compression implied by a `.gz`/`.zst` file extension.
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("gz") | Some("gzip") => Self::Gzip,
+            Some("zst") | Some("zstd") => Self::Zstd,
+            _ => Self::None,
+        }
+    }
+
+    fn sniff(header: &[u8]) -> Self {
+        if header.starts_with(&GZIP_MAGIC) {
+            Self::Gzip
+        } else if header.starts_with(&ZSTD_MAGIC) {
+            Self::Zstd
+        } else {
+            Self::None
+        }
+    }
+}
+
+/// Open a file for reading, decompressing gzip or zstd content on the fly.
+pub fn open_reader(path: impl AsRef<Path>) -> Result<Box<dyn BufRead>> {
+    let path = path.as_ref();
+    let file = File::open(path).with_context(|| format!("failed to open {:?}", path))?;
+    let mut reader = BufReader::new(file);
+    let header = reader
+        .fill_buf()
+        .with_context(|| format!("failed to read {:?}", path))?;
+    Ok(match Compression::sniff(header) {
+        Compression::Gzip => Box::new(BufReader::new(MultiGzDecoder::new(reader))),
+        Compression::Zstd => Box::new(BufReader::new(
+            zstd::Decoder::with_buffer(reader)
+                .with_context(|| format!("failed to init zstd decoder for {:?}", path))?,
+        )),
+        Compression::None => Box::new(reader),
+    })
+}
+
+/// Read a whole (possibly compressed) file into a string.
+pub fn read_to_string(path: impl AsRef<Path>) -> Result<String> {
+    let path = path.as_ref();
+    let mut data = String::new();
+    open_reader(path)?
+        .read_to_string(&mut data)
+        .with_context(|| format!("failed to read {:?}", path))?;
+    Ok(data)
+}
+
+/// Deserialize a (possibly compressed) JSON file.
+pub fn read_json<T: DeserializeOwned>(path: impl AsRef<Path>) -> Result<T> {
+    let path = path.as_ref();
+    serde_json::from_reader(open_reader(path)?)
+        .with_context(|| format!("failed to parse JSON in {:?}", path))
+}
+
+/// File writer compressing according to the target extension.
+pub enum CompressedWriter {
+    Plain(BufWriter<File>),
+    Gzip(GzEncoder<BufWriter<File>>),
+    Zstd(zstd::Encoder<'static, BufWriter<File>>),
+}
+
+impl CompressedWriter {
+    pub fn create(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let file = BufWriter::new(
+            File::create(path).with_context(|| format!("failed to create {:?}", path))?,
+        );
+        Ok(match Compression::from_path(path) {
+            Compression::Gzip => Self::Gzip(GzEncoder::new(file, flate2::Compression::default())),
+            Compression::Zstd => Self::Zstd(
+                zstd::Encoder::new(file, 0)
+                    .with_context(|| format!("failed to init zstd encoder for {:?}", path))?,
+            ),
+            Compression::None => Self::Plain(file),
+        })
+    }
+
+    /// Write the compression trailer and flush; must be called before dropping.
+    pub fn finish(self) -> io::Result<()> {
+        match self {
+            Self::Plain(mut writer) => writer.flush(),
+            Self::Gzip(encoder) => encoder.finish()?.flush(),
+            Self::Zstd(encoder) => encoder.finish()?.flush(),
+        }
+    }
+}
+
+impl Write for CompressedWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Self::Plain(writer) => writer.write(buf),
+            Self::Gzip(writer) => writer.write(buf),
+            Self::Zstd(writer) => writer.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Self::Plain(writer) => writer.flush(),
+            Self::Gzip(writer) => writer.flush(),
+            Self::Zstd(writer) => writer.flush(),
+        }
+    }
+}
+
+/// Newline-delimited JSON sink on top of [`CompressedWriter`].
+pub struct NdjsonWriter {
+    inner: CompressedWriter,
+    format: JsonFormat,
+}
+
+impl NdjsonWriter {
+    pub fn create(path: impl AsRef<Path>) -> Result<Self> {
+        Ok(Self {
+            inner: CompressedWriter::create(path)?,
+            format: JsonFormat::default(),
+        })
+    }
+
+    /// Render the UI amounts of every record as `format`.
+    pub fn with_ui_amount_format(mut self, format: UiAmountFormat) -> Self {
+        self.format.ui_amounts = format;
+        self
+    }
+
+    /// Render every record as `format`, UI amounts included.
+    pub fn with_json_format(mut self, format: JsonFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    pub fn write_record<T: Serialize>(&mut self, record: &T) -> Result<()> {
+        if self.format == JsonFormat::default() {
+            serde_json::to_writer(&mut self.inner, record)?;
+        } else {
+            serde_json::to_writer(&mut self.inner, &to_json_value(record, self.format)?)?;
+        }
+        self.inner.write_all(b"\n")?;
+        Ok(())
+    }
+
+    pub fn finish(self) -> Result<()> {
+        self.inner.finish()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,280 @@
+//! Per-day, per-venue, per-token trade rollups of parse results.
+
+use std::collections::{BTreeMap, HashSet};
+use std::io::{self, Write};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::ParseConfig;
+use crate::core::block_stats::venue;
+use crate::types::{BlockParseResult, ParseResult, TradeInfo};
+
+const SECONDS_PER_DAY: u64 = 86_400;
+const CSV_HEADER: &str = "date,venue,mint,trades,volume,volume_sol,unique_wallets";
+#[cfg(feature = "parquet")]
+const PARQUET_SCHEMA: &str = "
+    message daily_rollup {
+        REQUIRED BYTE_ARRAY date (UTF8);
+        REQUIRED BYTE_ARRAY venue (UTF8);
+        REQUIRED BYTE_ARRAY mint (UTF8);
+        REQUIRED INT64 trades;
+        REQUIRED DOUBLE volume;
+        REQUIRED DOUBLE volume_sol;
+        REQUIRED INT64 unique_wallets;
+    }
+";
+
+/// Totals of one token on one venue over one UTC day.
+///
+/// Every trade counts towards both of its tokens. `volume` is in UI units of `mint`;
+/// `volume_sol` sums the trades that carry a SOL valuation.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RollupRow {
+    /// UTC day as `YYYY-MM-DD`.
+    pub date: String,
+    pub venue: String,
+    pub mint: String,
+    pub trades: u64,
+    pub volume: f64,
+    pub volume_sol: f64,
+    pub unique_wallets: u64,
+}
+
+#[derive(Default)]
+struct Totals {
+    trades: u64,
+    volume: f64,
+    volume_sol: f64,
+    wallets: HashSet<String>,
+}
+
+/// Accumulates trades into daily rollup rows.
+///
+/// Feed it parse results in any order, from files or straight from a pipeline sink; rows
+/// are keyed by `(date, venue, mint)` and come out sorted by that key.
+#[derive(Default)]
+pub struct DailyRollup {
+    config: ParseConfig,
+    totals: BTreeMap<(String, String, String), Totals>,
+}
+
+impl DailyRollup {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Name the venue of trades without an AMM with `config`, so venues of its program tables
+    /// are known.
+    pub fn with_config(mut self, config: ParseConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    pub fn observe_block(&mut self, block: &BlockParseResult) {
+        for result in &block.transactions {
+            self.observe(result);
+        }
+    }
+
+    pub fn observe(&mut self, result: &ParseResult) {
+        for trade in &result.trades {
+            self.observe_trade(trade);
+        }
+    }
+
+    pub fn observe_trade(&mut self, trade: &TradeInfo) {
+        let date = utc_date(trade.timestamp);
+        let venue = venue(trade, &self.config);
+        let wallet = trade.user.clone().or_else(|| {
+            trade
+                .signer
+                .as_ref()
+                .and_then(|signers| signers.first().cloned())
+        });
+        for token in [&trade.input_token, &trade.output_token] {
+            let totals = self
+                .totals
+                .entry((date.clone(), venue.clone(), token.mint.clone()))
+                .or_default();
+            totals.trades += 1;
+            totals.volume += token.amount;
+            totals.volume_sol += trade.volume_sol.unwrap_or(0.0);
+            if let Some(wallet) = &wallet {
+                totals.wallets.insert(wallet.clone());
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.totals.is_empty()
+    }
+
+    pub fn rows(&self) -> Vec<RollupRow> {
+        self.totals
+            .iter()
+            .map(|((date, venue, mint), totals)| RollupRow {
+                date: date.clone(),
+                venue: venue.clone(),
+                mint: mint.clone(),
+                trades: totals.trades,
+                volume: totals.volume,
+                volume_sol: totals.volume_sol,
+                unique_wallets: totals.wallets.len() as u64,
+            })
+            .collect()
+    }
+
+    /// Write the rows as CSV with a header line.
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(writer, "{CSV_HEADER}")?;
+        for row in self.rows() {
+            writeln!(
+                writer,
+                "{},{},{},{},{},{},{}",
+                row.date,
+                csv_field(&row.venue),
+                csv_field(&row.mint),
+                row.trades,
+                row.volume,
+                row.volume_sol,
+                row.unique_wallets
+            )?;
+        }
+        writer.flush()
+    }
+
+    /// Write the rows as a Parquet file with one row group and the CSV's columns.
+    #[cfg(feature = "parquet")]
+    pub fn write_parquet<W: Write + Send>(&self, writer: W) -> parquet::errors::Result<()> {
+        use std::sync::Arc;
+
+        use parquet::data_type::{ByteArray, ByteArrayType, DoubleType, Int64Type};
+        use parquet::file::properties::WriterProperties;
+        use parquet::file::writer::SerializedFileWriter;
+        use parquet::schema::parser::parse_message_type;
+
+        let rows = self.rows();
+        let strings = |field: fn(&RollupRow) -> &str| -> Vec<ByteArray> {
+            rows.iter().map(|row| ByteArray::from(field(row))).collect()
+        };
+        let counts = |field: fn(&RollupRow) -> u64| -> Vec<i64> {
+            rows.iter().map(|row| field(row) as i64).collect()
+        };
+        let amounts =
+            |field: fn(&RollupRow) -> f64| -> Vec<f64> { rows.iter().map(field).collect() };
+
+        let schema = Arc::new(parse_message_type(PARQUET_SCHEMA)?);
+        let properties = Arc::new(WriterProperties::builder().build());
+        let mut file = SerializedFileWriter::new(writer, schema, properties)?;
+        let mut row_group = file.next_row_group()?;
+        let mut index = 0;
+        while let Some(mut column) = row_group.next_column()? {
+            match index {
+                0 => column.typed::<ByteArrayType>().write_batch(
+                    &strings(|row| &row.date),
+                    None,
+                    None,
+                )?,
+                1 => column.typed::<ByteArrayType>().write_batch(
+                    &strings(|row| &row.venue),
+                    None,
+                    None,
+                )?,
+                2 => column.typed::<ByteArrayType>().write_batch(
+                    &strings(|row| &row.mint),
+                    None,
+                    None,
+                )?,
+                3 => column.typed::<Int64Type>().write_batch(
+                    &counts(|row| row.trades),
+                    None,
+                    None,
+                )?,
+                4 => column.typed::<DoubleType>().write_batch(
+                    &amounts(|row| row.volume),
+                    None,
+                    None,
+                )?,
+                5 => column.typed::<DoubleType>().write_batch(
+                    &amounts(|row| row.volume_sol),
+                    None,
+                    None,
+                )?,
+                _ => column.typed::<Int64Type>().write_batch(
+                    &counts(|row| row.unique_wallets),
+                    None,
+                    None,
+                )?,
+            };
+            column.close()?;
+            index += 1;
+        }
+        row_group.close()?;
+        file.close()?;
+        Ok(())
+    }
+
+    /// Roll up dump files of parse results: NDJSON or JSON documents holding either
+    /// `ParseResult`s or `BlockParseResult`s, as written by `dexp`, optionally compressed.
+    #[cfg(feature = "compression")]
+    pub fn from_files<P: AsRef<std::path::Path>>(
+        paths: impl IntoIterator<Item = P>,
+    ) -> anyhow::Result<Self> {
+        let mut rollup = Self::new();
+        rollup.observe_files(paths)?;
+        Ok(rollup)
+    }
+
+    /// Add the parse results of dump files, read as in [`from_files`](Self::from_files).
+    #[cfg(feature = "compression")]
+    pub fn observe_files<P: AsRef<std::path::Path>>(
+        &mut self,
+        paths: impl IntoIterator<Item = P>,
+    ) -> anyhow::Result<()> {
+        use anyhow::Context;
+
+        for path in paths {
+            let path = path.as_ref();
+            let text = crate::io::read_to_string(path)?;
+            for value in serde_json::Deserializer::from_str(&text).into_iter::<serde_json::Value>()
+            {
+                let value = value.with_context(|| format!("invalid JSON in {}", path.display()))?;
+                if value.get("transactions").is_some() {
+                    self.observe_block(&serde_json::from_value(value)?);
+                } else {
+                    self.observe(&serde_json::from_value(value)?);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Civil UTC date of a unix timestamp in seconds.
+fn utc_date(timestamp: u64) -> String {
+    // Days-to-civil conversion from Howard Hinnant's date algorithms.
+    let days = (timestamp / SECONDS_PER_DAY) as i64 + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
@@ -0,0 +1,3 @@
+//! Batch jobs built on top of parse results.
+
+pub mod daily_rollup;
@@ -2,14 +2,71 @@
 
 pub mod config;
 pub mod core;
+pub mod format;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+#[cfg(feature = "sdk-interop")]
+pub mod interop;
+#[cfg(feature = "compression")]
+pub mod io;
+pub mod jobs;
 pub mod protocols;
 pub mod rpc;
+pub mod service;
+pub mod stream;
 pub mod types;
+pub mod watch;
+#[cfg(feature = "ws")]
+pub mod ws;
 
-pub use crate::config::ParseConfig;
-pub use crate::core::dex_parser::DexParser;
+pub use crate::config::{NetworkProfile, ParseConfig, ProtocolConfig, TradingBot};
+pub use crate::core::dex_parser::{DexParser, NormalizeMiddleware, PostProcessor};
+pub use crate::core::migration::MigrationTracker;
+pub use crate::core::pnl::{PnlEvent, PnlTracker, Position};
+pub use crate::core::pool::{ParserPool, PooledParser};
+pub use crate::core::pool_state::{PoolStateProvider, PrefetchedPoolStates};
+pub use crate::core::pool_tracker::{PoolStateTracker, TrackedPool};
+pub use crate::core::price::{CachedPriceProvider, PriceProvider, StablePairPrice};
+pub use crate::format::{format_amount, format_amount_with, JsonFormat, Rounding, UiAmountFormat};
+pub use crate::rpc::SimulationContext;
+#[cfg(feature = "rpc")]
+pub use crate::rpc::{BackfillOptions, RetryPolicy};
 pub use crate::types::{
-    BalanceChange, BlockInput, BlockParseResult, ClassifiedInstruction, DexInfo, MemeEvent,
-    ParseResult, PoolEvent, SolanaBlock, SolanaInstruction, SolanaTransaction, TokenAmount,
-    TradeInfo, TransactionMeta, TransactionStatus, TransferData,
+    ArbitrageInfo, ArbitrageLeg, BalanceChange, BlockContext, BlockInput, BlockParseResult,
+    BlockStats, BridgeDirection, BridgeEvent, ClassifiedInstruction, ComputeBudget, DexInfo,
+    FailedCpi, FeeBreakdown, FeeShare, FlowLabel, JitoTip, MemeEvent, MevEvent, MevType,
+    MigrationEvent, ParseResult, ParseTimings, PoolEvent, PoolState, PositionInfo,
+    ProgramInvocation, SolanaBlock, SolanaInstruction, SolanaTransaction, StageTiming,
+    TokenAccountOwner, TokenAmount, TradeInfo, TradeStatus, TransactionMeta, TransactionStatus,
+    TransferData, VolumeStats, WalletFlow,
 };
+#[cfg(feature = "webhooks")]
+pub use crate::watch::WatchSpec;
+pub use crate::watch::{AlertTarget, TradeAlert, Watch, WatchList};
+
+// Results cross async channels and rayon pools, and parsers are shared between threads;
+// any non-thread-safe or borrowed state creeping into them fails the build here. `Any`
+// stands for `'static`.
+macro_rules! assert_thread_safe {
+    ($($ty:ty),* $(,)?) => {
+        $(static_assertions::assert_impl_all!($ty: Send, Sync, std::any::Any);)*
+    };
+}
+
+assert_thread_safe!(
+    DexParser,
+    ParserPool,
+    ParseConfig,
+    crate::core::error::ParserError,
+    SolanaTransaction,
+    SolanaBlock,
+    ParseResult,
+    BlockParseResult,
+    TradeInfo,
+    PoolEvent,
+    TransferData,
+    MemeEvent,
+    MigrationEvent,
+    BridgeEvent,
+    MevEvent,
+);
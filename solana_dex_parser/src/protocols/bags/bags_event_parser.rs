@@ -0,0 +1,167 @@
+use crate::protocols::pumpfun::binary_reader::BinaryReader;
+use crate::protocols::pumpfun::util::{decode_instruction_data, sort_by_idx, HasIdx};
+use crate::types::{ClassifiedInstruction, FeeShare};
+
+use super::constants::discriminators::{bags_events, bags_instructions};
+use super::error::BagsError;
+
+/// Account positions of `(creator, mint, bonding_curve, quote_mint)` in `create`.
+const CREATE_ACCOUNTS: (usize, usize, usize, usize) = (0, 1, 2, 3);
+
+/// Token launch; the payload carries the metadata and the creator fee split in bps.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BagsCreateEvent {
+    pub creator: String,
+    pub mint: String,
+    pub bonding_curve: String,
+    pub quote_mint: String,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub fee_shares: Vec<FeeShare>,
+}
+
+/// `TradeEvent` emitted by `buy` and `sell`.
+///
+/// `quote_amount` is what the user paid including fees on a buy and what they received net
+/// of fees on a sell. The creator fee is paid out to `fee_shares` in the listed amounts.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BagsTradeEvent {
+    pub mint: String,
+    pub quote_mint: String,
+    pub user: String,
+    pub bonding_curve: String,
+    pub is_buy: bool,
+    pub base_amount: u64,
+    pub quote_amount: u64,
+    pub protocol_fee: u64,
+    pub protocol_fee_recipient: String,
+    pub creator_fee: u64,
+    pub fee_shares: Vec<(String, u64)>,
+}
+
+/// `ClaimFeeEvent` emitted when a fee-share wallet withdraws its accrued creator fees.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BagsClaimFeeEvent {
+    pub mint: String,
+    pub quote_mint: String,
+    pub claimer: String,
+    pub amount: u64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum BagsEventData {
+    Create(BagsCreateEvent),
+    Trade(BagsTradeEvent),
+    ClaimFee(BagsClaimFeeEvent),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BagsEvent {
+    pub data: BagsEventData,
+    pub idx: String,
+}
+
+impl HasIdx for BagsEvent {
+    fn idx(&self) -> &str {
+        &self.idx
+    }
+}
+
+/// Decodes launches from the `create` instruction, and trades and fee claims from the
+/// events the program emits through its self-CPI.
+pub struct BagsEventParser;
+
+impl BagsEventParser {
+    pub fn parse_instructions(
+        instructions: &[ClassifiedInstruction],
+    ) -> Result<Vec<BagsEvent>, BagsError> {
+        let mut events = Vec::new();
+        for classified in instructions {
+            let data = decode_instruction_data(&classified.data.data)
+                .map_err(|err| BagsError::instruction_data(err.to_string()))?;
+            let event = if data.starts_with(&bags_instructions::CREATE) {
+                BagsEventData::Create(Self::decode_create(
+                    &classified.data.accounts,
+                    data[8..].to_vec(),
+                )?)
+            } else if data.starts_with(&bags_events::TRADE) {
+                BagsEventData::Trade(Self::decode_trade(data[16..].to_vec())?)
+            } else if data.starts_with(&bags_events::CLAIM_FEE) {
+                BagsEventData::ClaimFee(Self::decode_claim_fee(data[16..].to_vec())?)
+            } else {
+                continue;
+            };
+            events.push(BagsEvent {
+                data: event,
+                idx: format!(
+                    "{}-{}",
+                    classified.outer_index,
+                    classified.inner_index.unwrap_or(0)
+                ),
+            });
+        }
+        Ok(sort_by_idx(events))
+    }
+
+    fn decode_create(accounts: &[String], payload: Vec<u8>) -> Result<BagsCreateEvent, BagsError> {
+        let (creator, mint, bonding_curve, quote_mint) = CREATE_ACCOUNTS;
+        let account = |index: usize| accounts.get(index).cloned().unwrap_or_default();
+        let mut reader = BinaryReader::new(payload);
+        let name = reader.read_string()?;
+        let symbol = reader.read_string()?;
+        let uri = reader.read_string()?;
+        let shares = reader.read_u32()?;
+        let fee_shares = (0..shares)
+            .map(|_| {
+                Ok(FeeShare {
+                    wallet: reader.read_pubkey()?,
+                    bps: reader.read_u16()?,
+                })
+            })
+            .collect::<Result<_, BagsError>>()?;
+        Ok(BagsCreateEvent {
+            creator: account(creator),
+            mint: account(mint),
+            bonding_curve: account(bonding_curve),
+            quote_mint: account(quote_mint),
+            name,
+            symbol,
+            uri,
+            fee_shares,
+        })
+    }
+
+    fn decode_trade(payload: Vec<u8>) -> Result<BagsTradeEvent, BagsError> {
+        let mut reader = BinaryReader::new(payload);
+        let mut event = BagsTradeEvent {
+            mint: reader.read_pubkey()?,
+            quote_mint: reader.read_pubkey()?,
+            user: reader.read_pubkey()?,
+            bonding_curve: reader.read_pubkey()?,
+            is_buy: reader.read_bool()?,
+            base_amount: reader.read_u64()?,
+            quote_amount: reader.read_u64()?,
+            protocol_fee: reader.read_u64()?,
+            protocol_fee_recipient: reader.read_pubkey()?,
+            creator_fee: reader.read_u64()?,
+            fee_shares: Vec::new(),
+        };
+        for _ in 0..reader.read_u32()? {
+            event
+                .fee_shares
+                .push((reader.read_pubkey()?, reader.read_u64()?));
+        }
+        Ok(event)
+    }
+
+    fn decode_claim_fee(payload: Vec<u8>) -> Result<BagsClaimFeeEvent, BagsError> {
+        let mut reader = BinaryReader::new(payload);
+        Ok(BagsClaimFeeEvent {
+            mint: reader.read_pubkey()?,
+            quote_mint: reader.read_pubkey()?,
+            claimer: reader.read_pubkey()?,
+            amount: reader.read_u64()?,
+        })
+    }
+}
@@ -0,0 +1,277 @@
+use crate::core::amounts::convert_to_ui_amount;
+use crate::core::instruction_classifier::InstructionClassifier;
+use crate::core::transaction_adapter::TransactionAdapter;
+use crate::protocols::pumpfun::util::{
+    attach_token_transfers, build_fee_claim_event, build_fee_info, build_token_info,
+};
+use crate::protocols::simple::{MemeEventParser, TradeParser};
+use crate::types::{
+    ClassifiedInstruction, DexInfo, FeeInfo, MemeEvent, TradeInfo, TradeType, TransferMap,
+};
+
+use super::bags_event_parser::{
+    BagsClaimFeeEvent, BagsCreateEvent, BagsEvent, BagsEventData, BagsEventParser, BagsTradeEvent,
+};
+use super::constants::{BAGS_PROGRAM_ID, BAGS_PROGRAM_NAME};
+
+/// Bags tokens launch with 6 decimals and trade against SOL unless the mint says otherwise.
+const DEFAULT_BASE_DECIMALS: u8 = 6;
+const DEFAULT_QUOTE_DECIMALS: u8 = 9;
+
+fn mint_decimals(adapter: &TransactionAdapter, mint: &str, default: u8) -> u8 {
+    adapter
+        .spl_decimals_map()
+        .get(mint)
+        .copied()
+        .unwrap_or(default)
+}
+
+/// `(input, output)` legs of a trade as `(mint, raw amount, decimals)`.
+fn trade_legs(
+    adapter: &TransactionAdapter,
+    trade: &BagsTradeEvent,
+) -> ((String, u64, u8), (String, u64, u8)) {
+    let base = (
+        trade.mint.clone(),
+        trade.base_amount,
+        mint_decimals(adapter, &trade.mint, DEFAULT_BASE_DECIMALS),
+    );
+    let quote = (
+        trade.quote_mint.clone(),
+        trade.quote_amount,
+        mint_decimals(adapter, &trade.quote_mint, DEFAULT_QUOTE_DECIMALS),
+    );
+    if trade.is_buy {
+        (quote, base)
+    } else {
+        (base, quote)
+    }
+}
+
+/// Fee entries of a trade in the pumpswap layout: the protocol cut, then one creator entry
+/// per fee-share wallet.
+fn trade_fees(trade: &BagsTradeEvent, decimals: u8) -> Vec<FeeInfo> {
+    let fee = |amount: u64, fee_type: &str, recipient: &str| FeeInfo {
+        fee_type: Some(fee_type.to_string()),
+        recipient: Some(recipient.to_string()),
+        ..build_fee_info(
+            &trade.quote_mint,
+            amount as u128,
+            decimals,
+            Some(BAGS_PROGRAM_NAME.to_string()),
+        )
+    };
+    let mut fees = vec![fee(
+        trade.protocol_fee,
+        "protocol",
+        &trade.protocol_fee_recipient,
+    )];
+    fees.extend(
+        trade
+            .fee_shares
+            .iter()
+            .filter(|(_, amount)| *amount > 0)
+            .map(|(wallet, amount)| fee(*amount, "coinCreator", wallet)),
+    );
+    fees
+}
+
+/// Bonding-curve buys and sells of Bags tokens, with the creator fee split across its
+/// fee-share wallets.
+pub struct BagsParser {
+    adapter: TransactionAdapter,
+    dex_info: DexInfo,
+    transfer_actions: TransferMap,
+    classified_instructions: Vec<ClassifiedInstruction>,
+}
+
+impl BagsParser {
+    pub fn new(
+        adapter: TransactionAdapter,
+        dex_info: DexInfo,
+        transfer_actions: TransferMap,
+        classified_instructions: Vec<ClassifiedInstruction>,
+    ) -> Self {
+        Self {
+            adapter,
+            dex_info,
+            transfer_actions,
+            classified_instructions,
+        }
+    }
+
+    fn build_trade(&self, trade: &BagsTradeEvent, idx: String) -> TradeInfo {
+        let (
+            (input_mint, input_amount, input_decimals),
+            (output_mint, output_amount, output_decimals),
+        ) = trade_legs(&self.adapter, trade);
+        let quote_decimals =
+            mint_decimals(&self.adapter, &trade.quote_mint, DEFAULT_QUOTE_DECIMALS);
+        let total_fee = trade.protocol_fee as u128 + trade.creator_fee as u128;
+
+        let trade_info = TradeInfo {
+            trade_type: if trade.is_buy {
+                TradeType::Buy
+            } else {
+                TradeType::Sell
+            },
+            pool: vec![trade.bonding_curve.clone()],
+            input_token: build_token_info(&input_mint, input_amount as u128, input_decimals, None),
+            output_token: build_token_info(
+                &output_mint,
+                output_amount as u128,
+                output_decimals,
+                None,
+            ),
+            fee: Some(build_fee_info(
+                &trade.quote_mint,
+                total_fee,
+                quote_decimals,
+                None,
+            )),
+            fees: trade_fees(trade, quote_decimals),
+            user: Some(trade.user.clone()),
+            program_id: Some(BAGS_PROGRAM_ID.to_string()),
+            amm: Some(BAGS_PROGRAM_NAME.to_string()),
+            route: self.dex_info.route.clone(),
+            slot: self.adapter.slot(),
+            timestamp: self.adapter.block_time(),
+            signature: self.adapter.shared_signature(),
+            idx,
+            ..TradeInfo::default()
+        };
+        attach_token_transfers(&self.adapter, trade_info, &self.transfer_actions)
+    }
+}
+
+impl TradeParser for BagsParser {
+    fn process_trades(&mut self) -> Vec<TradeInfo> {
+        match BagsEventParser::parse_instructions(&self.classified_instructions) {
+            Ok(events) => events
+                .into_iter()
+                .filter_map(|event| match event.data {
+                    BagsEventData::Trade(trade) => Some(self.build_trade(&trade, event.idx)),
+                    _ => None,
+                })
+                .collect(),
+            Err(err) => {
+                tracing::error!("failed to parse bags trade events: {err}");
+                Vec::new()
+            }
+        }
+    }
+}
+
+pub struct BagsMemeParser {
+    adapter: TransactionAdapter,
+    _transfer_actions: TransferMap,
+}
+
+impl BagsMemeParser {
+    pub fn new(adapter: TransactionAdapter, transfer_actions: TransferMap) -> Self {
+        Self {
+            adapter,
+            _transfer_actions: transfer_actions,
+        }
+    }
+
+    fn build_event(&self, event: BagsEvent) -> MemeEvent {
+        let mut meme = match event.data {
+            BagsEventData::Create(create) => Self::create_event(create),
+            BagsEventData::Trade(trade) => self.trade_event(trade),
+            BagsEventData::ClaimFee(claim) => self.claim_event(claim),
+        };
+        meme.signature = self.adapter.shared_signature();
+        meme.slot = self.adapter.slot();
+        meme.timestamp = self.adapter.block_time();
+        meme.idx = event.idx;
+        meme
+    }
+
+    fn create_event(create: BagsCreateEvent) -> MemeEvent {
+        MemeEvent {
+            event_type: TradeType::Create,
+            user: create.creator.clone(),
+            base_mint: create.mint,
+            quote_mint: create.quote_mint,
+            name: Some(create.name),
+            symbol: Some(create.symbol),
+            uri: Some(create.uri),
+            protocol: Some(BAGS_PROGRAM_NAME.to_string()),
+            creator: Some(create.creator),
+            bonding_curve: Some(create.bonding_curve),
+            fee_shares: create.fee_shares,
+            ..MemeEvent::default()
+        }
+    }
+
+    fn trade_event(&self, trade: BagsTradeEvent) -> MemeEvent {
+        let (
+            (input_mint, input_amount, input_decimals),
+            (output_mint, output_amount, output_decimals),
+        ) = trade_legs(&self.adapter, &trade);
+        let quote_decimals =
+            mint_decimals(&self.adapter, &trade.quote_mint, DEFAULT_QUOTE_DECIMALS);
+        let ui = |amount: u64| convert_to_ui_amount(amount, quote_decimals);
+        MemeEvent {
+            event_type: if trade.is_buy {
+                TradeType::Buy
+            } else {
+                TradeType::Sell
+            },
+            user: trade.user,
+            base_mint: trade.mint,
+            quote_mint: trade.quote_mint,
+            input_token: Some(build_token_info(
+                &input_mint,
+                input_amount as u128,
+                input_decimals,
+                None,
+            )),
+            output_token: Some(build_token_info(
+                &output_mint,
+                output_amount as u128,
+                output_decimals,
+                None,
+            )),
+            fee: Some(ui(trade.protocol_fee) + ui(trade.creator_fee)),
+            protocol_fee: Some(ui(trade.protocol_fee)),
+            creator_fee: Some(ui(trade.creator_fee)),
+            protocol: Some(BAGS_PROGRAM_NAME.to_string()),
+            bonding_curve: Some(trade.bonding_curve.clone()),
+            pool: Some(trade.bonding_curve),
+            ..MemeEvent::default()
+        }
+    }
+
+    fn claim_event(&self, claim: BagsClaimFeeEvent) -> MemeEvent {
+        let decimals = mint_decimals(&self.adapter, &claim.quote_mint, DEFAULT_QUOTE_DECIMALS);
+        let mut event = build_fee_claim_event(
+            &claim.claimer,
+            &claim.quote_mint,
+            claim.amount as u128,
+            decimals,
+            BAGS_PROGRAM_NAME,
+        );
+        event.base_mint = claim.mint;
+        event.quote_mint = claim.quote_mint;
+        event
+    }
+}
+
+impl MemeEventParser for BagsMemeParser {
+    fn process_events(&mut self) -> Vec<MemeEvent> {
+        let classifier = InstructionClassifier::new(&self.adapter);
+        let instructions = classifier.get_instructions(BAGS_PROGRAM_ID);
+        match BagsEventParser::parse_instructions(&instructions) {
+            Ok(events) => events
+                .into_iter()
+                .map(|event| self.build_event(event))
+                .collect(),
+            Err(err) => {
+                tracing::error!("failed to parse bags meme events: {err}");
+                Vec::new()
+            }
+        }
+    }
+}
@@ -0,0 +1,20 @@
+pub const BAGS_PROGRAM_ID: &str = "BAGSB9TpGrZxQbEsrEznv5jXXdwyP6AXerN8aVRiAmcv";
+pub const BAGS_PROGRAM_NAME: &str = "Bags";
+
+pub mod discriminators {
+    pub mod bags_instructions {
+        pub const CREATE: [u8; 8] = [24, 30, 200, 40, 5, 28, 7, 119];
+        pub const BUY: [u8; 8] = [102, 6, 61, 18, 1, 218, 235, 234];
+        pub const SELL: [u8; 8] = [51, 230, 133, 164, 1, 127, 131, 173];
+        pub const CLAIM_FEE: [u8; 8] = [169, 32, 79, 137, 136, 232, 70, 137];
+    }
+
+    pub mod bags_events {
+        pub const TRADE: [u8; 16] = [
+            228, 69, 165, 46, 81, 203, 154, 29, 189, 219, 127, 211, 78, 230, 97, 238,
+        ];
+        pub const CLAIM_FEE: [u8; 16] = [
+            228, 69, 165, 46, 81, 203, 154, 29, 173, 80, 235, 15, 116, 19, 144, 33,
+        ];
+    }
+}
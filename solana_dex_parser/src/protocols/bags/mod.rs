@@ -0,0 +1,31 @@
+pub mod bags_event_parser;
+pub mod bags_parser;
+pub mod constants;
+pub mod error;
+
+use crate::core::transaction_adapter::TransactionAdapter;
+use crate::protocols::simple::{MemeEventParser, TradeParser};
+use crate::types::{ClassifiedInstruction, DexInfo, TransferMap};
+
+use bags_parser::{BagsMemeParser, BagsParser};
+
+pub fn build_bags_trade_parser(
+    adapter: TransactionAdapter,
+    dex_info: DexInfo,
+    transfer_actions: TransferMap,
+    classified_instructions: Vec<ClassifiedInstruction>,
+) -> Box<dyn TradeParser> {
+    Box::new(BagsParser::new(
+        adapter,
+        dex_info,
+        transfer_actions,
+        classified_instructions,
+    ))
+}
+
+pub fn build_bags_meme_parser(
+    adapter: TransactionAdapter,
+    transfer_actions: TransferMap,
+) -> Box<dyn MemeEventParser> {
+    Box::new(BagsMemeParser::new(adapter, transfer_actions))
+}
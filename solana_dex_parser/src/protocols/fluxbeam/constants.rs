@@ -0,0 +1,11 @@
+pub const FLUXBEAM_PROGRAM_ID: &str = "FLUXubRmkEi2q6K3Y9kBPg9248ggaZVsoSFhtJHSrm1X";
+pub const FLUXBEAM_PROGRAM_NAME: &str = "FluxBeam";
+
+pub mod discriminators {
+    /// FluxBeam runs the SPL token-swap layout: a single leading tag selects the instruction.
+    pub mod fluxbeam_instructions {
+        pub const SWAP: [u8; 1] = [1];
+        pub const DEPOSIT_ALL_TOKEN_TYPES: [u8; 1] = [2];
+        pub const WITHDRAW_ALL_TOKEN_TYPES: [u8; 1] = [3];
+    }
+}
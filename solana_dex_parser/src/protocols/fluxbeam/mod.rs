@@ -0,0 +1,9 @@
+//! FluxBeam runs the SPL token-swap program on token-2022 pools and is parsed by
+//! [`crate::protocols::token_swap`].
+
+pub mod constants;
+
+pub use crate::protocols::token_swap::{
+    build_token_swap_liquidity_parser as build_fluxbeam_liquidity_parser,
+    build_token_swap_trade_parser as build_fluxbeam_trade_parser,
+};
@@ -0,0 +1,9 @@
+pub const HEAVEN_PROGRAM_ID: &str = "HEAVENoP2qxoeuF8Dj2oT1GHEnu49U5mJYkdeC8BAX2o";
+pub const HEAVEN_PROGRAM_NAME: &str = "Heaven";
+
+pub mod discriminators {
+    pub mod heaven_instructions {
+        pub const BUY: [u8; 8] = [102, 6, 61, 18, 1, 218, 235, 234];
+        pub const SELL: [u8; 8] = [51, 230, 133, 164, 1, 127, 131, 173];
+    }
+}
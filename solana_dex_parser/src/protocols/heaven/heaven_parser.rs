@@ -0,0 +1,196 @@
+use crate::core::matcher::{Leg, TransferMatcher};
+use crate::core::transaction_adapter::TransactionAdapter;
+use crate::core::transaction_utils::TransactionUtils;
+use crate::protocols::pumpfun::binary_reader::BinaryReader;
+use crate::protocols::pumpfun::util::{build_fee_info, decode_instruction_data, get_trade_type};
+use crate::protocols::simple::TradeParser;
+use crate::types::{ClassifiedInstruction, DexInfo, FeeInfo, TradeInfo, TransferData, TransferMap};
+
+use super::constants::{
+    discriminators::heaven_instructions, HEAVEN_PROGRAM_ID, HEAVEN_PROGRAM_NAME,
+};
+use super::error::HeavenError;
+
+/// Decoded `buy` or `sell`. Accounts: pool at 4, user at 5, base mint at 6 and quote mint at
+/// 7; the payload is the exact input amount and the minimum output.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HeavenSwapInstruction {
+    pub is_buy: bool,
+    pub pool: String,
+    pub user: String,
+    pub base_mint: String,
+    pub quote_mint: String,
+    pub amount_in: u64,
+    pub minimum_amount_out: u64,
+}
+
+/// Swaps on Heaven's launch AMM pools.
+///
+/// The legs are the largest user payment of the input mint and the largest credit of the
+/// output mint to the user; any other quote-mint transfer of the instruction that leaves
+/// both the user and the pool is a protocol fee, listed in `fees` and summed in `fee`.
+pub struct HeavenParser {
+    adapter: TransactionAdapter,
+    dex_info: DexInfo,
+    transfer_actions: TransferMap,
+    classified_instructions: Vec<ClassifiedInstruction>,
+}
+
+impl HeavenParser {
+    pub fn new(
+        adapter: TransactionAdapter,
+        dex_info: DexInfo,
+        transfer_actions: TransferMap,
+        classified_instructions: Vec<ClassifiedInstruction>,
+    ) -> Self {
+        Self {
+            adapter,
+            dex_info,
+            transfer_actions,
+            classified_instructions,
+        }
+    }
+
+    pub fn decode_swap(
+        accounts: &[String],
+        payload: &[u8],
+        is_buy: bool,
+    ) -> Result<HeavenSwapInstruction, HeavenError> {
+        let account = |index: usize| accounts.get(index).cloned().unwrap_or_default();
+        let mut reader = BinaryReader::new(payload.to_vec());
+        Ok(HeavenSwapInstruction {
+            is_buy,
+            pool: account(4),
+            user: account(5),
+            base_mint: account(6),
+            quote_mint: account(7),
+            amount_in: reader.read_u64()?,
+            minimum_amount_out: reader.read_u64()?,
+        })
+    }
+
+    fn build_trade(
+        &self,
+        classified: &ClassifiedInstruction,
+        swap: &HeavenSwapInstruction,
+    ) -> Option<TradeInfo> {
+        let matcher = TransferMatcher::new(&self.transfer_actions);
+        let (input_mint, output_mint) = if swap.is_buy {
+            (&swap.quote_mint, &swap.base_mint)
+        } else {
+            (&swap.base_mint, &swap.quote_mint)
+        };
+        let is_user = |owner: &Option<String>| owner.as_deref() == Some(swap.user.as_str());
+        let input = matcher
+            .find_legs(
+                classified.outer_index,
+                &Leg::of(input_mint).from_owner(&swap.user),
+            )
+            .into_iter()
+            .max_by_key(|transfer| raw_amount(transfer))?;
+        let output = matcher
+            .find_legs(
+                classified.outer_index,
+                &Leg::of(output_mint).to_owner(&swap.user),
+            )
+            .into_iter()
+            .max_by_key(|transfer| raw_amount(transfer))?;
+
+        let fees: Vec<FeeInfo> = matcher
+            .find_legs(classified.outer_index, &Leg::of(&swap.quote_mint))
+            .into_iter()
+            .filter(|transfer| {
+                !std::ptr::eq(*transfer, input)
+                    && !std::ptr::eq(*transfer, output)
+                    && !is_user(&transfer.info.destination_owner)
+                    && transfer.info.destination_owner.as_deref() != Some(swap.pool.as_str())
+            })
+            .map(|transfer| FeeInfo {
+                fee_type: Some("protocol".to_string()),
+                recipient: Some(
+                    transfer
+                        .info
+                        .destination_owner
+                        .clone()
+                        .unwrap_or_else(|| transfer.info.destination.clone()),
+                ),
+                ..build_fee_info(
+                    &transfer.info.mint,
+                    raw_amount(transfer),
+                    transfer.info.token_amount.decimals,
+                    Some(HEAVEN_PROGRAM_NAME.to_string()),
+                )
+            })
+            .collect();
+        let fee = fees.first().map(|first| {
+            let total = fees
+                .iter()
+                .map(|fee| fee.amount_raw.parse::<u128>().unwrap_or(0))
+                .sum();
+            build_fee_info(&first.mint, total, first.decimals, None)
+        });
+
+        let input_token = TransactionUtils::transfer_to_token_info(input);
+        let output_token = TransactionUtils::transfer_to_token_info(output);
+        Some(TradeInfo {
+            trade_type: get_trade_type(&input_token.mint, &output_token.mint),
+            pool: vec![swap.pool.clone()],
+            input_token,
+            output_token,
+            fee,
+            fees,
+            user: Some(swap.user.clone()),
+            program_id: Some(HEAVEN_PROGRAM_ID.to_string()),
+            amm: Some(HEAVEN_PROGRAM_NAME.to_string()),
+            route: self.dex_info.route.clone(),
+            slot: self.adapter.slot(),
+            timestamp: self.adapter.block_time(),
+            signature: self.adapter.shared_signature(),
+            idx: format!(
+                "{}-{}",
+                classified.outer_index,
+                classified.inner_index.unwrap_or(0)
+            ),
+            signer: Some(self.adapter.signers().to_vec()),
+            ..TradeInfo::default()
+        })
+    }
+
+    fn parse_swaps(&self) -> Result<Vec<TradeInfo>, HeavenError> {
+        let mut trades = Vec::new();
+        for classified in &self.classified_instructions {
+            let data = decode_instruction_data(&classified.data.data)
+                .map_err(|err| HeavenError::instruction_data(err.to_string()))?;
+            if data.len() < 8 {
+                continue;
+            }
+            let (discriminator, payload) = data.split_at(8);
+            let is_buy = if discriminator == heaven_instructions::BUY {
+                true
+            } else if discriminator == heaven_instructions::SELL {
+                false
+            } else {
+                continue;
+            };
+            let swap = Self::decode_swap(&classified.data.accounts, payload, is_buy)?;
+            trades.extend(self.build_trade(classified, &swap));
+        }
+        Ok(trades)
+    }
+}
+
+impl TradeParser for HeavenParser {
+    fn process_trades(&mut self) -> Vec<TradeInfo> {
+        match self.parse_swaps() {
+            Ok(trades) => trades,
+            Err(err) => {
+                tracing::error!("failed to parse heaven instructions: {err}");
+                Vec::new()
+            }
+        }
+    }
+}
+
+fn raw_amount(transfer: &TransferData) -> u128 {
+    transfer.info.token_amount.amount.parse().unwrap_or(0)
+}
@@ -0,0 +1,23 @@
+pub mod constants;
+pub mod error;
+pub mod heaven_parser;
+
+use crate::core::transaction_adapter::TransactionAdapter;
+use crate::protocols::simple::TradeParser;
+use crate::types::{ClassifiedInstruction, DexInfo, TransferMap};
+
+use heaven_parser::HeavenParser;
+
+pub fn build_heaven_trade_parser(
+    adapter: TransactionAdapter,
+    dex_info: DexInfo,
+    transfer_actions: TransferMap,
+    classified_instructions: Vec<ClassifiedInstruction>,
+) -> Box<dyn TradeParser> {
+    Box::new(HeavenParser::new(
+        adapter,
+        dex_info,
+        transfer_actions,
+        classified_instructions,
+    ))
+}
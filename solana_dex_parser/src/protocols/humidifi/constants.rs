@@ -0,0 +1,5 @@
+pub const HUMIDIFI_PROGRAM_ID: &str = "9H6tua7jkLhdm3w8BvgpTn5LZNU7g4ZynDmCiNN3q6Rp";
+pub const HUMIDIFI_PROGRAM_NAME: &str = "HumidiFi";
+
+/// Accounts a swap needs before the optional sysvar and token program accounts.
+pub const SWAP_ACCOUNTS: usize = 6;
@@ -0,0 +1,121 @@
+use crate::core::matcher::TransferMatcher;
+use crate::core::transaction_adapter::TransactionAdapter;
+use crate::protocols::pumpfun::util::get_trade_type;
+use crate::protocols::raydium::util::vault_swap_tokens;
+use crate::protocols::simple::TradeParser;
+use crate::types::{ClassifiedInstruction, DexInfo, TradeInfo, TransferMap};
+
+use super::constants::{HUMIDIFI_PROGRAM_ID, HUMIDIFI_PROGRAM_NAME, SWAP_ACCOUNTS};
+
+/// Accounts of a swap: user, pool, base vault, quote vault, user base account and user
+/// quote account.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HumidiFiSwapAccounts {
+    pub user: String,
+    pub pool: String,
+    pub base_vault: String,
+    pub quote_vault: String,
+    pub user_base_account: String,
+    pub user_quote_account: String,
+}
+
+/// Swaps against HumidiFi's market-maker pools, usually reached as a Jupiter hop.
+///
+/// HumidiFi obfuscates its instruction data, so swaps are recognised from the accounts and
+/// the vault transfers alone; the direction follows the tokens that moved. The fee is
+/// folded into the quoted price, so trades carry no separate fee entry.
+pub struct HumidiFiParser {
+    adapter: TransactionAdapter,
+    dex_info: DexInfo,
+    transfer_actions: TransferMap,
+    classified_instructions: Vec<ClassifiedInstruction>,
+}
+
+impl HumidiFiParser {
+    pub fn new(
+        adapter: TransactionAdapter,
+        dex_info: DexInfo,
+        transfer_actions: TransferMap,
+        classified_instructions: Vec<ClassifiedInstruction>,
+    ) -> Self {
+        Self {
+            adapter,
+            dex_info,
+            transfer_actions,
+            classified_instructions,
+        }
+    }
+
+    pub fn decode_swap(accounts: &[String]) -> Option<HumidiFiSwapAccounts> {
+        if accounts.len() < SWAP_ACCOUNTS {
+            return None;
+        }
+        Some(HumidiFiSwapAccounts {
+            user: accounts[0].clone(),
+            pool: accounts[1].clone(),
+            base_vault: accounts[2].clone(),
+            quote_vault: accounts[3].clone(),
+            user_base_account: accounts[4].clone(),
+            user_quote_account: accounts[5].clone(),
+        })
+    }
+
+    fn build_trade(
+        &self,
+        classified: &ClassifiedInstruction,
+        swap: &HumidiFiSwapAccounts,
+    ) -> Option<TradeInfo> {
+        let matcher = TransferMatcher::new(&self.transfer_actions);
+        let (input_token, output_token) = vault_swap_tokens(
+            &matcher,
+            classified.outer_index,
+            &swap.user_base_account,
+            &swap.base_vault,
+            &swap.quote_vault,
+            &swap.user_quote_account,
+        )
+        .or_else(|| {
+            vault_swap_tokens(
+                &matcher,
+                classified.outer_index,
+                &swap.user_quote_account,
+                &swap.quote_vault,
+                &swap.base_vault,
+                &swap.user_base_account,
+            )
+        })?;
+
+        Some(TradeInfo {
+            trade_type: get_trade_type(&input_token.mint, &output_token.mint),
+            pool: vec![swap.pool.clone()],
+            input_token,
+            output_token,
+            user: Some(swap.user.clone()),
+            program_id: Some(HUMIDIFI_PROGRAM_ID.to_string()),
+            amm: Some(HUMIDIFI_PROGRAM_NAME.to_string()),
+            route: self.dex_info.route.clone(),
+            slot: self.adapter.slot(),
+            timestamp: self.adapter.block_time(),
+            signature: self.adapter.shared_signature(),
+            idx: format!(
+                "{}-{}",
+                classified.outer_index,
+                classified.inner_index.unwrap_or(0)
+            ),
+            signer: Some(self.adapter.signers().to_vec()),
+            ..TradeInfo::default()
+        })
+    }
+}
+
+impl TradeParser for HumidiFiParser {
+    fn process_trades(&mut self) -> Vec<TradeInfo> {
+        self.classified_instructions
+            .iter()
+            .filter_map(|classified| {
+                let swap = Self::decode_swap(&classified.data.accounts)?;
+                self.build_trade(classified, &swap)
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,45 @@
+pub const JUPITER_PROGRAM_ID: &str = "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4";
+pub const JUPITER_PROGRAM_NAME: &str = "Jupiter";
+pub const JUPITER_LIMIT_ORDER_PROGRAM_ID: &str = "jupoNjAxXgZ4rjzxzPMP4oxduvQsQtZzyknqvzYNrNu";
+pub const JUPITER_LIMIT_ORDER_PROGRAM_NAME: &str = "JupiterLimit";
+pub const JUPITER_TRIGGER_PROGRAM_ID: &str = "j1o2qRpjcyUwEvwtcfhEQefh773ZgjxcVRry7LDqg5X";
+pub const JUPITER_TRIGGER_PROGRAM_NAME: &str = "JupiterTrigger";
+pub const JUPITER_DCA_PROGRAM_ID: &str = "DCA265Vj8a9CEuX1eb1LWRnDT7uK6q1xMipnNyatn23M";
+pub const JUPITER_DCA_PROGRAM_NAME: &str = "JupiterDCA";
+
+pub mod discriminators {
+    pub mod jupiter_instructions {
+        pub const ROUTE: [u8; 8] = [229, 23, 203, 151, 122, 227, 173, 42];
+        pub const SHARED_ACCOUNTS_ROUTE: [u8; 8] = [193, 32, 155, 51, 65, 214, 156, 129];
+    }
+
+    /// Shared by the limit order (v1) and Trigger programs.
+    pub mod limit_order_instructions {
+        pub const FILL_ORDER: [u8; 8] = [232, 122, 115, 25, 199, 143, 136, 162];
+        pub const PRE_FLASH_FILL_ORDER: [u8; 8] = [240, 47, 153, 68, 13, 190, 225, 42];
+        pub const FLASH_FILL_ORDER: [u8; 8] = [252, 104, 18, 134, 164, 78, 18, 140];
+    }
+
+    pub mod limit_order_events {
+        pub const TRADE: [u8; 16] = [
+            228, 69, 165, 46, 81, 203, 154, 29, 189, 219, 127, 211, 78, 230, 97, 238,
+        ];
+    }
+
+    pub mod dca_instructions {
+        pub const FULFILL_FLASH_FILL: [u8; 8] = [115, 64, 226, 78, 33, 211, 105, 162];
+        pub const FULFILL_DLMM_FILL: [u8; 8] = [1, 230, 118, 251, 45, 177, 101, 187];
+    }
+
+    pub mod dca_events {
+        pub const FILLED: [u8; 16] = [
+            228, 69, 165, 46, 81, 203, 154, 29, 28, 65, 191, 210, 123, 85, 50, 188,
+        ];
+    }
+
+    pub mod jupiter_events {
+        pub const SWAP: [u8; 16] = [
+            228, 69, 165, 46, 81, 203, 154, 29, 64, 198, 205, 232, 38, 8, 113, 226,
+        ];
+    }
+}
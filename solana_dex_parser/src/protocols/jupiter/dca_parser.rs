@@ -0,0 +1,178 @@
+use crate::core::transaction_adapter::TransactionAdapter;
+use crate::protocols::pumpfun::binary_reader::BinaryReader;
+use crate::protocols::pumpfun::util::{build_fee_info, build_token_info, decode_instruction_data};
+use crate::protocols::simple::TradeParser;
+use crate::types::{
+    ClassifiedInstruction, DcaFillInfo, DexInfo, FeeInfo, TradeInfo, TradeType, TransferMap,
+};
+
+use super::constants::{
+    discriminators::dca_events, JUPITER_DCA_PROGRAM_ID, JUPITER_DCA_PROGRAM_NAME,
+};
+use super::error::JupiterError;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DcaFilledEvent {
+    pub user: String,
+    pub dca: String,
+    pub input_mint: String,
+    pub output_mint: String,
+    pub in_amount: u64,
+    pub out_amount: u64,
+    pub fee_mint: String,
+    pub fee: u64,
+}
+
+/// Keeper fills of Jupiter DCA orders, one `DCA_FILL` trade per `FilledEvent`.
+pub struct JupiterDcaParser {
+    adapter: TransactionAdapter,
+    dex_info: DexInfo,
+    _transfer_actions: TransferMap,
+    classified_instructions: Vec<ClassifiedInstruction>,
+}
+
+impl JupiterDcaParser {
+    pub fn new(
+        adapter: TransactionAdapter,
+        dex_info: DexInfo,
+        transfer_actions: TransferMap,
+        classified_instructions: Vec<ClassifiedInstruction>,
+    ) -> Self {
+        Self {
+            adapter,
+            dex_info,
+            _transfer_actions: transfer_actions,
+            classified_instructions,
+        }
+    }
+
+    fn parse_events(
+        &self,
+        ordered: &[ClassifiedInstruction],
+    ) -> Result<Vec<(String, DcaFilledEvent)>, JupiterError> {
+        let mut events = Vec::new();
+        for classified in ordered {
+            let data = decode_instruction_data(&classified.data.data)
+                .map_err(|err| JupiterError::instruction_data(err.to_string()))?;
+            if data.len() < 16 || data[..16] != dca_events::FILLED {
+                continue;
+            }
+            let idx = format!(
+                "{}-{}",
+                classified.outer_index,
+                classified.inner_index.unwrap_or(0)
+            );
+            events.push((idx, Self::decode_filled_event(&data[16..])?));
+        }
+        Ok(events)
+    }
+
+    pub(crate) fn decode_filled_event(payload: &[u8]) -> Result<DcaFilledEvent, JupiterError> {
+        let mut reader = BinaryReader::new(payload.to_vec());
+        Ok(DcaFilledEvent {
+            user: reader.read_pubkey()?,
+            dca: reader.read_pubkey()?,
+            input_mint: reader.read_pubkey()?,
+            output_mint: reader.read_pubkey()?,
+            in_amount: reader.read_u64()?,
+            out_amount: reader.read_u64()?,
+            fee_mint: reader.read_pubkey()?,
+            fee: reader.read_u64()?,
+        })
+    }
+
+    fn decimals(&self, mint: &str) -> u8 {
+        self.adapter
+            .spl_decimals_map()
+            .get(mint)
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Input left in the order's token account once the fill settled.
+    fn remaining_in_amount(&self, event: &DcaFilledEvent) -> Option<u64> {
+        self.adapter
+            .post_token_balances()
+            .iter()
+            .find(|balance| {
+                balance.owner.as_deref() == Some(event.dca.as_str())
+                    && balance.mint == event.input_mint
+            })
+            .and_then(|balance| balance.ui_token_amount.amount.parse().ok())
+    }
+
+    fn build_trade(&self, idx: &str, event: &DcaFilledEvent) -> TradeInfo {
+        let remaining = self.remaining_in_amount(event);
+
+        TradeInfo {
+            trade_type: TradeType::DcaFill,
+            input_token: build_token_info(
+                &event.input_mint,
+                event.in_amount as u128,
+                self.decimals(&event.input_mint),
+                None,
+            ),
+            output_token: build_token_info(
+                &event.output_mint,
+                event.out_amount as u128,
+                self.decimals(&event.output_mint),
+                None,
+            ),
+            fee: (event.fee > 0).then(|| FeeInfo {
+                fee_type: Some("protocol".to_string()),
+                ..build_fee_info(
+                    &event.fee_mint,
+                    event.fee as u128,
+                    self.decimals(&event.fee_mint),
+                    Some(JUPITER_DCA_PROGRAM_NAME.to_string()),
+                )
+            }),
+            user: Some(event.user.clone()),
+            dca: Some(DcaFillInfo {
+                dca: event.dca.clone(),
+                cycle_in_amount: event.in_amount.to_string(),
+                remaining_in_amount: remaining.map(|amount| amount.to_string()),
+                remaining_cycles: remaining
+                    .filter(|_| event.in_amount > 0)
+                    .map(|amount| amount.div_ceil(event.in_amount)),
+            }),
+            program_id: Some(
+                self.dex_info
+                    .program_id
+                    .clone()
+                    .unwrap_or_else(|| JUPITER_DCA_PROGRAM_ID.to_string()),
+            ),
+            amm: Some(JUPITER_DCA_PROGRAM_NAME.to_string()),
+            route: self.dex_info.route.clone(),
+            slot: self.adapter.slot(),
+            timestamp: self.adapter.block_time(),
+            signature: self.adapter.shared_signature(),
+            idx: idx.to_string(),
+            signer: Some(self.adapter.signers().to_vec()),
+            ..TradeInfo::default()
+        }
+    }
+}
+
+impl TradeParser for JupiterDcaParser {
+    fn process_trades(&mut self) -> Vec<TradeInfo> {
+        let mut ordered = self.classified_instructions.clone();
+        ordered.sort_by_key(|classified| {
+            (
+                classified.outer_index,
+                classified.inner_index.map_or(0, |index| index + 1),
+            )
+        });
+
+        match self.parse_events(&ordered) {
+            Ok(events) => events
+                .iter()
+                .map(|(idx, event)| self.build_trade(idx, event))
+                .collect(),
+            Err(err) => {
+                tracing::error!("failed to parse jupiter dca instructions: {err}");
+                Vec::new()
+            }
+        }
+    }
+}
@@ -0,0 +1,17 @@
+use thiserror::Error;
+
+use crate::protocols::pumpfun::binary_reader::BinaryReaderError;
+
+#[derive(Debug, Error)]
+pub enum JupiterError {
+    #[error("failed to decode instruction data: {0}")]
+    InstructionData(String),
+    #[error("binary reader error: {0}")]
+    BinaryReader(#[from] BinaryReaderError),
+}
+
+impl JupiterError {
+    pub fn instruction_data(message: impl Into<String>) -> Self {
+        Self::InstructionData(message.into())
+    }
+}
@@ -0,0 +1,282 @@
+use std::collections::{BTreeMap, HashSet};
+
+use crate::core::instruction_classifier::{InstructionClassifier, InvocationNode};
+use crate::core::transaction_adapter::TransactionAdapter;
+use crate::protocols::pumpfun::binary_reader::BinaryReader;
+use crate::protocols::pumpfun::util::{
+    build_token_info, decode_instruction_data, get_trade_type, slippage_bps,
+};
+use crate::protocols::simple::{SimpleTradeParser, TradeParser};
+use crate::types::{ClassifiedInstruction, DexInfo, TradeInfo, TransferMap};
+
+use super::constants::{
+    discriminators::{jupiter_events, jupiter_instructions},
+    JUPITER_PROGRAM_ID, JUPITER_PROGRAM_NAME,
+};
+use super::error::JupiterError;
+
+/// Position of `user_transfer_authority` in `route` and `sharedAccountsRoute`.
+pub(crate) const ROUTE_USER_ACCOUNT: usize = 1;
+pub(crate) const SHARED_ACCOUNTS_ROUTE_USER_ACCOUNT: usize = 2;
+
+/// `in_amount`, `quoted_out_amount`, `slippage_bps` and `platform_fee_bps` trail the
+/// variable-length route plan.
+const ROUTE_TAIL_LEN: usize = 8 + 8 + 2 + 1;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct JupiterRouteInstruction {
+    pub user: String,
+    pub in_amount: u64,
+    pub quoted_out_amount: u64,
+    pub slippage_bps: u16,
+    pub platform_fee_bps: u8,
+}
+
+impl JupiterRouteInstruction {
+    /// Least output the route accepts: the quote less the slippage tolerance.
+    pub fn minimum_out_amount(&self) -> u64 {
+        let tolerance = 10_000u128.saturating_sub(self.slippage_bps as u128);
+        (self.quoted_out_amount as u128 * tolerance / 10_000) as u64
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct JupiterSwapEvent {
+    pub amm: String,
+    pub input_mint: String,
+    pub input_amount: u64,
+    pub output_mint: String,
+    pub output_amount: u64,
+}
+
+/// Route instruction and the swap events of its hops.
+#[derive(Default)]
+struct JupiterRoute {
+    instruction: Option<JupiterRouteInstruction>,
+    hops: Vec<(String, JupiterSwapEvent)>,
+}
+
+pub struct JupiterParser {
+    adapter: TransactionAdapter,
+    dex_info: DexInfo,
+    transfer_actions: TransferMap,
+    classified_instructions: Vec<ClassifiedInstruction>,
+}
+
+impl JupiterParser {
+    pub fn new(
+        adapter: TransactionAdapter,
+        dex_info: DexInfo,
+        transfer_actions: TransferMap,
+        classified_instructions: Vec<ClassifiedInstruction>,
+    ) -> Self {
+        Self {
+            adapter,
+            dex_info,
+            transfer_actions,
+            classified_instructions,
+        }
+    }
+
+    /// Routes keyed by the execution position of their route instruction. A swap event
+    /// belongs to the route whose invocation emitted it; without stack heights, to the latest
+    /// route of its outer instruction.
+    fn parse_routes(&self) -> Result<BTreeMap<(usize, usize), JupiterRoute>, JupiterError> {
+        let programs: HashSet<&str> = self
+            .classified_instructions
+            .iter()
+            .map(|classified| classified.program_id.as_str())
+            .collect();
+        let mut routes = BTreeMap::new();
+        for root in InstructionClassifier::new(&self.adapter).invocation_tree() {
+            let mut latest = None;
+            self.collect_routes(&root, &programs, None, &mut latest, &mut routes)?;
+        }
+        Ok(routes)
+    }
+
+    fn collect_routes(
+        &self,
+        node: &InvocationNode,
+        programs: &HashSet<&str>,
+        parent: Option<(usize, usize)>,
+        latest: &mut Option<(usize, usize)>,
+        routes: &mut BTreeMap<(usize, usize), JupiterRoute>,
+    ) -> Result<(), JupiterError> {
+        let classified = &node.instruction;
+        let mut route_key = parent;
+        if programs.contains(classified.program_id.as_str()) {
+            let data = decode_instruction_data(&classified.data.data)
+                .map_err(|err| JupiterError::instruction_data(err.to_string()))?;
+            let user_account = if data.len() < 8 {
+                None
+            } else if data[..8] == jupiter_instructions::ROUTE {
+                Some(ROUTE_USER_ACCOUNT)
+            } else if data[..8] == jupiter_instructions::SHARED_ACCOUNTS_ROUTE {
+                Some(SHARED_ACCOUNTS_ROUTE_USER_ACCOUNT)
+            } else {
+                None
+            };
+            if let Some(user_account) = user_account {
+                let user = classified
+                    .data
+                    .accounts
+                    .get(user_account)
+                    .cloned()
+                    .unwrap_or_default();
+                let key = (
+                    classified.outer_index,
+                    classified.inner_index.map_or(0, |index| index + 1),
+                );
+                routes.entry(key).or_default().instruction =
+                    Some(Self::decode_route(user, &data[8..])?);
+                route_key = Some(key);
+                *latest = Some(key);
+            } else if data.len() >= 16 && data[..16] == jupiter_events::SWAP {
+                let idx = format!(
+                    "{}-{}",
+                    classified.outer_index,
+                    classified.inner_index.unwrap_or(0)
+                );
+                let key = parent.or(*latest).unwrap_or((classified.outer_index, 0));
+                routes
+                    .entry(key)
+                    .or_default()
+                    .hops
+                    .push((idx, Self::decode_swap_event(&data[16..])?));
+            }
+        }
+        for child in &node.children {
+            self.collect_routes(child, programs, route_key, latest, routes)?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn decode_route(
+        user: String,
+        payload: &[u8],
+    ) -> Result<JupiterRouteInstruction, JupiterError> {
+        let tail = payload
+            .len()
+            .checked_sub(ROUTE_TAIL_LEN)
+            .ok_or_else(|| JupiterError::instruction_data("route arguments are truncated"))?;
+        let mut reader = BinaryReader::new(payload[tail..].to_vec());
+        Ok(JupiterRouteInstruction {
+            user,
+            in_amount: reader.read_u64()?,
+            quoted_out_amount: reader.read_u64()?,
+            slippage_bps: reader.read_u16()?,
+            platform_fee_bps: reader.read_u8()?,
+        })
+    }
+
+    pub(crate) fn decode_swap_event(payload: &[u8]) -> Result<JupiterSwapEvent, JupiterError> {
+        let mut reader = BinaryReader::new(payload.to_vec());
+        Ok(JupiterSwapEvent {
+            amm: reader.read_pubkey()?,
+            input_mint: reader.read_pubkey()?,
+            input_amount: reader.read_u64()?,
+            output_mint: reader.read_pubkey()?,
+            output_amount: reader.read_u64()?,
+        })
+    }
+
+    fn decimals(&self, mint: &str) -> u8 {
+        self.adapter
+            .spl_decimals_map()
+            .get(mint)
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// One trade per route, from the first hop's input mint to the last hop's output mint.
+    /// Split legs are summed; intermediate mints drop out.
+    fn build_trade(&self, route: &JupiterRoute) -> Option<TradeInfo> {
+        let (_, first) = route.hops.first()?;
+        let (idx, last) = route.hops.last()?;
+        let amount_in: u128 = route
+            .hops
+            .iter()
+            .filter(|(_, hop)| hop.input_mint == first.input_mint)
+            .map(|(_, hop)| hop.input_amount as u128)
+            .sum();
+        let amount_out: u128 = route
+            .hops
+            .iter()
+            .filter(|(_, hop)| hop.output_mint == last.output_mint)
+            .map(|(_, hop)| hop.output_amount as u128)
+            .sum();
+        let amms: Vec<String> = route
+            .hops
+            .iter()
+            .map(|(_, hop)| self.adapter.config().program_name(&hop.amm))
+            .collect();
+
+        Some(TradeInfo {
+            trade_type: get_trade_type(&first.input_mint, &last.output_mint),
+            input_token: build_token_info(
+                &first.input_mint,
+                amount_in,
+                self.decimals(&first.input_mint),
+                None,
+            ),
+            output_token: build_token_info(
+                &last.output_mint,
+                amount_out,
+                self.decimals(&last.output_mint),
+                None,
+            ),
+            slippage_bps: route
+                .instruction
+                .as_ref()
+                .and_then(|instruction| slippage_bps(amount_out, instruction.minimum_out_amount())),
+            user: Some(route.instruction.as_ref().map_or_else(
+                || self.adapter.signer(),
+                |instruction| instruction.user.clone(),
+            )),
+            program_id: Some(
+                self.dex_info
+                    .program_id
+                    .clone()
+                    .unwrap_or_else(|| JUPITER_PROGRAM_ID.to_string()),
+            ),
+            amm: amms.first().cloned(),
+            amms: Some(amms),
+            route: Some(JUPITER_PROGRAM_NAME.to_string()),
+            slot: self.adapter.slot(),
+            timestamp: self.adapter.block_time(),
+            signature: self.adapter.shared_signature(),
+            idx: idx.clone(),
+            signer: Some(self.adapter.signers().to_vec()),
+            ..TradeInfo::default()
+        })
+    }
+}
+
+impl TradeParser for JupiterParser {
+    fn process_trades(&mut self) -> Vec<TradeInfo> {
+        // Swap events are emitted by self-CPI after the hop they describe.
+        let trades: Vec<TradeInfo> = match self.parse_routes() {
+            Ok(routes) => routes
+                .values()
+                .filter_map(|route| self.build_trade(route))
+                .collect(),
+            Err(err) => {
+                tracing::error!("failed to parse jupiter instructions: {err}");
+                Vec::new()
+            }
+        };
+        if !trades.is_empty() {
+            return trades;
+        }
+
+        // Routes without swap events fall back to the program's transfers.
+        SimpleTradeParser::new(
+            self.adapter.clone(),
+            self.dex_info.clone(),
+            self.transfer_actions.clone(),
+            self.classified_instructions.clone(),
+        )
+        .process_trades()
+    }
+}
@@ -0,0 +1,249 @@
+use crate::core::transaction_adapter::TransactionAdapter;
+use crate::protocols::pumpfun::binary_reader::BinaryReader;
+use crate::protocols::pumpfun::util::{build_token_info, decode_instruction_data};
+use crate::protocols::simple::TradeParser;
+use crate::types::{ClassifiedInstruction, DexInfo, TradeInfo, TradeType, TransferMap};
+
+use super::constants::{
+    discriminators::{limit_order_events, limit_order_instructions},
+    JUPITER_TRIGGER_PROGRAM_ID,
+};
+use super::error::JupiterError;
+
+/// Account positions of a fill instruction; `output_mint` is only named by flash fills.
+struct FillAccounts {
+    taker: usize,
+    maker: usize,
+    order: usize,
+    input_reserve: usize,
+    maker_output: usize,
+    output_mint: Option<usize>,
+}
+
+const LIMIT_ORDER_FILL_ACCOUNTS: FillAccounts = FillAccounts {
+    order: 0,
+    input_reserve: 1,
+    maker: 2,
+    taker: 3,
+    maker_output: 4,
+    output_mint: None,
+};
+
+const TRIGGER_FILL_ACCOUNTS: FillAccounts = FillAccounts {
+    taker: 0,
+    maker: 1,
+    order: 2,
+    input_reserve: 3,
+    maker_output: 4,
+    output_mint: None,
+};
+
+const TRIGGER_FLASH_FILL_ACCOUNTS: FillAccounts = FillAccounts {
+    output_mint: Some(8),
+    ..TRIGGER_FILL_ACCOUNTS
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LimitFillInstruction {
+    pub taker: String,
+    pub maker: String,
+    pub order: String,
+    pub input_reserve: String,
+    pub maker_output: String,
+    pub output_mint: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LimitOrderTradeEvent {
+    pub order_key: String,
+    pub taker: String,
+    pub remaining_making_amount: u64,
+    pub remaining_taking_amount: u64,
+    pub making_amount: u64,
+    pub taking_amount: u64,
+}
+
+/// Fill instruction together with the `TradeEvent` it emitted, if any.
+struct LimitFill<'a> {
+    classified: &'a ClassifiedInstruction,
+    fill: LimitFillInstruction,
+    traded: Option<(String, LimitOrderTradeEvent)>,
+}
+
+/// Fills of the Jupiter limit order (v1) and Trigger programs.
+pub struct JupiterLimitOrderParser {
+    adapter: TransactionAdapter,
+    dex_info: DexInfo,
+    _transfer_actions: TransferMap,
+    classified_instructions: Vec<ClassifiedInstruction>,
+}
+
+impl JupiterLimitOrderParser {
+    pub fn new(
+        adapter: TransactionAdapter,
+        dex_info: DexInfo,
+        transfer_actions: TransferMap,
+        classified_instructions: Vec<ClassifiedInstruction>,
+    ) -> Self {
+        Self {
+            adapter,
+            dex_info,
+            _transfer_actions: transfer_actions,
+            classified_instructions,
+        }
+    }
+
+    /// Decode fill instructions, pairing each `TradeEvent` with the latest unmatched fill of
+    /// the same order. A flash fill is bracketed by `preFlashFillOrder`, and the event
+    /// belongs to the closing `flashFillOrder`.
+    fn parse_fills<'a>(
+        &self,
+        ordered: &'a [ClassifiedInstruction],
+    ) -> Result<Vec<LimitFill<'a>>, JupiterError> {
+        let mut fills: Vec<LimitFill<'a>> = Vec::new();
+        for classified in ordered {
+            let data = decode_instruction_data(&classified.data.data)
+                .map_err(|err| JupiterError::instruction_data(err.to_string()))?;
+            if data.len() < 8 {
+                continue;
+            }
+
+            let trigger = classified.program_id == JUPITER_TRIGGER_PROGRAM_ID;
+            let positions = if data[..8] == limit_order_instructions::FILL_ORDER {
+                Some(if trigger {
+                    &TRIGGER_FILL_ACCOUNTS
+                } else {
+                    &LIMIT_ORDER_FILL_ACCOUNTS
+                })
+            } else if data[..8] == limit_order_instructions::PRE_FLASH_FILL_ORDER
+                || data[..8] == limit_order_instructions::FLASH_FILL_ORDER
+            {
+                Some(if trigger {
+                    &TRIGGER_FLASH_FILL_ACCOUNTS
+                } else {
+                    &LIMIT_ORDER_FILL_ACCOUNTS
+                })
+            } else {
+                None
+            };
+            if let Some(positions) = positions {
+                fills.push(LimitFill {
+                    classified,
+                    fill: Self::decode_fill(&classified.data.accounts, positions),
+                    traded: None,
+                });
+                continue;
+            }
+
+            if data.len() >= 16 && data[..16] == limit_order_events::TRADE {
+                let traded = Self::decode_trade_event(&data[16..])?;
+                let idx = format!(
+                    "{}-{}",
+                    classified.outer_index,
+                    classified.inner_index.unwrap_or(0)
+                );
+                if let Some(pending) = fills.iter_mut().rev().find(|pending| {
+                    pending.traded.is_none() && pending.fill.order == traded.order_key
+                }) {
+                    pending.traded = Some((idx, traded));
+                }
+            }
+        }
+        Ok(fills)
+    }
+
+    fn decode_fill(accounts: &[String], positions: &FillAccounts) -> LimitFillInstruction {
+        let account = |index: usize| accounts.get(index).cloned().unwrap_or_default();
+        LimitFillInstruction {
+            taker: account(positions.taker),
+            maker: account(positions.maker),
+            order: account(positions.order),
+            input_reserve: account(positions.input_reserve),
+            maker_output: account(positions.maker_output),
+            output_mint: positions
+                .output_mint
+                .and_then(|index| accounts.get(index).cloned()),
+        }
+    }
+
+    pub(crate) fn decode_trade_event(payload: &[u8]) -> Result<LimitOrderTradeEvent, JupiterError> {
+        let mut reader = BinaryReader::new(payload.to_vec());
+        Ok(LimitOrderTradeEvent {
+            order_key: reader.read_pubkey()?,
+            taker: reader.read_pubkey()?,
+            remaining_making_amount: reader.read_u64()?,
+            remaining_taking_amount: reader.read_u64()?,
+            making_amount: reader.read_u64()?,
+            taking_amount: reader.read_u64()?,
+        })
+    }
+
+    /// Output mint and decimals, from the flash fill accounts or the maker's output account.
+    fn output_mint(&self, fill: &LimitFillInstruction) -> Option<(String, u8)> {
+        if let Some(mint) = fill.output_mint.as_ref() {
+            let decimals = self.adapter.spl_decimals_map().get(mint).copied()?;
+            return Some((mint.clone(), decimals));
+        }
+        let info = self.adapter.spl_token_map().get(&fill.maker_output)?;
+        Some((info.mint.clone(), info.decimals))
+    }
+
+    fn build_trade(&self, fill: &LimitFill) -> Option<TradeInfo> {
+        let (idx, traded) = fill.traded.as_ref()?;
+        let input = self.adapter.spl_token_map().get(&fill.fill.input_reserve)?;
+        let (output_mint, output_decimals) = self.output_mint(&fill.fill)?;
+        let program_id = fill.classified.program_id.clone();
+
+        Some(TradeInfo {
+            trade_type: TradeType::LimitFill,
+            input_token: build_token_info(
+                &input.mint,
+                traded.making_amount as u128,
+                input.decimals,
+                None,
+            ),
+            output_token: build_token_info(
+                &output_mint,
+                traded.taking_amount as u128,
+                output_decimals,
+                None,
+            ),
+            user: Some(traded.taker.clone()),
+            maker: Some(fill.fill.maker.clone()),
+            order: Some(traded.order_key.clone()),
+            amm: Some(self.adapter.config().program_name(&program_id)),
+            program_id: Some(program_id),
+            route: self.dex_info.route.clone(),
+            slot: self.adapter.slot(),
+            timestamp: self.adapter.block_time(),
+            signature: self.adapter.shared_signature(),
+            idx: idx.clone(),
+            signer: Some(self.adapter.signers().to_vec()),
+            ..TradeInfo::default()
+        })
+    }
+}
+
+impl TradeParser for JupiterLimitOrderParser {
+    fn process_trades(&mut self) -> Vec<TradeInfo> {
+        // `TradeEvent` is emitted by self-CPI after the fill it describes.
+        let mut ordered = self.classified_instructions.clone();
+        ordered.sort_by_key(|classified| {
+            (
+                classified.outer_index,
+                classified.inner_index.map_or(0, |index| index + 1),
+            )
+        });
+
+        match self.parse_fills(&ordered) {
+            Ok(fills) => fills
+                .iter()
+                .filter_map(|fill| self.build_trade(fill))
+                .collect(),
+            Err(err) => {
+                tracing::error!("failed to parse jupiter limit order instructions: {err}");
+                Vec::new()
+            }
+        }
+    }
+}
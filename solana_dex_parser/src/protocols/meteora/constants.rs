@@ -0,0 +1,26 @@
+pub const METEORA_DBC_PROGRAM_ID: &str = "dbcij3LWUppWqq96dh6gJWwBifmcGfLSB5D4DuSMaqN";
+pub const METEORA_DBC_PROGRAM_NAME: &str = "MeteoraDBC";
+
+pub mod discriminators {
+    pub mod dbc_instructions {
+        pub const SWAP: [u8; 8] = [248, 198, 158, 145, 225, 117, 135, 200];
+        pub const SWAP2: [u8; 8] = [65, 75, 63, 76, 235, 91, 91, 136];
+        pub const INITIALIZE_VIRTUAL_POOL_WITH_SPL_TOKEN: [u8; 8] =
+            [140, 85, 215, 176, 102, 54, 104, 79];
+        pub const INITIALIZE_VIRTUAL_POOL_WITH_TOKEN2022: [u8; 8] =
+            [169, 118, 51, 78, 145, 110, 220, 155];
+        pub const CLAIM_TRADING_FEE: [u8; 8] = [8, 236, 89, 49, 152, 125, 177, 81];
+        pub const CLAIM_CREATOR_TRADING_FEE: [u8; 8] = [82, 220, 250, 189, 3, 85, 107, 45];
+        pub const MIGRATE_METEORA_DAMM: [u8; 8] = [27, 1, 48, 22, 180, 63, 118, 217];
+        pub const MIGRATION_DAMM_V2: [u8; 8] = [156, 169, 230, 103, 53, 228, 80, 64];
+    }
+
+    pub mod dbc_events {
+        pub const CLAIM_TRADING_FEE: [u8; 16] = [
+            228, 69, 165, 46, 81, 203, 154, 29, 26, 83, 117, 240, 92, 202, 112, 254,
+        ];
+        pub const CLAIM_CREATOR_TRADING_FEE: [u8; 16] = [
+            228, 69, 165, 46, 81, 203, 154, 29, 154, 228, 215, 202, 133, 155, 214, 138,
+        ];
+    }
+}
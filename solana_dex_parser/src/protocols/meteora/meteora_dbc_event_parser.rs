@@ -0,0 +1,340 @@
+use crate::core::constants::{dex_program_names, dex_programs};
+use crate::core::instruction_classifier::InstructionClassifier;
+use crate::core::matcher::TransferMatcher;
+use crate::core::transaction_adapter::TransactionAdapter;
+use crate::protocols::pumpfun::binary_reader::BinaryReader;
+use crate::protocols::pumpfun::util::{
+    build_fee_claim_event, decode_instruction_data, sort_by_idx,
+};
+use crate::protocols::raydium::util::vault_swap_tokens;
+use crate::protocols::simple::MemeEventParser;
+use crate::types::{ClassifiedInstruction, MemeEvent, TradeType, TransferMap};
+
+use super::constants::{
+    discriminators::{dbc_events, dbc_instructions},
+    METEORA_DBC_PROGRAM_ID, METEORA_DBC_PROGRAM_NAME,
+};
+use super::error::MeteoraError;
+
+/// Account positions of `(base_mint, quote_mint, claimer)` in a claim instruction.
+const PARTNER_CLAIM_ACCOUNTS: (usize, usize, usize) = (7, 8, 9);
+const CREATOR_CLAIM_ACCOUNTS: (usize, usize, usize) = (6, 7, 8);
+/// Position of the pool config in a partner claim; creator claims do not pass it.
+const PARTNER_CLAIM_CONFIG: usize = 1;
+
+/// Account positions of `swap`/`swap2`.
+struct SwapAccounts {
+    config: usize,
+    pool: usize,
+    input_token_account: usize,
+    output_token_account: usize,
+    base_vault: usize,
+    quote_vault: usize,
+    base_mint: usize,
+    quote_mint: usize,
+    payer: usize,
+}
+
+const SWAP_ACCOUNTS: SwapAccounts = SwapAccounts {
+    config: 1,
+    pool: 2,
+    input_token_account: 3,
+    output_token_account: 4,
+    base_vault: 5,
+    quote_vault: 6,
+    base_mint: 7,
+    quote_mint: 8,
+    payer: 9,
+};
+
+/// Account positions of `(config, creator, base_mint, quote_mint, pool)` in pool initialisation.
+const INITIALIZE_ACCOUNTS: (usize, usize, usize, usize, usize) = (0, 2, 3, 4, 5);
+
+/// Account positions of a migration instruction and the DAMM program receiving the liquidity.
+struct MigrateAccounts {
+    virtual_pool: usize,
+    config: usize,
+    pool: usize,
+    base_mint: usize,
+    quote_mint: usize,
+    destination: &'static str,
+}
+
+const MIGRATE_DAMM_ACCOUNTS: MigrateAccounts = MigrateAccounts {
+    virtual_pool: 0,
+    config: 2,
+    pool: 4,
+    base_mint: 7,
+    quote_mint: 8,
+    destination: dex_programs::METEORA_DAMM,
+};
+
+const MIGRATE_DAMM_V2_ACCOUNTS: MigrateAccounts = MigrateAccounts {
+    virtual_pool: 0,
+    config: 2,
+    pool: 4,
+    base_mint: 13,
+    quote_mint: 14,
+    destination: dex_programs::METEORA_DAMM_V2,
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MeteoraDbcClaimFeeEvent {
+    pub pool: String,
+    pub token_base_amount: u64,
+    pub token_quote_amount: u64,
+}
+
+pub struct MeteoraDbcEventParser {
+    adapter: TransactionAdapter,
+    transfer_actions: TransferMap,
+}
+
+impl MeteoraDbcEventParser {
+    pub fn new(adapter: TransactionAdapter, transfer_actions: TransferMap) -> Self {
+        Self {
+            adapter,
+            transfer_actions,
+        }
+    }
+
+    /// Decode curve creation, swaps and migrations from their instructions, and fee claims by
+    /// pairing each claim event with the claim instruction that emitted it.
+    pub fn parse_instructions(
+        &self,
+        instructions: &[ClassifiedInstruction],
+    ) -> Result<Vec<MemeEvent>, MeteoraError> {
+        let mut events = Vec::new();
+        let mut claim_accounts: Option<(String, String, String)> = None;
+        let mut claim_config: Option<String> = None;
+        for classified in instructions {
+            let data = decode_instruction_data(&classified.data.data)
+                .map_err(|err| MeteoraError::instruction_data(err.to_string()))?;
+            if data.len() < 8 {
+                continue;
+            }
+            let idx = format!(
+                "{}-{}",
+                classified.outer_index,
+                classified.inner_index.unwrap_or(0)
+            );
+
+            let accounts = &classified.data.accounts;
+            let event =
+                if data[..8] == dbc_instructions::SWAP || data[..8] == dbc_instructions::SWAP2 {
+                    self.build_swap_event(classified)
+                } else if data[..8] == dbc_instructions::INITIALIZE_VIRTUAL_POOL_WITH_SPL_TOKEN
+                    || data[..8] == dbc_instructions::INITIALIZE_VIRTUAL_POOL_WITH_TOKEN2022
+                {
+                    Some(Self::build_create_event(accounts, data[8..].to_vec())?)
+                } else if data[..8] == dbc_instructions::MIGRATE_METEORA_DAMM {
+                    Some(self.build_migrate_event(accounts, &MIGRATE_DAMM_ACCOUNTS))
+                } else if data[..8] == dbc_instructions::MIGRATION_DAMM_V2 {
+                    Some(self.build_migrate_event(accounts, &MIGRATE_DAMM_V2_ACCOUNTS))
+                } else {
+                    None
+                };
+            if let Some(event) = event {
+                events.push(self.stamp(event, &idx));
+                continue;
+            }
+
+            let positions = if data[..8] == dbc_instructions::CLAIM_TRADING_FEE {
+                Some(PARTNER_CLAIM_ACCOUNTS)
+            } else if data[..8] == dbc_instructions::CLAIM_CREATOR_TRADING_FEE {
+                Some(CREATOR_CLAIM_ACCOUNTS)
+            } else {
+                None
+            };
+            if let Some((base, quote, claimer)) = positions {
+                claim_config = (data[..8] == dbc_instructions::CLAIM_TRADING_FEE)
+                    .then(|| accounts.get(PARTNER_CLAIM_CONFIG).cloned())
+                    .flatten();
+                claim_accounts = match (
+                    accounts.get(base),
+                    accounts.get(quote),
+                    accounts.get(claimer),
+                ) {
+                    (Some(base), Some(quote), Some(claimer)) => {
+                        Some((base.clone(), quote.clone(), claimer.clone()))
+                    }
+                    _ => None,
+                };
+                continue;
+            }
+
+            if data.len() < 16
+                || (data[..16] != dbc_events::CLAIM_TRADING_FEE
+                    && data[..16] != dbc_events::CLAIM_CREATOR_TRADING_FEE)
+            {
+                continue;
+            }
+            let Some((base_mint, quote_mint, claimer)) = claim_accounts.take() else {
+                continue;
+            };
+            let claim = self.decode_claim_event(data[16..].to_vec())?;
+
+            for (mint, amount) in [
+                (&base_mint, claim.token_base_amount),
+                (&quote_mint, claim.token_quote_amount),
+            ] {
+                if amount == 0 {
+                    continue;
+                }
+                let decimals = self
+                    .adapter
+                    .spl_decimals_map()
+                    .get(mint)
+                    .copied()
+                    .unwrap_or(9);
+                let mut event = build_fee_claim_event(
+                    &claimer,
+                    mint,
+                    amount as u128,
+                    decimals,
+                    METEORA_DBC_PROGRAM_NAME,
+                );
+                event.pool = Some(claim.pool.clone());
+                event.platform_config = claim_config.clone();
+                events.push(self.stamp(event, &idx));
+            }
+        }
+
+        Ok(sort_by_idx(events))
+    }
+
+    fn stamp(&self, mut event: MemeEvent, idx: &str) -> MemeEvent {
+        event.signature = self.adapter.shared_signature();
+        event.slot = self.adapter.slot();
+        event.timestamp = self.adapter.block_time();
+        event.idx = idx.to_string();
+        event
+    }
+
+    /// Swap legs from the vault transfers; paying into the base vault is a sell.
+    fn build_swap_event(&self, classified: &ClassifiedInstruction) -> Option<MemeEvent> {
+        let accounts = &classified.data.accounts;
+        let account = |index: usize| accounts.get(index).cloned().unwrap_or_default();
+        let positions = &SWAP_ACCOUNTS;
+        let input_account = account(positions.input_token_account);
+        let output_account = account(positions.output_token_account);
+        let base_vault = account(positions.base_vault);
+        let quote_vault = account(positions.quote_vault);
+
+        let matcher = TransferMatcher::new(&self.transfer_actions);
+        let (event_type, (input_token, output_token)) = match vault_swap_tokens(
+            &matcher,
+            classified.outer_index,
+            &input_account,
+            &base_vault,
+            &quote_vault,
+            &output_account,
+        ) {
+            Some(tokens) => (TradeType::Sell, tokens),
+            None => (
+                TradeType::Buy,
+                vault_swap_tokens(
+                    &matcher,
+                    classified.outer_index,
+                    &input_account,
+                    &quote_vault,
+                    &base_vault,
+                    &output_account,
+                )?,
+            ),
+        };
+
+        Some(MemeEvent {
+            event_type,
+            user: account(positions.payer),
+            base_mint: account(positions.base_mint),
+            quote_mint: account(positions.quote_mint),
+            input_token: Some(input_token),
+            output_token: Some(output_token),
+            protocol: Some(METEORA_DBC_PROGRAM_NAME.to_string()),
+            platform_config: accounts.get(positions.config).cloned(),
+            bonding_curve: accounts.get(positions.pool).cloned(),
+            pool: accounts.get(positions.pool).cloned(),
+            ..MemeEvent::default()
+        })
+    }
+
+    fn build_create_event(
+        accounts: &[String],
+        payload: Vec<u8>,
+    ) -> Result<MemeEvent, MeteoraError> {
+        let (config, creator, base_mint, quote_mint, pool) = INITIALIZE_ACCOUNTS;
+        let account = |index: usize| accounts.get(index).cloned().unwrap_or_default();
+        let mut reader = BinaryReader::new(payload);
+        Ok(MemeEvent {
+            event_type: TradeType::Create,
+            user: account(creator),
+            base_mint: account(base_mint),
+            quote_mint: account(quote_mint),
+            name: Some(reader.read_string()?),
+            symbol: Some(reader.read_string()?),
+            uri: Some(reader.read_string()?),
+            protocol: Some(METEORA_DBC_PROGRAM_NAME.to_string()),
+            platform_config: accounts.get(config).cloned(),
+            creator: accounts.get(creator).cloned(),
+            bonding_curve: accounts.get(pool).cloned(),
+            pool: accounts.get(pool).cloned(),
+            ..MemeEvent::default()
+        })
+    }
+
+    fn build_migrate_event(&self, accounts: &[String], positions: &MigrateAccounts) -> MemeEvent {
+        let account = |index: usize| accounts.get(index).cloned().unwrap_or_default();
+        MemeEvent {
+            event_type: TradeType::Migrate,
+            user: self.adapter.signer(),
+            base_mint: account(positions.base_mint),
+            quote_mint: account(positions.quote_mint),
+            protocol: Some(METEORA_DBC_PROGRAM_NAME.to_string()),
+            platform_config: accounts.get(positions.config).cloned(),
+            bonding_curve: accounts.get(positions.virtual_pool).cloned(),
+            pool: accounts.get(positions.pool).cloned(),
+            pool_dex: Some(dex_program_names::name(positions.destination).to_string()),
+            ..MemeEvent::default()
+        }
+    }
+
+    fn decode_claim_event(&self, data: Vec<u8>) -> Result<MeteoraDbcClaimFeeEvent, MeteoraError> {
+        let mut reader = BinaryReader::new(data);
+        Ok(MeteoraDbcClaimFeeEvent {
+            pool: reader.read_pubkey()?,
+            token_base_amount: reader.read_u64()?,
+            token_quote_amount: reader.read_u64()?,
+        })
+    }
+}
+
+pub struct MeteoraDbcMemeParser {
+    adapter: TransactionAdapter,
+    transfer_actions: TransferMap,
+}
+
+impl MeteoraDbcMemeParser {
+    pub fn new(adapter: TransactionAdapter, transfer_actions: TransferMap) -> Self {
+        Self {
+            adapter,
+            transfer_actions,
+        }
+    }
+}
+
+impl MemeEventParser for MeteoraDbcMemeParser {
+    fn process_events(&mut self) -> Vec<MemeEvent> {
+        let classifier = InstructionClassifier::new(&self.adapter);
+        let instructions = classifier.get_instructions(METEORA_DBC_PROGRAM_ID);
+        let parser =
+            MeteoraDbcEventParser::new(self.adapter.clone(), self.transfer_actions.clone());
+        match parser.parse_instructions(&instructions) {
+            Ok(events) => events,
+            Err(err) => {
+                tracing::error!("failed to parse meteora dbc events: {err}");
+                Vec::new()
+            }
+        }
+    }
+}
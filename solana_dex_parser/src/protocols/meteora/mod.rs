@@ -0,0 +1,16 @@
+pub mod constants;
+pub mod error;
+pub mod meteora_dbc_event_parser;
+
+use crate::core::transaction_adapter::TransactionAdapter;
+use crate::protocols::simple::MemeEventParser;
+use crate::types::TransferMap;
+
+use meteora_dbc_event_parser::MeteoraDbcMemeParser;
+
+pub fn build_meteora_dbc_meme_parser(
+    adapter: TransactionAdapter,
+    transfer_actions: TransferMap,
+) -> Box<dyn MemeEventParser> {
+    Box::new(MeteoraDbcMemeParser::new(adapter, transfer_actions))
+}
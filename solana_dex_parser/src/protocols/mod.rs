@@ -1,2 +1,19 @@
+pub mod bags;
+pub mod fluxbeam;
+pub mod heaven;
+pub mod humidifi;
+pub mod jupiter;
+pub mod meteora;
+pub mod obric;
+pub mod okx;
+pub mod openbook;
+pub mod orca;
 pub mod pumpfun;
+pub mod raydium;
+pub mod registry;
+pub mod saber;
 pub mod simple;
+pub mod solfi;
+pub mod token_swap;
+pub mod virtuals;
+pub mod zerofi;
@@ -0,0 +1,8 @@
+pub const OBRIC_V2_PROGRAM_ID: &str = "obriQD1zbpyLz95G5n7nJe6a4DPjpFwa5XYPoNm113y";
+pub const OBRIC_V2_PROGRAM_NAME: &str = "ObricV2";
+
+pub mod discriminators {
+    pub mod obric_instructions {
+        pub const SWAP: [u8; 8] = [248, 198, 158, 145, 225, 117, 135, 200];
+    }
+}
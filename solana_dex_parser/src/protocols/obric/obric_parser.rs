@@ -0,0 +1,147 @@
+use crate::core::matcher::TransferMatcher;
+use crate::core::transaction_adapter::TransactionAdapter;
+use crate::protocols::pumpfun::binary_reader::BinaryReader;
+use crate::protocols::pumpfun::util::{decode_instruction_data, get_trade_type};
+use crate::protocols::raydium::util::vault_swap_tokens;
+use crate::protocols::simple::TradeParser;
+use crate::types::{ClassifiedInstruction, DexInfo, TradeInfo, TransferMap};
+
+use super::constants::{
+    discriminators::obric_instructions, OBRIC_V2_PROGRAM_ID, OBRIC_V2_PROGRAM_NAME,
+};
+use super::error::ObricError;
+
+/// Decoded Obric V2 `swap`. Accounts: trading pair, mint x, mint y, reserve x, reserve y,
+/// user x account, user y account, protocol fee, x price feed, y price feed, user; the
+/// payload is the direction, `amount_in` and `min_amount_out`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ObricSwapInstruction {
+    pub trading_pair: String,
+    pub reserve_x: String,
+    pub reserve_y: String,
+    pub user_x_account: String,
+    pub user_y_account: String,
+    pub user: String,
+    pub is_x_to_y: bool,
+    pub amount_in: u64,
+    pub min_amount_out: u64,
+}
+
+/// Swaps against Obric V2 pairs, which quote from Pyth price feeds instead of a curve.
+pub struct ObricV2Parser {
+    adapter: TransactionAdapter,
+    dex_info: DexInfo,
+    transfer_actions: TransferMap,
+    classified_instructions: Vec<ClassifiedInstruction>,
+}
+
+impl ObricV2Parser {
+    pub fn new(
+        adapter: TransactionAdapter,
+        dex_info: DexInfo,
+        transfer_actions: TransferMap,
+        classified_instructions: Vec<ClassifiedInstruction>,
+    ) -> Self {
+        Self {
+            adapter,
+            dex_info,
+            transfer_actions,
+            classified_instructions,
+        }
+    }
+
+    pub fn decode_swap(
+        accounts: &[String],
+        payload: &[u8],
+    ) -> Result<ObricSwapInstruction, ObricError> {
+        let account = |index: usize| accounts.get(index).cloned().unwrap_or_default();
+        let mut reader = BinaryReader::new(payload.to_vec());
+        Ok(ObricSwapInstruction {
+            trading_pair: account(0),
+            reserve_x: account(3),
+            reserve_y: account(4),
+            user_x_account: account(5),
+            user_y_account: account(6),
+            user: account(10),
+            is_x_to_y: reader.read_bool()?,
+            amount_in: reader.read_u64()?,
+            min_amount_out: reader.read_u64()?,
+        })
+    }
+
+    fn build_trade(
+        &self,
+        classified: &ClassifiedInstruction,
+        swap: &ObricSwapInstruction,
+    ) -> Option<TradeInfo> {
+        let matcher = TransferMatcher::new(&self.transfer_actions);
+        let (input_token, output_token) = if swap.is_x_to_y {
+            vault_swap_tokens(
+                &matcher,
+                classified.outer_index,
+                &swap.user_x_account,
+                &swap.reserve_x,
+                &swap.reserve_y,
+                &swap.user_y_account,
+            )?
+        } else {
+            vault_swap_tokens(
+                &matcher,
+                classified.outer_index,
+                &swap.user_y_account,
+                &swap.reserve_y,
+                &swap.reserve_x,
+                &swap.user_x_account,
+            )?
+        };
+
+        Some(TradeInfo {
+            trade_type: get_trade_type(&input_token.mint, &output_token.mint),
+            pool: vec![swap.trading_pair.clone()],
+            oracle_price: (input_token.amount > 0.0)
+                .then(|| output_token.amount / input_token.amount),
+            input_token,
+            output_token,
+            user: Some(swap.user.clone()),
+            program_id: Some(OBRIC_V2_PROGRAM_ID.to_string()),
+            amm: Some(OBRIC_V2_PROGRAM_NAME.to_string()),
+            route: self.dex_info.route.clone(),
+            slot: self.adapter.slot(),
+            timestamp: self.adapter.block_time(),
+            signature: self.adapter.shared_signature(),
+            idx: format!(
+                "{}-{}",
+                classified.outer_index,
+                classified.inner_index.unwrap_or(0)
+            ),
+            signer: Some(self.adapter.signers().to_vec()),
+            ..TradeInfo::default()
+        })
+    }
+
+    fn parse_swaps(&self) -> Result<Vec<TradeInfo>, ObricError> {
+        let mut trades = Vec::new();
+        for classified in &self.classified_instructions {
+            let data = decode_instruction_data(&classified.data.data)
+                .map_err(|err| ObricError::instruction_data(err.to_string()))?;
+            if data.len() < 8 || data[..8] != obric_instructions::SWAP {
+                continue;
+            }
+            let swap = Self::decode_swap(&classified.data.accounts, &data[8..])?;
+            trades.extend(self.build_trade(classified, &swap));
+        }
+        Ok(trades)
+    }
+}
+
+impl TradeParser for ObricV2Parser {
+    fn process_trades(&mut self) -> Vec<TradeInfo> {
+        match self.parse_swaps() {
+            Ok(trades) => trades,
+            Err(err) => {
+                tracing::error!("failed to parse obric v2 instructions: {err}");
+                Vec::new()
+            }
+        }
+    }
+}
@@ -0,0 +1,22 @@
+pub const OKX_PROGRAM_ID: &str = "6m2CDdhRgxpH4WjvdzxAYbGxwdGUz5MziiL5jek2kBma";
+pub const OKX_PROGRAM_NAME: &str = "OKX";
+
+pub mod discriminators {
+    /// Route instructions; all start with `(payer, source_token_account,
+    /// destination_token_account, source_mint, destination_mint)` and the swap arguments.
+    pub mod okx_instructions {
+        pub const SWAP: [u8; 8] = [248, 198, 158, 145, 225, 117, 135, 200];
+        pub const SWAP_TOB: [u8; 8] = [170, 41, 85, 177, 132, 80, 31, 53];
+        pub const PROXY_SWAP: [u8; 8] = [19, 44, 130, 148, 72, 56, 44, 238];
+        pub const COMMISSION_SPL_SWAP: [u8; 8] = [235, 71, 211, 196, 114, 199, 143, 92];
+        pub const COMMISSION_SOL_SWAP: [u8; 8] = [81, 128, 134, 73, 114, 73, 45, 94];
+
+        pub const ALL: [[u8; 8]; 5] = [
+            SWAP,
+            SWAP_TOB,
+            PROXY_SWAP,
+            COMMISSION_SPL_SWAP,
+            COMMISSION_SOL_SWAP,
+        ];
+    }
+}
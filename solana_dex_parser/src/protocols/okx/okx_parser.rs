@@ -0,0 +1,200 @@
+use crate::core::constants::system_programs;
+use crate::core::instruction_classifier::{InstructionClassifier, InvocationNode};
+use crate::core::matcher::{Leg, TransferMatcher};
+use crate::core::transaction_adapter::TransactionAdapter;
+use crate::protocols::pumpfun::binary_reader::BinaryReader;
+use crate::protocols::pumpfun::util::{
+    build_token_info, compare_idx, decode_instruction_data, get_trade_type, slippage_bps,
+};
+use crate::protocols::simple::TradeParser;
+use crate::types::{ClassifiedInstruction, DexInfo, TradeInfo, TransferData, TransferMap};
+
+use super::constants::{discriminators::okx_instructions, OKX_PROGRAM_ID, OKX_PROGRAM_NAME};
+use super::error::OkxError;
+
+/// Account positions shared by every route instruction.
+struct RouteAccounts {
+    payer: usize,
+    source_token_account: usize,
+    destination_token_account: usize,
+    source_mint: usize,
+    destination_mint: usize,
+}
+
+const ROUTE_ACCOUNTS: RouteAccounts = RouteAccounts {
+    payer: 0,
+    source_token_account: 1,
+    destination_token_account: 2,
+    source_mint: 3,
+    destination_mint: 4,
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct OkxRouteInstruction {
+    pub payer: String,
+    pub source_token_account: String,
+    pub destination_token_account: String,
+    pub source_mint: String,
+    pub destination_mint: String,
+    pub amount_in: u64,
+    pub expect_amount_out: u64,
+    pub min_return: u64,
+}
+
+/// Whole OKX DEX routes, from the user's source account to their destination account.
+///
+/// The hops are left to the parsers of the venues they ran on, which report them as
+/// separate trades routed through OKX.
+pub struct OkxParser {
+    adapter: TransactionAdapter,
+    dex_info: DexInfo,
+    transfer_actions: TransferMap,
+    classified_instructions: Vec<ClassifiedInstruction>,
+}
+
+impl OkxParser {
+    pub fn new(
+        adapter: TransactionAdapter,
+        dex_info: DexInfo,
+        transfer_actions: TransferMap,
+        classified_instructions: Vec<ClassifiedInstruction>,
+    ) -> Self {
+        Self {
+            adapter,
+            dex_info,
+            transfer_actions,
+            classified_instructions,
+        }
+    }
+
+    fn decode_route(accounts: &[String], payload: &[u8]) -> Result<OkxRouteInstruction, OkxError> {
+        let account = |index: usize| accounts.get(index).cloned().unwrap_or_default();
+        let positions = &ROUTE_ACCOUNTS;
+        let mut reader = BinaryReader::new(payload.to_vec());
+        Ok(OkxRouteInstruction {
+            payer: account(positions.payer),
+            source_token_account: account(positions.source_token_account),
+            destination_token_account: account(positions.destination_token_account),
+            source_mint: account(positions.source_mint),
+            destination_mint: account(positions.destination_mint),
+            amount_in: reader.read_u64()?,
+            expect_amount_out: reader.read_u64()?,
+            min_return: reader.read_u64()?,
+        })
+    }
+
+    fn decimals(&self, mint: &str, transfers: &[&TransferData]) -> u8 {
+        transfers
+            .first()
+            .map(|transfer| transfer.info.token_amount.decimals)
+            .or_else(|| self.adapter.spl_decimals_map().get(mint).copied())
+            .unwrap_or(0)
+    }
+
+    /// Venues the route hopped through, in execution order: the programs it invoked
+    /// directly, not the CPIs those venues made in turn.
+    fn hop_amms(&self, hops: &[InvocationNode]) -> Vec<String> {
+        hops.iter()
+            .map(|hop| hop.instruction.program_id.as_str())
+            .filter(|program| *program != OKX_PROGRAM_ID && !system_programs::ALL.contains(program))
+            .map(|program| self.adapter.config().program_name(program))
+            .filter(|name| name != "Unknown DEX")
+            .collect()
+    }
+
+    /// One trade per route: what left the source account against what reached the
+    /// destination account within the route's invocation, so two routes sharing an outer
+    /// instruction stay apart. Native SOL inputs fall back to the routed amount.
+    fn build_trade(
+        &self,
+        classifier: &InstructionClassifier,
+        classified: &ClassifiedInstruction,
+        route: &OkxRouteInstruction,
+    ) -> Option<TradeInfo> {
+        // Without stack heights a CPI'd route has no known children; scope it to its outer
+        // instruction instead.
+        let invocation = classifier
+            .invocation_of(classified)
+            .filter(|node| classified.inner_index.is_none() || !node.children.is_empty());
+        let hops = match &invocation {
+            Some(invocation) => invocation.children.clone(),
+            None => classifier.children_of(classified.outer_index),
+        };
+        let matcher = TransferMatcher::new(&self.transfer_actions);
+        let legs = |leg: &Leg| match &invocation {
+            Some(invocation) => matcher.find_legs_in(invocation, leg),
+            None => matcher.find_legs(classified.outer_index, leg),
+        };
+        let paid = legs(&Leg::of(&route.source_mint).from(&route.source_token_account));
+        let received = legs(&Leg::of(&route.destination_mint).to(&route.destination_token_account));
+        let last = received.iter().max_by(|a, b| compare_idx(&a.idx, &b.idx))?;
+
+        let raw = |transfer: &&TransferData| {
+            transfer
+                .info
+                .token_amount
+                .amount
+                .parse::<u128>()
+                .unwrap_or(0)
+        };
+        let amount_in = if paid.is_empty() {
+            route.amount_in as u128
+        } else {
+            paid.iter().map(raw).sum()
+        };
+        let amount_out: u128 = received.iter().map(raw).sum();
+        let amms = self.hop_amms(&hops);
+
+        Some(TradeInfo {
+            trade_type: get_trade_type(&route.source_mint, &route.destination_mint),
+            input_token: build_token_info(
+                &route.source_mint,
+                amount_in,
+                self.decimals(&route.source_mint, &paid),
+                None,
+            ),
+            output_token: build_token_info(
+                &route.destination_mint,
+                amount_out,
+                self.decimals(&route.destination_mint, &received),
+                None,
+            ),
+            slippage_bps: slippage_bps(amount_out, route.min_return),
+            user: Some(route.payer.clone()),
+            program_id: Some(OKX_PROGRAM_ID.to_string()),
+            amm: amms.first().cloned().or_else(|| self.dex_info.amm.clone()),
+            amms: Some(amms),
+            route: Some(OKX_PROGRAM_NAME.to_string()),
+            slot: self.adapter.slot(),
+            timestamp: self.adapter.block_time(),
+            signature: self.adapter.shared_signature(),
+            idx: last.idx.clone(),
+            signer: Some(self.adapter.signers().to_vec()),
+            ..TradeInfo::default()
+        })
+    }
+}
+
+impl TradeParser for OkxParser {
+    fn process_trades(&mut self) -> Vec<TradeInfo> {
+        let classifier = InstructionClassifier::new(&self.adapter);
+        let mut trades = Vec::new();
+        for classified in &self.classified_instructions {
+            let data = match decode_instruction_data(&classified.data.data) {
+                Ok(data) => data,
+                Err(err) => {
+                    tracing::error!("failed to decode okx instruction: {err}");
+                    continue;
+                }
+            };
+            if data.len() < 8 || !okx_instructions::ALL.iter().any(|d| data[..8] == *d) {
+                continue;
+            }
+            match Self::decode_route(&classified.data.accounts, &data[8..]) {
+                Ok(route) => trades.extend(self.build_trade(&classifier, classified, &route)),
+                Err(err) => tracing::error!("failed to parse okx route: {err}"),
+            }
+        }
+        trades
+    }
+}
@@ -0,0 +1,14 @@
+pub const OPENBOOK_V2_PROGRAM_ID: &str = "opnb2LAfJYbRMAHHvqjCwQxanZn7ReEHp1k81EohpZb";
+pub const OPENBOOK_V2_PROGRAM_NAME: &str = "OpenBookV2";
+
+pub mod discriminators {
+    pub mod openbook_instructions {
+        pub const CREATE_MARKET: [u8; 8] = [103, 226, 97, 235, 200, 188, 251, 254];
+        pub const PLACE_ORDER: [u8; 8] = [51, 194, 155, 175, 109, 130, 96, 106];
+        pub const PLACE_ORDER_PEGGED: [u8; 8] = [141, 185, 251, 63, 74, 85, 210, 145];
+        pub const PLACE_TAKE_ORDER: [u8; 8] = [3, 44, 71, 3, 26, 199, 203, 85];
+        pub const SETTLE_FUNDS: [u8; 8] = [238, 64, 163, 96, 75, 171, 16, 33];
+        pub const CONSUME_EVENTS: [u8; 8] = [221, 145, 177, 52, 31, 47, 63, 201];
+        pub const CONSUME_GIVEN_EVENTS: [u8; 8] = [209, 227, 54, 4, 109, 172, 41, 71];
+    }
+}
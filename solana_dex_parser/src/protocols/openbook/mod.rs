@@ -0,0 +1,38 @@
+pub mod constants;
+pub mod error;
+pub mod openbook_instruction_parser;
+pub mod openbook_liquidity_parser;
+pub mod openbook_parser;
+
+use crate::core::transaction_adapter::TransactionAdapter;
+use crate::protocols::simple::{LiquidityParser, TradeParser};
+use crate::types::{ClassifiedInstruction, DexInfo, TransferMap};
+
+use openbook_liquidity_parser::OpenBookLiquidityParser;
+use openbook_parser::OpenBookParser;
+
+pub fn build_openbook_trade_parser(
+    adapter: TransactionAdapter,
+    dex_info: DexInfo,
+    transfer_actions: TransferMap,
+    classified_instructions: Vec<ClassifiedInstruction>,
+) -> Box<dyn TradeParser> {
+    Box::new(OpenBookParser::new(
+        adapter,
+        dex_info,
+        transfer_actions,
+        classified_instructions,
+    ))
+}
+
+pub fn build_openbook_liquidity_parser(
+    adapter: TransactionAdapter,
+    transfer_actions: TransferMap,
+    classified_instructions: Vec<ClassifiedInstruction>,
+) -> Box<dyn LiquidityParser> {
+    Box::new(OpenBookLiquidityParser::new(
+        adapter,
+        transfer_actions,
+        classified_instructions,
+    ))
+}
@@ -0,0 +1,216 @@
+use std::sync::Arc;
+
+use crate::core::transaction_adapter::TransactionAdapter;
+use crate::protocols::pumpfun::binary_reader::BinaryReader;
+use crate::protocols::pumpfun::util::{decode_instruction_data, sort_by_idx, HasIdx};
+use crate::types::ClassifiedInstruction;
+
+use super::constants::discriminators::openbook_instructions;
+use super::error::OpenBookError;
+
+/// Order side, the leading byte of every order instruction's arguments.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OpenBookSide {
+    Bid,
+    Ask,
+}
+
+impl OpenBookSide {
+    fn from_u8(value: u8) -> Result<Self, OpenBookError> {
+        match value {
+            0 => Ok(Self::Bid),
+            1 => Ok(Self::Ask),
+            other => Err(OpenBookError::instruction_data(format!(
+                "unknown order side {other}"
+            ))),
+        }
+    }
+}
+
+/// Taker order against the book, settled straight into the user's token accounts.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OpenBookTakeOrder {
+    pub side: OpenBookSide,
+    pub signer: String,
+    pub market: String,
+    pub base_vault: String,
+    pub quote_vault: String,
+    pub user_base_account: String,
+    pub user_quote_account: String,
+}
+
+/// Order placed through an open orders account; the deposit lands in `market_vault`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OpenBookPlaceOrder {
+    pub side: OpenBookSide,
+    pub signer: String,
+    pub open_orders: String,
+    pub user_token_account: String,
+    pub market: String,
+    pub market_vault: String,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct OpenBookSettleFunds {
+    pub owner: String,
+    pub open_orders: String,
+    pub market: String,
+    pub base_vault: String,
+    pub quote_vault: String,
+    pub user_base_account: String,
+    pub user_quote_account: String,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct OpenBookCreateMarket {
+    pub market: String,
+    pub payer: String,
+    pub base_vault: String,
+    pub quote_vault: String,
+    pub base_mint: String,
+    pub quote_mint: String,
+    pub name: String,
+    pub quote_lot_size: i64,
+    pub base_lot_size: i64,
+    pub maker_fee: i64,
+    pub taker_fee: i64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum OpenBookInstructionData {
+    PlaceTakeOrder(OpenBookTakeOrder),
+    PlaceOrder(OpenBookPlaceOrder),
+    SettleFunds(OpenBookSettleFunds),
+    /// Crank moving fills from the event heap into open orders accounts; no tokens move.
+    ConsumeEvents {
+        market: String,
+    },
+    CreateMarket(OpenBookCreateMarket),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct OpenBookInstruction {
+    pub data: OpenBookInstructionData,
+    pub outer_index: usize,
+    pub inner_index: Option<usize>,
+    pub slot: u64,
+    pub timestamp: u64,
+    pub signature: Arc<str>,
+    pub idx: String,
+    pub signer: Vec<String>,
+}
+
+impl HasIdx for OpenBookInstruction {
+    fn idx(&self) -> &str {
+        &self.idx
+    }
+}
+
+pub struct OpenBookInstructionParser {
+    adapter: TransactionAdapter,
+}
+
+impl OpenBookInstructionParser {
+    pub fn new(adapter: TransactionAdapter) -> Self {
+        Self { adapter }
+    }
+
+    pub fn parse_instructions(
+        &self,
+        instructions: &[ClassifiedInstruction],
+    ) -> Result<Vec<OpenBookInstruction>, OpenBookError> {
+        let mut parsed = Vec::new();
+        for instruction in instructions {
+            let data = decode_instruction_data(&instruction.data.data)
+                .map_err(|err| OpenBookError::instruction_data(err.to_string()))?;
+            if data.len() < 8 {
+                continue;
+            }
+            let Some(data) = Self::decode_instruction(instruction, &data[..8], &data[8..])? else {
+                continue;
+            };
+            parsed.push(OpenBookInstruction {
+                data,
+                outer_index: instruction.outer_index,
+                inner_index: instruction.inner_index,
+                slot: self.adapter.slot(),
+                timestamp: self.adapter.block_time(),
+                signature: self.adapter.shared_signature(),
+                idx: format!(
+                    "{}-{}",
+                    instruction.outer_index,
+                    instruction.inner_index.unwrap_or(0)
+                ),
+                signer: self.adapter.signers().to_vec(),
+            });
+        }
+
+        Ok(sort_by_idx(parsed))
+    }
+
+    fn decode_instruction(
+        instruction: &ClassifiedInstruction,
+        discriminator: &[u8],
+        payload: &[u8],
+    ) -> Result<Option<OpenBookInstructionData>, OpenBookError> {
+        let accounts = &instruction.data.accounts;
+        let account = |index: usize| accounts.get(index).cloned().unwrap_or_default();
+        let mut reader = BinaryReader::new(payload.to_vec());
+        let data = if discriminator == openbook_instructions::PLACE_TAKE_ORDER {
+            OpenBookInstructionData::PlaceTakeOrder(OpenBookTakeOrder {
+                side: OpenBookSide::from_u8(reader.read_u8()?)?,
+                signer: account(0),
+                market: account(2),
+                base_vault: account(6),
+                quote_vault: account(7),
+                user_base_account: account(9),
+                user_quote_account: account(10),
+            })
+        } else if discriminator == openbook_instructions::PLACE_ORDER
+            || discriminator == openbook_instructions::PLACE_ORDER_PEGGED
+        {
+            OpenBookInstructionData::PlaceOrder(OpenBookPlaceOrder {
+                side: OpenBookSide::from_u8(reader.read_u8()?)?,
+                signer: account(0),
+                open_orders: account(1),
+                user_token_account: account(3),
+                market: account(4),
+                market_vault: account(8),
+            })
+        } else if discriminator == openbook_instructions::SETTLE_FUNDS {
+            OpenBookInstructionData::SettleFunds(OpenBookSettleFunds {
+                owner: account(0),
+                open_orders: account(2),
+                market: account(3),
+                base_vault: account(5),
+                quote_vault: account(6),
+                user_base_account: account(7),
+                user_quote_account: account(8),
+            })
+        } else if discriminator == openbook_instructions::CONSUME_EVENTS
+            || discriminator == openbook_instructions::CONSUME_GIVEN_EVENTS
+        {
+            OpenBookInstructionData::ConsumeEvents { market: account(1) }
+        } else if discriminator == openbook_instructions::CREATE_MARKET {
+            let name = reader.read_string()?;
+            // `oracle_config`: conf_filter (f32) and max_staleness_slots (i64).
+            reader.read_fixed_array(4 + 8)?;
+            OpenBookInstructionData::CreateMarket(OpenBookCreateMarket {
+                market: account(0),
+                payer: account(5),
+                base_vault: account(6),
+                quote_vault: account(7),
+                base_mint: account(8),
+                quote_mint: account(9),
+                name,
+                quote_lot_size: reader.read_i64()?,
+                base_lot_size: reader.read_i64()?,
+                maker_fee: reader.read_i64()?,
+                taker_fee: reader.read_i64()?,
+            })
+        } else {
+            return Ok(None);
+        };
+        Ok(Some(data))
+    }
+}
@@ -0,0 +1,82 @@
+use crate::core::transaction_adapter::TransactionAdapter;
+use crate::protocols::simple::LiquidityParser;
+use crate::types::{ClassifiedInstruction, PoolEvent, TradeType, TransferMap};
+
+use super::constants::{OPENBOOK_V2_PROGRAM_ID, OPENBOOK_V2_PROGRAM_NAME};
+use super::openbook_instruction_parser::{
+    OpenBookCreateMarket, OpenBookInstruction, OpenBookInstructionData, OpenBookInstructionParser,
+};
+
+/// Market creation; a book holds no pooled liquidity, so no amounts are reported.
+pub struct OpenBookLiquidityParser {
+    adapter: TransactionAdapter,
+    _transfer_actions: TransferMap,
+    classified_instructions: Vec<ClassifiedInstruction>,
+    instruction_parser: OpenBookInstructionParser,
+}
+
+impl OpenBookLiquidityParser {
+    pub fn new(
+        adapter: TransactionAdapter,
+        transfer_actions: TransferMap,
+        classified_instructions: Vec<ClassifiedInstruction>,
+    ) -> Self {
+        let instruction_parser = OpenBookInstructionParser::new(adapter.clone());
+        Self {
+            adapter,
+            _transfer_actions: transfer_actions,
+            classified_instructions,
+            instruction_parser,
+        }
+    }
+
+    fn build_event(
+        &self,
+        instruction: &OpenBookInstruction,
+        market: &OpenBookCreateMarket,
+    ) -> PoolEvent {
+        let decimals = self.adapter.spl_decimals_map();
+        PoolEvent {
+            user: market.payer.clone(),
+            event_type: TradeType::Create,
+            program_id: Some(OPENBOOK_V2_PROGRAM_ID.to_string()),
+            amm: Some(OPENBOOK_V2_PROGRAM_NAME.to_string()),
+            slot: instruction.slot,
+            timestamp: instruction.timestamp,
+            signature: instruction.signature.clone(),
+            idx: instruction.idx.clone(),
+            signer: Some(instruction.signer.clone()),
+            pool_id: market.market.clone(),
+            token0_mint: Some(market.base_mint.clone()),
+            token0_decimals: decimals.get(&market.base_mint).copied(),
+            token1_mint: Some(market.quote_mint.clone()),
+            token1_decimals: decimals.get(&market.quote_mint).copied(),
+            ..PoolEvent::default()
+        }
+    }
+}
+
+impl LiquidityParser for OpenBookLiquidityParser {
+    fn process_liquidity(&mut self) -> Vec<PoolEvent> {
+        let instructions = match self
+            .instruction_parser
+            .parse_instructions(&self.classified_instructions)
+        {
+            Ok(instructions) => instructions,
+            Err(err) => {
+                tracing::error!("failed to parse openbook liquidity instructions: {err}");
+                return Vec::new();
+            }
+        };
+
+        instructions
+            .iter()
+            .filter_map(|instruction| match &instruction.data {
+                OpenBookInstructionData::CreateMarket(market) => {
+                    Some(self.build_event(instruction, market))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,191 @@
+use crate::core::matcher::{Leg, TransferMatcher};
+use crate::core::transaction_adapter::TransactionAdapter;
+use crate::core::transaction_utils::TransactionUtils;
+use crate::protocols::pumpfun::util::build_token_info;
+use crate::protocols::raydium::util::vault_swap_tokens;
+use crate::protocols::simple::TradeParser;
+use crate::types::{
+    ClassifiedInstruction, DexInfo, TokenInfo, TradeInfo, TradeType, TransferData, TransferMap,
+};
+
+use super::constants::{OPENBOOK_V2_PROGRAM_ID, OPENBOOK_V2_PROGRAM_NAME};
+use super::openbook_instruction_parser::{
+    OpenBookInstruction, OpenBookInstructionData, OpenBookInstructionParser, OpenBookPlaceOrder,
+    OpenBookSettleFunds, OpenBookSide, OpenBookTakeOrder,
+};
+
+/// Taker fills of `placeTakeOrder`, and `placeOrder` deposits completed by a later
+/// `settleFunds` in the same transaction. `consumeEvents` moves no tokens, so fills it
+/// cranks only surface once the maker settles.
+pub struct OpenBookParser {
+    dex_info: DexInfo,
+    transfer_actions: TransferMap,
+    classified_instructions: Vec<ClassifiedInstruction>,
+    instruction_parser: OpenBookInstructionParser,
+}
+
+impl OpenBookParser {
+    pub fn new(
+        adapter: TransactionAdapter,
+        dex_info: DexInfo,
+        transfer_actions: TransferMap,
+        classified_instructions: Vec<ClassifiedInstruction>,
+    ) -> Self {
+        Self {
+            dex_info,
+            transfer_actions,
+            classified_instructions,
+            instruction_parser: OpenBookInstructionParser::new(adapter),
+        }
+    }
+
+    fn build_take_trade(
+        &self,
+        instruction: &OpenBookInstruction,
+        order: &OpenBookTakeOrder,
+    ) -> Option<TradeInfo> {
+        let matcher = TransferMatcher::new(&self.transfer_actions);
+        let (input_token, output_token) = match order.side {
+            OpenBookSide::Bid => vault_swap_tokens(
+                &matcher,
+                instruction.outer_index,
+                &order.user_quote_account,
+                &order.quote_vault,
+                &order.base_vault,
+                &order.user_base_account,
+            )?,
+            OpenBookSide::Ask => vault_swap_tokens(
+                &matcher,
+                instruction.outer_index,
+                &order.user_base_account,
+                &order.base_vault,
+                &order.quote_vault,
+                &order.user_quote_account,
+            )?,
+        };
+        Some(self.trade(
+            instruction,
+            order.side,
+            &order.market,
+            &order.signer,
+            input_token,
+            output_token,
+        ))
+    }
+
+    /// Deposit of `order` less what `settle` refunded, against the other token it paid out.
+    fn build_settled_trade(
+        &self,
+        instruction: &OpenBookInstruction,
+        order: &OpenBookPlaceOrder,
+        settle_instruction: &OpenBookInstruction,
+        settle: &OpenBookSettleFunds,
+    ) -> Option<TradeInfo> {
+        let matcher = TransferMatcher::new(&self.transfer_actions);
+        let deposit = matcher.find_leg(
+            instruction.outer_index,
+            &Leg::between(&order.user_token_account, &order.market_vault),
+        )?;
+
+        let payout = |vault: &str, user: &str| -> Option<&TransferData> {
+            matcher.find_leg(settle_instruction.outer_index, &Leg::between(vault, user))
+        };
+        let base = payout(&settle.base_vault, &settle.user_base_account);
+        let quote = payout(&settle.quote_vault, &settle.user_quote_account);
+        let (filled, refund) = match order.side {
+            OpenBookSide::Bid => (base?, quote),
+            OpenBookSide::Ask => (quote?, base),
+        };
+
+        let raw = |transfer: &TransferData| -> u128 {
+            transfer.info.token_amount.amount.parse().unwrap_or(0)
+        };
+        let spent = raw(deposit).saturating_sub(refund.map_or(0, raw));
+        if spent == 0 {
+            return None;
+        }
+        let input_token = build_token_info(
+            &deposit.info.mint,
+            spent,
+            deposit.info.token_amount.decimals,
+            None,
+        );
+        Some(self.trade(
+            instruction,
+            order.side,
+            &order.market,
+            &order.signer,
+            input_token,
+            TransactionUtils::transfer_to_token_info(filled),
+        ))
+    }
+
+    fn trade(
+        &self,
+        instruction: &OpenBookInstruction,
+        side: OpenBookSide,
+        market: &str,
+        user: &str,
+        input_token: TokenInfo,
+        output_token: TokenInfo,
+    ) -> TradeInfo {
+        TradeInfo {
+            trade_type: match side {
+                OpenBookSide::Bid => TradeType::Buy,
+                OpenBookSide::Ask => TradeType::Sell,
+            },
+            pool: vec![market.to_string()],
+            input_token,
+            output_token,
+            user: Some(user.to_string()),
+            program_id: Some(OPENBOOK_V2_PROGRAM_ID.to_string()),
+            amm: Some(OPENBOOK_V2_PROGRAM_NAME.to_string()),
+            route: self.dex_info.route.clone(),
+            slot: instruction.slot,
+            timestamp: instruction.timestamp,
+            signature: instruction.signature.clone(),
+            idx: instruction.idx.clone(),
+            signer: Some(instruction.signer.clone()),
+            ..TradeInfo::default()
+        }
+    }
+}
+
+impl TradeParser for OpenBookParser {
+    fn process_trades(&mut self) -> Vec<TradeInfo> {
+        let instructions = match self
+            .instruction_parser
+            .parse_instructions(&self.classified_instructions)
+        {
+            Ok(instructions) => instructions,
+            Err(err) => {
+                tracing::error!("failed to parse openbook instructions: {err}");
+                return Vec::new();
+            }
+        };
+
+        let mut trades = Vec::new();
+        // Orders placed through an open orders account, waiting for their settlement.
+        let mut pending: Vec<(&OpenBookInstruction, &OpenBookPlaceOrder)> = Vec::new();
+        for instruction in &instructions {
+            match &instruction.data {
+                OpenBookInstructionData::PlaceTakeOrder(order) => {
+                    trades.extend(self.build_take_trade(instruction, order));
+                }
+                OpenBookInstructionData::PlaceOrder(order) => pending.push((instruction, order)),
+                OpenBookInstructionData::SettleFunds(settle) => {
+                    let Some(position) = pending.iter().rposition(|(_, order)| {
+                        order.open_orders == settle.open_orders && order.market == settle.market
+                    }) else {
+                        continue;
+                    };
+                    let (placed, order) = pending.remove(position);
+                    trades.extend(self.build_settled_trade(placed, order, instruction, settle));
+                }
+                OpenBookInstructionData::ConsumeEvents { .. }
+                | OpenBookInstructionData::CreateMarket(_) => {}
+            }
+        }
+        trades
+    }
+}
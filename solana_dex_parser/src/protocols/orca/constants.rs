@@ -0,0 +1,24 @@
+pub const ORCA_WHIRLPOOL_PROGRAM_ID: &str = "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc";
+pub const ORCA_WHIRLPOOL_PROGRAM_NAME: &str = "Orca";
+
+pub mod discriminators {
+    pub mod whirlpool_instructions {
+        pub const SWAP: [u8; 8] = [248, 198, 158, 145, 225, 117, 135, 200];
+        pub const SWAP_V2: [u8; 8] = [43, 4, 237, 11, 26, 201, 30, 98];
+        pub const OPEN_POSITION: [u8; 8] = [135, 128, 47, 77, 15, 152, 240, 49];
+        pub const OPEN_POSITION_WITH_METADATA: [u8; 8] = [242, 29, 134, 48, 58, 110, 14, 60];
+        pub const INCREASE_LIQUIDITY: [u8; 8] = [46, 156, 243, 118, 13, 205, 251, 178];
+        pub const INCREASE_LIQUIDITY_V2: [u8; 8] = [133, 29, 89, 223, 69, 238, 176, 10];
+        pub const DECREASE_LIQUIDITY: [u8; 8] = [160, 38, 208, 111, 104, 91, 44, 1];
+        pub const DECREASE_LIQUIDITY_V2: [u8; 8] = [58, 127, 188, 62, 79, 82, 196, 96];
+        pub const COLLECT_FEES: [u8; 8] = [164, 152, 207, 99, 30, 186, 19, 182];
+        pub const COLLECT_FEES_V2: [u8; 8] = [207, 117, 95, 191, 229, 180, 226, 15];
+        pub const CLOSE_POSITION: [u8; 8] = [123, 134, 81, 0, 49, 68, 98, 98];
+    }
+
+    pub mod whirlpool_events {
+        pub const TRADED: [u8; 16] = [
+            228, 69, 165, 46, 81, 203, 154, 29, 225, 202, 73, 175, 147, 43, 160, 150,
+        ];
+    }
+}
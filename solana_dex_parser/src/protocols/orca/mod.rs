@@ -0,0 +1,37 @@
+pub mod constants;
+pub mod error;
+pub mod whirlpool_liquidity_parser;
+pub mod whirlpool_parser;
+
+use crate::core::transaction_adapter::TransactionAdapter;
+use crate::protocols::simple::{LiquidityParser, TradeParser};
+use crate::types::{ClassifiedInstruction, DexInfo, TransferMap};
+
+use whirlpool_liquidity_parser::OrcaWhirlpoolLiquidityParser;
+use whirlpool_parser::OrcaWhirlpoolParser;
+
+pub fn build_orca_whirlpool_trade_parser(
+    adapter: TransactionAdapter,
+    dex_info: DexInfo,
+    transfer_actions: TransferMap,
+    classified_instructions: Vec<ClassifiedInstruction>,
+) -> Box<dyn TradeParser> {
+    Box::new(OrcaWhirlpoolParser::new(
+        adapter,
+        dex_info,
+        transfer_actions,
+        classified_instructions,
+    ))
+}
+
+pub fn build_orca_whirlpool_liquidity_parser(
+    adapter: TransactionAdapter,
+    transfer_actions: TransferMap,
+    classified_instructions: Vec<ClassifiedInstruction>,
+) -> Box<dyn LiquidityParser> {
+    Box::new(OrcaWhirlpoolLiquidityParser::new(
+        adapter,
+        transfer_actions,
+        classified_instructions,
+    ))
+}
@@ -0,0 +1,386 @@
+use std::collections::HashSet;
+
+use crate::core::matcher::{Leg, TransferMatcher};
+use crate::core::transaction_adapter::TransactionAdapter;
+use crate::protocols::pumpfun::binary_reader::BinaryReader;
+use crate::protocols::pumpfun::util::decode_instruction_data;
+use crate::protocols::simple::LiquidityParser;
+use crate::types::{
+    ClassifiedInstruction, PoolEvent, PositionInfo, TradeType, TransferData, TransferMap,
+};
+
+use super::constants::{
+    discriminators::whirlpool_instructions, ORCA_WHIRLPOOL_PROGRAM_ID, ORCA_WHIRLPOOL_PROGRAM_NAME,
+};
+use super::error::OrcaError;
+
+/// Account positions of a position instruction; `None` when the instruction lacks the account.
+struct PositionAccounts {
+    owner: usize,
+    whirlpool: Option<usize>,
+    position: usize,
+    position_mint: Option<usize>,
+    tick_array_lower: Option<usize>,
+    tick_array_upper: Option<usize>,
+    token_vault_a: Option<usize>,
+    token_vault_b: Option<usize>,
+}
+
+const OPEN_POSITION_ACCOUNTS: PositionAccounts = PositionAccounts {
+    owner: 1,
+    whirlpool: Some(5),
+    position: 2,
+    position_mint: Some(3),
+    tick_array_lower: None,
+    tick_array_upper: None,
+    token_vault_a: None,
+    token_vault_b: None,
+};
+
+const OPEN_POSITION_WITH_METADATA_ACCOUNTS: PositionAccounts = PositionAccounts {
+    whirlpool: Some(6),
+    ..OPEN_POSITION_ACCOUNTS
+};
+
+const MODIFY_LIQUIDITY_ACCOUNTS: PositionAccounts = PositionAccounts {
+    owner: 2,
+    whirlpool: Some(0),
+    position: 3,
+    position_mint: None,
+    tick_array_lower: Some(9),
+    tick_array_upper: Some(10),
+    token_vault_a: Some(7),
+    token_vault_b: Some(8),
+};
+
+const MODIFY_LIQUIDITY_V2_ACCOUNTS: PositionAccounts = PositionAccounts {
+    owner: 4,
+    whirlpool: Some(0),
+    position: 5,
+    position_mint: None,
+    tick_array_lower: Some(13),
+    tick_array_upper: Some(14),
+    token_vault_a: Some(11),
+    token_vault_b: Some(12),
+};
+
+const COLLECT_FEES_ACCOUNTS: PositionAccounts = PositionAccounts {
+    owner: 1,
+    whirlpool: Some(0),
+    position: 2,
+    position_mint: None,
+    tick_array_lower: None,
+    tick_array_upper: None,
+    token_vault_a: Some(5),
+    token_vault_b: Some(7),
+};
+
+const COLLECT_FEES_V2_ACCOUNTS: PositionAccounts = PositionAccounts {
+    token_vault_a: Some(7),
+    token_vault_b: Some(9),
+    ..COLLECT_FEES_ACCOUNTS
+};
+
+const CLOSE_POSITION_ACCOUNTS: PositionAccounts = PositionAccounts {
+    owner: 0,
+    whirlpool: None,
+    position: 2,
+    position_mint: Some(3),
+    tick_array_lower: None,
+    tick_array_upper: None,
+    token_vault_a: None,
+    token_vault_b: None,
+};
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct WhirlpoolPositionInstruction {
+    pub owner: String,
+    pub whirlpool: Option<String>,
+    pub position: String,
+    pub position_mint: Option<String>,
+    pub tick_array_lower: Option<String>,
+    pub tick_array_upper: Option<String>,
+    pub token_vault_a: Option<String>,
+    pub token_vault_b: Option<String>,
+    pub tick_lower_index: Option<i32>,
+    pub tick_upper_index: Option<i32>,
+    pub liquidity: Option<u128>,
+}
+
+pub struct OrcaWhirlpoolLiquidityParser {
+    adapter: TransactionAdapter,
+    transfer_actions: TransferMap,
+    classified_instructions: Vec<ClassifiedInstruction>,
+}
+
+impl OrcaWhirlpoolLiquidityParser {
+    pub fn new(
+        adapter: TransactionAdapter,
+        transfer_actions: TransferMap,
+        classified_instructions: Vec<ClassifiedInstruction>,
+    ) -> Self {
+        Self {
+            adapter,
+            transfer_actions,
+            classified_instructions,
+        }
+    }
+
+    /// Decode a position lifecycle instruction into its data and the pool event type it maps to.
+    fn decode_instruction(
+        accounts: &[String],
+        data: &[u8],
+    ) -> Result<Option<(WhirlpoolPositionInstruction, TradeType)>, OrcaError> {
+        let (discriminator, payload) = data.split_at(8);
+        let mut reader = BinaryReader::new(payload.to_vec());
+        let decoded = if discriminator == whirlpool_instructions::OPEN_POSITION {
+            let _position_bump = reader.read_u8()?;
+            (
+                Self::decode_open_position(accounts, &OPEN_POSITION_ACCOUNTS, &mut reader)?,
+                TradeType::Add,
+            )
+        } else if discriminator == whirlpool_instructions::OPEN_POSITION_WITH_METADATA {
+            let _position_bump = reader.read_u8()?;
+            let _metadata_bump = reader.read_u8()?;
+            (
+                Self::decode_open_position(
+                    accounts,
+                    &OPEN_POSITION_WITH_METADATA_ACCOUNTS,
+                    &mut reader,
+                )?,
+                TradeType::Add,
+            )
+        } else if discriminator == whirlpool_instructions::INCREASE_LIQUIDITY {
+            (
+                Self::decode_liquidity_change(accounts, &MODIFY_LIQUIDITY_ACCOUNTS, &mut reader)?,
+                TradeType::Add,
+            )
+        } else if discriminator == whirlpool_instructions::INCREASE_LIQUIDITY_V2 {
+            (
+                Self::decode_liquidity_change(
+                    accounts,
+                    &MODIFY_LIQUIDITY_V2_ACCOUNTS,
+                    &mut reader,
+                )?,
+                TradeType::Add,
+            )
+        } else if discriminator == whirlpool_instructions::DECREASE_LIQUIDITY {
+            (
+                Self::decode_liquidity_change(accounts, &MODIFY_LIQUIDITY_ACCOUNTS, &mut reader)?,
+                TradeType::Remove,
+            )
+        } else if discriminator == whirlpool_instructions::DECREASE_LIQUIDITY_V2 {
+            (
+                Self::decode_liquidity_change(
+                    accounts,
+                    &MODIFY_LIQUIDITY_V2_ACCOUNTS,
+                    &mut reader,
+                )?,
+                TradeType::Remove,
+            )
+        } else if discriminator == whirlpool_instructions::COLLECT_FEES {
+            (
+                Self::position_accounts(accounts, &COLLECT_FEES_ACCOUNTS),
+                TradeType::FeeClaim,
+            )
+        } else if discriminator == whirlpool_instructions::COLLECT_FEES_V2 {
+            (
+                Self::position_accounts(accounts, &COLLECT_FEES_V2_ACCOUNTS),
+                TradeType::FeeClaim,
+            )
+        } else if discriminator == whirlpool_instructions::CLOSE_POSITION {
+            (
+                Self::position_accounts(accounts, &CLOSE_POSITION_ACCOUNTS),
+                TradeType::Remove,
+            )
+        } else {
+            return Ok(None);
+        };
+        Ok(Some(decoded))
+    }
+
+    fn decode_open_position(
+        accounts: &[String],
+        positions: &PositionAccounts,
+        reader: &mut BinaryReader,
+    ) -> Result<WhirlpoolPositionInstruction, OrcaError> {
+        Ok(WhirlpoolPositionInstruction {
+            tick_lower_index: Some(reader.read_i32()?),
+            tick_upper_index: Some(reader.read_i32()?),
+            ..Self::position_accounts(accounts, positions)
+        })
+    }
+
+    fn decode_liquidity_change(
+        accounts: &[String],
+        positions: &PositionAccounts,
+        reader: &mut BinaryReader,
+    ) -> Result<WhirlpoolPositionInstruction, OrcaError> {
+        Ok(WhirlpoolPositionInstruction {
+            liquidity: Some(reader.read_u128()?),
+            ..Self::position_accounts(accounts, positions)
+        })
+    }
+
+    fn position_accounts(
+        accounts: &[String],
+        positions: &PositionAccounts,
+    ) -> WhirlpoolPositionInstruction {
+        let optional = |index: Option<usize>| index.and_then(|index| accounts.get(index).cloned());
+        WhirlpoolPositionInstruction {
+            owner: accounts.get(positions.owner).cloned().unwrap_or_default(),
+            whirlpool: optional(positions.whirlpool),
+            position: accounts
+                .get(positions.position)
+                .cloned()
+                .unwrap_or_default(),
+            position_mint: optional(positions.position_mint),
+            tick_array_lower: optional(positions.tick_array_lower),
+            tick_array_upper: optional(positions.tick_array_upper),
+            token_vault_a: optional(positions.token_vault_a),
+            token_vault_b: optional(positions.token_vault_b),
+            ..WhirlpoolPositionInstruction::default()
+        }
+    }
+
+    fn build_event(
+        &self,
+        classified: &ClassifiedInstruction,
+        data: &WhirlpoolPositionInstruction,
+        event_type: TradeType,
+        pool_id: String,
+        used: &mut HashSet<String>,
+    ) -> PoolEvent {
+        let matcher = TransferMatcher::new(&self.transfer_actions);
+        // Several position instructions may share an outer instruction; claim each transfer once.
+        let mut vault_transfer = |vault: &Option<String>| {
+            let vault = vault.as_deref()?;
+            let leg = if event_type == TradeType::Add {
+                Leg::default().to(vault)
+            } else {
+                Leg::default().from(vault)
+            };
+            let transfer = matcher
+                .find_legs(classified.outer_index, &leg)
+                .into_iter()
+                .find(|transfer| !used.contains(&transfer.idx))?;
+            used.insert(transfer.idx.clone());
+            Some(transfer)
+        };
+        let token_a = vault_transfer(&data.token_vault_a);
+        let token_b = vault_transfer(&data.token_vault_b);
+
+        let mut event = PoolEvent {
+            user: data.owner.clone(),
+            event_type,
+            program_id: Some(ORCA_WHIRLPOOL_PROGRAM_ID.to_string()),
+            amm: Some(ORCA_WHIRLPOOL_PROGRAM_NAME.to_string()),
+            slot: self.adapter.slot(),
+            timestamp: self.adapter.block_time(),
+            signature: self.adapter.shared_signature(),
+            idx: format!(
+                "{}-{}",
+                classified.outer_index,
+                classified.inner_index.unwrap_or(0)
+            ),
+            signer: Some(self.adapter.signers().to_vec()),
+            pool_id,
+            token0_mint: self.vault_mint(token_a, &data.token_vault_a),
+            token1_mint: self.vault_mint(token_b, &data.token_vault_b),
+            position: Some(PositionInfo {
+                position: data.position.clone(),
+                nft_mint: data.position_mint.clone(),
+                tick_lower_index: data.tick_lower_index,
+                tick_upper_index: data.tick_upper_index,
+                tick_array_lower: data.tick_array_lower.clone(),
+                tick_array_upper: data.tick_array_upper.clone(),
+                liquidity: data.liquidity.map(|liquidity| liquidity.to_string()),
+            }),
+            ..PoolEvent::default()
+        };
+        if let Some(transfer) = token_a {
+            let amount = &transfer.info.token_amount;
+            event.token0_amount = amount.ui_amount;
+            event.token0_amount_raw = Some(amount.amount.clone());
+            event.token0_decimals = Some(amount.decimals);
+        }
+        if let Some(transfer) = token_b {
+            let amount = &transfer.info.token_amount;
+            event.token1_amount = amount.ui_amount;
+            event.token1_amount_raw = Some(amount.amount.clone());
+            event.token1_decimals = Some(amount.decimals);
+        }
+        event
+    }
+
+    fn vault_mint(
+        &self,
+        transfer: Option<&TransferData>,
+        vault: &Option<String>,
+    ) -> Option<String> {
+        transfer
+            .map(|transfer| transfer.info.mint.clone())
+            .or_else(|| {
+                let vault = vault.as_deref()?;
+                self.adapter
+                    .spl_token_map()
+                    .get(vault)
+                    .map(|info| info.mint.clone())
+            })
+    }
+
+    fn parse_events(&self) -> Result<Vec<PoolEvent>, OrcaError> {
+        let mut ordered = self.classified_instructions.clone();
+        ordered.sort_by_key(|classified| {
+            (
+                classified.outer_index,
+                classified.inner_index.map_or(0, |index| index + 1),
+            )
+        });
+
+        let mut events: Vec<PoolEvent> = Vec::new();
+        let mut used = HashSet::new();
+        for classified in &ordered {
+            let data = decode_instruction_data(&classified.data.data)
+                .map_err(|err| OrcaError::instruction_data(err.to_string()))?;
+            if data.len() < 8 {
+                continue;
+            }
+            let Some((data, event_type)) =
+                Self::decode_instruction(&classified.data.accounts, &data)?
+            else {
+                continue;
+            };
+            // closePosition carries no whirlpool account; borrow it from an earlier op on the position.
+            let pool_id = data.whirlpool.clone().or_else(|| {
+                events
+                    .iter()
+                    .rev()
+                    .find(|event| {
+                        event
+                            .position
+                            .as_ref()
+                            .is_some_and(|position| position.position == data.position)
+                    })
+                    .map(|event| event.pool_id.clone())
+            });
+            let Some(pool_id) = pool_id else {
+                continue;
+            };
+            events.push(self.build_event(classified, &data, event_type, pool_id, &mut used));
+        }
+        Ok(events)
+    }
+}
+
+impl LiquidityParser for OrcaWhirlpoolLiquidityParser {
+    fn process_liquidity(&mut self) -> Vec<PoolEvent> {
+        match self.parse_events() {
+            Ok(events) => events,
+            Err(err) => {
+                tracing::error!("failed to parse orca whirlpool liquidity instructions: {err}");
+                Vec::new()
+            }
+        }
+    }
+}
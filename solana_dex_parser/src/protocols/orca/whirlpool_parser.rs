@@ -0,0 +1,377 @@
+use crate::core::matcher::TransferMatcher;
+use crate::core::reserves::{sqrt_price_x64_to_price, vault_reserves};
+use crate::core::transaction_adapter::TransactionAdapter;
+use crate::protocols::pumpfun::binary_reader::BinaryReader;
+use crate::protocols::pumpfun::util::{
+    build_fee_info, build_token_info, decode_instruction_data, get_trade_type,
+};
+use crate::protocols::raydium::util::vault_swap_tokens;
+use crate::protocols::simple::TradeParser;
+use crate::types::{ClassifiedInstruction, DexInfo, FeeInfo, TokenInfo, TradeInfo, TransferMap};
+
+use super::constants::{
+    discriminators::{whirlpool_events, whirlpool_instructions},
+    ORCA_WHIRLPOOL_PROGRAM_ID, ORCA_WHIRLPOOL_PROGRAM_NAME,
+};
+use super::error::OrcaError;
+
+/// Account positions of a swap instruction; `None` when the instruction lacks the account.
+struct SwapAccounts {
+    token_authority: usize,
+    whirlpool: usize,
+    token_mint_a: Option<usize>,
+    token_mint_b: Option<usize>,
+    token_owner_account_a: usize,
+    token_vault_a: usize,
+    token_owner_account_b: usize,
+    token_vault_b: usize,
+}
+
+const SWAP_ACCOUNTS: SwapAccounts = SwapAccounts {
+    token_authority: 1,
+    whirlpool: 2,
+    token_mint_a: None,
+    token_mint_b: None,
+    token_owner_account_a: 3,
+    token_vault_a: 4,
+    token_owner_account_b: 5,
+    token_vault_b: 6,
+};
+
+const SWAP_V2_ACCOUNTS: SwapAccounts = SwapAccounts {
+    token_authority: 3,
+    whirlpool: 4,
+    token_mint_a: Some(5),
+    token_mint_b: Some(6),
+    token_owner_account_a: 7,
+    token_vault_a: 8,
+    token_owner_account_b: 9,
+    token_vault_b: 10,
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct WhirlpoolSwapInstruction {
+    pub token_authority: String,
+    pub whirlpool: String,
+    /// Only carried by `swapV2`; `swap` mints are resolved from the vaults.
+    pub token_mint_a: Option<String>,
+    pub token_mint_b: Option<String>,
+    pub token_owner_account_a: String,
+    pub token_vault_a: String,
+    pub token_owner_account_b: String,
+    pub token_vault_b: String,
+    pub amount: u64,
+    pub other_amount_threshold: u64,
+    pub amount_specified_is_input: bool,
+    pub a_to_b: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct WhirlpoolTradedEvent {
+    pub whirlpool: String,
+    pub a_to_b: bool,
+    pub pre_sqrt_price: u128,
+    pub post_sqrt_price: u128,
+    pub input_amount: u64,
+    pub output_amount: u64,
+    pub input_transfer_fee: u64,
+    pub output_transfer_fee: u64,
+    pub lp_fee: u64,
+    pub protocol_fee: u64,
+}
+
+/// Swap instruction together with the `Traded` event it emitted, if any.
+struct WhirlpoolSwap<'a> {
+    classified: &'a ClassifiedInstruction,
+    swap: WhirlpoolSwapInstruction,
+    traded: Option<WhirlpoolTradedEvent>,
+}
+
+pub struct OrcaWhirlpoolParser {
+    adapter: TransactionAdapter,
+    dex_info: DexInfo,
+    transfer_actions: TransferMap,
+    classified_instructions: Vec<ClassifiedInstruction>,
+}
+
+impl OrcaWhirlpoolParser {
+    pub fn new(
+        adapter: TransactionAdapter,
+        dex_info: DexInfo,
+        transfer_actions: TransferMap,
+        classified_instructions: Vec<ClassifiedInstruction>,
+    ) -> Self {
+        Self {
+            adapter,
+            dex_info,
+            transfer_actions,
+            classified_instructions,
+        }
+    }
+
+    /// Decode `swap`/`swapV2` instructions, pairing each with the next `Traded` event of the
+    /// same whirlpool under the same outer instruction.
+    fn parse_swaps<'a>(
+        &self,
+        ordered: &'a [ClassifiedInstruction],
+    ) -> Result<Vec<WhirlpoolSwap<'a>>, OrcaError> {
+        let mut swaps: Vec<WhirlpoolSwap<'a>> = Vec::new();
+        for classified in ordered {
+            let data = decode_instruction_data(&classified.data.data)
+                .map_err(|err| OrcaError::instruction_data(err.to_string()))?;
+            if data.len() < 8 {
+                continue;
+            }
+
+            let positions = if data[..8] == whirlpool_instructions::SWAP {
+                Some(&SWAP_ACCOUNTS)
+            } else if data[..8] == whirlpool_instructions::SWAP_V2 {
+                Some(&SWAP_V2_ACCOUNTS)
+            } else {
+                None
+            };
+            if let Some(positions) = positions {
+                swaps.push(WhirlpoolSwap {
+                    classified,
+                    swap: Self::decode_swap(&classified.data.accounts, positions, &data[8..])?,
+                    traded: None,
+                });
+                continue;
+            }
+
+            if data.len() >= 16 && data[..16] == whirlpool_events::TRADED {
+                let traded = Self::decode_traded_event(&data[16..])?;
+                if let Some(pending) = swaps.iter_mut().rev().find(|pending| {
+                    pending.traded.is_none()
+                        && pending.classified.outer_index == classified.outer_index
+                        && pending.swap.whirlpool == traded.whirlpool
+                }) {
+                    pending.traded = Some(traded);
+                }
+            }
+        }
+        Ok(swaps)
+    }
+
+    fn decode_swap(
+        accounts: &[String],
+        positions: &SwapAccounts,
+        payload: &[u8],
+    ) -> Result<WhirlpoolSwapInstruction, OrcaError> {
+        let account = |index: usize| accounts.get(index).cloned().unwrap_or_default();
+        let optional = |index: Option<usize>| index.and_then(|index| accounts.get(index).cloned());
+        let mut reader = BinaryReader::new(payload.to_vec());
+        let amount = reader.read_u64()?;
+        let other_amount_threshold = reader.read_u64()?;
+        let _sqrt_price_limit = reader.read_u128()?;
+        Ok(WhirlpoolSwapInstruction {
+            token_authority: account(positions.token_authority),
+            whirlpool: account(positions.whirlpool),
+            token_mint_a: optional(positions.token_mint_a),
+            token_mint_b: optional(positions.token_mint_b),
+            token_owner_account_a: account(positions.token_owner_account_a),
+            token_vault_a: account(positions.token_vault_a),
+            token_owner_account_b: account(positions.token_owner_account_b),
+            token_vault_b: account(positions.token_vault_b),
+            amount,
+            other_amount_threshold,
+            amount_specified_is_input: reader.read_bool()?,
+            a_to_b: reader.read_bool()?,
+        })
+    }
+
+    pub(crate) fn decode_traded_event(payload: &[u8]) -> Result<WhirlpoolTradedEvent, OrcaError> {
+        let mut reader = BinaryReader::new(payload.to_vec());
+        Ok(WhirlpoolTradedEvent {
+            whirlpool: reader.read_pubkey()?,
+            a_to_b: reader.read_bool()?,
+            pre_sqrt_price: reader.read_u128()?,
+            post_sqrt_price: reader.read_u128()?,
+            input_amount: reader.read_u64()?,
+            output_amount: reader.read_u64()?,
+            input_transfer_fee: reader.read_u64()?,
+            output_transfer_fee: reader.read_u64()?,
+            lp_fee: reader.read_u64()?,
+            protocol_fee: reader.read_u64()?,
+        })
+    }
+
+    /// Mint and decimals held by a pool vault, falling back to the mint named by `swapV2`.
+    fn vault_mint(&self, vault: &str, fallback: Option<&String>) -> Option<(String, u8)> {
+        if let Some(info) = self.adapter.spl_token_map().get(vault) {
+            return Some((info.mint.clone(), info.decimals));
+        }
+        let mint = fallback?;
+        let decimals = self.adapter.spl_decimals_map().get(mint).copied()?;
+        Some((mint.clone(), decimals))
+    }
+
+    /// Trade legs from the `Traded` event, with mints resolved from the whirlpool vaults.
+    fn event_tokens(
+        &self,
+        swap: &WhirlpoolSwapInstruction,
+        traded: &WhirlpoolTradedEvent,
+    ) -> Option<(TokenInfo, TokenInfo, Vec<FeeInfo>)> {
+        let side_a = (
+            self.vault_mint(&swap.token_vault_a, swap.token_mint_a.as_ref())?,
+            &swap.token_owner_account_a,
+            &swap.token_vault_a,
+        );
+        let side_b = (
+            self.vault_mint(&swap.token_vault_b, swap.token_mint_b.as_ref())?,
+            &swap.token_owner_account_b,
+            &swap.token_vault_b,
+        );
+        let (input_side, output_side) = if traded.a_to_b {
+            (side_a, side_b)
+        } else {
+            (side_b, side_a)
+        };
+
+        let ((input_mint, input_decimals), user_input, input_vault) = input_side;
+        let ((output_mint, output_decimals), user_output, output_vault) = output_side;
+        let input_token = TokenInfo {
+            source: Some(user_input.clone()),
+            destination: Some(input_vault.clone()),
+            authority: Some(swap.token_authority.clone()),
+            ..build_token_info(
+                &input_mint,
+                traded.input_amount as u128,
+                input_decimals,
+                None,
+            )
+        };
+        let output_token = TokenInfo {
+            source: Some(output_vault.clone()),
+            destination: Some(user_output.clone()),
+            ..build_token_info(
+                &output_mint,
+                traded.output_amount as u128,
+                output_decimals,
+                None,
+            )
+        };
+
+        let fees = [("lp", traded.lp_fee), ("protocol", traded.protocol_fee)]
+            .into_iter()
+            .filter(|(_, amount)| *amount > 0)
+            .map(|(fee_type, amount)| FeeInfo {
+                fee_type: Some(fee_type.to_string()),
+                ..build_fee_info(
+                    &input_mint,
+                    amount as u128,
+                    input_decimals,
+                    Some(ORCA_WHIRLPOOL_PROGRAM_NAME.to_string()),
+                )
+            })
+            .collect();
+        Some((input_token, output_token, fees))
+    }
+
+    /// Trade legs from the vault transfers when no `Traded` event was emitted.
+    fn transfer_tokens(
+        &self,
+        outer_index: usize,
+        swap: &WhirlpoolSwapInstruction,
+    ) -> Option<(TokenInfo, TokenInfo)> {
+        let matcher = TransferMatcher::new(&self.transfer_actions);
+        if swap.a_to_b {
+            vault_swap_tokens(
+                &matcher,
+                outer_index,
+                &swap.token_owner_account_a,
+                &swap.token_vault_a,
+                &swap.token_vault_b,
+                &swap.token_owner_account_b,
+            )
+        } else {
+            vault_swap_tokens(
+                &matcher,
+                outer_index,
+                &swap.token_owner_account_b,
+                &swap.token_vault_b,
+                &swap.token_vault_a,
+                &swap.token_owner_account_a,
+            )
+        }
+    }
+
+    fn build_trade(&self, swap: &WhirlpoolSwap) -> Option<TradeInfo> {
+        let classified = swap.classified;
+        let (input_token, output_token, fees) = match swap
+            .traded
+            .as_ref()
+            .and_then(|traded| self.event_tokens(&swap.swap, traded))
+        {
+            Some(tokens) => tokens,
+            None => {
+                let (input_token, output_token) =
+                    self.transfer_tokens(classified.outer_index, &swap.swap)?;
+                (input_token, output_token, Vec::new())
+            }
+        };
+        let price_after = swap.traded.as_ref().and_then(|traded| {
+            let (_, decimals_a) =
+                self.vault_mint(&swap.swap.token_vault_a, swap.swap.token_mint_a.as_ref())?;
+            let (_, decimals_b) =
+                self.vault_mint(&swap.swap.token_vault_b, swap.swap.token_mint_b.as_ref())?;
+            Some(sqrt_price_x64_to_price(
+                traded.post_sqrt_price,
+                decimals_a,
+                decimals_b,
+            ))
+        });
+
+        Some(TradeInfo {
+            trade_type: get_trade_type(&input_token.mint, &output_token.mint),
+            pool: vec![swap.swap.whirlpool.clone()],
+            input_token,
+            output_token,
+            fees,
+            user: Some(swap.swap.token_authority.clone()),
+            program_id: Some(ORCA_WHIRLPOOL_PROGRAM_ID.to_string()),
+            amm: Some(ORCA_WHIRLPOOL_PROGRAM_NAME.to_string()),
+            route: self.dex_info.route.clone(),
+            slot: self.adapter.slot(),
+            timestamp: self.adapter.block_time(),
+            signature: self.adapter.shared_signature(),
+            idx: format!(
+                "{}-{}",
+                classified.outer_index,
+                classified.inner_index.unwrap_or(0)
+            ),
+            signer: Some(self.adapter.signers().to_vec()),
+            pool_reserves: vault_reserves(
+                &self.adapter,
+                &swap.swap.token_vault_a,
+                &swap.swap.token_vault_b,
+            ),
+            price_after,
+            ..TradeInfo::default()
+        })
+    }
+}
+
+impl TradeParser for OrcaWhirlpoolParser {
+    fn process_trades(&mut self) -> Vec<TradeInfo> {
+        // `Traded` is emitted by self-CPI after the swap it describes.
+        let mut ordered = self.classified_instructions.clone();
+        ordered.sort_by_key(|classified| {
+            (
+                classified.outer_index,
+                classified.inner_index.map_or(0, |index| index + 1),
+            )
+        });
+
+        match self.parse_swaps(&ordered) {
+            Ok(swaps) => swaps
+                .iter()
+                .filter_map(|swap| self.build_trade(swap))
+                .collect(),
+            Err(err) => {
+                tracing::error!("failed to parse orca whirlpool instructions: {err}");
+                Vec::new()
+            }
+        }
+    }
+}
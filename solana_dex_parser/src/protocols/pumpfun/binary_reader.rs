@@ -40,6 +40,26 @@ impl BinaryReader {
         Ok(value)
     }
 
+    pub fn read_u32(&mut self) -> Result<u32, BinaryReaderError> {
+        self.check_bounds(4)?;
+        let mut cursor = Cursor::new(&self.buffer[self.offset..self.offset + 4]);
+        let value = cursor
+            .read_u32::<LittleEndian>()
+            .map_err(BinaryReaderError::Io)?;
+        self.offset += 4;
+        Ok(value)
+    }
+
+    pub fn read_i32(&mut self) -> Result<i32, BinaryReaderError> {
+        self.check_bounds(4)?;
+        let mut cursor = Cursor::new(&self.buffer[self.offset..self.offset + 4]);
+        let value = cursor
+            .read_i32::<LittleEndian>()
+            .map_err(BinaryReaderError::Io)?;
+        self.offset += 4;
+        Ok(value)
+    }
+
     pub fn read_u64(&mut self) -> Result<u64, BinaryReaderError> {
         self.check_bounds(8)?;
         let mut cursor = Cursor::new(&self.buffer[self.offset..self.offset + 8]);
@@ -60,6 +80,20 @@ impl BinaryReader {
         Ok(value)
     }
 
+    pub fn read_u128(&mut self) -> Result<u128, BinaryReaderError> {
+        self.check_bounds(16)?;
+        let mut cursor = Cursor::new(&self.buffer[self.offset..self.offset + 16]);
+        let value = cursor
+            .read_u128::<LittleEndian>()
+            .map_err(BinaryReaderError::Io)?;
+        self.offset += 16;
+        Ok(value)
+    }
+
+    pub fn read_bool(&mut self) -> Result<bool, BinaryReaderError> {
+        Ok(self.read_u8()? != 0)
+    }
+
     pub fn read_string(&mut self) -> Result<String, BinaryReaderError> {
         self.check_bounds(4)?;
         let mut cursor = Cursor::new(&self.buffer[self.offset..self.offset + 4]);
@@ -6,12 +6,18 @@ pub const PUMP_SWAP_PROGRAM_NAME: &str = "Pumpswap";
 
 pub const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
 
+/// Tokens a new bonding curve sells before it completes.
+pub const INITIAL_REAL_TOKEN_RESERVES: u64 = 793_100_000_000_000;
+/// Virtual token reserves of a new bonding curve; the excess over the real reserves never sells.
+pub const INITIAL_VIRTUAL_TOKEN_RESERVES: u64 = 1_073_000_000_000_000;
+
 pub mod discriminators {
     pub mod pumpfun_instructions {
         pub const CREATE: [u8; 8] = [24, 30, 200, 40, 5, 28, 7, 119];
         pub const MIGRATE: [u8; 8] = [155, 234, 231, 146, 236, 158, 162, 30];
         pub const BUY: [u8; 8] = [102, 6, 61, 18, 1, 218, 235, 234];
         pub const SELL: [u8; 8] = [51, 230, 133, 164, 1, 127, 131, 173];
+        pub const COLLECT_CREATOR_FEE: [u8; 8] = [20, 22, 86, 123, 198, 28, 219, 132];
     }
 
     pub mod pumpfun_events {
@@ -27,6 +33,21 @@ pub mod discriminators {
         pub const MIGRATE: [u8; 16] = [
             228, 69, 165, 46, 81, 203, 154, 29, 189, 233, 93, 185, 92, 148, 234, 148,
         ];
+        pub const SET_PARAMS: [u8; 16] = [
+            228, 69, 165, 46, 81, 203, 154, 29, 223, 195, 159, 246, 62, 48, 143, 131,
+        ];
+        pub const SET_CREATOR: [u8; 16] = [
+            228, 69, 165, 46, 81, 203, 154, 29, 237, 52, 123, 37, 245, 251, 72, 210,
+        ];
+        pub const ADMIN_SET_CREATOR: [u8; 16] = [
+            228, 69, 165, 46, 81, 203, 154, 29, 64, 69, 192, 104, 29, 30, 25, 107,
+        ];
+        pub const UPDATE_GLOBAL_AUTHORITY: [u8; 16] = [
+            228, 69, 165, 46, 81, 203, 154, 29, 182, 195, 137, 42, 35, 206, 207, 247,
+        ];
+        pub const COLLECT_CREATOR_FEE: [u8; 16] = [
+            228, 69, 165, 46, 81, 203, 154, 29, 122, 2, 127, 1, 14, 191, 12, 175,
+        ];
     }
 
     pub mod pumpswap_instructions {
@@ -35,6 +56,7 @@ pub mod discriminators {
         pub const REMOVE_LIQUIDITY: [u8; 8] = [183, 18, 70, 156, 148, 109, 161, 34];
         pub const BUY: [u8; 8] = [102, 6, 61, 18, 1, 218, 235, 234];
         pub const SELL: [u8; 8] = [51, 230, 133, 164, 1, 127, 131, 173];
+        pub const COLLECT_COIN_CREATOR_FEE: [u8; 8] = [160, 57, 89, 42, 181, 139, 43, 66];
     }
 
     pub mod pumpswap_events {
@@ -53,5 +75,8 @@ pub mod discriminators {
         pub const SELL: [u8; 16] = [
             228, 69, 165, 46, 81, 203, 154, 29, 62, 47, 55, 10, 165, 3, 220, 42,
         ];
+        pub const COLLECT_COIN_CREATOR_FEE: [u8; 16] = [
+            228, 69, 165, 46, 81, 203, 154, 29, 232, 245, 194, 238, 234, 218, 58, 89,
+        ];
     }
 }
@@ -68,6 +68,16 @@ pub fn build_pumpfun_meme_parser(
     ))
 }
 
+pub fn build_pumpswap_meme_parser(
+    adapter: TransactionAdapter,
+    transfer_actions: TransferMap,
+) -> Box<dyn MemeEventParser> {
+    Box::new(pumpswap_parser::PumpswapMemeParser::new(
+        adapter,
+        transfer_actions,
+    ))
+}
+
 pub fn build_pumpswap_transfer_parser(
     adapter: TransactionAdapter,
     dex_info: DexInfo,
@@ -1,17 +1,21 @@
+use std::sync::Arc;
+
 use bs58::encode as bs58_encode;
 
-use crate::types::{ClassifiedInstruction, MemeEvent, TradeType};
+use crate::types::{ClassifiedInstruction, MemeEvent, PoolReserves, TradeType};
 
 use super::binary_reader::BinaryReader;
 use super::constants::{
-    discriminators::pumpfun_events, PUMP_FUN_PROGRAM_NAME, PUMP_SWAP_PROGRAM_NAME, SOL_MINT,
+    discriminators::pumpfun_events, INITIAL_REAL_TOKEN_RESERVES, INITIAL_VIRTUAL_TOKEN_RESERVES,
+    PUMP_FUN_PROGRAM_NAME, PUMP_SWAP_PROGRAM_NAME, SOL_MINT,
 };
 use super::error::PumpfunError;
 use super::util::{
-    build_token_info, get_instruction_data, get_prev_instruction_by_index, get_trade_type,
-    sort_by_idx, HasIdx,
+    build_fee_claim_event, build_token_info, get_instruction_data, get_prev_instruction_by_index,
+    get_trade_type, sort_by_idx, HasIdx,
 };
 
+use crate::core::amounts::convert_to_ui_amount;
 use crate::core::transaction_adapter::TransactionAdapter;
 
 pub struct PumpfunEventParser {
@@ -44,6 +48,16 @@ impl PumpfunEventParser {
                 Some(self.decode_complete_event(payload)?)
             } else if discriminator == pumpfun_events::MIGRATE {
                 Some(self.decode_migrate_event(payload)?)
+            } else if discriminator == pumpfun_events::SET_PARAMS {
+                Some(self.decode_set_params_event(payload)?)
+            } else if discriminator == pumpfun_events::SET_CREATOR {
+                Some(self.decode_set_creator_event(payload)?)
+            } else if discriminator == pumpfun_events::ADMIN_SET_CREATOR {
+                Some(self.decode_admin_set_creator_event(payload)?)
+            } else if discriminator == pumpfun_events::UPDATE_GLOBAL_AUTHORITY {
+                Some(self.decode_update_global_authority_event(payload)?)
+            } else if discriminator == pumpfun_events::COLLECT_CREATOR_FEE {
+                Some(self.decode_collect_creator_fee_event(payload)?)
             } else {
                 None
             };
@@ -62,7 +76,7 @@ impl PumpfunEventParser {
                         }
                     }
                 }
-                meme_event.signature = self.adapter.signature().to_string();
+                meme_event.signature = self.adapter.shared_signature();
                 meme_event.slot = self.adapter.slot();
                 meme_event.timestamp = self.adapter.block_time();
                 meme_event.idx = format!(
@@ -86,16 +100,29 @@ impl PumpfunEventParser {
         let is_buy = reader.read_u8()? == 1;
         let user = bs58_encode(reader.read_fixed_array(32)?).into_string();
         let _event_timestamp = reader.read_i64()?;
-        let _virtual_sol = reader.read_u64()?;
-        let _virtual_token = reader.read_u64()?;
+        let virtual_sol = reader.read_u64()?;
+        let virtual_token = reader.read_u64()?;
+        // The curve prices trades off its virtual reserves.
+        let pool_reserves = PoolReserves {
+            base_mint: mint.clone(),
+            base_reserve: virtual_token.to_string(),
+            base_decimals: 6,
+            quote_mint: quote_mint.clone(),
+            quote_reserve: virtual_sol.to_string(),
+            quote_decimals: 9,
+        };
 
         let mut fee = None;
         let mut creator = None;
         let mut creator_fee = None;
+        // Older events only carry the virtual reserves, which run ahead of the real ones
+        // by a fixed amount.
+        let mut real_token_reserves = virtual_token
+            .saturating_sub(INITIAL_VIRTUAL_TOKEN_RESERVES - INITIAL_REAL_TOKEN_RESERVES);
 
         if reader.remaining() >= 52 {
             let _real_sol_reserves = reader.read_u64()?;
-            let _real_token_reserves = reader.read_u64()?;
+            real_token_reserves = reader.read_u64()?;
             let _fee_recipient = reader.read_pubkey()?;
             let _fee_basis_points = reader.read_u16()?;
             let raw_fee = reader.read_u64()?;
@@ -136,7 +163,7 @@ impl PumpfunEventParser {
             timestamp: 0,
             idx: String::new(),
             slot: 0,
-            signature: String::new(),
+            signature: Arc::default(),
             user: user.clone(),
             base_mint: mint,
             quote_mint,
@@ -161,6 +188,17 @@ impl PumpfunEventParser {
             pool_a_reserve: None,
             pool_b_reserve: None,
             pool_fee_rate: None,
+            mint_authority_renounced: None,
+            freeze_authority_renounced: None,
+            fee_shares: Vec::new(),
+            fee_basis_points: None,
+            creator_fee_basis_points: None,
+            previous_creator: None,
+            new_authority: None,
+            bonding_curve_progress: Some(bonding_curve_progress(real_token_reserves)),
+            pool_reserves: Some(pool_reserves),
+            price_usd: None,
+            volume_usd: None,
         };
 
         Ok(event)
@@ -196,7 +234,7 @@ impl PumpfunEventParser {
             timestamp,
             idx: String::new(),
             slot: 0,
-            signature: String::new(),
+            signature: Arc::default(),
             user,
             base_mint: mint,
             quote_mint: SOL_MINT.to_string(),
@@ -221,6 +259,17 @@ impl PumpfunEventParser {
             pool_a_reserve: None,
             pool_b_reserve: None,
             pool_fee_rate: None,
+            mint_authority_renounced: None,
+            freeze_authority_renounced: None,
+            fee_shares: Vec::new(),
+            fee_basis_points: None,
+            creator_fee_basis_points: None,
+            previous_creator: None,
+            new_authority: None,
+            bonding_curve_progress: None,
+            pool_reserves: None,
+            price_usd: None,
+            volume_usd: None,
         })
     }
 
@@ -237,7 +286,7 @@ impl PumpfunEventParser {
             timestamp,
             idx: String::new(),
             slot: 0,
-            signature: String::new(),
+            signature: Arc::default(),
             user,
             base_mint: mint,
             quote_mint: SOL_MINT.to_string(),
@@ -262,6 +311,17 @@ impl PumpfunEventParser {
             pool_a_reserve: None,
             pool_b_reserve: None,
             pool_fee_rate: None,
+            mint_authority_renounced: None,
+            freeze_authority_renounced: None,
+            fee_shares: Vec::new(),
+            fee_basis_points: None,
+            creator_fee_basis_points: None,
+            previous_creator: None,
+            new_authority: None,
+            bonding_curve_progress: None,
+            pool_reserves: None,
+            price_usd: None,
+            volume_usd: None,
         })
     }
 
@@ -269,8 +329,8 @@ impl PumpfunEventParser {
         let mut reader = BinaryReader::new(data);
         let user = bs58_encode(reader.read_fixed_array(32)?).into_string();
         let mint = bs58_encode(reader.read_fixed_array(32)?).into_string();
-        let _mint_amount = reader.read_u64()?;
-        let _sol_amount = reader.read_u64()?;
+        let mint_amount = reader.read_u64()?;
+        let sol_amount = reader.read_u64()?;
         let _pool_migrate_fee = reader.read_u64()? as u128;
         let bonding_curve = bs58_encode(reader.read_fixed_array(32)?).into_string();
         let ts = reader.read_i64()?;
@@ -282,7 +342,7 @@ impl PumpfunEventParser {
             timestamp,
             idx: String::new(),
             slot: 0,
-            signature: String::new(),
+            signature: Arc::default(),
             user,
             base_mint: mint,
             quote_mint: SOL_MINT.to_string(),
@@ -304,11 +364,144 @@ impl PumpfunEventParser {
             bonding_curve: Some(bonding_curve),
             pool: Some(pool.clone()),
             pool_dex: Some(PUMP_SWAP_PROGRAM_NAME.to_string()),
-            pool_a_reserve: None,
-            pool_b_reserve: None,
+            pool_a_reserve: Some(convert_to_ui_amount(mint_amount, 6)),
+            pool_b_reserve: Some(convert_to_ui_amount(sol_amount, 9)),
             pool_fee_rate: None,
+            mint_authority_renounced: None,
+            freeze_authority_renounced: None,
+            fee_shares: Vec::new(),
+            fee_basis_points: None,
+            creator_fee_basis_points: None,
+            previous_creator: None,
+            new_authority: None,
+            bonding_curve_progress: None,
+            pool_reserves: None,
+            price_usd: None,
+            volume_usd: None,
+        })
+    }
+
+    /// Global config change: reserves and supply for new curves, and the fee rates.
+    fn decode_set_params_event(&self, data: Vec<u8>) -> Result<MemeEvent, PumpfunError> {
+        let mut reader = BinaryReader::new(data);
+        let _initial_virtual_token_reserves = reader.read_u64()?;
+        let _initial_virtual_sol_reserves = reader.read_u64()?;
+        let _initial_real_token_reserves = reader.read_u64()?;
+        let _final_real_sol_reserves = reader.read_u64()?;
+        let token_total_supply = reader.read_u64()?;
+        let fee_basis_points = reader.read_u64()?;
+
+        // Creator fees were added to the event later; older logs end at the fee rate.
+        let mut creator_fee_basis_points = None;
+        let mut timestamp = 0;
+        if reader.remaining() >= 49 + 32 * 8 + 8 {
+            let _withdraw_authority = reader.read_pubkey()?;
+            let _enable_migrate = reader.read_u8()?;
+            let _pool_migration_fee = reader.read_u64()?;
+            creator_fee_basis_points = Some(reader.read_u64()?);
+            reader.read_fixed_array(32 * 8)?;
+            timestamp = reader.read_i64()?.max(0) as u64;
+        }
+
+        Ok(MemeEvent {
+            event_type: TradeType::SetParams,
+            timestamp,
+            user: self.adapter.signer(),
+            quote_mint: SOL_MINT.to_string(),
+            total_supply: Some(token_total_supply),
+            protocol: Some(PUMP_FUN_PROGRAM_NAME.to_string()),
+            fee_basis_points: Some(fee_basis_points),
+            creator_fee_basis_points,
+            ..MemeEvent::default()
+        })
+    }
+
+    fn decode_set_creator_event(&self, data: Vec<u8>) -> Result<MemeEvent, PumpfunError> {
+        let mut reader = BinaryReader::new(data);
+        let timestamp = reader.read_i64()?.max(0) as u64;
+        let mint = reader.read_pubkey()?;
+        let bonding_curve = reader.read_pubkey()?;
+        let creator = reader.read_pubkey()?;
+
+        Ok(MemeEvent {
+            event_type: TradeType::SetCreator,
+            timestamp,
+            user: self.adapter.signer(),
+            base_mint: mint,
+            quote_mint: SOL_MINT.to_string(),
+            protocol: Some(PUMP_FUN_PROGRAM_NAME.to_string()),
+            creator: Some(creator),
+            bonding_curve: Some(bonding_curve),
+            ..MemeEvent::default()
         })
     }
+
+    /// Creator reassignment by the admin authority, which also names the replaced creator.
+    fn decode_admin_set_creator_event(&self, data: Vec<u8>) -> Result<MemeEvent, PumpfunError> {
+        let mut reader = BinaryReader::new(data);
+        let timestamp = reader.read_i64()?.max(0) as u64;
+        let authority = reader.read_pubkey()?;
+        let mint = reader.read_pubkey()?;
+        let bonding_curve = reader.read_pubkey()?;
+        let old_creator = reader.read_pubkey()?;
+        let new_creator = reader.read_pubkey()?;
+
+        Ok(MemeEvent {
+            event_type: TradeType::SetCreator,
+            timestamp,
+            user: authority,
+            base_mint: mint,
+            quote_mint: SOL_MINT.to_string(),
+            protocol: Some(PUMP_FUN_PROGRAM_NAME.to_string()),
+            creator: Some(new_creator),
+            bonding_curve: Some(bonding_curve),
+            previous_creator: Some(old_creator),
+            ..MemeEvent::default()
+        })
+    }
+
+    fn decode_update_global_authority_event(
+        &self,
+        data: Vec<u8>,
+    ) -> Result<MemeEvent, PumpfunError> {
+        let mut reader = BinaryReader::new(data);
+        let _global = reader.read_pubkey()?;
+        let new_authority = reader.read_pubkey()?;
+        let timestamp = reader.read_i64()?.max(0) as u64;
+
+        Ok(MemeEvent {
+            event_type: TradeType::UpdateGlobalAuthority,
+            timestamp,
+            user: self.adapter.signer(),
+            quote_mint: SOL_MINT.to_string(),
+            protocol: Some(PUMP_FUN_PROGRAM_NAME.to_string()),
+            new_authority: Some(new_authority),
+            ..MemeEvent::default()
+        })
+    }
+
+    /// Creator fees accrued on the bonding curve, paid out in SOL from the creator vault.
+    fn decode_collect_creator_fee_event(&self, data: Vec<u8>) -> Result<MemeEvent, PumpfunError> {
+        let mut reader = BinaryReader::new(data);
+        let _timestamp = reader.read_i64()?;
+        let creator = reader.read_pubkey()?;
+        let creator_fee = reader.read_u64()?;
+
+        Ok(build_fee_claim_event(
+            &creator,
+            SOL_MINT,
+            creator_fee as u128,
+            9,
+            PUMP_FUN_PROGRAM_NAME,
+        ))
+    }
+}
+
+/// Percentage of the curve's sellable tokens already sold, given what is left of them.
+fn bonding_curve_progress(real_token_reserves: u64) -> f64 {
+    let remaining = real_token_reserves.min(INITIAL_REAL_TOKEN_RESERVES);
+    let sold = INITIAL_REAL_TOKEN_RESERVES - remaining;
+    sold as f64 * 100.0 / INITIAL_REAL_TOKEN_RESERVES as f64
 }
 
 impl HasIdx for MemeEvent {
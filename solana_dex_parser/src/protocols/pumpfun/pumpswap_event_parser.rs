@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use crate::core::transaction_adapter::TransactionAdapter;
 use crate::types::ClassifiedInstruction;
 
@@ -13,6 +15,7 @@ pub enum PumpswapEventType {
     Remove,
     Buy,
     Sell,
+    CollectCoinCreatorFee,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -21,7 +24,7 @@ pub struct PumpswapEvent {
     pub data: PumpswapEventData,
     pub slot: u64,
     pub timestamp: u64,
-    pub signature: String,
+    pub signature: Arc<str>,
     pub idx: String,
     pub signer: Option<Vec<String>>,
 }
@@ -33,6 +36,7 @@ pub enum PumpswapEventData {
     Create(PumpswapCreatePoolEvent),
     Deposit(PumpswapDepositEvent),
     Withdraw(PumpswapWithdrawEvent),
+    CollectCoinCreatorFee(PumpswapCollectCoinCreatorFeeEvent),
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -152,6 +156,15 @@ pub struct PumpswapWithdrawEvent {
     pub user_pool_token_account: String,
 }
 
+#[derive(Clone, Debug, PartialEq)]
+pub struct PumpswapCollectCoinCreatorFeeEvent {
+    pub timestamp: u64,
+    pub coin_creator: String,
+    pub coin_creator_fee: u64,
+    pub coin_creator_vault_ata: String,
+    pub coin_creator_token_account: String,
+}
+
 pub struct PumpswapEventParser {
     adapter: TransactionAdapter,
 }
@@ -184,6 +197,8 @@ impl PumpswapEventParser {
                 Some(PumpswapEventType::Buy)
             } else if discriminator == pumpswap_events::SELL {
                 Some(PumpswapEventType::Sell)
+            } else if discriminator == pumpswap_events::COLLECT_COIN_CREATOR_FEE {
+                Some(PumpswapEventType::CollectCoinCreatorFee)
             } else {
                 None
             };
@@ -195,7 +210,7 @@ impl PumpswapEventParser {
                     data,
                     slot: self.adapter.slot(),
                     timestamp: self.adapter.block_time(),
-                    signature: self.adapter.signature().to_string(),
+                    signature: self.adapter.shared_signature(),
                     idx: format!(
                         "{}-{}",
                         classified.outer_index,
@@ -227,6 +242,9 @@ impl PumpswapEventParser {
             PumpswapEventType::Remove => Ok(PumpswapEventData::Withdraw(
                 self.decode_remove_liquidity(data)?,
             )),
+            PumpswapEventType::CollectCoinCreatorFee => Ok(
+                PumpswapEventData::CollectCoinCreatorFee(self.decode_collect_creator_fee(data)?),
+            ),
         }
     }
 
@@ -365,6 +383,21 @@ impl PumpswapEventParser {
         })
     }
 
+    fn decode_collect_creator_fee(
+        &self,
+        data: Vec<u8>,
+    ) -> Result<PumpswapCollectCoinCreatorFeeEvent, PumpfunError> {
+        let mut reader = BinaryReader::new(data);
+        let timestamp = reader.read_i64()?;
+        Ok(PumpswapCollectCoinCreatorFeeEvent {
+            timestamp: normalize_timestamp(timestamp),
+            coin_creator: reader.read_pubkey()?,
+            coin_creator_fee: reader.read_u64()?,
+            coin_creator_vault_ata: reader.read_pubkey()?,
+            coin_creator_token_account: reader.read_pubkey()?,
+        })
+    }
+
     fn decode_remove_liquidity(
         &self,
         data: Vec<u8>,
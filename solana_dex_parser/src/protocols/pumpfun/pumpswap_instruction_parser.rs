@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use crate::core::transaction_adapter::TransactionAdapter;
 use crate::types::ClassifiedInstruction;
 
@@ -34,7 +36,7 @@ pub struct PumpswapInstruction {
     pub data: PumpswapInstructionData,
     pub slot: u64,
     pub timestamp: u64,
-    pub signature: String,
+    pub signature: Arc<str>,
     pub idx: String,
     pub signer: Vec<String>,
 }
@@ -81,7 +83,7 @@ impl PumpswapInstructionParser {
                     data,
                     slot: self.adapter.slot(),
                     timestamp: self.adapter.block_time(),
-                    signature: self.adapter.signature().to_string(),
+                    signature: self.adapter.shared_signature(),
                     idx: format!(
                         "{}-{}",
                         instruction.outer_index,
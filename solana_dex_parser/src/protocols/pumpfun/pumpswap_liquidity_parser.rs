@@ -7,7 +7,7 @@ use super::pumpswap_event_parser::{
     PumpswapCreatePoolEvent, PumpswapDepositEvent, PumpswapEvent, PumpswapEventData,
     PumpswapEventParser, PumpswapWithdrawEvent,
 };
-use super::util::convert_to_ui_amount;
+use crate::core::amounts::convert_to_ui_amount;
 
 pub struct PumpswapLiquidityParser {
     adapter: TransactionAdapter,
@@ -49,8 +49,12 @@ impl PumpswapLiquidityParser {
         event: &PumpswapEvent,
         data: &PumpswapCreatePoolEvent,
     ) -> PoolEvent {
+        let lp_decimals = self
+            .adapter
+            .token_decimals(&data.lp_mint)
+            .unwrap_or(data.base_mint_decimals);
         PoolEvent {
-            user: self.adapter.signer().cloned().unwrap_or_default(),
+            user: self.adapter.signer(),
             event_type: TradeType::Create,
             program_id: Some(PUMP_SWAP_PROGRAM_ID.to_string()),
             amm: Some(PUMP_SWAP_PROGRAM_NAME.to_string()),
@@ -80,9 +84,18 @@ impl PumpswapLiquidityParser {
             token1_decimals: Some(data.quote_mint_decimals),
             lp_amount: Some(convert_to_ui_amount(
                 data.lp_token_amount_out as u128,
-                data.base_mint_decimals,
+                lp_decimals,
             )),
             lp_amount_raw: Some(data.lp_token_amount_out.to_string()),
+            // Supply starts at the initial liquidity, of which the minimum is never paid out.
+            lp_supply: Some(convert_to_ui_amount(
+                data.initial_liquidity as u128,
+                lp_decimals,
+            )),
+            lp_supply_raw: Some(data.initial_liquidity.to_string()),
+            lp_burned: None,
+            lp_locked: None,
+            position: None,
         }
     }
 
@@ -115,7 +128,7 @@ impl PumpswapLiquidityParser {
             .unwrap_or(lp_info.decimals);
 
         Some(PoolEvent {
-            user: self.adapter.signer().cloned().unwrap_or_default(),
+            user: self.adapter.signer(),
             event_type: TradeType::Add,
             program_id: Some(PUMP_SWAP_PROGRAM_ID.to_string()),
             amm: Some(PUMP_SWAP_PROGRAM_NAME.to_string()),
@@ -148,6 +161,14 @@ impl PumpswapLiquidityParser {
                 lp_decimals,
             )),
             lp_amount_raw: Some(data.lp_token_amount_out.to_string()),
+            lp_supply: Some(convert_to_ui_amount(
+                data.lp_mint_supply as u128,
+                lp_decimals,
+            )),
+            lp_supply_raw: Some(data.lp_mint_supply.to_string()),
+            lp_burned: None,
+            lp_locked: None,
+            position: None,
         })
     }
 
@@ -180,7 +201,7 @@ impl PumpswapLiquidityParser {
             .unwrap_or(lp_info.decimals);
 
         Some(PoolEvent {
-            user: self.adapter.signer().cloned().unwrap_or_default(),
+            user: self.adapter.signer(),
             event_type: TradeType::Remove,
             program_id: Some(PUMP_SWAP_PROGRAM_ID.to_string()),
             amm: Some(PUMP_SWAP_PROGRAM_NAME.to_string()),
@@ -213,6 +234,14 @@ impl PumpswapLiquidityParser {
                 lp_decimals,
             )),
             lp_amount_raw: Some(data.lp_token_amount_in.to_string()),
+            lp_supply: Some(convert_to_ui_amount(
+                data.lp_mint_supply as u128,
+                lp_decimals,
+            )),
+            lp_supply_raw: Some(data.lp_mint_supply.to_string()),
+            lp_burned: None,
+            lp_locked: None,
+            position: None,
         })
     }
 }
@@ -1,9 +1,16 @@
+use crate::core::instruction_classifier::InstructionClassifier;
 use crate::core::transaction_adapter::TransactionAdapter;
-use crate::protocols::simple::TradeParser;
-use crate::types::{ClassifiedInstruction, DexInfo, TradeInfo, TransferMap};
+use crate::protocols::simple::{MemeEventParser, TradeParser};
+use crate::types::{ClassifiedInstruction, DexInfo, MemeEvent, TradeInfo, TransferMap};
 
-use super::pumpswap_event_parser::{PumpswapEvent, PumpswapEventData, PumpswapEventParser};
-use super::util::{attach_token_transfers, build_pumpswap_buy_trade, build_pumpswap_sell_trade};
+use super::constants::{PUMP_SWAP_PROGRAM_ID, PUMP_SWAP_PROGRAM_NAME, SOL_MINT};
+use super::pumpswap_event_parser::{
+    PumpswapCollectCoinCreatorFeeEvent, PumpswapEvent, PumpswapEventData, PumpswapEventParser,
+};
+use super::util::{
+    attach_token_transfers, build_fee_claim_event, build_pumpswap_buy_trade,
+    build_pumpswap_sell_trade,
+};
 
 pub struct PumpswapParser {
     adapter: TransactionAdapter,
@@ -43,6 +50,24 @@ impl PumpswapParser {
         }
     }
 
+    /// Report the configured fee recipient on protocol fees, for forks that collect elsewhere.
+    fn apply_fee_recipient(&self, mut trade: TradeInfo) -> TradeInfo {
+        let recipient = self
+            .dex_info
+            .program_id
+            .as_deref()
+            .and_then(|program_id| self.adapter.config().protocol(program_id))
+            .and_then(|overrides| overrides.fee_recipient.clone());
+        if let Some(recipient) = recipient {
+            trade
+                .fees
+                .iter_mut()
+                .filter(|fee| fee.fee_type.as_deref() == Some("protocol"))
+                .for_each(|fee| fee.recipient = Some(recipient.clone()));
+        }
+        trade
+    }
+
     fn create_buy_trade(
         &self,
         event: &PumpswapEvent,
@@ -82,7 +107,7 @@ impl PumpswapParser {
 
         Some(attach_token_transfers(
             &self.adapter,
-            trade,
+            self.apply_fee_recipient(trade),
             &self.transfer_actions,
         ))
     }
@@ -126,7 +151,7 @@ impl PumpswapParser {
 
         Some(attach_token_transfers(
             &self.adapter,
-            trade,
+            self.apply_fee_recipient(trade),
             &self.transfer_actions,
         ))
     }
@@ -153,3 +178,72 @@ impl TradeParser for PumpswapParser {
         trades
     }
 }
+
+pub struct PumpswapMemeParser {
+    adapter: TransactionAdapter,
+    _transfer_actions: TransferMap,
+}
+
+impl PumpswapMemeParser {
+    pub fn new(adapter: TransactionAdapter, transfer_actions: TransferMap) -> Self {
+        Self {
+            adapter,
+            _transfer_actions: transfer_actions,
+        }
+    }
+
+    fn build_fee_claim(
+        &self,
+        event: &PumpswapEvent,
+        data: &PumpswapCollectCoinCreatorFeeEvent,
+    ) -> MemeEvent {
+        let token_map = self.adapter.spl_token_map();
+        let mint = token_map
+            .get(&data.coin_creator_vault_ata)
+            .or_else(|| token_map.get(&data.coin_creator_token_account))
+            .map(|info| info.mint.clone())
+            .unwrap_or_else(|| SOL_MINT.to_string());
+        let decimals = self
+            .adapter
+            .spl_decimals_map()
+            .get(&mint)
+            .copied()
+            .unwrap_or(9);
+
+        let mut claim = build_fee_claim_event(
+            &data.coin_creator,
+            &mint,
+            data.coin_creator_fee as u128,
+            decimals,
+            PUMP_SWAP_PROGRAM_NAME,
+        );
+        claim.slot = event.slot;
+        claim.timestamp = event.timestamp;
+        claim.signature = event.signature.clone();
+        claim.idx = event.idx.clone();
+        claim
+    }
+}
+
+impl MemeEventParser for PumpswapMemeParser {
+    fn process_events(&mut self) -> Vec<MemeEvent> {
+        let classifier = InstructionClassifier::new(&self.adapter);
+        let instructions = classifier.get_instructions(PUMP_SWAP_PROGRAM_ID);
+        let parser = PumpswapEventParser::new(self.adapter.clone());
+        match parser.parse_instructions(&instructions) {
+            Ok(events) => events
+                .iter()
+                .filter_map(|event| match &event.data {
+                    PumpswapEventData::CollectCoinCreatorFee(data) => {
+                        Some(self.build_fee_claim(event, data))
+                    }
+                    _ => None,
+                })
+                .collect(),
+            Err(err) => {
+                tracing::error!("failed to parse pumpswap meme events: {err}");
+                Vec::new()
+            }
+        }
+    }
+}
@@ -1,80 +1,36 @@
-use base64::{engine::general_purpose::STANDARD as BASE64_STANDARD, Engine as _};
-use bs58::decode as bs58_decode;
 use serde::de::DeserializeOwned;
 
+use crate::core::amounts::convert_to_ui_amount;
+pub use crate::core::amounts::{build_fee_info, build_token_info};
+use crate::core::matcher::TransferMatcher;
 use crate::core::transaction_adapter::TransactionAdapter;
-use crate::types::{DexInfo, FeeInfo, MemeEvent, TokenInfo, TradeInfo, TradeType, TransferMap};
+use crate::core::util;
+pub use crate::core::util::{compare_idx, get_trade_type, sort_by_idx, HasIdx};
+use crate::types::{
+    DexInfo, FeeInfo, MemeEvent, PoolReserves, TokenInfo, TradeInfo, TradeStatus, TradeType,
+    TransferData, TransferMap,
+};
 
 use super::constants::{
     PUMP_FUN_PROGRAM_ID, PUMP_FUN_PROGRAM_NAME, PUMP_SWAP_PROGRAM_ID, PUMP_SWAP_PROGRAM_NAME,
-    SOL_MINT,
 };
 use super::error::PumpfunError;
 use super::pumpswap_event_parser::{
     PumpswapBuyEvent, PumpswapEvent, PumpswapEventData, PumpswapSellEvent,
 };
 
-pub fn convert_to_ui_amount(amount: impl Into<u128>, decimals: u8) -> f64 {
-    let value: u128 = amount.into();
-    if decimals == 0 {
-        return value as f64;
-    }
-    let scale = 10u128.pow(decimals as u32);
-    (value as f64) / (scale as f64)
-}
-
-pub fn get_trade_type(input_mint: &str, output_mint: &str) -> TradeType {
-    if input_mint == SOL_MINT {
-        TradeType::Buy
-    } else if output_mint == SOL_MINT {
-        TradeType::Sell
-    } else {
-        TradeType::Swap
-    }
-}
-
-pub fn sort_by_idx<T>(mut items: Vec<T>) -> Vec<T>
-where
-    T: HasIdx,
-{
-    items.sort_by(|a, b| compare_idx(a.idx(), b.idx()));
-    items
-}
-
-pub fn compare_idx(a: &str, b: &str) -> std::cmp::Ordering {
-    let (a_main, a_sub) = parse_idx(a);
-    let (b_main, b_sub) = parse_idx(b);
-    a_main.cmp(&b_main).then_with(|| a_sub.cmp(&b_sub))
-}
-
-fn parse_idx(value: &str) -> (u64, u64) {
-    let mut parts = value.split('-');
-    let main = parts
-        .next()
-        .and_then(|p| p.parse::<u64>().ok())
-        .unwrap_or_default();
-    let sub = parts
-        .next()
-        .and_then(|p| p.parse::<u64>().ok())
-        .unwrap_or_default();
-    (main, sub)
-}
-
-pub trait HasIdx {
-    fn idx(&self) -> &str;
+/// Slippage a swap allowed, in basis points of the executed amount: how far the instruction's
+/// `limit` (minimum out or maximum in) sits from what `executed`. `None` when nothing moved.
+pub fn slippage_bps(executed: impl Into<u128>, limit: impl Into<u128>) -> Option<u64> {
+    let executed: u128 = executed.into();
+    (executed > 0).then(|| {
+        let bps = executed.abs_diff(limit.into()).saturating_mul(10_000) / executed;
+        u64::try_from(bps).unwrap_or(u64::MAX)
+    })
 }
 
 pub fn decode_instruction_data(data: &str) -> Result<Vec<u8>, PumpfunError> {
-    if data.is_empty() {
-        return Ok(Vec::new());
-    }
-    if let Ok(decoded) = bs58_decode(data).into_vec() {
-        return Ok(decoded);
-    }
-    if let Ok(decoded) = BASE64_STANDARD.decode(data) {
-        return Ok(decoded);
-    }
-    Ok(data.as_bytes().to_vec())
+    Ok(util::decode_instruction_data(data))
 }
 
 pub fn get_instruction_data(
@@ -102,22 +58,20 @@ pub fn get_prev_instruction_by_index(
         })
 }
 
+/// Attach the transfers that settled `trade`, paired by [`TransferMatcher`]: the user
+/// defaults to the paying account and each leg gains its accounts and balances.
 pub fn attach_token_transfers(
     adapter: &TransactionAdapter,
     mut trade: TradeInfo,
     transfers: &TransferMap,
 ) -> TradeInfo {
-    if let Some(program_id) = trade.program_id.clone() {
-        if let Some(entries) = transfers.get(&program_id) {
-            if let Some(transfer) = entries.iter().find(|entry| {
-                entry.info.mint == trade.input_token.mint
-                    && entry.info.token_amount.amount == trade.input_token.amount_raw
-            }) {
-                trade
-                    .user
-                    .get_or_insert_with(|| transfer.info.source.clone());
-            }
-        }
+    let legs = TransferMatcher::new(transfers).match_trade(&trade);
+    if let Some(input) = legs.input {
+        trade.user.get_or_insert_with(|| input.info.source.clone());
+        attach_transfer_accounts(&mut trade.input_token, input);
+    }
+    if let Some(output) = legs.output {
+        attach_transfer_accounts(&mut trade.output_token, output);
     }
 
     if trade.signer.is_none() {
@@ -127,40 +81,45 @@ pub fn attach_token_transfers(
     trade
 }
 
-pub fn build_fee_info(mint: &str, amount: u128, decimals: u8, dex: Option<String>) -> FeeInfo {
-    FeeInfo {
-        mint: mint.to_string(),
-        amount: convert_to_ui_amount(amount, decimals),
-        amount_raw: amount.to_string(),
-        decimals,
-        dex,
-        fee_type: None,
-        recipient: None,
+/// Fill the account details `token` lacks from its transfer; decoded amounts are kept.
+fn attach_transfer_accounts(token: &mut TokenInfo, transfer: &TransferData) {
+    fn fill<T: Clone>(slot: &mut Option<T>, value: &Option<T>) {
+        if slot.is_none() {
+            *slot = value.clone();
+        }
     }
+    let info = &transfer.info;
+    token.source.get_or_insert_with(|| info.source.clone());
+    token
+        .destination
+        .get_or_insert_with(|| info.destination.clone());
+    fill(&mut token.authority, &info.authority);
+    fill(&mut token.destination_owner, &info.destination_owner);
+    fill(&mut token.source_balance, &info.source_balance);
+    fill(&mut token.source_pre_balance, &info.source_pre_balance);
+    fill(&mut token.destination_balance, &info.destination_balance);
+    fill(
+        &mut token.destination_pre_balance,
+        &info.destination_pre_balance,
+    );
 }
 
-pub fn build_token_info(
+/// Fee claim paid out to a creator or partner, shared by the launchpad parsers.
+pub fn build_fee_claim_event(
+    claimer: &str,
     mint: &str,
     amount: u128,
     decimals: u8,
-    _owner: Option<String>,
-) -> TokenInfo {
-    TokenInfo {
-        mint: mint.to_string(),
-        amount: convert_to_ui_amount(amount, decimals),
-        amount_raw: amount.to_string(),
-        decimals,
-        authority: None,
-        destination: None,
-        destination_owner: None,
-        destination_balance: None,
-        destination_pre_balance: None,
-        source: None,
-        source_balance: None,
-        source_pre_balance: None,
-        destination_balance_change: None,
-        source_balance_change: None,
-        balance_change: None,
+    protocol: &str,
+) -> MemeEvent {
+    MemeEvent {
+        event_type: TradeType::FeeClaim,
+        user: claimer.to_string(),
+        base_mint: mint.to_string(),
+        output_token: Some(build_token_info(mint, amount, decimals, None)),
+        creator: Some(claimer.to_string()),
+        protocol: Some(protocol.to_string()),
+        ..MemeEvent::default()
     }
 }
 
@@ -207,6 +166,22 @@ pub fn get_pumpfun_trade_info(
         signature: event.signature.clone(),
         idx: event.idx.clone(),
         signer: Some(adapter.signers().to_vec()),
+        volume_sol: None,
+        price_usd: None,
+        volume_usd: None,
+        routing_improvement_bps: None,
+        maker: None,
+        order: None,
+        dca: None,
+        pool_reserves: event.pool_reserves.clone(),
+        price_after: event.pool_reserves.as_ref().and_then(PoolReserves::price),
+        pool_state: None,
+        oracle_price: None,
+        ticks_crossed: None,
+        suspect: false,
+        suspected_wash: false,
+        status: TradeStatus::Success,
+        limit: None,
     }
 }
 
@@ -255,6 +230,22 @@ pub fn get_pumpswap_trade_info(
         signature: event.signature.clone(),
         idx: event.idx.clone(),
         signer: event.signer.clone(),
+        volume_sol: None,
+        price_usd: None,
+        volume_usd: None,
+        routing_improvement_bps: None,
+        maker: None,
+        order: None,
+        dca: None,
+        pool_reserves: None,
+        price_after: None,
+        pool_state: None,
+        oracle_price: None,
+        ticks_crossed: None,
+        suspect: false,
+        suspected_wash: false,
+        status: TradeStatus::Success,
+        limit: None,
     }
 }
 
@@ -303,7 +294,7 @@ pub fn build_pumpswap_buy_trade(
         recipient: None,
     };
 
-    get_pumpswap_trade_info(
+    let trade = get_pumpswap_trade_info(
         event,
         dex_info,
         (
@@ -315,7 +306,26 @@ pub fn build_pumpswap_buy_trade(
         fee_info,
         fees,
         buy.user.clone(),
-    )
+    );
+    let mut trade = with_pool_reserves(
+        trade,
+        PoolReserves {
+            base_mint: output_mint.to_string(),
+            base_reserve: buy
+                .pool_base_token_reserves
+                .saturating_sub(buy.base_amount_out)
+                .to_string(),
+            base_decimals: output_decimals,
+            quote_mint: input_mint.to_string(),
+            quote_reserve: (buy.pool_quote_token_reserves as u128
+                + buy.quote_amount_in as u128
+                + buy.lp_fee as u128)
+                .to_string(),
+            quote_decimals: input_decimals,
+        },
+    );
+    trade.slippage_bps = slippage_bps(buy.user_quote_amount_in, buy.max_quote_amount_in);
+    trade
 }
 
 pub fn build_pumpswap_sell_trade(
@@ -363,7 +373,7 @@ pub fn build_pumpswap_sell_trade(
         recipient: None,
     };
 
-    get_pumpswap_trade_info(
+    let trade = get_pumpswap_trade_info(
         event,
         dex_info,
         (input_mint, input_decimals, sell.base_amount_in as u128),
@@ -375,7 +385,33 @@ pub fn build_pumpswap_sell_trade(
         fee_info,
         fees,
         sell.user.clone(),
-    )
+    );
+    let mut trade = with_pool_reserves(
+        trade,
+        PoolReserves {
+            base_mint: input_mint.to_string(),
+            base_reserve: (sell.pool_base_token_reserves as u128 + sell.base_amount_in as u128)
+                .to_string(),
+            base_decimals: input_decimals,
+            quote_mint: output_mint.to_string(),
+            quote_reserve: (sell.pool_quote_token_reserves as u128 + sell.lp_fee as u128)
+                .saturating_sub(sell.quote_amount_out as u128)
+                .to_string(),
+            quote_decimals: output_decimals,
+        },
+    );
+    trade.slippage_bps = slippage_bps(sell.user_quote_amount_out, sell.min_quote_amount_out);
+    trade
+}
+
+/// Attach the pool reserves after a trade and the price they imply.
+///
+/// Pumpswap events report the reserves the trade was priced against, before it executed;
+/// callers pass them moved by the curve amounts, with the LP fee left in the pool.
+fn with_pool_reserves(mut trade: TradeInfo, reserves: PoolReserves) -> TradeInfo {
+    trade.price_after = reserves.price();
+    trade.pool_reserves = Some(reserves);
+    trade
 }
 
 pub fn parse_json_value<T: DeserializeOwned>(value: serde_json::Value) -> Result<T, PumpfunError> {
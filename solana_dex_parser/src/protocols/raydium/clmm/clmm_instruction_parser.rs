@@ -0,0 +1,304 @@
+use std::sync::Arc;
+
+use crate::core::transaction_adapter::TransactionAdapter;
+use crate::protocols::pumpfun::binary_reader::BinaryReader;
+use crate::protocols::pumpfun::util::{decode_instruction_data, sort_by_idx, HasIdx};
+use crate::protocols::raydium::error::RaydiumError;
+use crate::types::ClassifiedInstruction;
+
+use super::constants::discriminators::clmm_instructions;
+
+/// Account positions of a position instruction; `None` when the instruction lacks the account.
+struct PositionAccounts {
+    owner: usize,
+    nft_mint: Option<usize>,
+    pool: Option<usize>,
+    position: usize,
+    tick_array_lower: Option<usize>,
+    tick_array_upper: Option<usize>,
+    token_vault_0: Option<usize>,
+    token_vault_1: Option<usize>,
+}
+
+const OPEN_POSITION_ACCOUNTS: PositionAccounts = PositionAccounts {
+    owner: 1,
+    nft_mint: Some(2),
+    pool: Some(5),
+    position: 9,
+    tick_array_lower: Some(7),
+    tick_array_upper: Some(8),
+    token_vault_0: Some(12),
+    token_vault_1: Some(13),
+};
+
+const OPEN_POSITION_TOKEN22_ACCOUNTS: PositionAccounts = PositionAccounts {
+    owner: 1,
+    nft_mint: Some(2),
+    pool: Some(4),
+    position: 8,
+    tick_array_lower: Some(6),
+    tick_array_upper: Some(7),
+    token_vault_0: Some(11),
+    token_vault_1: Some(12),
+};
+
+const INCREASE_LIQUIDITY_ACCOUNTS: PositionAccounts = PositionAccounts {
+    owner: 0,
+    nft_mint: None,
+    pool: Some(2),
+    position: 4,
+    tick_array_lower: Some(5),
+    tick_array_upper: Some(6),
+    token_vault_0: Some(9),
+    token_vault_1: Some(10),
+};
+
+const DECREASE_LIQUIDITY_ACCOUNTS: PositionAccounts = PositionAccounts {
+    owner: 0,
+    nft_mint: None,
+    pool: Some(3),
+    position: 2,
+    tick_array_lower: Some(7),
+    tick_array_upper: Some(8),
+    token_vault_0: Some(5),
+    token_vault_1: Some(6),
+};
+
+const CLOSE_POSITION_ACCOUNTS: PositionAccounts = PositionAccounts {
+    owner: 0,
+    nft_mint: Some(1),
+    pool: None,
+    position: 3,
+    tick_array_lower: None,
+    tick_array_upper: None,
+    token_vault_0: None,
+    token_vault_1: None,
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClmmSwapInstruction {
+    pub payer: String,
+    pub pool: String,
+    pub input_token_account: String,
+    pub output_token_account: String,
+    pub input_vault: String,
+    pub output_vault: String,
+    pub amount: u64,
+    pub other_amount_threshold: u64,
+    pub is_base_input: bool,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ClmmPositionInstruction {
+    pub owner: String,
+    pub pool: Option<String>,
+    pub position: String,
+    pub nft_mint: Option<String>,
+    pub tick_array_lower: Option<String>,
+    pub tick_array_upper: Option<String>,
+    pub token_vault_0: Option<String>,
+    pub token_vault_1: Option<String>,
+    pub tick_lower_index: Option<i32>,
+    pub tick_upper_index: Option<i32>,
+    pub liquidity: Option<u128>,
+    /// Slippage bound on token 0: maximum deposited or minimum withdrawn.
+    pub amount_0_limit: Option<u64>,
+    pub amount_1_limit: Option<u64>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ClmmInstructionData {
+    Swap(ClmmSwapInstruction),
+    OpenPosition(ClmmPositionInstruction),
+    IncreaseLiquidity(ClmmPositionInstruction),
+    DecreaseLiquidity(ClmmPositionInstruction),
+    ClosePosition(ClmmPositionInstruction),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClmmInstruction {
+    pub data: ClmmInstructionData,
+    pub outer_index: usize,
+    pub inner_index: Option<usize>,
+    pub slot: u64,
+    pub timestamp: u64,
+    pub signature: Arc<str>,
+    pub idx: String,
+    pub signer: Vec<String>,
+}
+
+impl HasIdx for ClmmInstruction {
+    fn idx(&self) -> &str {
+        &self.idx
+    }
+}
+
+pub struct ClmmInstructionParser {
+    adapter: TransactionAdapter,
+}
+
+impl ClmmInstructionParser {
+    pub fn new(adapter: TransactionAdapter) -> Self {
+        Self { adapter }
+    }
+
+    pub fn parse_instructions(
+        &self,
+        instructions: &[ClassifiedInstruction],
+    ) -> Result<Vec<ClmmInstruction>, RaydiumError> {
+        let mut parsed = Vec::new();
+        for instruction in instructions {
+            let data = decode_instruction_data(&instruction.data.data)
+                .map_err(|err| RaydiumError::instruction_data(err.to_string()))?;
+            if data.len() < 8 {
+                continue;
+            }
+            let Some(data) = self.decode_instruction(instruction, &data[..8], &data[8..])? else {
+                continue;
+            };
+            parsed.push(ClmmInstruction {
+                data,
+                outer_index: instruction.outer_index,
+                inner_index: instruction.inner_index,
+                slot: self.adapter.slot(),
+                timestamp: self.adapter.block_time(),
+                signature: self.adapter.shared_signature(),
+                idx: format!(
+                    "{}-{}",
+                    instruction.outer_index,
+                    instruction.inner_index.unwrap_or(0)
+                ),
+                signer: self.adapter.signers().to_vec(),
+            });
+        }
+
+        Ok(sort_by_idx(parsed))
+    }
+
+    fn decode_instruction(
+        &self,
+        instruction: &ClassifiedInstruction,
+        discriminator: &[u8],
+        payload: &[u8],
+    ) -> Result<Option<ClmmInstructionData>, RaydiumError> {
+        let accounts = &instruction.data.accounts;
+        let mut reader = BinaryReader::new(payload.to_vec());
+        let data = if discriminator == clmm_instructions::SWAP
+            || discriminator == clmm_instructions::SWAP_V2
+        {
+            ClmmInstructionData::Swap(Self::decode_swap(accounts, &mut reader)?)
+        } else if discriminator == clmm_instructions::OPEN_POSITION
+            || discriminator == clmm_instructions::OPEN_POSITION_V2
+        {
+            ClmmInstructionData::OpenPosition(Self::decode_open_position(
+                accounts,
+                &OPEN_POSITION_ACCOUNTS,
+                &mut reader,
+            )?)
+        } else if discriminator == clmm_instructions::OPEN_POSITION_WITH_TOKEN22_NFT {
+            ClmmInstructionData::OpenPosition(Self::decode_open_position(
+                accounts,
+                &OPEN_POSITION_TOKEN22_ACCOUNTS,
+                &mut reader,
+            )?)
+        } else if discriminator == clmm_instructions::INCREASE_LIQUIDITY
+            || discriminator == clmm_instructions::INCREASE_LIQUIDITY_V2
+        {
+            ClmmInstructionData::IncreaseLiquidity(Self::decode_liquidity_change(
+                accounts,
+                &INCREASE_LIQUIDITY_ACCOUNTS,
+                &mut reader,
+            )?)
+        } else if discriminator == clmm_instructions::DECREASE_LIQUIDITY
+            || discriminator == clmm_instructions::DECREASE_LIQUIDITY_V2
+        {
+            ClmmInstructionData::DecreaseLiquidity(Self::decode_liquidity_change(
+                accounts,
+                &DECREASE_LIQUIDITY_ACCOUNTS,
+                &mut reader,
+            )?)
+        } else if discriminator == clmm_instructions::CLOSE_POSITION {
+            ClmmInstructionData::ClosePosition(Self::position_accounts(
+                accounts,
+                &CLOSE_POSITION_ACCOUNTS,
+            ))
+        } else {
+            return Ok(None);
+        };
+        Ok(Some(data))
+    }
+
+    fn decode_swap(
+        accounts: &[String],
+        reader: &mut BinaryReader,
+    ) -> Result<ClmmSwapInstruction, RaydiumError> {
+        let account = |index: usize| accounts.get(index).cloned().unwrap_or_default();
+        let amount = reader.read_u64()?;
+        let other_amount_threshold = reader.read_u64()?;
+        let _sqrt_price_limit_x64 = reader.read_u128()?;
+        Ok(ClmmSwapInstruction {
+            payer: account(0),
+            pool: account(2),
+            input_token_account: account(3),
+            output_token_account: account(4),
+            input_vault: account(5),
+            output_vault: account(6),
+            amount,
+            other_amount_threshold,
+            is_base_input: reader.read_bool()?,
+        })
+    }
+
+    fn decode_open_position(
+        accounts: &[String],
+        positions: &PositionAccounts,
+        reader: &mut BinaryReader,
+    ) -> Result<ClmmPositionInstruction, RaydiumError> {
+        let tick_lower_index = reader.read_i32()?;
+        let tick_upper_index = reader.read_i32()?;
+        let _tick_array_lower_start_index = reader.read_i32()?;
+        let _tick_array_upper_start_index = reader.read_i32()?;
+        Ok(ClmmPositionInstruction {
+            tick_lower_index: Some(tick_lower_index),
+            tick_upper_index: Some(tick_upper_index),
+            liquidity: Some(reader.read_u128()?),
+            amount_0_limit: Some(reader.read_u64()?),
+            amount_1_limit: Some(reader.read_u64()?),
+            ..Self::position_accounts(accounts, positions)
+        })
+    }
+
+    fn decode_liquidity_change(
+        accounts: &[String],
+        positions: &PositionAccounts,
+        reader: &mut BinaryReader,
+    ) -> Result<ClmmPositionInstruction, RaydiumError> {
+        Ok(ClmmPositionInstruction {
+            liquidity: Some(reader.read_u128()?),
+            amount_0_limit: Some(reader.read_u64()?),
+            amount_1_limit: Some(reader.read_u64()?),
+            ..Self::position_accounts(accounts, positions)
+        })
+    }
+
+    fn position_accounts(
+        accounts: &[String],
+        positions: &PositionAccounts,
+    ) -> ClmmPositionInstruction {
+        let optional = |index: Option<usize>| index.and_then(|index| accounts.get(index).cloned());
+        ClmmPositionInstruction {
+            owner: accounts.get(positions.owner).cloned().unwrap_or_default(),
+            pool: optional(positions.pool),
+            position: accounts
+                .get(positions.position)
+                .cloned()
+                .unwrap_or_default(),
+            nft_mint: optional(positions.nft_mint),
+            tick_array_lower: optional(positions.tick_array_lower),
+            tick_array_upper: optional(positions.tick_array_upper),
+            token_vault_0: optional(positions.token_vault_0),
+            token_vault_1: optional(positions.token_vault_1),
+            ..ClmmPositionInstruction::default()
+        }
+    }
+}
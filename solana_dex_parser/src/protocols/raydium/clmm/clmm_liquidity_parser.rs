@@ -0,0 +1,153 @@
+use crate::core::matcher::{Leg, TransferMatcher};
+use crate::core::transaction_adapter::TransactionAdapter;
+use crate::protocols::simple::LiquidityParser;
+use crate::types::{
+    ClassifiedInstruction, PoolEvent, PositionInfo, TradeType, TransferData, TransferMap,
+};
+
+use super::clmm_instruction_parser::{
+    ClmmInstruction, ClmmInstructionData, ClmmInstructionParser, ClmmPositionInstruction,
+};
+use super::constants::{RAYDIUM_CLMM_PROGRAM_ID, RAYDIUM_CLMM_PROGRAM_NAME};
+
+pub struct RaydiumClmmLiquidityParser {
+    adapter: TransactionAdapter,
+    transfer_actions: TransferMap,
+    classified_instructions: Vec<ClassifiedInstruction>,
+    instruction_parser: ClmmInstructionParser,
+}
+
+impl RaydiumClmmLiquidityParser {
+    pub fn new(
+        adapter: TransactionAdapter,
+        transfer_actions: TransferMap,
+        classified_instructions: Vec<ClassifiedInstruction>,
+    ) -> Self {
+        let instruction_parser = ClmmInstructionParser::new(adapter.clone());
+        Self {
+            adapter,
+            transfer_actions,
+            classified_instructions,
+            instruction_parser,
+        }
+    }
+
+    fn build_event(
+        &self,
+        instruction: &ClmmInstruction,
+        data: &ClmmPositionInstruction,
+        event_type: TradeType,
+        pool_id: String,
+    ) -> PoolEvent {
+        let matcher = TransferMatcher::new(&self.transfer_actions);
+        let vault_transfer = |vault: &Option<String>| {
+            let vault = vault.as_deref()?;
+            let leg = if event_type == TradeType::Add {
+                Leg::default().to(vault)
+            } else {
+                Leg::default().from(vault)
+            };
+            matcher.find_leg(instruction.outer_index, &leg)
+        };
+        let token0 = vault_transfer(&data.token_vault_0);
+        let token1 = vault_transfer(&data.token_vault_1);
+
+        let mut event = PoolEvent {
+            user: data.owner.clone(),
+            event_type,
+            program_id: Some(RAYDIUM_CLMM_PROGRAM_ID.to_string()),
+            amm: Some(RAYDIUM_CLMM_PROGRAM_NAME.to_string()),
+            slot: instruction.slot,
+            timestamp: instruction.timestamp,
+            signature: instruction.signature.clone(),
+            idx: instruction.idx.clone(),
+            signer: Some(instruction.signer.clone()),
+            pool_id,
+            token0_mint: self.vault_mint(token0, &data.token_vault_0),
+            token1_mint: self.vault_mint(token1, &data.token_vault_1),
+            position: Some(PositionInfo {
+                position: data.position.clone(),
+                nft_mint: data.nft_mint.clone(),
+                tick_lower_index: data.tick_lower_index,
+                tick_upper_index: data.tick_upper_index,
+                tick_array_lower: data.tick_array_lower.clone(),
+                tick_array_upper: data.tick_array_upper.clone(),
+                liquidity: data.liquidity.map(|liquidity| liquidity.to_string()),
+            }),
+            ..PoolEvent::default()
+        };
+        if let Some(transfer) = token0 {
+            let amount = &transfer.info.token_amount;
+            event.token0_amount = amount.ui_amount;
+            event.token0_amount_raw = Some(amount.amount.clone());
+            event.token0_decimals = Some(amount.decimals);
+        }
+        if let Some(transfer) = token1 {
+            let amount = &transfer.info.token_amount;
+            event.token1_amount = amount.ui_amount;
+            event.token1_amount_raw = Some(amount.amount.clone());
+            event.token1_decimals = Some(amount.decimals);
+        }
+        event
+    }
+
+    fn vault_mint(
+        &self,
+        transfer: Option<&TransferData>,
+        vault: &Option<String>,
+    ) -> Option<String> {
+        transfer
+            .map(|transfer| transfer.info.mint.clone())
+            .or_else(|| {
+                let vault = vault.as_deref()?;
+                self.adapter
+                    .spl_token_map()
+                    .get(vault)
+                    .map(|info| info.mint.clone())
+            })
+    }
+}
+
+impl LiquidityParser for RaydiumClmmLiquidityParser {
+    fn process_liquidity(&mut self) -> Vec<PoolEvent> {
+        let instructions = match self
+            .instruction_parser
+            .parse_instructions(&self.classified_instructions)
+        {
+            Ok(instructions) => instructions,
+            Err(err) => {
+                tracing::error!("failed to parse raydium clmm liquidity instructions: {err}");
+                return Vec::new();
+            }
+        };
+
+        let mut events: Vec<PoolEvent> = Vec::new();
+        for instruction in &instructions {
+            let (data, event_type) = match &instruction.data {
+                ClmmInstructionData::OpenPosition(data)
+                | ClmmInstructionData::IncreaseLiquidity(data) => (data, TradeType::Add),
+                ClmmInstructionData::DecreaseLiquidity(data)
+                | ClmmInstructionData::ClosePosition(data) => (data, TradeType::Remove),
+                ClmmInstructionData::Swap(_) => continue,
+            };
+            // closePosition carries no pool account; borrow it from an earlier op on the position.
+            let pool_id = data.pool.clone().or_else(|| {
+                events
+                    .iter()
+                    .rev()
+                    .find(|event| {
+                        event
+                            .position
+                            .as_ref()
+                            .is_some_and(|position| position.position == data.position)
+                    })
+                    .map(|event| event.pool_id.clone())
+            });
+            let Some(pool_id) = pool_id else {
+                continue;
+            };
+            events.push(self.build_event(instruction, data, event_type, pool_id));
+        }
+        events
+    }
+}
@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+
+use crate::core::cpi::program_data;
+use crate::core::matcher::TransferMatcher;
+use crate::core::reserves::{sqrt_price_x64_to_price, vault_reserves};
+use crate::core::transaction_adapter::TransactionAdapter;
+use crate::protocols::pumpfun::binary_reader::BinaryReader;
+use crate::protocols::pumpfun::util::get_trade_type;
+use crate::protocols::raydium::util::{swap_slippage_bps, vault_swap_tokens};
+use crate::protocols::simple::TradeParser;
+use crate::types::{ClassifiedInstruction, DexInfo, TradeInfo, TransferMap};
+
+use super::clmm_instruction_parser::{
+    ClmmInstruction, ClmmInstructionData, ClmmInstructionParser, ClmmSwapInstruction,
+};
+use super::constants::{
+    discriminators::clmm_events, RAYDIUM_CLMM_PROGRAM_ID, RAYDIUM_CLMM_PROGRAM_NAME,
+};
+
+/// Initialized ticks crossed by the swap that logged `events`: one `LiquidityChangeEvent`
+/// per tick ahead of its `SwapEvent`. `None` when the swap logged no `SwapEvent`.
+fn ticks_crossed(events: &[Vec<u8>]) -> Option<u32> {
+    events
+        .iter()
+        .any(|event| event.starts_with(&clmm_events::SWAP))
+        .then(|| {
+            events
+                .iter()
+                .filter(|event| event.starts_with(&clmm_events::LIQUIDITY_CHANGE))
+                .count() as u32
+        })
+}
+
+/// Direction and Q64.64 square-root price after the swap that logged `events`, read from its
+/// `SwapEvent`.
+fn swap_price(events: &[Vec<u8>]) -> Option<(bool, u128)> {
+    let event = events
+        .iter()
+        .find(|event| event.starts_with(&clmm_events::SWAP))?;
+    // Discriminator, pool, sender, both token accounts, both amounts and transfer fees.
+    let mut reader = BinaryReader::new(event.get(8 + 4 * 32 + 4 * 8..)?.to_vec());
+    let zero_for_one = reader.read_bool().ok()?;
+    let sqrt_price_x64 = reader.read_u128().ok()?;
+    Some((zero_for_one, sqrt_price_x64))
+}
+
+pub struct RaydiumClmmParser {
+    adapter: TransactionAdapter,
+    dex_info: DexInfo,
+    transfer_actions: TransferMap,
+    classified_instructions: Vec<ClassifiedInstruction>,
+    instruction_parser: ClmmInstructionParser,
+}
+
+impl RaydiumClmmParser {
+    pub fn new(
+        adapter: TransactionAdapter,
+        dex_info: DexInfo,
+        transfer_actions: TransferMap,
+        classified_instructions: Vec<ClassifiedInstruction>,
+    ) -> Self {
+        Self {
+            adapter: adapter.clone(),
+            dex_info,
+            transfer_actions,
+            classified_instructions,
+            instruction_parser: ClmmInstructionParser::new(adapter),
+        }
+    }
+
+    /// Build a trade from the vault transfers of a swap; `None` if they are missing.
+    fn build_trade(
+        &self,
+        instruction: &ClmmInstruction,
+        swap: &ClmmSwapInstruction,
+        logged: &HashMap<(usize, Option<usize>), Vec<Vec<u8>>>,
+    ) -> Option<TradeInfo> {
+        let matcher = TransferMatcher::new(&self.transfer_actions);
+        let (input_token, output_token) = vault_swap_tokens(
+            &matcher,
+            instruction.outer_index,
+            &swap.input_token_account,
+            &swap.input_vault,
+            &swap.output_vault,
+            &swap.output_token_account,
+        )?;
+        let events = logged.get(&(instruction.outer_index, instruction.inner_index));
+        let swapped = events.and_then(|events| swap_price(events));
+        // Token 0 is the input of a zero-for-one swap.
+        let (token_0, token_1) = match swapped {
+            Some((false, _)) => (&output_token, &input_token),
+            _ => (&input_token, &output_token),
+        };
+        let price_after = swapped.map(|(_, sqrt_price_x64)| {
+            sqrt_price_x64_to_price(sqrt_price_x64, token_0.decimals, token_1.decimals)
+        });
+        let pool_reserves = swapped.and_then(|(zero_for_one, _)| {
+            let (vault_0, vault_1) = if zero_for_one {
+                (&swap.input_vault, &swap.output_vault)
+            } else {
+                (&swap.output_vault, &swap.input_vault)
+            };
+            vault_reserves(&self.adapter, vault_0, vault_1)
+        });
+        let slippage_bps = swap_slippage_bps(
+            &input_token,
+            &output_token,
+            swap.is_base_input,
+            swap.other_amount_threshold,
+        );
+        Some(TradeInfo {
+            trade_type: get_trade_type(&input_token.mint, &output_token.mint),
+            pool: vec![swap.pool.clone()],
+            input_token,
+            output_token,
+            slippage_bps,
+            user: Some(swap.payer.clone()),
+            program_id: Some(RAYDIUM_CLMM_PROGRAM_ID.to_string()),
+            amm: Some(RAYDIUM_CLMM_PROGRAM_NAME.to_string()),
+            route: self.dex_info.route.clone(),
+            slot: instruction.slot,
+            timestamp: instruction.timestamp,
+            signature: instruction.signature.clone(),
+            idx: instruction.idx.clone(),
+            signer: Some(instruction.signer.clone()),
+            ticks_crossed: events.and_then(|events| ticks_crossed(events)),
+            pool_reserves,
+            price_after,
+            ..TradeInfo::default()
+        })
+    }
+}
+
+impl TradeParser for RaydiumClmmParser {
+    fn process_trades(&mut self) -> Vec<TradeInfo> {
+        let instructions = match self
+            .instruction_parser
+            .parse_instructions(&self.classified_instructions)
+        {
+            Ok(instructions) => instructions,
+            Err(err) => {
+                tracing::error!("failed to parse raydium clmm instructions: {err}");
+                return Vec::new();
+            }
+        };
+
+        let logged = program_data(&self.adapter, RAYDIUM_CLMM_PROGRAM_ID);
+        instructions
+            .iter()
+            .filter_map(|instruction| match &instruction.data {
+                ClmmInstructionData::Swap(swap) => self.build_trade(instruction, swap, &logged),
+                _ => None,
+            })
+            .collect()
+    }
+}
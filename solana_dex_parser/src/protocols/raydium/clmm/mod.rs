@@ -0,0 +1,37 @@
+pub mod clmm_instruction_parser;
+pub mod clmm_liquidity_parser;
+pub mod clmm_parser;
+pub mod constants;
+
+use crate::core::transaction_adapter::TransactionAdapter;
+use crate::protocols::simple::{LiquidityParser, TradeParser};
+use crate::types::{ClassifiedInstruction, DexInfo, TransferMap};
+
+use clmm_liquidity_parser::RaydiumClmmLiquidityParser;
+use clmm_parser::RaydiumClmmParser;
+
+pub fn build_raydium_clmm_trade_parser(
+    adapter: TransactionAdapter,
+    dex_info: DexInfo,
+    transfer_actions: TransferMap,
+    classified_instructions: Vec<ClassifiedInstruction>,
+) -> Box<dyn TradeParser> {
+    Box::new(RaydiumClmmParser::new(
+        adapter,
+        dex_info,
+        transfer_actions,
+        classified_instructions,
+    ))
+}
+
+pub fn build_raydium_clmm_liquidity_parser(
+    adapter: TransactionAdapter,
+    transfer_actions: TransferMap,
+    classified_instructions: Vec<ClassifiedInstruction>,
+) -> Box<dyn LiquidityParser> {
+    Box::new(RaydiumClmmLiquidityParser::new(
+        adapter,
+        transfer_actions,
+        classified_instructions,
+    ))
+}
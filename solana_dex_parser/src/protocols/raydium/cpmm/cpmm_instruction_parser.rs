@@ -0,0 +1,206 @@
+use std::sync::Arc;
+
+use crate::core::transaction_adapter::TransactionAdapter;
+use crate::protocols::pumpfun::binary_reader::BinaryReader;
+use crate::protocols::pumpfun::util::{decode_instruction_data, sort_by_idx, HasIdx};
+use crate::protocols::raydium::error::RaydiumError;
+use crate::types::ClassifiedInstruction;
+
+use super::constants::discriminators::cpmm_instructions;
+
+/// Account positions of `(owner, pool, lp_mint, mint_0, mint_1, vault_0, vault_1)`.
+type LiquidityAccounts = (usize, usize, usize, usize, usize, usize, usize);
+
+const INITIALIZE_ACCOUNTS: LiquidityAccounts = (0, 3, 6, 4, 5, 10, 11);
+const DEPOSIT_WITHDRAW_ACCOUNTS: LiquidityAccounts = (0, 2, 12, 10, 11, 6, 7);
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CpmmSwapInstruction {
+    pub payer: String,
+    pub pool: String,
+    pub input_token_account: String,
+    pub output_token_account: String,
+    pub input_vault: String,
+    pub output_vault: String,
+    pub input_mint: String,
+    pub output_mint: String,
+    /// `amount_in` for base-input swaps, `max_amount_in` for base-output swaps.
+    pub amount_in: u64,
+    /// `minimum_amount_out` for base-input swaps, `amount_out` for base-output swaps.
+    pub amount_out: u64,
+    pub is_base_input: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CpmmLiquidityInstruction {
+    pub owner: String,
+    pub pool: String,
+    pub lp_mint: String,
+    pub token_0_mint: String,
+    pub token_1_mint: String,
+    pub token_0_vault: String,
+    pub token_1_vault: String,
+    /// LP tokens minted or burned; `None` on pool initialization.
+    pub lp_amount: Option<u64>,
+    /// Initial deposit or the slippage bound of a deposit/withdraw.
+    pub amount_0: u64,
+    pub amount_1: u64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum CpmmInstructionData {
+    Swap(CpmmSwapInstruction),
+    Initialize(CpmmLiquidityInstruction),
+    Deposit(CpmmLiquidityInstruction),
+    Withdraw(CpmmLiquidityInstruction),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CpmmInstruction {
+    pub data: CpmmInstructionData,
+    pub outer_index: usize,
+    pub inner_index: Option<usize>,
+    pub slot: u64,
+    pub timestamp: u64,
+    pub signature: Arc<str>,
+    pub idx: String,
+    pub signer: Vec<String>,
+}
+
+impl HasIdx for CpmmInstruction {
+    fn idx(&self) -> &str {
+        &self.idx
+    }
+}
+
+pub struct CpmmInstructionParser {
+    adapter: TransactionAdapter,
+}
+
+impl CpmmInstructionParser {
+    pub fn new(adapter: TransactionAdapter) -> Self {
+        Self { adapter }
+    }
+
+    pub fn parse_instructions(
+        &self,
+        instructions: &[ClassifiedInstruction],
+    ) -> Result<Vec<CpmmInstruction>, RaydiumError> {
+        let mut parsed = Vec::new();
+        for instruction in instructions {
+            let data = decode_instruction_data(&instruction.data.data)
+                .map_err(|err| RaydiumError::instruction_data(err.to_string()))?;
+            if data.len() < 8 {
+                continue;
+            }
+            let Some(data) = Self::decode_instruction(instruction, &data[..8], &data[8..])? else {
+                continue;
+            };
+            parsed.push(CpmmInstruction {
+                data,
+                outer_index: instruction.outer_index,
+                inner_index: instruction.inner_index,
+                slot: self.adapter.slot(),
+                timestamp: self.adapter.block_time(),
+                signature: self.adapter.shared_signature(),
+                idx: format!(
+                    "{}-{}",
+                    instruction.outer_index,
+                    instruction.inner_index.unwrap_or(0)
+                ),
+                signer: self.adapter.signers().to_vec(),
+            });
+        }
+
+        Ok(sort_by_idx(parsed))
+    }
+
+    fn decode_instruction(
+        instruction: &ClassifiedInstruction,
+        discriminator: &[u8],
+        payload: &[u8],
+    ) -> Result<Option<CpmmInstructionData>, RaydiumError> {
+        let accounts = &instruction.data.accounts;
+        let mut reader = BinaryReader::new(payload.to_vec());
+        let data = if discriminator == cpmm_instructions::SWAP_BASE_INPUT
+            || discriminator == cpmm_instructions::SWAP_BASE_OUTPUT
+        {
+            let is_base_input = discriminator == cpmm_instructions::SWAP_BASE_INPUT;
+            CpmmInstructionData::Swap(Self::decode_swap(accounts, &mut reader, is_base_input)?)
+        } else if discriminator == cpmm_instructions::INITIALIZE {
+            let amount_0 = reader.read_u64()?;
+            let amount_1 = reader.read_u64()?;
+            CpmmInstructionData::Initialize(Self::liquidity_accounts(
+                accounts,
+                INITIALIZE_ACCOUNTS,
+                None,
+                amount_0,
+                amount_1,
+            ))
+        } else if discriminator == cpmm_instructions::DEPOSIT
+            || discriminator == cpmm_instructions::WITHDRAW
+        {
+            let lp_amount = reader.read_u64()?;
+            let amount_0 = reader.read_u64()?;
+            let amount_1 = reader.read_u64()?;
+            let data = Self::liquidity_accounts(
+                accounts,
+                DEPOSIT_WITHDRAW_ACCOUNTS,
+                Some(lp_amount),
+                amount_0,
+                amount_1,
+            );
+            if discriminator == cpmm_instructions::DEPOSIT {
+                CpmmInstructionData::Deposit(data)
+            } else {
+                CpmmInstructionData::Withdraw(data)
+            }
+        } else {
+            return Ok(None);
+        };
+        Ok(Some(data))
+    }
+
+    fn decode_swap(
+        accounts: &[String],
+        reader: &mut BinaryReader,
+        is_base_input: bool,
+    ) -> Result<CpmmSwapInstruction, RaydiumError> {
+        let account = |index: usize| accounts.get(index).cloned().unwrap_or_default();
+        Ok(CpmmSwapInstruction {
+            payer: account(0),
+            pool: account(3),
+            input_token_account: account(4),
+            output_token_account: account(5),
+            input_vault: account(6),
+            output_vault: account(7),
+            input_mint: account(10),
+            output_mint: account(11),
+            amount_in: reader.read_u64()?,
+            amount_out: reader.read_u64()?,
+            is_base_input,
+        })
+    }
+
+    fn liquidity_accounts(
+        accounts: &[String],
+        (owner, pool, lp_mint, mint_0, mint_1, vault_0, vault_1): LiquidityAccounts,
+        lp_amount: Option<u64>,
+        amount_0: u64,
+        amount_1: u64,
+    ) -> CpmmLiquidityInstruction {
+        let account = |index: usize| accounts.get(index).cloned().unwrap_or_default();
+        CpmmLiquidityInstruction {
+            owner: account(owner),
+            pool: account(pool),
+            lp_mint: account(lp_mint),
+            token_0_mint: account(mint_0),
+            token_1_mint: account(mint_1),
+            token_0_vault: account(vault_0),
+            token_1_vault: account(vault_1),
+            lp_amount,
+            amount_0,
+            amount_1,
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use crate::core::amounts::convert_to_ui_amount;
+use crate::core::matcher::{Leg, TransferMatcher};
+use crate::core::transaction_adapter::TransactionAdapter;
+use crate::protocols::simple::LiquidityParser;
+use crate::types::{ClassifiedInstruction, PoolEvent, TradeType, TransferData, TransferMap};
+
+use super::constants::{LP_MINT_DECIMALS, RAYDIUM_CPMM_PROGRAM_ID, RAYDIUM_CPMM_PROGRAM_NAME};
+use super::cpmm_instruction_parser::{
+    CpmmInstruction, CpmmInstructionData, CpmmInstructionParser, CpmmLiquidityInstruction,
+};
+
+pub struct RaydiumCpmmLiquidityParser {
+    adapter: TransactionAdapter,
+    transfer_actions: TransferMap,
+    classified_instructions: Vec<ClassifiedInstruction>,
+    instruction_parser: CpmmInstructionParser,
+}
+
+impl RaydiumCpmmLiquidityParser {
+    pub fn new(
+        adapter: TransactionAdapter,
+        transfer_actions: TransferMap,
+        classified_instructions: Vec<ClassifiedInstruction>,
+    ) -> Self {
+        let instruction_parser = CpmmInstructionParser::new(adapter.clone());
+        Self {
+            adapter,
+            transfer_actions,
+            classified_instructions,
+            instruction_parser,
+        }
+    }
+
+    fn build_event(
+        &self,
+        instruction: &CpmmInstruction,
+        data: &CpmmLiquidityInstruction,
+        event_type: TradeType,
+    ) -> PoolEvent {
+        let matcher = TransferMatcher::new(&self.transfer_actions);
+        let is_deposit = event_type != TradeType::Remove;
+        let vault_transfer = |vault: &str| {
+            let leg = if is_deposit {
+                Leg::default().to(vault)
+            } else {
+                Leg::default().from(vault)
+            };
+            matcher.find_leg(instruction.outer_index, &leg)
+        };
+        // Initialization deposits exactly its arguments; deposit/withdraw only bound them.
+        let fallback = |amount: u64| (event_type == TradeType::Create).then_some(amount);
+        let (token0_amount, token0_amount_raw, token0_decimals) = self.token_amount(
+            vault_transfer(&data.token_0_vault),
+            &data.token_0_mint,
+            fallback(data.amount_0),
+        );
+        let (token1_amount, token1_amount_raw, token1_decimals) = self.token_amount(
+            vault_transfer(&data.token_1_vault),
+            &data.token_1_mint,
+            fallback(data.amount_1),
+        );
+
+        PoolEvent {
+            user: data.owner.clone(),
+            event_type,
+            program_id: Some(RAYDIUM_CPMM_PROGRAM_ID.to_string()),
+            amm: Some(RAYDIUM_CPMM_PROGRAM_NAME.to_string()),
+            slot: instruction.slot,
+            timestamp: instruction.timestamp,
+            signature: instruction.signature.clone(),
+            idx: instruction.idx.clone(),
+            signer: Some(instruction.signer.clone()),
+            pool_id: data.pool.clone(),
+            pool_lp_mint: Some(data.lp_mint.clone()),
+            token0_mint: Some(data.token_0_mint.clone()),
+            token0_amount,
+            token0_amount_raw,
+            token0_decimals,
+            token1_mint: Some(data.token_1_mint.clone()),
+            token1_amount,
+            token1_amount_raw,
+            token1_decimals,
+            lp_amount: data
+                .lp_amount
+                .map(|amount| convert_to_ui_amount(amount, LP_MINT_DECIMALS)),
+            lp_amount_raw: data.lp_amount.map(|amount| amount.to_string()),
+            ..PoolEvent::default()
+        }
+    }
+
+    /// `(ui amount, raw amount, decimals)` from the vault transfer, else from `fallback`.
+    fn token_amount(
+        &self,
+        transfer: Option<&TransferData>,
+        mint: &str,
+        fallback: Option<u64>,
+    ) -> (Option<f64>, Option<String>, Option<u8>) {
+        if let Some(transfer) = transfer {
+            let amount = &transfer.info.token_amount;
+            return (
+                amount.ui_amount,
+                Some(amount.amount.clone()),
+                Some(amount.decimals),
+            );
+        }
+        let decimals = self.adapter.spl_decimals_map().get(mint).copied();
+        match (fallback, decimals) {
+            (Some(amount), Some(decimals)) => (
+                Some(convert_to_ui_amount(amount, decimals)),
+                Some(amount.to_string()),
+                Some(decimals),
+            ),
+            (Some(amount), None) => (None, Some(amount.to_string()), None),
+            (None, decimals) => (None, None, decimals),
+        }
+    }
+}
+
+impl LiquidityParser for RaydiumCpmmLiquidityParser {
+    fn process_liquidity(&mut self) -> Vec<PoolEvent> {
+        let instructions = match self
+            .instruction_parser
+            .parse_instructions(&self.classified_instructions)
+        {
+            Ok(instructions) => instructions,
+            Err(err) => {
+                tracing::error!("failed to parse raydium cpmm liquidity instructions: {err}");
+                return Vec::new();
+            }
+        };
+
+        instructions
+            .iter()
+            .filter_map(|instruction| {
+                let (data, event_type) = match &instruction.data {
+                    CpmmInstructionData::Initialize(data) => (data, TradeType::Create),
+                    CpmmInstructionData::Deposit(data) => (data, TradeType::Add),
+                    CpmmInstructionData::Withdraw(data) => (data, TradeType::Remove),
+                    CpmmInstructionData::Swap(_) => return None,
+                };
+                Some(self.build_event(instruction, data, event_type))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,98 @@
+use crate::core::matcher::TransferMatcher;
+use crate::core::transaction_adapter::TransactionAdapter;
+use crate::protocols::pumpfun::util::get_trade_type;
+use crate::protocols::raydium::util::{swap_slippage_bps, vault_swap_tokens};
+use crate::protocols::simple::TradeParser;
+use crate::types::{ClassifiedInstruction, DexInfo, TradeInfo, TransferMap};
+
+use super::constants::{RAYDIUM_CPMM_PROGRAM_ID, RAYDIUM_CPMM_PROGRAM_NAME};
+use super::cpmm_instruction_parser::{
+    CpmmInstruction, CpmmInstructionData, CpmmInstructionParser, CpmmSwapInstruction,
+};
+
+pub struct RaydiumCpmmParser {
+    dex_info: DexInfo,
+    transfer_actions: TransferMap,
+    classified_instructions: Vec<ClassifiedInstruction>,
+    instruction_parser: CpmmInstructionParser,
+}
+
+impl RaydiumCpmmParser {
+    pub fn new(
+        adapter: TransactionAdapter,
+        dex_info: DexInfo,
+        transfer_actions: TransferMap,
+        classified_instructions: Vec<ClassifiedInstruction>,
+    ) -> Self {
+        Self {
+            dex_info,
+            transfer_actions,
+            classified_instructions,
+            instruction_parser: CpmmInstructionParser::new(adapter),
+        }
+    }
+
+    /// Build a trade from the vault transfers of a swap; `None` if they are missing.
+    fn build_trade(
+        &self,
+        instruction: &CpmmInstruction,
+        swap: &CpmmSwapInstruction,
+    ) -> Option<TradeInfo> {
+        let matcher = TransferMatcher::new(&self.transfer_actions);
+        let (input_token, output_token) = vault_swap_tokens(
+            &matcher,
+            instruction.outer_index,
+            &swap.input_token_account,
+            &swap.input_vault,
+            &swap.output_vault,
+            &swap.output_token_account,
+        )?;
+        let limit = if swap.is_base_input {
+            swap.amount_out
+        } else {
+            swap.amount_in
+        };
+        let slippage_bps =
+            swap_slippage_bps(&input_token, &output_token, swap.is_base_input, limit);
+        Some(TradeInfo {
+            trade_type: get_trade_type(&input_token.mint, &output_token.mint),
+            pool: vec![swap.pool.clone()],
+            input_token,
+            output_token,
+            slippage_bps,
+            user: Some(swap.payer.clone()),
+            program_id: Some(RAYDIUM_CPMM_PROGRAM_ID.to_string()),
+            amm: Some(RAYDIUM_CPMM_PROGRAM_NAME.to_string()),
+            route: self.dex_info.route.clone(),
+            slot: instruction.slot,
+            timestamp: instruction.timestamp,
+            signature: instruction.signature.clone(),
+            idx: instruction.idx.clone(),
+            signer: Some(instruction.signer.clone()),
+            ..TradeInfo::default()
+        })
+    }
+}
+
+impl TradeParser for RaydiumCpmmParser {
+    fn process_trades(&mut self) -> Vec<TradeInfo> {
+        let instructions = match self
+            .instruction_parser
+            .parse_instructions(&self.classified_instructions)
+        {
+            Ok(instructions) => instructions,
+            Err(err) => {
+                tracing::error!("failed to parse raydium cpmm instructions: {err}");
+                return Vec::new();
+            }
+        };
+
+        instructions
+            .iter()
+            .filter_map(|instruction| match &instruction.data {
+                CpmmInstructionData::Swap(swap) => self.build_trade(instruction, swap),
+                _ => None,
+            })
+            .collect()
+    }
+}
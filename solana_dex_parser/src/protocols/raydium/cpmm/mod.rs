@@ -0,0 +1,37 @@
+pub mod constants;
+pub mod cpmm_instruction_parser;
+pub mod cpmm_liquidity_parser;
+pub mod cpmm_parser;
+
+use crate::core::transaction_adapter::TransactionAdapter;
+use crate::protocols::simple::{LiquidityParser, TradeParser};
+use crate::types::{ClassifiedInstruction, DexInfo, TransferMap};
+
+use cpmm_liquidity_parser::RaydiumCpmmLiquidityParser;
+use cpmm_parser::RaydiumCpmmParser;
+
+pub fn build_raydium_cpmm_trade_parser(
+    adapter: TransactionAdapter,
+    dex_info: DexInfo,
+    transfer_actions: TransferMap,
+    classified_instructions: Vec<ClassifiedInstruction>,
+) -> Box<dyn TradeParser> {
+    Box::new(RaydiumCpmmParser::new(
+        adapter,
+        dex_info,
+        transfer_actions,
+        classified_instructions,
+    ))
+}
+
+pub fn build_raydium_cpmm_liquidity_parser(
+    adapter: TransactionAdapter,
+    transfer_actions: TransferMap,
+    classified_instructions: Vec<ClassifiedInstruction>,
+) -> Box<dyn LiquidityParser> {
+    Box::new(RaydiumCpmmLiquidityParser::new(
+        adapter,
+        transfer_actions,
+        classified_instructions,
+    ))
+}
@@ -0,0 +1,26 @@
+pub const RAYDIUM_LAUNCHLAB_PROGRAM_ID: &str = "LanMV9sAd7wArD4vJFi2qDdfnVhFxYSUg6eADduJ3uj";
+pub const RAYDIUM_LAUNCHLAB_PROGRAM_NAME: &str = "RaydiumLaunchLab";
+
+/// Fallback decimals when the mints are absent from the token balances.
+pub const DEFAULT_BASE_DECIMALS: u8 = 6;
+pub const DEFAULT_QUOTE_DECIMALS: u8 = 9;
+
+pub mod discriminators {
+    pub mod launchlab_instructions {
+        pub const BUY_EXACT_IN: [u8; 8] = [250, 234, 13, 123, 213, 156, 19, 236];
+        pub const BUY_EXACT_OUT: [u8; 8] = [24, 211, 116, 40, 105, 3, 153, 56];
+        pub const SELL_EXACT_IN: [u8; 8] = [149, 39, 222, 155, 211, 124, 152, 26];
+        pub const SELL_EXACT_OUT: [u8; 8] = [95, 200, 71, 34, 8, 9, 11, 166];
+        pub const MIGRATE_TO_AMM: [u8; 8] = [207, 82, 192, 145, 254, 207, 145, 223];
+        pub const MIGRATE_TO_CPSWAP: [u8; 8] = [136, 92, 200, 103, 28, 218, 144, 140];
+    }
+
+    pub mod launchlab_events {
+        pub const POOL_CREATE: [u8; 16] = [
+            228, 69, 165, 46, 81, 203, 154, 29, 151, 215, 226, 9, 118, 161, 115, 174,
+        ];
+        pub const TRADE: [u8; 16] = [
+            228, 69, 165, 46, 81, 203, 154, 29, 189, 219, 127, 211, 78, 230, 97, 238,
+        ];
+    }
+}
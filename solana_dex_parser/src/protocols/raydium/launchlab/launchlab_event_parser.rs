@@ -0,0 +1,369 @@
+use crate::core::constants::dex_program_names;
+use crate::core::constants::dex_programs;
+use crate::core::instruction_classifier::InstructionClassifier;
+use crate::core::transaction_adapter::TransactionAdapter;
+use crate::protocols::pumpfun::binary_reader::BinaryReader;
+use crate::protocols::pumpfun::util::{
+    build_token_info, decode_instruction_data, get_prev_instruction_by_index, sort_by_idx,
+};
+use crate::protocols::raydium::error::RaydiumError;
+use crate::protocols::simple::MemeEventParser;
+use crate::types::{ClassifiedInstruction, MemeEvent, TradeType, TransferMap};
+
+use super::constants::{
+    discriminators::{launchlab_events, launchlab_instructions},
+    DEFAULT_BASE_DECIMALS, DEFAULT_QUOTE_DECIMALS, RAYDIUM_LAUNCHLAB_PROGRAM_ID,
+    RAYDIUM_LAUNCHLAB_PROGRAM_NAME,
+};
+
+/// Payload length of a `TradeEvent` that carries the creator fee.
+const TRADE_EVENT_V2_LEN: usize = 138;
+
+/// Account positions of `(user, platform_config, base_mint, quote_mint)` in buy/sell
+/// and pool initialisation instructions.
+const TRADE_ACCOUNTS: (usize, usize, usize, usize) = (0, 3, 9, 10);
+const INITIALIZE_ACCOUNTS: (usize, usize, usize, usize) = (1, 3, 6, 7);
+
+/// Account positions of a migration instruction and the AMM receiving the liquidity.
+struct MigrateAccounts {
+    base_mint: usize,
+    quote_mint: usize,
+    pool_state: usize,
+    pool: usize,
+    destination: &'static str,
+}
+
+const MIGRATE_TO_AMM_ACCOUNTS: MigrateAccounts = MigrateAccounts {
+    base_mint: 1,
+    quote_mint: 2,
+    pool_state: 22,
+    pool: 13,
+    destination: dex_programs::RAYDIUM,
+};
+
+const MIGRATE_TO_CPSWAP_ACCOUNTS: MigrateAccounts = MigrateAccounts {
+    base_mint: 1,
+    quote_mint: 2,
+    pool_state: 17,
+    pool: 5,
+    destination: dex_programs::RAYDIUM_CPMM,
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LaunchLabTradeEvent {
+    pub pool_state: String,
+    pub total_base_sell: u64,
+    pub virtual_base: u64,
+    pub virtual_quote: u64,
+    pub real_base_before: u64,
+    pub real_quote_before: u64,
+    pub real_base_after: u64,
+    pub real_quote_after: u64,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub protocol_fee: u64,
+    pub platform_fee: u64,
+    /// Absent from events emitted before creator fees were introduced.
+    pub creator_fee: Option<u64>,
+    pub share_fee: u64,
+    pub is_buy: bool,
+    pub pool_status: u8,
+}
+
+impl LaunchLabTradeEvent {
+    /// Base and quote reserves of the bonding curve after the trade.
+    pub fn curve_reserves(&self) -> (u64, u64) {
+        (
+            self.virtual_base.saturating_sub(self.real_base_after),
+            self.virtual_quote.saturating_add(self.real_quote_after),
+        )
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LaunchLabPoolCreateEvent {
+    pub pool_state: String,
+    pub creator: String,
+    pub config: String,
+    pub decimals: u8,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub supply: u64,
+}
+
+pub struct RaydiumLaunchLabEventParser {
+    adapter: TransactionAdapter,
+}
+
+impl RaydiumLaunchLabEventParser {
+    pub fn new(adapter: TransactionAdapter) -> Self {
+        Self { adapter }
+    }
+
+    /// Decode trade and pool-create events, reading mints and the platform config from the
+    /// instruction that emitted them, plus migrations from the migrate instructions.
+    pub fn parse_instructions(
+        &self,
+        instructions: &[ClassifiedInstruction],
+    ) -> Result<Vec<MemeEvent>, RaydiumError> {
+        // Events are emitted by self-CPI right after the instruction they describe.
+        let mut ordered = instructions.to_vec();
+        ordered.sort_by_key(|classified| {
+            (
+                classified.outer_index,
+                classified.inner_index.map_or(0, |index| index + 1),
+            )
+        });
+
+        let classifier = InstructionClassifier::new(&self.adapter);
+        let mut events = Vec::new();
+        for classified in &ordered {
+            let data = decode_instruction_data(&classified.data.data)
+                .map_err(|err| RaydiumError::instruction_data(err.to_string()))?;
+            if data.len() < 8 {
+                continue;
+            }
+
+            let emitter = |pool_state: &str| {
+                Self::emitter(&classifier, classified, pool_state).or_else(|| {
+                    get_prev_instruction_by_index(
+                        &ordered,
+                        classified.outer_index,
+                        classified.inner_index,
+                    )
+                    .filter(|emitter| emitter.outer_index == classified.outer_index)
+                })
+            };
+            let event = if data.len() >= 16 && data[..16] == launchlab_events::TRADE {
+                let trade = Self::decode_trade_event(data[16..].to_vec())?;
+                emitter(&trade.pool_state)
+                    .map(|emitter| self.build_trade_event(trade, &emitter.data.accounts))
+            } else if data.len() >= 16 && data[..16] == launchlab_events::POOL_CREATE {
+                let create = Self::decode_pool_create_event(data[16..].to_vec())?;
+                emitter(&create.pool_state)
+                    .map(|emitter| Self::build_create_event(create, &emitter.data.accounts))
+            } else if data[..8] == launchlab_instructions::MIGRATE_TO_AMM {
+                Some(Self::build_migrate_event(
+                    &classified.data.accounts,
+                    &MIGRATE_TO_AMM_ACCOUNTS,
+                ))
+            } else if data[..8] == launchlab_instructions::MIGRATE_TO_CPSWAP {
+                Some(Self::build_migrate_event(
+                    &classified.data.accounts,
+                    &MIGRATE_TO_CPSWAP_ACCOUNTS,
+                ))
+            } else {
+                None
+            };
+
+            if let Some(mut meme_event) = event {
+                meme_event.signature = self.adapter.shared_signature();
+                meme_event.slot = self.adapter.slot();
+                meme_event.timestamp = self.adapter.block_time();
+                meme_event.idx = format!(
+                    "{}-{}",
+                    classified.outer_index,
+                    classified.inner_index.unwrap_or(0)
+                );
+                events.push(meme_event);
+            }
+        }
+
+        Ok(sort_by_idx(events))
+    }
+
+    /// LaunchLab instruction that emitted the event at `event`: the last one before it in the
+    /// same outer instruction that touched `pool_state`, so an earlier event CPI in between
+    /// does not take its place.
+    fn emitter(
+        classifier: &InstructionClassifier,
+        event: &ClassifiedInstruction,
+        pool_state: &str,
+    ) -> Option<ClassifiedInstruction> {
+        let position = |ci: &ClassifiedInstruction| ci.inner_index.map_or(0, |index| index + 1);
+        classifier
+            .find_by_account(pool_state)
+            .into_iter()
+            .rev()
+            .find(|ci| {
+                ci.program_id == RAYDIUM_LAUNCHLAB_PROGRAM_ID
+                    && ci.outer_index == event.outer_index
+                    && position(ci) < position(event)
+            })
+    }
+
+    fn decode_trade_event(data: Vec<u8>) -> Result<LaunchLabTradeEvent, RaydiumError> {
+        let has_creator_fee = data.len() >= TRADE_EVENT_V2_LEN;
+        let mut reader = BinaryReader::new(data);
+        Ok(LaunchLabTradeEvent {
+            pool_state: reader.read_pubkey()?,
+            total_base_sell: reader.read_u64()?,
+            virtual_base: reader.read_u64()?,
+            virtual_quote: reader.read_u64()?,
+            real_base_before: reader.read_u64()?,
+            real_quote_before: reader.read_u64()?,
+            real_base_after: reader.read_u64()?,
+            real_quote_after: reader.read_u64()?,
+            amount_in: reader.read_u64()?,
+            amount_out: reader.read_u64()?,
+            protocol_fee: reader.read_u64()?,
+            platform_fee: reader.read_u64()?,
+            creator_fee: if has_creator_fee {
+                Some(reader.read_u64()?)
+            } else {
+                None
+            },
+            share_fee: reader.read_u64()?,
+            is_buy: reader.read_u8()? == 0,
+            pool_status: reader.read_u8()?,
+        })
+    }
+
+    fn decode_pool_create_event(data: Vec<u8>) -> Result<LaunchLabPoolCreateEvent, RaydiumError> {
+        let mut reader = BinaryReader::new(data);
+        let pool_state = reader.read_pubkey()?;
+        let creator = reader.read_pubkey()?;
+        let config = reader.read_pubkey()?;
+        let decimals = reader.read_u8()?;
+        let name = reader.read_string()?;
+        let symbol = reader.read_string()?;
+        let uri = reader.read_string()?;
+        // Every curve variant (constant, fixed, linear) starts with the total supply.
+        let _curve_type = reader.read_u8()?;
+        Ok(LaunchLabPoolCreateEvent {
+            pool_state,
+            creator,
+            config,
+            decimals,
+            name,
+            symbol,
+            uri,
+            supply: reader.read_u64()?,
+        })
+    }
+
+    fn build_trade_event(&self, trade: LaunchLabTradeEvent, accounts: &[String]) -> MemeEvent {
+        let (user, platform_config, base_mint, quote_mint) = TRADE_ACCOUNTS;
+        let account = |index: usize| accounts.get(index).cloned().unwrap_or_default();
+        let base_mint = account(base_mint);
+        let quote_mint = account(quote_mint);
+        let decimals = |mint: &str, default: u8| {
+            self.adapter
+                .spl_decimals_map()
+                .get(mint)
+                .copied()
+                .unwrap_or(default)
+        };
+        let base_decimals = decimals(&base_mint, DEFAULT_BASE_DECIMALS);
+        let quote_decimals = decimals(&quote_mint, DEFAULT_QUOTE_DECIMALS);
+
+        let base = (base_mint.as_str(), base_decimals);
+        let quote = (quote_mint.as_str(), quote_decimals);
+        let ((input_mint, input_decimals), (output_mint, output_decimals)) = if trade.is_buy {
+            (quote, base)
+        } else {
+            (base, quote)
+        };
+        let (base_reserve, quote_reserve) = trade.curve_reserves();
+
+        MemeEvent {
+            event_type: if trade.is_buy {
+                TradeType::Buy
+            } else {
+                TradeType::Sell
+            },
+            user: account(user),
+            input_token: Some(build_token_info(
+                input_mint,
+                trade.amount_in as u128,
+                input_decimals,
+                None,
+            )),
+            output_token: Some(build_token_info(
+                output_mint,
+                trade.amount_out as u128,
+                output_decimals,
+                None,
+            )),
+            fee: Some(trade.protocol_fee as f64),
+            protocol_fee: Some(trade.protocol_fee as f64),
+            platform_fee: Some(trade.platform_fee as f64),
+            share_fee: Some(trade.share_fee as f64),
+            creator_fee: trade.creator_fee.map(|fee| fee as f64),
+            protocol: Some(RAYDIUM_LAUNCHLAB_PROGRAM_NAME.to_string()),
+            platform_config: accounts.get(platform_config).cloned(),
+            bonding_curve: Some(trade.pool_state),
+            pool_a_reserve: Some(base_reserve as f64),
+            pool_b_reserve: Some(quote_reserve as f64),
+            base_mint,
+            quote_mint,
+            ..MemeEvent::default()
+        }
+    }
+
+    fn build_create_event(create: LaunchLabPoolCreateEvent, accounts: &[String]) -> MemeEvent {
+        let (_, platform_config, base_mint, quote_mint) = INITIALIZE_ACCOUNTS;
+        let account = |index: usize| accounts.get(index).cloned().unwrap_or_default();
+        MemeEvent {
+            event_type: TradeType::Create,
+            user: create.creator.clone(),
+            base_mint: account(base_mint),
+            quote_mint: account(quote_mint),
+            name: Some(create.name),
+            symbol: Some(create.symbol),
+            uri: Some(create.uri),
+            decimals: Some(create.decimals),
+            total_supply: Some(create.supply),
+            protocol: Some(RAYDIUM_LAUNCHLAB_PROGRAM_NAME.to_string()),
+            platform_config: accounts.get(platform_config).cloned(),
+            creator: Some(create.creator),
+            bonding_curve: Some(create.pool_state),
+            ..MemeEvent::default()
+        }
+    }
+
+    fn build_migrate_event(accounts: &[String], positions: &MigrateAccounts) -> MemeEvent {
+        let account = |index: usize| accounts.get(index).cloned().unwrap_or_default();
+        MemeEvent {
+            event_type: TradeType::Migrate,
+            user: account(0),
+            base_mint: account(positions.base_mint),
+            quote_mint: account(positions.quote_mint),
+            protocol: Some(RAYDIUM_LAUNCHLAB_PROGRAM_NAME.to_string()),
+            bonding_curve: accounts.get(positions.pool_state).cloned(),
+            pool: accounts.get(positions.pool).cloned(),
+            pool_dex: Some(dex_program_names::name(positions.destination).to_string()),
+            ..MemeEvent::default()
+        }
+    }
+}
+
+pub struct RaydiumLaunchLabMemeParser {
+    adapter: TransactionAdapter,
+    _transfer_actions: TransferMap,
+}
+
+impl RaydiumLaunchLabMemeParser {
+    pub fn new(adapter: TransactionAdapter, transfer_actions: TransferMap) -> Self {
+        Self {
+            adapter,
+            _transfer_actions: transfer_actions,
+        }
+    }
+}
+
+impl MemeEventParser for RaydiumLaunchLabMemeParser {
+    fn process_events(&mut self) -> Vec<MemeEvent> {
+        let classifier = InstructionClassifier::new(&self.adapter);
+        let instructions = classifier.get_instructions(RAYDIUM_LAUNCHLAB_PROGRAM_ID);
+        let parser = RaydiumLaunchLabEventParser::new(self.adapter.clone());
+        match parser.parse_instructions(&instructions) {
+            Ok(events) => events,
+            Err(err) => {
+                tracing::error!("failed to parse raydium launchlab events: {err}");
+                Vec::new()
+            }
+        }
+    }
+}
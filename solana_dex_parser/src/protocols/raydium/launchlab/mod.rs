@@ -0,0 +1,15 @@
+pub mod constants;
+pub mod launchlab_event_parser;
+
+use crate::core::transaction_adapter::TransactionAdapter;
+use crate::protocols::simple::MemeEventParser;
+use crate::types::TransferMap;
+
+use launchlab_event_parser::RaydiumLaunchLabMemeParser;
+
+pub fn build_raydium_launchlab_meme_parser(
+    adapter: TransactionAdapter,
+    transfer_actions: TransferMap,
+) -> Box<dyn MemeEventParser> {
+    Box::new(RaydiumLaunchLabMemeParser::new(adapter, transfer_actions))
+}
@@ -0,0 +1,9 @@
+pub mod clmm;
+pub mod cpmm;
+pub mod error;
+pub mod launchlab;
+pub mod util;
+
+pub use clmm::{build_raydium_clmm_liquidity_parser, build_raydium_clmm_trade_parser};
+pub use cpmm::{build_raydium_cpmm_liquidity_parser, build_raydium_cpmm_trade_parser};
+pub use launchlab::build_raydium_launchlab_meme_parser;
@@ -0,0 +1,34 @@
+use crate::core::matcher::{Leg, TransferMatcher};
+use crate::core::transaction_utils::TransactionUtils;
+use crate::protocols::pumpfun::util::slippage_bps;
+use crate::types::TokenInfo;
+
+/// Input and output legs of a vault swap under the outer instruction at `outer_index`,
+/// matched on the user accounts and pool vaults.
+pub fn vault_swap_tokens(
+    matcher: &TransferMatcher,
+    outer_index: usize,
+    user_input: &str,
+    input_vault: &str,
+    output_vault: &str,
+    user_output: &str,
+) -> Option<(TokenInfo, TokenInfo)> {
+    let input = matcher.find_leg(outer_index, &Leg::between(user_input, input_vault))?;
+    let output = matcher.find_leg(outer_index, &Leg::between(output_vault, user_output))?;
+    Some((
+        TransactionUtils::transfer_to_token_info(input),
+        TransactionUtils::transfer_to_token_info(output),
+    ))
+}
+
+/// Slippage a swap allowed, measured against its executed legs: `limit` is the minimum output
+/// of a base-input swap and the maximum input of a base-output swap.
+pub fn swap_slippage_bps(
+    input: &TokenInfo,
+    output: &TokenInfo,
+    is_base_input: bool,
+    limit: u64,
+) -> Option<u64> {
+    let executed = if is_base_input { output } else { input };
+    slippage_bps(executed.amount_raw.parse::<u128>().ok()?, limit)
+}
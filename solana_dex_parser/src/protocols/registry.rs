@@ -0,0 +1,740 @@
+use std::borrow::Cow;
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+
+use crate::core::constants::{dex_program_names, dex_programs, migration_instructions};
+use crate::core::program_tables::ProgramTables;
+
+use super::bags::constants::{
+    discriminators::{bags_events, bags_instructions},
+    BAGS_PROGRAM_ID, BAGS_PROGRAM_NAME,
+};
+use super::fluxbeam::constants::{
+    discriminators::fluxbeam_instructions, FLUXBEAM_PROGRAM_ID, FLUXBEAM_PROGRAM_NAME,
+};
+use super::heaven::constants::{
+    discriminators::heaven_instructions, HEAVEN_PROGRAM_ID, HEAVEN_PROGRAM_NAME,
+};
+use super::jupiter::constants::{
+    discriminators::{
+        dca_events, dca_instructions, jupiter_events, jupiter_instructions, limit_order_events,
+        limit_order_instructions,
+    },
+    JUPITER_DCA_PROGRAM_ID, JUPITER_DCA_PROGRAM_NAME, JUPITER_LIMIT_ORDER_PROGRAM_ID,
+    JUPITER_LIMIT_ORDER_PROGRAM_NAME, JUPITER_PROGRAM_ID, JUPITER_PROGRAM_NAME,
+    JUPITER_TRIGGER_PROGRAM_ID, JUPITER_TRIGGER_PROGRAM_NAME,
+};
+use super::meteora::constants::{
+    discriminators::{dbc_events, dbc_instructions},
+    METEORA_DBC_PROGRAM_ID, METEORA_DBC_PROGRAM_NAME,
+};
+use super::obric::constants::{
+    discriminators::obric_instructions, OBRIC_V2_PROGRAM_ID, OBRIC_V2_PROGRAM_NAME,
+};
+use super::okx::constants::{discriminators::okx_instructions, OKX_PROGRAM_ID, OKX_PROGRAM_NAME};
+use super::openbook::constants::{
+    discriminators::openbook_instructions, OPENBOOK_V2_PROGRAM_ID, OPENBOOK_V2_PROGRAM_NAME,
+};
+use super::orca::constants::{
+    discriminators::{whirlpool_events, whirlpool_instructions},
+    ORCA_WHIRLPOOL_PROGRAM_ID, ORCA_WHIRLPOOL_PROGRAM_NAME,
+};
+use super::pumpfun::constants::{
+    discriminators::{
+        pumpfun_events, pumpfun_instructions, pumpswap_events, pumpswap_instructions,
+    },
+    PUMP_FUN_PROGRAM_ID, PUMP_FUN_PROGRAM_NAME, PUMP_SWAP_PROGRAM_ID, PUMP_SWAP_PROGRAM_NAME,
+};
+use super::raydium::clmm::constants::{
+    discriminators::{clmm_events, clmm_instructions},
+    RAYDIUM_CLMM_PROGRAM_ID, RAYDIUM_CLMM_PROGRAM_NAME,
+};
+use super::raydium::cpmm::constants::{
+    discriminators::cpmm_instructions, RAYDIUM_CPMM_PROGRAM_ID, RAYDIUM_CPMM_PROGRAM_NAME,
+};
+use super::raydium::launchlab::constants::{
+    discriminators::{launchlab_events, launchlab_instructions},
+    RAYDIUM_LAUNCHLAB_PROGRAM_ID, RAYDIUM_LAUNCHLAB_PROGRAM_NAME,
+};
+use super::saber::constants::{
+    discriminators::{decimal_wrapper_instructions, saber_instructions},
+    SABER_DECIMAL_WRAPPER_PROGRAM_ID, SABER_DECIMAL_WRAPPER_PROGRAM_NAME, SABER_PROGRAM_ID,
+    SABER_PROGRAM_NAME,
+};
+use super::solfi::constants::{
+    discriminators::solfi_instructions, SOLFI_PROGRAM_ID, SOLFI_PROGRAM_NAME,
+};
+use super::token_swap::constants::{
+    discriminators::token_swap_instructions, TOKEN_SWAP_PROGRAM_IDS,
+};
+use super::virtuals::constants::{
+    discriminators::virtuals_instructions, VIRTUALS_PROGRAM_ID, VIRTUALS_PROGRAM_NAME,
+};
+use super::zerofi::constants::{
+    discriminators::zerofi_instructions, ZEROFI_PROGRAM_ID, ZEROFI_PROGRAM_NAME,
+};
+
+/// Whether a discriminator prefixes instruction data or an emitted (CPI) event.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DiscriminatorKind {
+    Instruction,
+    Event,
+}
+
+/// Single entry of the discriminator registry.
+#[derive(Clone, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DiscriminatorEntry {
+    pub program_id: Cow<'static, str>,
+    pub program_name: Cow<'static, str>,
+    pub kind: DiscriminatorKind,
+    pub name: Cow<'static, str>,
+    pub discriminator: Cow<'static, [u8]>,
+}
+
+impl DiscriminatorEntry {
+    const fn new(
+        program_id: &'static str,
+        program_name: &'static str,
+        kind: DiscriminatorKind,
+        name: &'static str,
+        discriminator: &'static [u8],
+    ) -> Self {
+        Self {
+            program_id: Cow::Borrowed(program_id),
+            program_name: Cow::Borrowed(program_name),
+            kind,
+            name: Cow::Borrowed(name),
+            discriminator: Cow::Borrowed(discriminator),
+        }
+    }
+}
+
+static KNOWN_DISCRIMINATORS: Lazy<Vec<DiscriminatorEntry>> = Lazy::new(|| {
+    use DiscriminatorKind::{Event, Instruction};
+
+    let pumpfun = |kind, name, bytes| {
+        DiscriminatorEntry::new(
+            PUMP_FUN_PROGRAM_ID,
+            PUMP_FUN_PROGRAM_NAME,
+            kind,
+            name,
+            bytes,
+        )
+    };
+    let pumpswap = |kind, name, bytes| {
+        DiscriminatorEntry::new(
+            PUMP_SWAP_PROGRAM_ID,
+            PUMP_SWAP_PROGRAM_NAME,
+            kind,
+            name,
+            bytes,
+        )
+    };
+    let jupiter = |kind, name, bytes| {
+        DiscriminatorEntry::new(JUPITER_PROGRAM_ID, JUPITER_PROGRAM_NAME, kind, name, bytes)
+    };
+    let jupiter_limit = |kind, name, bytes| {
+        DiscriminatorEntry::new(
+            JUPITER_LIMIT_ORDER_PROGRAM_ID,
+            JUPITER_LIMIT_ORDER_PROGRAM_NAME,
+            kind,
+            name,
+            bytes,
+        )
+    };
+    let jupiter_trigger = |kind, name, bytes| {
+        DiscriminatorEntry::new(
+            JUPITER_TRIGGER_PROGRAM_ID,
+            JUPITER_TRIGGER_PROGRAM_NAME,
+            kind,
+            name,
+            bytes,
+        )
+    };
+    let jupiter_dca = |kind, name, bytes| {
+        DiscriminatorEntry::new(
+            JUPITER_DCA_PROGRAM_ID,
+            JUPITER_DCA_PROGRAM_NAME,
+            kind,
+            name,
+            bytes,
+        )
+    };
+    let meteora_dbc = |kind, name, bytes| {
+        DiscriminatorEntry::new(
+            METEORA_DBC_PROGRAM_ID,
+            METEORA_DBC_PROGRAM_NAME,
+            kind,
+            name,
+            bytes,
+        )
+    };
+    let openbook = |kind, name, bytes| {
+        DiscriminatorEntry::new(
+            OPENBOOK_V2_PROGRAM_ID,
+            OPENBOOK_V2_PROGRAM_NAME,
+            kind,
+            name,
+            bytes,
+        )
+    };
+    let orca_whirlpool = |kind, name, bytes| {
+        DiscriminatorEntry::new(
+            ORCA_WHIRLPOOL_PROGRAM_ID,
+            ORCA_WHIRLPOOL_PROGRAM_NAME,
+            kind,
+            name,
+            bytes,
+        )
+    };
+    let raydium_clmm = |kind, name, bytes| {
+        DiscriminatorEntry::new(
+            RAYDIUM_CLMM_PROGRAM_ID,
+            RAYDIUM_CLMM_PROGRAM_NAME,
+            kind,
+            name,
+            bytes,
+        )
+    };
+    let raydium_cpmm = |kind, name, bytes| {
+        DiscriminatorEntry::new(
+            RAYDIUM_CPMM_PROGRAM_ID,
+            RAYDIUM_CPMM_PROGRAM_NAME,
+            kind,
+            name,
+            bytes,
+        )
+    };
+    let raydium_launchlab = |kind, name, bytes| {
+        DiscriminatorEntry::new(
+            RAYDIUM_LAUNCHLAB_PROGRAM_ID,
+            RAYDIUM_LAUNCHLAB_PROGRAM_NAME,
+            kind,
+            name,
+            bytes,
+        )
+    };
+    let fluxbeam = |kind, name, bytes| {
+        DiscriminatorEntry::new(
+            FLUXBEAM_PROGRAM_ID,
+            FLUXBEAM_PROGRAM_NAME,
+            kind,
+            name,
+            bytes,
+        )
+    };
+    let saber = |kind, name, bytes| {
+        DiscriminatorEntry::new(SABER_PROGRAM_ID, SABER_PROGRAM_NAME, kind, name, bytes)
+    };
+    let solfi = |kind, name, bytes| {
+        DiscriminatorEntry::new(SOLFI_PROGRAM_ID, SOLFI_PROGRAM_NAME, kind, name, bytes)
+    };
+    let zerofi = |kind, name, bytes| {
+        DiscriminatorEntry::new(ZEROFI_PROGRAM_ID, ZEROFI_PROGRAM_NAME, kind, name, bytes)
+    };
+    let obric = |kind, name, bytes| {
+        DiscriminatorEntry::new(
+            OBRIC_V2_PROGRAM_ID,
+            OBRIC_V2_PROGRAM_NAME,
+            kind,
+            name,
+            bytes,
+        )
+    };
+    let heaven = |kind, name, bytes| {
+        DiscriminatorEntry::new(HEAVEN_PROGRAM_ID, HEAVEN_PROGRAM_NAME, kind, name, bytes)
+    };
+    let virtuals = |kind, name, bytes| {
+        DiscriminatorEntry::new(
+            VIRTUALS_PROGRAM_ID,
+            VIRTUALS_PROGRAM_NAME,
+            kind,
+            name,
+            bytes,
+        )
+    };
+    let okx = |kind, name, bytes| {
+        DiscriminatorEntry::new(OKX_PROGRAM_ID, OKX_PROGRAM_NAME, kind, name, bytes)
+    };
+    let bags = |kind, name, bytes| {
+        DiscriminatorEntry::new(BAGS_PROGRAM_ID, BAGS_PROGRAM_NAME, kind, name, bytes)
+    };
+    let saber_decimal_wrapper = |kind, name, bytes| {
+        DiscriminatorEntry::new(
+            SABER_DECIMAL_WRAPPER_PROGRAM_ID,
+            SABER_DECIMAL_WRAPPER_PROGRAM_NAME,
+            kind,
+            name,
+            bytes,
+        )
+    };
+    let other = |program_id, kind, name, bytes| {
+        DiscriminatorEntry::new(
+            program_id,
+            dex_program_names::name(program_id),
+            kind,
+            name,
+            bytes,
+        )
+    };
+
+    let mut entries = vec![
+        pumpfun(Instruction, "create", &pumpfun_instructions::CREATE),
+        pumpfun(Instruction, "migrate", &pumpfun_instructions::MIGRATE),
+        pumpfun(Instruction, "buy", &pumpfun_instructions::BUY),
+        pumpfun(Instruction, "sell", &pumpfun_instructions::SELL),
+        pumpfun(
+            Instruction,
+            "collect_creator_fee",
+            &pumpfun_instructions::COLLECT_CREATOR_FEE,
+        ),
+        pumpfun(Event, "TradeEvent", &pumpfun_events::TRADE),
+        pumpfun(Event, "CreateEvent", &pumpfun_events::CREATE),
+        pumpfun(Event, "CompleteEvent", &pumpfun_events::COMPLETE),
+        pumpfun(
+            Event,
+            "CompletePumpAmmMigrationEvent",
+            &pumpfun_events::MIGRATE,
+        ),
+        pumpfun(Event, "SetParamsEvent", &pumpfun_events::SET_PARAMS),
+        pumpfun(Event, "SetCreatorEvent", &pumpfun_events::SET_CREATOR),
+        pumpfun(
+            Event,
+            "AdminSetCreatorEvent",
+            &pumpfun_events::ADMIN_SET_CREATOR,
+        ),
+        pumpfun(
+            Event,
+            "UpdateGlobalAuthorityEvent",
+            &pumpfun_events::UPDATE_GLOBAL_AUTHORITY,
+        ),
+        pumpfun(
+            Event,
+            "CollectCreatorFeeEvent",
+            &pumpfun_events::COLLECT_CREATOR_FEE,
+        ),
+        pumpswap(
+            Instruction,
+            "create_pool",
+            &pumpswap_instructions::CREATE_POOL,
+        ),
+        pumpswap(
+            Instruction,
+            "deposit",
+            &pumpswap_instructions::ADD_LIQUIDITY,
+        ),
+        pumpswap(
+            Instruction,
+            "withdraw",
+            &pumpswap_instructions::REMOVE_LIQUIDITY,
+        ),
+        pumpswap(Instruction, "buy", &pumpswap_instructions::BUY),
+        pumpswap(Instruction, "sell", &pumpswap_instructions::SELL),
+        pumpswap(
+            Instruction,
+            "collect_coin_creator_fee",
+            &pumpswap_instructions::COLLECT_COIN_CREATOR_FEE,
+        ),
+        pumpswap(Event, "CreatePoolEvent", &pumpswap_events::CREATE_POOL),
+        pumpswap(Event, "DepositEvent", &pumpswap_events::ADD_LIQUIDITY),
+        pumpswap(Event, "WithdrawEvent", &pumpswap_events::REMOVE_LIQUIDITY),
+        pumpswap(Event, "BuyEvent", &pumpswap_events::BUY),
+        pumpswap(Event, "SellEvent", &pumpswap_events::SELL),
+        pumpswap(
+            Event,
+            "CollectCoinCreatorFeeEvent",
+            &pumpswap_events::COLLECT_COIN_CREATOR_FEE,
+        ),
+        meteora_dbc(Instruction, "swap", &dbc_instructions::SWAP),
+        meteora_dbc(Instruction, "swap2", &dbc_instructions::SWAP2),
+        meteora_dbc(
+            Instruction,
+            "initialize_virtual_pool_with_spl_token",
+            &dbc_instructions::INITIALIZE_VIRTUAL_POOL_WITH_SPL_TOKEN,
+        ),
+        meteora_dbc(
+            Instruction,
+            "initialize_virtual_pool_with_token2022",
+            &dbc_instructions::INITIALIZE_VIRTUAL_POOL_WITH_TOKEN2022,
+        ),
+        meteora_dbc(
+            Instruction,
+            "claim_trading_fee",
+            &dbc_instructions::CLAIM_TRADING_FEE,
+        ),
+        meteora_dbc(
+            Instruction,
+            "claim_creator_trading_fee",
+            &dbc_instructions::CLAIM_CREATOR_TRADING_FEE,
+        ),
+        meteora_dbc(
+            Instruction,
+            "migrate_meteora_damm",
+            &dbc_instructions::MIGRATE_METEORA_DAMM,
+        ),
+        meteora_dbc(
+            Instruction,
+            "migration_damm_v2",
+            &dbc_instructions::MIGRATION_DAMM_V2,
+        ),
+        meteora_dbc(Event, "EvtClaimTradingFee", &dbc_events::CLAIM_TRADING_FEE),
+        meteora_dbc(
+            Event,
+            "EvtClaimCreatorTradingFee",
+            &dbc_events::CLAIM_CREATOR_TRADING_FEE,
+        ),
+        raydium_clmm(Instruction, "swap", &clmm_instructions::SWAP),
+        raydium_clmm(Instruction, "swap_v2", &clmm_instructions::SWAP_V2),
+        raydium_clmm(
+            Instruction,
+            "open_position",
+            &clmm_instructions::OPEN_POSITION,
+        ),
+        raydium_clmm(
+            Instruction,
+            "open_position_v2",
+            &clmm_instructions::OPEN_POSITION_V2,
+        ),
+        raydium_clmm(
+            Instruction,
+            "open_position_with_token22_nft",
+            &clmm_instructions::OPEN_POSITION_WITH_TOKEN22_NFT,
+        ),
+        raydium_clmm(
+            Instruction,
+            "increase_liquidity",
+            &clmm_instructions::INCREASE_LIQUIDITY,
+        ),
+        raydium_clmm(
+            Instruction,
+            "increase_liquidity_v2",
+            &clmm_instructions::INCREASE_LIQUIDITY_V2,
+        ),
+        raydium_clmm(
+            Instruction,
+            "decrease_liquidity",
+            &clmm_instructions::DECREASE_LIQUIDITY,
+        ),
+        raydium_clmm(
+            Instruction,
+            "decrease_liquidity_v2",
+            &clmm_instructions::DECREASE_LIQUIDITY_V2,
+        ),
+        raydium_clmm(
+            Instruction,
+            "close_position",
+            &clmm_instructions::CLOSE_POSITION,
+        ),
+        raydium_clmm(Event, "SwapEvent", &clmm_events::SWAP),
+        raydium_clmm(
+            Event,
+            "LiquidityChangeEvent",
+            &clmm_events::LIQUIDITY_CHANGE,
+        ),
+        raydium_cpmm(Instruction, "initialize", &cpmm_instructions::INITIALIZE),
+        raydium_cpmm(Instruction, "deposit", &cpmm_instructions::DEPOSIT),
+        raydium_cpmm(Instruction, "withdraw", &cpmm_instructions::WITHDRAW),
+        raydium_cpmm(
+            Instruction,
+            "swap_base_input",
+            &cpmm_instructions::SWAP_BASE_INPUT,
+        ),
+        raydium_cpmm(
+            Instruction,
+            "swap_base_output",
+            &cpmm_instructions::SWAP_BASE_OUTPUT,
+        ),
+        orca_whirlpool(Instruction, "swap", &whirlpool_instructions::SWAP),
+        orca_whirlpool(Instruction, "swapV2", &whirlpool_instructions::SWAP_V2),
+        orca_whirlpool(
+            Instruction,
+            "openPosition",
+            &whirlpool_instructions::OPEN_POSITION,
+        ),
+        orca_whirlpool(
+            Instruction,
+            "openPositionWithMetadata",
+            &whirlpool_instructions::OPEN_POSITION_WITH_METADATA,
+        ),
+        orca_whirlpool(
+            Instruction,
+            "increaseLiquidity",
+            &whirlpool_instructions::INCREASE_LIQUIDITY,
+        ),
+        orca_whirlpool(
+            Instruction,
+            "increaseLiquidityV2",
+            &whirlpool_instructions::INCREASE_LIQUIDITY_V2,
+        ),
+        orca_whirlpool(
+            Instruction,
+            "decreaseLiquidity",
+            &whirlpool_instructions::DECREASE_LIQUIDITY,
+        ),
+        orca_whirlpool(
+            Instruction,
+            "decreaseLiquidityV2",
+            &whirlpool_instructions::DECREASE_LIQUIDITY_V2,
+        ),
+        orca_whirlpool(
+            Instruction,
+            "collectFees",
+            &whirlpool_instructions::COLLECT_FEES,
+        ),
+        orca_whirlpool(
+            Instruction,
+            "collectFeesV2",
+            &whirlpool_instructions::COLLECT_FEES_V2,
+        ),
+        orca_whirlpool(
+            Instruction,
+            "closePosition",
+            &whirlpool_instructions::CLOSE_POSITION,
+        ),
+        orca_whirlpool(Event, "Traded", &whirlpool_events::TRADED),
+        openbook(
+            Instruction,
+            "createMarket",
+            &openbook_instructions::CREATE_MARKET,
+        ),
+        openbook(
+            Instruction,
+            "placeOrder",
+            &openbook_instructions::PLACE_ORDER,
+        ),
+        openbook(
+            Instruction,
+            "placeOrderPegged",
+            &openbook_instructions::PLACE_ORDER_PEGGED,
+        ),
+        openbook(
+            Instruction,
+            "placeTakeOrder",
+            &openbook_instructions::PLACE_TAKE_ORDER,
+        ),
+        openbook(
+            Instruction,
+            "settleFunds",
+            &openbook_instructions::SETTLE_FUNDS,
+        ),
+        openbook(
+            Instruction,
+            "consumeEvents",
+            &openbook_instructions::CONSUME_EVENTS,
+        ),
+        openbook(
+            Instruction,
+            "consumeGivenEvents",
+            &openbook_instructions::CONSUME_GIVEN_EVENTS,
+        ),
+        jupiter(Instruction, "route", &jupiter_instructions::ROUTE),
+        jupiter(
+            Instruction,
+            "sharedAccountsRoute",
+            &jupiter_instructions::SHARED_ACCOUNTS_ROUTE,
+        ),
+        jupiter(Event, "SwapEvent", &jupiter_events::SWAP),
+        jupiter_limit(
+            Instruction,
+            "fillOrder",
+            &limit_order_instructions::FILL_ORDER,
+        ),
+        jupiter_limit(
+            Instruction,
+            "preFlashFillOrder",
+            &limit_order_instructions::PRE_FLASH_FILL_ORDER,
+        ),
+        jupiter_limit(
+            Instruction,
+            "flashFillOrder",
+            &limit_order_instructions::FLASH_FILL_ORDER,
+        ),
+        jupiter_limit(Event, "TradeEvent", &limit_order_events::TRADE),
+        jupiter_trigger(
+            Instruction,
+            "fillOrder",
+            &limit_order_instructions::FILL_ORDER,
+        ),
+        jupiter_trigger(
+            Instruction,
+            "preFlashFillOrder",
+            &limit_order_instructions::PRE_FLASH_FILL_ORDER,
+        ),
+        jupiter_trigger(
+            Instruction,
+            "flashFillOrder",
+            &limit_order_instructions::FLASH_FILL_ORDER,
+        ),
+        jupiter_trigger(Event, "TradeEvent", &limit_order_events::TRADE),
+        jupiter_dca(
+            Instruction,
+            "fulfillFlashFill",
+            &dca_instructions::FULFILL_FLASH_FILL,
+        ),
+        jupiter_dca(
+            Instruction,
+            "fulfillDlmmFill",
+            &dca_instructions::FULFILL_DLMM_FILL,
+        ),
+        jupiter_dca(Event, "FilledEvent", &dca_events::FILLED),
+        other(
+            dex_programs::MOONSHOT,
+            Instruction,
+            "migrate_funds",
+            &migration_instructions::MOONSHOT_MIGRATE_FUNDS,
+        ),
+        raydium_launchlab(
+            Instruction,
+            "buy_exact_in",
+            &launchlab_instructions::BUY_EXACT_IN,
+        ),
+        raydium_launchlab(
+            Instruction,
+            "buy_exact_out",
+            &launchlab_instructions::BUY_EXACT_OUT,
+        ),
+        raydium_launchlab(
+            Instruction,
+            "sell_exact_in",
+            &launchlab_instructions::SELL_EXACT_IN,
+        ),
+        raydium_launchlab(
+            Instruction,
+            "sell_exact_out",
+            &launchlab_instructions::SELL_EXACT_OUT,
+        ),
+        raydium_launchlab(
+            Instruction,
+            "migrate_to_amm",
+            &launchlab_instructions::MIGRATE_TO_AMM,
+        ),
+        raydium_launchlab(
+            Instruction,
+            "migrate_to_cpswap",
+            &launchlab_instructions::MIGRATE_TO_CPSWAP,
+        ),
+        raydium_launchlab(Event, "PoolCreateEvent", &launchlab_events::POOL_CREATE),
+        raydium_launchlab(Event, "TradeEvent", &launchlab_events::TRADE),
+        saber(Instruction, "swap", &saber_instructions::SWAP),
+        saber(Instruction, "deposit", &saber_instructions::DEPOSIT),
+        saber(Instruction, "withdraw", &saber_instructions::WITHDRAW),
+        saber(
+            Instruction,
+            "withdrawOne",
+            &saber_instructions::WITHDRAW_ONE,
+        ),
+        saber_decimal_wrapper(
+            Instruction,
+            "deposit",
+            &decimal_wrapper_instructions::DEPOSIT,
+        ),
+        saber_decimal_wrapper(
+            Instruction,
+            "withdraw",
+            &decimal_wrapper_instructions::WITHDRAW,
+        ),
+        saber_decimal_wrapper(
+            Instruction,
+            "withdrawAll",
+            &decimal_wrapper_instructions::WITHDRAW_ALL,
+        ),
+        fluxbeam(Instruction, "swap", &fluxbeam_instructions::SWAP),
+        fluxbeam(
+            Instruction,
+            "depositAllTokenTypes",
+            &fluxbeam_instructions::DEPOSIT_ALL_TOKEN_TYPES,
+        ),
+        fluxbeam(
+            Instruction,
+            "withdrawAllTokenTypes",
+            &fluxbeam_instructions::WITHDRAW_ALL_TOKEN_TYPES,
+        ),
+        solfi(Instruction, "swap", &solfi_instructions::SWAP),
+        zerofi(Instruction, "swap", &zerofi_instructions::SWAP),
+        obric(Instruction, "swap", &obric_instructions::SWAP),
+        heaven(Instruction, "buy", &heaven_instructions::BUY),
+        heaven(Instruction, "sell", &heaven_instructions::SELL),
+        virtuals(Instruction, "launch", &virtuals_instructions::LAUNCH),
+        virtuals(Instruction, "buy", &virtuals_instructions::BUY),
+        virtuals(Instruction, "sell", &virtuals_instructions::SELL),
+        virtuals(Instruction, "graduate", &virtuals_instructions::GRADUATE),
+        bags(Instruction, "create", &bags_instructions::CREATE),
+        bags(Instruction, "buy", &bags_instructions::BUY),
+        bags(Instruction, "sell", &bags_instructions::SELL),
+        bags(Instruction, "claimFee", &bags_instructions::CLAIM_FEE),
+        bags(Event, "TradeEvent", &bags_events::TRADE),
+        bags(Event, "ClaimFeeEvent", &bags_events::CLAIM_FEE),
+        okx(Instruction, "swap", &okx_instructions::SWAP),
+        okx(Instruction, "swapTob", &okx_instructions::SWAP_TOB),
+        okx(Instruction, "proxySwap", &okx_instructions::PROXY_SWAP),
+        okx(
+            Instruction,
+            "commissionSplSwap",
+            &okx_instructions::COMMISSION_SPL_SWAP,
+        ),
+        okx(
+            Instruction,
+            "commissionSolSwap",
+            &okx_instructions::COMMISSION_SOL_SWAP,
+        ),
+    ];
+    for program_id in TOKEN_SWAP_PROGRAM_IDS {
+        entries.extend([
+            other(
+                program_id,
+                Instruction,
+                "swap",
+                &token_swap_instructions::SWAP,
+            ),
+            other(
+                program_id,
+                Instruction,
+                "depositAllTokenTypes",
+                &token_swap_instructions::DEPOSIT_ALL_TOKEN_TYPES,
+            ),
+            other(
+                program_id,
+                Instruction,
+                "withdrawAllTokenTypes",
+                &token_swap_instructions::WITHDRAW_ALL_TOKEN_TYPES,
+            ),
+        ]);
+    }
+    entries
+});
+
+/// Every discriminator the bundled decoders know about.
+pub fn known_discriminators() -> Vec<DiscriminatorEntry> {
+    KNOWN_DISCRIMINATORS.clone()
+}
+
+/// Discriminators registered for a single program id.
+pub fn discriminators_for(program_id: &str) -> Vec<DiscriminatorEntry> {
+    known_discriminators()
+        .into_iter()
+        .filter(|entry| entry.program_id == program_id)
+        .collect()
+}
+
+/// [`known_discriminators`] followed by those listed in `tables`, in file order.
+pub fn discriminators_with(tables: &ProgramTables) -> Vec<DiscriminatorEntry> {
+    let mut entries = known_discriminators();
+    for program in &tables.programs {
+        entries.extend(
+            program
+                .discriminators
+                .iter()
+                .map(|entry| DiscriminatorEntry {
+                    program_id: Cow::Owned(program.program_id.clone()),
+                    program_name: Cow::Owned(program.name.clone()),
+                    kind: entry.kind,
+                    name: Cow::Owned(entry.name.clone()),
+                    discriminator: Cow::Owned(entry.discriminator.clone()),
+                }),
+        );
+    }
+    entries
+}
@@ -0,0 +1,20 @@
+pub const SABER_PROGRAM_ID: &str = "SSwpkEEcbUqx4vtoEByFjSkhKdCT5PRxhsJq6Nf1ppq";
+pub const SABER_PROGRAM_NAME: &str = "Saber";
+pub const SABER_DECIMAL_WRAPPER_PROGRAM_ID: &str = "DecZY86MU5Gj7kppfUCEmd4LbXXuyZH1yHaP2NTqdiZB";
+pub const SABER_DECIMAL_WRAPPER_PROGRAM_NAME: &str = "SaberDecimalWrapper";
+
+pub mod discriminators {
+    /// Stable swap is not an Anchor program: a single leading tag selects the instruction.
+    pub mod saber_instructions {
+        pub const SWAP: [u8; 1] = [1];
+        pub const DEPOSIT: [u8; 1] = [2];
+        pub const WITHDRAW: [u8; 1] = [3];
+        pub const WITHDRAW_ONE: [u8; 1] = [4];
+    }
+
+    pub mod decimal_wrapper_instructions {
+        pub const DEPOSIT: [u8; 8] = [242, 35, 198, 137, 82, 225, 242, 182];
+        pub const WITHDRAW: [u8; 8] = [183, 18, 70, 156, 148, 109, 161, 34];
+        pub const WITHDRAW_ALL: [u8; 8] = [96, 246, 166, 130, 229, 50, 43, 70];
+    }
+}
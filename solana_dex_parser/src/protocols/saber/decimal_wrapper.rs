@@ -0,0 +1,135 @@
+use crate::core::instruction_classifier::InstructionClassifier;
+use crate::core::matcher::{Leg, TransferMatcher};
+use crate::core::transaction_adapter::TransactionAdapter;
+use crate::core::transaction_utils::TransactionUtils;
+use crate::protocols::pumpfun::util::{build_token_info, compare_idx};
+use crate::types::{TokenInfo, TradeInfo, TransferMap};
+
+use super::constants::{
+    discriminators::decimal_wrapper_instructions, SABER_DECIMAL_WRAPPER_PROGRAM_ID,
+};
+
+/// One pass through the decimal wrapper: `deposit` wraps the underlying token, `withdraw`
+/// and `withdrawAll` unwrap it.
+#[derive(Clone, Debug, PartialEq)]
+struct WrapperMovement {
+    wraps: bool,
+    wrapper_mint: String,
+    idx: String,
+    /// Underlying leg moved between the user and the wrapper's reserve.
+    underlying: Option<TokenInfo>,
+    /// Mint and decimals of the wrapper's reserve, for rescaling when no leg was found.
+    underlying_mint: Option<(String, u8)>,
+}
+
+/// Wrapper mints seen in a transaction, resolved to the underlying token they stand for.
+/// Saber pools trade wrapped tokens so that both sides share a precision; the wrapper
+/// mints mean nothing outside of the pool.
+pub struct DecimalWrappers {
+    movements: Vec<WrapperMovement>,
+}
+
+impl DecimalWrappers {
+    pub fn new(adapter: &TransactionAdapter, transfer_actions: &TransferMap) -> Self {
+        let classifier = InstructionClassifier::new(adapter);
+        let matcher = TransferMatcher::new(transfer_actions);
+        let mut passes: Vec<_> = [
+            (decimal_wrapper_instructions::DEPOSIT, true),
+            (decimal_wrapper_instructions::WITHDRAW, false),
+            (decimal_wrapper_instructions::WITHDRAW_ALL, false),
+        ]
+        .into_iter()
+        .flat_map(|(discriminator, wraps)| {
+            classifier
+                .find_instructions(SABER_DECIMAL_WRAPPER_PROGRAM_ID, &discriminator)
+                .into_iter()
+                .map(move |instruction| (instruction, wraps))
+        })
+        .collect();
+        passes.sort_by_key(|(instruction, _)| {
+            (
+                instruction.outer_index,
+                instruction.inner_index.map_or(0, |index| index + 1),
+            )
+        });
+
+        let mut movements = Vec::new();
+        for (instruction, wraps) in passes {
+            // wrapper, wrapper_mint, wrapper_underlying_tokens, owner, user_underlying_tokens
+            let accounts = &instruction.data.accounts;
+            let account = |index: usize| accounts.get(index).cloned().unwrap_or_default();
+            let reserve = account(2);
+            let user_account = account(4);
+            let (source, destination) = if wraps {
+                (&user_account, &reserve)
+            } else {
+                (&reserve, &user_account)
+            };
+            let underlying = matcher
+                .find_leg(instruction.outer_index, &Leg::between(source, destination))
+                .map(TransactionUtils::transfer_to_token_info);
+            movements.push(WrapperMovement {
+                wraps,
+                wrapper_mint: account(1),
+                idx: format!(
+                    "{}-{}",
+                    instruction.outer_index,
+                    instruction.inner_index.unwrap_or(0)
+                ),
+                underlying_mint: adapter
+                    .spl_token_map()
+                    .get(&reserve)
+                    .map(|token| (token.mint.clone(), token.decimals)),
+                underlying,
+            });
+        }
+
+        Self { movements }
+    }
+
+    /// Replace wrapper mints in the legs of `trade` with their underlying tokens.
+    pub fn unwrap_trade(&self, mut trade: TradeInfo) -> TradeInfo {
+        if let Some(token) = self.underlying(&trade.input_token, true, &trade.idx) {
+            trade.input_token = token;
+        }
+        if let Some(token) = self.underlying(&trade.output_token, false, &trade.idx) {
+            trade.output_token = token;
+        }
+        trade
+    }
+
+    /// The input was wrapped before the swap and the output unwrapped after it, so take
+    /// the closest matching movement on that side of `idx`.
+    fn underlying(&self, token: &TokenInfo, wraps: bool, idx: &str) -> Option<TokenInfo> {
+        let candidates: Vec<&WrapperMovement> = self
+            .movements
+            .iter()
+            .filter(|movement| movement.wraps == wraps && movement.wrapper_mint == token.mint)
+            .collect();
+        let movement = if wraps {
+            candidates
+                .iter()
+                .rev()
+                .find(|movement| compare_idx(&movement.idx, idx).is_le())
+                .or(candidates.first())
+        } else {
+            candidates
+                .iter()
+                .find(|movement| compare_idx(&movement.idx, idx).is_ge())
+                .or(candidates.last())
+        }?;
+
+        if let Some(underlying) = &movement.underlying {
+            return Some(underlying.clone());
+        }
+        // No underlying transfer to read: rescale the wrapped amount to the underlying mint.
+        let (mint, decimals) = movement.underlying_mint.clone()?;
+        let wrapped: u128 = token.amount_raw.parse().ok()?;
+        let amount = if decimals >= token.decimals {
+            wrapped * 10u128.pow(u32::from(decimals - token.decimals))
+        } else {
+            wrapped / 10u128.pow(u32::from(token.decimals - decimals))
+        };
+        Some(build_token_info(&mint, amount, decimals, None))
+    }
+}
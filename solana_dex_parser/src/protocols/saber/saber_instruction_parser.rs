@@ -0,0 +1,150 @@
+use std::sync::Arc;
+
+use crate::core::transaction_adapter::TransactionAdapter;
+use crate::protocols::pumpfun::binary_reader::BinaryReader;
+use crate::protocols::pumpfun::util::{decode_instruction_data, sort_by_idx, HasIdx};
+use crate::types::ClassifiedInstruction;
+
+use super::constants::discriminators::saber_instructions;
+use super::error::SaberError;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SaberSwap {
+    pub swap: String,
+    pub user: String,
+    pub user_source: String,
+    pub reserve_source: String,
+    pub reserve_destination: String,
+    pub user_destination: String,
+    pub amount_in: u64,
+    pub minimum_amount_out: u64,
+}
+
+/// Deposit or withdrawal of both reserves; `withdrawOne` leaves the second side empty.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SaberLiquidity {
+    pub swap: String,
+    pub user: String,
+    pub pool_mint: String,
+    pub reserve_a: String,
+    pub reserve_b: Option<String>,
+    /// Pool tokens burned; deposits only bound the amount they mint.
+    pub pool_token_amount: Option<u64>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SaberInstructionData {
+    Swap(SaberSwap),
+    Deposit(SaberLiquidity),
+    Withdraw(SaberLiquidity),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SaberInstruction {
+    pub data: SaberInstructionData,
+    pub outer_index: usize,
+    pub inner_index: Option<usize>,
+    pub slot: u64,
+    pub timestamp: u64,
+    pub signature: Arc<str>,
+    pub idx: String,
+    pub signer: Vec<String>,
+}
+
+impl HasIdx for SaberInstruction {
+    fn idx(&self) -> &str {
+        &self.idx
+    }
+}
+
+pub struct SaberInstructionParser {
+    adapter: TransactionAdapter,
+}
+
+impl SaberInstructionParser {
+    pub fn new(adapter: TransactionAdapter) -> Self {
+        Self { adapter }
+    }
+
+    pub fn parse_instructions(
+        &self,
+        instructions: &[ClassifiedInstruction],
+    ) -> Result<Vec<SaberInstruction>, SaberError> {
+        let mut parsed = Vec::new();
+        for instruction in instructions {
+            let data = decode_instruction_data(&instruction.data.data)
+                .map_err(|err| SaberError::instruction_data(err.to_string()))?;
+            let Some((tag, payload)) = data.split_first() else {
+                continue;
+            };
+            let Some(data) = Self::decode_instruction(instruction, *tag, payload)? else {
+                continue;
+            };
+            parsed.push(SaberInstruction {
+                data,
+                outer_index: instruction.outer_index,
+                inner_index: instruction.inner_index,
+                slot: self.adapter.slot(),
+                timestamp: self.adapter.block_time(),
+                signature: self.adapter.shared_signature(),
+                idx: format!(
+                    "{}-{}",
+                    instruction.outer_index,
+                    instruction.inner_index.unwrap_or(0)
+                ),
+                signer: self.adapter.signers().to_vec(),
+            });
+        }
+
+        Ok(sort_by_idx(parsed))
+    }
+
+    fn decode_instruction(
+        instruction: &ClassifiedInstruction,
+        tag: u8,
+        payload: &[u8],
+    ) -> Result<Option<SaberInstructionData>, SaberError> {
+        let accounts = &instruction.data.accounts;
+        let account = |index: usize| accounts.get(index).cloned().unwrap_or_default();
+        let mut reader = BinaryReader::new(payload.to_vec());
+        let data = match [tag] {
+            saber_instructions::SWAP => SaberInstructionData::Swap(SaberSwap {
+                swap: account(0),
+                user: account(2),
+                user_source: account(3),
+                reserve_source: account(4),
+                reserve_destination: account(5),
+                user_destination: account(6),
+                amount_in: reader.read_u64()?,
+                minimum_amount_out: reader.read_u64()?,
+            }),
+            saber_instructions::DEPOSIT => SaberInstructionData::Deposit(SaberLiquidity {
+                swap: account(0),
+                user: account(2),
+                pool_mint: account(7),
+                reserve_a: account(5),
+                reserve_b: Some(account(6)),
+                pool_token_amount: None,
+            }),
+            saber_instructions::WITHDRAW => SaberInstructionData::Withdraw(SaberLiquidity {
+                swap: account(0),
+                user: account(2),
+                pool_mint: account(3),
+                reserve_a: account(5),
+                reserve_b: Some(account(6)),
+                pool_token_amount: Some(reader.read_u64()?),
+            }),
+            // `reserve_a` is the reserve paid out, `swap_base_token` in the program's terms.
+            saber_instructions::WITHDRAW_ONE => SaberInstructionData::Withdraw(SaberLiquidity {
+                swap: account(0),
+                user: account(2),
+                pool_mint: account(3),
+                reserve_a: account(5),
+                reserve_b: None,
+                pool_token_amount: Some(reader.read_u64()?),
+            }),
+            _ => return Ok(None),
+        };
+        Ok(Some(data))
+    }
+}
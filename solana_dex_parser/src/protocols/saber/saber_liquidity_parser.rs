@@ -0,0 +1,143 @@
+use crate::core::amounts::convert_to_ui_amount;
+use crate::core::matcher::{Leg, TransferMatcher};
+use crate::core::transaction_adapter::TransactionAdapter;
+use crate::protocols::simple::LiquidityParser;
+use crate::types::{ClassifiedInstruction, PoolEvent, TradeType, TransferData, TransferMap};
+
+use super::constants::{SABER_PROGRAM_ID, SABER_PROGRAM_NAME};
+use super::saber_instruction_parser::{
+    SaberInstruction, SaberInstructionData, SaberInstructionParser, SaberLiquidity,
+};
+
+pub struct SaberLiquidityParser {
+    adapter: TransactionAdapter,
+    transfer_actions: TransferMap,
+    classified_instructions: Vec<ClassifiedInstruction>,
+    instruction_parser: SaberInstructionParser,
+}
+
+impl SaberLiquidityParser {
+    pub fn new(
+        adapter: TransactionAdapter,
+        transfer_actions: TransferMap,
+        classified_instructions: Vec<ClassifiedInstruction>,
+    ) -> Self {
+        let instruction_parser = SaberInstructionParser::new(adapter.clone());
+        Self {
+            adapter,
+            transfer_actions,
+            classified_instructions,
+            instruction_parser,
+        }
+    }
+
+    fn build_event(
+        &self,
+        instruction: &SaberInstruction,
+        data: &SaberLiquidity,
+        event_type: TradeType,
+    ) -> PoolEvent {
+        let matcher = TransferMatcher::new(&self.transfer_actions);
+        let reserve_transfer = |reserve: &str| -> Option<&TransferData> {
+            let leg = if event_type == TradeType::Add {
+                Leg::default().to(reserve)
+            } else {
+                Leg::default().from(reserve)
+            };
+            matcher.find_leg(instruction.outer_index, &leg)
+        };
+        let token = |reserve: Option<&String>| {
+            let Some(reserve) = reserve else {
+                return (None, None, None, None);
+            };
+            let mint = self
+                .adapter
+                .spl_token_map()
+                .get(reserve)
+                .map(|token| token.mint.clone());
+            match reserve_transfer(reserve) {
+                Some(transfer) => {
+                    let amount = &transfer.info.token_amount;
+                    (
+                        Some(transfer.info.mint.clone()),
+                        amount.ui_amount,
+                        Some(amount.amount.clone()),
+                        Some(amount.decimals),
+                    )
+                }
+                None => (mint, None, None, None),
+            }
+        };
+        let (token0_mint, token0_amount, token0_amount_raw, token0_decimals) =
+            token(Some(&data.reserve_a));
+        let (token1_mint, token1_amount, token1_amount_raw, token1_decimals) =
+            token(data.reserve_b.as_ref());
+
+        // Deposits mint an amount only bounded by the arguments; read it off the mint.
+        let lp_amount_raw = data.pool_token_amount.map(u128::from).or_else(|| {
+            matcher
+                .find_leg(instruction.outer_index, &Leg::of(&data.pool_mint))
+                .and_then(|transfer| transfer.info.token_amount.amount.parse().ok())
+        });
+        let lp_decimals = self
+            .adapter
+            .spl_decimals_map()
+            .get(&data.pool_mint)
+            .copied();
+
+        PoolEvent {
+            user: data.user.clone(),
+            event_type,
+            program_id: Some(SABER_PROGRAM_ID.to_string()),
+            amm: Some(SABER_PROGRAM_NAME.to_string()),
+            slot: instruction.slot,
+            timestamp: instruction.timestamp,
+            signature: instruction.signature.clone(),
+            idx: instruction.idx.clone(),
+            signer: Some(instruction.signer.clone()),
+            pool_id: data.swap.clone(),
+            pool_lp_mint: Some(data.pool_mint.clone()),
+            token0_mint,
+            token0_amount,
+            token0_amount_raw,
+            token0_decimals,
+            token1_mint,
+            token1_amount,
+            token1_amount_raw,
+            token1_decimals,
+            lp_amount: lp_amount_raw
+                .zip(lp_decimals)
+                .map(|(amount, decimals)| convert_to_ui_amount(amount, decimals)),
+            lp_amount_raw: lp_amount_raw.map(|amount| amount.to_string()),
+            ..PoolEvent::default()
+        }
+    }
+}
+
+impl LiquidityParser for SaberLiquidityParser {
+    fn process_liquidity(&mut self) -> Vec<PoolEvent> {
+        let instructions = match self
+            .instruction_parser
+            .parse_instructions(&self.classified_instructions)
+        {
+            Ok(instructions) => instructions,
+            Err(err) => {
+                tracing::error!("failed to parse saber liquidity instructions: {err}");
+                return Vec::new();
+            }
+        };
+
+        instructions
+            .iter()
+            .filter_map(|instruction| match &instruction.data {
+                SaberInstructionData::Deposit(data) => {
+                    Some(self.build_event(instruction, data, TradeType::Add))
+                }
+                SaberInstructionData::Withdraw(data) => {
+                    Some(self.build_event(instruction, data, TradeType::Remove))
+                }
+                SaberInstructionData::Swap(_) => None,
+            })
+            .collect()
+    }
+}
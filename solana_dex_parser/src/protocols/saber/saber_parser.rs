@@ -0,0 +1,99 @@
+use crate::core::matcher::TransferMatcher;
+use crate::core::transaction_adapter::TransactionAdapter;
+use crate::protocols::pumpfun::util::get_trade_type;
+use crate::protocols::raydium::util::vault_swap_tokens;
+use crate::protocols::simple::TradeParser;
+use crate::types::{ClassifiedInstruction, DexInfo, TradeInfo, TransferMap};
+
+use super::constants::{SABER_PROGRAM_ID, SABER_PROGRAM_NAME};
+use super::decimal_wrapper::DecimalWrappers;
+use super::saber_instruction_parser::{
+    SaberInstruction, SaberInstructionData, SaberInstructionParser, SaberSwap,
+};
+
+/// Stable swaps, reported in the underlying tokens when either side went through the
+/// decimal wrapper.
+pub struct SaberParser {
+    adapter: TransactionAdapter,
+    dex_info: DexInfo,
+    transfer_actions: TransferMap,
+    classified_instructions: Vec<ClassifiedInstruction>,
+    instruction_parser: SaberInstructionParser,
+}
+
+impl SaberParser {
+    pub fn new(
+        adapter: TransactionAdapter,
+        dex_info: DexInfo,
+        transfer_actions: TransferMap,
+        classified_instructions: Vec<ClassifiedInstruction>,
+    ) -> Self {
+        let instruction_parser = SaberInstructionParser::new(adapter.clone());
+        Self {
+            adapter,
+            dex_info,
+            transfer_actions,
+            classified_instructions,
+            instruction_parser,
+        }
+    }
+
+    fn build_trade(&self, instruction: &SaberInstruction, swap: &SaberSwap) -> Option<TradeInfo> {
+        let matcher = TransferMatcher::new(&self.transfer_actions);
+        let (input_token, output_token) = vault_swap_tokens(
+            &matcher,
+            instruction.outer_index,
+            &swap.user_source,
+            &swap.reserve_source,
+            &swap.reserve_destination,
+            &swap.user_destination,
+        )?;
+        Some(TradeInfo {
+            trade_type: get_trade_type(&input_token.mint, &output_token.mint),
+            pool: vec![swap.swap.clone()],
+            input_token,
+            output_token,
+            user: Some(swap.user.clone()),
+            program_id: Some(SABER_PROGRAM_ID.to_string()),
+            amm: Some(SABER_PROGRAM_NAME.to_string()),
+            route: self.dex_info.route.clone(),
+            slot: instruction.slot,
+            timestamp: instruction.timestamp,
+            signature: instruction.signature.clone(),
+            idx: instruction.idx.clone(),
+            signer: Some(instruction.signer.clone()),
+            ..TradeInfo::default()
+        })
+    }
+}
+
+impl TradeParser for SaberParser {
+    fn process_trades(&mut self) -> Vec<TradeInfo> {
+        let instructions = match self
+            .instruction_parser
+            .parse_instructions(&self.classified_instructions)
+        {
+            Ok(instructions) => instructions,
+            Err(err) => {
+                tracing::error!("failed to parse saber instructions: {err}");
+                return Vec::new();
+            }
+        };
+
+        let wrappers = DecimalWrappers::new(&self.adapter, &self.transfer_actions);
+        instructions
+            .iter()
+            .filter_map(|instruction| match &instruction.data {
+                SaberInstructionData::Swap(swap) => self.build_trade(instruction, swap),
+                _ => None,
+            })
+            .map(|trade| {
+                // The swap itself is priced in wrapped units; report what the user holds.
+                let mut trade = wrappers.unwrap_trade(trade);
+                trade.trade_type =
+                    get_trade_type(&trade.input_token.mint, &trade.output_token.mint);
+                trade
+            })
+            .collect()
+    }
+}
@@ -1,4 +1,3 @@
-use crate::core::constants::dex_program_names;
 use crate::core::transaction_adapter::TransactionAdapter;
 use crate::types::{ClassifiedInstruction, PoolEvent, TradeType, TransferMap};
 
@@ -71,13 +70,13 @@ impl LiquidityParser for SimpleLiquidityParser {
                 let token1 = instruction.data.accounts.get(1).cloned();
 
                 PoolEvent {
-                    user: self.adapter.signer().cloned().unwrap_or_default(),
+                    user: self.adapter.signer(),
                     event_type: TradeType::Add,
                     program_id: Some(instruction.program_id.clone()),
-                    amm: Some(dex_program_names::name(&instruction.program_id).to_string()),
+                    amm: Some(self.adapter.config().program_name(&instruction.program_id)),
                     slot: self.adapter.slot(),
                     timestamp: self.adapter.block_time(),
-                    signature: self.adapter.signature().to_string(),
+                    signature: self.adapter.shared_signature(),
                     idx,
                     signer: Some(self.adapter.signers().to_vec()),
                     pool_id,
@@ -102,6 +101,11 @@ impl LiquidityParser for SimpleLiquidityParser {
                     token1_decimals: None,
                     lp_amount: None,
                     lp_amount_raw: None,
+                    lp_supply: None,
+                    lp_supply_raw: None,
+                    lp_burned: None,
+                    lp_locked: None,
+                    position: None,
                 }
             })
             .collect()
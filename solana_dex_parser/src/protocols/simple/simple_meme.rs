@@ -59,6 +59,17 @@ impl MemeEventParser for SimpleMemeParser {
                 pool_a_reserve: None,
                 pool_b_reserve: None,
                 pool_fee_rate: None,
+                mint_authority_renounced: None,
+                freeze_authority_renounced: None,
+                fee_shares: Vec::new(),
+                fee_basis_points: None,
+                creator_fee_basis_points: None,
+                previous_creator: None,
+                new_authority: None,
+                bonding_curve_progress: None,
+                pool_reserves: None,
+                price_usd: None,
+                volume_usd: None,
             })
             .collect()
     }
@@ -44,17 +44,17 @@ impl SimpleTradeParser {
 impl TradeParser for SimpleTradeParser {
     fn process_trades(&mut self) -> Vec<TradeInfo> {
         let mut trades = Vec::new();
-        if let Some(program_id) = self.dex_info.program_id.clone() {
-            if let Some(transfers) = self.transfer_actions.get(&program_id) {
-                if let Some(trade) = self.utils.process_swap_data(transfers, &self.dex_info) {
-                    trades.push(trade);
-                }
-            }
-        } else if let Some(first) = self.classified_instructions.first() {
-            if let Some(transfers) = self.transfer_actions.get(&first.program_id) {
-                if let Some(trade) = self.utils.process_swap_data(transfers, &self.dex_info) {
-                    trades.push(trade);
-                }
+        let program_id = self.dex_info.program_id.as_deref().or_else(|| {
+            self.classified_instructions
+                .first()
+                .map(|first| first.program_id.as_str())
+        });
+        if let Some(program_id) = program_id {
+            if let Some(trade) =
+                self.utils
+                    .process_swap_data(&self.transfer_actions, program_id, &self.dex_info)
+            {
+                trades.push(trade);
             }
         }
         trades
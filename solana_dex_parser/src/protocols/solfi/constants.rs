@@ -0,0 +1,9 @@
+pub const SOLFI_PROGRAM_ID: &str = "SoLFiHG9TfgtdUXUjWAxi3LtvYuFyDLVhBWxdMZxyCe";
+pub const SOLFI_PROGRAM_NAME: &str = "SolFi";
+
+pub mod discriminators {
+    /// SolFi is a native program: a single leading tag selects the instruction.
+    pub mod solfi_instructions {
+        pub const SWAP: [u8; 1] = [7];
+    }
+}
@@ -0,0 +1,153 @@
+use crate::core::matcher::TransferMatcher;
+use crate::core::transaction_adapter::TransactionAdapter;
+use crate::protocols::pumpfun::binary_reader::BinaryReader;
+use crate::protocols::pumpfun::util::{decode_instruction_data, get_trade_type};
+use crate::protocols::raydium::util::vault_swap_tokens;
+use crate::protocols::simple::TradeParser;
+use crate::types::{ClassifiedInstruction, DexInfo, TradeInfo, TransferMap};
+
+use super::constants::{discriminators::solfi_instructions, SOLFI_PROGRAM_ID, SOLFI_PROGRAM_NAME};
+use super::error::SolFiError;
+
+/// Decoded `swap`. Accounts: user, pair, base vault, quote vault, user base account, user
+/// quote account; the payload is `amount_in`, `min_amount_out` and a direction byte.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SolFiSwapInstruction {
+    pub user: String,
+    pub pair: String,
+    pub base_vault: String,
+    pub quote_vault: String,
+    pub user_base_account: String,
+    pub user_quote_account: String,
+    pub amount_in: u64,
+    pub min_amount_out: u64,
+    /// `false` sells base for quote, `true` buys base with quote.
+    pub quote_to_base: bool,
+}
+
+/// Swaps against SolFi's proprietary market-maker pairs, often reached as a Jupiter hop.
+pub struct SolFiParser {
+    adapter: TransactionAdapter,
+    dex_info: DexInfo,
+    transfer_actions: TransferMap,
+    classified_instructions: Vec<ClassifiedInstruction>,
+}
+
+impl SolFiParser {
+    pub fn new(
+        adapter: TransactionAdapter,
+        dex_info: DexInfo,
+        transfer_actions: TransferMap,
+        classified_instructions: Vec<ClassifiedInstruction>,
+    ) -> Self {
+        Self {
+            adapter,
+            dex_info,
+            transfer_actions,
+            classified_instructions,
+        }
+    }
+
+    pub fn decode_swap(
+        accounts: &[String],
+        payload: &[u8],
+    ) -> Result<SolFiSwapInstruction, SolFiError> {
+        let account = |index: usize| accounts.get(index).cloned().unwrap_or_default();
+        let mut reader = BinaryReader::new(payload.to_vec());
+        Ok(SolFiSwapInstruction {
+            user: account(0),
+            pair: account(1),
+            base_vault: account(2),
+            quote_vault: account(3),
+            user_base_account: account(4),
+            user_quote_account: account(5),
+            amount_in: reader.read_u64()?,
+            min_amount_out: reader.read_u64()?,
+            quote_to_base: reader.read_u8()? != 0,
+        })
+    }
+
+    fn build_trade(
+        &self,
+        classified: &ClassifiedInstruction,
+        swap: &SolFiSwapInstruction,
+    ) -> Option<TradeInfo> {
+        let matcher = TransferMatcher::new(&self.transfer_actions);
+        let sell_base = || {
+            vault_swap_tokens(
+                &matcher,
+                classified.outer_index,
+                &swap.user_base_account,
+                &swap.base_vault,
+                &swap.quote_vault,
+                &swap.user_quote_account,
+            )
+        };
+        let buy_base = || {
+            vault_swap_tokens(
+                &matcher,
+                classified.outer_index,
+                &swap.user_quote_account,
+                &swap.quote_vault,
+                &swap.base_vault,
+                &swap.user_base_account,
+            )
+        };
+        // Trust the moved tokens over the direction byte when the two disagree.
+        let (input_token, output_token) = if swap.quote_to_base {
+            buy_base().or_else(sell_base)?
+        } else {
+            sell_base().or_else(buy_base)?
+        };
+
+        Some(TradeInfo {
+            trade_type: get_trade_type(&input_token.mint, &output_token.mint),
+            pool: vec![swap.pair.clone()],
+            input_token,
+            output_token,
+            user: Some(swap.user.clone()),
+            program_id: Some(SOLFI_PROGRAM_ID.to_string()),
+            amm: Some(SOLFI_PROGRAM_NAME.to_string()),
+            route: self.dex_info.route.clone(),
+            slot: self.adapter.slot(),
+            timestamp: self.adapter.block_time(),
+            signature: self.adapter.shared_signature(),
+            idx: format!(
+                "{}-{}",
+                classified.outer_index,
+                classified.inner_index.unwrap_or(0)
+            ),
+            signer: Some(self.adapter.signers().to_vec()),
+            ..TradeInfo::default()
+        })
+    }
+
+    fn parse_swaps(&self) -> Result<Vec<TradeInfo>, SolFiError> {
+        let mut trades = Vec::new();
+        for classified in &self.classified_instructions {
+            let data = decode_instruction_data(&classified.data.data)
+                .map_err(|err| SolFiError::instruction_data(err.to_string()))?;
+            let Some((tag, payload)) = data.split_first() else {
+                continue;
+            };
+            if [*tag] != solfi_instructions::SWAP {
+                continue;
+            }
+            let swap = Self::decode_swap(&classified.data.accounts, payload)?;
+            trades.extend(self.build_trade(classified, &swap));
+        }
+        Ok(trades)
+    }
+}
+
+impl TradeParser for SolFiParser {
+    fn process_trades(&mut self) -> Vec<TradeInfo> {
+        match self.parse_swaps() {
+            Ok(trades) => trades,
+            Err(err) => {
+                tracing::error!("failed to parse solfi instructions: {err}");
+                Vec::new()
+            }
+        }
+    }
+}
@@ -0,0 +1,26 @@
+pub const TOKEN_SWAP_PROGRAM_ID: &str = "SwaPpA9LAaLfeLi3a68M4DjnLqgtticKg6CnyNwgAC8";
+pub const TOKEN_SWAP_PROGRAM_NAME: &str = "SplTokenSwap";
+pub const STEP_PROGRAM_ID: &str = "SSwpMgqNDsyV7mAgN9ady4bDVu5ySjmmXejXvy2vLt1";
+pub const STEP_PROGRAM_NAME: &str = "Step";
+pub const PENGUIN_PROGRAM_ID: &str = "PSwapMdSai8tjrEXcxFeQth87xC4rRsa4VA5mhGhXkP";
+pub const PENGUIN_PROGRAM_NAME: &str = "Penguin";
+pub const SAROS_PROGRAM_ID: &str = "SSwapUtytfBdBn1b9NUGG6foMVPtcWgpRU32HToDUZr";
+pub const SAROS_PROGRAM_NAME: &str = "Saros";
+
+/// The original program and the forks that kept its instruction layout unchanged; FluxBeam
+/// is one too and has its own module.
+pub const TOKEN_SWAP_PROGRAM_IDS: [&str; 4] = [
+    TOKEN_SWAP_PROGRAM_ID,
+    STEP_PROGRAM_ID,
+    PENGUIN_PROGRAM_ID,
+    SAROS_PROGRAM_ID,
+];
+
+pub mod discriminators {
+    /// SPL token-swap layout: a single leading tag selects the instruction.
+    pub mod token_swap_instructions {
+        pub const SWAP: [u8; 1] = [1];
+        pub const DEPOSIT_ALL_TOKEN_TYPES: [u8; 1] = [2];
+        pub const WITHDRAW_ALL_TOKEN_TYPES: [u8; 1] = [3];
+    }
+}
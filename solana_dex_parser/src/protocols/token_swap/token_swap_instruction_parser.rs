@@ -0,0 +1,147 @@
+use std::sync::Arc;
+
+use crate::core::transaction_adapter::TransactionAdapter;
+use crate::protocols::pumpfun::binary_reader::BinaryReader;
+use crate::protocols::pumpfun::util::{decode_instruction_data, sort_by_idx, HasIdx};
+use crate::types::ClassifiedInstruction;
+
+use super::constants::discriminators::token_swap_instructions;
+use super::error::TokenSwapError;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TokenSwapTrade {
+    pub swap: String,
+    pub user: String,
+    pub user_source: String,
+    pub reserve_source: String,
+    pub reserve_destination: String,
+    pub user_destination: String,
+    pub amount_in: u64,
+    pub minimum_amount_out: u64,
+}
+
+/// Deposit or withdrawal of both reserves against an exact amount of pool tokens.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TokenSwapLiquidity {
+    pub swap: String,
+    pub user: String,
+    pub pool_mint: String,
+    pub reserve_a: String,
+    pub reserve_b: String,
+    pub pool_token_amount: u64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TokenSwapInstructionData {
+    Swap(TokenSwapTrade),
+    Deposit(TokenSwapLiquidity),
+    Withdraw(TokenSwapLiquidity),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TokenSwapInstruction {
+    pub data: TokenSwapInstructionData,
+    /// Token-swap program or fork the instruction ran on.
+    pub program_id: String,
+    pub outer_index: usize,
+    pub inner_index: Option<usize>,
+    pub slot: u64,
+    pub timestamp: u64,
+    pub signature: Arc<str>,
+    pub idx: String,
+    pub signer: Vec<String>,
+}
+
+impl HasIdx for TokenSwapInstruction {
+    fn idx(&self) -> &str {
+        &self.idx
+    }
+}
+
+pub struct TokenSwapInstructionParser {
+    adapter: TransactionAdapter,
+}
+
+impl TokenSwapInstructionParser {
+    pub fn new(adapter: TransactionAdapter) -> Self {
+        Self { adapter }
+    }
+
+    pub fn parse_instructions(
+        &self,
+        instructions: &[ClassifiedInstruction],
+    ) -> Result<Vec<TokenSwapInstruction>, TokenSwapError> {
+        let mut parsed = Vec::new();
+        for instruction in instructions {
+            let data = decode_instruction_data(&instruction.data.data)
+                .map_err(|err| TokenSwapError::instruction_data(err.to_string()))?;
+            let Some((tag, payload)) = data.split_first() else {
+                continue;
+            };
+            let Some(data) = Self::decode_instruction(instruction, *tag, payload)? else {
+                continue;
+            };
+            parsed.push(TokenSwapInstruction {
+                data,
+                program_id: instruction.program_id.clone(),
+                outer_index: instruction.outer_index,
+                inner_index: instruction.inner_index,
+                slot: self.adapter.slot(),
+                timestamp: self.adapter.block_time(),
+                signature: self.adapter.shared_signature(),
+                idx: format!(
+                    "{}-{}",
+                    instruction.outer_index,
+                    instruction.inner_index.unwrap_or(0)
+                ),
+                signer: self.adapter.signers().to_vec(),
+            });
+        }
+
+        Ok(sort_by_idx(parsed))
+    }
+
+    fn decode_instruction(
+        instruction: &ClassifiedInstruction,
+        tag: u8,
+        payload: &[u8],
+    ) -> Result<Option<TokenSwapInstructionData>, TokenSwapError> {
+        let accounts = &instruction.data.accounts;
+        let account = |index: usize| accounts.get(index).cloned().unwrap_or_default();
+        let mut reader = BinaryReader::new(payload.to_vec());
+        let data = match [tag] {
+            token_swap_instructions::SWAP => TokenSwapInstructionData::Swap(TokenSwapTrade {
+                swap: account(0),
+                user: account(2),
+                user_source: account(3),
+                reserve_source: account(4),
+                reserve_destination: account(5),
+                user_destination: account(6),
+                amount_in: reader.read_u64()?,
+                minimum_amount_out: reader.read_u64()?,
+            }),
+            token_swap_instructions::DEPOSIT_ALL_TOKEN_TYPES => {
+                TokenSwapInstructionData::Deposit(TokenSwapLiquidity {
+                    swap: account(0),
+                    user: account(2),
+                    pool_mint: account(7),
+                    reserve_a: account(5),
+                    reserve_b: account(6),
+                    pool_token_amount: reader.read_u64()?,
+                })
+            }
+            token_swap_instructions::WITHDRAW_ALL_TOKEN_TYPES => {
+                TokenSwapInstructionData::Withdraw(TokenSwapLiquidity {
+                    swap: account(0),
+                    user: account(2),
+                    pool_mint: account(3),
+                    reserve_a: account(5),
+                    reserve_b: account(6),
+                    pool_token_amount: reader.read_u64()?,
+                })
+            }
+            _ => return Ok(None),
+        };
+        Ok(Some(data))
+    }
+}
@@ -0,0 +1,129 @@
+use crate::core::amounts::convert_to_ui_amount;
+use crate::core::constants::dex_program_names;
+use crate::core::matcher::{Leg, TransferMatcher};
+use crate::core::transaction_adapter::TransactionAdapter;
+use crate::core::transaction_utils::TransactionUtils;
+use crate::protocols::simple::LiquidityParser;
+use crate::types::{ClassifiedInstruction, PoolEvent, TokenInfo, TradeType, TransferMap};
+
+use super::token_swap_instruction_parser::{
+    TokenSwapInstruction, TokenSwapInstructionData, TokenSwapInstructionParser, TokenSwapLiquidity,
+};
+
+/// Deposits and withdrawals on SPL token-swap pools and its forks; token amounts are net of token-2022
+/// transfer fees, as credited to the reserves or to the user.
+pub struct TokenSwapLiquidityParser {
+    adapter: TransactionAdapter,
+    transfer_actions: TransferMap,
+    classified_instructions: Vec<ClassifiedInstruction>,
+    instruction_parser: TokenSwapInstructionParser,
+}
+
+impl TokenSwapLiquidityParser {
+    pub fn new(
+        adapter: TransactionAdapter,
+        transfer_actions: TransferMap,
+        classified_instructions: Vec<ClassifiedInstruction>,
+    ) -> Self {
+        let instruction_parser = TokenSwapInstructionParser::new(adapter.clone());
+        Self {
+            adapter,
+            transfer_actions,
+            classified_instructions,
+            instruction_parser,
+        }
+    }
+
+    fn build_event(
+        &self,
+        instruction: &TokenSwapInstruction,
+        data: &TokenSwapLiquidity,
+        event_type: TradeType,
+    ) -> PoolEvent {
+        let matcher = TransferMatcher::new(&self.transfer_actions);
+        let token = |reserve: &str| -> Option<TokenInfo> {
+            let leg = if event_type == TradeType::Add {
+                Leg::default().to(reserve)
+            } else {
+                Leg::default().from(reserve)
+            };
+            matcher
+                .find_leg(instruction.outer_index, &leg)
+                .map(TransactionUtils::transfer_to_received_token_info)
+        };
+        let token0 = token(&data.reserve_a);
+        let token1 = token(&data.reserve_b);
+        let reserve_mint = |reserve: &str| {
+            self.adapter
+                .spl_token_map()
+                .get(reserve)
+                .map(|token| token.mint.clone())
+        };
+
+        let lp_decimals = self
+            .adapter
+            .spl_decimals_map()
+            .get(&data.pool_mint)
+            .copied();
+
+        PoolEvent {
+            user: data.user.clone(),
+            event_type,
+            program_id: Some(instruction.program_id.clone()),
+            amm: Some(dex_program_names::name(&instruction.program_id).to_string()),
+            slot: instruction.slot,
+            timestamp: instruction.timestamp,
+            signature: instruction.signature.clone(),
+            idx: instruction.idx.clone(),
+            signer: Some(instruction.signer.clone()),
+            pool_id: data.swap.clone(),
+            pool_lp_mint: Some(data.pool_mint.clone()),
+            token0_mint: token0
+                .as_ref()
+                .map(|token| token.mint.clone())
+                .or_else(|| reserve_mint(&data.reserve_a)),
+            token0_amount: token0.as_ref().map(|token| token.amount),
+            token0_amount_raw: token0.as_ref().map(|token| token.amount_raw.clone()),
+            token0_decimals: token0.as_ref().map(|token| token.decimals),
+            token1_mint: token1
+                .as_ref()
+                .map(|token| token.mint.clone())
+                .or_else(|| reserve_mint(&data.reserve_b)),
+            token1_amount: token1.as_ref().map(|token| token.amount),
+            token1_amount_raw: token1.as_ref().map(|token| token.amount_raw.clone()),
+            token1_decimals: token1.as_ref().map(|token| token.decimals),
+            lp_amount: lp_decimals
+                .map(|decimals| convert_to_ui_amount(data.pool_token_amount, decimals)),
+            lp_amount_raw: Some(data.pool_token_amount.to_string()),
+            ..PoolEvent::default()
+        }
+    }
+}
+
+impl LiquidityParser for TokenSwapLiquidityParser {
+    fn process_liquidity(&mut self) -> Vec<PoolEvent> {
+        let instructions = match self
+            .instruction_parser
+            .parse_instructions(&self.classified_instructions)
+        {
+            Ok(instructions) => instructions,
+            Err(err) => {
+                tracing::error!("failed to parse token-swap liquidity instructions: {err}");
+                return Vec::new();
+            }
+        };
+
+        instructions
+            .iter()
+            .filter_map(|instruction| match &instruction.data {
+                TokenSwapInstructionData::Deposit(data) => {
+                    Some(self.build_event(instruction, data, TradeType::Add))
+                }
+                TokenSwapInstructionData::Withdraw(data) => {
+                    Some(self.build_event(instruction, data, TradeType::Remove))
+                }
+                TokenSwapInstructionData::Swap(_) => None,
+            })
+            .collect()
+    }
+}
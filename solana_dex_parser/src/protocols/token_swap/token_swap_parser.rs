@@ -0,0 +1,103 @@
+use crate::core::constants::dex_program_names;
+use crate::core::matcher::{Leg, TransferMatcher};
+use crate::core::transaction_adapter::TransactionAdapter;
+use crate::core::transaction_utils::TransactionUtils;
+use crate::protocols::pumpfun::util::get_trade_type;
+use crate::protocols::simple::TradeParser;
+use crate::types::{ClassifiedInstruction, DexInfo, TradeInfo, TransferMap};
+
+use super::token_swap_instruction_parser::{
+    TokenSwapInstruction, TokenSwapInstructionData, TokenSwapInstructionParser, TokenSwapTrade,
+};
+
+/// Swaps on SPL token-swap pools and its forks, named after the program they ran on.
+///
+/// Both legs report the amount their destination was credited: token-2022 transfer fees, as
+/// on FluxBeam pools, are deducted from the transferred amount and listed in `fees` as
+/// `transferFee`.
+pub struct TokenSwapParser {
+    dex_info: DexInfo,
+    transfer_actions: TransferMap,
+    classified_instructions: Vec<ClassifiedInstruction>,
+    instruction_parser: TokenSwapInstructionParser,
+}
+
+impl TokenSwapParser {
+    pub fn new(
+        adapter: TransactionAdapter,
+        dex_info: DexInfo,
+        transfer_actions: TransferMap,
+        classified_instructions: Vec<ClassifiedInstruction>,
+    ) -> Self {
+        Self {
+            instruction_parser: TokenSwapInstructionParser::new(adapter),
+            dex_info,
+            transfer_actions,
+            classified_instructions,
+        }
+    }
+
+    fn build_trade(
+        &self,
+        instruction: &TokenSwapInstruction,
+        swap: &TokenSwapTrade,
+    ) -> Option<TradeInfo> {
+        let matcher = TransferMatcher::new(&self.transfer_actions);
+        let input = matcher.find_leg(
+            instruction.outer_index,
+            &Leg::between(&swap.user_source, &swap.reserve_source),
+        )?;
+        let output = matcher.find_leg(
+            instruction.outer_index,
+            &Leg::between(&swap.reserve_destination, &swap.user_destination),
+        )?;
+        let venue = dex_program_names::name(&instruction.program_id);
+        let input_token = TransactionUtils::transfer_to_received_token_info(input);
+        let output_token = TransactionUtils::transfer_to_received_token_info(output);
+        let fees = [input, output]
+            .into_iter()
+            .filter_map(|transfer| TransactionUtils::transfer_fee_info(transfer, venue))
+            .collect();
+
+        Some(TradeInfo {
+            trade_type: get_trade_type(&input_token.mint, &output_token.mint),
+            pool: vec![swap.swap.clone()],
+            input_token,
+            output_token,
+            fees,
+            user: Some(swap.user.clone()),
+            program_id: Some(instruction.program_id.clone()),
+            amm: Some(venue.to_string()),
+            route: self.dex_info.route.clone(),
+            slot: instruction.slot,
+            timestamp: instruction.timestamp,
+            signature: instruction.signature.clone(),
+            idx: instruction.idx.clone(),
+            signer: Some(instruction.signer.clone()),
+            ..TradeInfo::default()
+        })
+    }
+}
+
+impl TradeParser for TokenSwapParser {
+    fn process_trades(&mut self) -> Vec<TradeInfo> {
+        let instructions = match self
+            .instruction_parser
+            .parse_instructions(&self.classified_instructions)
+        {
+            Ok(instructions) => instructions,
+            Err(err) => {
+                tracing::error!("failed to parse token-swap instructions: {err}");
+                return Vec::new();
+            }
+        };
+
+        instructions
+            .iter()
+            .filter_map(|instruction| match &instruction.data {
+                TokenSwapInstructionData::Swap(swap) => self.build_trade(instruction, swap),
+                _ => None,
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,11 @@
+pub const VIRTUALS_PROGRAM_ID: &str = "5U3EU2ubXtK84QcRjWVmYt9RaDyA8gKxdUrPFXmZyaki";
+pub const VIRTUALS_PROGRAM_NAME: &str = "Virtuals";
+
+pub mod discriminators {
+    pub mod virtuals_instructions {
+        pub const LAUNCH: [u8; 8] = [153, 241, 93, 225, 22, 69, 74, 61];
+        pub const BUY: [u8; 8] = [102, 6, 61, 18, 1, 218, 235, 234];
+        pub const SELL: [u8; 8] = [51, 230, 133, 164, 1, 127, 131, 173];
+        pub const GRADUATE: [u8; 8] = [45, 235, 225, 181, 17, 218, 64, 130];
+    }
+}
@@ -0,0 +1,31 @@
+pub mod constants;
+pub mod error;
+pub mod virtuals_event_parser;
+pub mod virtuals_parser;
+
+use crate::core::transaction_adapter::TransactionAdapter;
+use crate::protocols::simple::{MemeEventParser, TradeParser};
+use crate::types::{ClassifiedInstruction, DexInfo, TransferMap};
+
+use virtuals_parser::{VirtualsMemeParser, VirtualsParser};
+
+pub fn build_virtuals_trade_parser(
+    adapter: TransactionAdapter,
+    dex_info: DexInfo,
+    transfer_actions: TransferMap,
+    classified_instructions: Vec<ClassifiedInstruction>,
+) -> Box<dyn TradeParser> {
+    Box::new(VirtualsParser::new(
+        adapter,
+        dex_info,
+        transfer_actions,
+        classified_instructions,
+    ))
+}
+
+pub fn build_virtuals_meme_parser(
+    adapter: TransactionAdapter,
+    transfer_actions: TransferMap,
+) -> Box<dyn MemeEventParser> {
+    Box::new(VirtualsMemeParser::new(adapter, transfer_actions))
+}
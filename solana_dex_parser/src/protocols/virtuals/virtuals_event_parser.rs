@@ -0,0 +1,187 @@
+use crate::core::constants::{dex_program_names, dex_programs};
+use crate::core::matcher::TransferMatcher;
+use crate::core::transaction_adapter::TransactionAdapter;
+use crate::protocols::pumpfun::binary_reader::BinaryReader;
+use crate::protocols::pumpfun::util::{decode_instruction_data, sort_by_idx};
+use crate::protocols::raydium::util::vault_swap_tokens;
+use crate::types::{ClassifiedInstruction, MemeEvent, TradeType, TransferMap};
+
+use super::constants::{discriminators::virtuals_instructions, VIRTUALS_PROGRAM_NAME};
+use super::error::VirtualsError;
+
+/// Account positions of `(creator, mint, bonding_curve, config)` in `launch`.
+const LAUNCH_ACCOUNTS: (usize, usize, usize, usize) = (0, 1, 2, 3);
+
+/// Account positions of `buy`/`sell`.
+struct SwapAccounts {
+    user: usize,
+    mint: usize,
+    quote_mint: usize,
+    bonding_curve: usize,
+    token_vault: usize,
+    quote_vault: usize,
+    user_token_account: usize,
+    user_quote_account: usize,
+}
+
+const SWAP_ACCOUNTS: SwapAccounts = SwapAccounts {
+    user: 0,
+    mint: 1,
+    quote_mint: 2,
+    bonding_curve: 3,
+    token_vault: 4,
+    quote_vault: 5,
+    user_token_account: 6,
+    user_quote_account: 7,
+};
+
+/// Account positions of `(mint, quote_mint, bonding_curve, pool)` in `graduate`.
+const GRADUATE_ACCOUNTS: (usize, usize, usize, usize) = (1, 2, 3, 4);
+
+/// Decodes agent-token launches, bonding-curve trades and graduations of the Virtuals
+/// launch program. Curves are priced in VIRTUAL and graduate into a Meteora DAMM v2 pool.
+pub struct VirtualsEventParser {
+    adapter: TransactionAdapter,
+    transfer_actions: TransferMap,
+}
+
+impl VirtualsEventParser {
+    pub fn new(adapter: TransactionAdapter, transfer_actions: TransferMap) -> Self {
+        Self {
+            adapter,
+            transfer_actions,
+        }
+    }
+
+    pub fn parse_instructions(
+        &self,
+        instructions: &[ClassifiedInstruction],
+    ) -> Result<Vec<MemeEvent>, VirtualsError> {
+        let mut events = Vec::new();
+        for classified in instructions {
+            let data = decode_instruction_data(&classified.data.data)
+                .map_err(|err| VirtualsError::instruction_data(err.to_string()))?;
+            if data.len() < 8 {
+                continue;
+            }
+            let accounts = &classified.data.accounts;
+            let (discriminator, payload) = data.split_at(8);
+            let event = if discriminator == virtuals_instructions::LAUNCH {
+                Some(Self::build_launch_event(accounts, payload.to_vec())?)
+            } else if discriminator == virtuals_instructions::BUY {
+                self.build_swap_event(classified, TradeType::Buy)
+            } else if discriminator == virtuals_instructions::SELL {
+                self.build_swap_event(classified, TradeType::Sell)
+            } else if discriminator == virtuals_instructions::GRADUATE {
+                Some(self.build_graduate_event(accounts))
+            } else {
+                None
+            };
+            if let Some(event) = event {
+                let idx = format!(
+                    "{}-{}",
+                    classified.outer_index,
+                    classified.inner_index.unwrap_or(0)
+                );
+                events.push(self.stamp(event, &idx));
+            }
+        }
+        Ok(sort_by_idx(events))
+    }
+
+    fn stamp(&self, mut event: MemeEvent, idx: &str) -> MemeEvent {
+        event.signature = self.adapter.shared_signature();
+        event.slot = self.adapter.slot();
+        event.timestamp = self.adapter.block_time();
+        event.idx = idx.to_string();
+        event
+    }
+
+    fn build_launch_event(
+        accounts: &[String],
+        payload: Vec<u8>,
+    ) -> Result<MemeEvent, VirtualsError> {
+        let (creator, mint, bonding_curve, config) = LAUNCH_ACCOUNTS;
+        let account = |index: usize| accounts.get(index).cloned().unwrap_or_default();
+        let mut reader = BinaryReader::new(payload);
+        Ok(MemeEvent {
+            event_type: TradeType::Create,
+            user: account(creator),
+            base_mint: account(mint),
+            name: Some(reader.read_string()?),
+            symbol: Some(reader.read_string()?),
+            uri: Some(reader.read_string()?),
+            protocol: Some(VIRTUALS_PROGRAM_NAME.to_string()),
+            platform_config: accounts.get(config).cloned(),
+            creator: accounts.get(creator).cloned(),
+            bonding_curve: accounts.get(bonding_curve).cloned(),
+            ..MemeEvent::default()
+        })
+    }
+
+    /// Swap legs from the curve vault transfers: a buy pays VIRTUAL into the quote vault
+    /// and receives agent tokens from the token vault, a sell the reverse.
+    fn build_swap_event(
+        &self,
+        classified: &ClassifiedInstruction,
+        event_type: TradeType,
+    ) -> Option<MemeEvent> {
+        let accounts = &classified.data.accounts;
+        let account = |index: usize| accounts.get(index).cloned().unwrap_or_default();
+        let positions = &SWAP_ACCOUNTS;
+        let user_token_account = account(positions.user_token_account);
+        let user_quote_account = account(positions.user_quote_account);
+        let token_vault = account(positions.token_vault);
+        let quote_vault = account(positions.quote_vault);
+
+        let matcher = TransferMatcher::new(&self.transfer_actions);
+        let (input_token, output_token) = if event_type == TradeType::Buy {
+            vault_swap_tokens(
+                &matcher,
+                classified.outer_index,
+                &user_quote_account,
+                &quote_vault,
+                &token_vault,
+                &user_token_account,
+            )?
+        } else {
+            vault_swap_tokens(
+                &matcher,
+                classified.outer_index,
+                &user_token_account,
+                &token_vault,
+                &quote_vault,
+                &user_quote_account,
+            )?
+        };
+
+        Some(MemeEvent {
+            event_type,
+            user: account(positions.user),
+            base_mint: account(positions.mint),
+            quote_mint: account(positions.quote_mint),
+            input_token: Some(input_token),
+            output_token: Some(output_token),
+            protocol: Some(VIRTUALS_PROGRAM_NAME.to_string()),
+            bonding_curve: accounts.get(positions.bonding_curve).cloned(),
+            pool: accounts.get(positions.bonding_curve).cloned(),
+            ..MemeEvent::default()
+        })
+    }
+
+    fn build_graduate_event(&self, accounts: &[String]) -> MemeEvent {
+        let (mint, quote_mint, bonding_curve, pool) = GRADUATE_ACCOUNTS;
+        let account = |index: usize| accounts.get(index).cloned().unwrap_or_default();
+        MemeEvent {
+            event_type: TradeType::Migrate,
+            user: self.adapter.signer(),
+            base_mint: account(mint),
+            quote_mint: account(quote_mint),
+            protocol: Some(VIRTUALS_PROGRAM_NAME.to_string()),
+            bonding_curve: accounts.get(bonding_curve).cloned(),
+            pool: accounts.get(pool).cloned(),
+            pool_dex: Some(dex_program_names::name(dex_programs::METEORA_DAMM_V2).to_string()),
+            ..MemeEvent::default()
+        }
+    }
+}
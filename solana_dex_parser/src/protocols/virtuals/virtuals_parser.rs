@@ -0,0 +1,102 @@
+use crate::core::instruction_classifier::InstructionClassifier;
+use crate::core::transaction_adapter::TransactionAdapter;
+use crate::protocols::simple::{MemeEventParser, TradeParser};
+use crate::types::{ClassifiedInstruction, DexInfo, MemeEvent, TradeInfo, TradeType, TransferMap};
+
+use super::constants::{VIRTUALS_PROGRAM_ID, VIRTUALS_PROGRAM_NAME};
+use super::error::VirtualsError;
+use super::virtuals_event_parser::VirtualsEventParser;
+
+/// Bonding-curve buys and sells of Virtuals agent tokens.
+pub struct VirtualsParser {
+    adapter: TransactionAdapter,
+    dex_info: DexInfo,
+    classified_instructions: Vec<ClassifiedInstruction>,
+    event_parser: VirtualsEventParser,
+}
+
+impl VirtualsParser {
+    pub fn new(
+        adapter: TransactionAdapter,
+        dex_info: DexInfo,
+        transfer_actions: TransferMap,
+        classified_instructions: Vec<ClassifiedInstruction>,
+    ) -> Self {
+        let event_parser = VirtualsEventParser::new(adapter.clone(), transfer_actions);
+        Self {
+            adapter,
+            dex_info,
+            classified_instructions,
+            event_parser,
+        }
+    }
+
+    fn parse_events(&self) -> Result<Vec<MemeEvent>, VirtualsError> {
+        self.event_parser
+            .parse_instructions(&self.classified_instructions)
+    }
+
+    fn build_trade(&self, event: MemeEvent) -> Option<TradeInfo> {
+        Some(TradeInfo {
+            trade_type: event.event_type,
+            pool: event.pool.into_iter().collect(),
+            input_token: event.input_token?,
+            output_token: event.output_token?,
+            user: Some(event.user),
+            program_id: Some(VIRTUALS_PROGRAM_ID.to_string()),
+            amm: Some(VIRTUALS_PROGRAM_NAME.to_string()),
+            route: self.dex_info.route.clone(),
+            slot: event.slot,
+            timestamp: event.timestamp,
+            signature: event.signature,
+            idx: event.idx,
+            signer: Some(self.adapter.signers().to_vec()),
+            ..TradeInfo::default()
+        })
+    }
+}
+
+impl TradeParser for VirtualsParser {
+    fn process_trades(&mut self) -> Vec<TradeInfo> {
+        match self.parse_events() {
+            Ok(events) => events
+                .into_iter()
+                .filter(|event| matches!(event.event_type, TradeType::Buy | TradeType::Sell))
+                .filter_map(|event| self.build_trade(event))
+                .collect(),
+            Err(err) => {
+                tracing::error!("failed to parse virtuals trade events: {err}");
+                Vec::new()
+            }
+        }
+    }
+}
+
+pub struct VirtualsMemeParser {
+    adapter: TransactionAdapter,
+    transfer_actions: TransferMap,
+}
+
+impl VirtualsMemeParser {
+    pub fn new(adapter: TransactionAdapter, transfer_actions: TransferMap) -> Self {
+        Self {
+            adapter,
+            transfer_actions,
+        }
+    }
+}
+
+impl MemeEventParser for VirtualsMemeParser {
+    fn process_events(&mut self) -> Vec<MemeEvent> {
+        let classifier = InstructionClassifier::new(&self.adapter);
+        let instructions = classifier.get_instructions(VIRTUALS_PROGRAM_ID);
+        let parser = VirtualsEventParser::new(self.adapter.clone(), self.transfer_actions.clone());
+        match parser.parse_instructions(&instructions) {
+            Ok(events) => events,
+            Err(err) => {
+                tracing::error!("failed to parse virtuals meme events: {err}");
+                Vec::new()
+            }
+        }
+    }
+}
@@ -0,0 +1,9 @@
+pub const ZEROFI_PROGRAM_ID: &str = "ZERor4xhbUycZ6gb9ntrhqscUcZmAbQDjEAtCf4hbZY";
+pub const ZEROFI_PROGRAM_NAME: &str = "ZeroFi";
+
+pub mod discriminators {
+    /// ZeroFi is a native program: a single leading tag selects the instruction.
+    pub mod zerofi_instructions {
+        pub const SWAP: [u8; 1] = [6];
+    }
+}
@@ -0,0 +1,137 @@
+use crate::core::matcher::TransferMatcher;
+use crate::core::transaction_adapter::TransactionAdapter;
+use crate::protocols::pumpfun::binary_reader::BinaryReader;
+use crate::protocols::pumpfun::util::{decode_instruction_data, get_trade_type};
+use crate::protocols::raydium::util::vault_swap_tokens;
+use crate::protocols::simple::TradeParser;
+use crate::types::{ClassifiedInstruction, DexInfo, TradeInfo, TransferMap};
+
+use super::constants::{
+    discriminators::zerofi_instructions, ZEROFI_PROGRAM_ID, ZEROFI_PROGRAM_NAME,
+};
+use super::error::ZeroFiError;
+
+/// Decoded `swap`. Accounts: pair, input vault info, input vault, output vault info, output
+/// vault, user input account, user output account, user; the payload is `amount_in` and the
+/// quoted `desired_amount_out`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ZeroFiSwapInstruction {
+    pub pair: String,
+    pub vault_in: String,
+    pub vault_out: String,
+    pub user_source: String,
+    pub user_destination: String,
+    pub user: String,
+    pub amount_in: u64,
+    pub desired_amount_out: u64,
+}
+
+/// Swaps against ZeroFi's oracle-quoted pairs, usually reached as a Jupiter hop.
+pub struct ZeroFiParser {
+    adapter: TransactionAdapter,
+    dex_info: DexInfo,
+    transfer_actions: TransferMap,
+    classified_instructions: Vec<ClassifiedInstruction>,
+}
+
+impl ZeroFiParser {
+    pub fn new(
+        adapter: TransactionAdapter,
+        dex_info: DexInfo,
+        transfer_actions: TransferMap,
+        classified_instructions: Vec<ClassifiedInstruction>,
+    ) -> Self {
+        Self {
+            adapter,
+            dex_info,
+            transfer_actions,
+            classified_instructions,
+        }
+    }
+
+    pub fn decode_swap(
+        accounts: &[String],
+        payload: &[u8],
+    ) -> Result<ZeroFiSwapInstruction, ZeroFiError> {
+        let account = |index: usize| accounts.get(index).cloned().unwrap_or_default();
+        let mut reader = BinaryReader::new(payload.to_vec());
+        Ok(ZeroFiSwapInstruction {
+            pair: account(0),
+            vault_in: account(2),
+            vault_out: account(4),
+            user_source: account(5),
+            user_destination: account(6),
+            user: account(7),
+            amount_in: reader.read_u64()?,
+            desired_amount_out: reader.read_u64()?,
+        })
+    }
+
+    fn build_trade(
+        &self,
+        classified: &ClassifiedInstruction,
+        swap: &ZeroFiSwapInstruction,
+    ) -> Option<TradeInfo> {
+        let matcher = TransferMatcher::new(&self.transfer_actions);
+        let (input_token, output_token) = vault_swap_tokens(
+            &matcher,
+            classified.outer_index,
+            &swap.user_source,
+            &swap.vault_in,
+            &swap.vault_out,
+            &swap.user_destination,
+        )?;
+
+        Some(TradeInfo {
+            trade_type: get_trade_type(&input_token.mint, &output_token.mint),
+            pool: vec![swap.pair.clone()],
+            oracle_price: (input_token.amount > 0.0)
+                .then(|| output_token.amount / input_token.amount),
+            input_token,
+            output_token,
+            user: Some(swap.user.clone()),
+            program_id: Some(ZEROFI_PROGRAM_ID.to_string()),
+            amm: Some(ZEROFI_PROGRAM_NAME.to_string()),
+            route: self.dex_info.route.clone(),
+            slot: self.adapter.slot(),
+            timestamp: self.adapter.block_time(),
+            signature: self.adapter.shared_signature(),
+            idx: format!(
+                "{}-{}",
+                classified.outer_index,
+                classified.inner_index.unwrap_or(0)
+            ),
+            signer: Some(self.adapter.signers().to_vec()),
+            ..TradeInfo::default()
+        })
+    }
+
+    fn parse_swaps(&self) -> Result<Vec<TradeInfo>, ZeroFiError> {
+        let mut trades = Vec::new();
+        for classified in &self.classified_instructions {
+            let data = decode_instruction_data(&classified.data.data)
+                .map_err(|err| ZeroFiError::instruction_data(err.to_string()))?;
+            let Some((tag, payload)) = data.split_first() else {
+                continue;
+            };
+            if [*tag] != zerofi_instructions::SWAP {
+                continue;
+            }
+            let swap = Self::decode_swap(&classified.data.accounts, payload)?;
+            trades.extend(self.build_trade(classified, &swap));
+        }
+        Ok(trades)
+    }
+}
+
+impl TradeParser for ZeroFiParser {
+    fn process_trades(&mut self) -> Vec<TradeInfo> {
+        match self.parse_swaps() {
+            Ok(trades) => trades,
+            Err(err) => {
+                tracing::error!("failed to parse zerofi instructions: {err}");
+                Vec::new()
+            }
+        }
+    }
+}
@@ -1,17 +1,54 @@
 use std::collections::HashMap;
+#[cfg(feature = "rpc")]
+use std::future::Future;
 use std::str::FromStr;
+#[cfg(feature = "rpc")]
+use std::time::Duration;
 
 use anyhow::{anyhow, Context, Result};
+use base64::{engine::general_purpose::STANDARD as BASE64_STANDARD, Engine as _};
+use serde_json::Value;
+use solana_account_decoder::parse_token::UiTokenAmount;
+use solana_account_decoder::{UiAccount, UiAccountData};
+#[cfg(feature = "rpc")]
+use solana_client::client_error::{ClientError, ClientErrorKind, Result as ClientResult};
+#[cfg(feature = "rpc")]
+use solana_client::nonblocking::rpc_client::RpcClient as NonblockingRpcClient;
 use solana_client::rpc_client::RpcClient;
+#[cfg(feature = "rpc")]
+use solana_client::rpc_config::RpcBlockConfig;
 use solana_client::rpc_config::RpcTransactionConfig;
+#[cfg(feature = "rpc")]
+use solana_client::rpc_custom_error::{
+    JSON_RPC_SERVER_ERROR_LONG_TERM_STORAGE_SLOT_SKIPPED, JSON_RPC_SERVER_ERROR_SLOT_SKIPPED,
+};
+#[cfg(feature = "rpc")]
+use solana_client::rpc_request::RpcError;
+use solana_client::rpc_response::RpcSimulateTransactionResult;
 use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::instruction::InstructionError;
+use solana_sdk::message::v0::LoadedAddresses;
+use solana_sdk::message::MessageHeader;
 use solana_sdk::signature::Signature;
+use solana_sdk::transaction::{TransactionError, VersionedTransaction};
+use solana_transaction_status::option_serializer::OptionSerializer;
+#[cfg(feature = "rpc")]
+use solana_transaction_status::TransactionDetails;
 use solana_transaction_status::{
-    EncodedConfirmedTransactionWithStatusMeta, EncodedTransaction, UiCompiledInstruction,
-    UiInnerInstructions, UiInstruction, UiLoadedAddresses, UiMessage, UiParsedInstruction,
+    ConfirmedTransactionWithStatusMeta, EncodedConfirmedTransactionWithStatusMeta,
+    EncodedTransaction, EncodedTransactionWithStatusMeta, TransactionStatusMeta,
+    TransactionTokenBalance, TransactionWithStatusMeta, UiCompiledInstruction, UiInnerInstructions,
+    UiInstruction, UiLoadedAddresses, UiMessage, UiParsedInstruction, UiRawMessage, UiTransaction,
     UiTransactionEncoding, UiTransactionStatusMeta, UiTransactionTokenBalance,
+    VersionedTransactionWithStatusMeta,
 };
 
+#[cfg(feature = "rpc")]
+use crate::config::ParseConfig;
+use crate::core::compute_budget::instructions_budget;
+use crate::core::constants::system_programs;
+#[cfg(feature = "rpc")]
+use crate::types::SolanaBlock;
 use crate::types::{
     BalanceChange, InnerInstruction, SolanaInstruction, SolanaTransaction, TokenAmount,
     TokenBalance, TransactionMeta, TransactionStatus,
@@ -19,6 +56,12 @@ use crate::types::{
 
 type MessageExtraction = (Vec<SolanaInstruction>, Vec<String>, Vec<String>, String);
 
+/// Placeholder blockhash used when rebuilding RPC payloads; the normalized form does not keep it.
+const EMPTY_BLOCKHASH: &str = "11111111111111111111111111111111";
+
+/// Lamports charged per signature, the base transaction fee.
+const LAMPORTS_PER_SIGNATURE: u64 = 5_000;
+
 /// Fetch a transaction from RPC and convert it into the internal SolanaTransaction type.
 pub fn fetch_transaction(rpc_url: &str, signature: &str) -> Result<SolanaTransaction> {
     let client = RpcClient::new(rpc_url.to_string());
@@ -35,6 +78,528 @@ pub fn fetch_transaction(rpc_url: &str, signature: &str) -> Result<SolanaTransac
     convert_transaction(encoded)
 }
 
+/// Attempts and backoff of the async fetchers.
+#[cfg(feature = "rpc")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Requests made before giving up, the first one included.
+    pub attempts: u32,
+    /// Wait before the first retry, doubled for every retry after it.
+    pub initial_backoff: Duration,
+    /// Longest wait between two attempts.
+    pub max_backoff: Duration,
+}
+
+#[cfg(feature = "rpc")]
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            attempts: 4,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(4),
+        }
+    }
+}
+
+#[cfg(feature = "rpc")]
+impl RetryPolicy {
+    /// Wait before retry number `retry`, counting from 0.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(2u32.saturating_pow(retry))
+            .min(self.max_backoff)
+    }
+
+    /// Run `request` until it succeeds, fails with an error `retriable` rejects, or the
+    /// attempts run out; errors come with the number of attempts made.
+    async fn run<T, F, Fut>(
+        &self,
+        mut request: F,
+        retriable: fn(&ClientError) -> bool,
+    ) -> std::result::Result<T, (ClientError, u32)>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = ClientResult<T>>,
+    {
+        let attempts = self.attempts.max(1);
+        let mut attempt = 1;
+        loop {
+            match request().await {
+                Ok(value) => return Ok(value),
+                Err(err) if attempt >= attempts || !retriable(&err) => return Err((err, attempt)),
+                Err(err) => {
+                    tracing::debug!("RPC request failed (attempt {attempt}): {err}");
+                    tokio::time::sleep(self.backoff(attempt - 1)).await;
+                    attempt += 1;
+                }
+            }
+        }
+    }
+}
+
+/// Options of [`DexParser::backfill`](crate::DexParser::backfill).
+#[cfg(feature = "rpc")]
+#[derive(Clone, Debug)]
+pub struct BackfillOptions {
+    /// Blocks fetched at the same time.
+    pub concurrency: usize,
+    pub retry: RetryPolicy,
+    pub config: Option<ParseConfig>,
+}
+
+#[cfg(feature = "rpc")]
+impl Default for BackfillOptions {
+    fn default() -> Self {
+        Self {
+            concurrency: 4,
+            retry: RetryPolicy::default(),
+            config: None,
+        }
+    }
+}
+
+/// Fetch a transaction from RPC without blocking and convert it into the internal
+/// SolanaTransaction type.
+///
+/// Failed requests are retried as `retry` allows, which also covers a transaction the node
+/// does not return yet right after it landed. An invalid signature fails immediately.
+#[cfg(feature = "rpc")]
+pub async fn fetch_transaction_with_retry(
+    rpc_url: &str,
+    signature: &str,
+    retry: &RetryPolicy,
+) -> Result<SolanaTransaction> {
+    let client = NonblockingRpcClient::new(rpc_url.to_string());
+    let signature = Signature::from_str(signature).context("invalid signature")?;
+    let config = RpcTransactionConfig {
+        encoding: Some(UiTransactionEncoding::Json),
+        commitment: Some(CommitmentConfig::confirmed()),
+        max_supported_transaction_version: Some(0),
+    };
+
+    let encoded = retry
+        .run(
+            || client.get_transaction_with_config(&signature, config),
+            |_| true,
+        )
+        .await
+        .map_err(|(err, attempts)| {
+            anyhow::Error::new(err).context(format!(
+                "failed to fetch transaction {signature} after {attempts} attempts"
+            ))
+        })?;
+    convert_transaction(encoded)
+}
+
+/// Fetch a block with `getBlock` without blocking and convert its transactions.
+///
+/// Returns `None` for a slot the cluster skipped, which is not retried. Vote transactions
+/// are kept so positions in the block stay exact; see [`is_vote_transaction`].
+#[cfg(feature = "rpc")]
+pub async fn fetch_block_with_retry(
+    rpc_url: &str,
+    slot: u64,
+    retry: &RetryPolicy,
+) -> Result<Option<SolanaBlock>> {
+    let client = NonblockingRpcClient::new(rpc_url.to_string());
+    let config = RpcBlockConfig {
+        encoding: Some(UiTransactionEncoding::Json),
+        transaction_details: Some(TransactionDetails::Full),
+        rewards: Some(false),
+        commitment: Some(CommitmentConfig::confirmed()),
+        max_supported_transaction_version: Some(0),
+    };
+
+    let block = match retry
+        .run(
+            || client.get_block_with_config(slot, config),
+            |err| !is_skipped_slot(err),
+        )
+        .await
+    {
+        Ok(block) => block,
+        Err((err, _)) if is_skipped_slot(&err) => return Ok(None),
+        Err((err, attempts)) => {
+            return Err(anyhow::Error::new(err).context(format!(
+                "failed to fetch block {slot} after {attempts} attempts"
+            )))
+        }
+    };
+
+    let transactions = block
+        .transactions
+        .unwrap_or_default()
+        .into_iter()
+        .map(|transaction| {
+            convert_transaction(EncodedConfirmedTransactionWithStatusMeta {
+                slot,
+                transaction,
+                block_time: block.block_time,
+            })
+        })
+        .collect::<Result<_>>()
+        .with_context(|| format!("failed to convert block {slot}"))?;
+    Ok(Some(SolanaBlock {
+        slot,
+        block_time: block.block_time.map(|time| time as u64),
+        epoch: None,
+        leader: None,
+        transactions,
+    }))
+}
+
+#[cfg(feature = "rpc")]
+fn is_skipped_slot(err: &ClientError) -> bool {
+    matches!(
+        err.kind(),
+        ClientErrorKind::RpcError(RpcError::RpcResponseError { code, .. })
+            if *code == JSON_RPC_SERVER_ERROR_SLOT_SKIPPED
+                || *code == JSON_RPC_SERVER_ERROR_LONG_TERM_STORAGE_SLOT_SKIPPED
+    )
+}
+
+/// Whether a transaction is a validator vote, which carries no DEX activity.
+pub fn is_vote_transaction(tx: &SolanaTransaction) -> bool {
+    tx.instructions
+        .iter()
+        .any(|instruction| instruction.program_id == system_programs::VOTE)
+}
+
+/// Convert a saved `getTransaction` JSON payload into the internal SolanaTransaction type.
+///
+/// Both the bare result object and the full JSON-RPC response envelope are accepted.
+pub fn transaction_from_rpc_json(value: Value) -> Result<SolanaTransaction> {
+    let value = match value {
+        Value::Object(mut object) if object.contains_key("jsonrpc") => object
+            .remove("result")
+            .filter(|result| !result.is_null())
+            .context("RPC response has no result")?,
+        other => other,
+    };
+    let encoded: EncodedConfirmedTransactionWithStatusMeta =
+        serde_json::from_value(value).context("failed to deserialize RPC transaction")?;
+    convert_transaction(encoded)
+}
+
+/// Decode a wire-format (bincode) `VersionedTransaction` and its status meta into the
+/// internal SolanaTransaction type.
+///
+/// For transactions stored as raw bytes rather than RPC JSON. Lookup-table accounts are
+/// resolved from `meta.loaded_addresses`, which must hold the addresses the transaction loaded.
+pub fn transaction_from_wire(
+    bytes: &[u8],
+    meta: TransactionStatusMeta,
+    slot: u64,
+    block_time: Option<i64>,
+) -> Result<SolanaTransaction> {
+    let transaction: VersionedTransaction =
+        bincode::deserialize(bytes).context("failed to decode wire transaction")?;
+    convert_versioned(transaction, meta, slot, block_time)
+}
+
+/// Same as [`transaction_from_wire`] for base64 encoded transaction bytes.
+pub fn transaction_from_base64(
+    encoded: &str,
+    meta: TransactionStatusMeta,
+    slot: u64,
+    block_time: Option<i64>,
+) -> Result<SolanaTransaction> {
+    let bytes = BASE64_STANDARD
+        .decode(encoded.trim())
+        .context("transaction is not valid base64")?;
+    transaction_from_wire(&bytes, meta, slot, block_time)
+}
+
+/// What a `simulateTransaction` result leaves out and [`transaction_from_simulation`] needs.
+#[derive(Clone, Debug, Default)]
+pub struct SimulationContext {
+    /// Addresses passed in the `accounts` config of the simulation, in order.
+    pub addresses: Vec<String>,
+    /// States of `addresses` before the simulation, in the same order, e.g. from
+    /// `getMultipleAccounts`; `None` for accounts that do not exist yet.
+    pub pre_accounts: Vec<Option<UiAccount>>,
+    /// Addresses the transaction loads from lookup tables.
+    pub loaded_addresses: LoadedAddresses,
+    /// Slot of the simulation, from the response context.
+    pub slot: u64,
+}
+
+/// Convert the result of simulating `transaction` into the internal SolanaTransaction type,
+/// so trades and fees can be seen before sending it.
+///
+/// Inner instructions and logs come from the simulation, which must run with
+/// `innerInstructions: true`. SOL and token balances are known for the addresses of
+/// `context` only: the simulation returns their states after, `context.pre_accounts` holds
+/// those before, and token balances need both in `jsonParsed` encoding. The fee is the base
+/// fee per signature plus the priority fee of the compute budget instructions.
+pub fn transaction_from_simulation(
+    transaction: VersionedTransaction,
+    simulation: &RpcSimulateTransactionResult,
+    context: &SimulationContext,
+) -> Result<SolanaTransaction> {
+    let mut account_keys: Vec<String> = transaction
+        .message
+        .static_account_keys()
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    let loaded = &context.loaded_addresses;
+    account_keys.extend(
+        loaded
+            .writable
+            .iter()
+            .chain(&loaded.readonly)
+            .map(ToString::to_string),
+    );
+
+    let post_accounts = simulation.accounts.as_deref().unwrap_or_default();
+    let mut pre_balances = vec![0; account_keys.len()];
+    let mut post_balances = vec![0; account_keys.len()];
+    let mut pre_token_balances = Vec::new();
+    let mut post_token_balances = Vec::new();
+    for (position, address) in context.addresses.iter().enumerate() {
+        let Some(index) = account_keys.iter().position(|key| key == address) else {
+            continue;
+        };
+        let (Some(pre), Some(post)) = (
+            context.pre_accounts.get(position),
+            post_accounts.get(position),
+        ) else {
+            continue;
+        };
+        pre_balances[index] = pre.as_ref().map_or(0, |account| account.lamports);
+        post_balances[index] = post.as_ref().map_or(0, |account| account.lamports);
+        pre_token_balances.extend(
+            pre.as_ref()
+                .and_then(|account| token_balance(index, account)),
+        );
+        post_token_balances.extend(
+            post.as_ref()
+                .and_then(|account| token_balance(index, account)),
+        );
+    }
+
+    let signatures = u64::from(transaction.message.header().num_required_signatures);
+    let meta = TransactionStatusMeta {
+        status: simulation.err.clone().map_or(Ok(()), Err),
+        pre_balances,
+        post_balances,
+        log_messages: simulation.logs.clone(),
+        pre_token_balances: Some(pre_token_balances),
+        post_token_balances: Some(post_token_balances),
+        loaded_addresses: context.loaded_addresses.clone(),
+        compute_units_consumed: simulation.units_consumed,
+        ..TransactionStatusMeta::default()
+    };
+    let mut tx = convert_versioned(transaction, meta, context.slot, None)?;
+
+    tx.inner_instructions =
+        convert_inner_instructions(simulation.inner_instructions.as_ref(), &account_keys)?;
+    let priority_fee = instructions_budget(&tx.instructions)
+        .and_then(|budget| budget.priority_fee)
+        .and_then(|fee| fee.amount.parse::<u64>().ok())
+        .unwrap_or(0);
+    tx.meta.fee = signatures * LAMPORTS_PER_SIGNATURE + priority_fee;
+    Ok(tx)
+}
+
+/// Token balance of a `jsonParsed` token account at `index` in the account keys.
+fn token_balance(index: usize, account: &UiAccount) -> Option<TransactionTokenBalance> {
+    let UiAccountData::Json(data) = &account.data else {
+        return None;
+    };
+    if data.parsed["type"] != "account" {
+        return None;
+    }
+    let info = &data.parsed["info"];
+    Some(TransactionTokenBalance {
+        account_index: u8::try_from(index).ok()?,
+        mint: info["mint"].as_str()?.to_string(),
+        ui_token_amount: serde_json::from_value(info["tokenAmount"].clone()).ok()?,
+        owner: info["owner"].as_str()?.to_string(),
+        program_id: account.owner.clone(),
+    })
+}
+
+/// Encode a decoded transaction and its status meta the way RPC would and convert it.
+fn convert_versioned(
+    transaction: VersionedTransaction,
+    meta: TransactionStatusMeta,
+    slot: u64,
+    block_time: Option<i64>,
+) -> Result<SolanaTransaction> {
+    let confirmed = ConfirmedTransactionWithStatusMeta {
+        slot,
+        tx_with_meta: TransactionWithStatusMeta::Complete(VersionedTransactionWithStatusMeta {
+            transaction,
+            meta,
+        }),
+        block_time,
+    };
+    let encoded = confirmed
+        .encode(UiTransactionEncoding::Json, Some(0))
+        .map_err(|err| anyhow!("failed to encode wire transaction: {err}"))?;
+    convert_transaction(encoded)
+}
+
+/// Rebuild a JSON-encoded RPC transaction from the internal SolanaTransaction type.
+///
+/// Data the normalized form does not keep (blockhash, exact error, balances of
+/// untouched accounts) is filled with placeholders, and `transfers` plus token balance
+/// changes are dropped, so the result is meant for fixtures rather than replaying.
+pub fn transaction_to_rpc(
+    tx: &SolanaTransaction,
+) -> Result<EncodedConfirmedTransactionWithStatusMeta> {
+    let account_keys = collect_account_keys(tx);
+    let index_of = |key: &str| -> Result<u8> {
+        let index = account_keys
+            .iter()
+            .position(|candidate| candidate == key)
+            .context("account key missing from key list")?;
+        u8::try_from(index).map_err(|_| anyhow!("too many account keys to encode ({index})"))
+    };
+    let compile = |instruction: &SolanaInstruction| -> Result<UiCompiledInstruction> {
+        Ok(UiCompiledInstruction {
+            program_id_index: index_of(&instruction.program_id)?,
+            accounts: instruction
+                .accounts
+                .iter()
+                .map(|account| index_of(account))
+                .collect::<Result<_>>()?,
+            data: instruction.data.clone(),
+            stack_height: instruction.stack_height,
+        })
+    };
+    let token_balances = |balances: &[TokenBalance]| -> Result<Vec<UiTransactionTokenBalance>> {
+        balances
+            .iter()
+            .map(|balance| {
+                Ok(UiTransactionTokenBalance {
+                    account_index: index_of(&balance.account)?,
+                    mint: balance.mint.clone(),
+                    ui_token_amount: UiTokenAmount {
+                        ui_amount: balance.ui_token_amount.ui_amount,
+                        decimals: balance.ui_token_amount.decimals,
+                        amount: balance.ui_token_amount.amount.clone(),
+                        ui_amount_string: balance
+                            .ui_token_amount
+                            .ui_amount
+                            .map(|amount| amount.to_string())
+                            .unwrap_or_default(),
+                    },
+                    owner: balance.owner.clone().into(),
+                    program_id: OptionSerializer::Skip,
+                })
+            })
+            .collect()
+    };
+
+    let instructions = tx.instructions.iter().map(compile).collect::<Result<_>>()?;
+    let inner_instructions = tx
+        .inner_instructions
+        .iter()
+        .map(|set| {
+            Ok(UiInnerInstructions {
+                index: u8::try_from(set.index)
+                    .map_err(|_| anyhow!("inner instruction index {} out of range", set.index))?,
+                instructions: set
+                    .instructions
+                    .iter()
+                    .map(|ix| compile(ix).map(UiInstruction::Compiled))
+                    .collect::<Result<_>>()?,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let (pre_balances, post_balances) = account_keys
+        .iter()
+        .map(|key| {
+            tx.meta
+                .sol_balance_changes
+                .get(key)
+                .map(|change| (change.pre.max(0) as u64, change.post.max(0) as u64))
+                .unwrap_or_default()
+        })
+        .unzip();
+    let err = match tx.meta.status {
+        TransactionStatus::Failed => Some(tx.meta.err.clone().unwrap_or(
+            TransactionError::InstructionError(0, InstructionError::GenericError),
+        )),
+        _ => None,
+    };
+
+    let meta = UiTransactionStatusMeta {
+        status: err.clone().map_or(Ok(()), Err),
+        err,
+        fee: tx.meta.fee,
+        pre_balances,
+        post_balances,
+        inner_instructions: OptionSerializer::Some(inner_instructions),
+        log_messages: if tx.meta.log_messages.is_empty() {
+            OptionSerializer::None
+        } else {
+            OptionSerializer::Some(tx.meta.log_messages.clone())
+        },
+        pre_token_balances: OptionSerializer::Some(token_balances(&tx.pre_token_balances)?),
+        post_token_balances: OptionSerializer::Some(token_balances(&tx.post_token_balances)?),
+        rewards: OptionSerializer::None,
+        loaded_addresses: OptionSerializer::Skip,
+        return_data: OptionSerializer::Skip,
+        compute_units_consumed: OptionSerializer::Some(tx.meta.compute_units),
+    };
+    let message = UiRawMessage {
+        header: MessageHeader {
+            num_required_signatures: u8::try_from(tx.signers.len())
+                .map_err(|_| anyhow!("too many signers to encode"))?,
+            num_readonly_signed_accounts: 0,
+            num_readonly_unsigned_accounts: 0,
+        },
+        account_keys,
+        recent_blockhash: EMPTY_BLOCKHASH.to_string(),
+        instructions,
+        address_table_lookups: None,
+    };
+
+    Ok(EncodedConfirmedTransactionWithStatusMeta {
+        slot: tx.slot,
+        transaction: EncodedTransactionWithStatusMeta {
+            transaction: EncodedTransaction::Json(UiTransaction {
+                signatures: vec![tx.signature.clone()],
+                message: UiMessage::Raw(message),
+            }),
+            meta: Some(meta),
+            version: None,
+        },
+        block_time: Some(tx.block_time as i64),
+    })
+}
+
+/// Signers first, then every other key in order of first appearance.
+fn collect_account_keys(tx: &SolanaTransaction) -> Vec<String> {
+    let mut keys: Vec<String> = Vec::new();
+    let mut push = |key: &String| {
+        if !keys.contains(key) {
+            keys.push(key.clone());
+        }
+    };
+    tx.signers.iter().for_each(&mut push);
+    let inner = tx
+        .inner_instructions
+        .iter()
+        .flat_map(|set| &set.instructions);
+    for instruction in tx.instructions.iter().chain(inner) {
+        push(&instruction.program_id);
+        instruction.accounts.iter().for_each(&mut push);
+    }
+    tx.pre_token_balances
+        .iter()
+        .chain(&tx.post_token_balances)
+        .for_each(|balance| push(&balance.account));
+    let mut changed: Vec<&String> = tx.meta.sol_balance_changes.keys().collect();
+    changed.sort();
+    changed.into_iter().for_each(push);
+    keys
+}
+
 fn convert_transaction(tx: EncodedConfirmedTransactionWithStatusMeta) -> Result<SolanaTransaction> {
     let meta = tx
         .transaction
@@ -45,7 +610,7 @@ fn convert_transaction(tx: EncodedConfirmedTransactionWithStatusMeta) -> Result<
         extract_message(&tx.transaction.transaction, meta)?;
 
     let inner_instructions =
-        convert_inner_instructions(meta.inner_instructions.as_ref().into(), &account_keys);
+        convert_inner_instructions(meta.inner_instructions.as_ref().into(), &account_keys)?;
     let pre_token_balances =
         convert_token_balances(meta.pre_token_balances.as_ref().into(), &account_keys);
     let post_token_balances =
@@ -71,6 +636,9 @@ fn convert_transaction(tx: EncodedConfirmedTransactionWithStatusMeta) -> Result<
             },
             sol_balance_changes: collect_sol_balance_changes(meta, &account_keys),
             token_balance_changes: HashMap::new(),
+            log_messages: Option::<Vec<String>>::from(meta.log_messages.clone())
+                .unwrap_or_default(),
+            err: meta.err.clone(),
         },
     };
 
@@ -105,7 +673,7 @@ fn extract_message(
                 .instructions
                 .iter()
                 .map(|ix| convert_compiled_instruction(ix, &account_keys))
-                .collect();
+                .collect::<Result<_>>()?;
             Ok((instructions, account_keys, signers, signature))
         }
         UiMessage::Parsed(parsed) => {
@@ -125,7 +693,7 @@ fn extract_message(
                 .instructions
                 .iter()
                 .map(|ix| convert_ui_instruction(ix, &account_keys))
-                .collect();
+                .collect::<Result<_>>()?;
             Ok((instructions, account_keys, signers, signature))
         }
     }
@@ -141,21 +709,22 @@ fn append_loaded_addresses(keys: &mut Vec<String>, meta: &UiTransactionStatusMet
 fn convert_inner_instructions(
     sets: Option<&Vec<UiInnerInstructions>>,
     account_keys: &[String],
-) -> Vec<InnerInstruction> {
-    sets.map(|inner_sets| {
+) -> Result<Vec<InnerInstruction>> {
+    sets.map_or(Ok(Vec::new()), |inner_sets| {
         inner_sets
             .iter()
-            .map(|set| InnerInstruction {
-                index: set.index as usize,
-                instructions: set
-                    .instructions
-                    .iter()
-                    .map(|ix| convert_ui_instruction(ix, account_keys))
-                    .collect(),
+            .map(|set| {
+                Ok(InnerInstruction {
+                    index: set.index as usize,
+                    instructions: set
+                        .instructions
+                        .iter()
+                        .map(|ix| convert_ui_instruction(ix, account_keys))
+                        .collect::<Result<_>>()?,
+                })
             })
             .collect()
     })
-    .unwrap_or_default()
 }
 
 fn convert_token_balances(
@@ -206,43 +775,53 @@ fn collect_sol_balance_changes(
     changes
 }
 
+/// Account key at `index`. Parsers read accounts by position, so an index past the keys
+/// (e.g. a v0 payload without `loadedAddresses`) fails the conversion instead of shifting
+/// or blanking accounts.
+fn account_key(account_keys: &[String], index: u8) -> Result<String> {
+    account_keys.get(index as usize).cloned().ok_or_else(|| {
+        anyhow!(
+            "account index {index} is past the {} resolved account keys; are the lookup-table addresses missing?",
+            account_keys.len()
+        )
+    })
+}
+
 fn convert_compiled_instruction(
     instruction: &UiCompiledInstruction,
     account_keys: &[String],
-) -> SolanaInstruction {
-    let program_id = account_keys
-        .get(instruction.program_id_index as usize)
-        .cloned()
-        .unwrap_or_default();
-    let accounts = instruction
-        .accounts
-        .iter()
-        .filter_map(|index| account_keys.get(*index as usize).cloned())
-        .collect();
-    SolanaInstruction {
-        program_id,
-        accounts,
+) -> Result<SolanaInstruction> {
+    Ok(SolanaInstruction {
+        program_id: account_key(account_keys, instruction.program_id_index)?,
+        accounts: instruction
+            .accounts
+            .iter()
+            .map(|index| account_key(account_keys, *index))
+            .collect::<Result<_>>()?,
         data: instruction.data.clone(),
-    }
+        stack_height: instruction.stack_height,
+    })
 }
 
 fn convert_ui_instruction(
     instruction: &UiInstruction,
     account_keys: &[String],
-) -> SolanaInstruction {
-    match instruction {
-        UiInstruction::Compiled(compiled) => convert_compiled_instruction(compiled, account_keys),
+) -> Result<SolanaInstruction> {
+    Ok(match instruction {
+        UiInstruction::Compiled(compiled) => convert_compiled_instruction(compiled, account_keys)?,
         UiInstruction::Parsed(parsed) => match parsed {
             UiParsedInstruction::PartiallyDecoded(instruction) => SolanaInstruction {
                 program_id: instruction.program_id.clone(),
                 accounts: instruction.accounts.clone(),
                 data: instruction.data.clone(),
+                stack_height: instruction.stack_height,
             },
             UiParsedInstruction::Parsed(instruction) => SolanaInstruction {
                 program_id: instruction.program_id.clone(),
                 accounts: Vec::new(),
                 data: instruction.parsed.to_string(),
+                stack_height: instruction.stack_height,
             },
         },
-    }
+    })
 }
@@ -0,0 +1,232 @@
+//! Health and status reporting for running the parser as a long-lived service.
+
+use std::collections::BTreeMap;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::stream::{ChannelCounters, ChannelMetrics};
+use crate::types::ParseResult;
+
+/// Counters shared between the parsing loop and the admin endpoint.
+pub struct ServiceStats {
+    started: Instant,
+    ready: AtomicBool,
+    last_slot: AtomicU64,
+    processed: AtomicU64,
+    failed: AtomicU64,
+    caches: Mutex<BTreeMap<String, usize>>,
+    channels: Mutex<BTreeMap<String, Arc<ChannelCounters>>>,
+}
+
+/// Point-in-time view of [`ServiceStats`], served as JSON on `/status`.
+#[derive(Clone, Debug, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceStatus {
+    pub ready: bool,
+    pub uptime_secs: u64,
+    pub last_slot: Option<u64>,
+    pub processed: u64,
+    pub failed: u64,
+    /// Share of processed inputs that failed to parse, over the service lifetime.
+    pub error_rate: f64,
+    pub caches: BTreeMap<String, usize>,
+    pub channels: BTreeMap<String, ChannelMetrics>,
+}
+
+impl Default for ServiceStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ServiceStats {
+    pub fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            ready: AtomicBool::new(false),
+            last_slot: AtomicU64::new(0),
+            processed: AtomicU64::new(0),
+            failed: AtomicU64::new(0),
+            caches: Mutex::new(BTreeMap::new()),
+            channels: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Mark the service as able (or no longer able) to take input; drives `/readyz`.
+    pub fn set_ready(&self, ready: bool) {
+        self.ready.store(ready, Ordering::Relaxed);
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.ready.load(Ordering::Relaxed)
+    }
+
+    /// Count a parsed transaction; results with `state == false` count as failures.
+    pub fn record(&self, result: &ParseResult) {
+        self.processed.fetch_add(1, Ordering::Relaxed);
+        self.last_slot.fetch_max(result.slot, Ordering::Relaxed);
+        if !result.state {
+            self.failed.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Count an input that never reached the parser, e.g. malformed JSON.
+    pub fn record_failure(&self) {
+        self.processed.fetch_add(1, Ordering::Relaxed);
+        self.failed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Report the current number of entries held by a named cache.
+    pub fn set_cache_size(&self, name: impl Into<String>, entries: usize) {
+        self.caches
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(name.into(), entries);
+    }
+
+    /// Expose the depth, drops and parked time of a pipeline channel on `/status`.
+    pub fn register_channel(&self, name: impl Into<String>, counters: Arc<ChannelCounters>) {
+        self.channels
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(name.into(), counters);
+    }
+
+    pub fn snapshot(&self) -> ServiceStatus {
+        let processed = self.processed.load(Ordering::Relaxed);
+        let failed = self.failed.load(Ordering::Relaxed);
+        let last_slot = self.last_slot.load(Ordering::Relaxed);
+        ServiceStatus {
+            ready: self.is_ready(),
+            uptime_secs: self.started.elapsed().as_secs(),
+            last_slot: (last_slot > 0).then_some(last_slot),
+            processed,
+            failed,
+            error_rate: if processed == 0 {
+                0.0
+            } else {
+                failed as f64 / processed as f64
+            },
+            caches: self
+                .caches
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .clone(),
+            channels: self
+                .channels
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .iter()
+                .map(|(name, counters)| (name.clone(), counters.snapshot()))
+                .collect(),
+        }
+    }
+}
+
+/// How long the admin endpoint waits for a probe to send its request line.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Minimal HTTP endpoint for supervisors and probes.
+///
+/// - `GET /healthz`: `200` while the process is serving requests (liveness).
+/// - `GET /readyz`: `200` once [`ServiceStats::set_ready`] was called with `true`, else `503`.
+/// - `GET /status`: [`ServiceStatus`] as JSON.
+pub struct AdminServer {
+    addr: SocketAddr,
+    shutdown: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl AdminServer {
+    /// Bind `addr` and answer probes on a background thread until [`AdminServer::shutdown`].
+    pub fn start(addr: impl ToSocketAddrs, stats: Arc<ServiceStats>) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        let addr = listener.local_addr()?;
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let stop = shutdown.clone();
+        let handle = thread::spawn(move || {
+            for stream in listener.incoming() {
+                if stop.load(Ordering::Relaxed) {
+                    break;
+                }
+                let Ok(stream) = stream else { continue };
+                if let Err(err) = respond(stream, &stats) {
+                    tracing::debug!("admin request failed: {err}");
+                }
+            }
+        });
+        Ok(Self {
+            addr,
+            shutdown,
+            handle: Some(handle),
+        })
+    }
+
+    pub fn local_addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Stop accepting probes and wait for the listener thread to exit.
+    pub fn shutdown(mut self) {
+        self.stop();
+    }
+
+    fn stop(&mut self) {
+        let Some(handle) = self.handle.take() else {
+            return;
+        };
+        self.shutdown.store(true, Ordering::Relaxed);
+        // Wake the blocking accept so the thread sees the flag.
+        let _ = TcpStream::connect(self.addr);
+        let _ = handle.join();
+    }
+}
+
+impl Drop for AdminServer {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+fn respond(stream: TcpStream, stats: &ServiceStats) -> io::Result<()> {
+    stream.set_read_timeout(Some(REQUEST_TIMEOUT))?;
+    let mut reader = BufReader::new(&stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // Drain the headers so closing the socket does not reset the connection.
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 2 {
+        header.clear();
+    }
+    let mut parts = request_line.split_whitespace();
+    let (method, path) = (parts.next().unwrap_or(""), parts.next().unwrap_or(""));
+
+    let (status, body) = match (method, path) {
+        ("GET", "/healthz") => ("200 OK", r#"{"status":"ok"}"#.to_string()),
+        ("GET", "/readyz") if stats.is_ready() => ("200 OK", r#"{"ready":true}"#.to_string()),
+        ("GET", "/readyz") => ("503 Service Unavailable", r#"{"ready":false}"#.to_string()),
+        ("GET", "/status") => (
+            "200 OK",
+            serde_json::to_string(&stats.snapshot()).map_err(io::Error::other)?,
+        ),
+        ("GET", _) => ("404 Not Found", r#"{"error":"not found"}"#.to_string()),
+        _ => (
+            "405 Method Not Allowed",
+            r#"{"error":"method not allowed"}"#.to_string(),
+        ),
+    };
+
+    let mut stream = stream;
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )?;
+    stream.flush()
+}
@@ -0,0 +1,261 @@
+//! Bounded channels for the streaming pipeline.
+//!
+//! Every stage hands work to the next through a queue of fixed capacity, so a slow sink
+//! holds memory at `capacity` items per stage instead of growing without bound. What happens
+//! when a queue is full is chosen per channel with [`OverflowPolicy`].
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError};
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+
+/// Behaviour of [`Sender::send`] on a full channel.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum OverflowPolicy {
+    /// Block the sender until a slot frees up, pushing the backpressure upstream.
+    #[default]
+    Park,
+    /// Discard the item being sent.
+    DropNewest,
+    /// Discard the oldest queued item to make room, favouring fresh data.
+    DropOldest,
+}
+
+/// Counters of one channel, readable while the pipeline runs.
+#[derive(Debug)]
+pub struct ChannelCounters {
+    capacity: usize,
+    policy: OverflowPolicy,
+    depth: AtomicUsize,
+    max_depth: AtomicUsize,
+    sent: AtomicU64,
+    dropped: AtomicU64,
+    parked_micros: AtomicU64,
+}
+
+/// Point-in-time view of [`ChannelCounters`].
+#[derive(Clone, Debug, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ChannelMetrics {
+    pub capacity: usize,
+    pub policy: OverflowPolicy,
+    /// Items queued right now; a depth pinned at `capacity` means the consumer lags.
+    pub depth: usize,
+    pub max_depth: usize,
+    pub sent: u64,
+    pub dropped: u64,
+    /// Total time senders spent blocked on a full queue.
+    pub parked_ms: u64,
+}
+
+impl ChannelCounters {
+    pub fn snapshot(&self) -> ChannelMetrics {
+        ChannelMetrics {
+            capacity: self.capacity,
+            policy: self.policy,
+            depth: self.depth.load(Ordering::Relaxed),
+            max_depth: self.max_depth.load(Ordering::Relaxed),
+            sent: self.sent.load(Ordering::Relaxed),
+            dropped: self.dropped.load(Ordering::Relaxed),
+            parked_ms: self.parked_micros.load(Ordering::Relaxed) / 1_000,
+        }
+    }
+}
+
+struct State<T> {
+    queue: VecDeque<T>,
+    senders: usize,
+    receivers: usize,
+}
+
+struct Shared<T> {
+    state: Mutex<State<T>>,
+    not_empty: Condvar,
+    not_full: Condvar,
+    counters: Arc<ChannelCounters>,
+}
+
+impl<T> Shared<T> {
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn record_depth(&self, depth: usize) {
+        self.counters.depth.store(depth, Ordering::Relaxed);
+        self.counters.max_depth.fetch_max(depth, Ordering::Relaxed);
+    }
+}
+
+/// Create a channel holding at most `capacity` items (at least one).
+pub fn bounded<T>(capacity: usize, policy: OverflowPolicy) -> (Sender<T>, Receiver<T>) {
+    let capacity = capacity.max(1);
+    let shared = Arc::new(Shared {
+        state: Mutex::new(State {
+            queue: VecDeque::with_capacity(capacity),
+            senders: 1,
+            receivers: 1,
+        }),
+        not_empty: Condvar::new(),
+        not_full: Condvar::new(),
+        counters: Arc::new(ChannelCounters {
+            capacity,
+            policy,
+            depth: AtomicUsize::new(0),
+            max_depth: AtomicUsize::new(0),
+            sent: AtomicU64::new(0),
+            dropped: AtomicU64::new(0),
+            parked_micros: AtomicU64::new(0),
+        }),
+    });
+    (
+        Sender {
+            shared: shared.clone(),
+        },
+        Receiver { shared },
+    )
+}
+
+/// Returned by [`Sender::send`] once every receiver is gone; carries the unsent item.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Disconnected<T>(pub T);
+
+pub struct Sender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> Sender<T> {
+    /// Queue `item`, applying the channel's [`OverflowPolicy`] when it is full.
+    ///
+    /// Items discarded by a drop policy still return `Ok`; they are counted in
+    /// [`ChannelMetrics::dropped`].
+    pub fn send(&self, item: T) -> Result<(), Disconnected<T>> {
+        let shared = &*self.shared;
+        let counters = &shared.counters;
+        let mut state = shared.lock();
+        if state.receivers == 0 {
+            return Err(Disconnected(item));
+        }
+
+        if state.queue.len() >= counters.capacity {
+            match counters.policy {
+                OverflowPolicy::Park => {
+                    let parked = Instant::now();
+                    while state.queue.len() >= counters.capacity && state.receivers > 0 {
+                        state = shared
+                            .not_full
+                            .wait(state)
+                            .unwrap_or_else(PoisonError::into_inner);
+                    }
+                    counters
+                        .parked_micros
+                        .fetch_add(parked.elapsed().as_micros() as u64, Ordering::Relaxed);
+                    if state.receivers == 0 {
+                        return Err(Disconnected(item));
+                    }
+                }
+                OverflowPolicy::DropNewest => {
+                    counters.dropped.fetch_add(1, Ordering::Relaxed);
+                    return Ok(());
+                }
+                OverflowPolicy::DropOldest => {
+                    state.queue.pop_front();
+                    counters.dropped.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+        }
+
+        state.queue.push_back(item);
+        counters.sent.fetch_add(1, Ordering::Relaxed);
+        shared.record_depth(state.queue.len());
+        drop(state);
+        shared.not_empty.notify_one();
+        Ok(())
+    }
+
+    pub fn counters(&self) -> Arc<ChannelCounters> {
+        self.shared.counters.clone()
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        self.shared.lock().senders += 1;
+        Self {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.lock();
+        state.senders -= 1;
+        if state.senders == 0 {
+            drop(state);
+            self.shared.not_empty.notify_all();
+        }
+    }
+}
+
+/// Receiving end; clone it to fan work out over several consumers.
+pub struct Receiver<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> Receiver<T> {
+    /// Wait for the next item; `None` once the queue is drained and every sender is gone.
+    pub fn recv(&self) -> Option<T> {
+        let shared = &*self.shared;
+        let mut state = shared.lock();
+        loop {
+            if let Some(item) = state.queue.pop_front() {
+                shared.record_depth(state.queue.len());
+                drop(state);
+                shared.not_full.notify_one();
+                return Some(item);
+            }
+            if state.senders == 0 {
+                return None;
+            }
+            state = shared
+                .not_empty
+                .wait(state)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+    }
+
+    pub fn counters(&self) -> Arc<ChannelCounters> {
+        self.shared.counters.clone()
+    }
+}
+
+impl<T> Clone for Receiver<T> {
+    fn clone(&self) -> Self {
+        self.shared.lock().receivers += 1;
+        Self {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.lock();
+        state.receivers -= 1;
+        if state.receivers == 0 {
+            drop(state);
+            self.shared.not_full.notify_all();
+        }
+    }
+}
+
+impl<T> Iterator for Receiver<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.recv()
+    }
+}
@@ -1,12 +1,20 @@
 use std::collections::HashMap;
+use std::sync::Arc;
 
 use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
+use solana_sdk::transaction::TransactionError;
 
 use crate::config::ParseConfig;
+use crate::core::aggregate::aggregate_route;
+use crate::core::amounts::convert_to_ui_amount;
+use crate::core::arbitrage::detect_arbitrage;
+use crate::core::fees::trade_fees;
+use crate::core::util::compare_idx;
 
 /// Representation of a raw token amount and its UI value.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct TokenAmount {
     pub amount: String,
@@ -37,6 +45,7 @@ impl Default for TokenAmount {
 
 /// Token balance change helper struct used for SOL/token deltas.
 #[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct BalanceChange {
     pub pre: i128,
@@ -46,6 +55,7 @@ pub struct BalanceChange {
 
 /// Snapshot of a token account balance from transaction meta.
 #[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct TokenBalance {
     pub account: String,
@@ -57,23 +67,60 @@ pub struct TokenBalance {
 }
 
 /// Execution status for a Solana transaction.
-#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum TransactionStatus {
+    #[default]
     #[serde(alias = "UNKNOWN")]
     Unknown,
     Success,
     Failed,
 }
 
-impl Default for TransactionStatus {
-    fn default() -> Self {
-        Self::Unknown
+/// Whether a trade executed or is the attempt of a failed transaction.
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TradeStatus {
+    #[default]
+    Success,
+    /// Decoded from the instruction of a failed transaction with `ParseConfig::parse_failed`:
+    /// the amounts are the instruction's arguments, nothing moved.
+    Failed,
+}
+
+impl TradeStatus {
+    pub fn is_success(&self) -> bool {
+        *self == TradeStatus::Success
     }
 }
 
+/// Bound an attempted trade set on the side whose amount it did not fix, in that side's token.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct TradeLimit {
+    pub kind: TradeLimitKind,
+    pub amount: f64,
+    pub amount_raw: String,
+}
+
+/// Which side of a trade a `TradeLimit` bounds.
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TradeLimitKind {
+    /// Most the trade would spend of its input token.
+    #[default]
+    MaxInput,
+    /// Least the trade would accept of its output token.
+    MinOutput,
+}
+
 /// Trade directions supported by the parser.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "UPPERCASE")]
 pub enum TradeType {
     Buy,
@@ -87,10 +134,23 @@ pub enum TradeType {
     Remove,
     Lock,
     Burn,
+    #[serde(rename = "FEE_CLAIM")]
+    FeeClaim,
+    #[serde(rename = "LIMIT_FILL")]
+    LimitFill,
+    #[serde(rename = "DCA_FILL")]
+    DcaFill,
+    #[serde(rename = "SET_PARAMS")]
+    SetParams,
+    #[serde(rename = "SET_CREATOR")]
+    SetCreator,
+    #[serde(rename = "UPDATE_GLOBAL_AUTHORITY")]
+    UpdateGlobalAuthority,
 }
 
 /// Detailed token information used for trades and events.
 #[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct TokenInfo {
     pub mint: String,
@@ -123,6 +183,7 @@ pub struct TokenInfo {
 
 /// Fee information associated with a trade.
 #[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct FeeInfo {
     pub mint: String,
@@ -139,6 +200,7 @@ pub struct FeeInfo {
 
 /// High level trade information extracted from a transaction.
 #[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct TradeInfo {
     #[serde(rename = "type")]
@@ -165,14 +227,127 @@ pub struct TradeInfo {
     pub route: Option<String>,
     pub slot: u64,
     pub timestamp: u64,
-    pub signature: String,
+    /// Shared with every other event parsed from the same transaction.
+    pub signature: Arc<str>,
     pub idx: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub signer: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub volume_sol: Option<f64>,
+    /// USD price of one whole traded token, the leg that is neither SOL nor a stablecoin.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price_usd: Option<f64>,
+    /// Value of the trade in USD.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub volume_usd: Option<f64>,
+    /// Output gained over the best single venue, set on multi-hop aggregate trades.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub routing_improvement_bps: Option<i64>,
+    /// Maker of the limit order a `LIMIT_FILL` trade filled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maker: Option<String>,
+    /// Order account of the limit order a `LIMIT_FILL` trade filled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub order: Option<String>,
+    /// Recurring order state after a `DCA_FILL` trade.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dca: Option<DcaFillInfo>,
+    /// Reserves of the traded pool right after the trade, as the venue's swap event reports
+    /// them, or the pool vault balances at the end of the transaction.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pool_reserves: Option<PoolReserves>,
+    /// Pool price right after the trade, in quote tokens per whole base token: the launched
+    /// token against SOL on Pump curves and pools, token A against token B on
+    /// concentrated-liquidity pools.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price_after: Option<f64>,
+    /// Pool state after the trade, attached by a pool state provider to the last trade
+    /// on the pool in its slot.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pool_state: Option<PoolState>,
+    /// Price an oracle-quoted venue (ZeroFi, Obric) filled the leg at, in output tokens per
+    /// input token.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub oracle_price: Option<f64>,
+    /// Initialized ticks a concentrated-liquidity swap crossed, counted from the events it
+    /// logged; `None` when the venue or the missing logs do not tell.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ticks_crossed: Option<u32>,
+    /// Set by the anomaly checks of block parsing when the trade looks implausible.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub suspect: bool,
+    /// Set by `detect_wash_trades` when the trade and an opposite trade of the same pool
+    /// resolve to one wallet, or to wallets funded by the same parent.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub suspected_wash: bool,
+    /// `FAILED` for the attempted trades of failed transactions; omitted for executed ones.
+    #[serde(default, skip_serializing_if = "TradeStatus::is_success")]
+    pub status: TradeStatus,
+    /// Limit of an attempted trade on the side its instruction did not fix; that side's
+    /// amount is left at 0.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<TradeLimit>,
+}
+
+/// Reserves of a pool right after a trade, in raw units.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct PoolReserves {
+    pub base_mint: String,
+    pub base_reserve: String,
+    pub base_decimals: u8,
+    pub quote_mint: String,
+    pub quote_reserve: String,
+    pub quote_decimals: u8,
+}
+
+impl PoolReserves {
+    /// Quote tokens per whole base token implied by the reserves, for constant-product pools.
+    pub fn price(&self) -> Option<f64> {
+        let base = self.base_reserve.parse::<u128>().ok()?;
+        let quote = self.quote_reserve.parse::<u128>().ok()?;
+        (base > 0).then(|| {
+            convert_to_ui_amount(quote, self.quote_decimals)
+                / convert_to_ui_amount(base, self.base_decimals)
+        })
+    }
+}
+
+/// Reserves and liquidity of a pool account at the end of a slot, in raw units.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct PoolState {
+    pub slot: u64,
+    pub mint_a: String,
+    pub reserve_a: String,
+    pub mint_b: String,
+    pub reserve_b: String,
+    /// Active liquidity of concentrated-liquidity pools.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub liquidity: Option<String>,
+}
+
+/// Jupiter DCA order filled by a `DCA_FILL` trade.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct DcaFillInfo {
+    pub dca: String,
+    /// Input spent by this cycle, in raw units.
+    pub cycle_in_amount: String,
+    /// Input still deposited in the order after the fill, when its token balance is known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remaining_in_amount: Option<String>,
+    /// Cycles left at the size of this one; the last cycle may be smaller.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remaining_cycles: Option<u64>,
 }
 
 /// Detailed transfer information mirroring the TypeScript structure.
 #[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct TransferInfo {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -183,6 +358,10 @@ pub struct TransferInfo {
     pub mint: String,
     pub source: String,
     pub token_amount: TokenAmount,
+    /// Token-2022 transfer fee withheld from `token_amount` (`transferCheckedWithFee`); the
+    /// destination is credited the difference.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_amount: Option<TokenAmount>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub source_balance: Option<TokenAmount>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -197,6 +376,7 @@ pub struct TransferInfo {
 
 /// Transfer data emitted by the meta simulation.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct TransferData {
     #[serde(rename = "type")]
@@ -205,13 +385,14 @@ pub struct TransferData {
     pub info: TransferInfo,
     pub idx: String,
     pub timestamp: u64,
-    pub signature: String,
+    pub signature: Arc<str>,
     #[serde(default)]
     pub is_fee: bool,
 }
 
 /// High level liquidity pool event (add/remove liquidity etc.).
 #[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct PoolEvent {
     pub user: String,
@@ -223,7 +404,7 @@ pub struct PoolEvent {
     pub amm: Option<String>,
     pub slot: u64,
     pub timestamp: u64,
-    pub signature: String,
+    pub signature: Arc<str>,
     pub idx: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub signer: Option<Vec<String>>,
@@ -256,10 +437,43 @@ pub struct PoolEvent {
     pub lp_amount: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub lp_amount_raw: Option<String>,
+    /// LP mint supply as logged by the protocol alongside the event.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lp_supply: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lp_supply_raw: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lp_burned: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lp_locked: Option<bool>,
+    /// Concentrated-liquidity position touched by the event.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position: Option<PositionInfo>,
+}
+
+/// Position accounts and range of a concentrated-liquidity pool event.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct PositionInfo {
+    pub position: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nft_mint: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tick_lower_index: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tick_upper_index: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tick_array_lower: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tick_array_upper: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub liquidity: Option<String>,
 }
 
 /// Meme/launch events emitted by platforms such as Pumpfun.
 #[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct MemeEvent {
     #[serde(rename = "type")]
@@ -267,7 +481,7 @@ pub struct MemeEvent {
     pub timestamp: u64,
     pub idx: String,
     pub slot: u64,
-    pub signature: String,
+    pub signature: Arc<str>,
     pub user: String,
     pub base_mint: String,
     pub quote_mint: String,
@@ -313,10 +527,190 @@ pub struct MemeEvent {
     pub pool_b_reserve: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub pool_fee_rate: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mint_authority_renounced: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub freeze_authority_renounced: Option<bool>,
+    /// Wallets splitting the creator fee of the token, as configured at launch.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fee_shares: Vec<FeeShare>,
+    /// Protocol fee rate set by a `SET_PARAMS` event.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_basis_points: Option<u64>,
+    /// Creator fee rate set by a `SET_PARAMS` event.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub creator_fee_basis_points: Option<u64>,
+    /// Creator replaced by an admin `SET_CREATOR` event.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_creator: Option<String>,
+    /// Authority installed by an `UPDATE_GLOBAL_AUTHORITY` event.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub new_authority: Option<String>,
+    /// Share of the bonding curve's tokens sold after a trade, in percent; 100 graduates.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bonding_curve_progress: Option<f64>,
+    /// Curve reserves right after a trade event.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pool_reserves: Option<PoolReserves>,
+    /// USD price of one whole `base_mint` token, for events that trade it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price_usd: Option<f64>,
+    /// Value of the event's SOL or stablecoin leg in USD.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub volume_usd: Option<f64>,
+}
+
+/// One wallet's cut of a launchpad creator fee.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct FeeShare {
+    pub wallet: String,
+    pub bps: u16,
+}
+
+/// Bonding-curve graduation into an AMM pool, uniform across launchpads.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct MigrationEvent {
+    pub source_program: String,
+    pub source_protocol: String,
+    pub bonding_curve: String,
+    pub mint: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub destination_program: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub destination_protocol: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pool: Option<String>,
+    /// Transaction that created the destination pool when it differs from `signature`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pool_signature: Option<String>,
+    /// Mint paired with `mint` in the destination pool.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quote_mint: Option<String>,
+    /// Amount of `mint` the destination pool was seeded with.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub initial_base_reserve: Option<f64>,
+    /// Amount of `quote_mint` the destination pool was seeded with.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub initial_quote_reserve: Option<f64>,
+    pub slot: u64,
+    pub timestamp: u64,
+    pub signature: Arc<str>,
+    pub idx: String,
+}
+
+/// Swap of an arbitrage cycle.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ArbitrageLeg {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub amm: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub program_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pool: Option<String>,
+    pub input_mint: String,
+    pub output_mint: String,
+    pub idx: String,
+}
+
+/// Swaps of one transaction that start and end in the same mint with more than they spent.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ArbitrageInfo {
+    /// Wallet that ran the cycle: the transaction's first signer.
+    pub wallet: String,
+    /// Last leg output less first leg input, in the mint the cycle starts from.
+    pub profit: TokenInfo,
+    pub legs: Vec<ArbitrageLeg>,
+}
+
+/// Kind of MEV an [`MevEvent`] reports.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "UPPERCASE")]
+pub enum MevType {
+    /// A victim's swap wrapped between a frontrun and a backrun of the same attacker.
+    #[default]
+    Sandwich,
+}
+
+/// MEV extracted from a victim, found by analyzing a parsed block.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct MevEvent {
+    #[serde(rename = "type")]
+    pub mev_type: MevType,
+    pub pool: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub amm: Option<String>,
+    pub attacker: String,
+    pub victim: String,
+    pub frontrun_signature: String,
+    pub victim_signature: String,
+    pub backrun_signature: String,
+    /// Backrun output less frontrun input, in the token the frontrun spent. A frontrun and
+    /// backrun around several victims report the same value on each of their events.
+    pub extracted_value: TokenInfo,
+    pub slot: u64,
+    pub timestamp: u64,
+}
+
+/// Side of a bridge transfer, seen from Solana.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "UPPERCASE")]
+pub enum BridgeDirection {
+    /// Locked or burned on Solana for another chain.
+    #[default]
+    Outbound,
+    /// Released or minted on Solana from another chain.
+    Inbound,
+}
+
+/// Tokens moved across chains by a bridge instruction.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct BridgeEvent {
+    pub program_id: String,
+    pub protocol: String,
+    pub direction: BridgeDirection,
+    /// Sender of outbound transfers, recipient of inbound ones.
+    pub user: String,
+    pub token: TokenInfo,
+    /// Destination chain of outbound transfers, in the bridge's own chain ids (Wormhole
+    /// chain ids, EVM chain ids for deBridge).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_chain: Option<u64>,
+    /// Hex-encoded recipient on the destination chain.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recipient: Option<String>,
+    pub slot: u64,
+    pub timestamp: u64,
+    pub signature: Arc<str>,
+    pub idx: String,
+}
+
+/// SOL paid to the Jito tip accounts for bundle inclusion.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct JitoTip {
+    pub recipient: String,
+    pub amount: TokenAmount,
+    pub idx: String,
 }
 
 /// Additional context information about the parsed transaction.
 #[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct DexInfo {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -327,13 +721,143 @@ pub struct DexInfo {
     pub route: Option<String>,
 }
 
+/// Wallet owning a token account and the mint it holds.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct TokenAccountOwner {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    pub mint: String,
+}
+
+/// Net movements of one wallet in a transaction, across the wallet and its token accounts.
+///
+/// SOL, wrapped or not, is only reported in `sol`: the lamports of a wrapped SOL account
+/// already hold its balance. The signer's `sol` includes the transaction fee.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct WalletFlow {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sol: Option<BalanceChange>,
+    /// Raw token amounts per mint.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub tokens: HashMap<String, BalanceChange>,
+}
+
+/// Order-flow label assigned by the bot heuristic.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum FlowLabel {
+    LikelyBot,
+    LikelyHuman,
+}
+
+/// Inner CPI that failed and was rolled back while the transaction itself succeeded.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct FailedCpi {
+    pub program_id: String,
+    pub idx: String,
+    /// Reason logged after `failed:`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Idx of the failed instruction and of every CPI it made; none of them took effect.
+    pub reverted: Vec<String>,
+}
+
+/// Number of outer and inner instructions a program executed in the transaction.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ProgramInvocation {
+    pub program_id: String,
+    pub name: String,
+    pub outer_count: usize,
+    pub inner_count: usize,
+}
+
+/// Where a transaction sits in its block, set when it was parsed as part of a block.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct BlockContext {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub epoch: Option<u64>,
+    /// Identity of the slot leader that produced the block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub leader: Option<String>,
+    /// Position of the transaction in the block, starting at 0.
+    pub transaction_index: usize,
+}
+
+/// Fees a transaction paid, split by what they paid for.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct FeeBreakdown {
+    /// Signature fee: the transaction fee without its priority fee.
+    pub base: TokenAmount,
+    /// Priority fee bought with the compute unit price.
+    pub priority: TokenAmount,
+    /// Jito bundle tip.
+    pub tip: TokenAmount,
+    /// Protocol, creator, bot and other fees of the trades, summed per `type` and mint.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub trade_fees: Vec<FeeInfo>,
+}
+
+/// Compute budget requested by a transaction's compute budget instructions.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ComputeBudget {
+    /// Requested compute unit limit; the runtime default applies when `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unit_limit: Option<u32>,
+    /// Compute unit price, in micro-lamports.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unit_price: Option<u64>,
+    /// Priority fee paid on top of the base fee, in SOL; set when a unit price is requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority_fee: Option<TokenAmount>,
+}
+
+/// Wall-clock time spent in one stage of a parse.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct StageTiming {
+    pub stage: String,
+    pub micros: u64,
+}
+
+/// Time a parse took, in microseconds, with the stages that ran in execution order.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ParseTimings {
+    /// Whole parse, middleware and post-processors included.
+    pub total_micros: u64,
+    pub stages: Vec<StageTiming>,
+}
+
 /// Aggregated parsing result returned by the Rust parser.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ParseResult {
     pub state: bool,
-    #[serde(default)]
+    /// Transaction fee, base and priority fee together; left empty, and not serialized, when
+    /// `include_legacy_fee` is off.
+    #[serde(default, skip_serializing_if = "is_unset")]
     pub fee: TokenAmount,
+    /// `fee` split into its base and priority parts, next to the tip and the trades' fees.
+    #[serde(default)]
+    pub fees: FeeBreakdown,
     #[serde(default)]
     pub aggregate_trade: Option<TradeInfo>,
     #[serde(default)]
@@ -346,8 +870,58 @@ pub struct ParseResult {
     pub sol_balance_change: Option<BalanceChange>,
     #[serde(default)]
     pub token_balance_change: HashMap<String, BalanceChange>,
+    /// Net lamports the fee payer gained across its wallet and token accounts, fee and
+    /// wrapped SOL included; the `sol` of its `WalletFlow`.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub signer_sol_change: i128,
+    /// Net raw amount per mint the fee payer gained across its token accounts, wrapped SOL
+    /// excluded; the `tokens` of its `WalletFlow`.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub signer_token_changes: HashMap<String, i128>,
+    /// SOL balance change per owner, set when `include_balance_maps` is enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sol_balance_changes: Option<HashMap<String, BalanceChange>>,
+    /// Token balance change per owner and mint, set when `include_balance_maps` is enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_balance_changes: Option<HashMap<String, HashMap<String, BalanceChange>>>,
+    /// Wallet and mint per token account, set when `include_token_account_owners` is enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_account_owners: Option<HashMap<String, TokenAccountOwner>>,
+    /// Net SOL and token movements per wallet, set when `include_wallet_flows` is enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wallet_flows: Option<HashMap<String, WalletFlow>>,
+    /// Non-system programs invoked by the transaction, in order of first appearance.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub programs_invoked: Vec<ProgramInvocation>,
+    /// Bot/human classification of the trades, set when `classify_flow` is enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub flow_label: Option<FlowLabel>,
+    /// Rolled-back inner CPIs found in the logs, set when `include_failed_cpis` is enabled.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub failed_cpis: Vec<FailedCpi>,
     #[serde(default)]
     pub meme_events: Vec<MemeEvent>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub migrations: Vec<MigrationEvent>,
+    /// Bridge lockups and releases; their transfers are left out of `transfers`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bridges: Vec<BridgeEvent>,
+    /// Cycle of swaps across several pools ending with more of the starting mint.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub arbitrage: Option<ArbitrageInfo>,
+    /// Jito bundle tip; its transfers are left out of `transfers`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tip: Option<JitoTip>,
+    /// Jito bundle the transaction landed in, named by the signature of its tipping
+    /// transaction; set by block parsing.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bundle_id: Option<String>,
+    /// Position of the transaction in its bundle, starting at 0.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bundle_index: Option<usize>,
+    /// Epoch, leader and position in the block, set by the block parsing helpers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_context: Option<BlockContext>,
     #[serde(default)]
     pub slot: u64,
     #[serde(default)]
@@ -358,43 +932,254 @@ pub struct ParseResult {
     pub signer: Vec<String>,
     #[serde(default)]
     pub compute_units: u64,
+    /// Requested compute unit limit and price, and the priority fee they add.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compute_budget: Option<ComputeBudget>,
+    /// Parse duration and per-stage breakdown, set when `record_timings` is enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timings: Option<ParseTimings>,
     #[serde(default)]
     pub tx_status: TransactionStatus,
+    /// Why a failed transaction failed, decoded from its error and program logs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_reason: Option<String>,
     #[serde(default)]
     pub msg: Option<String>,
 }
 
+fn is_zero(value: &i128) -> bool {
+    *value == 0
+}
+
+fn is_unset(fee: &TokenAmount) -> bool {
+    fee.amount.is_empty()
+}
+
 impl ParseResult {
     pub fn new() -> Self {
         Self {
             state: true,
             fee: TokenAmount::default(),
+            fees: FeeBreakdown::default(),
             aggregate_trade: None,
             trades: Vec::new(),
             liquidities: Vec::new(),
             transfers: Vec::new(),
             sol_balance_change: None,
             token_balance_change: HashMap::new(),
+            signer_sol_change: 0,
+            signer_token_changes: HashMap::new(),
+            sol_balance_changes: None,
+            token_balance_changes: None,
+            token_account_owners: None,
+            wallet_flows: None,
+            programs_invoked: Vec::new(),
+            flow_label: None,
+            failed_cpis: Vec::new(),
             meme_events: Vec::new(),
+            migrations: Vec::new(),
+            bridges: Vec::new(),
+            arbitrage: None,
+            tip: None,
+            bundle_id: None,
+            bundle_index: None,
+            block_context: None,
             slot: 0,
             timestamp: 0,
             signature: String::new(),
             signer: Vec::new(),
             compute_units: 0,
+            compute_budget: None,
+            timings: None,
             tx_status: TransactionStatus::default(),
+            error_reason: None,
             msg: None,
         }
     }
 }
 
+impl ParseResult {
+    /// Execution order key across results: slot, then position in the block when known.
+    ///
+    /// Results without a block context compare equal within a slot, so a stable sort keeps
+    /// them in the order they were produced.
+    pub fn block_position(&self) -> (u64, Option<usize>) {
+        (
+            self.slot,
+            self.block_context
+                .as_ref()
+                .map(|context| context.transaction_index),
+        )
+    }
+
+    /// Fold another partial parse of the same transaction into this one.
+    ///
+    /// Events are deduplicated by `(signature, idx)` with entries from `other` winning,
+    /// scalar fields are taken from `other` whenever it carries a value, and the
+    /// aggregate trade is rebuilt from the merged trade list with `aggregate_route`.
+    /// Summaries of the trades, `fees.tradeFees` and `arbitrage`, are recomputed from the
+    /// merged list; the fee payer's changes come from `other` as a pair when it has them, and
+    /// `programsInvoked` keeps every program with the higher of the two counts. The merged
+    /// `state` is true only when both sides parsed successfully.
+    pub fn merge(&mut self, other: ParseResult) {
+        let aggregate_fee = other
+            .aggregate_trade
+            .as_ref()
+            .or(self.aggregate_trade.as_ref())
+            .map(|trade| (trade.fee.clone(), trade.routing_improvement_bps));
+
+        merge_events(&mut self.trades, other.trades, |t| (&t.signature, &t.idx));
+        merge_events(&mut self.liquidities, other.liquidities, |l| {
+            (&l.signature, &l.idx)
+        });
+        merge_events(&mut self.transfers, other.transfers, |t| {
+            (&t.signature, &t.idx)
+        });
+        merge_events(&mut self.meme_events, other.meme_events, |e| {
+            (&e.signature, &e.idx)
+        });
+        merge_events(&mut self.migrations, other.migrations, |m| {
+            (&m.signature, &m.idx)
+        });
+        merge_events(&mut self.bridges, other.bridges, |b| (&b.signature, &b.idx));
+
+        self.state &= other.state;
+        if !is_unset(&other.fee) && other.fee != TokenAmount::default() {
+            self.fee = other.fee;
+        }
+        if other.fees != FeeBreakdown::default() {
+            self.fees = other.fees;
+        }
+        if other.sol_balance_change.is_some() {
+            self.sol_balance_change = other.sol_balance_change;
+        }
+        self.token_balance_change.extend(other.token_balance_change);
+        if other.signer_sol_change != 0 || !other.signer_token_changes.is_empty() {
+            self.signer_sol_change = other.signer_sol_change;
+            self.signer_token_changes = other.signer_token_changes;
+        }
+        if other.sol_balance_changes.is_some() {
+            self.sol_balance_changes = other.sol_balance_changes;
+        }
+        if other.token_balance_changes.is_some() {
+            self.token_balance_changes = other.token_balance_changes;
+        }
+        if let Some(owners) = other.token_account_owners {
+            self.token_account_owners
+                .get_or_insert_with(HashMap::new)
+                .extend(owners);
+        }
+        if other.wallet_flows.is_some() {
+            self.wallet_flows = other.wallet_flows;
+        }
+        for invocation in other.programs_invoked {
+            match self
+                .programs_invoked
+                .iter_mut()
+                .find(|existing| existing.program_id == invocation.program_id)
+            {
+                Some(existing) => {
+                    existing.outer_count = existing.outer_count.max(invocation.outer_count);
+                    existing.inner_count = existing.inner_count.max(invocation.inner_count);
+                }
+                None => self.programs_invoked.push(invocation),
+            }
+        }
+        if other.flow_label.is_some() {
+            self.flow_label = other.flow_label;
+        }
+        if !other.failed_cpis.is_empty() {
+            self.failed_cpis = other.failed_cpis;
+        }
+        if other.tip.is_some() {
+            self.tip = other.tip;
+        }
+        if other.bundle_id.is_some() {
+            self.bundle_id = other.bundle_id;
+            self.bundle_index = other.bundle_index;
+        }
+        if other.block_context.is_some() {
+            self.block_context = other.block_context;
+        }
+        if other.slot != 0 {
+            self.slot = other.slot;
+        }
+        if other.timestamp != 0 {
+            self.timestamp = other.timestamp;
+        }
+        if !other.signature.is_empty() {
+            self.signature = other.signature;
+        }
+        for signer in other.signer {
+            if !self.signer.contains(&signer) {
+                self.signer.push(signer);
+            }
+        }
+        if other.compute_units != 0 {
+            self.compute_units = other.compute_units;
+        }
+        if other.compute_budget.is_some() {
+            self.compute_budget = other.compute_budget;
+        }
+        if other.timings.is_some() {
+            self.timings = other.timings;
+        }
+        if other.tx_status != TransactionStatus::Unknown {
+            self.tx_status = other.tx_status;
+        }
+        if other.error_reason.is_some() {
+            self.error_reason = other.error_reason;
+        }
+        if other.msg.is_some() {
+            self.msg = other.msg;
+        }
+
+        self.fees.trade_fees = trade_fees(&self.trades);
+        self.arbitrage = self
+            .signer
+            .first()
+            .and_then(|signer| detect_arbitrage(signer.clone(), &self.trades));
+        self.aggregate_trade = aggregate_fee.and_then(|(fee, routing_improvement_bps)| {
+            let mut trade = aggregate_route(&self.trades)?;
+            trade.fee = fee.or(trade.fee);
+            trade.routing_improvement_bps = routing_improvement_bps;
+            Some(trade)
+        });
+    }
+}
+
 impl Default for ParseResult {
     fn default() -> Self {
         Self::new()
     }
 }
 
+fn merge_events<T>(current: &mut Vec<T>, incoming: Vec<T>, id: fn(&T) -> (&str, &str)) {
+    let mut positions: HashMap<(String, String), usize> = current
+        .iter()
+        .enumerate()
+        .map(|(pos, event)| {
+            let (signature, idx) = id(event);
+            ((signature.to_string(), idx.to_string()), pos)
+        })
+        .collect();
+    for event in incoming {
+        let (signature, idx) = id(&event);
+        let key = (signature.to_string(), idx.to_string());
+        match positions.get(&key) {
+            Some(&pos) => current[pos] = event,
+            None => {
+                positions.insert(key, current.len());
+                current.push(event);
+            }
+        }
+    }
+    current.sort_by(|a, b| compare_idx(id(a).1, id(b).1));
+}
+
 /// Minimal instruction representation with bookkeeping indices.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ClassifiedInstruction {
     pub program_id: String,
@@ -405,16 +1190,21 @@ pub struct ClassifiedInstruction {
 
 /// Basic representation of a Solana instruction.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct SolanaInstruction {
     pub program_id: String,
     pub accounts: Vec<String>,
     #[serde(default)]
     pub data: String,
+    /// Invocation depth reported by the RPC node (1 for top-level instructions).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stack_height: Option<u32>,
 }
 
 /// Inner instruction grouping mirroring the Solana RPC payload.
 #[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct InnerInstruction {
     pub index: usize,
@@ -424,6 +1214,7 @@ pub struct InnerInstruction {
 
 /// Transaction meta information used by the adapter.
 #[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct TransactionMeta {
     pub fee: u64,
@@ -433,10 +1224,18 @@ pub struct TransactionMeta {
     pub sol_balance_changes: HashMap<String, BalanceChange>,
     #[serde(default)]
     pub token_balance_changes: HashMap<String, HashMap<String, BalanceChange>>,
+    /// Program log lines (`meta.logMessages`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub log_messages: Vec<String>,
+    /// Error of a failed transaction (`meta.err`), when the source reports it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schema", schemars(with = "Option<serde_json::Value>"))]
+    pub err: Option<TransactionError>,
 }
 
 /// Simplified transaction representation consumed by the parser.
 #[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct SolanaTransaction {
     pub slot: u64,
@@ -460,17 +1259,24 @@ pub struct SolanaTransaction {
 
 /// Block representation for CLI parsing.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct SolanaBlock {
     pub slot: u64,
     #[serde(default)]
     pub block_time: Option<u64>,
     #[serde(default)]
+    pub epoch: Option<u64>,
+    /// Identity of the slot leader that produced the block.
+    #[serde(default)]
+    pub leader: Option<String>,
+    #[serde(default)]
     pub transactions: Vec<SolanaTransaction>,
 }
 
 /// Input wrapper for CLI block parsing distinguishing between raw and parsed data.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum BlockInput {
     Raw {
@@ -481,8 +1287,37 @@ pub enum BlockInput {
     },
 }
 
+/// Trade count and volume of one AMM or pool within a block.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct VolumeStats {
+    pub trades: usize,
+    pub volume_sol: f64,
+    pub volume_usd: f64,
+}
+
+/// Trading activity of a block, summed over its parsed transactions.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct BlockStats {
+    pub transactions: usize,
+    pub trades: usize,
+    /// Distinct traders: the user of each trade, else its signer or its transaction's.
+    pub unique_wallets: usize,
+    pub volume_sol: f64,
+    pub volume_usd: f64,
+    /// Keyed by AMM, or by program name for trades without one.
+    pub volume_by_amm: HashMap<String, VolumeStats>,
+    pub volume_by_pool: HashMap<String, VolumeStats>,
+    /// Pools created in the block, by create events and migrations, in block order.
+    pub new_pools: Vec<String>,
+}
+
 /// Wrapper returned by `parse_block` helper functions.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct BlockParseResult {
     pub slot: u64,
@@ -491,6 +1326,49 @@ pub struct BlockParseResult {
     pub transactions: Vec<ParseResult>,
 }
 
+impl BlockParseResult {
+    /// Merge another partial parse of the same block, combining transactions by signature.
+    ///
+    /// When every transaction carries its block context they are put back in block order.
+    pub fn merge(&mut self, other: BlockParseResult) {
+        if other.slot != 0 {
+            self.slot = other.slot;
+        }
+        if other.timestamp.is_some() {
+            self.timestamp = other.timestamp;
+        }
+        for tx in other.transactions {
+            let existing = self
+                .transactions
+                .iter_mut()
+                .find(|existing| !tx.signature.is_empty() && existing.signature == tx.signature);
+            match existing {
+                Some(existing) => existing.merge(tx),
+                None => self.transactions.push(tx),
+            }
+        }
+        if self
+            .transactions
+            .iter()
+            .all(|tx| tx.block_context.is_some())
+        {
+            self.transactions
+                .sort_by_key(|tx| tx.block_context.as_ref().map(|c| c.transaction_index));
+        }
+    }
+
+    /// Sandwiches of the block: see [`detect_sandwiches`](crate::core::mev::detect_sandwiches).
+    pub fn detect_sandwiches(&self) -> Vec<MevEvent> {
+        crate::core::mev::detect_sandwiches(&self.transactions)
+    }
+
+    /// Volume and pool statistics of the block: see
+    /// [`block_stats`](crate::core::block_stats::block_stats).
+    pub fn stats(&self, config: &ParseConfig) -> BlockStats {
+        crate::core::block_stats::block_stats(&self.transactions, config)
+    }
+}
+
 /// Convenience alias used by parsers.
 pub type TransferMap = HashMap<String, Vec<TransferData>>;
 
@@ -0,0 +1,214 @@
+//! Watch list pushing the trades of selected wallets and mints to callbacks or webhooks.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::Arc;
+#[cfg(feature = "webhooks")]
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::{ParseResult, TradeInfo};
+
+/// How long a webhook may take to accept an alert before delivery counts as failed.
+#[cfg(feature = "webhooks")]
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Trade that matched a watch, as passed to callbacks and posted to webhooks.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TradeAlert {
+    /// Name of the watch that matched.
+    pub watch: String,
+    pub trade: TradeInfo,
+    /// Position of the watch in its list, so watches sharing a name keep their own target.
+    #[serde(skip)]
+    watch_index: usize,
+}
+
+/// Where the alerts of a watch go.
+#[derive(Clone)]
+pub enum AlertTarget {
+    Callback(Arc<dyn Fn(&TradeAlert) + Send + Sync>),
+    /// JSON `POST` of the alert to this URL.
+    #[cfg(feature = "webhooks")]
+    Webhook(String),
+}
+
+impl fmt::Debug for AlertTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Callback(_) => f.write_str("Callback(..)"),
+            #[cfg(feature = "webhooks")]
+            Self::Webhook(url) => f.debug_tuple("Webhook").field(url).finish(),
+        }
+    }
+}
+
+/// Wallets and mints whose trades are sent to one target.
+///
+/// A trade matches when its user or one of its signers is a watched wallet, or when either
+/// of its tokens is a watched mint.
+#[derive(Clone, Debug)]
+pub struct Watch {
+    name: String,
+    wallets: HashSet<String>,
+    mints: HashSet<String>,
+    target: AlertTarget,
+}
+
+impl Watch {
+    pub fn new(name: impl Into<String>, target: AlertTarget) -> Self {
+        Self {
+            name: name.into(),
+            wallets: HashSet::new(),
+            mints: HashSet::new(),
+            target,
+        }
+    }
+
+    pub fn wallet(mut self, wallet: impl Into<String>) -> Self {
+        self.wallets.insert(wallet.into());
+        self
+    }
+
+    pub fn mint(mut self, mint: impl Into<String>) -> Self {
+        self.mints.insert(mint.into());
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn matches(&self, trade: &TradeInfo) -> bool {
+        let wallet = trade
+            .user
+            .iter()
+            .chain(trade.signer.iter().flatten())
+            .any(|wallet| self.wallets.contains(wallet));
+        wallet
+            || self.mints.contains(&trade.input_token.mint)
+            || self.mints.contains(&trade.output_token.mint)
+    }
+}
+
+/// Watch entry of a JSON watch-list file.
+#[cfg(feature = "webhooks")]
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchSpec {
+    pub name: String,
+    #[serde(default)]
+    pub wallets: Vec<String>,
+    #[serde(default)]
+    pub mints: Vec<String>,
+    pub webhook: String,
+}
+
+#[cfg(feature = "webhooks")]
+impl From<WatchSpec> for Watch {
+    fn from(spec: WatchSpec) -> Self {
+        let watch = Watch::new(spec.name, AlertTarget::Webhook(spec.webhook));
+        let watch = spec.wallets.into_iter().fold(watch, Watch::wallet);
+        spec.mints.into_iter().fold(watch, Watch::mint)
+    }
+}
+
+/// Registered watches and the HTTP client their webhooks share.
+///
+/// The blocking client is only built once a webhook watch is added: creating or dropping it
+/// inside an async runtime panics, which lists of callbacks never risk.
+#[derive(Clone, Debug, Default)]
+pub struct WatchList {
+    watches: Vec<Watch>,
+    #[cfg(feature = "webhooks")]
+    client: Option<reqwest::blocking::Client>,
+}
+
+impl WatchList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build a list from the entries of a watch-list file.
+    #[cfg(feature = "webhooks")]
+    pub fn from_specs(specs: impl IntoIterator<Item = WatchSpec>) -> Self {
+        specs
+            .into_iter()
+            .fold(Self::new(), |list, spec| list.with_watch(spec.into()))
+    }
+
+    pub fn with_watch(mut self, watch: Watch) -> Self {
+        #[cfg(feature = "webhooks")]
+        if matches!(watch.target, AlertTarget::Webhook(_)) && self.client.is_none() {
+            self.client = Some(
+                reqwest::blocking::Client::builder()
+                    .timeout(WEBHOOK_TIMEOUT)
+                    .build()
+                    .unwrap_or_default(),
+            );
+        }
+        self.watches.push(watch);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.watches.is_empty()
+    }
+
+    /// One alert per trade of `result` and watch it matches, in trade order.
+    pub fn alerts(&self, result: &ParseResult) -> Vec<TradeAlert> {
+        result
+            .trades
+            .iter()
+            .flat_map(|trade| {
+                self.watches
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, watch)| watch.matches(trade))
+                    .map(|(watch_index, watch)| TradeAlert {
+                        watch: watch.name.clone(),
+                        trade: trade.clone(),
+                        watch_index,
+                    })
+            })
+            .collect()
+    }
+
+    /// Hand `alert` to the target of the watch that raised it.
+    pub fn deliver(&self, alert: &TradeAlert) -> anyhow::Result<()> {
+        let watch = self
+            .watches
+            .get(alert.watch_index)
+            .filter(|watch| watch.name == alert.watch)
+            .ok_or_else(|| anyhow::anyhow!("no watch {} in this list", alert.watch))?;
+        match &watch.target {
+            AlertTarget::Callback(callback) => callback(alert),
+            #[cfg(feature = "webhooks")]
+            AlertTarget::Webhook(url) => {
+                let client = self
+                    .client
+                    .as_ref()
+                    .expect("webhook watches build the client");
+                client.post(url).json(alert).send()?.error_for_status()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Deliver every alert raised by `result`, logging failed webhooks; returns the number of
+    /// alerts delivered.
+    pub fn notify(&self, result: &ParseResult) -> usize {
+        self.alerts(result)
+            .iter()
+            .filter(|alert| match self.deliver(alert) {
+                Ok(()) => true,
+                Err(err) => {
+                    tracing::warn!("failed to deliver alert for watch {}: {err}", alert.watch);
+                    false
+                }
+            })
+            .count()
+    }
+}
@@ -0,0 +1,194 @@
+//! Real-time feeds over a Solana websocket, behind the `ws` feature.
+//!
+//! [`subscribe`] opens a subscription, turns each notification into a [`SolanaTransaction`],
+//! parses it and hands the [`ParseResult`] to a channel. Two sources are supported: the
+//! standard `logsSubscribe`, whose notifications only carry a signature and are completed
+//! with `getTransaction`, and the `transactionSubscribe` of enhanced providers (Helius and
+//! compatible), whose notifications carry the whole transaction.
+
+use std::sync::Arc;
+
+use anyhow::{anyhow, bail, Context, Result};
+use futures::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use tokio::sync::mpsc;
+use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::Message;
+
+use crate::config::ParseConfig;
+use crate::core::dex_parser::DexParser;
+use crate::rpc::{self, RetryPolicy};
+use crate::types::{ParseResult, SolanaTransaction, TransactionStatus};
+
+/// JSON-RPC id of the subscribe request.
+const SUBSCRIBE_ID: u64 = 1;
+
+/// What to subscribe to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Subscription {
+    /// `logsSubscribe` to transactions mentioning `account`, each fetched from `rpc_url`.
+    Logs { account: String, rpc_url: String },
+    /// `transactionSubscribe` to transactions touching any of `accounts`.
+    Transactions { accounts: Vec<String> },
+}
+
+impl Subscription {
+    /// The JSON-RPC subscribe request. Both ask for `confirmed` commitment, the lowest
+    /// `getTransaction` serves.
+    pub fn request(&self) -> Value {
+        let (method, params) = match self {
+            Subscription::Logs { account, .. } => (
+                "logsSubscribe",
+                json!([{"mentions": [account]}, {"commitment": "confirmed"}]),
+            ),
+            Subscription::Transactions { accounts } => (
+                "transactionSubscribe",
+                json!([
+                    {"accountInclude": accounts, "vote": false},
+                    {
+                        "commitment": "confirmed",
+                        "encoding": "json",
+                        "transactionDetails": "full",
+                        "maxSupportedTransactionVersion": 0,
+                    },
+                ]),
+            ),
+        };
+        json!({"jsonrpc": "2.0", "id": SUBSCRIBE_ID, "method": method, "params": params})
+    }
+}
+
+/// Options of [`subscribe`].
+#[derive(Clone, Debug)]
+pub struct SubscribeOptions {
+    /// Results buffered in the channel before the feed waits for the consumer.
+    pub capacity: usize,
+    /// Retries of the `getTransaction` calls of a [`Subscription::Logs`] feed.
+    pub retry: RetryPolicy,
+    pub config: Option<ParseConfig>,
+    /// Pass failed transactions through instead of leaving them out.
+    pub include_failed: bool,
+}
+
+impl Default for SubscribeOptions {
+    fn default() -> Self {
+        Self {
+            capacity: 256,
+            retry: RetryPolicy::default(),
+            config: None,
+            include_failed: false,
+        }
+    }
+}
+
+/// Connect to `ws_url`, open `subscription` and stream the parsed transactions.
+///
+/// Returns once the node confirmed the subscription; connection and subscription errors are
+/// returned here. Afterwards a notification that cannot be converted or fetched yields an
+/// `Err` in its place and the feed goes on. The feed ends, closing the channel, when the
+/// socket closes or fails, or when the receiver is dropped; it does not reconnect.
+///
+/// Must be called inside a Tokio runtime, which runs the feed.
+pub async fn subscribe(
+    ws_url: &str,
+    subscription: Subscription,
+    parser: Arc<DexParser>,
+    options: SubscribeOptions,
+) -> Result<mpsc::Receiver<Result<ParseResult>>> {
+    let (mut socket, _) = connect_async(ws_url)
+        .await
+        .with_context(|| format!("failed to connect to {ws_url}"))?;
+    socket
+        .send(Message::Text(subscription.request().to_string()))
+        .await
+        .context("failed to send the subscribe request")?;
+
+    loop {
+        let text = match socket.next().await {
+            Some(Ok(Message::Text(text))) => text,
+            Some(Ok(Message::Close(_))) | None => {
+                bail!("websocket closed before the subscription was confirmed")
+            }
+            Some(Ok(_)) => continue,
+            Some(Err(err)) => return Err(err).context("websocket failed"),
+        };
+        let reply: Value = serde_json::from_str(&text).context("invalid websocket message")?;
+        if reply["id"] != SUBSCRIBE_ID {
+            continue;
+        }
+        if let Some(err) = reply.get("error") {
+            bail!("subscription rejected: {err}");
+        }
+        break;
+    }
+
+    let (sender, receiver) = mpsc::channel(options.capacity.max(1));
+    tokio::spawn(async move {
+        while let Some(message) = socket.next().await {
+            let text = match message {
+                Ok(Message::Text(text)) => text,
+                Ok(Message::Close(_)) => break,
+                Ok(_) => continue,
+                Err(err) => {
+                    let _ = sender
+                        .send(Err(anyhow::Error::new(err).context("websocket failed")))
+                        .await;
+                    break;
+                }
+            };
+            let tx = match notification(&text, &subscription, &options).await {
+                Ok(Some(tx)) => tx,
+                Ok(None) => continue,
+                Err(err) => {
+                    if sender.send(Err(err)).await.is_err() {
+                        break;
+                    }
+                    continue;
+                }
+            };
+            if !options.include_failed && tx.meta.status == TransactionStatus::Failed {
+                continue;
+            }
+            let result = parser.parse_all(tx, options.config.clone());
+            if sender.send(Ok(result)).await.is_err() {
+                break;
+            }
+        }
+    });
+    Ok(receiver)
+}
+
+/// The transaction a websocket message notifies about, `None` for other messages and for
+/// failed transactions a logs feed does not fetch.
+async fn notification(
+    text: &str,
+    subscription: &Subscription,
+    options: &SubscribeOptions,
+) -> Result<Option<SolanaTransaction>> {
+    let message: Value = serde_json::from_str(text).context("invalid websocket message")?;
+    let result = &message["params"]["result"];
+    match (message["method"].as_str(), subscription) {
+        (Some("logsNotification"), Subscription::Logs { rpc_url, .. }) => {
+            let value = &result["value"];
+            if !options.include_failed && !value["err"].is_null() {
+                return Ok(None);
+            }
+            let signature = value["signature"]
+                .as_str()
+                .ok_or_else(|| anyhow!("logs notification has no signature"))?;
+            rpc::fetch_transaction_with_retry(rpc_url, signature, &options.retry)
+                .await
+                .map(Some)
+        }
+        (Some("transactionNotification"), Subscription::Transactions { .. }) => {
+            // The payload is a `getTransaction` result short of its slot and block time.
+            let mut payload = result["transaction"].clone();
+            let object = payload
+                .as_object_mut()
+                .ok_or_else(|| anyhow!("transaction notification has no transaction"))?;
+            object.insert("slot".to_string(), result["slot"].clone());
+            rpc::transaction_from_rpc_json(payload).map(Some)
+        }
+        _ => Ok(None),
+    }
+}
@@ -0,0 +1,97 @@
+use solana_dex_parser::core::aggregate::aggregate_route;
+use solana_dex_parser::types::{TokenInfo, TradeType};
+use solana_dex_parser::TradeInfo;
+
+mod common;
+
+const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+const USDC_MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+const TOKEN_MINT: &str = "4wBqpZM9xaSheZzJSMawUKKwhdpChKbZ5eu5ky4Vigw";
+
+fn hop(idx: &str, amm: &str, pool: &str, input: TokenInfo, output: TokenInfo) -> TradeInfo {
+    TradeInfo {
+        trade_type: TradeType::Swap,
+        pool: vec![pool.to_string()],
+        input_token: input,
+        output_token: output,
+        user: Some("user".to_string()),
+        amm: Some(amm.to_string()),
+        signature: "route".into(),
+        idx: idx.to_string(),
+        ..TradeInfo::default()
+    }
+}
+
+#[test]
+fn chained_and_split_hops_net_into_one_trade() {
+    let trades = [
+        hop(
+            "0-0",
+            "RaydiumCPMM",
+            "pool-a",
+            common::token(SOL_MINT, 600_000_000, 9),
+            common::token(USDC_MINT, 90_000_000, 6),
+        ),
+        hop(
+            "1-0",
+            "Orca",
+            "pool-b",
+            common::token(SOL_MINT, 400_000_000, 9),
+            common::token(USDC_MINT, 60_000_000, 6),
+        ),
+        hop(
+            "2-0",
+            "Meteora",
+            "pool-c",
+            common::token(USDC_MINT, 150_000_000, 6),
+            common::token(TOKEN_MINT, 49_800_000_000, 9),
+        ),
+    ];
+
+    let net = aggregate_route(&trades).expect("net trade");
+
+    assert_eq!(net.trade_type, TradeType::Buy);
+    assert_eq!(net.input_token.mint, SOL_MINT);
+    assert_eq!(net.input_token.amount_raw, "1000000000");
+    assert_eq!(net.input_token.amount, 1.0);
+    assert_eq!(net.output_token.mint, TOKEN_MINT);
+    assert_eq!(net.output_token.amount_raw, "49800000000");
+    assert_eq!(net.pool, ["pool-a", "pool-b", "pool-c"]);
+    assert_eq!(
+        net.amms.as_deref(),
+        Some(
+            &[
+                "RaydiumCPMM".to_string(),
+                "Orca".to_string(),
+                "Meteora".to_string()
+            ][..]
+        )
+    );
+    assert_eq!(net.route.as_deref(), Some("RaydiumCPMM -> Orca -> Meteora"));
+    assert_eq!(net.idx, "0-0");
+}
+
+#[test]
+fn unrelated_swaps_keep_the_last_route() {
+    let trades = [
+        hop(
+            "0-0",
+            "RaydiumCPMM",
+            "pool-a",
+            common::token(SOL_MINT, 1_000_000_000, 9),
+            common::token(USDC_MINT, 150_000_000, 6),
+        ),
+        hop(
+            "1-0",
+            "Orca",
+            "pool-b",
+            common::token(TOKEN_MINT, 5_000_000_000, 9),
+            common::token(SOL_MINT, 100_000_000, 9),
+        ),
+    ];
+
+    let net = aggregate_route(&trades).expect("net trade");
+
+    assert_eq!(net, trades[1]);
+    assert!(aggregate_route(&[]).is_none());
+}
@@ -0,0 +1,86 @@
+use solana_dex_parser::core::anomaly::flag_suspect_trades;
+use solana_dex_parser::{ParseResult, PoolState, TradeInfo};
+
+mod common;
+
+const POOL: &str = "58oQChx4yWmvKdwLLZzBi4ChoCc2fqCUWBkwMihLYQo2";
+const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+const USDC_MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+
+/// Sells `sol` for `usdc` on the pool, or buys when `buy` is set.
+fn trade(sol: f64, usdc: f64, buy: bool) -> TradeInfo {
+    let sol = common::ui_token(SOL_MINT, sol, 9);
+    let usdc = common::ui_token(USDC_MINT, usdc, 6);
+    TradeInfo {
+        pool: vec![POOL.to_string()],
+        slot: 100,
+        ..common::swap(sol, usdc, !buy)
+    }
+}
+
+fn results(trades: Vec<TradeInfo>) -> Vec<ParseResult> {
+    trades
+        .into_iter()
+        .map(|trade| ParseResult {
+            trades: vec![trade],
+            ..ParseResult::new()
+        })
+        .collect()
+}
+
+fn flags(results: &[ParseResult]) -> Vec<bool> {
+    results
+        .iter()
+        .map(|result| result.trades[0].suspect)
+        .collect()
+}
+
+#[test]
+fn price_far_from_the_pools_other_trades_is_suspect() {
+    let mut block = results(vec![
+        trade(1.0, 150.0, false),
+        trade(2.0, 302.0, true),
+        // A decimals regression reads 150 USDC as 150_000.
+        trade(1.0, 150_000.0, false),
+        trade(0.5, 74.5, true),
+    ]);
+
+    flag_suspect_trades(&mut block, 0.1);
+
+    assert_eq!(flags(&block), [false, false, true, false]);
+    let serialized = serde_json::to_value(&block[2].trades[0]).unwrap();
+    assert_eq!(serialized["suspect"], true);
+    assert!(serde_json::to_value(&block[0].trades[0])
+        .unwrap()
+        .get("suspect")
+        .is_none());
+}
+
+#[test]
+fn two_disagreeing_trades_are_not_enough_to_tell() {
+    let mut block = results(vec![trade(1.0, 150.0, false), trade(1.0, 1.5, false)]);
+
+    flag_suspect_trades(&mut block, 0.1);
+
+    assert_eq!(flags(&block), [false, false]);
+}
+
+#[test]
+fn trade_moving_more_than_the_pool_holds_is_suspect() {
+    let mut drained = trade(1.0, 150.0, false);
+    drained.pool_state = Some(PoolState {
+        slot: 100,
+        mint_a: SOL_MINT.to_string(),
+        reserve_a: "500000000000".to_string(),
+        mint_b: USDC_MINT.to_string(),
+        reserve_b: "100000000".to_string(),
+        liquidity: None,
+    });
+    let mut fine = drained.clone();
+    fine.pool_state.as_mut().unwrap().reserve_b = "900000000".to_string();
+    let mut block = results(vec![drained, fine]);
+
+    flag_suspect_trades(&mut block, 0.1);
+
+    assert_eq!(flags(&block), [true, false]);
+}
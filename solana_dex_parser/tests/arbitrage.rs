@@ -0,0 +1,144 @@
+use anyhow::Result;
+use serde_json::{json, Value};
+use solana_dex_parser::core::arbitrage::detect_arbitrage;
+use solana_dex_parser::types::TokenInfo;
+use solana_dex_parser::{DexParser, SolanaTransaction, TradeInfo};
+
+mod common;
+
+const STEP: &str = "SSwpMgqNDsyV7mAgN9ady4bDVu5ySjmmXejXvy2vLt1";
+const SAROS: &str = "SSwapUtytfBdBn1b9NUGG6foMVPtcWgpRU32HToDUZr";
+const TOKEN_PROGRAM: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+const USDC_MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+const BONK_MINT: &str = "DezXAZ8z7PnrnRJjz3wXBoRgixCa6xjnB7YaB1pPB263";
+
+fn transfer(idx: &str, source: &str, destination: &str, mint: &str, amount: u64) -> Value {
+    let decimals = if mint == USDC_MINT { 6 } else { 9 };
+    common::transfer(
+        TOKEN_PROGRAM,
+        idx,
+        source,
+        destination,
+        mint,
+        amount,
+        decimals,
+    )
+}
+
+fn swap(
+    program_id: &str,
+    pool: &str,
+    [source, vault_in, vault_out, destination]: [&str; 4],
+) -> Value {
+    let mut data = vec![1u8];
+    data.extend_from_slice(&1_000u64.to_le_bytes());
+    data.extend_from_slice(&0u64.to_le_bytes());
+    json!({
+        "programId": program_id,
+        "accounts": [
+            pool,
+            "authority",
+            "bot",
+            source,
+            vault_in,
+            vault_out,
+            destination,
+            "lp-mint",
+            "fee-account",
+            TOKEN_PROGRAM,
+        ],
+        "data": bs58::encode(data).into_string(),
+    })
+}
+
+fn transaction(back_to_sol: u64) -> Result<SolanaTransaction> {
+    common::transaction(
+        "arb",
+        json!({
+            "signers": ["bot"],
+            "instructions": [
+                swap(STEP, "step-pool", ["bot-sol", "step-vault-sol", "step-vault-usdc", "bot-usdc"]),
+                swap(SAROS, "saros-pool", ["bot-usdc", "saros-vault-usdc", "saros-vault-sol", "bot-sol"]),
+            ],
+            "transfers": [
+                transfer("0-0", "bot-sol", "step-vault-sol", SOL_MINT, 1_000_000_000),
+                transfer("0-1", "step-vault-usdc", "bot-usdc", USDC_MINT, 150_000_000),
+                transfer("1-0", "bot-usdc", "saros-vault-usdc", USDC_MINT, 150_000_000),
+                transfer("1-1", "saros-vault-sol", "bot-sol", SOL_MINT, back_to_sol),
+            ],
+        }),
+    )
+}
+
+#[test]
+fn cycle_across_two_venues_with_a_profit_is_an_arbitrage() -> Result<()> {
+    let result = DexParser::new().parse_all(transaction(1_020_000_000)?, None);
+
+    let arbitrage = result.arbitrage.expect("arbitrage");
+    assert_eq!(arbitrage.wallet, "bot");
+    assert_eq!(arbitrage.profit.mint, SOL_MINT);
+    assert_eq!(arbitrage.profit.amount_raw, "20000000");
+    assert_eq!(arbitrage.profit.amount, 0.02);
+    let legs: Vec<_> = arbitrage
+        .legs
+        .iter()
+        .map(|leg| (leg.amm.as_deref(), leg.pool.as_deref(), leg.idx.as_str()))
+        .collect();
+    assert_eq!(
+        legs,
+        [
+            (Some("Step"), Some("step-pool"), "0-0"),
+            (Some("Saros"), Some("saros-pool"), "1-0"),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn losing_cycles_are_not_arbitrage() -> Result<()> {
+    let result = DexParser::new().parse_all(transaction(990_000_000)?, None);
+
+    assert_eq!(result.trades.len(), 2);
+    assert_eq!(result.arbitrage, None);
+    Ok(())
+}
+
+fn trade(pool: &str, idx: &str, input: (&str, u64), output: (&str, u64)) -> TradeInfo {
+    let token = |(mint, raw): (&str, u64)| TokenInfo {
+        mint: mint.to_string(),
+        amount_raw: raw.to_string(),
+        decimals: 9,
+        ..TokenInfo::default()
+    };
+    TradeInfo {
+        pool: vec![pool.to_string()],
+        idx: idx.to_string(),
+        input_token: token(input),
+        output_token: token(output),
+        ..TradeInfo::default()
+    }
+}
+
+#[test]
+fn three_leg_cycles_skip_unrelated_swaps() {
+    let trades = [
+        trade("a", "0-0", (SOL_MINT, 100), (USDC_MINT, 15)),
+        trade("x", "1-0", (BONK_MINT, 7), (SOL_MINT, 1)),
+        trade("b", "2-0", (USDC_MINT, 15), (BONK_MINT, 900)),
+        trade("c", "3-0", (BONK_MINT, 900), (SOL_MINT, 103)),
+    ];
+
+    let arbitrage = detect_arbitrage("bot".to_string(), &trades).expect("arbitrage");
+
+    let idxs: Vec<_> = arbitrage.legs.iter().map(|leg| leg.idx.as_str()).collect();
+    assert_eq!(idxs, ["0-0", "2-0", "3-0"]);
+    assert_eq!(arbitrage.profit.amount_raw, "3");
+
+    // A buy and sell on one pool is a round trip, not an arbitrage.
+    let round_trip = [
+        trade("a", "0-0", (SOL_MINT, 100), (USDC_MINT, 15)),
+        trade("a", "1-0", (USDC_MINT, 15), (SOL_MINT, 101)),
+    ];
+    assert_eq!(detect_arbitrage("bot".to_string(), &round_trip), None);
+}
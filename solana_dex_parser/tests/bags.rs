@@ -0,0 +1,219 @@
+use anyhow::Result;
+use serde_json::{json, Value};
+use solana_dex_parser::types::{FeeShare, TradeType};
+use solana_dex_parser::{DexParser, SolanaTransaction};
+
+mod common;
+
+const BAGS: &str = "BAGSB9TpGrZxQbEsrEznv5jXXdwyP6AXerN8aVRiAmcv";
+const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+const TOKEN_MINT: &str = "4wBqpZM9xaSheZzJSMawUKKwhdpChKbZ5eu5ky4Vigw";
+const USER: &str = "9xQeWvG816bUx9EPjHmaT23yvVM2ZWbrrpZb9PusVFin";
+const CURVE: &str = "3iQL8BFS2vE7mww4ehAqQHAsbmRNCrPxizWAT2Zfyr9y";
+const TREASURY: &str = "CebN5WGQ4jvEPvsVU4EoHEpgzq1VV7AbicfhtW4xC9iM";
+const CREATOR: &str = "7YttLkHDoNj9wyDur5pM1ejNaAvT9X4eqaYcHQqtj2G5";
+const PARTNER: &str = "62qc2CNXwrYqQScmEdiZFFAnJR262PxWEuNQtxfafNgV";
+
+const CREATE: [u8; 8] = [24, 30, 200, 40, 5, 28, 7, 119];
+const BUY: [u8; 8] = [102, 6, 61, 18, 1, 218, 235, 234];
+const CLAIM_FEE: [u8; 8] = [169, 32, 79, 137, 136, 232, 70, 137];
+const EVT_TRADE: [u8; 16] = [
+    228, 69, 165, 46, 81, 203, 154, 29, 189, 219, 127, 211, 78, 230, 97, 238,
+];
+const EVT_CLAIM_FEE: [u8; 16] = [
+    228, 69, 165, 46, 81, 203, 154, 29, 173, 80, 235, 15, 116, 19, 144, 33,
+];
+
+fn borsh_string(value: &str) -> Vec<u8> {
+    let mut bytes = (value.len() as u32).to_le_bytes().to_vec();
+    bytes.extend_from_slice(value.as_bytes());
+    bytes
+}
+
+fn pubkey(value: &str) -> Vec<u8> {
+    bs58::decode(value).into_vec().expect("valid pubkey")
+}
+
+fn encode(parts: &[&[u8]]) -> String {
+    bs58::encode(parts.concat()).into_string()
+}
+
+fn transaction(instructions: Value, inner_instructions: Value) -> Result<SolanaTransaction> {
+    common::transaction(
+        "bags",
+        json!({
+            "signers": [USER],
+            "instructions": instructions,
+            "innerInstructions": inner_instructions,
+            "transfers": [],
+        }),
+    )
+}
+
+fn fee_share_instruction(shares: &[(&str, u16)]) -> Vec<u8> {
+    let mut data = (shares.len() as u32).to_le_bytes().to_vec();
+    for (wallet, bps) in shares {
+        data.extend(pubkey(wallet));
+        data.extend(bps.to_le_bytes());
+    }
+    data
+}
+
+fn trade_event(is_buy: bool, shares: &[(&str, u64)]) -> String {
+    let mut split = (shares.len() as u32).to_le_bytes().to_vec();
+    for (wallet, amount) in shares {
+        split.extend(pubkey(wallet));
+        split.extend(amount.to_le_bytes());
+    }
+    let creator_fee: u64 = shares.iter().map(|(_, amount)| amount).sum();
+    encode(&[
+        &EVT_TRADE,
+        &pubkey(TOKEN_MINT),
+        &pubkey(SOL_MINT),
+        &pubkey(USER),
+        &pubkey(CURVE),
+        &[u8::from(is_buy)],
+        &5_000_000_000u64.to_le_bytes(),
+        &1_020_000_000u64.to_le_bytes(),
+        &10_000_000u64.to_le_bytes(),
+        &pubkey(TREASURY),
+        &creator_fee.to_le_bytes(),
+        &split,
+    ])
+}
+
+#[test]
+fn create_records_the_creator_fee_split() -> Result<()> {
+    let data = encode(&[
+        &CREATE,
+        &borsh_string("Bag Token"),
+        &borsh_string("BAG"),
+        &borsh_string("https://example.com/bag.json"),
+        &fee_share_instruction(&[(CREATOR, 7_500), (PARTNER, 2_500)]),
+    ]);
+    let tx = transaction(
+        json!([{
+            "programId": BAGS,
+            "accounts": [CREATOR, TOKEN_MINT, CURVE, SOL_MINT],
+            "data": data,
+        }]),
+        json!([]),
+    )?;
+
+    let result = DexParser::new().parse_all(tx, None);
+
+    assert!(result.trades.is_empty());
+    let create = &result.meme_events[0];
+    assert_eq!(create.event_type, TradeType::Create);
+    assert_eq!(create.protocol.as_deref(), Some("Bags"));
+    assert_eq!(create.symbol.as_deref(), Some("BAG"));
+    assert_eq!(create.creator.as_deref(), Some(CREATOR));
+    assert_eq!(create.bonding_curve.as_deref(), Some(CURVE));
+    assert_eq!(
+        create.fee_shares,
+        [
+            FeeShare {
+                wallet: CREATOR.to_string(),
+                bps: 7_500,
+            },
+            FeeShare {
+                wallet: PARTNER.to_string(),
+                bps: 2_500,
+            },
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn buys_split_the_creator_fee_across_fee_share_wallets() -> Result<()> {
+    let tx = transaction(
+        json!([{
+            "programId": BAGS,
+            "accounts": [USER, TOKEN_MINT, CURVE],
+            "data": encode(&[&BUY, &5_000_000_000u64.to_le_bytes(), &u64::MAX.to_le_bytes()]),
+        }]),
+        json!([{
+            "index": 0,
+            "instructions": [{
+                "programId": BAGS,
+                "accounts": [],
+                "data": trade_event(true, &[(CREATOR, 7_500_000), (PARTNER, 2_500_000)]),
+            }],
+        }]),
+    )?;
+
+    let result = DexParser::new().parse_all(tx, None);
+
+    assert_eq!(result.trades.len(), 1);
+    let trade = &result.trades[0];
+    assert_eq!(trade.trade_type, TradeType::Buy);
+    assert_eq!(trade.amm.as_deref(), Some("Bags"));
+    assert_eq!(trade.pool, [CURVE]);
+    assert_eq!(trade.input_token.mint, SOL_MINT);
+    assert_eq!(trade.input_token.amount_raw, "1020000000");
+    assert_eq!(trade.output_token.mint, TOKEN_MINT);
+    assert_eq!(trade.output_token.amount, 5_000.0);
+
+    let fees: Vec<_> = trade
+        .fees
+        .iter()
+        .map(|fee| {
+            (
+                fee.fee_type.as_deref(),
+                fee.recipient.as_deref(),
+                fee.amount_raw.as_str(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        fees,
+        [
+            (Some("protocol"), Some(TREASURY), "10000000"),
+            (Some("coinCreator"), Some(CREATOR), "7500000"),
+            (Some("coinCreator"), Some(PARTNER), "2500000"),
+        ]
+    );
+    assert_eq!(trade.fee.as_ref().map(|fee| fee.amount), Some(0.02));
+
+    let buy = &result.meme_events[0];
+    assert_eq!(buy.event_type, TradeType::Buy);
+    assert_eq!(buy.creator_fee, Some(0.01));
+    assert_eq!(buy.protocol_fee, Some(0.01));
+    Ok(())
+}
+
+#[test]
+fn fee_claims_are_reported_per_wallet() -> Result<()> {
+    let claim = encode(&[
+        &EVT_CLAIM_FEE,
+        &pubkey(TOKEN_MINT),
+        &pubkey(SOL_MINT),
+        &pubkey(PARTNER),
+        &250_000_000u64.to_le_bytes(),
+    ]);
+    let tx = transaction(
+        json!([{
+            "programId": BAGS,
+            "accounts": [PARTNER, TOKEN_MINT],
+            "data": encode(&[&CLAIM_FEE]),
+        }]),
+        json!([{
+            "index": 0,
+            "instructions": [{ "programId": BAGS, "accounts": [], "data": claim }],
+        }]),
+    )?;
+
+    let result = DexParser::new().parse_all(tx, None);
+
+    assert_eq!(result.meme_events.len(), 1);
+    let claim = &result.meme_events[0];
+    assert_eq!(claim.event_type, TradeType::FeeClaim);
+    assert_eq!(claim.user, PARTNER);
+    assert_eq!(claim.base_mint, TOKEN_MINT);
+    assert_eq!(claim.protocol.as_deref(), Some("Bags"));
+    let payout = claim.output_token.as_ref().expect("payout");
+    assert_eq!(payout.mint, SOL_MINT);
+    assert_eq!(payout.amount, 0.25);
+    Ok(())
+}
@@ -0,0 +1,171 @@
+use anyhow::Result;
+use serde_json::{json, Value};
+use solana_dex_parser::{BlockContext, DexParser, ParseConfig, SolanaBlock};
+
+const LEADER: &str = "Leader1111111111111111111111111111111111111";
+
+fn transaction(signature: &str) -> Value {
+    json!({
+        "slot": 300_000_000,
+        "signature": signature,
+        "blockTime": 1_730_000_000,
+        "signers": ["user"],
+    })
+}
+
+#[test]
+fn parsed_block_results_carry_epoch_leader_and_position() -> Result<()> {
+    let block: SolanaBlock = serde_json::from_value(json!({
+        "slot": 300_000_000,
+        "blockTime": 1_730_000_000,
+        "epoch": 694,
+        "leader": LEADER,
+        "transactions": [transaction("front"), transaction("victim"), transaction("back")],
+    }))?;
+
+    let result = DexParser::new().parse_block_parsed(&block, None);
+
+    let contexts: Vec<_> = result
+        .transactions
+        .iter()
+        .map(|tx| (tx.signature.as_str(), tx.block_context.clone()))
+        .collect();
+    let context = |transaction_index| {
+        Some(BlockContext {
+            epoch: Some(694),
+            leader: Some(LEADER.to_string()),
+            transaction_index,
+        })
+    };
+    assert_eq!(
+        contexts,
+        [
+            ("front", context(0)),
+            ("victim", context(1)),
+            ("back", context(2)),
+        ]
+    );
+    let serialized = serde_json::to_value(&result.transactions[1])?;
+    assert_eq!(
+        serialized["blockContext"],
+        json!({ "epoch": 694, "leader": LEADER, "transactionIndex": 1 })
+    );
+    Ok(())
+}
+
+#[test]
+fn raw_blocks_only_know_the_position_and_single_parses_have_no_context() -> Result<()> {
+    let parser = DexParser::new();
+    let raw = parser.parse_block_raw(&[transaction("a"), transaction("b")], None)?;
+
+    let second = raw.transactions[1].block_context.as_ref();
+    assert_eq!(second.map(|context| context.transaction_index), Some(1));
+    assert_eq!(second.and_then(|context| context.epoch), None);
+
+    let single = parser.parse_all(serde_json::from_value(transaction("a"))?, None);
+    assert_eq!(single.block_context, None);
+    assert!(serde_json::to_value(&single)?.get("blockContext").is_none());
+    Ok(())
+}
+
+#[test]
+fn sampled_blocks_keep_the_same_transactions_and_their_positions() -> Result<()> {
+    let signatures: Vec<String> = (0..400).map(|i| format!("signature-{i}")).collect();
+    let raw: Vec<Value> = signatures
+        .iter()
+        .map(|signature| transaction(signature))
+        .collect();
+    let block: SolanaBlock = serde_json::from_value(json!({
+        "slot": 300_000_000,
+        "transactions": raw,
+    }))?;
+    let parser = DexParser::new();
+    let config = ParseConfig::default().with_sample_rate(0.25);
+
+    let parsed = parser.parse_block_parsed(&block, Some(config.clone()));
+    let positions: Vec<(String, usize)> = parsed
+        .transactions
+        .iter()
+        .map(|tx| {
+            let index = tx
+                .block_context
+                .as_ref()
+                .map_or(usize::MAX, |c| c.transaction_index);
+            (tx.signature.clone(), index)
+        })
+        .collect();
+
+    assert!((60..140).contains(&positions.len()), "{}", positions.len());
+    for (signature, index) in &positions {
+        assert_eq!(signature, &signatures[*index]);
+    }
+    let again = parser.parse_block_raw(&raw, Some(config))?;
+    let raw_signatures: Vec<&str> = again
+        .transactions
+        .iter()
+        .map(|tx| tx.signature.as_str())
+        .collect();
+    let parsed_signatures: Vec<&str> = positions.iter().map(|(s, _)| s.as_str()).collect();
+    assert_eq!(raw_signatures, parsed_signatures);
+
+    let everything = ParseConfig::default().with_sample_rate(1.0);
+    assert_eq!(
+        parser
+            .parse_block_parsed(&block, Some(everything))
+            .transactions
+            .len(),
+        400
+    );
+    let nothing = ParseConfig::default().with_sample_rate(0.0);
+    assert!(parser
+        .parse_block_parsed(&block, Some(nothing))
+        .transactions
+        .is_empty());
+    Ok(())
+}
+
+#[test]
+fn sample_rates_outside_the_unit_range_are_clamped_or_rejected() {
+    assert_eq!(
+        ParseConfig::default().with_sample_rate(1.5).sample_rate,
+        Some(1.0)
+    );
+    assert_eq!(
+        ParseConfig::default().with_sample_rate(-0.5).sample_rate,
+        Some(0.0)
+    );
+    assert_eq!(
+        ParseConfig::default()
+            .with_sample_rate(f64::NAN)
+            .sample_rate,
+        Some(0.0)
+    );
+
+    let config: ParseConfig = serde_json::from_value(json!({ "sampleRate": 0.5 })).unwrap();
+    assert_eq!(config.sample_rate, Some(0.5));
+    let err = serde_json::from_value::<ParseConfig>(json!({ "sampleRate": 2.0 })).unwrap_err();
+    assert!(err.to_string().contains("sampleRate"), "{err}");
+}
+
+#[test]
+fn struct_literal_sample_rates_are_clamped_when_parsing() -> Result<()> {
+    let raw: Vec<Value> = (0..20)
+        .map(|i| transaction(&format!("signature-{i}")))
+        .collect();
+    let parser = DexParser::new();
+    let sampled = |rate: f64| -> Result<usize> {
+        let config = ParseConfig {
+            sample_rate: Some(rate),
+            ..ParseConfig::default()
+        };
+        Ok(parser
+            .parse_block_raw(&raw, Some(config))?
+            .transactions
+            .len())
+    };
+
+    assert_eq!(sampled(5.0)?, 20);
+    assert_eq!(sampled(-1.0)?, 0);
+    assert_eq!(sampled(f64::NAN)?, 0);
+    Ok(())
+}
@@ -0,0 +1,84 @@
+use solana_dex_parser::types::TradeType;
+use solana_dex_parser::{
+    BlockParseResult, MigrationEvent, ParseConfig, ParseResult, PoolEvent, TradeInfo,
+};
+
+const JUPITER: &str = "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4";
+
+fn trade(amm: &str, pool: &str, user: &str, volume_sol: f64) -> TradeInfo {
+    TradeInfo {
+        amm: Some(amm.to_string()),
+        pool: vec![pool.to_string()],
+        user: Some(user.to_string()),
+        volume_sol: Some(volume_sol),
+        volume_usd: Some(volume_sol * 150.0),
+        ..TradeInfo::default()
+    }
+}
+
+fn result(signer: &str, trades: Vec<TradeInfo>) -> ParseResult {
+    ParseResult {
+        signer: vec![signer.to_string()],
+        trades,
+        ..ParseResult::new()
+    }
+}
+
+fn block() -> BlockParseResult {
+    let routed = TradeInfo {
+        program_id: Some(JUPITER.to_string()),
+        ..trade("Orca", "pool-a", "carol", 3.0)
+    };
+    let mut created = result("dave", Vec::new());
+    created.liquidities.push(PoolEvent {
+        event_type: TradeType::Create,
+        pool_id: "pool-c".to_string(),
+        ..PoolEvent::default()
+    });
+    created.migrations.push(MigrationEvent {
+        pool: Some("pool-d".to_string()),
+        ..MigrationEvent::default()
+    });
+
+    BlockParseResult {
+        slot: 1,
+        timestamp: None,
+        transactions: vec![
+            result("alice", vec![trade("Orca", "pool-a", "alice", 1.0)]),
+            result(
+                "bob",
+                vec![
+                    trade("Orca", "pool-a", "bob", 2.0),
+                    trade("RaydiumCPMM", "pool-b", "bob", 0.5),
+                ],
+            ),
+            result("carol", vec![routed, trade("Orca", "pool-a", "carol", 3.0)]),
+            created,
+        ],
+    }
+}
+
+#[test]
+fn block_stats_sum_volume_per_amm_and_pool() {
+    let stats = block().stats(&ParseConfig::default());
+
+    assert_eq!(stats.transactions, 4);
+    // The Jupiter route is left out; its Orca hop is counted instead.
+    assert_eq!(stats.trades, 4);
+    assert_eq!(stats.unique_wallets, 3);
+    assert_eq!(stats.volume_sol, 6.5);
+    assert_eq!(stats.volume_usd, 975.0);
+    assert_eq!(stats.volume_by_amm.len(), 2);
+    assert_eq!(stats.volume_by_amm["Orca"].trades, 3);
+    assert_eq!(stats.volume_by_amm["Orca"].volume_sol, 6.0);
+    assert_eq!(stats.volume_by_amm["RaydiumCPMM"].volume_usd, 75.0);
+    assert_eq!(stats.volume_by_pool["pool-a"].trades, 3);
+    assert_eq!(stats.volume_by_pool["pool-b"].volume_sol, 0.5);
+}
+
+#[test]
+fn block_stats_list_pools_created_and_migrated() {
+    let stats = block().stats(&ParseConfig::default());
+
+    assert_eq!(stats.new_pools, ["pool-c", "pool-d"]);
+}
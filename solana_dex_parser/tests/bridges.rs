@@ -0,0 +1,157 @@
+use anyhow::Result;
+use serde_json::{json, Value};
+use solana_dex_parser::{BridgeDirection, DexParser, SolanaTransaction};
+
+mod common;
+
+const WORMHOLE: &str = "wormDTUJ6AWPNvk59vGQbDvGJmqbDTdgWgAqcLBCgUb";
+const DLN_SOURCE: &str = "src5qyZHqTqecJV4aY6Cb6zDZLMDzrDKKezs22MPHr4";
+const TOKEN_PROGRAM: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+const USDC_MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+
+const TRANSFER_NATIVE: u8 = 5;
+const COMPLETE_NATIVE: u8 = 2;
+const CREATE_ORDER: [u8; 8] = [141, 54, 37, 207, 237, 210, 250, 215];
+const ETHEREUM_WORMHOLE_CHAIN: u16 = 2;
+
+fn encode(parts: &[&[u8]]) -> String {
+    bs58::encode(parts.concat()).into_string()
+}
+
+fn transfer(program_id: &str, idx: &str, source: &str, destination: &str, amount: u64) -> Value {
+    common::transfer(program_id, idx, source, destination, USDC_MINT, amount, 6)
+}
+
+fn transaction(
+    program_id: &str,
+    accounts: &[&str],
+    data: String,
+    transfers: Value,
+) -> Result<SolanaTransaction> {
+    common::transaction(
+        "bridge",
+        json!({
+            "instructions": [{ "programId": program_id, "accounts": accounts, "data": data }],
+            "innerInstructions": [{
+                "index": 0,
+                "instructions": [{ "programId": TOKEN_PROGRAM, "accounts": [], "data": "" }],
+            }],
+            "transfers": transfers,
+        }),
+    )
+}
+
+#[test]
+fn wormhole_lockup_is_a_bridge_event_not_a_transfer() -> Result<()> {
+    let data = encode(&[
+        &[TRANSFER_NATIVE],
+        &7u32.to_le_bytes(),
+        &250_000_000_000u64.to_le_bytes(),
+        &0u64.to_le_bytes(),
+        &[0xab; 32],
+        &ETHEREUM_WORMHOLE_CHAIN.to_le_bytes(),
+    ]);
+    let tx = transaction(
+        WORMHOLE,
+        &["user", "config", "user-usdc", USDC_MINT, "custody"],
+        data,
+        json!([transfer(
+            WORMHOLE,
+            "0-0",
+            "user-usdc",
+            "custody",
+            250_000_000_000
+        )]),
+    )?;
+
+    let result = DexParser::new().parse_all(tx, None);
+
+    assert!(result.transfers.is_empty());
+    assert_eq!(result.bridges.len(), 1);
+    let bridge = &result.bridges[0];
+    assert_eq!(bridge.protocol, "Wormhole");
+    assert_eq!(bridge.direction, BridgeDirection::Outbound);
+    assert_eq!(bridge.user, "user");
+    assert_eq!(bridge.token.mint, USDC_MINT);
+    assert_eq!(bridge.token.amount, 250_000.0);
+    assert_eq!(bridge.target_chain, Some(2));
+    assert_eq!(
+        bridge.recipient.as_deref(),
+        Some(format!("0x{}", "ab".repeat(32)).as_str())
+    );
+    Ok(())
+}
+
+#[test]
+fn wormhole_release_reads_the_amount_from_the_payout() -> Result<()> {
+    let tx = transaction(
+        WORMHOLE,
+        &[
+            "relayer",
+            "config",
+            "vaa",
+            "claim",
+            "endpoint",
+            "user-usdc",
+            "fees",
+            "custody",
+        ],
+        encode(&[&[COMPLETE_NATIVE]]),
+        json!([transfer(WORMHOLE, "0-0", "custody", "user-usdc", 1_500_000)]),
+    )?;
+
+    let parser = DexParser::new();
+    assert!(parser.parse_transfers(tx.clone(), None).is_empty());
+    let result = parser.parse_all(tx, None);
+
+    let bridge = &result.bridges[0];
+    assert_eq!(bridge.direction, BridgeDirection::Inbound);
+    assert_eq!(bridge.user, "user-usdc");
+    assert_eq!(bridge.token.amount_raw, "1500000");
+    assert_eq!(bridge.target_chain, None);
+    Ok(())
+}
+
+#[test]
+fn debridge_order_reports_its_take_chain() -> Result<()> {
+    let mut take_chain = [0u8; 32];
+    take_chain[24..].copy_from_slice(&56u64.to_be_bytes());
+    let receiver = [0x11; 20];
+    let data = encode(&[
+        &CREATE_ORDER,
+        &10_000_000u64.to_le_bytes(),
+        &take_chain,
+        &20u32.to_le_bytes(),
+        &[0x22; 20],
+        &[0; 32],
+        &20u32.to_le_bytes(),
+        &receiver,
+    ]);
+    let tx = transaction(
+        DLN_SOURCE,
+        &["user", "state", USDC_MINT],
+        data,
+        json!([transfer(
+            DLN_SOURCE,
+            "0-0",
+            "user-usdc",
+            "give-wallet",
+            10_000_000
+        )]),
+    )?;
+
+    let result = DexParser::new().parse_all(tx, None);
+
+    assert!(result.trades.is_empty());
+    assert!(result.transfers.is_empty());
+    let bridge = &result.bridges[0];
+    assert_eq!(bridge.protocol, "deBridge");
+    assert_eq!(bridge.direction, BridgeDirection::Outbound);
+    assert_eq!(bridge.token.amount, 10.0);
+    assert_eq!(bridge.target_chain, Some(56));
+    assert_eq!(
+        bridge.recipient.as_deref(),
+        Some(format!("0x{}", "11".repeat(20)).as_str())
+    );
+    Ok(())
+}
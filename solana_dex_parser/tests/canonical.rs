@@ -0,0 +1,65 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use solana_dex_parser::format::{to_canonical_string, to_json_value};
+use solana_dex_parser::{BalanceChange, JsonFormat, ParseResult, TradeInfo, UiAmountFormat};
+
+fn trade(idx: &str) -> TradeInfo {
+    TradeInfo {
+        signature: "sig".into(),
+        idx: idx.to_string(),
+        ..TradeInfo::default()
+    }
+}
+
+fn result(owners: &[&str], idxs: &[&str]) -> ParseResult {
+    let mut result = ParseResult::new();
+    result.signature = "sig".to_string();
+    result.trades = idxs.iter().map(|idx| trade(idx)).collect();
+    result.token_balance_change = owners
+        .iter()
+        .map(|owner| (owner.to_string(), BalanceChange::default()))
+        .collect::<HashMap<_, _>>();
+    result
+}
+
+#[test]
+fn equal_results_render_byte_identical() -> Result<()> {
+    let owners = ["mint-c", "mint-a", "mint-b", "mint-e", "mint-d"];
+    let mut reversed = owners;
+    reversed.reverse();
+    let a = result(&owners, &["10-0", "2-1", "2-0"]);
+    let b = result(&reversed, &["2-0", "10-0", "2-1"]);
+
+    let rendered = to_canonical_string(&a)?;
+    assert_eq!(rendered, to_canonical_string(&b)?);
+
+    let value: serde_json::Value = serde_json::from_str(&rendered)?;
+    let idxs: Vec<_> = value["trades"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|trade| trade["idx"].as_str().unwrap())
+        .collect();
+    assert_eq!(idxs, ["2-0", "2-1", "10-0"]);
+    assert!(rendered.find("\"fee\"") < rendered.find("\"signature\""));
+    Ok(())
+}
+
+#[test]
+fn canonical_mode_combines_with_ui_amount_strings() -> Result<()> {
+    let format = JsonFormat {
+        ui_amounts: UiAmountFormat::String,
+        canonical: true,
+    };
+    let mut result = result(&[], &["1-0", "0-0"]);
+    result.trades[0].input_token.amount = 1.5;
+    result.trades[0].input_token.amount_raw = "1500000".to_string();
+    result.trades[0].input_token.decimals = 6;
+
+    let value = to_json_value(&result, format)?;
+
+    assert_eq!(value["trades"][1]["idx"], "1-0");
+    assert_eq!(value["trades"][1]["inputToken"]["amount"], "1.5");
+    Ok(())
+}
@@ -0,0 +1,171 @@
+use anyhow::Result;
+use serde_json::{json, Value};
+use solana_dex_parser::core::instruction_classifier::InstructionClassifier;
+use solana_dex_parser::core::transaction_adapter::TransactionAdapter;
+use solana_dex_parser::{ParseConfig, SolanaTransaction};
+
+const PUMP_SWAP: &str = "pAMMBay6oceH9fJKBRHGP5D4bD4sWpmSwMn52FMfXEA";
+const TOKEN_PROGRAM: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+const JUPITER: &str = "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4";
+const ORCA_WHIRLPOOL: &str = "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc";
+const RAYDIUM_CPMM: &str = "CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C";
+
+const BUY: [u8; 8] = [102, 6, 61, 18, 1, 218, 235, 234];
+const BUY_EVENT: [u8; 16] = [
+    228, 69, 165, 46, 81, 203, 154, 29, 103, 244, 82, 31, 44, 245, 119, 119,
+];
+
+fn classifier() -> Result<InstructionClassifier> {
+    let tx: SolanaTransaction = serde_json::from_value(json!({
+        "slot": 1,
+        "signature": "classifier",
+        "blockTime": 1_700_000_000,
+        "signers": ["user"],
+        "instructions": [
+            {
+                "programId": PUMP_SWAP,
+                "accounts": ["pool", "user"],
+                "data": bs58::encode(BUY).into_string(),
+            },
+            {
+                "programId": PUMP_SWAP,
+                "accounts": ["other-pool", "user"],
+                "data": bs58::encode(BUY).into_string(),
+            },
+        ],
+        "innerInstructions": [{
+            "index": 0,
+            "instructions": [
+                {
+                    "programId": TOKEN_PROGRAM,
+                    "accounts": ["user-token", "pool-token", "user"],
+                    "data": "",
+                },
+                {
+                    "programId": PUMP_SWAP,
+                    "accounts": ["event-authority"],
+                    "data": bs58::encode(BUY_EVENT).into_string(),
+                },
+            ],
+        }],
+    }))?;
+    let adapter = TransactionAdapter::new(tx, ParseConfig::default());
+    Ok(InstructionClassifier::new(&adapter))
+}
+
+#[test]
+fn find_instructions_matches_discriminator_prefix_in_execution_order() -> Result<()> {
+    let classifier = classifier()?;
+
+    let buys = classifier.find_instructions(PUMP_SWAP, &BUY);
+    let positions: Vec<_> = buys
+        .iter()
+        .map(|ci| (ci.outer_index, ci.inner_index))
+        .collect();
+    assert_eq!(positions, vec![(0, None), (1, None)]);
+
+    let events = classifier.find_instructions(PUMP_SWAP, &BUY_EVENT[..8]);
+    assert_eq!(events.len(), 1);
+    assert_eq!((events[0].outer_index, events[0].inner_index), (0, Some(1)));
+
+    assert!(classifier.find_instructions(TOKEN_PROGRAM, &BUY).is_empty());
+    Ok(())
+}
+
+#[test]
+fn find_by_account_spans_programs_and_inner_instructions() -> Result<()> {
+    let classifier = classifier()?;
+
+    let touching_user: Vec<_> = classifier
+        .find_by_account("user")
+        .iter()
+        .map(|ci| (ci.program_id.clone(), ci.outer_index, ci.inner_index))
+        .collect();
+    assert_eq!(
+        touching_user,
+        vec![
+            (PUMP_SWAP.to_string(), 0, None),
+            (TOKEN_PROGRAM.to_string(), 0, Some(0)),
+            (PUMP_SWAP.to_string(), 1, None),
+        ]
+    );
+
+    let pool = classifier.find_by_account("other-pool");
+    assert_eq!(pool.len(), 1);
+    assert_eq!(pool[0].outer_index, 1);
+    assert!(classifier.find_by_account("missing").is_empty());
+    Ok(())
+}
+
+fn route_classifier(stack_heights: bool) -> Result<InstructionClassifier> {
+    let cpi = |program_id: &str, height: u32| -> Value {
+        let mut instruction = json!({ "programId": program_id, "accounts": [], "data": "" });
+        if stack_heights {
+            instruction["stackHeight"] = json!(height);
+        }
+        instruction
+    };
+    let tx: SolanaTransaction = serde_json::from_value(json!({
+        "slot": 1,
+        "signature": "route",
+        "blockTime": 1_700_000_000,
+        "signers": ["user"],
+        "instructions": [{ "programId": JUPITER, "accounts": [], "data": "" }],
+        "innerInstructions": [{
+            "index": 0,
+            "instructions": [
+                cpi(ORCA_WHIRLPOOL, 2),
+                cpi(TOKEN_PROGRAM, 3),
+                cpi(TOKEN_PROGRAM, 3),
+                cpi(RAYDIUM_CPMM, 2),
+                cpi(TOKEN_PROGRAM, 3),
+                cpi(TOKEN_PROGRAM, 3),
+            ],
+        }],
+    }))?;
+    let adapter = TransactionAdapter::new(tx, ParseConfig::default());
+    Ok(InstructionClassifier::new(&adapter))
+}
+
+#[test]
+fn invocation_tree_nests_transfers_under_their_hop() -> Result<()> {
+    let classifier = route_classifier(true)?;
+
+    let tree = classifier.invocation_tree();
+    assert_eq!(tree.len(), 1);
+    assert_eq!(tree[0].instruction.program_id, JUPITER);
+
+    let hops = classifier.children_of(0);
+    assert_eq!(hops, tree[0].children);
+    let hop_programs: Vec<_> = hops
+        .iter()
+        .map(|hop| hop.instruction.program_id.as_str())
+        .collect();
+    assert_eq!(hop_programs, [ORCA_WHIRLPOOL, RAYDIUM_CPMM]);
+
+    let raydium_transfers: Vec<_> = hops[1]
+        .descendants()
+        .iter()
+        .map(|ci| ci.inner_index)
+        .collect();
+    assert_eq!(raydium_transfers, [Some(4), Some(5)]);
+    assert_eq!(tree[0].descendants().len(), 6);
+
+    assert!(hops[1].contains("0-4") && hops[1].contains("0-5"));
+    assert!(!hops[1].contains("0-1") && !hops[1].contains("1-4"));
+    assert!(tree[0].contains("0-1"));
+    let orca = classifier.get_instructions(ORCA_WHIRLPOOL);
+    assert_eq!(classifier.invocation_of(&orca[0]).as_ref(), Some(&hops[0]));
+    Ok(())
+}
+
+#[test]
+fn missing_stack_heights_keep_inner_instructions_flat() -> Result<()> {
+    let classifier = route_classifier(false)?;
+
+    let children = classifier.children_of(0);
+    assert_eq!(children.len(), 6);
+    assert!(children.iter().all(|child| child.children.is_empty()));
+    assert!(classifier.children_of(1).is_empty());
+    Ok(())
+}
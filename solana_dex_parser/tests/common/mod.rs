@@ -1,12 +1,16 @@
+//! Helpers shared by the integration tests; each test crate uses only some of them.
+#![allow(dead_code)]
+
 use std::str::FromStr;
 
 use anyhow::{Context, Result};
+use serde_json::{json, Value};
 use solana_client::rpc_client::RpcClient;
 use solana_sdk::pubkey::Pubkey;
 use solana_sdk::signature::Signature;
 
 use solana_dex_parser::rpc;
-use solana_dex_parser::types::SolanaTransaction;
+use solana_dex_parser::types::{SolanaTransaction, TokenInfo, TradeInfo};
 
 pub fn fetch_transaction_with_fallback(
     rpc_url: &str,
@@ -30,3 +34,82 @@ fn fetch_recent_signature(client: &RpcClient) -> Result<Signature> {
         .context("no signatures returned for system program")?;
     Signature::from_str(&sig.signature).context("invalid signature from RPC response")
 }
+
+/// Parsed `transferChecked` of `amount` base units of `mint`, recorded under `program_id`'s
+/// instruction `idx` (`"outer-inner"`).
+pub fn transfer(
+    program_id: &str,
+    idx: &str,
+    source: &str,
+    destination: &str,
+    mint: &str,
+    amount: u64,
+    decimals: u8,
+) -> Value {
+    json!({
+        "type": "transferChecked",
+        "programId": program_id,
+        "info": {
+            "destination": destination,
+            "mint": mint,
+            "source": source,
+            "tokenAmount": {
+                "amount": amount.to_string(),
+                "uiAmount": amount as f64 / 10f64.powi(i32::from(decimals)),
+                "decimals": decimals,
+            },
+        },
+        "idx": idx,
+        "timestamp": 1_700_000_000,
+        "signature": "transfer",
+        "isFee": false,
+    })
+}
+
+/// Transaction `signature` signed by `user` at a fixed slot and block time; `fields` add
+/// `instructions`, `transfers` and the like, or override the defaults.
+pub fn transaction(signature: &str, fields: Value) -> Result<SolanaTransaction> {
+    let mut tx = json!({
+        "slot": 1_000,
+        "signature": signature,
+        "blockTime": 1_700_000_000,
+        "signers": ["user"],
+    });
+    if let (Some(tx), Value::Object(fields)) = (tx.as_object_mut(), fields) {
+        tx.extend(fields);
+    }
+    Ok(serde_json::from_value(tx)?)
+}
+
+/// `raw` base units of `mint`, which has `decimals` decimals.
+pub fn token(mint: &str, raw: u64, decimals: u8) -> TokenInfo {
+    TokenInfo {
+        mint: mint.to_string(),
+        amount: raw as f64 / 10f64.powi(i32::from(decimals)),
+        amount_raw: raw.to_string(),
+        decimals,
+        ..TokenInfo::default()
+    }
+}
+
+/// `amount` UI units of `mint`, which has `decimals` decimals.
+pub fn ui_token(mint: &str, amount: f64, decimals: u8) -> TokenInfo {
+    TokenInfo {
+        mint: mint.to_string(),
+        amount,
+        amount_raw: ((amount * 10f64.powi(i32::from(decimals))) as u128).to_string(),
+        decimals,
+        ..TokenInfo::default()
+    }
+}
+
+/// Swap between `base` and `quote`: a buy of `base` with `quote` when `buy` is set, else a
+/// sell of `base` for `quote`.
+pub fn swap(base: TokenInfo, quote: TokenInfo, buy: bool) -> TradeInfo {
+    let (input_token, output_token) = if buy { (quote, base) } else { (base, quote) };
+    TradeInfo {
+        input_token,
+        output_token,
+        ..TradeInfo::default()
+    }
+}
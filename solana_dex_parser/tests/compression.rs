@@ -0,0 +1,27 @@
+#![cfg(feature = "compression")]
+
+use anyhow::Result;
+use serde_json::{json, Value};
+use solana_dex_parser::io::{self as dex_io, NdjsonWriter};
+
+#[test]
+fn compressed_ndjson_round_trips() -> Result<()> {
+    let records = vec![json!({ "slot": 1 }), json!({ "slot": 2 })];
+    for name in ["records.ndjson", "records.ndjson.gz", "records.ndjson.zst"] {
+        let path = std::env::temp_dir().join(format!("dexp-{}-{name}", std::process::id()));
+        let mut writer = NdjsonWriter::create(&path)?;
+        for record in &records {
+            writer.write_record(record)?;
+        }
+        writer.finish()?;
+
+        let data = dex_io::read_to_string(&path)?;
+        let decoded: Vec<Value> = data
+            .lines()
+            .map(serde_json::from_str)
+            .collect::<Result<_, _>>()?;
+        std::fs::remove_file(&path)?;
+        assert_eq!(decoded, records, "{name}");
+    }
+    Ok(())
+}
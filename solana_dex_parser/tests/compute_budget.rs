@@ -0,0 +1,93 @@
+use anyhow::Result;
+use serde_json::{json, Value};
+use solana_dex_parser::{ComputeBudget, DexParser, SolanaTransaction, TokenAmount};
+
+mod common;
+
+const COMPUTE_BUDGET: &str = "ComputeBudget111111111111111111111111111111";
+const MEMO: &str = "MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr";
+
+fn set_unit_limit(limit: u32) -> Value {
+    let mut data = vec![2u8];
+    data.extend_from_slice(&limit.to_le_bytes());
+    json!({ "programId": COMPUTE_BUDGET, "accounts": [], "data": bs58::encode(data).into_string() })
+}
+
+fn set_unit_price(micro_lamports: u64) -> Value {
+    let mut data = vec![3u8];
+    data.extend_from_slice(&micro_lamports.to_le_bytes());
+    json!({ "programId": COMPUTE_BUDGET, "accounts": [], "data": bs58::encode(data).into_string() })
+}
+
+fn memo() -> Value {
+    json!({ "programId": MEMO, "accounts": ["user"], "data": "" })
+}
+
+fn transaction(instructions: Value) -> Result<SolanaTransaction> {
+    common::transaction(
+        "budget",
+        json!({
+            "instructions": instructions,
+        }),
+    )
+}
+
+#[test]
+fn priority_fee_is_the_price_of_the_requested_limit() -> Result<()> {
+    let tx = transaction(json!([
+        set_unit_limit(300_000),
+        set_unit_price(1_000_001),
+        memo()
+    ]))?;
+
+    let result = DexParser::new().parse_all(tx, None);
+
+    assert_eq!(
+        result.compute_budget,
+        Some(ComputeBudget {
+            unit_limit: Some(300_000),
+            unit_price: Some(1_000_001),
+            // 300_000.3 lamports, charged as 300_001.
+            priority_fee: Some(TokenAmount::new("300001", 9, Some(0.000300001))),
+        })
+    );
+    let serialized = serde_json::to_value(&result)?;
+    assert_eq!(serialized["computeBudget"]["unitLimit"], 300_000);
+    assert_eq!(
+        serialized["computeBudget"]["priorityFee"]["amount"],
+        "300001"
+    );
+    Ok(())
+}
+
+#[test]
+fn default_limit_applies_without_a_requested_one() -> Result<()> {
+    let tx = transaction(json!([set_unit_price(10_000), memo(), memo()]))?;
+
+    let budget = DexParser::new()
+        .parse_all(tx, None)
+        .compute_budget
+        .expect("compute budget");
+
+    assert_eq!(budget.unit_limit, None);
+    // Two instructions at 200k units each.
+    assert_eq!(
+        budget.priority_fee.map(|fee| fee.amount),
+        Some("4000".to_string())
+    );
+    Ok(())
+}
+
+#[test]
+fn transactions_without_budget_instructions_have_none() -> Result<()> {
+    let plain = DexParser::new().parse_all(transaction(json!([memo()]))?, None);
+    assert_eq!(plain.compute_budget, None);
+    assert!(serde_json::to_value(&plain)?.get("computeBudget").is_none());
+
+    let limit_only =
+        DexParser::new().parse_all(transaction(json!([set_unit_limit(50_000), memo()]))?, None);
+    let budget = limit_only.compute_budget.expect("compute budget");
+    assert_eq!(budget.unit_limit, Some(50_000));
+    assert_eq!(budget.priority_fee, None);
+    Ok(())
+}
@@ -0,0 +1,162 @@
+use anyhow::Result;
+use solana_dex_parser::jobs::daily_rollup::{DailyRollup, RollupRow};
+use solana_dex_parser::{BlockParseResult, ParseResult, TradeInfo};
+
+mod common;
+
+const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+const USDC_MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+
+/// 2023-11-14T22:13:20Z.
+const DAY_ONE: u64 = 1_700_000_000;
+const DAY_TWO: u64 = DAY_ONE + 86_400;
+
+fn trade(amm: &str, user: &str, timestamp: u64, sol: f64, usdc: f64) -> TradeInfo {
+    TradeInfo {
+        amm: Some(amm.to_string()),
+        user: Some(user.to_string()),
+        timestamp,
+        input_token: common::ui_token(SOL_MINT, sol, 9),
+        output_token: common::ui_token(USDC_MINT, usdc, 6),
+        volume_sol: Some(sol),
+        ..TradeInfo::default()
+    }
+}
+
+fn result(trades: Vec<TradeInfo>) -> ParseResult {
+    ParseResult {
+        trades,
+        ..ParseResult::new()
+    }
+}
+
+fn row(date: &str, venue: &str, mint: &str, trades: u64, volume: f64, wallets: u64) -> RollupRow {
+    RollupRow {
+        date: date.to_string(),
+        venue: venue.to_string(),
+        mint: mint.to_string(),
+        trades,
+        volume,
+        volume_sol: if venue == "Orca" { 1.0 } else { 3.0 },
+        unique_wallets: wallets,
+    }
+}
+
+fn sample_block() -> BlockParseResult {
+    BlockParseResult {
+        slot: 1,
+        timestamp: Some(DAY_ONE),
+        transactions: vec![
+            result(vec![trade("Raydium", "alice", DAY_ONE, 1.0, 150.0)]),
+            result(vec![
+                trade("Raydium", "alice", DAY_ONE, 2.0, 300.0),
+                trade("Orca", "bob", DAY_ONE, 1.0, 151.0),
+            ]),
+        ],
+    }
+}
+
+#[test]
+fn rolls_trades_up_per_day_venue_and_token() {
+    let mut rollup = DailyRollup::new();
+    rollup.observe_block(&sample_block());
+    rollup.observe(&result(vec![trade("Raydium", "carol", DAY_TWO, 0.5, 75.0)]));
+
+    let rows = rollup.rows();
+    assert_eq!(
+        rows[..4],
+        [
+            row("2023-11-14", "Orca", USDC_MINT, 1, 151.0, 1),
+            row("2023-11-14", "Orca", SOL_MINT, 1, 1.0, 1),
+            row("2023-11-14", "Raydium", USDC_MINT, 2, 450.0, 1),
+            row("2023-11-14", "Raydium", SOL_MINT, 2, 3.0, 1),
+        ]
+    );
+    assert_eq!(rows.len(), 6);
+    assert_eq!(rows[4].date, "2023-11-15");
+    assert_eq!(rows[4].unique_wallets, 1);
+}
+
+#[test]
+fn writes_csv_with_a_header() -> Result<()> {
+    let mut rollup = DailyRollup::new();
+    rollup.observe_block(&sample_block());
+
+    let mut csv = Vec::new();
+    rollup.write_csv(&mut csv)?;
+    let csv = String::from_utf8(csv)?;
+    let lines: Vec<&str> = csv.lines().collect();
+
+    assert_eq!(
+        lines[0],
+        "date,venue,mint,trades,volume,volume_sol,unique_wallets"
+    );
+    assert_eq!(lines[1], format!("2023-11-14,Orca,{USDC_MINT},1,151,1,1"));
+    assert_eq!(lines.len(), 5);
+    Ok(())
+}
+
+#[cfg(feature = "compression")]
+#[test]
+fn reads_result_and_block_dumps() -> Result<()> {
+    use solana_dex_parser::io::NdjsonWriter;
+
+    let dir = std::env::temp_dir().join(format!("dexp-rollup-{}", std::process::id()));
+    std::fs::create_dir_all(&dir)?;
+    let results = dir.join("results.ndjson.gz");
+    let mut writer = NdjsonWriter::create(&results)?;
+    writer.write_record(&result(vec![trade("Raydium", "dave", DAY_ONE, 4.0, 600.0)]))?;
+    writer.finish()?;
+    let block = dir.join("block.json");
+    std::fs::write(&block, serde_json::to_string_pretty(&sample_block())?)?;
+
+    let rows = DailyRollup::from_files([&results, &block])?.rows();
+    std::fs::remove_dir_all(&dir)?;
+
+    let raydium_sol = rows
+        .iter()
+        .find(|row| row.venue == "Raydium" && row.mint == SOL_MINT)
+        .expect("raydium sol row");
+    assert_eq!(raydium_sol.trades, 3);
+    assert_eq!(raydium_sol.volume, 7.0);
+    assert_eq!(raydium_sol.unique_wallets, 2);
+    Ok(())
+}
+
+#[cfg(feature = "parquet")]
+#[test]
+fn writes_parquet_with_the_csv_columns() -> Result<()> {
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+
+    let mut rollup = DailyRollup::new();
+    rollup.observe_block(&sample_block());
+    let path = std::env::temp_dir().join(format!("dexp-rollup-{}.parquet", std::process::id()));
+    rollup.write_parquet(std::fs::File::create(&path)?)?;
+
+    let reader = SerializedFileReader::new(std::fs::File::open(&path)?)?;
+    let columns: Vec<String> = reader
+        .metadata()
+        .file_metadata()
+        .schema_descr()
+        .columns()
+        .iter()
+        .map(|column| column.name().to_string())
+        .collect();
+    let rows = reader.get_row_iter(None)?.collect::<Result<Vec<_>, _>>()?;
+    std::fs::remove_file(&path)?;
+
+    assert_eq!(
+        columns.join(","),
+        "date,venue,mint,trades,volume,volume_sol,unique_wallets"
+    );
+    assert_eq!(rows.len(), 4);
+    let first = rows[0].to_string();
+    assert_eq!(
+        first,
+        format!(
+            "{{date: \"2023-11-14\", venue: \"Orca\", mint: \"{USDC_MINT}\", trades: 1, \
+             volume: 151.0, volume_sol: 1.0, unique_wallets: 1}}"
+        )
+    );
+    Ok(())
+}
@@ -0,0 +1,52 @@
+#![cfg(feature = "cli")]
+
+use std::process::Command;
+
+use anyhow::{ensure, Result};
+use serde_json::Value;
+
+const PUMP_FUN: &str = "6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P";
+
+fn dexp(args: &[&str]) -> Result<Value> {
+    let output = Command::new(env!("CARGO_BIN_EXE_dexp"))
+        .args(args)
+        .output()?;
+    ensure!(
+        output.status.success(),
+        "dexp {args:?} failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    Ok(serde_json::from_slice(&output.stdout)?)
+}
+
+#[test]
+fn schema_describes_the_requested_type() -> Result<()> {
+    let schema = dexp(&["schema"])?;
+    assert_eq!(schema["title"], "ParseResult");
+    assert!(schema["properties"]["trades"].is_object());
+
+    let trade = dexp(&["schema", "--type", "trade"])?;
+    assert_eq!(trade["title"], "TradeInfo");
+    assert!(trade["properties"]["inputToken"].is_object());
+    assert!(trade["properties"]["outputToken"].is_object());
+    Ok(())
+}
+
+#[test]
+fn discriminators_filter_by_program() -> Result<()> {
+    let all = dexp(&["discriminators"])?;
+    let all = all.as_array().expect("registry is a list");
+
+    let pumpfun = dexp(&["discriminators", "--program", PUMP_FUN])?;
+    let pumpfun = pumpfun.as_array().expect("registry is a list");
+    assert!(!pumpfun.is_empty());
+    assert!(pumpfun.len() < all.len());
+    assert!(pumpfun.iter().all(|entry| entry["programId"] == PUMP_FUN));
+    assert!(pumpfun
+        .iter()
+        .any(|entry| entry["kind"] == "instruction" && entry["name"] == "buy"));
+    assert!(pumpfun.iter().all(|entry| entry["discriminator"]
+        .as_array()
+        .is_some_and(|bytes| !bytes.is_empty())));
+    Ok(())
+}
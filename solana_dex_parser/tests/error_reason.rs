@@ -0,0 +1,108 @@
+use std::fs;
+
+use anyhow::Result;
+use serde_json::{json, Value};
+use solana_dex_parser::rpc;
+use solana_dex_parser::types::TransactionStatus;
+use solana_dex_parser::{DexParser, ParseResult};
+
+const RAYDIUM_V4: &str = "675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8";
+const CLMM: &str = "CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK";
+/// Raydium v4 swap with its AMM at instruction 2.
+const RAYDIUM_V4_SWAP: &str = "tests/fixtures/raydium_v4_openbook_market_swap.json";
+/// Raydium CLMM swap with its pool at instruction 4.
+const CLMM_SWAP: &str = "tests/fixtures/raydium_clmm_v0_lookup.json";
+
+/// The recorded transaction at `fixture` failed with `err`, logging `logs`.
+fn parse_failed(fixture: &str, err: Value, logs: &[String]) -> Result<ParseResult> {
+    let mut payload: Value = serde_json::from_str(&fs::read_to_string(fixture)?)?;
+    payload["meta"]["err"] = err.clone();
+    payload["meta"]["status"] = json!({ "Err": err });
+    payload["meta"]["logMessages"] = json!(logs);
+
+    let tx = rpc::transaction_from_rpc_json(payload)?;
+    Ok(DexParser::new().parse_all(tx, None))
+}
+
+#[test]
+fn known_program_error_is_named() -> Result<()> {
+    let result = parse_failed(
+        RAYDIUM_V4_SWAP,
+        json!({"InstructionError": [2, {"Custom": 30}]}),
+        &[
+            format!("Program {RAYDIUM_V4} invoke [1]"),
+            format!("Program {RAYDIUM_V4} failed: custom program error: 0x1e"),
+        ],
+    )?;
+
+    assert_eq!(result.tx_status, TransactionStatus::Failed);
+    assert_eq!(
+        result.error_reason.as_deref(),
+        Some("instruction 2 failed: Raydium: Exceeds desired slippage limit (error 30)")
+    );
+    Ok(())
+}
+
+#[test]
+fn anchor_error_of_the_failing_cpi_is_read_from_the_logs() -> Result<()> {
+    let result = parse_failed(
+        CLMM_SWAP,
+        json!({"InstructionError": [4, {"Custom": 6022}]}),
+        &[
+            format!("Program {CLMM} invoke [1]"),
+            "Program log: AnchorError occurred. Error Code: TooLittleOutputReceived. Error Number: 6022. Error Message: Too little output received.".to_string(),
+            format!("Program {CLMM} failed: custom program error: 0x1786"),
+        ],
+    )?;
+
+    assert_eq!(
+        result.error_reason.as_deref(),
+        Some("instruction 4 failed: RaydiumCLMM: Too little output received (error 6022)")
+    );
+    Ok(())
+}
+
+#[test]
+fn unknown_custom_error_keeps_its_code() -> Result<()> {
+    let result = parse_failed(
+        CLMM_SWAP,
+        json!({"InstructionError": [4, {"Custom": 6100}]}),
+        &[],
+    )?;
+
+    assert_eq!(
+        result.error_reason.as_deref(),
+        Some("instruction 4 failed: RaydiumCLMM: custom program error 0x17d4")
+    );
+    Ok(())
+}
+
+#[test]
+fn builtin_errors_use_their_description() -> Result<()> {
+    let instruction = parse_failed(
+        CLMM_SWAP,
+        json!({"InstructionError": [0, "InsufficientFunds"]}),
+        &[],
+    )?;
+    let transaction = parse_failed(CLMM_SWAP, json!("AccountNotFound"), &[])?;
+
+    assert_eq!(
+        instruction.error_reason.as_deref(),
+        Some("instruction 0 failed: insufficient funds for instruction")
+    );
+    assert_eq!(
+        transaction.error_reason.as_deref(),
+        Some("Attempt to debit an account but found no record of a prior credit.")
+    );
+    Ok(())
+}
+
+#[test]
+fn successful_transaction_has_no_error_reason() -> Result<()> {
+    let payload: Value = serde_json::from_str(&fs::read_to_string(CLMM_SWAP)?)?;
+
+    let result = DexParser::new().parse_all(rpc::transaction_from_rpc_json(payload)?, None);
+
+    assert_eq!(result.error_reason, None);
+    Ok(())
+}
@@ -0,0 +1,130 @@
+use anyhow::Result;
+use serde_json::{json, Value};
+use solana_dex_parser::{DexParser, FailedCpi, ParseConfig, SolanaTransaction};
+
+mod common;
+
+const ROUTER: &str = "RouterProgram1111111111111111111111111111111";
+const SOLFI: &str = "SoLFiHG9TfgtdUXUjWAxi3LtvYuFyDLVhBWxdMZxyCe";
+const TOKEN_PROGRAM: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+const USDC_MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+
+fn transfer(idx: &str, source: &str, destination: &str, mint: &str, amount: u64) -> Value {
+    common::transfer(TOKEN_PROGRAM, idx, source, destination, mint, amount, 6)
+}
+
+fn solfi_swap() -> Value {
+    let mut data = vec![7];
+    data.extend_from_slice(&150_000_000u64.to_le_bytes());
+    data.extend_from_slice(&0u64.to_le_bytes());
+    data.push(1);
+    json!({
+        "programId": SOLFI,
+        "accounts": ["user", "pair", "base-vault", "quote-vault", "user-base", "user-quote"],
+        "data": bs58::encode(data).into_string(),
+        "stackHeight": 2,
+    })
+}
+
+fn token_transfer() -> Value {
+    json!({ "programId": TOKEN_PROGRAM, "accounts": [], "data": "", "stackHeight": 3 })
+}
+
+/// A router trying a SolFi hop that fails and falling back to a second SolFi hop.
+fn fallback_route(logs: &[&str]) -> Result<SolanaTransaction> {
+    Ok(serde_json::from_value(json!({
+        "slot": 900,
+        "signature": "fallback",
+        "blockTime": 1_700_000_000,
+        "signers": ["user"],
+        "instructions": [{ "programId": ROUTER, "accounts": [], "data": "" }],
+        "innerInstructions": [{
+            "index": 0,
+            "instructions": [
+                solfi_swap(),
+                token_transfer(),
+                solfi_swap(),
+                token_transfer(),
+                token_transfer(),
+            ],
+        }],
+        "transfers": [
+            transfer("0-3", "user-quote", "quote-vault", USDC_MINT, 150_000_000),
+            transfer("0-4", "base-vault", "user-base", SOL_MINT, 1_000_000),
+        ],
+        "meta": { "fee": 5000, "computeUnits": 90_000, "status": "SUCCESS", "logMessages": logs },
+    }))?)
+}
+
+const FALLBACK_LOGS: [&str; 14] = [
+    "Program RouterProgram1111111111111111111111111111111 invoke [1]",
+    "Program SoLFiHG9TfgtdUXUjWAxi3LtvYuFyDLVhBWxdMZxyCe invoke [2]",
+    "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA invoke [3]",
+    "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA success",
+    "Program SoLFiHG9TfgtdUXUjWAxi3LtvYuFyDLVhBWxdMZxyCe failed: custom program error: 0x1",
+    "Program log: hop failed, trying fallback",
+    "Program SoLFiHG9TfgtdUXUjWAxi3LtvYuFyDLVhBWxdMZxyCe invoke [2]",
+    "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA invoke [3]",
+    "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA success",
+    "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA invoke [3]",
+    "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA success",
+    "Program SoLFiHG9TfgtdUXUjWAxi3LtvYuFyDLVhBWxdMZxyCe success",
+    "Program RouterProgram1111111111111111111111111111111 consumed 90000 of 200000 compute units",
+    "Program RouterProgram1111111111111111111111111111111 success",
+];
+
+#[test]
+fn drops_trades_of_rolled_back_hops() -> Result<()> {
+    let parser = DexParser::new();
+    let without_logs = parser.parse_trades(fallback_route(&[])?, None);
+    assert_eq!(
+        without_logs
+            .iter()
+            .map(|t| t.idx.as_str())
+            .collect::<Vec<_>>(),
+        ["0-0", "0-2"]
+    );
+
+    let result = parser.parse_all(fallback_route(&FALLBACK_LOGS)?, None);
+    assert_eq!(result.trades.len(), 1);
+    assert_eq!(result.trades[0].idx, "0-2");
+    assert!(result.failed_cpis.is_empty());
+    Ok(())
+}
+
+#[test]
+fn reports_failed_cpis_when_enabled() -> Result<()> {
+    let config = ParseConfig {
+        include_failed_cpis: true,
+        ..ParseConfig::default()
+    };
+    let result = DexParser::new().parse_all(fallback_route(&FALLBACK_LOGS)?, Some(config));
+
+    assert_eq!(
+        result.failed_cpis,
+        [FailedCpi {
+            program_id: SOLFI.to_string(),
+            idx: "0-0".to_string(),
+            error: Some("custom program error: 0x1".to_string()),
+            reverted: vec!["0-0".to_string(), "0-1".to_string()],
+        }]
+    );
+    Ok(())
+}
+
+#[test]
+fn ignores_logs_that_do_not_match_the_instructions() -> Result<()> {
+    let mut logs = FALLBACK_LOGS.to_vec();
+    logs.remove(2);
+    logs.remove(2);
+    let config = ParseConfig {
+        include_failed_cpis: true,
+        ..ParseConfig::default()
+    };
+    let result = DexParser::new().parse_all(fallback_route(&logs)?, Some(config));
+
+    assert_eq!(result.trades.len(), 2);
+    assert!(result.failed_cpis.is_empty());
+    Ok(())
+}
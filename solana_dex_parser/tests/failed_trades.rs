@@ -0,0 +1,145 @@
+use std::fs;
+
+use anyhow::Result;
+use serde_json::{json, Value};
+use solana_dex_parser::types::{TradeLimitKind, TradeStatus, TradeType};
+use solana_dex_parser::{DexParser, ParseConfig, SolanaTransaction};
+
+const PUMP_FUN_PROGRAM: &str = "6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P";
+const JUPITER_PROGRAM: &str = "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4";
+const SOL: &str = "So11111111111111111111111111111111111111112";
+const USDC: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+const BUY: [u8; 8] = [102, 6, 61, 18, 1, 218, 235, 234];
+const SHARED_ACCOUNTS_ROUTE: [u8; 8] = [193, 32, 155, 51, 65, 214, 156, 129];
+
+fn parse_failed() -> Option<ParseConfig> {
+    Some(ParseConfig {
+        parse_failed: true,
+        ..ParseConfig::default()
+    })
+}
+
+/// `tests/fixtures/pumpfun_trade.json` with `status` and its instructions replaced by
+/// `instruction`.
+fn transaction(status: &str, instruction: Value) -> Result<SolanaTransaction> {
+    let mut tx: Value =
+        serde_json::from_str(&fs::read_to_string("tests/fixtures/pumpfun_trade.json")?)?;
+    tx["instructions"] = json!([instruction]);
+    tx["meta"]["status"] = json!(status);
+    Ok(serde_json::from_value(tx)?)
+}
+
+/// Pump.fun buy of 1,000 tokens for at most 0.25 SOL.
+fn pumpfun_buy() -> Value {
+    let data = [
+        BUY.as_slice(),
+        &1_000_000_000u64.to_le_bytes(),
+        &250_000_000u64.to_le_bytes(),
+    ]
+    .concat();
+    json!({
+        "programId": PUMP_FUN_PROGRAM,
+        "accounts": [
+            "global", "fee-recipient", "mint", "bonding-curve",
+            "curve-ata", "user-ata", "pumpfun-user",
+        ],
+        "data": bs58::encode(data).into_string(),
+    })
+}
+
+/// Jupiter `shared_accounts_route` of 2 `input_mint` for a quoted 300,000,000 raw units of
+/// `output_mint`, at 0.5% slippage.
+fn jupiter_route(input_mint: &str, output_mint: &str) -> Value {
+    let data = [
+        SHARED_ACCOUNTS_ROUTE.as_slice(),
+        &[3, 0, 0, 0, 0],
+        &2_000_000_000u64.to_le_bytes(),
+        &300_000_000u64.to_le_bytes(),
+        &50u16.to_le_bytes(),
+        &[0],
+    ]
+    .concat();
+    json!({
+        "programId": JUPITER_PROGRAM,
+        "accounts": [
+            "token-program", "program-authority", "jupiter-user", "source",
+            "program-source", "program-destination", "destination", input_mint, output_mint,
+        ],
+        "data": bs58::encode(data).into_string(),
+    })
+}
+
+#[test]
+fn failed_pumpfun_buy_is_an_attempted_trade() -> Result<()> {
+    let tx = transaction("FAILED", pumpfun_buy())?;
+    let parser = DexParser::new();
+
+    assert!(parser.parse_all(tx.clone(), None).trades.is_empty());
+
+    let result = parser.parse_all(tx, parse_failed());
+    assert_eq!(result.trades.len(), 1);
+    let trade = &result.trades[0];
+    assert_eq!(trade.status, TradeStatus::Failed);
+    assert_eq!(trade.trade_type, TradeType::Buy);
+    assert_eq!(trade.idx, "0-0");
+    assert_eq!(trade.pool, vec!["bonding-curve".to_string()]);
+    assert_eq!(trade.user.as_deref(), Some("pumpfun-user"));
+    assert_eq!(trade.input_token.mint, SOL);
+    assert_eq!(trade.input_token.amount_raw, "0");
+    assert_eq!(trade.output_token.mint, "mint");
+    assert!((trade.output_token.amount - 1_000.0).abs() < 1e-9);
+    let limit = trade.limit.as_ref().expect("max SOL cost");
+    assert_eq!(limit.kind, TradeLimitKind::MaxInput);
+    assert_eq!(limit.amount_raw, "250000000");
+    assert!((limit.amount - 0.25).abs() < 1e-9);
+
+    let json = serde_json::to_value(trade)?;
+    assert_eq!(json["status"], "FAILED");
+    assert_eq!(json["limit"]["kind"], "MAX_INPUT");
+    Ok(())
+}
+
+#[test]
+fn failed_jupiter_route_keeps_quote_and_slippage() -> Result<()> {
+    let tx = transaction("FAILED", jupiter_route(SOL, USDC))?;
+
+    let result = DexParser::new().parse_all(tx, parse_failed());
+
+    assert_eq!(result.trades.len(), 1);
+    let trade = &result.trades[0];
+    assert_eq!(trade.status, TradeStatus::Failed);
+    assert_eq!(trade.trade_type, TradeType::Buy);
+    assert_eq!(trade.user.as_deref(), Some("jupiter-user"));
+    assert_eq!(trade.input_token.amount_raw, "2000000000");
+    assert_eq!(trade.output_token.mint, USDC);
+    assert_eq!(trade.output_token.amount_raw, "300000000");
+    assert_eq!(trade.slippage_bps, Some(50));
+    assert!((trade.output_token.amount - 300.0).abs() < 1e-9);
+    assert_eq!(trade.limit, None);
+    Ok(())
+}
+
+#[test]
+fn route_to_a_mint_of_unknown_decimals_keeps_only_raw_amounts() -> Result<()> {
+    let tx = transaction("FAILED", jupiter_route(SOL, "unlisted-mint"))?;
+
+    let result = DexParser::new().parse_all(tx, parse_failed());
+
+    let output = &result.trades[0].output_token;
+    assert_eq!(output.amount_raw, "300000000");
+    assert_eq!(output.amount, 0.0);
+    Ok(())
+}
+
+#[test]
+fn successful_transactions_have_no_attempted_trades() -> Result<()> {
+    let tx = transaction("SUCCESS", pumpfun_buy())?;
+
+    let result = DexParser::new().parse_all(tx, parse_failed());
+
+    assert!(result
+        .trades
+        .iter()
+        .all(|trade| trade.status == TradeStatus::Success));
+    Ok(())
+}
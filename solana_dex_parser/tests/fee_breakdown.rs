@@ -0,0 +1,116 @@
+use anyhow::Result;
+use serde_json::{json, Value};
+use solana_dex_parser::{DexParser, ParseConfig, SolanaTransaction, TokenAmount};
+
+mod common;
+
+const STEP: &str = "SSwpMgqNDsyV7mAgN9ady4bDVu5ySjmmXejXvy2vLt1";
+const SYSTEM_PROGRAM: &str = "11111111111111111111111111111111";
+const COMPUTE_BUDGET: &str = "ComputeBudget111111111111111111111111111111";
+const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+const USDC_MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+const PHOTON_FEE_WALLET: &str = "AVUCZyuT35YSuj4RH7fwiyPu82Djn2Hfg7y2ND2XcnZH";
+const TIP_ACCOUNT: &str = "96gYZGLnJYVFmbjzopPSU6QiEV5fGqZNyN9nmNhvrZU5";
+
+fn instruction(program_id: &str, accounts: &[&str], data: Vec<u8>) -> Value {
+    json!({
+        "programId": program_id,
+        "accounts": accounts,
+        "data": bs58::encode(data).into_string(),
+    })
+}
+
+fn system_transfer(recipient: &str, lamports: u64) -> Value {
+    let data = [&[2u8, 0, 0, 0][..], &lamports.to_le_bytes()].concat();
+    instruction(SYSTEM_PROGRAM, &["user", recipient], data)
+}
+
+fn transfer(idx: &str, source: &str, destination: &str, mint: &str, amount: u64) -> Value {
+    let decimals = if mint == USDC_MINT { 6 } else { 9 };
+    common::transfer(STEP, idx, source, destination, mint, amount, decimals)
+}
+
+/// A Step swap through Photon with a priority fee and a Jito tip.
+fn transaction() -> Result<SolanaTransaction> {
+    let swap = [
+        &[1u8][..],
+        &1_000_000_000u64.to_le_bytes(),
+        &0u64.to_le_bytes(),
+    ]
+    .concat();
+    let limit = [&[2u8][..], &200_000u32.to_le_bytes()].concat();
+    let price = [&[3u8][..], &50_000u64.to_le_bytes()].concat();
+    common::transaction(
+        "fees",
+        json!({
+            "instructions": [
+                instruction(COMPUTE_BUDGET, &[], limit),
+                instruction(COMPUTE_BUDGET, &[], price),
+                instruction(
+                    STEP,
+                    &["pool", "authority", "user", "user-sol", "vault-sol", "vault-usdc", "user-usdc"],
+                    swap,
+                ),
+                system_transfer(PHOTON_FEE_WALLET, 10_000_000),
+                system_transfer(TIP_ACCOUNT, 1_000_000),
+            ],
+            "transfers": [
+                transfer("2-0", "user-sol", "vault-sol", SOL_MINT, 1_000_000_000),
+                transfer("2-1", "vault-usdc", "user-usdc", USDC_MINT, 150_000_000),
+            ],
+            "meta": {
+                "fee": 15_000,
+                "computeUnits": 120_000,
+                "status": "SUCCESS",
+            },
+        }),
+    )
+}
+
+fn sol(lamports: u64) -> TokenAmount {
+    TokenAmount::new(lamports.to_string(), 9, Some(lamports as f64 / 1e9))
+}
+
+#[test]
+fn fees_are_split_into_base_priority_tip_and_trade_fees() -> Result<()> {
+    let result = DexParser::new().parse_all(transaction()?, None);
+
+    let fees = &result.fees;
+    // 50_000 micro-lamports for 200k units.
+    assert_eq!(fees.priority, sol(10_000));
+    assert_eq!(fees.base, sol(5_000));
+    assert_eq!(fees.tip, sol(1_000_000));
+    assert_eq!(fees.trade_fees.len(), 1);
+    let bot = &fees.trade_fees[0];
+    assert_eq!(bot.fee_type.as_deref(), Some("bot"));
+    assert_eq!(bot.mint, SOL_MINT);
+    assert_eq!(bot.amount_raw, "10000000");
+    assert_eq!(bot.recipient, None);
+    assert_eq!(result.fee, sol(15_000));
+    Ok(())
+}
+
+#[test]
+fn fee_stays_next_to_its_breakdown() -> Result<()> {
+    let result = DexParser::new().parse_all(transaction()?, None);
+
+    let serialized = serde_json::to_value(&result)?;
+    assert_eq!(serialized["fee"]["amount"], "15000");
+    assert_eq!(serialized["fees"]["base"]["amount"], "5000");
+    Ok(())
+}
+
+#[test]
+fn legacy_fee_can_be_left_out() -> Result<()> {
+    let config = ParseConfig {
+        include_legacy_fee: false,
+        ..ParseConfig::default()
+    };
+
+    let result = DexParser::new().parse_all(transaction()?, Some(config));
+
+    let serialized = serde_json::to_value(&result)?;
+    assert!(serialized.get("fee").is_none());
+    assert_eq!(serialized["fees"]["base"]["amount"], "5000");
+    Ok(())
+}
@@ -0,0 +1,108 @@
+use anyhow::Result;
+use serde_json::{json, Value};
+use solana_dex_parser::{DexParser, FlowLabel, ParseConfig, SolanaTransaction};
+
+mod common;
+
+const RAYDIUM_CPMM: &str = "CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C";
+const COMPUTE_BUDGET: &str = "ComputeBudget111111111111111111111111111111";
+const SYSTEM_PROGRAM: &str = "11111111111111111111111111111111";
+const JITO_TIP: &str = "96gYZGLnJYVFmbjzopPSU6QiEV5fGqZNyN9nmNhvrZU5";
+const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+const TOKEN_MINT: &str = "4wBqpZM9xaSheZzJSMawUKKwhdpChKbZ5eu5ky4Vigw";
+
+const SWAP_BASE_INPUT: [u8; 8] = [143, 190, 90, 218, 196, 30, 51, 222];
+
+fn swap_instruction() -> Value {
+    let accounts: Vec<String> = (0..13)
+        .map(|i| match i {
+            0 => "user".to_string(),
+            3 => "pool".to_string(),
+            4 => "user-sol".to_string(),
+            5 => "user-token".to_string(),
+            6 => "vault-sol".to_string(),
+            7 => "vault-token".to_string(),
+            10 => SOL_MINT.to_string(),
+            11 => TOKEN_MINT.to_string(),
+            _ => format!("account-{i}"),
+        })
+        .collect();
+    let mut data = SWAP_BASE_INPUT.to_vec();
+    data.extend_from_slice(&1_000_000_000u64.to_le_bytes());
+    data.extend_from_slice(&1u64.to_le_bytes());
+    json!({
+        "programId": RAYDIUM_CPMM,
+        "accounts": accounts,
+        "data": bs58::encode(data).into_string(),
+    })
+}
+
+fn transfer(idx: &str, source: &str, destination: &str, mint: &str, amount: u64) -> Value {
+    common::transfer(RAYDIUM_CPMM, idx, source, destination, mint, amount, 9)
+}
+
+fn transaction(mut instructions: Vec<Value>, compute_units: u64) -> Result<SolanaTransaction> {
+    let swap_index = instructions.len();
+    instructions.push(swap_instruction());
+    common::transaction(
+        "flow",
+        json!({
+            "instructions": instructions,
+            "transfers": [
+                transfer(&format!("{swap_index}-0"), "user-sol", "vault-sol", SOL_MINT, 1_000_000_000),
+                transfer(&format!("{swap_index}-1"), "vault-token", "user-token", TOKEN_MINT, 7_000_000_000),
+            ],
+            "meta": { "fee": 5000, "computeUnits": compute_units, "status": "SUCCESS" },
+        }),
+    )
+}
+
+fn classify() -> Option<ParseConfig> {
+    Some(ParseConfig {
+        classify_flow: true,
+        ..ParseConfig::default()
+    })
+}
+
+#[test]
+fn priority_fee_tuned_limit_and_tip_label_bot_flow() -> Result<()> {
+    let mut set_limit = vec![2u8];
+    set_limit.extend_from_slice(&100_000u32.to_le_bytes());
+    let mut set_price = vec![3u8];
+    set_price.extend_from_slice(&5_000_000u64.to_le_bytes());
+    let tx = transaction(
+        vec![
+            json!({
+                "programId": COMPUTE_BUDGET,
+                "accounts": [],
+                "data": bs58::encode(set_limit).into_string(),
+            }),
+            json!({
+                "programId": COMPUTE_BUDGET,
+                "accounts": [],
+                "data": bs58::encode(set_price).into_string(),
+            }),
+            json!({ "programId": SYSTEM_PROGRAM, "accounts": ["user", JITO_TIP], "data": "" }),
+        ],
+        95_000,
+    )?;
+
+    let result = DexParser::new().parse_all(tx, classify());
+
+    assert_eq!(result.trades.len(), 1);
+    assert_eq!(result.flow_label, Some(FlowLabel::LikelyBot));
+    Ok(())
+}
+
+#[test]
+fn plain_swap_is_human_flow_and_label_is_opt_in() -> Result<()> {
+    let parser = DexParser::new();
+
+    let result = parser.parse_all(transaction(Vec::new(), 60_000)?, classify());
+    assert_eq!(result.trades.len(), 1);
+    assert_eq!(result.flow_label, Some(FlowLabel::LikelyHuman));
+
+    let result = parser.parse_all(transaction(Vec::new(), 60_000)?, None);
+    assert_eq!(result.flow_label, None);
+    Ok(())
+}
@@ -0,0 +1,178 @@
+use anyhow::Result;
+use serde_json::{json, Value};
+use solana_dex_parser::types::TradeType;
+use solana_dex_parser::{DexParser, SolanaTransaction};
+
+mod common;
+
+const FLUXBEAM: &str = "FLUXubRmkEi2q6K3Y9kBPg9248ggaZVsoSFhtJHSrm1X";
+const TOKEN_2022_PROGRAM: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+const FEE_MINT: &str = "2b1kV6DkPAnxd5ixfnxCpjxmKwqjjaYmCZfHsFu24GXo";
+
+fn transfer(
+    idx: &str,
+    source: &str,
+    destination: &str,
+    mint: &str,
+    amount: u64,
+    fee: u64,
+    decimals: u8,
+) -> Value {
+    let mut transfer = common::transfer(
+        TOKEN_2022_PROGRAM,
+        idx,
+        source,
+        destination,
+        mint,
+        amount,
+        decimals,
+    );
+    if fee > 0 {
+        transfer["type"] = json!("transferCheckedWithFee");
+        transfer["info"]["feeAmount"] = json!({
+            "amount": fee.to_string(),
+            "uiAmount": fee as f64 / 10f64.powi(decimals as i32),
+            "decimals": decimals,
+        });
+    }
+    transfer
+}
+
+fn instruction(tag: u8, args: [u64; 3], accounts: &[&str]) -> Value {
+    let mut data = vec![tag];
+    for arg in args {
+        data.extend_from_slice(&arg.to_le_bytes());
+    }
+    json!({
+        "programId": FLUXBEAM,
+        "accounts": accounts,
+        "data": bs58::encode(data).into_string(),
+    })
+}
+
+fn transaction(instruction: Value, transfers: Vec<Value>) -> Result<SolanaTransaction> {
+    common::transaction(
+        "fluxbeam",
+        json!({
+            "instructions": [instruction],
+            "transfers": transfers,
+        }),
+    )
+}
+
+#[test]
+fn swap_output_is_net_of_the_transfer_fee() -> Result<()> {
+    let swap = instruction(
+        1,
+        [1_000_000_000, 0, 0],
+        &[
+            "pool",
+            "authority",
+            "user",
+            "user-sol",
+            "vault-sol",
+            "vault-fee",
+            "user-fee",
+            "lp-mint",
+            "fee-account",
+            SOL_MINT,
+            FEE_MINT,
+        ],
+    );
+    let tx = transaction(
+        swap,
+        vec![
+            transfer(
+                "0-0",
+                "user-sol",
+                "vault-sol",
+                SOL_MINT,
+                1_000_000_000,
+                0,
+                9,
+            ),
+            transfer(
+                "0-1",
+                "vault-fee",
+                "user-fee",
+                FEE_MINT,
+                5_000_000,
+                50_000,
+                6,
+            ),
+        ],
+    )?;
+
+    let trades = DexParser::new().parse_trades(tx, None);
+
+    assert_eq!(trades.len(), 1);
+    let trade = &trades[0];
+    assert_eq!(trade.trade_type, TradeType::Buy);
+    assert_eq!(trade.amm.as_deref(), Some("FluxBeam"));
+    assert_eq!(trade.pool, ["pool"]);
+    assert_eq!(trade.input_token.amount_raw, "1000000000");
+    assert_eq!(trade.output_token.mint, FEE_MINT);
+    assert_eq!(trade.output_token.amount_raw, "4950000");
+    assert_eq!(trade.output_token.amount, 4.95);
+    assert_eq!(trade.fees.len(), 1);
+    assert_eq!(trade.fees[0].fee_type.as_deref(), Some("transferFee"));
+    assert_eq!(trade.fees[0].amount_raw, "50000");
+    Ok(())
+}
+
+#[test]
+fn deposit_credits_reserves_net_of_the_transfer_fee() -> Result<()> {
+    let deposit = instruction(
+        2,
+        [7_000, u64::MAX, u64::MAX],
+        &[
+            "pool",
+            "authority",
+            "user",
+            "user-sol",
+            "user-fee",
+            "vault-sol",
+            "vault-fee",
+            "lp-mint",
+            "user-lp",
+            SOL_MINT,
+            FEE_MINT,
+        ],
+    );
+    let tx = transaction(
+        deposit,
+        vec![
+            transfer(
+                "0-0",
+                "user-sol",
+                "vault-sol",
+                SOL_MINT,
+                2_000_000_000,
+                0,
+                9,
+            ),
+            transfer(
+                "0-1",
+                "user-fee",
+                "vault-fee",
+                FEE_MINT,
+                10_000_000,
+                100_000,
+                6,
+            ),
+        ],
+    )?;
+
+    let events = DexParser::new().parse_liquidity(tx, None);
+
+    assert_eq!(events.len(), 1);
+    let event = &events[0];
+    assert_eq!(event.event_type, TradeType::Add);
+    assert_eq!(event.pool_id, "pool");
+    assert_eq!(event.token0_mint.as_deref(), Some(SOL_MINT));
+    assert_eq!(event.token0_amount_raw.as_deref(), Some("2000000000"));
+    assert_eq!(event.token1_amount_raw.as_deref(), Some("9900000"));
+    assert_eq!(event.lp_amount_raw.as_deref(), Some("7000"));
+    Ok(())
+}
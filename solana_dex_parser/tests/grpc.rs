@@ -0,0 +1,202 @@
+#![cfg(feature = "grpc")]
+
+use std::fs;
+
+use anyhow::Result;
+use serde_json::Value;
+use solana_dex_parser::grpc::transaction_from_grpc;
+use solana_dex_parser::rpc;
+use solana_dex_parser::types::TransactionStatus;
+use yellowstone_grpc_proto::geyser::{SubscribeUpdateTransaction, SubscribeUpdateTransactionInfo};
+use yellowstone_grpc_proto::solana::storage::confirmed_block::{
+    CompiledInstruction, InnerInstruction, InnerInstructions, Message, MessageHeader, TokenBalance,
+    Transaction, TransactionError, TransactionStatusMeta, UiTokenAmount,
+};
+
+fn decode(value: &Value) -> Vec<u8> {
+    bs58::decode(value.as_str().unwrap()).into_vec().unwrap()
+}
+
+fn u32_of(value: &Value) -> u32 {
+    value.as_u64().unwrap() as u32
+}
+
+fn indexes(value: &Value) -> Vec<u8> {
+    value
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|index| index.as_u64().unwrap() as u8)
+        .collect()
+}
+
+fn u64s(value: &Value) -> Vec<u64> {
+    value
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item.as_u64().unwrap())
+        .collect()
+}
+
+fn token_balances(value: &Value) -> Vec<TokenBalance> {
+    value
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|balance| TokenBalance {
+            account_index: u32_of(&balance["accountIndex"]),
+            mint: balance["mint"].as_str().unwrap().to_string(),
+            ui_token_amount: Some(UiTokenAmount {
+                ui_amount: balance["uiTokenAmount"]["uiAmount"].as_f64().unwrap_or(0.0),
+                decimals: u32_of(&balance["uiTokenAmount"]["decimals"]),
+                amount: balance["uiTokenAmount"]["amount"]
+                    .as_str()
+                    .unwrap()
+                    .to_string(),
+                ui_amount_string: String::new(),
+            }),
+            owner: balance["owner"].as_str().unwrap_or_default().to_string(),
+            program_id: String::new(),
+        })
+        .collect()
+}
+
+/// The Geyser update a Yellowstone endpoint streams for a `getTransaction` JSON payload.
+fn update_from_rpc_json(payload: &Value) -> SubscribeUpdateTransaction {
+    let transaction = &payload["transaction"];
+    let message = &transaction["message"];
+    let meta = &payload["meta"];
+    let signatures: Vec<Vec<u8>> = transaction["signatures"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(decode)
+        .collect();
+    let addresses = |kind: &str| {
+        meta["loadedAddresses"][kind]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(decode)
+            .collect()
+    };
+
+    SubscribeUpdateTransaction {
+        slot: payload["slot"].as_u64().unwrap(),
+        transaction: Some(SubscribeUpdateTransactionInfo {
+            signature: signatures[0].clone(),
+            is_vote: false,
+            transaction: Some(Transaction {
+                signatures,
+                message: Some(Message {
+                    header: Some(MessageHeader {
+                        num_required_signatures: u32_of(
+                            &message["header"]["numRequiredSignatures"],
+                        ),
+                        num_readonly_signed_accounts: u32_of(
+                            &message["header"]["numReadonlySignedAccounts"],
+                        ),
+                        num_readonly_unsigned_accounts: u32_of(
+                            &message["header"]["numReadonlyUnsignedAccounts"],
+                        ),
+                    }),
+                    account_keys: message["accountKeys"]
+                        .as_array()
+                        .unwrap()
+                        .iter()
+                        .map(decode)
+                        .collect(),
+                    instructions: message["instructions"]
+                        .as_array()
+                        .unwrap()
+                        .iter()
+                        .map(|ix| CompiledInstruction {
+                            program_id_index: u32_of(&ix["programIdIndex"]),
+                            accounts: indexes(&ix["accounts"]),
+                            data: decode(&ix["data"]),
+                        })
+                        .collect(),
+                    versioned: true,
+                    ..Message::default()
+                }),
+            }),
+            meta: Some(TransactionStatusMeta {
+                err: (!meta["err"].is_null()).then(TransactionError::default),
+                fee: meta["fee"].as_u64().unwrap(),
+                pre_balances: u64s(&meta["preBalances"]),
+                post_balances: u64s(&meta["postBalances"]),
+                inner_instructions: meta["innerInstructions"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|set| InnerInstructions {
+                        index: u32_of(&set["index"]),
+                        instructions: set["instructions"]
+                            .as_array()
+                            .unwrap()
+                            .iter()
+                            .map(|ix| InnerInstruction {
+                                program_id_index: u32_of(&ix["programIdIndex"]),
+                                accounts: indexes(&ix["accounts"]),
+                                data: decode(&ix["data"]),
+                                stack_height: ix["stackHeight"].as_u64().map(|h| h as u32),
+                            })
+                            .collect(),
+                    })
+                    .collect(),
+                log_messages: meta["logMessages"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|line| line.as_str().unwrap().to_string())
+                    .collect(),
+                pre_token_balances: token_balances(&meta["preTokenBalances"]),
+                post_token_balances: token_balances(&meta["postTokenBalances"]),
+                loaded_writable_addresses: addresses("writable"),
+                loaded_readonly_addresses: addresses("readonly"),
+                compute_units_consumed: meta["computeUnitsConsumed"].as_u64(),
+                ..TransactionStatusMeta::default()
+            }),
+            index: 0,
+        }),
+    }
+}
+
+#[test]
+fn geyser_update_converts_like_the_rpc_payload() -> Result<()> {
+    let payload: Value = serde_json::from_str(&fs::read_to_string(
+        "tests/fixtures/raydium_clmm_v0_lookup.json",
+    )?)?;
+    let expected = rpc::transaction_from_rpc_json(payload.clone())?;
+
+    let tx = transaction_from_grpc(&update_from_rpc_json(&payload))?;
+
+    assert_eq!(tx.signature, expected.signature);
+    assert_eq!(tx.slot, expected.slot);
+    assert_eq!(tx.signers, expected.signers);
+    assert_eq!(tx.instructions, expected.instructions);
+    assert_eq!(tx.inner_instructions, expected.inner_instructions);
+    assert_eq!(tx.pre_token_balances, expected.pre_token_balances);
+    assert_eq!(tx.post_token_balances, expected.post_token_balances);
+    assert_eq!(tx.meta.fee, expected.meta.fee);
+    assert_eq!(tx.meta.compute_units, expected.meta.compute_units);
+    assert_eq!(tx.meta.status, TransactionStatus::Success);
+    assert_eq!(
+        tx.meta.sol_balance_changes,
+        expected.meta.sol_balance_changes
+    );
+    assert_eq!(tx.meta.log_messages, expected.meta.log_messages);
+    assert_eq!(tx.block_time, 0);
+    Ok(())
+}
+
+#[test]
+fn update_without_transaction_is_an_error() {
+    let update = SubscribeUpdateTransaction {
+        slot: 1,
+        transaction: None,
+    };
+
+    assert!(transaction_from_grpc(&update).is_err());
+}
@@ -0,0 +1,219 @@
+use anyhow::Result;
+use serde_json::{json, Value};
+use solana_dex_parser::types::TradeType;
+use solana_dex_parser::{DexParser, SolanaTransaction};
+
+mod common;
+
+const HEAVEN: &str = "HEAVENoP2qxoeuF8Dj2oT1GHEnu49U5mJYkdeC8BAX2o";
+const HUMIDIFI: &str = "9H6tua7jkLhdm3w8BvgpTn5LZNU7g4ZynDmCiNN3q6Rp";
+const TOKEN_PROGRAM: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+const USDC_MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+const MEME_MINT: &str = "4wBqpZM9xaSheZzJSMawUKKwhdpChKbZ5eu5ky4Vigw";
+
+const HEAVEN_BUY: [u8; 8] = [102, 6, 61, 18, 1, 218, 235, 234];
+const HEAVEN_SELL: [u8; 8] = [51, 230, 133, 164, 1, 127, 131, 173];
+
+#[allow(clippy::too_many_arguments)]
+fn transfer(
+    idx: &str,
+    source: &str,
+    destination: &str,
+    authority: &str,
+    destination_owner: &str,
+    mint: &str,
+    amount: u64,
+    decimals: u8,
+) -> Value {
+    let mut transfer = common::transfer(
+        TOKEN_PROGRAM,
+        idx,
+        source,
+        destination,
+        mint,
+        amount,
+        decimals,
+    );
+    transfer["info"]["authority"] = json!(authority);
+    transfer["info"]["destinationOwner"] = json!(destination_owner);
+    transfer
+}
+
+fn heaven_swap(discriminator: [u8; 8], amount_in: u64) -> Value {
+    let mut data = discriminator.to_vec();
+    data.extend_from_slice(&amount_in.to_le_bytes());
+    data.extend_from_slice(&1u64.to_le_bytes());
+    json!({
+        "programId": HEAVEN,
+        "accounts": [
+            TOKEN_PROGRAM,
+            TOKEN_PROGRAM,
+            "account-2",
+            "account-3",
+            "pool",
+            "user",
+            MEME_MINT,
+            SOL_MINT,
+        ],
+        "data": bs58::encode(data).into_string(),
+    })
+}
+
+fn transaction(
+    signature: &str,
+    instruction: Value,
+    transfers: Vec<Value>,
+) -> Result<SolanaTransaction> {
+    common::transaction(
+        signature,
+        json!({
+            "instructions": [instruction],
+            "transfers": transfers,
+        }),
+    )
+}
+
+#[test]
+fn heaven_buy_reports_protocol_fee_breakdown() -> Result<()> {
+    let tx = transaction(
+        "heaven",
+        heaven_swap(HEAVEN_BUY, 990_000_000),
+        vec![
+            transfer(
+                "0-0",
+                "user-sol",
+                "pool-sol",
+                "user",
+                "pool",
+                SOL_MINT,
+                990_000_000,
+                9,
+            ),
+            transfer(
+                "0-1", "user-sol", "fee-sol", "user", "treasury", SOL_MINT, 10_000_000, 9,
+            ),
+            transfer(
+                "0-2",
+                "pool-meme",
+                "user-meme",
+                "pool",
+                "user",
+                MEME_MINT,
+                5_000_000,
+                6,
+            ),
+        ],
+    )?;
+
+    let trades = DexParser::new().parse_trades(tx, None);
+
+    assert_eq!(trades.len(), 1);
+    let trade = &trades[0];
+    assert_eq!(trade.trade_type, TradeType::Buy);
+    assert_eq!(trade.program_id.as_deref(), Some(HEAVEN));
+    assert_eq!(trade.amm.as_deref(), Some("Heaven"));
+    assert_eq!(trade.pool, ["pool"]);
+    assert_eq!(trade.input_token.mint, SOL_MINT);
+    assert_eq!(trade.input_token.amount_raw, "990000000");
+    assert_eq!(trade.output_token.mint, MEME_MINT);
+    assert_eq!(trade.output_token.amount, 5.0);
+    assert_eq!(trade.fees.len(), 1);
+    assert_eq!(trade.fees[0].fee_type.as_deref(), Some("protocol"));
+    assert_eq!(trade.fees[0].recipient.as_deref(), Some("treasury"));
+    assert_eq!(trade.fee.as_ref().map(|fee| fee.amount), Some(0.01));
+    Ok(())
+}
+
+#[test]
+fn heaven_sell_takes_fee_from_the_quote_output() -> Result<()> {
+    let tx = transaction(
+        "heaven",
+        heaven_swap(HEAVEN_SELL, 5_000_000),
+        vec![
+            transfer(
+                "0-0",
+                "user-meme",
+                "pool-meme",
+                "user",
+                "pool",
+                MEME_MINT,
+                5_000_000,
+                6,
+            ),
+            transfer(
+                "0-1",
+                "pool-sol",
+                "user-sol",
+                "pool",
+                "user",
+                SOL_MINT,
+                980_000_000,
+                9,
+            ),
+            transfer(
+                "0-2", "pool-sol", "fee-sol", "pool", "treasury", SOL_MINT, 10_000_000, 9,
+            ),
+        ],
+    )?;
+
+    let trades = DexParser::new().parse_trades(tx, None);
+
+    assert_eq!(trades.len(), 1);
+    let trade = &trades[0];
+    assert_eq!(trade.trade_type, TradeType::Sell);
+    assert_eq!(trade.input_token.mint, MEME_MINT);
+    assert_eq!(trade.output_token.amount_raw, "980000000");
+    assert_eq!(
+        trade.fee.as_ref().map(|fee| fee.amount_raw.as_str()),
+        Some("10000000")
+    );
+    Ok(())
+}
+
+#[test]
+fn humidifi_swap_follows_the_vault_transfers() -> Result<()> {
+    let tx = transaction(
+        "humidifi",
+        json!({
+            "programId": HUMIDIFI,
+            "accounts": ["user", "pool", "base-vault", "quote-vault", "user-base", "user-quote"],
+            "data": "3Bxs4h24hBtQy9rw",
+        }),
+        vec![
+            transfer(
+                "0-0",
+                "user-base",
+                "base-vault",
+                "user",
+                "pool",
+                SOL_MINT,
+                1_000_000_000,
+                9,
+            ),
+            transfer(
+                "0-1",
+                "quote-vault",
+                "user-quote",
+                "pool",
+                "user",
+                USDC_MINT,
+                150_000_000,
+                6,
+            ),
+        ],
+    )?;
+
+    let trades = DexParser::new().parse_trades(tx, None);
+
+    assert_eq!(trades.len(), 1);
+    let trade = &trades[0];
+    assert_eq!(trade.program_id.as_deref(), Some(HUMIDIFI));
+    assert_eq!(trade.amm.as_deref(), Some("HumidiFi"));
+    assert_eq!(trade.pool, ["pool"]);
+    assert_eq!(trade.input_token.mint, SOL_MINT);
+    assert_eq!(trade.output_token.mint, USDC_MINT);
+    assert_eq!(trade.output_token.amount, 150.0);
+    assert!(trade.fees.is_empty());
+    Ok(())
+}
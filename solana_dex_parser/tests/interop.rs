@@ -0,0 +1,69 @@
+#![cfg(feature = "sdk-interop")]
+
+use anyhow::Result;
+use solana_account_decoder::parse_token::UiTokenAmount;
+use solana_dex_parser::interop::InteropError;
+use solana_dex_parser::protocols::jupiter::constants::discriminators::jupiter_events;
+use solana_dex_parser::protocols::jupiter::jupiter_parser::JupiterSwapEvent;
+use solana_dex_parser::{TokenAmount, TradeInfo};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+
+#[test]
+fn token_amounts_round_trip_through_ui_token_amount() {
+    let amount = TokenAmount::new("1500000", 6, Some(1.5));
+
+    let ui: UiTokenAmount = (&amount).into();
+    assert_eq!(ui.ui_amount_string, "1.5");
+    assert_eq!(ui.amount, "1500000");
+    assert_eq!(TokenAmount::from(ui), amount);
+}
+
+#[test]
+fn trade_addresses_and_signature_parse_into_sdk_types() -> Result<()> {
+    let signature = Signature::from([7u8; 64]);
+    let pool = Pubkey::new_unique();
+    let trade = TradeInfo {
+        signature: signature.to_string().into(),
+        pool: vec![pool.to_string()],
+        user: Some("not-a-pubkey".to_string()),
+        ..TradeInfo::default()
+    };
+
+    assert_eq!(Signature::try_from(&trade)?, signature);
+    assert_eq!(trade.pool_pubkeys()?, [pool]);
+    assert!(matches!(
+        trade.user_pubkey(),
+        Err(InteropError::InvalidPubkey { field: "user", .. })
+    ));
+    Ok(())
+}
+
+#[test]
+fn anchor_swap_event_decodes_from_log_and_cpi_data() -> Result<()> {
+    let (amm, input_mint, output_mint) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let mut body = Vec::new();
+    body.extend_from_slice(amm.as_ref());
+    body.extend_from_slice(input_mint.as_ref());
+    body.extend_from_slice(&1_000u64.to_le_bytes());
+    body.extend_from_slice(output_mint.as_ref());
+    body.extend_from_slice(&2_000u64.to_le_bytes());
+
+    let cpi = [&jupiter_events::SWAP[..], &body].concat();
+    let log = [&jupiter_events::SWAP[8..], &body].concat();
+    let event = JupiterSwapEvent::try_from(cpi.as_slice())?;
+
+    assert_eq!(event.amm, amm.to_string());
+    assert_eq!(event.input_amount, 1_000);
+    assert_eq!(event.output_mint, output_mint.to_string());
+    assert_eq!(JupiterSwapEvent::try_from(log.as_slice())?, event);
+    assert!(matches!(
+        JupiterSwapEvent::try_from(body.as_slice()),
+        Err(InteropError::Discriminator("SwapEvent"))
+    ));
+    Ok(())
+}
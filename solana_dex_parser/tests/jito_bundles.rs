@@ -0,0 +1,92 @@
+use solana_dex_parser::core::bundles::group_bundles;
+use solana_dex_parser::types::TokenAmount;
+use solana_dex_parser::{BlockContext, JitoTip, ParseResult, TradeInfo};
+
+/// Transaction at `position` in the block from `signer`, trading on `pool` when given.
+fn tx(position: usize, signer: &str, pool: Option<&str>, tip: bool) -> ParseResult {
+    ParseResult {
+        signature: format!("tx-{position}"),
+        signer: vec![signer.to_string()],
+        trades: pool
+            .map(|pool| TradeInfo {
+                pool: vec![pool.to_string()],
+                ..TradeInfo::default()
+            })
+            .into_iter()
+            .collect(),
+        tip: tip.then(|| JitoTip {
+            recipient: "96gYZGLnJYVFmbjzopPSU6QiEV5fGqZNyN9nmNhvrZU5".to_string(),
+            amount: TokenAmount::default(),
+            idx: "2".to_string(),
+        }),
+        block_context: Some(BlockContext {
+            transaction_index: position,
+            ..BlockContext::default()
+        }),
+        ..ParseResult::new()
+    }
+}
+
+fn bundles(results: &[ParseResult]) -> Vec<Option<(&str, usize)>> {
+    results
+        .iter()
+        .map(|result| result.bundle_id.as_deref().zip(result.bundle_index))
+        .collect()
+}
+
+#[test]
+fn sandwich_bundle_groups_the_victim_with_the_attacker() {
+    let mut block = vec![
+        tx(0, "someone", Some("pool-b"), false),
+        tx(1, "attacker", Some("pool-a"), false),
+        tx(2, "victim", Some("pool-a"), false),
+        tx(3, "attacker", Some("pool-a"), true),
+        tx(4, "other", Some("pool-a"), false),
+    ];
+
+    group_bundles(&mut block);
+
+    assert_eq!(
+        bundles(&block),
+        [
+            None,
+            Some(("tx-3", 0)),
+            Some(("tx-3", 1)),
+            Some(("tx-3", 2)),
+            None
+        ]
+    );
+}
+
+#[test]
+fn bundles_stop_at_gaps_and_earlier_tips() {
+    let mut block = vec![
+        tx(0, "alice", None, true),
+        tx(1, "alice", None, true),
+        tx(3, "alice", None, false),
+        tx(4, "alice", None, true),
+    ];
+
+    group_bundles(&mut block);
+
+    assert_eq!(
+        bundles(&block),
+        [
+            Some(("tx-0", 0)),
+            Some(("tx-1", 0)),
+            Some(("tx-4", 0)),
+            Some(("tx-4", 1))
+        ]
+    );
+}
+
+#[test]
+fn bundles_hold_at_most_five_transactions() {
+    let mut block: Vec<ParseResult> = (0..6).map(|i| tx(i, "bot", None, i == 5)).collect();
+
+    group_bundles(&mut block);
+
+    assert_eq!(block[0].bundle_id, None);
+    assert_eq!(block[1].bundle_index, Some(0));
+    assert_eq!(block[5].bundle_index, Some(4));
+}
@@ -0,0 +1,98 @@
+use anyhow::Result;
+use serde_json::{json, Value};
+use solana_dex_parser::{DexParser, SolanaTransaction};
+
+mod common;
+
+const SYSTEM_PROGRAM: &str = "11111111111111111111111111111111";
+const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+const TIP_ACCOUNT: &str = "96gYZGLnJYVFmbjzopPSU6QiEV5fGqZNyN9nmNhvrZU5";
+const OTHER_TIP_ACCOUNT: &str = "HFqU5x63VTqvQss8hp11i4wVV8bD44PvwucfZ2bU7gRe";
+
+fn system_transfer(recipient: &str, lamports: u64) -> Value {
+    let mut data = vec![2u8, 0, 0, 0];
+    data.extend_from_slice(&lamports.to_le_bytes());
+    json!({
+        "programId": SYSTEM_PROGRAM,
+        "accounts": ["user", recipient],
+        "data": bs58::encode(data).into_string(),
+    })
+}
+
+fn sol_transfer(idx: &str, destination: &str, lamports: u64) -> Value {
+    json!({
+        "type": "transfer",
+        "programId": SYSTEM_PROGRAM,
+        "info": {
+            "destination": destination,
+            "mint": SOL_MINT,
+            "source": "user",
+            "tokenAmount": {
+                "amount": lamports.to_string(),
+                "uiAmount": lamports as f64 / 1e9,
+                "decimals": 9,
+            },
+        },
+        "idx": idx,
+        "timestamp": 1_700_000_000,
+        "signature": "tipped",
+        "isFee": false,
+    })
+}
+
+fn transaction(instructions: Value, transfers: Value) -> Result<SolanaTransaction> {
+    common::transaction(
+        "tipped",
+        json!({
+            "instructions": instructions,
+            "transfers": transfers,
+        }),
+    )
+}
+
+#[test]
+fn tip_is_reported_apart_from_the_transfers() -> Result<()> {
+    let tx = transaction(
+        json!([
+            system_transfer("friend", 2_000_000_000),
+            system_transfer(TIP_ACCOUNT, 1_000_000),
+        ]),
+        json!([
+            sol_transfer("0", "friend", 2_000_000_000),
+            sol_transfer("1", TIP_ACCOUNT, 1_000_000),
+        ]),
+    )?;
+
+    let result = DexParser::new().parse_all(tx, None);
+
+    assert_eq!(result.transfers.len(), 1);
+    assert_eq!(result.transfers[0].info.destination, "friend");
+    let tip = result.tip.as_ref().expect("tip");
+    assert_eq!(tip.recipient, TIP_ACCOUNT);
+    assert_eq!(tip.amount.amount, "1000000");
+    assert_eq!(tip.amount.ui_amount, Some(0.001));
+    assert_eq!(tip.idx, "1");
+    let serialized = serde_json::to_value(&result)?;
+    assert_eq!(serialized["tip"]["recipient"], TIP_ACCOUNT);
+    Ok(())
+}
+
+#[test]
+fn tips_to_several_accounts_are_summed_and_untipped_transactions_have_none() -> Result<()> {
+    let tx = transaction(
+        json!([
+            system_transfer(TIP_ACCOUNT, 1_000_000),
+            system_transfer(OTHER_TIP_ACCOUNT, 500_000),
+        ]),
+        json!([]),
+    )?;
+    let tip = DexParser::new().parse_all(tx, None).tip.expect("tip");
+    assert_eq!(tip.recipient, TIP_ACCOUNT);
+    assert_eq!(tip.amount.amount, "1500000");
+
+    let plain = transaction(json!([system_transfer("friend", 1_000_000)]), json!([]))?;
+    let result = DexParser::new().parse_all(plain, None);
+    assert_eq!(result.tip, None);
+    assert!(serde_json::to_value(&result)?.get("tip").is_none());
+    Ok(())
+}
@@ -0,0 +1,298 @@
+use anyhow::Result;
+use serde_json::{json, Value};
+use solana_dex_parser::types::TradeType;
+use solana_dex_parser::{DexParser, SolanaTransaction};
+
+const JUPITER: &str = "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4";
+const JUPITER_DCA: &str = "DCA265Vj8a9CEuX1eb1LWRnDT7uK6q1xMipnNyatn23M";
+const JUPITER_TRIGGER: &str = "j1o2qRpjcyUwEvwtcfhEQefh773ZgjxcVRry7LDqg5X";
+const ORCA_WHIRLPOOL: &str = "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc";
+const RAYDIUM_CPMM: &str = "CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C";
+const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+const USDC_MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+const TOKEN_MINT: &str = "4wBqpZM9xaSheZzJSMawUKKwhdpChKbZ5eu5ky4Vigw";
+
+const SHARED_ACCOUNTS_ROUTE: [u8; 8] = [193, 32, 155, 51, 65, 214, 156, 129];
+const FILL_ORDER: [u8; 8] = [232, 122, 115, 25, 199, 143, 136, 162];
+const FULFILL_FLASH_FILL: [u8; 8] = [115, 64, 226, 78, 33, 211, 105, 162];
+const SWAP_EVENT: [u8; 16] = [
+    228, 69, 165, 46, 81, 203, 154, 29, 64, 198, 205, 232, 38, 8, 113, 226,
+];
+const LIMIT_TRADE_EVENT: [u8; 16] = [
+    228, 69, 165, 46, 81, 203, 154, 29, 189, 219, 127, 211, 78, 230, 97, 238,
+];
+const DCA_FILLED_EVENT: [u8; 16] = [
+    228, 69, 165, 46, 81, 203, 154, 29, 28, 65, 191, 210, 123, 85, 50, 188,
+];
+
+fn pubkey_bytes(address: &str) -> Result<Vec<u8>> {
+    Ok(bs58::decode(address).into_vec()?)
+}
+
+fn swap_event(amm: &str, input: (&str, u64), output: (&str, u64)) -> Result<Value> {
+    let mut data = SWAP_EVENT.to_vec();
+    data.extend(pubkey_bytes(amm)?);
+    data.extend(pubkey_bytes(input.0)?);
+    data.extend_from_slice(&input.1.to_le_bytes());
+    data.extend(pubkey_bytes(output.0)?);
+    data.extend_from_slice(&output.1.to_le_bytes());
+    Ok(json!({
+        "programId": JUPITER,
+        "accounts": [],
+        "data": bs58::encode(data).into_string(),
+    }))
+}
+
+fn balance(account: &str, mint: &str, decimals: u8) -> Value {
+    json!({
+        "account": account,
+        "mint": mint,
+        "owner": "user",
+        "uiTokenAmount": { "amount": "0", "decimals": decimals },
+    })
+}
+
+/// `sharedAccountsRoute` of 1 SOL quoted at 7,000 tokens with 0.5% slippage.
+fn shared_accounts_route(user: &str) -> Value {
+    // Route plan with a single step, followed by the fixed trailing arguments.
+    let mut data = SHARED_ACCOUNTS_ROUTE.to_vec();
+    data.push(0); // id
+    data.extend_from_slice(&1u32.to_le_bytes());
+    data.extend_from_slice(&[17, 100, 0, 1]);
+    data.extend_from_slice(&1_000_000_000u64.to_le_bytes()); // in_amount
+    data.extend_from_slice(&7_000_000_000u64.to_le_bytes()); // quoted_out_amount
+    data.extend_from_slice(&50u16.to_le_bytes()); // slippage_bps
+    data.push(0); // platform_fee_bps
+    let accounts: Vec<String> = (0..13)
+        .map(|i| match i {
+            2 => user.to_string(),
+            7 => SOL_MINT.to_string(),
+            8 => TOKEN_MINT.to_string(),
+            _ => format!("account-{i}"),
+        })
+        .collect();
+    json!({
+        "programId": JUPITER,
+        "accounts": accounts,
+        "data": bs58::encode(data).into_string(),
+    })
+}
+
+#[test]
+fn shared_accounts_route_becomes_one_trade_with_every_hop() -> Result<()> {
+    let tx: SolanaTransaction = serde_json::from_value(json!({
+        "slot": 900,
+        "signature": "jupiter",
+        "blockTime": 1_700_000_000,
+        "signers": ["user"],
+        "instructions": [shared_accounts_route("user")],
+        "innerInstructions": [{
+            "index": 0,
+            "instructions": [
+                { "programId": ORCA_WHIRLPOOL, "accounts": [], "data": "" },
+                swap_event(ORCA_WHIRLPOOL, (SOL_MINT, 1_000_000_000), (USDC_MINT, 150_000_000))?,
+                { "programId": RAYDIUM_CPMM, "accounts": [], "data": "" },
+                swap_event(RAYDIUM_CPMM, (USDC_MINT, 150_000_000), (TOKEN_MINT, 7_012_345_678))?,
+            ],
+        }],
+        "preTokenBalances": [
+            balance("user-sol", SOL_MINT, 9),
+            balance("user-usdc", USDC_MINT, 6),
+            balance("user-token", TOKEN_MINT, 9),
+        ],
+    }))?;
+
+    let trades = DexParser::new().parse_trades(tx, None);
+
+    let jupiter: Vec<_> = trades
+        .iter()
+        .filter(|trade| trade.program_id.as_deref() == Some(JUPITER))
+        .collect();
+    assert_eq!(jupiter.len(), 1);
+    let trade = jupiter[0];
+    assert_eq!(trade.trade_type, TradeType::Buy);
+    assert_eq!(trade.user.as_deref(), Some("user"));
+    assert_eq!(trade.route.as_deref(), Some("Jupiter"));
+    assert_eq!(
+        trade.amms.as_deref(),
+        Some(["Orca".to_string(), "RaydiumCPMM".to_string()].as_slice())
+    );
+    assert_eq!(trade.amm.as_deref(), Some("Orca"));
+    // 0.5% below the 7,000 quote leaves 6,965; 7,012.345678 was executed.
+    assert_eq!(trade.slippage_bps, Some(67));
+    assert_eq!(trade.idx, "0-3");
+    assert_eq!(trade.input_token.mint, SOL_MINT);
+    assert_eq!(trade.input_token.amount, 1.0);
+    assert_eq!(trade.output_token.mint, TOKEN_MINT);
+    assert_eq!(trade.output_token.amount_raw, "7012345678");
+    Ok(())
+}
+
+#[test]
+fn routes_invoked_by_one_instruction_keep_their_own_hops() -> Result<()> {
+    let at = |mut instruction: Value, height: u32| {
+        instruction["stackHeight"] = json!(height);
+        instruction
+    };
+    let tx: SolanaTransaction = serde_json::from_value(json!({
+        "slot": 900,
+        "signature": "jupiter-twice",
+        "blockTime": 1_700_000_000,
+        "signers": ["user"],
+        "instructions": [{ "programId": "bot-program", "accounts": [], "data": "" }],
+        "innerInstructions": [{
+            "index": 0,
+            "instructions": [
+                at(shared_accounts_route("first-user"), 2),
+                at(json!({ "programId": ORCA_WHIRLPOOL, "accounts": [], "data": "" }), 3),
+                at(swap_event(ORCA_WHIRLPOOL, (SOL_MINT, 1_000_000_000), (TOKEN_MINT, 7_000_000_000))?, 3),
+                at(shared_accounts_route("second-user"), 2),
+                at(json!({ "programId": RAYDIUM_CPMM, "accounts": [], "data": "" }), 3),
+                at(swap_event(RAYDIUM_CPMM, (SOL_MINT, 1_000_000_000), (TOKEN_MINT, 6_990_000_000))?, 3),
+            ],
+        }],
+        "preTokenBalances": [balance("user-sol", SOL_MINT, 9), balance("user-token", TOKEN_MINT, 9)],
+    }))?;
+
+    let trades = DexParser::new().parse_trades(tx, None);
+
+    let jupiter: Vec<_> = trades
+        .iter()
+        .filter(|trade| trade.program_id.as_deref() == Some(JUPITER))
+        .collect();
+    assert_eq!(jupiter.len(), 2);
+    assert_eq!(jupiter[0].user.as_deref(), Some("first-user"));
+    assert_eq!(jupiter[0].amm.as_deref(), Some("Orca"));
+    assert_eq!(jupiter[0].output_token.amount_raw, "7000000000");
+    assert_eq!(jupiter[1].user.as_deref(), Some("second-user"));
+    assert_eq!(jupiter[1].amm.as_deref(), Some("RaydiumCPMM"));
+    assert_eq!(jupiter[1].output_token.amount_raw, "6990000000");
+    Ok(())
+}
+
+#[test]
+fn trigger_fill_order_becomes_limit_fill() -> Result<()> {
+    let order = bs58::encode([9u8; 32]).into_string();
+    let taker = bs58::encode([7u8; 32]).into_string();
+    let accounts = [
+        taker.as_str(),
+        "maker",
+        order.as_str(),
+        "order-reserve",
+        "maker-output",
+        "taker-input",
+    ];
+    let mut event = LIMIT_TRADE_EVENT.to_vec();
+    event.extend_from_slice(&[9u8; 32]);
+    event.extend_from_slice(&[7u8; 32]);
+    event.extend_from_slice(&0u64.to_le_bytes()); // remaining_making_amount
+    event.extend_from_slice(&0u64.to_le_bytes()); // remaining_taking_amount
+    event.extend_from_slice(&150_000_000u64.to_le_bytes()); // making_amount
+    event.extend_from_slice(&1_000_000_000u64.to_le_bytes()); // taking_amount
+
+    let tx: SolanaTransaction = serde_json::from_value(json!({
+        "slot": 901,
+        "signature": "trigger",
+        "blockTime": 1_700_000_000,
+        "signers": [taker],
+        "instructions": [{
+            "programId": JUPITER_TRIGGER,
+            "accounts": accounts,
+            "data": bs58::encode(FILL_ORDER).into_string(),
+        }],
+        "innerInstructions": [{
+            "index": 0,
+            "instructions": [{
+                "programId": JUPITER_TRIGGER,
+                "accounts": [],
+                "data": bs58::encode(event).into_string(),
+            }],
+        }],
+        "preTokenBalances": [
+            balance("order-reserve", USDC_MINT, 6),
+            balance("maker-output", SOL_MINT, 9),
+        ],
+    }))?;
+
+    let trades = DexParser::new().parse_trades(tx, None);
+
+    assert_eq!(trades.len(), 1);
+    let trade = &trades[0];
+    assert_eq!(trade.trade_type, TradeType::LimitFill);
+    assert_eq!(trade.user.as_deref(), Some(taker.as_str()));
+    assert_eq!(trade.maker.as_deref(), Some("maker"));
+    assert_eq!(trade.order.as_deref(), Some(order.as_str()));
+    assert_eq!(trade.amm.as_deref(), Some("JupiterTrigger"));
+    assert_eq!(trade.idx, "0-0");
+    assert_eq!(trade.input_token.mint, USDC_MINT);
+    assert_eq!(trade.input_token.amount, 150.0);
+    assert_eq!(trade.output_token.mint, SOL_MINT);
+    assert_eq!(trade.output_token.amount, 1.0);
+    Ok(())
+}
+
+#[test]
+fn dca_fill_reports_cycle_and_remaining_input() -> Result<()> {
+    let user = bs58::encode([3u8; 32]).into_string();
+    let dca = bs58::encode([4u8; 32]).into_string();
+    let mut event = DCA_FILLED_EVENT.to_vec();
+    event.extend(pubkey_bytes(&user)?);
+    event.extend(pubkey_bytes(&dca)?);
+    event.extend(pubkey_bytes(USDC_MINT)?);
+    event.extend(pubkey_bytes(SOL_MINT)?);
+    event.extend_from_slice(&25_000_000u64.to_le_bytes()); // in_amount
+    event.extend_from_slice(&166_000_000u64.to_le_bytes()); // out_amount
+    event.extend(pubkey_bytes(SOL_MINT)?);
+    event.extend_from_slice(&166_000u64.to_le_bytes()); // fee
+
+    let tx: SolanaTransaction = serde_json::from_value(json!({
+        "slot": 902,
+        "signature": "dca",
+        "blockTime": 1_700_000_000,
+        "signers": ["keeper"],
+        "instructions": [{
+            "programId": JUPITER_DCA,
+            "accounts": ["keeper", dca],
+            "data": bs58::encode(FULFILL_FLASH_FILL).into_string(),
+        }],
+        "innerInstructions": [{
+            "index": 0,
+            "instructions": [{
+                "programId": JUPITER_DCA,
+                "accounts": [],
+                "data": bs58::encode(event).into_string(),
+            }],
+        }],
+        "preTokenBalances": [
+            balance("dca-usdc", USDC_MINT, 6),
+            balance("dca-sol", SOL_MINT, 9),
+        ],
+        "postTokenBalances": [{
+            "account": "dca-usdc",
+            "mint": USDC_MINT,
+            "owner": dca,
+            "uiTokenAmount": { "amount": "60000000", "decimals": 6 },
+        }],
+    }))?;
+
+    let trades = DexParser::new().parse_trades(tx, None);
+
+    assert_eq!(trades.len(), 1);
+    let trade = &trades[0];
+    assert_eq!(trade.trade_type, TradeType::DcaFill);
+    assert_eq!(trade.user.as_deref(), Some(user.as_str()));
+    assert_eq!(trade.amm.as_deref(), Some("JupiterDCA"));
+    assert_eq!(trade.idx, "0-0");
+    assert_eq!(trade.input_token.amount, 25.0);
+    assert_eq!(trade.output_token.mint, SOL_MINT);
+    assert_eq!(trade.output_token.amount, 0.166);
+    let fee = trade.fee.as_ref().expect("dca fee");
+    assert_eq!(fee.mint, SOL_MINT);
+    assert_eq!(fee.amount_raw, "166000");
+    let fill = trade.dca.as_ref().expect("dca fill info");
+    assert_eq!(fill.dca, dca);
+    assert_eq!(fill.cycle_in_amount, "25000000");
+    assert_eq!(fill.remaining_in_amount.as_deref(), Some("60000000"));
+    assert_eq!(fill.remaining_cycles, Some(3));
+    Ok(())
+}
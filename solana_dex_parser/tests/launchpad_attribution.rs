@@ -0,0 +1,145 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use serde_json::{json, Value};
+use solana_dex_parser::types::TradeType;
+use solana_dex_parser::{DexParser, ParseConfig, SolanaTransaction};
+
+mod common;
+
+const METEORA_DBC: &str = "dbcij3LWUppWqq96dh6gJWwBifmcGfLSB5D4DuSMaqN";
+const BELIEVE_CONFIG: &str = "5qWya6UjwWnGVhdSBL3hyZ7B45jbk6Byt1hwd7ohEGXE";
+const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+const TOKEN_MINT: &str = "4wBqpZM9xaSheZzJSMawUKKwhdpChKbZ5eu5ky4Vigw";
+
+const SWAP: [u8; 8] = [248, 198, 158, 145, 225, 117, 135, 200];
+const CLAIM_CREATOR_TRADING_FEE: [u8; 8] = [82, 220, 250, 189, 3, 85, 107, 45];
+const EVT_CLAIM_CREATOR_TRADING_FEE: [u8; 16] = [
+    228, 69, 165, 46, 81, 203, 154, 29, 154, 228, 215, 202, 133, 155, 214, 138,
+];
+
+fn encode(parts: &[&[u8]]) -> String {
+    bs58::encode(parts.concat()).into_string()
+}
+
+fn transfer(idx: &str, source: &str, destination: &str, mint: &str, amount: u64) -> Value {
+    common::transfer(METEORA_DBC, idx, source, destination, mint, amount, 9)
+}
+
+fn swap(config: &str) -> Value {
+    json!({
+        "programId": METEORA_DBC,
+        "accounts": [
+            "pool-authority",
+            config,
+            "virtual-pool",
+            "user-sol",
+            "user-token",
+            "base-vault",
+            "quote-vault",
+            TOKEN_MINT,
+            SOL_MINT,
+            "user",
+        ],
+        "data": encode(&[&SWAP, &1_000_000_000u64.to_le_bytes(), &0u64.to_le_bytes()]),
+    })
+}
+
+fn transaction(instructions: Value, inner_instructions: Value) -> Result<SolanaTransaction> {
+    common::transaction(
+        "believe",
+        json!({
+            "instructions": instructions,
+            "innerInstructions": inner_instructions,
+            "transfers": [
+                transfer("0-0", "user-sol", "quote-vault", SOL_MINT, 1_000_000_000),
+                transfer("0-1", "base-vault", "user-token", TOKEN_MINT, 35_000_000_000),
+            ],
+        }),
+    )
+}
+
+#[test]
+fn believe_curves_are_attributed_by_platform_config() -> Result<()> {
+    let believe =
+        DexParser::new().parse_all(transaction(json!([swap(BELIEVE_CONFIG)]), json!([]))?, None);
+    assert_eq!(believe.meme_events[0].protocol.as_deref(), Some("Believe"));
+    assert_eq!(
+        believe.meme_events[0].platform_config.as_deref(),
+        Some(BELIEVE_CONFIG)
+    );
+
+    let plain =
+        DexParser::new().parse_all(transaction(json!([swap("other-config")]), json!([]))?, None);
+    assert_eq!(plain.meme_events[0].protocol.as_deref(), Some("MeteoraDBC"));
+    Ok(())
+}
+
+#[test]
+fn configured_platforms_take_precedence() -> Result<()> {
+    let config = ParseConfig {
+        launchpad_platforms: HashMap::from([
+            ("studio-config".to_string(), "Studio".to_string()),
+            (BELIEVE_CONFIG.to_string(), "BelieveApp".to_string()),
+        ]),
+        ..ParseConfig::default()
+    };
+    let parser = DexParser::new();
+
+    let studio = parser.parse_all(
+        transaction(json!([swap("studio-config")]), json!([]))?,
+        Some(config.clone()),
+    );
+    assert_eq!(studio.meme_events[0].protocol.as_deref(), Some("Studio"));
+    let believe = parser.parse_all(
+        transaction(json!([swap(BELIEVE_CONFIG)]), json!([]))?,
+        Some(config),
+    );
+    assert_eq!(
+        believe.meme_events[0].protocol.as_deref(),
+        Some("BelieveApp")
+    );
+    Ok(())
+}
+
+#[test]
+fn events_without_a_config_borrow_it_from_their_curve() -> Result<()> {
+    let mut claim_accounts: Vec<String> = (0..9).map(|i| format!("account-{i}")).collect();
+    claim_accounts[6] = TOKEN_MINT.to_string();
+    claim_accounts[7] = SOL_MINT.to_string();
+    claim_accounts[8] = "creator".to_string();
+    let pool = bs58::decode("9xQeWvG816bUx9EPjHmaT23yvVM2ZWbrrpZb9PusVFin").into_vec()?;
+    let claim_event = encode(&[
+        &EVT_CLAIM_CREATOR_TRADING_FEE,
+        &pool,
+        &0u64.to_le_bytes(),
+        &10_000_000u64.to_le_bytes(),
+    ]);
+    let mut swap = swap(BELIEVE_CONFIG);
+    swap["accounts"][2] = json!("9xQeWvG816bUx9EPjHmaT23yvVM2ZWbrrpZb9PusVFin");
+    let tx = transaction(
+        json!([
+            swap,
+            {
+                "programId": METEORA_DBC,
+                "accounts": claim_accounts,
+                "data": encode(&[&CLAIM_CREATOR_TRADING_FEE]),
+            },
+        ]),
+        json!([{
+            "index": 1,
+            "instructions": [{ "programId": METEORA_DBC, "accounts": [], "data": claim_event }],
+        }]),
+    )?;
+
+    let result = DexParser::new().parse_all(tx, None);
+
+    let claim = result
+        .meme_events
+        .iter()
+        .find(|event| event.event_type == TradeType::FeeClaim)
+        .expect("fee claim");
+    assert_eq!(claim.platform_config.as_deref(), Some(BELIEVE_CONFIG));
+    assert_eq!(claim.protocol.as_deref(), Some("Believe"));
+    Ok(())
+}
@@ -0,0 +1,95 @@
+use std::fs;
+
+use anyhow::Result;
+use base64::{engine::general_purpose::STANDARD as BASE64_STANDARD, Engine as _};
+use serde_json::{json, Value};
+use solana_dex_parser::types::TradeType;
+use solana_dex_parser::{DexParser, ParseConfig, SolanaTransaction};
+
+const PUMP_FUN_PROGRAM: &str = "6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P";
+const COMPUTE_BUDGET_PROGRAM: &str = "ComputeBudget111111111111111111111111111111";
+const BUY: [u8; 8] = [102, 6, 61, 18, 1, 218, 235, 234];
+
+fn logs_fallback() -> Option<ParseConfig> {
+    Some(ParseConfig {
+        use_logs_fallback: true,
+        ..ParseConfig::default()
+    })
+}
+
+/// The trade of `tests/fixtures/pumpfun_trade.json` as a provider without inner
+/// instructions returns it: a pump.fun buy whose event only shows in the logs.
+fn stripped_buy(logs: impl FnOnce(&str) -> Vec<String>) -> Result<SolanaTransaction> {
+    let mut tx: Value =
+        serde_json::from_str(&fs::read_to_string("tests/fixtures/pumpfun_trade.json")?)?;
+    let event = bs58::decode(tx["instructions"][0]["data"].as_str().unwrap()).into_vec()?;
+    let logged = BASE64_STANDARD.encode(&event[8..]);
+
+    tx["instructions"] = json!([
+        {"programId": COMPUTE_BUDGET_PROGRAM, "accounts": [], "data": "3DTZbgwsozUF"},
+        {
+            "programId": PUMP_FUN_PROGRAM,
+            "accounts": ["global", "fee-recipient", "mint", "bonding-curve"],
+            "data": bs58::encode(BUY).into_string(),
+        },
+    ]);
+    tx["meta"]["logMessages"] = json!(logs(&logged));
+    Ok(serde_json::from_value(tx)?)
+}
+
+fn buy_logs(logged: &str) -> Vec<String> {
+    vec![
+        format!("Program {COMPUTE_BUDGET_PROGRAM} invoke [1]"),
+        format!("Program {COMPUTE_BUDGET_PROGRAM} success"),
+        format!("Program {PUMP_FUN_PROGRAM} invoke [1]"),
+        "Program log: Instruction: Buy".to_string(),
+        format!("Program data: {logged}"),
+        format!("Program {PUMP_FUN_PROGRAM} consumed 30000 of 200000 compute units"),
+        format!("Program {PUMP_FUN_PROGRAM} success"),
+    ]
+}
+
+#[test]
+fn logged_pumpfun_event_is_parsed_without_inner_instructions() -> Result<()> {
+    let tx = stripped_buy(buy_logs)?;
+    let parser = DexParser::new();
+
+    assert!(parser.parse_all(tx.clone(), None).trades.is_empty());
+
+    let result = parser.parse_all(tx, logs_fallback());
+    assert_eq!(result.trades.len(), 1);
+    let trade = &result.trades[0];
+    assert_eq!(trade.trade_type, TradeType::Buy);
+    assert_eq!(trade.program_id.as_deref(), Some(PUMP_FUN_PROGRAM));
+    assert_eq!(trade.idx, "1-0");
+    assert!((trade.input_token.amount - 0.5).abs() < 1e-9);
+    assert!((trade.output_token.amount - 12_345.6).abs() < 1e-9);
+    Ok(())
+}
+
+#[test]
+fn logs_out_of_line_with_instructions_are_ignored() -> Result<()> {
+    // The logs skip the compute budget invocation, so they cannot be matched up.
+    let tx = stripped_buy(|logged| buy_logs(logged).split_off(2))?;
+
+    let result = DexParser::new().parse_all(tx, logs_fallback());
+
+    assert!(result.trades.is_empty());
+    Ok(())
+}
+
+#[test]
+fn recorded_inner_instructions_take_precedence() -> Result<()> {
+    let mut tx = stripped_buy(buy_logs)?;
+    let mut recorded: Value =
+        serde_json::from_str(&fs::read_to_string("tests/fixtures/pumpfun_trade.json")?)?;
+    recorded["instructions"][0]["stackHeight"] = json!(2);
+    tx.inner_instructions = serde_json::from_value(json!([
+        {"index": 1, "instructions": [recorded["instructions"][0]]},
+    ]))?;
+
+    let result = DexParser::new().parse_all(tx, logs_fallback());
+
+    assert_eq!(result.trades.len(), 1);
+    Ok(())
+}
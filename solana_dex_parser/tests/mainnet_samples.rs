@@ -0,0 +1,213 @@
+//! Recorded mainnet transactions, one per protocol and instruction variant, fetched from RPC
+//! and checked against the events they are known to contain.
+//!
+//! The live check is opt-in: `cargo test --test mainnet_samples -- --ignored`, against
+//! `SOLANA_RPC_URL` (mainnet-beta by default). `SOLANA_SAMPLES` points at another sample
+//! list. Add a sample to `fixtures/mainnet_samples.json` whenever a protocol or
+//! instruction variant gains support.
+
+use std::collections::HashSet;
+use std::fs;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use solana_dex_parser::core::constants::dex_program_names;
+use solana_dex_parser::{rpc, DexParser, ParseResult};
+
+const SAMPLES: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/tests/fixtures/mainnet_samples.json"
+);
+
+/// A recorded transaction and the minimum it must parse into.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Sample {
+    protocol: String,
+    variant: String,
+    signature: String,
+    program_id: String,
+    /// Trades executed by `program_id`.
+    #[serde(default)]
+    trades: usize,
+    /// Liquidity events emitted by `program_id`.
+    #[serde(default)]
+    liquidities: usize,
+    /// Meme events reported under `protocol`.
+    #[serde(default)]
+    meme_events: usize,
+}
+
+impl Sample {
+    fn label(&self) -> String {
+        format!("{} {} ({})", self.protocol, self.variant, self.signature)
+    }
+
+    fn check(&self, result: &ParseResult) -> Vec<String> {
+        let program = Some(self.program_id.as_str());
+        let found = [
+            (
+                "trades",
+                self.trades,
+                result
+                    .trades
+                    .iter()
+                    .filter(|trade| trade.program_id.as_deref() == program)
+                    .count(),
+            ),
+            (
+                "liquidities",
+                self.liquidities,
+                result
+                    .liquidities
+                    .iter()
+                    .filter(|pool| pool.program_id.as_deref() == program)
+                    .count(),
+            ),
+            (
+                "meme events",
+                self.meme_events,
+                result
+                    .meme_events
+                    .iter()
+                    .filter(|event| event.protocol.as_deref() == Some(self.protocol.as_str()))
+                    .count(),
+            ),
+        ];
+
+        let mut failures = Vec::new();
+        if !result.state {
+            failures.push(format!("parse failed: {:?}", result.msg));
+        }
+        if result.signature != self.signature {
+            failures.push(format!("signature {}", result.signature));
+        }
+        for (kind, expected, actual) in found {
+            if actual < expected {
+                failures.push(format!("{actual} {kind}, expected at least {expected}"));
+            }
+        }
+        failures
+    }
+}
+
+/// Protocols whose every parsed instruction family has a sample. Protocols without a
+/// recorded signature yet (OpenBook v2, Saber, FluxBeam, SolFi, ZeroFi, Obric v2, Heaven,
+/// HumidiFi, Virtuals, Bags, Meteora DBC and the token-swap forks) are not listed.
+const SAMPLED: [(&str, &[&str]); 10] = [
+    (
+        "Raydium",
+        &["swap", "create pool", "add liquidity", "remove liquidity"],
+    ),
+    (
+        "RaydiumCPMM",
+        &["swap", "create pool", "deposit", "withdraw"],
+    ),
+    (
+        "RaydiumCLMM",
+        &[
+            "swap",
+            "create pool",
+            "increase liquidity",
+            "decrease liquidity",
+        ],
+    ),
+    (
+        "Orca",
+        &["swap", "increase liquidity", "decrease liquidity"],
+    ),
+    ("Meteora", &["swap", "add liquidity", "remove liquidity"]),
+    ("Jupiter", &["route"]),
+    ("JupiterDCA", &["fill"]),
+    ("Pumpfun", &["buy", "create"]),
+    (
+        "Pumpswap",
+        &["buy", "sell", "create pool", "deposit", "withdraw"],
+    ),
+    ("RaydiumLaunchLab", &["create", "buy", "sell", "migrate"]),
+];
+
+fn load_samples(path: &str) -> Result<Vec<Sample>> {
+    let raw = fs::read_to_string(path).with_context(|| format!("failed to read {path}"))?;
+    serde_json::from_str(&raw).with_context(|| format!("failed to parse {path}"))
+}
+
+#[test]
+fn sample_list_is_well_formed() -> Result<()> {
+    let samples = load_samples(SAMPLES)?;
+    assert!(!samples.is_empty());
+
+    let mut signatures = HashSet::new();
+    for sample in &samples {
+        let label = sample.label();
+        let decoded = bs58::decode(&sample.signature).into_vec()?;
+        assert_eq!(decoded.len(), 64, "{label}: not a transaction signature");
+        assert!(
+            signatures.insert(sample.signature.as_str()),
+            "{label}: listed twice"
+        );
+        assert_eq!(
+            dex_program_names::name(&sample.program_id),
+            sample.protocol,
+            "{label}: program id does not belong to the protocol"
+        );
+        assert!(
+            sample.trades + sample.liquidities + sample.meme_events > 0,
+            "{label}: asserts nothing"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn sampled_protocols_cover_their_variants() -> Result<()> {
+    let samples = load_samples(SAMPLES)?;
+    for (protocol, variants) in SAMPLED {
+        for variant in variants {
+            assert!(
+                samples
+                    .iter()
+                    .any(|sample| sample.protocol == protocol && sample.variant.contains(variant)),
+                "{protocol}: no sample for {variant}"
+            );
+        }
+    }
+    Ok(())
+}
+
+#[test]
+#[ignore]
+fn mainnet_samples_parse_into_their_known_events() -> Result<()> {
+    let rpc_url = std::env::var("SOLANA_RPC_URL")
+        .unwrap_or_else(|_| "https://api.mainnet-beta.solana.com".to_string());
+    let path = std::env::var("SOLANA_SAMPLES").unwrap_or_else(|_| SAMPLES.to_string());
+    let samples = load_samples(&path)?;
+
+    let parser = DexParser::new();
+    let mut failures = Vec::new();
+    let mut failed_samples = 0;
+    for sample in &samples {
+        let problems = match rpc::fetch_transaction(&rpc_url, &sample.signature) {
+            Ok(tx) => sample.check(&parser.parse_all(tx, None)),
+            Err(err) => vec![format!("fetch failed: {err:#}")],
+        };
+        if !problems.is_empty() {
+            failed_samples += 1;
+        }
+        failures.extend(
+            problems
+                .into_iter()
+                .map(|problem| format!("{}: {problem}", sample.label())),
+        );
+    }
+
+    // Every sample is checked before failing, so one run reports the whole matrix.
+    assert!(
+        failures.is_empty(),
+        "{} of {} samples failed:\n{}",
+        failed_samples,
+        samples.len(),
+        failures.join("\n")
+    );
+    Ok(())
+}
@@ -0,0 +1,183 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use serde_json::json;
+use solana_dex_parser::core::matcher::{Leg, TransferMatcher};
+use solana_dex_parser::types::{TradeInfo, TransferData};
+
+mod common;
+
+const PUMP_SWAP: &str = "pAMMBay6oceH9fJKBRHGP5D4bD4sWpmSwMn52FMfXEA";
+const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+const TOKEN_MINT: &str = "4wBqpZM9xaSheZzJSMawUKKwhdpChKbZ5eu5ky4Vigw";
+
+fn transfer(
+    idx: &str,
+    source: &str,
+    destination: &str,
+    mint: &str,
+    amount: u64,
+) -> Result<TransferData> {
+    let mut transfer = common::transfer(PUMP_SWAP, idx, source, destination, mint, amount, 9);
+    transfer["info"]["authority"] = json!("user");
+    transfer["info"]["destinationOwner"] = json!(if destination.starts_with("user") {
+        "user"
+    } else {
+        "pool"
+    });
+    Ok(serde_json::from_value(transfer)?)
+}
+
+fn trade(idx: &str) -> TradeInfo {
+    TradeInfo {
+        program_id: Some(PUMP_SWAP.to_string()),
+        user: Some("user".to_string()),
+        idx: idx.to_string(),
+        input_token: common::token(SOL_MINT, 1_000_000_000, 9),
+        output_token: common::token(TOKEN_MINT, 50_000_000_000, 9),
+        ..TradeInfo::default()
+    }
+}
+
+#[test]
+fn fee_adjusted_legs_match_within_tolerance() -> Result<()> {
+    let transfers = HashMap::from([(
+        PUMP_SWAP.to_string(),
+        vec![
+            // The pool receives the input net of a 1% protocol fee.
+            transfer("1-0", "user-sol", "pool-sol", SOL_MINT, 990_000_000)?,
+            transfer("1-1", "user-sol", "fee-sol", SOL_MINT, 10_000_000)?,
+            transfer(
+                "1-2",
+                "pool-token",
+                "user-token",
+                TOKEN_MINT,
+                49_800_000_000,
+            )?,
+        ],
+    )]);
+
+    let legs = TransferMatcher::new(&transfers).match_trade(&trade("1-0"));
+
+    assert_eq!(legs.input.map(|leg| leg.idx.as_str()), Some("1-0"));
+    assert_eq!(legs.output.map(|leg| leg.idx.as_str()), Some("1-2"));
+
+    let strict = TransferMatcher::new(&transfers)
+        .with_tolerance_bps(0)
+        .match_trade(&trade("1-0"));
+    assert!(strict.input.is_none());
+    assert!(strict.output.is_none());
+    Ok(())
+}
+
+#[test]
+fn legs_prefer_the_trade_invocation_over_equal_amounts_elsewhere() -> Result<()> {
+    let transfers = HashMap::from([(
+        PUMP_SWAP.to_string(),
+        vec![
+            transfer("0-0", "user-sol", "pool-sol", SOL_MINT, 1_000_000_000)?,
+            transfer(
+                "0-1",
+                "pool-token",
+                "user-token",
+                TOKEN_MINT,
+                50_000_000_000,
+            )?,
+            transfer("2-0", "user-sol", "pool-sol", SOL_MINT, 1_000_000_000)?,
+            transfer(
+                "2-1",
+                "pool-token",
+                "user-token",
+                TOKEN_MINT,
+                50_000_000_000,
+            )?,
+        ],
+    )]);
+
+    let legs = TransferMatcher::new(&transfers).match_trade(&trade("2-3"));
+
+    assert_eq!(legs.input.map(|leg| leg.idx.as_str()), Some("2-0"));
+    assert_eq!(legs.output.map(|leg| leg.idx.as_str()), Some("2-1"));
+    Ok(())
+}
+
+#[test]
+fn legs_are_never_taken_from_another_invocation() -> Result<()> {
+    let transfers = HashMap::from([(
+        PUMP_SWAP.to_string(),
+        vec![
+            // The trade's own instruction moved a different amount.
+            transfer("0-0", "user-sol", "pool-sol", SOL_MINT, 400_000_000)?,
+            transfer("2-0", "user-sol", "pool-sol", SOL_MINT, 1_000_000_000)?,
+            transfer(
+                "2-1",
+                "pool-token",
+                "user-token",
+                TOKEN_MINT,
+                50_000_000_000,
+            )?,
+        ],
+    )]);
+
+    let legs = TransferMatcher::new(&transfers).match_trade(&trade("0-0"));
+
+    assert!(legs.input.is_none());
+    assert!(legs.output.is_none());
+    Ok(())
+}
+
+#[test]
+fn user_owned_legs_outrank_a_closer_amount() -> Result<()> {
+    let mut other = transfer("1-1", "other-sol", "pool-sol", SOL_MINT, 1_000_000_000)?;
+    other.info.authority = Some("other".to_string());
+    let transfers = HashMap::from([(
+        PUMP_SWAP.to_string(),
+        vec![
+            transfer("1-0", "user-sol", "pool-sol", SOL_MINT, 990_000_000)?,
+            other,
+            transfer(
+                "1-2",
+                "pool-token",
+                "user-token",
+                TOKEN_MINT,
+                50_000_000_000,
+            )?,
+        ],
+    )]);
+
+    let legs = TransferMatcher::new(&transfers).match_trade(&trade("1-0"));
+
+    assert_eq!(legs.input.map(|leg| leg.idx.as_str()), Some("1-0"));
+    assert_eq!(legs.output.map(|leg| leg.idx.as_str()), Some("1-2"));
+    Ok(())
+}
+
+#[test]
+fn instruction_legs_match_on_their_accounts() -> Result<()> {
+    let transfers = HashMap::from([(
+        PUMP_SWAP.to_string(),
+        vec![
+            transfer("3-0", "user-a", "vault-a", SOL_MINT, 500_000_000)?,
+            transfer("3-1", "user-b", "vault-a", SOL_MINT, 500_000_000)?,
+            transfer("3-2", "vault-b", "user-token", TOKEN_MINT, 7_000_000)?,
+        ],
+    )]);
+    let matcher = TransferMatcher::new(&transfers);
+
+    let leg = matcher.find_leg(3, &Leg::between("user-b", "vault-a"));
+    assert_eq!(leg.map(|leg| leg.idx.as_str()), Some("3-1"));
+    assert!(matcher
+        .find_leg(3, &Leg::between("user-a", "vault-b"))
+        .is_none());
+    assert!(matcher
+        .find_leg(4, &Leg::between("user-b", "vault-a"))
+        .is_none());
+    assert!(matcher
+        .find_leg(3, &Leg::between("user-b", "vault-a").amount(400_000_000))
+        .is_none());
+
+    let payouts = matcher.find_legs(3, &Leg::of(TOKEN_MINT).to_owner("user"));
+    assert_eq!(payouts.len(), 1);
+    assert_eq!(matcher.find_legs(3, &Leg::default().to("vault-a")).len(), 2);
+    Ok(())
+}
@@ -0,0 +1,142 @@
+use solana_dex_parser::types::{FeeInfo, TokenInfo, TradeType};
+use solana_dex_parser::{
+    BlockContext, BlockParseResult, ParseResult, TradeInfo, TransactionStatus,
+};
+
+fn trade(idx: &str, trade_type: TradeType) -> TradeInfo {
+    TradeInfo {
+        trade_type,
+        signature: "sig".into(),
+        idx: idx.to_string(),
+        // Unrelated swaps, so the aggregate trade is the last of them.
+        input_token: TokenInfo {
+            mint: format!("in-{idx}"),
+            ..TokenInfo::default()
+        },
+        output_token: TokenInfo {
+            mint: format!("out-{idx}"),
+            ..TokenInfo::default()
+        },
+        ..TradeInfo::default()
+    }
+}
+
+#[test]
+fn merge_dedups_events_and_prefers_incoming_values() {
+    let mut intent = ParseResult {
+        signature: "sig".to_string(),
+        trades: vec![trade("1-0", TradeType::Buy), trade("10-0", TradeType::Buy)],
+        ..ParseResult::new()
+    };
+    intent.aggregate_trade = intent.trades.last().cloned();
+
+    let confirmed = ParseResult {
+        signature: "sig".to_string(),
+        slot: 42,
+        tx_status: TransactionStatus::Success,
+        trades: vec![
+            trade("2-0", TradeType::Sell),
+            trade("10-0", TradeType::Sell),
+        ],
+        ..ParseResult::new()
+    };
+    intent.merge(confirmed);
+
+    let order: Vec<&str> = intent.trades.iter().map(|t| t.idx.as_str()).collect();
+    assert_eq!(order, ["1-0", "2-0", "10-0"]);
+    assert_eq!(intent.trades[2].trade_type, TradeType::Sell);
+    assert_eq!(intent.slot, 42);
+    assert_eq!(intent.tx_status, TransactionStatus::Success);
+    assert_eq!(intent.aggregate_trade.as_ref(), intent.trades.last());
+}
+
+#[test]
+fn merge_recomputes_trade_summaries_and_keeps_failures() {
+    let with_fee = |idx: &str, raw: u64| TradeInfo {
+        fee: Some(FeeInfo {
+            mint: "fee-mint".to_string(),
+            amount_raw: raw.to_string(),
+            fee_type: Some("protocol".to_string()),
+            ..FeeInfo::default()
+        }),
+        ..trade(idx, TradeType::Buy)
+    };
+    let mut intent = ParseResult {
+        signature: "sig".to_string(),
+        trades: vec![with_fee("1-0", 100)],
+        ..ParseResult::new()
+    };
+    let mut confirmed = ParseResult {
+        signature: "sig".to_string(),
+        state: false,
+        trades: vec![with_fee("2-0", 50)],
+        ..ParseResult::new()
+    };
+    confirmed.fees.trade_fees = vec![FeeInfo {
+        amount_raw: "50".to_string(),
+        ..FeeInfo::default()
+    }];
+    intent.merge(confirmed);
+
+    assert_eq!(intent.fees.trade_fees.len(), 1);
+    assert_eq!(intent.fees.trade_fees[0].amount_raw, "150");
+    assert!(!intent.state);
+}
+
+#[test]
+fn block_merge_combines_transactions_by_signature() {
+    let tx = |signature: &str, slot: u64| ParseResult {
+        signature: signature.to_string(),
+        slot,
+        ..ParseResult::new()
+    };
+    let mut block = BlockParseResult {
+        slot: 7,
+        timestamp: None,
+        transactions: vec![tx("a", 0), tx("b", 0)],
+    };
+    block.merge(BlockParseResult {
+        slot: 7,
+        timestamp: Some(1_700_000_000),
+        transactions: vec![tx("b", 7), tx("c", 7)],
+    });
+
+    let signatures: Vec<&str> = block
+        .transactions
+        .iter()
+        .map(|tx| tx.signature.as_str())
+        .collect();
+    assert_eq!(signatures, ["a", "b", "c"]);
+    assert_eq!(block.transactions[1].slot, 7);
+    assert_eq!(block.timestamp, Some(1_700_000_000));
+}
+
+#[test]
+fn block_merge_restores_block_order_from_transaction_indexes() {
+    let tx = |signature: &str, transaction_index: usize| ParseResult {
+        signature: signature.to_string(),
+        slot: 7,
+        block_context: Some(BlockContext {
+            transaction_index,
+            ..BlockContext::default()
+        }),
+        ..ParseResult::new()
+    };
+    let mut block = BlockParseResult {
+        slot: 7,
+        timestamp: None,
+        transactions: vec![tx("a", 0), tx("c", 2)],
+    };
+    block.merge(BlockParseResult {
+        slot: 7,
+        timestamp: None,
+        transactions: vec![tx("b", 1), tx("d", 3)],
+    });
+
+    let signatures: Vec<&str> = block
+        .transactions
+        .iter()
+        .map(|tx| tx.signature.as_str())
+        .collect();
+    assert_eq!(signatures, ["a", "b", "c", "d"]);
+}
@@ -0,0 +1,170 @@
+use anyhow::Result;
+use serde_json::{json, Value};
+use solana_dex_parser::types::TradeType;
+use solana_dex_parser::{DexParser, SolanaTransaction};
+
+mod common;
+
+const METEORA_DBC: &str = "dbcij3LWUppWqq96dh6gJWwBifmcGfLSB5D4DuSMaqN";
+const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+const TOKEN_MINT: &str = "4wBqpZM9xaSheZzJSMawUKKwhdpChKbZ5eu5ky4Vigw";
+
+const SWAP: [u8; 8] = [248, 198, 158, 145, 225, 117, 135, 200];
+const INITIALIZE_VIRTUAL_POOL_WITH_SPL_TOKEN: [u8; 8] = [140, 85, 215, 176, 102, 54, 104, 79];
+const MIGRATE_METEORA_DAMM: [u8; 8] = [27, 1, 48, 22, 180, 63, 118, 217];
+
+fn accounts(named: &[(usize, &str)], len: usize) -> Vec<String> {
+    (0..len)
+        .map(|i| {
+            named
+                .iter()
+                .find(|(index, _)| *index == i)
+                .map(|(_, name)| name.to_string())
+                .unwrap_or_else(|| format!("account-{i}"))
+        })
+        .collect()
+}
+
+fn borsh_string(value: &str) -> Vec<u8> {
+    let mut bytes = (value.len() as u32).to_le_bytes().to_vec();
+    bytes.extend_from_slice(value.as_bytes());
+    bytes
+}
+
+fn encode(parts: &[&[u8]]) -> String {
+    bs58::encode(parts.concat()).into_string()
+}
+
+fn transfer(idx: &str, source: &str, destination: &str, mint: &str, amount: u64) -> Value {
+    common::transfer(METEORA_DBC, idx, source, destination, mint, amount, 9)
+}
+
+fn transaction(instructions: Value, transfers: Value) -> Result<SolanaTransaction> {
+    common::transaction(
+        "dbc",
+        json!({
+            "instructions": instructions,
+            "transfers": transfers,
+        }),
+    )
+}
+
+#[test]
+fn dbc_swap_into_quote_vault_is_a_buy() -> Result<()> {
+    let swap_accounts = accounts(
+        &[
+            (1, "dbc-config"),
+            (2, "virtual-pool"),
+            (3, "user-sol"),
+            (4, "user-token"),
+            (5, "base-vault"),
+            (6, "quote-vault"),
+            (7, TOKEN_MINT),
+            (8, SOL_MINT),
+            (9, "user"),
+        ],
+        14,
+    );
+    let tx = transaction(
+        json!([{
+            "programId": METEORA_DBC,
+            "accounts": swap_accounts,
+            "data": encode(&[&SWAP, &1_000_000_000u64.to_le_bytes(), &0u64.to_le_bytes()]),
+        }]),
+        json!([
+            transfer("0-0", "user-sol", "quote-vault", SOL_MINT, 1_000_000_000),
+            transfer(
+                "0-1",
+                "base-vault",
+                "user-token",
+                TOKEN_MINT,
+                35_000_000_000
+            ),
+        ]),
+    )?;
+
+    let result = DexParser::new().parse_all(tx, None);
+
+    assert_eq!(result.meme_events.len(), 1);
+    let buy = &result.meme_events[0];
+    assert_eq!(buy.event_type, TradeType::Buy);
+    assert_eq!(buy.protocol.as_deref(), Some("MeteoraDBC"));
+    assert_eq!(buy.user, "user");
+    assert_eq!(buy.base_mint, TOKEN_MINT);
+    assert_eq!(buy.quote_mint, SOL_MINT);
+    assert_eq!(buy.pool.as_deref(), Some("virtual-pool"));
+    assert_eq!(buy.platform_config.as_deref(), Some("dbc-config"));
+    assert_eq!(buy.idx, "0-0");
+    let input = buy.input_token.as_ref().expect("input token");
+    assert_eq!(input.mint, SOL_MINT);
+    assert_eq!(input.amount_raw, "1000000000");
+    let output = buy.output_token.as_ref().expect("output token");
+    assert_eq!(output.mint, TOKEN_MINT);
+    assert_eq!(output.amount_raw, "35000000000");
+    Ok(())
+}
+
+#[test]
+fn dbc_create_and_migrate_are_parsed() -> Result<()> {
+    let create_accounts = accounts(
+        &[
+            (0, "dbc-config"),
+            (2, "creator"),
+            (3, TOKEN_MINT),
+            (4, SOL_MINT),
+            (5, "virtual-pool"),
+        ],
+        16,
+    );
+    let create_data = encode(&[
+        &INITIALIZE_VIRTUAL_POOL_WITH_SPL_TOKEN,
+        &borsh_string("Curve Test"),
+        &borsh_string("CURVE"),
+        &borsh_string("https://example.com/curve.json"),
+    ]);
+    let migrate_accounts = accounts(
+        &[
+            (0, "virtual-pool"),
+            (2, "dbc-config"),
+            (4, "damm-pool"),
+            (7, TOKEN_MINT),
+            (8, SOL_MINT),
+        ],
+        30,
+    );
+    let tx = transaction(
+        json!([
+            { "programId": METEORA_DBC, "accounts": create_accounts, "data": create_data },
+            {
+                "programId": METEORA_DBC,
+                "accounts": migrate_accounts,
+                "data": encode(&[&MIGRATE_METEORA_DAMM]),
+            },
+        ]),
+        json!([]),
+    )?;
+
+    let result = DexParser::new().parse_all(tx, None);
+
+    assert_eq!(result.meme_events.len(), 2);
+    let create = &result.meme_events[0];
+    assert_eq!(create.event_type, TradeType::Create);
+    assert_eq!(create.name.as_deref(), Some("Curve Test"));
+    assert_eq!(create.symbol.as_deref(), Some("CURVE"));
+    assert_eq!(
+        create.uri.as_deref(),
+        Some("https://example.com/curve.json")
+    );
+    assert_eq!(create.creator.as_deref(), Some("creator"));
+    assert_eq!(create.base_mint, TOKEN_MINT);
+    assert_eq!(create.bonding_curve.as_deref(), Some("virtual-pool"));
+
+    let migrate = &result.meme_events[1];
+    assert_eq!(migrate.event_type, TradeType::Migrate);
+    assert_eq!(migrate.idx, "1-0");
+    assert_eq!(migrate.base_mint, TOKEN_MINT);
+    assert_eq!(migrate.bonding_curve.as_deref(), Some("virtual-pool"));
+    assert_eq!(migrate.pool.as_deref(), Some("damm-pool"));
+    assert_eq!(migrate.pool_dex.as_deref(), Some("MeteoraDAMM"));
+    Ok(())
+}
@@ -0,0 +1,151 @@
+use solana_dex_parser::{BlockContext, BlockParseResult, MevType, ParseResult, TradeInfo};
+
+mod common;
+
+const POOL: &str = "58oQChx4yWmvKdwLLZzBi4ChoCc2fqCUWBkwMihLYQo2";
+const OTHER_POOL: &str = "7qbRF6YsyGuLUVs6Y1q64bdVrfe4ZcUUz1JRdoVNUJnm";
+const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+const MEME_MINT: &str = "Meme111111111111111111111111111111111111111";
+
+fn buy(pool: &str, sol: u64, meme: u64) -> TradeInfo {
+    TradeInfo {
+        pool: vec![pool.to_string()],
+        slot: 300,
+        amm: Some("Pumpswap".to_string()),
+        input_token: common::token(SOL_MINT, sol, 9),
+        output_token: common::token(MEME_MINT, meme, 9),
+        ..TradeInfo::default()
+    }
+}
+
+fn sell(pool: &str, meme: u64, sol: u64) -> TradeInfo {
+    TradeInfo {
+        input_token: common::token(MEME_MINT, meme, 9),
+        output_token: common::token(SOL_MINT, sol, 9),
+        ..buy(pool, 0, 0)
+    }
+}
+
+fn tx(transaction_index: usize, signer: &str, trade: TradeInfo) -> ParseResult {
+    ParseResult {
+        slot: 300,
+        signature: format!("tx-{transaction_index}"),
+        signer: vec![signer.to_string()],
+        trades: vec![trade],
+        block_context: Some(BlockContext {
+            transaction_index,
+            ..BlockContext::default()
+        }),
+        ..ParseResult::new()
+    }
+}
+
+fn block(transactions: Vec<ParseResult>) -> BlockParseResult {
+    BlockParseResult {
+        slot: 300,
+        timestamp: None,
+        transactions,
+    }
+}
+
+#[test]
+fn frontrun_victim_and_backrun_on_one_pool_form_a_sandwich() {
+    // Gathered out of block order, with an unrelated trade on another pool in between.
+    let block = block(vec![
+        tx(3, "attacker", sell(POOL, 1_000_000, 1_100_000_000)),
+        tx(0, "attacker", buy(POOL, 1_000_000_000, 1_000_000)),
+        tx(2, "bystander", buy(OTHER_POOL, 5_000_000_000, 10)),
+        tx(1, "victim", buy(POOL, 3_000_000_000, 2_500_000)),
+    ]);
+
+    let events = block.detect_sandwiches();
+
+    assert_eq!(events.len(), 1);
+    let event = &events[0];
+    assert_eq!(event.mev_type, MevType::Sandwich);
+    assert_eq!(event.pool, POOL);
+    assert_eq!(event.amm.as_deref(), Some("Pumpswap"));
+    assert_eq!(event.attacker, "attacker");
+    assert_eq!(event.victim, "victim");
+    assert_eq!(
+        (
+            event.frontrun_signature.as_str(),
+            event.victim_signature.as_str(),
+            event.backrun_signature.as_str()
+        ),
+        ("tx-0", "tx-1", "tx-3")
+    );
+    assert_eq!(event.extracted_value.mint, SOL_MINT);
+    assert_eq!(event.extracted_value.amount_raw, "100000000");
+    assert_eq!(event.extracted_value.amount, 0.1);
+    let serialized = serde_json::to_value(event).unwrap();
+    assert_eq!(serialized["type"], "SANDWICH");
+}
+
+#[test]
+fn round_trips_without_a_victim_or_a_profit_are_not_sandwiches() {
+    let unprofitable = block(vec![
+        tx(0, "trader", buy(POOL, 1_000_000_000, 1_000_000)),
+        tx(1, "victim", buy(POOL, 3_000_000_000, 2_500_000)),
+        tx(2, "trader", sell(POOL, 1_000_000, 900_000_000)),
+    ]);
+    assert!(unprofitable.detect_sandwiches().is_empty());
+
+    let opposite_side = block(vec![
+        tx(0, "trader", buy(POOL, 1_000_000_000, 1_000_000)),
+        tx(1, "seller", sell(POOL, 2_000_000, 2_000_000_000)),
+        tx(2, "trader", sell(POOL, 1_000_000, 1_100_000_000)),
+    ]);
+    assert!(opposite_side.detect_sandwiches().is_empty());
+}
+
+#[test]
+fn attackers_are_the_trade_user_behind_a_relaying_signer() {
+    let by = |user: &str, trade: TradeInfo| TradeInfo {
+        user: Some(user.to_string()),
+        ..trade
+    };
+    // One relayer signs every transaction; the trades name their own users.
+    let block = block(vec![
+        tx(
+            0,
+            "relayer",
+            by("attacker", buy(POOL, 1_000_000_000, 1_000_000)),
+        ),
+        tx(
+            1,
+            "relayer",
+            by("victim", buy(POOL, 3_000_000_000, 2_500_000)),
+        ),
+        tx(
+            2,
+            "relayer",
+            by("attacker", sell(POOL, 1_000_000, 1_100_000_000)),
+        ),
+    ]);
+
+    let events = block.detect_sandwiches();
+
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].attacker, "attacker");
+    assert_eq!(events[0].victim, "victim");
+}
+
+#[test]
+fn a_backrun_closes_only_one_sandwich() {
+    // Two frontruns by the same attacker, closed by a single backrun.
+    let block = block(vec![
+        tx(0, "attacker", buy(POOL, 1_000_000_000, 1_000_000)),
+        tx(1, "victim", buy(POOL, 3_000_000_000, 2_500_000)),
+        tx(2, "attacker", buy(POOL, 1_000_000_000, 900_000)),
+        tx(3, "other-victim", buy(POOL, 2_000_000_000, 1_500_000)),
+        tx(4, "attacker", sell(POOL, 1_000_000, 1_100_000_000)),
+    ]);
+
+    let events = block.detect_sandwiches();
+
+    assert_eq!(events.len(), 2);
+    assert!(events
+        .iter()
+        .all(|event| event.frontrun_signature == "tx-0" && event.backrun_signature == "tx-4"));
+}
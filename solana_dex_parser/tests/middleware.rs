@@ -0,0 +1,142 @@
+use std::collections::HashMap;
+use std::fs;
+use std::sync::Arc;
+
+use anyhow::Result;
+use solana_dex_parser::{DexParser, ParseResult, ParserPool, SolanaTransaction};
+
+fn pumpfun_trade() -> Result<SolanaTransaction> {
+    let tx_data = fs::read_to_string("tests/fixtures/pumpfun_trade.json")?;
+    Ok(serde_json::from_str(&tx_data)?)
+}
+
+/// A vendor that drops `blockTime` on recent slots.
+fn fill_block_time(tx: &mut SolanaTransaction) {
+    if tx.block_time == 0 {
+        tx.block_time = 1_700_000_000;
+    }
+}
+
+fn redact_signers(tx: &mut SolanaTransaction) {
+    for signer in &mut tx.signers {
+        *signer = "redacted".to_string();
+    }
+}
+
+fn tag_redacted_signature(tx: &mut SolanaTransaction) {
+    if tx.signers.iter().all(|signer| signer == "redacted") {
+        tx.signature.push_str("-redacted");
+    }
+}
+
+#[test]
+fn middlewares_patch_the_transaction_before_parsing() -> Result<()> {
+    let mut tx = pumpfun_trade()?;
+    tx.block_time = 0;
+
+    let result = DexParser::new()
+        .with_middleware(Arc::new(fill_block_time))
+        .parse_all(tx, None);
+
+    assert_eq!(result.timestamp, 1_700_000_000);
+    assert_eq!(result.trades.len(), 1);
+    assert_eq!(result.trades[0].timestamp, 1_700_000_000);
+    Ok(())
+}
+
+#[test]
+fn middlewares_run_in_registration_order() -> Result<()> {
+    let parser = DexParser::new()
+        .with_middleware(Arc::new(redact_signers))
+        .with_middleware(Arc::new(tag_redacted_signature));
+
+    let result = parser.parse_all(pumpfun_trade()?, None);
+
+    assert_eq!(result.signer, ["redacted"]);
+    assert_eq!(result.signature, "pumpfun-signature-redacted");
+    Ok(())
+}
+
+/// Internal account tags kept outside the crate.
+fn tag_known_wallets(result: &mut ParseResult) {
+    for trade in &mut result.trades {
+        if trade.user.as_deref() == Some("5Pk716N113awdSaUDZEPZVi9Zs6hJmG5KCJtp5qQK3LB") {
+            trade.user = Some("desk:market-maker-1".to_string());
+        }
+    }
+}
+
+fn note_trade_count(result: &mut ParseResult) {
+    result.msg = Some(format!("{} trade(s)", result.trades.len()));
+}
+
+#[test]
+fn post_processors_see_the_parsed_result() -> Result<()> {
+    let parser = DexParser::new()
+        .with_middleware(Arc::new(redact_signers))
+        .with_post_processor(Arc::new(tag_known_wallets))
+        .with_post_processor(Arc::new(note_trade_count));
+
+    let result = parser.parse_all(pumpfun_trade()?, None);
+
+    assert_eq!(
+        result.trades[0].user.as_deref(),
+        Some("desk:market-maker-1")
+    );
+    assert_eq!(result.msg.as_deref(), Some("1 trade(s)"));
+    assert_eq!(result.signer, ["redacted"]);
+    Ok(())
+}
+
+#[test]
+fn middlewares_can_capture_their_own_state() -> Result<()> {
+    let aliases = HashMap::from([(
+        "pumpfun-user".to_string(),
+        "desk:market-maker-1".to_string(),
+    )]);
+    let parser = DexParser::new().with_middleware(Arc::new(move |tx: &mut SolanaTransaction| {
+        for signer in &mut tx.signers {
+            if let Some(alias) = aliases.get(signer) {
+                *signer = alias.clone();
+            }
+        }
+    }));
+
+    let result = parser.parse_all(pumpfun_trade()?, None);
+
+    assert_eq!(result.signer, ["desk:market-maker-1"]);
+    Ok(())
+}
+
+#[test]
+fn post_processors_can_capture_their_own_state() -> Result<()> {
+    let desks = HashMap::from([(
+        "5Pk716N113awdSaUDZEPZVi9Zs6hJmG5KCJtp5qQK3LB".to_string(),
+        "desk:market-maker-1".to_string(),
+    )]);
+    let parser = DexParser::new().with_post_processor(Arc::new(move |result: &mut ParseResult| {
+        for trade in &mut result.trades {
+            if let Some(desk) = trade.user.as_ref().and_then(|user| desks.get(user)) {
+                trade.user = Some(desk.clone());
+            }
+        }
+    }));
+
+    let result = parser.parse_all(pumpfun_trade()?, None);
+
+    assert_eq!(
+        result.trades[0].user.as_deref(),
+        Some("desk:market-maker-1")
+    );
+    Ok(())
+}
+
+#[test]
+fn pooled_parsers_run_post_processors() -> Result<()> {
+    let pool = ParserPool::new(2).with_post_processor(Arc::new(note_trade_count));
+
+    let result = pool.parse(pumpfun_trade()?);
+
+    assert_eq!(result.msg.as_deref(), Some("1 trade(s)"));
+    Ok(())
+}
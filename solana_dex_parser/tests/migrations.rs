@@ -0,0 +1,171 @@
+use anyhow::Result;
+use serde_json::json;
+use solana_dex_parser::types::TradeType;
+use solana_dex_parser::{DexParser, MigrationTracker, ParseResult, PoolEvent, SolanaTransaction};
+
+const METEORA_DBC: &str = "dbcij3LWUppWqq96dh6gJWwBifmcGfLSB5D4DuSMaqN";
+const METEORA_DAMM_V2: &str = "cpamdpZCGKUy5JxQXB4dcpGPiikHawvSWAd6mEn1sGG";
+const MOONSHOT: &str = "MoonCVVNZFSYkqNXP6bxHLPL6QQJiMagDL3qcqUQTrG";
+const RAYDIUM: &str = "675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8";
+const PUMP_FUN: &str = "6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P";
+const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+const PUMPFUN_MIGRATE: [u8; 8] = [155, 234, 231, 146, 236, 158, 162, 30];
+const PUMPFUN_MIGRATION_EVENT: [u8; 16] = [
+    228, 69, 165, 46, 81, 203, 154, 29, 189, 233, 93, 185, 92, 148, 234, 148,
+];
+// base58 of the anchor discriminators for `migration_damm_v2` and `migrate_funds`.
+const MIGRATION_DAMM_V2_DATA: &str = "TCqN7bA2Pd9";
+const MIGRATE_FUNDS_DATA: &str = "8B8g4BR2oTj";
+
+fn accounts(named: &[(usize, &str)], len: usize) -> Vec<String> {
+    (0..len)
+        .map(|i| {
+            named
+                .iter()
+                .find(|(index, _)| *index == i)
+                .map(|(_, name)| name.to_string())
+                .unwrap_or_else(|| format!("account-{i}"))
+        })
+        .collect()
+}
+
+#[test]
+fn dbc_migration_to_damm_v2_is_detected_in_one_transaction() -> Result<()> {
+    let tx: SolanaTransaction = serde_json::from_value(json!({
+        "slot": 100,
+        "signature": "dbc-migration",
+        "blockTime": 1_700_000_000,
+        "signers": ["migrator"],
+        "instructions": [{
+            "programId": METEORA_DBC,
+            "accounts": accounts(&[(0, "virtual-pool"), (4, "damm-pool"), (13, "MINT")], 16),
+            "data": MIGRATION_DAMM_V2_DATA,
+        }],
+        "innerInstructions": [{
+            "index": 0,
+            "instructions": [{
+                "programId": METEORA_DAMM_V2,
+                "accounts": ["damm-pool"],
+                "data": "",
+            }],
+        }],
+    }))?;
+
+    let result = DexParser::new().parse_all(tx, None);
+
+    assert_eq!(result.migrations.len(), 1);
+    let migration = &result.migrations[0];
+    assert_eq!(migration.source_protocol, "MeteoraDBC");
+    assert_eq!(migration.bonding_curve, "virtual-pool");
+    assert_eq!(migration.mint, "MINT");
+    assert_eq!(migration.pool.as_deref(), Some("damm-pool"));
+    assert_eq!(
+        migration.destination_program.as_deref(),
+        Some(METEORA_DAMM_V2)
+    );
+    assert_eq!(migration.idx, "0-0");
+    Ok(())
+}
+
+#[test]
+fn tracker_links_moonshot_migration_to_later_raydium_pool() -> Result<()> {
+    let tx: SolanaTransaction = serde_json::from_value(json!({
+        "slot": 200,
+        "signature": "moonshot-migration",
+        "blockTime": 1_700_000_000,
+        "signers": ["migrator"],
+        "instructions": [{
+            "programId": MOONSHOT,
+            "accounts": accounts(&[(2, "curve"), (5, "MINT")], 12),
+            "data": MIGRATE_FUNDS_DATA,
+        }],
+    }))?;
+    let migration_tx = DexParser::new().parse_all(tx, None);
+    assert_eq!(migration_tx.migrations.len(), 1);
+    assert!(migration_tx.migrations[0].pool.is_none());
+
+    let pool_tx = |slot: u64, signature: &str| ParseResult {
+        slot,
+        signature: signature.to_string(),
+        liquidities: vec![PoolEvent {
+            event_type: TradeType::Create,
+            program_id: Some(RAYDIUM.to_string()),
+            pool_id: "raydium-pool".to_string(),
+            token0_mint: Some(SOL_MINT.to_string()),
+            token0_amount: Some(79.0),
+            token1_mint: Some("MINT".to_string()),
+            token1_amount: Some(206_900_000.0),
+            ..PoolEvent::default()
+        }],
+        ..ParseResult::new()
+    };
+
+    let mut tracker = MigrationTracker::new(10);
+    assert!(tracker.observe(&migration_tx).is_empty());
+    let linked = tracker.observe(&pool_tx(205, "pool-create"));
+    assert_eq!(linked.len(), 1);
+    assert_eq!(linked[0].source_protocol, "Moonshot");
+    assert_eq!(linked[0].bonding_curve, "curve");
+    assert_eq!(linked[0].pool.as_deref(), Some("raydium-pool"));
+    assert_eq!(linked[0].pool_signature.as_deref(), Some("pool-create"));
+    assert_eq!(linked[0].destination_protocol.as_deref(), Some("Raydium"));
+    assert_eq!(linked[0].quote_mint.as_deref(), Some(SOL_MINT));
+    assert_eq!(linked[0].initial_base_reserve, Some(206_900_000.0));
+    assert_eq!(linked[0].initial_quote_reserve, Some(79.0));
+    assert!(tracker.pending().is_empty());
+
+    let mut expired = MigrationTracker::new(10);
+    expired.observe(&migration_tx);
+    assert!(expired.observe(&pool_tx(211, "late-pool")).is_empty());
+    assert!(expired.finish().is_empty());
+    Ok(())
+}
+
+#[test]
+fn pumpfun_migration_reports_the_pumpswap_seed_reserves() -> Result<()> {
+    let key = |byte: u8| bs58::encode([byte; 32]).into_string();
+    let (mint, curve, pool) = (key(2), key(3), key(9));
+    let event = [
+        &PUMPFUN_MIGRATION_EVENT[..],
+        &[1; 32],
+        &[2; 32],
+        &206_900_000_000_000u64.to_le_bytes(),
+        &84_990_359_051u64.to_le_bytes(),
+        &15_000_000u64.to_le_bytes(),
+        &[3; 32],
+        &1_700_000_000i64.to_le_bytes(),
+        &[9; 32],
+    ]
+    .concat();
+    let tx: SolanaTransaction = serde_json::from_value(json!({
+        "slot": 300,
+        "signature": "pumpfun-migration",
+        "blockTime": 1_700_000_000,
+        "signers": ["migrator"],
+        "instructions": [{
+            "programId": PUMP_FUN,
+            "accounts": accounts(&[(2, &mint), (3, &curve), (9, &pool)], 24),
+            "data": bs58::encode(PUMPFUN_MIGRATE).into_string(),
+        }],
+        "innerInstructions": [{
+            "index": 0,
+            "instructions": [{
+                "programId": PUMP_FUN,
+                "accounts": [],
+                "data": bs58::encode(event).into_string(),
+            }],
+        }],
+    }))?;
+
+    let result = DexParser::new().parse_all(tx, None);
+
+    assert_eq!(result.migrations.len(), 1);
+    let migration = &result.migrations[0];
+    assert_eq!(migration.source_protocol, "Pumpfun");
+    assert_eq!(migration.mint, mint);
+    assert_eq!(migration.pool.as_deref(), Some(pool.as_str()));
+    assert_eq!(migration.quote_mint.as_deref(), Some(SOL_MINT));
+    assert_eq!(migration.initial_base_reserve, Some(206_900_000.0));
+    assert_eq!(migration.initial_quote_reserve, Some(84.990359051));
+    Ok(())
+}
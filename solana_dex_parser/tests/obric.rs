@@ -0,0 +1,83 @@
+use anyhow::Result;
+use serde_json::{json, Value};
+use solana_dex_parser::{DexParser, SolanaTransaction};
+
+mod common;
+
+const OBRIC_V2: &str = "obriQD1zbpyLz95G5n7nJe6a4DPjpFwa5XYPoNm113y";
+const TOKEN_PROGRAM: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+const USDC_MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+
+const SWAP: [u8; 8] = [248, 198, 158, 145, 225, 117, 135, 200];
+
+fn transfer(
+    idx: &str,
+    source: &str,
+    destination: &str,
+    mint: &str,
+    amount: u64,
+    decimals: u8,
+) -> Value {
+    common::transfer(
+        TOKEN_PROGRAM,
+        idx,
+        source,
+        destination,
+        mint,
+        amount,
+        decimals,
+    )
+}
+
+fn obric_swap(is_x_to_y: bool, amount_in: u64) -> Value {
+    let mut data = SWAP.to_vec();
+    data.push(is_x_to_y as u8);
+    data.extend_from_slice(&amount_in.to_le_bytes());
+    data.extend_from_slice(&0u64.to_le_bytes());
+    json!({
+        "programId": OBRIC_V2,
+        "accounts": [
+            "pair",
+            SOL_MINT,
+            USDC_MINT,
+            "reserve-x",
+            "reserve-y",
+            "user-x",
+            "user-y",
+            "protocol-fee",
+            "x-price-feed",
+            "y-price-feed",
+            "user",
+            TOKEN_PROGRAM,
+        ],
+        "data": bs58::encode(data).into_string(),
+    })
+}
+
+#[test]
+fn y_to_x_swap_pays_out_of_the_x_reserve() -> Result<()> {
+    let tx: SolanaTransaction = serde_json::from_value(json!({
+        "slot": 900,
+        "signature": "obric",
+        "blockTime": 1_700_000_000,
+        "signers": ["user"],
+        "instructions": [obric_swap(false, 300_000_000)],
+        "transfers": [
+            transfer("0-0", "user-y", "reserve-y", USDC_MINT, 300_000_000, 6),
+            transfer("0-1", "reserve-x", "user-x", SOL_MINT, 2_000_000_000, 9),
+        ],
+    }))?;
+
+    let trades = DexParser::new().parse_trades(tx, None);
+
+    assert_eq!(trades.len(), 1);
+    let trade = &trades[0];
+    assert_eq!(trade.program_id.as_deref(), Some(OBRIC_V2));
+    assert_eq!(trade.amm.as_deref(), Some("ObricV2"));
+    assert_eq!(trade.user.as_deref(), Some("user"));
+    assert_eq!(trade.input_token.mint, USDC_MINT);
+    assert_eq!(trade.output_token.amount_raw, "2000000000");
+    assert_eq!(trade.oracle_price, Some(2.0 / 300.0));
+    Ok(())
+}
@@ -0,0 +1,245 @@
+use anyhow::Result;
+use serde_json::{json, Value};
+use solana_dex_parser::types::TradeType;
+use solana_dex_parser::{DexParser, ParseConfig, SolanaTransaction};
+
+mod common;
+
+const OKX: &str = "6m2CDdhRgxpH4WjvdzxAYbGxwdGUz5MziiL5jek2kBma";
+const RAYDIUM_CPMM: &str = "CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C";
+const TOKEN_PROGRAM: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+const USDC_MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+const TOKEN_MINT: &str = "4wBqpZM9xaSheZzJSMawUKKwhdpChKbZ5eu5ky4Vigw";
+
+const OKX_SWAP: [u8; 8] = [248, 198, 158, 145, 225, 117, 135, 200];
+const SWAP_BASE_INPUT: [u8; 8] = [143, 190, 90, 218, 196, 30, 51, 222];
+
+fn encode(parts: &[&[u8]]) -> String {
+    bs58::encode(parts.concat()).into_string()
+}
+
+/// CPMM hop from `input` into `output` through `pool`.
+fn hop(pool: &str, input: (&str, &str), output: (&str, &str)) -> Value {
+    let (input_account, input_mint) = input;
+    let (output_account, output_mint) = output;
+    let accounts: Vec<String> = (0..13)
+        .map(|i| match i {
+            0 => "okx-authority".to_string(),
+            3 => pool.to_string(),
+            4 => input_account.to_string(),
+            5 => output_account.to_string(),
+            6 => format!("{pool}-in"),
+            7 => format!("{pool}-out"),
+            10 => input_mint.to_string(),
+            11 => output_mint.to_string(),
+            _ => format!("account-{i}"),
+        })
+        .collect();
+    json!({
+        "programId": RAYDIUM_CPMM,
+        "accounts": accounts,
+        "data": encode(&[&SWAP_BASE_INPUT, &1u64.to_le_bytes(), &1u64.to_le_bytes()]),
+    })
+}
+
+fn token_transfer() -> Value {
+    json!({ "programId": TOKEN_PROGRAM, "accounts": [], "data": "" })
+}
+
+fn transfer(idx: &str, source: &str, destination: &str, mint: &str, amount: u64) -> Value {
+    let decimals = if mint == USDC_MINT { 6 } else { 9 };
+    common::transfer(
+        RAYDIUM_CPMM,
+        idx,
+        source,
+        destination,
+        mint,
+        amount,
+        decimals,
+    )
+}
+
+/// OKX `swap` of SOL from `user-sol` to TOKEN in `user-token`.
+fn okx_swap(amount_in: u64, expect_amount_out: u64, min_return: u64) -> Value {
+    json!({
+        "programId": OKX,
+        "accounts": ["user", "user-sol", "user-token", SOL_MINT, TOKEN_MINT],
+        "data": encode(&[
+            &OKX_SWAP,
+            &amount_in.to_le_bytes(),
+            &expect_amount_out.to_le_bytes(),
+            &min_return.to_le_bytes(),
+        ]),
+    })
+}
+
+/// SOL to TOKEN through USDC, two CPMM hops under one OKX `swap`.
+fn routed_swap() -> Result<SolanaTransaction> {
+    Ok(serde_json::from_value(json!({
+        "slot": 1_300,
+        "signature": "okx",
+        "blockTime": 1_700_000_000,
+        "signers": ["user"],
+        "instructions": [okx_swap(1_000_000_000, 50_000_000_000, 49_500_000_000)],
+        "innerInstructions": [{
+            "index": 0,
+            "instructions": [
+                hop("pool-a", ("user-sol", SOL_MINT), ("okx-usdc", USDC_MINT)),
+                token_transfer(),
+                token_transfer(),
+                hop("pool-b", ("okx-usdc", USDC_MINT), ("user-token", TOKEN_MINT)),
+                token_transfer(),
+                token_transfer(),
+            ],
+        }],
+        "transfers": [
+            transfer("0-1", "user-sol", "pool-a-in", SOL_MINT, 1_000_000_000),
+            transfer("0-2", "pool-a-out", "okx-usdc", USDC_MINT, 150_000_000),
+            transfer("0-4", "okx-usdc", "pool-b-in", USDC_MINT, 150_000_000),
+            transfer("0-5", "pool-b-out", "user-token", TOKEN_MINT, 49_800_000_000),
+        ],
+    }))?)
+}
+
+#[test]
+fn okx_route_becomes_one_aggregate_trade() -> Result<()> {
+    let config = ParseConfig {
+        aggregate_trades: true,
+        ..ParseConfig::default()
+    };
+    let result = DexParser::new().parse_all(routed_swap()?, Some(config));
+
+    let aggregate = result.aggregate_trade.as_ref().expect("aggregate trade");
+    assert_eq!(aggregate.program_id.as_deref(), Some(OKX));
+    assert_eq!(aggregate.route.as_deref(), Some("OKX"));
+    assert_eq!(aggregate.trade_type, TradeType::Buy);
+    assert_eq!(aggregate.user.as_deref(), Some("user"));
+    assert_eq!(aggregate.input_token.mint, SOL_MINT);
+    assert_eq!(aggregate.input_token.amount, 1.0);
+    assert_eq!(aggregate.output_token.mint, TOKEN_MINT);
+    assert_eq!(aggregate.output_token.amount_raw, "49800000000");
+    // 49,800 executed against a 49,500 minimum.
+    assert_eq!(aggregate.slippage_bps, Some(60));
+    assert_eq!(
+        aggregate.amms.as_deref(),
+        Some(&["RaydiumCPMM".to_string(), "RaydiumCPMM".to_string()][..])
+    );
+    Ok(())
+}
+
+#[test]
+fn okx_hops_stay_available_and_are_attributed_to_the_router() -> Result<()> {
+    let trades = DexParser::new().parse_trades(routed_swap()?, None);
+
+    let hops: Vec<_> = trades
+        .iter()
+        .filter(|trade| trade.program_id.as_deref() == Some(RAYDIUM_CPMM))
+        .collect();
+    assert_eq!(hops.len(), 2);
+    assert!(hops.iter().all(|hop| hop.route.as_deref() == Some("OKX")));
+    assert_eq!(hops[0].pool, ["pool-a"]);
+    assert_eq!(hops[1].idx, "0-3");
+    assert_eq!(hops[1].output_token.mint, TOKEN_MINT);
+    assert_eq!(trades.len(), 3);
+    Ok(())
+}
+
+#[test]
+fn okx_routes_invoked_by_one_instruction_keep_their_own_transfers() -> Result<()> {
+    let at = |mut instruction: Value, height: u32| {
+        instruction["stackHeight"] = json!(height);
+        instruction
+    };
+    let tx: SolanaTransaction = serde_json::from_value(json!({
+        "slot": 1_300,
+        "signature": "okx-twice",
+        "blockTime": 1_700_000_000,
+        "signers": ["user"],
+        "instructions": [{ "programId": "bot-program", "accounts": [], "data": "" }],
+        "innerInstructions": [{
+            "index": 0,
+            "instructions": [
+                at(okx_swap(1_000_000_000, 50_000_000_000, 49_500_000_000), 2),
+                at(hop("pool-a", ("user-sol", SOL_MINT), ("user-token", TOKEN_MINT)), 3),
+                at(token_transfer(), 4),
+                at(token_transfer(), 4),
+                at(okx_swap(500_000_000, 25_000_000_000, 24_000_000_000), 2),
+                at(hop("pool-b", ("user-sol", SOL_MINT), ("user-token", TOKEN_MINT)), 3),
+                at(token_transfer(), 4),
+                at(token_transfer(), 4),
+            ],
+        }],
+        "transfers": [
+            transfer("0-2", "user-sol", "pool-a-in", SOL_MINT, 1_000_000_000),
+            transfer("0-3", "pool-a-out", "user-token", TOKEN_MINT, 49_800_000_000),
+            transfer("0-6", "user-sol", "pool-b-in", SOL_MINT, 500_000_000),
+            transfer("0-7", "pool-b-out", "user-token", TOKEN_MINT, 24_800_000_000),
+        ],
+    }))?;
+
+    let trades = DexParser::new().parse_trades(tx, None);
+
+    let routes: Vec<_> = trades
+        .iter()
+        .filter(|trade| trade.program_id.as_deref() == Some(OKX))
+        .collect();
+    assert_eq!(routes.len(), 2);
+    assert_eq!(routes[0].input_token.amount_raw, "1000000000");
+    assert_eq!(routes[0].output_token.amount_raw, "49800000000");
+    assert_eq!(routes[0].idx, "0-3");
+    assert_eq!(routes[1].input_token.amount_raw, "500000000");
+    assert_eq!(routes[1].output_token.amount_raw, "24800000000");
+    assert_eq!(routes[1].idx, "0-7");
+    assert!(routes
+        .iter()
+        .all(|route| route.amms.as_deref() == Some(&["RaydiumCPMM".to_string()][..])));
+    Ok(())
+}
+
+#[test]
+fn direct_swaps_beside_a_route_are_not_attributed_to_it() -> Result<()> {
+    let tx: SolanaTransaction = serde_json::from_value(json!({
+        "slot": 1_300,
+        "signature": "okx-and-direct",
+        "blockTime": 1_700_000_000,
+        "signers": ["user"],
+        "instructions": [
+            okx_swap(1_000_000_000, 50_000_000_000, 49_500_000_000),
+            hop("pool-c", ("user-usdc", USDC_MINT), ("user-sol", SOL_MINT)),
+        ],
+        "innerInstructions": [
+            {
+                "index": 0,
+                "instructions": [
+                    hop("pool-a", ("user-sol", SOL_MINT), ("user-token", TOKEN_MINT)),
+                    token_transfer(),
+                    token_transfer(),
+                ],
+            },
+            {
+                "index": 1,
+                "instructions": [token_transfer(), token_transfer()],
+            },
+        ],
+        "transfers": [
+            transfer("0-1", "user-sol", "pool-a-in", SOL_MINT, 1_000_000_000),
+            transfer("0-2", "pool-a-out", "user-token", TOKEN_MINT, 49_800_000_000),
+            transfer("1-0", "user-usdc", "pool-c-in", USDC_MINT, 150_000_000),
+            transfer("1-1", "pool-c-out", "user-sol", SOL_MINT, 1_000_000_000),
+        ],
+    }))?;
+
+    let trades = DexParser::new().parse_trades(tx, None);
+
+    let hops: Vec<_> = trades
+        .iter()
+        .filter(|trade| trade.program_id.as_deref() == Some(RAYDIUM_CPMM))
+        .collect();
+    assert_eq!(hops.len(), 2);
+    assert_eq!(hops[0].pool, ["pool-a"]);
+    assert_eq!(hops[0].route.as_deref(), Some("OKX"));
+    assert_eq!(hops[1].pool, ["pool-c"]);
+    assert_eq!(hops[1].route, None);
+    Ok(())
+}
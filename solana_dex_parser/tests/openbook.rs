@@ -0,0 +1,193 @@
+use anyhow::Result;
+use serde_json::{json, Value};
+use solana_dex_parser::types::TradeType;
+use solana_dex_parser::{DexParser, SolanaTransaction};
+
+mod common;
+
+const OPENBOOK_V2: &str = "opnb2LAfJYbRMAHHvqjCwQxanZn7ReEHp1k81EohpZb";
+const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+const USDC_MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+
+const CREATE_MARKET: [u8; 8] = [103, 226, 97, 235, 200, 188, 251, 254];
+const PLACE_ORDER: [u8; 8] = [51, 194, 155, 175, 109, 130, 96, 106];
+const PLACE_TAKE_ORDER: [u8; 8] = [3, 44, 71, 3, 26, 199, 203, 85];
+const SETTLE_FUNDS: [u8; 8] = [238, 64, 163, 96, 75, 171, 16, 33];
+
+const BID: u8 = 0;
+const ASK: u8 = 1;
+
+fn accounts(named: &[(usize, &str)], len: usize) -> Vec<String> {
+    (0..len)
+        .map(|i| {
+            named
+                .iter()
+                .find(|(index, _)| *index == i)
+                .map(|(_, name)| name.to_string())
+                .unwrap_or_else(|| format!("account-{i}"))
+        })
+        .collect()
+}
+
+fn order_data(discriminator: [u8; 8], side: u8) -> String {
+    let mut data = discriminator.to_vec();
+    data.push(side);
+    data.extend_from_slice(&[0; 24]); // price_lots, max_base_lots, max_quote_lots
+    bs58::encode(data).into_string()
+}
+
+fn transfer(idx: &str, source: &str, destination: &str, mint: &str, amount: u64) -> Value {
+    let decimals = if mint == USDC_MINT { 6 } else { 9 };
+    common::transfer(
+        OPENBOOK_V2,
+        idx,
+        source,
+        destination,
+        mint,
+        amount,
+        decimals,
+    )
+}
+
+fn transaction(instructions: Value, transfers: Value) -> Result<SolanaTransaction> {
+    common::transaction(
+        "openbook",
+        json!({
+            "instructions": instructions,
+            "transfers": transfers,
+        }),
+    )
+}
+
+#[test]
+fn place_take_order_bid_is_a_buy_of_base() -> Result<()> {
+    let take_accounts = accounts(
+        &[
+            (0, "user"),
+            (2, "market"),
+            (6, "base-vault"),
+            (7, "quote-vault"),
+            (9, "user-sol"),
+            (10, "user-usdc"),
+        ],
+        16,
+    );
+    let tx = transaction(
+        json!([{
+            "programId": OPENBOOK_V2,
+            "accounts": take_accounts,
+            "data": order_data(PLACE_TAKE_ORDER, BID),
+        }]),
+        json!([
+            transfer("0-0", "user-usdc", "quote-vault", USDC_MINT, 150_000_000),
+            transfer("0-1", "base-vault", "user-sol", SOL_MINT, 1_000_000_000),
+        ]),
+    )?;
+
+    let trades = DexParser::new().parse_trades(tx, None);
+
+    assert_eq!(trades.len(), 1);
+    let trade = &trades[0];
+    assert_eq!(trade.trade_type, TradeType::Buy);
+    assert_eq!(trade.amm.as_deref(), Some("OpenBookV2"));
+    assert_eq!(trade.pool, vec!["market".to_string()]);
+    assert_eq!(trade.user.as_deref(), Some("user"));
+    assert_eq!(trade.input_token.mint, USDC_MINT);
+    assert_eq!(trade.input_token.amount, 150.0);
+    assert_eq!(trade.output_token.mint, SOL_MINT);
+    assert_eq!(trade.output_token.amount_raw, "1000000000");
+    Ok(())
+}
+
+#[test]
+fn settled_ask_nets_out_the_refund() -> Result<()> {
+    let place_accounts = accounts(
+        &[
+            (0, "user"),
+            (1, "open-orders"),
+            (3, "user-sol"),
+            (4, "market"),
+            (8, "base-vault"),
+        ],
+        12,
+    );
+    let settle_accounts = accounts(
+        &[
+            (0, "user"),
+            (2, "open-orders"),
+            (3, "market"),
+            (5, "base-vault"),
+            (6, "quote-vault"),
+            (7, "user-sol"),
+            (8, "user-usdc"),
+        ],
+        12,
+    );
+    let tx = transaction(
+        json!([
+            {
+                "programId": OPENBOOK_V2,
+                "accounts": place_accounts,
+                "data": order_data(PLACE_ORDER, ASK),
+            },
+            {
+                "programId": OPENBOOK_V2,
+                "accounts": settle_accounts,
+                "data": bs58::encode(SETTLE_FUNDS).into_string(),
+            },
+        ]),
+        json!([
+            transfer("0-0", "user-sol", "base-vault", SOL_MINT, 2_000_000_000),
+            transfer("1-0", "base-vault", "user-sol", SOL_MINT, 500_000_000),
+            transfer("1-1", "quote-vault", "user-usdc", USDC_MINT, 225_000_000),
+        ]),
+    )?;
+
+    let trades = DexParser::new().parse_trades(tx, None);
+
+    assert_eq!(trades.len(), 1);
+    let trade = &trades[0];
+    assert_eq!(trade.trade_type, TradeType::Sell);
+    assert_eq!(trade.idx, "0-0");
+    assert_eq!(trade.input_token.mint, SOL_MINT);
+    assert_eq!(trade.input_token.amount_raw, "1500000000");
+    assert_eq!(trade.input_token.amount, 1.5);
+    assert_eq!(trade.output_token.mint, USDC_MINT);
+    assert_eq!(trade.output_token.amount, 225.0);
+    Ok(())
+}
+
+#[test]
+fn create_market_becomes_pool_event() -> Result<()> {
+    let create_accounts = accounts(
+        &[(0, "market"), (5, "user"), (8, SOL_MINT), (9, USDC_MINT)],
+        21,
+    );
+    let mut data = CREATE_MARKET.to_vec();
+    data.extend_from_slice(&7u32.to_le_bytes());
+    data.extend_from_slice(b"SOL-USD");
+    data.extend_from_slice(&[0; 12]); // oracle_config
+    for value in [1i64, 1_000_000, -200, 400, 0] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    let tx = transaction(
+        json!([{
+            "programId": OPENBOOK_V2,
+            "accounts": create_accounts,
+            "data": bs58::encode(data).into_string(),
+        }]),
+        json!([]),
+    )?;
+
+    let result = DexParser::new().parse_all(tx, None);
+
+    assert!(result.trades.is_empty());
+    assert_eq!(result.liquidities.len(), 1);
+    let market = &result.liquidities[0];
+    assert_eq!(market.event_type, TradeType::Create);
+    assert_eq!(market.pool_id, "market");
+    assert_eq!(market.user, "user");
+    assert_eq!(market.token0_mint.as_deref(), Some(SOL_MINT));
+    assert_eq!(market.token1_mint.as_deref(), Some(USDC_MINT));
+    Ok(())
+}
@@ -0,0 +1,287 @@
+use anyhow::Result;
+use serde_json::{json, Value};
+use solana_dex_parser::types::TradeType;
+use solana_dex_parser::{DexParser, SolanaTransaction};
+
+mod common;
+
+const WHIRLPOOL: &str = "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc";
+const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+const USDC_MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+
+const SWAP: [u8; 8] = [248, 198, 158, 145, 225, 117, 135, 200];
+const OPEN_POSITION_WITH_METADATA: [u8; 8] = [242, 29, 134, 48, 58, 110, 14, 60];
+const INCREASE_LIQUIDITY: [u8; 8] = [46, 156, 243, 118, 13, 205, 251, 178];
+const COLLECT_FEES: [u8; 8] = [164, 152, 207, 99, 30, 186, 19, 182];
+const CLOSE_POSITION: [u8; 8] = [123, 134, 81, 0, 49, 68, 98, 98];
+const TRADED: [u8; 16] = [
+    228, 69, 165, 46, 81, 203, 154, 29, 225, 202, 73, 175, 147, 43, 160, 150,
+];
+
+fn pool() -> String {
+    bs58::encode([9u8; 32]).into_string()
+}
+
+fn swap_accounts() -> Vec<String> {
+    let pool = pool();
+    [
+        "token-program",
+        "user",
+        pool.as_str(),
+        "user-sol",
+        "vault-sol",
+        "user-usdc",
+        "vault-usdc",
+        "tick-array-0",
+        "tick-array-1",
+        "tick-array-2",
+        "oracle",
+    ]
+    .iter()
+    .map(|account| account.to_string())
+    .collect()
+}
+
+fn swap_data(amount: u64, a_to_b: bool) -> String {
+    let mut data = SWAP.to_vec();
+    data.extend_from_slice(&amount.to_le_bytes());
+    data.extend_from_slice(&0u64.to_le_bytes());
+    data.extend_from_slice(&0u128.to_le_bytes());
+    data.extend_from_slice(&[1, a_to_b as u8]);
+    bs58::encode(data).into_string()
+}
+
+fn token_balance(account: &str, mint: &str, decimals: u8) -> Value {
+    json!({
+        "account": account,
+        "mint": mint,
+        "owner": "whirlpool",
+        "uiTokenAmount": { "amount": "0", "decimals": decimals },
+    })
+}
+
+fn transfer(
+    idx: &str,
+    source: &str,
+    destination: &str,
+    mint: &str,
+    amount: u64,
+    decimals: u8,
+) -> Value {
+    common::transfer(WHIRLPOOL, idx, source, destination, mint, amount, decimals)
+}
+
+fn transaction(instructions: Value, inner: Value, transfers: Value) -> Result<SolanaTransaction> {
+    common::transaction(
+        "whirlpool",
+        json!({
+            "instructions": instructions,
+            "innerInstructions": inner,
+            "transfers": transfers,
+            "postTokenBalances": [
+                token_balance("vault-sol", SOL_MINT, 9),
+                token_balance("vault-usdc", USDC_MINT, 6),
+            ],
+        }),
+    )
+}
+
+#[test]
+fn whirlpool_swap_uses_traded_event_and_vault_mints() -> Result<()> {
+    let mut event = TRADED.to_vec();
+    event.extend_from_slice(&[9u8; 32]);
+    event.push(1); // a_to_b
+    event.extend_from_slice(&0u128.to_le_bytes());
+    // sqrt(0.15) in Q64.64: 150 USDC per SOL once decimals are applied.
+    event.extend_from_slice(&7_144_393_258_922_745_856u128.to_le_bytes());
+    for amount in [2_000_000_000u64, 300_000_000, 0, 0, 6_000_000, 1_000_000] {
+        event.extend_from_slice(&amount.to_le_bytes());
+    }
+    let tx = transaction(
+        json!([{
+            "programId": WHIRLPOOL,
+            "accounts": swap_accounts(),
+            "data": swap_data(2_000_000_000, true),
+        }]),
+        json!([{
+            "index": 0,
+            "instructions": [{
+                "programId": WHIRLPOOL,
+                "accounts": [],
+                "data": bs58::encode(event).into_string(),
+            }],
+        }]),
+        json!([]),
+    )?;
+
+    let trades = DexParser::new().parse_trades(tx, None);
+
+    assert_eq!(trades.len(), 1);
+    let trade = &trades[0];
+    assert_eq!(trade.trade_type, TradeType::Buy);
+    assert_eq!(trade.amm.as_deref(), Some("Orca"));
+    assert_eq!(trade.pool, [pool()]);
+    assert_eq!(trade.input_token.mint, SOL_MINT);
+    assert_eq!(trade.input_token.amount, 2.0);
+    assert_eq!(trade.input_token.source.as_deref(), Some("user-sol"));
+    assert_eq!(trade.output_token.mint, USDC_MINT);
+    assert_eq!(trade.output_token.amount, 300.0);
+    assert_eq!(trade.fees.len(), 2);
+    assert_eq!(trade.fees[0].fee_type.as_deref(), Some("lp"));
+    assert_eq!(trade.fees[0].amount_raw, "6000000");
+    assert_eq!(trade.fees[1].mint, SOL_MINT);
+    let price_after = trade.price_after.expect("price after the swap");
+    assert!((price_after - 150.0).abs() < 1e-6, "{price_after}");
+    let reserves = trade.pool_reserves.as_ref().expect("vault reserves");
+    assert_eq!(reserves.base_mint, SOL_MINT);
+    assert_eq!(reserves.quote_mint, USDC_MINT);
+    assert_eq!(reserves.quote_decimals, 6);
+    Ok(())
+}
+
+#[test]
+fn whirlpool_swap_without_event_falls_back_to_vault_transfers() -> Result<()> {
+    let tx = transaction(
+        json!([{
+            "programId": WHIRLPOOL,
+            "accounts": swap_accounts(),
+            "data": swap_data(150_000_000, false),
+        }]),
+        json!([]),
+        json!([
+            transfer("0-0", "user-usdc", "vault-usdc", USDC_MINT, 150_000_000, 6),
+            transfer("0-1", "vault-sol", "user-sol", SOL_MINT, 1_000_000_000, 9),
+        ]),
+    )?;
+
+    let trades = DexParser::new().parse_trades(tx, None);
+
+    assert_eq!(trades.len(), 1);
+    let trade = &trades[0];
+    assert_eq!(trade.trade_type, TradeType::Sell);
+    assert_eq!(trade.input_token.mint, USDC_MINT);
+    assert_eq!(trade.input_token.amount_raw, "150000000");
+    assert_eq!(trade.output_token.mint, SOL_MINT);
+    assert!(trade.fees.is_empty());
+    Ok(())
+}
+
+#[test]
+fn whirlpool_position_lifecycle_emits_pool_events() -> Result<()> {
+    let instruction = |discriminator: &[u8], args: &[&[u8]], accounts: Vec<String>| {
+        let mut data = discriminator.to_vec();
+        args.iter().for_each(|arg| data.extend_from_slice(arg));
+        json!({
+            "programId": WHIRLPOOL,
+            "accounts": accounts,
+            "data": bs58::encode(data).into_string(),
+        })
+    };
+    let accounts = |named: &[(usize, &str)], len: usize| {
+        let mut accounts: Vec<String> = (0..len).map(|i| format!("account-{i}")).collect();
+        for (index, name) in named {
+            accounts[*index] = name.to_string();
+        }
+        accounts
+    };
+    let pool = pool();
+    let modify_accounts = accounts(
+        &[
+            (0, pool.as_str()),
+            (2, "user"),
+            (3, "position"),
+            (7, "vault-sol"),
+            (8, "vault-usdc"),
+            (9, "tick-lower"),
+            (10, "tick-upper"),
+        ],
+        11,
+    );
+    let tx = transaction(
+        json!([
+            instruction(
+                &OPEN_POSITION_WITH_METADATA,
+                &[&[254, 255], &(-128i32).to_le_bytes(), &256i32.to_le_bytes()],
+                accounts(
+                    &[
+                        (1, "user"),
+                        (2, "position"),
+                        (3, "position-mint"),
+                        (6, pool.as_str())
+                    ],
+                    13,
+                ),
+            ),
+            instruction(
+                &INCREASE_LIQUIDITY,
+                &[
+                    &5_000u128.to_le_bytes(),
+                    &u64::MAX.to_le_bytes(),
+                    &u64::MAX.to_le_bytes(),
+                ],
+                modify_accounts,
+            ),
+            instruction(
+                &COLLECT_FEES,
+                &[],
+                accounts(
+                    &[
+                        (0, pool.as_str()),
+                        (1, "user"),
+                        (2, "position"),
+                        (5, "vault-sol"),
+                        (7, "vault-usdc"),
+                    ],
+                    9,
+                ),
+            ),
+            instruction(
+                &CLOSE_POSITION,
+                &[],
+                accounts(&[(0, "user"), (2, "position"), (3, "position-mint")], 6),
+            ),
+        ]),
+        json!([]),
+        json!([
+            transfer("1-0", "user-sol", "vault-sol", SOL_MINT, 1_000_000_000, 9),
+            transfer("1-1", "user-usdc", "vault-usdc", USDC_MINT, 150_000_000, 6),
+            transfer("2-0", "vault-sol", "user-sol", SOL_MINT, 2_000_000, 9),
+        ]),
+    )?;
+
+    let events = DexParser::new().parse_liquidity(tx, None);
+
+    let types: Vec<TradeType> = events
+        .iter()
+        .map(|event| event.event_type.clone())
+        .collect();
+    assert_eq!(
+        types,
+        [
+            TradeType::Add,
+            TradeType::Add,
+            TradeType::FeeClaim,
+            TradeType::Remove
+        ]
+    );
+    assert!(events.iter().all(|event| event.pool_id == pool));
+
+    let open = events[0].position.as_ref().expect("position info");
+    assert_eq!(open.nft_mint.as_deref(), Some("position-mint"));
+    assert_eq!(open.tick_lower_index, Some(-128));
+    assert_eq!(open.tick_upper_index, Some(256));
+
+    let increase = &events[1];
+    assert_eq!(increase.user, "user");
+    assert_eq!(increase.token0_mint.as_deref(), Some(SOL_MINT));
+    assert_eq!(increase.token0_amount_raw.as_deref(), Some("1000000000"));
+    assert_eq!(increase.token1_amount_raw.as_deref(), Some("150000000"));
+    let position = increase.position.as_ref().expect("position info");
+    assert_eq!(position.liquidity.as_deref(), Some("5000"));
+
+    let collect = &events[2];
+    assert_eq!(collect.token0_amount_raw.as_deref(), Some("2000000"));
+    assert_eq!(collect.token1_amount_raw, None);
+    assert_eq!(collect.token1_mint.as_deref(), Some(USDC_MINT));
+    Ok(())
+}
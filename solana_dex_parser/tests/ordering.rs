@@ -0,0 +1,119 @@
+use anyhow::Result;
+use serde_json::{json, Value};
+use solana_dex_parser::{DexParser, SolanaTransaction};
+
+const PUMP_FUN: &str = "6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P";
+const RAYDIUM_CPMM: &str = "CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C";
+const COMPUTE_BUDGET: &str = "ComputeBudget111111111111111111111111111111";
+const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+const TOKEN_MINT: &str = "4wBqpZM9xaSheZzJSMawUKKwhdpChKbZ5eu5ky4Vigw";
+
+const SWAP_BASE_INPUT: [u8; 8] = [143, 190, 90, 218, 196, 30, 51, 222];
+/// Pumpfun trade event from `tests/fixtures/pumpfun_trade.json`.
+const PUMPFUN_TRADE_EVENT: &str = "3Qf1fH3KwcWxhgT6SC3VMtDeNaxSW85hCjBGVvaJkXnzjtx2MGKDyRAMFqE8VRTLLAZ7GDNL4M49Rb43uBtyC6vcfmEpXnRbXW9F7twGAv67CsyY3nayXxA4t7vpUtWM4mCFGPYy1VYzHzm39fbPDuYmwKRWiRs7k7inaB";
+
+fn cpmm_swap(pool: &str) -> Value {
+    let accounts: Vec<String> = (0..13)
+        .map(|i| match i {
+            0 => "user".to_string(),
+            3 => pool.to_string(),
+            4 => format!("{pool}-user-token"),
+            5 => format!("{pool}-user-sol"),
+            6 => format!("{pool}-vault-token"),
+            7 => format!("{pool}-vault-sol"),
+            10 => TOKEN_MINT.to_string(),
+            11 => SOL_MINT.to_string(),
+            _ => format!("account-{i}"),
+        })
+        .collect();
+    let mut data = SWAP_BASE_INPUT.to_vec();
+    data.extend_from_slice(&5_000_000u64.to_le_bytes());
+    data.extend_from_slice(&1u64.to_le_bytes());
+    json!({
+        "programId": RAYDIUM_CPMM,
+        "accounts": accounts,
+        "data": bs58::encode(data).into_string(),
+    })
+}
+
+fn cpmm_transfers(outer: usize, pool: &str) -> Vec<Value> {
+    let transfer = |inner: usize, source: String, destination: String, mint: &str, amount: u64| {
+        let decimals = if mint == SOL_MINT { 9 } else { 6 };
+        json!({
+            "type": "transferChecked",
+            "programId": RAYDIUM_CPMM,
+            "info": {
+                "destination": destination,
+                "mint": mint,
+                "source": source,
+                "tokenAmount": {
+                    "amount": amount.to_string(),
+                    "uiAmount": amount as f64 / 10f64.powi(decimals),
+                    "decimals": decimals,
+                },
+            },
+            "idx": format!("{outer}-{inner}"),
+            "timestamp": 1_700_000_000,
+            "signature": "mixed",
+            "isFee": false,
+        })
+    };
+    vec![
+        transfer(
+            0,
+            format!("{pool}-user-token"),
+            format!("{pool}-vault-token"),
+            TOKEN_MINT,
+            5_000_000,
+        ),
+        transfer(
+            1,
+            format!("{pool}-vault-sol"),
+            format!("{pool}-user-sol"),
+            SOL_MINT,
+            250_000_000,
+        ),
+    ]
+}
+
+#[test]
+fn trades_from_different_protocols_follow_instruction_order() -> Result<()> {
+    // Raydium at 0 and 10 around a Pumpfun buy at 2: per-program parsing yields the
+    // Raydium trades first, and a string sort of idx would put "10-0" before "2-0".
+    let mut instructions: Vec<Value> = (0..11)
+        .map(|_| json!({ "programId": COMPUTE_BUDGET, "accounts": [], "data": "" }))
+        .collect();
+    instructions[0] = cpmm_swap("pool-a");
+    instructions[2] = json!({
+        "programId": PUMP_FUN,
+        "accounts": [],
+        "data": PUMPFUN_TRADE_EVENT,
+    });
+    instructions[10] = cpmm_swap("pool-b");
+    let mut transfers = cpmm_transfers(0, "pool-a");
+    transfers.extend(cpmm_transfers(10, "pool-b"));
+    let tx: SolanaTransaction = serde_json::from_value(json!({
+        "slot": 800,
+        "signature": "mixed",
+        "blockTime": 1_700_000_000,
+        "signers": ["user"],
+        "instructions": instructions,
+        "transfers": transfers,
+    }))?;
+
+    let trades = DexParser::new().parse_trades(tx, None);
+
+    let order: Vec<(&str, Option<&str>)> = trades
+        .iter()
+        .map(|trade| (trade.idx.as_str(), trade.program_id.as_deref()))
+        .collect();
+    assert_eq!(
+        order,
+        [
+            ("0-0", Some(RAYDIUM_CPMM)),
+            ("2-0", Some(PUMP_FUN)),
+            ("10-0", Some(RAYDIUM_CPMM)),
+        ]
+    );
+    Ok(())
+}
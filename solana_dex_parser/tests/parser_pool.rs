@@ -0,0 +1,120 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use anyhow::Result;
+use serde_json::{json, Value};
+use solana_dex_parser::{ParseConfig, ParserPool, PriceProvider, SolanaTransaction};
+
+mod common;
+
+const RAYDIUM_CPMM: &str = "CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C";
+const USDC_MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+const TOKEN_MINT: &str = "4wBqpZM9xaSheZzJSMawUKKwhdpChKbZ5eu5ky4Vigw";
+
+const SWAP_BASE_INPUT: [u8; 8] = [143, 190, 90, 218, 196, 30, 51, 222];
+
+fn transfer(idx: &str, source: &str, destination: &str, mint: &str, amount: u64) -> Value {
+    common::transfer(RAYDIUM_CPMM, idx, source, destination, mint, amount, 6)
+}
+
+/// USDC to token swap, so valuing it in SOL needs the price provider.
+fn transaction() -> Result<SolanaTransaction> {
+    let accounts: Vec<String> = (0..13)
+        .map(|i| match i {
+            0 => "user".to_string(),
+            3 => "pool".to_string(),
+            4 => "user-usdc".to_string(),
+            5 => "user-token".to_string(),
+            6 => "vault-usdc".to_string(),
+            7 => "vault-token".to_string(),
+            10 => USDC_MINT.to_string(),
+            11 => TOKEN_MINT.to_string(),
+            _ => format!("account-{i}"),
+        })
+        .collect();
+    let mut data = SWAP_BASE_INPUT.to_vec();
+    data.extend_from_slice(&10_000_000u64.to_le_bytes());
+    data.extend_from_slice(&1u64.to_le_bytes());
+    common::transaction(
+        "pool",
+        json!({
+            "instructions": [{
+                "programId": RAYDIUM_CPMM,
+                "accounts": accounts,
+                "data": bs58::encode(data).into_string(),
+            }],
+            "transfers": [
+                transfer("0-0", "user-usdc", "vault-usdc", USDC_MINT, 10_000_000),
+                transfer("0-1", "vault-token", "user-token", TOKEN_MINT, 50_000_000),
+            ],
+        }),
+    )
+}
+
+#[derive(Default)]
+struct CountingProvider {
+    calls: AtomicUsize,
+}
+
+impl PriceProvider for CountingProvider {
+    fn price_in_sol(&self, mint: &str, _timestamp: u64) -> Option<f64> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        (mint == USDC_MINT).then_some(0.005)
+    }
+}
+
+#[test]
+fn pooled_parsers_serve_concurrent_callers() -> Result<()> {
+    let pool = ParserPool::new(2);
+    let tx = transaction()?;
+
+    let trade_counts: Vec<usize> = std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..8)
+            .map(|_| scope.spawn(|| pool.parse(tx.clone()).trades.len()))
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("parser thread"))
+            .collect()
+    });
+
+    assert_eq!(trade_counts, [1; 8]);
+    assert_eq!(pool.size(), 2);
+    Ok(())
+}
+
+#[test]
+fn pool_shares_config_and_price_cache() -> Result<()> {
+    let provider = Arc::new(CountingProvider::default());
+    let pool = ParserPool::new(3)
+        .with_price_provider(provider.clone())
+        .with_config(ParseConfig {
+            aggregate_trades: false,
+            ..ParseConfig::default()
+        });
+
+    for _ in 0..3 {
+        let result = pool.parse(transaction()?);
+        assert_eq!(result.trades[0].volume_sol, Some(0.05));
+        assert!(result.aggregate_trade.is_none());
+    }
+
+    assert_eq!(provider.calls.load(Ordering::SeqCst), 1);
+    Ok(())
+}
+
+#[test]
+fn pool_middlewares_survive_adding_a_price_provider() -> Result<()> {
+    fn redact_signers(tx: &mut SolanaTransaction) {
+        tx.signers = vec!["redacted".to_string()];
+    }
+    let pool = ParserPool::new(2)
+        .with_middleware(Arc::new(redact_signers))
+        .with_price_provider(Arc::new(CountingProvider::default()));
+
+    let result = pool.parse(transaction()?);
+
+    assert_eq!(result.signer, ["redacted"]);
+    assert_eq!(result.trades[0].volume_sol, Some(0.05));
+    Ok(())
+}
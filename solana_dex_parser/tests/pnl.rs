@@ -0,0 +1,95 @@
+use solana_dex_parser::types::{FeeInfo, TokenAmount, TradeType};
+use solana_dex_parser::{ParseResult, PnlTracker, TradeInfo};
+
+mod common;
+
+const WALLET: &str = "wallet";
+const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+const TOKEN_MINT: &str = "4wBqpZM9xaSheZzJSMawUKKwhdpChKbZ5eu5ky4Vigw";
+
+/// Trade of `tokens` for `sol` by `user`, a buy when `buy` is set.
+fn trade(signature: &str, user: &str, buy: bool, tokens: f64, sol: f64) -> ParseResult {
+    let tokens = common::ui_token(TOKEN_MINT, tokens, 9);
+    let sol = common::ui_token(SOL_MINT, sol, 9);
+    ParseResult {
+        signature: signature.to_string(),
+        signer: vec![user.to_string()],
+        trades: vec![TradeInfo {
+            user: Some(user.to_string()),
+            idx: "0".to_string(),
+            ..common::swap(tokens, sol, buy)
+        }],
+        ..ParseResult::new()
+    }
+}
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9
+}
+
+#[test]
+fn sale_realizes_profit_over_average_cost() {
+    let mut tracker = PnlTracker::new(WALLET);
+
+    tracker.observe(&trade("buy-1", WALLET, true, 1000.0, 1.0));
+    tracker.observe(&trade("buy-2", WALLET, true, 1000.0, 3.0));
+    let events = tracker.observe(&trade("sell", WALLET, false, 1000.0, 4.0));
+
+    assert_eq!(events.len(), 1);
+    let event = &events[0];
+    assert_eq!(event.trade_type, TradeType::Sell);
+    assert!(close(event.realized_pnl_sol, 2.0));
+    assert!(close(event.position_amount, 1000.0));
+    assert!(close(event.average_cost.unwrap(), 0.002));
+    // The remaining 1000 tokens cost 2 SOL and are marked at 0.004.
+    assert!(close(event.unrealized_pnl_sol, 2.0));
+    assert!(close(tracker.realized_pnl(), 2.0));
+    assert!(close(tracker.unrealized_pnl(), 2.0));
+}
+
+#[test]
+fn fees_paid_by_the_wallet_count_against_pnl() {
+    let mut tracker = PnlTracker::new(WALLET);
+    let mut buy = trade("buy", WALLET, true, 1000.0, 1.0);
+    buy.fees.base = TokenAmount::new("5000", 9, Some(0.000005));
+    buy.fees.tip = TokenAmount::new("995000", 9, Some(0.000995));
+    let mut sell = trade("sell", WALLET, false, 1000.0, 2.0);
+    sell.trades[0].fee = Some(FeeInfo {
+        mint: SOL_MINT.to_string(),
+        amount: 0.02,
+        amount_raw: "20000000".to_string(),
+        decimals: 9,
+        dex: None,
+        fee_type: None,
+        recipient: None,
+    });
+
+    let bought = tracker.observe(&buy);
+    let sold = tracker.observe(&sell);
+
+    assert!(close(bought[0].fee_sol, 0.001));
+    assert!(close(tracker.position(TOKEN_MINT).unwrap().amount, 0.0));
+    assert!(close(sold[0].realized_pnl_sol, 2.0 - 0.02 - 1.001));
+}
+
+#[test]
+fn other_wallets_only_move_the_mark() {
+    let mut tracker = PnlTracker::new(WALLET);
+
+    tracker.observe(&trade("buy", WALLET, true, 1000.0, 1.0));
+    let events = tracker.observe(&trade("other", "someone", true, 1000.0, 5.0));
+
+    assert!(events.is_empty());
+    assert!(close(tracker.position(TOKEN_MINT).unwrap().amount, 1000.0));
+    assert!(close(tracker.unrealized_pnl(), 4.0));
+}
+
+#[test]
+fn selling_untracked_tokens_realizes_nothing() {
+    let mut tracker = PnlTracker::new(WALLET);
+
+    let events = tracker.observe(&trade("sell", WALLET, false, 1000.0, 1.0));
+
+    assert!(close(events[0].realized_pnl_sol, 0.0));
+    assert!(close(events[0].position_amount, 0.0));
+}
@@ -0,0 +1,64 @@
+use solana_dex_parser::core::pool_state::attach_pool_states;
+use solana_dex_parser::{BlockContext, ParseResult, PoolState, PrefetchedPoolStates, TradeInfo};
+
+const POOL_A: &str = "58oQChx4yWmvKdwLLZzBi4ChoCc2fqCUWBkwMihLYQo2";
+const POOL_B: &str = "7qbRF6YsyGuLUVs6Y1q64bdVrfe4ZcUUz1JRdoVNUJnm";
+
+fn trade(pool: &str, slot: u64, idx: &str) -> TradeInfo {
+    TradeInfo {
+        pool: vec![pool.to_string()],
+        slot,
+        idx: idx.to_string(),
+        ..TradeInfo::default()
+    }
+}
+
+fn state(slot: u64, reserve_a: &str) -> PoolState {
+    PoolState {
+        slot,
+        mint_a: "So11111111111111111111111111111111111111112".to_string(),
+        reserve_a: reserve_a.to_string(),
+        mint_b: "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v".to_string(),
+        reserve_b: "1000000".to_string(),
+        liquidity: None,
+    }
+}
+
+#[test]
+fn attaches_end_of_slot_state_to_last_trade_per_pool() {
+    let mut first = ParseResult::new();
+    first.trades = vec![trade(POOL_A, 100, "0-0"), trade(POOL_B, 100, "1-0")];
+    let mut second = ParseResult::new();
+    second.trades = vec![trade(POOL_A, 100, "0-0")];
+    let mut results = vec![first, second];
+
+    let states: PrefetchedPoolStates = [(POOL_A, state(100, "42")), (POOL_A, state(99, "7"))]
+        .into_iter()
+        .collect();
+    attach_pool_states(&mut results, &states);
+
+    assert_eq!(results[0].trades[0].pool_state, None);
+    assert_eq!(results[0].trades[1].pool_state, None);
+    assert_eq!(results[1].trades[0].pool_state, Some(state(100, "42")));
+}
+
+#[test]
+fn last_trade_follows_the_transaction_index_not_the_result_order() {
+    let result = |transaction_index: usize| ParseResult {
+        slot: 100,
+        trades: vec![trade(POOL_A, 100, "0-0")],
+        block_context: Some(BlockContext {
+            transaction_index,
+            ..BlockContext::default()
+        }),
+        ..ParseResult::new()
+    };
+    // Results gathered out of order, e.g. from parallel workers.
+    let mut results = vec![result(4), result(1)];
+
+    let states: PrefetchedPoolStates = [(POOL_A, state(100, "42"))].into_iter().collect();
+    attach_pool_states(&mut results, &states);
+
+    assert_eq!(results[0].trades[0].pool_state, Some(state(100, "42")));
+    assert_eq!(results[1].trades[0].pool_state, None);
+}
@@ -0,0 +1,125 @@
+use solana_dex_parser::types::{PoolEvent, PoolReserves, TradeType};
+use solana_dex_parser::{BlockContext, BlockParseResult, ParseResult, PoolStateTracker, TradeInfo};
+
+mod common;
+
+const POOL: &str = "58oQChx4yWmvKdwLLZzBi4ChoCc2fqCUWBkwMihLYQo2";
+const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+const TOKEN_MINT: &str = "4wBqpZM9xaSheZzJSMawUKKwhdpChKbZ5eu5ky4Vigw";
+
+fn pool_event(event_type: TradeType, sol_raw: &str, token_raw: &str) -> PoolEvent {
+    PoolEvent {
+        event_type,
+        pool_id: POOL.to_string(),
+        token0_mint: Some(SOL_MINT.to_string()),
+        token0_amount_raw: Some(sol_raw.to_string()),
+        token0_decimals: Some(9),
+        token1_mint: Some(TOKEN_MINT.to_string()),
+        token1_amount_raw: Some(token_raw.to_string()),
+        token1_decimals: Some(6),
+        ..PoolEvent::default()
+    }
+}
+
+/// Buy of the token with SOL on the pool.
+fn buy(sol_raw: u64, token_raw: u64) -> TradeInfo {
+    TradeInfo {
+        pool: vec![POOL.to_string()],
+        ..common::swap(
+            common::token(TOKEN_MINT, token_raw, 6),
+            common::token(SOL_MINT, sol_raw, 9),
+            true,
+        )
+    }
+}
+
+fn tx(position: usize, liquidities: Vec<PoolEvent>, trades: Vec<TradeInfo>) -> ParseResult {
+    ParseResult {
+        signature: format!("tx-{position}"),
+        slot: 100,
+        liquidities,
+        trades,
+        block_context: Some(BlockContext {
+            transaction_index: position,
+            ..BlockContext::default()
+        }),
+        ..ParseResult::new()
+    }
+}
+
+#[test]
+fn reserves_follow_creation_liquidity_and_trades() {
+    let block = BlockParseResult {
+        slot: 100,
+        timestamp: None,
+        transactions: vec![
+            // Out of block order: the sort puts the creation first.
+            tx(
+                1,
+                vec![pool_event(TradeType::Add, "10000000000", "100000000000")],
+                vec![buy(5_000_000_000, 30_000_000_000)],
+            ),
+            tx(
+                0,
+                vec![pool_event(TradeType::Create, "10000000000", "100000000000")],
+                vec![],
+            ),
+        ],
+    };
+    let mut tracker = PoolStateTracker::new();
+
+    tracker.observe_block(&block);
+
+    let pool = tracker.pool(POOL).unwrap();
+    assert_eq!(pool.reserves.quote_mint, SOL_MINT);
+    assert_eq!(pool.reserves.quote_reserve, "25000000000");
+    assert_eq!(pool.reserves.base_reserve, "170000000000");
+    assert_eq!(pool.signature, "tx-1");
+    let price = 25.0 / 170_000.0;
+    assert!((pool.price.unwrap() - price).abs() < 1e-12);
+    assert!((pool.tvl_sol().unwrap() - 50.0).abs() < 1e-9);
+}
+
+#[test]
+fn reported_reserves_replace_tracked_ones() {
+    let mut tracker = PoolStateTracker::new();
+    let mut trade = buy(1_000_000_000, 1_000_000);
+    trade.pool_reserves = Some(PoolReserves {
+        base_mint: TOKEN_MINT.to_string(),
+        base_reserve: "500000000".to_string(),
+        base_decimals: 6,
+        quote_mint: SOL_MINT.to_string(),
+        quote_reserve: "2000000000".to_string(),
+        quote_decimals: 9,
+    });
+    trade.price_after = Some(0.004);
+
+    tracker.observe(&tx(0, vec![], vec![buy(1, 1)]));
+    assert!(tracker.is_empty());
+    tracker.observe(&tx(1, vec![], vec![trade]));
+
+    let pool = tracker.pool(POOL).unwrap();
+    assert_eq!(pool.base_reserve(), 500.0);
+    assert_eq!(pool.quote_reserve(), 2.0);
+    assert_eq!(pool.price, Some(0.004));
+    assert!((pool.tvl().unwrap() - 4.0).abs() < 1e-9);
+}
+
+#[test]
+fn removals_leave_the_pool_tracked() {
+    let mut tracker = PoolStateTracker::new();
+
+    tracker.observe(&tx(
+        0,
+        vec![
+            pool_event(TradeType::Create, "10000000000", "100000000000"),
+            pool_event(TradeType::Remove, "10000000000", "100000000000"),
+        ],
+        vec![],
+    ));
+
+    let pool = tracker.pool(POOL).unwrap();
+    assert_eq!(pool.reserves.quote_reserve, "0");
+    assert_eq!(pool.reserves.base_reserve, "0");
+    assert_eq!(tracker.len(), 1);
+}
@@ -0,0 +1,144 @@
+use anyhow::Result;
+use serde_json::{json, Value};
+use solana_dex_parser::core::constants::dex_program_names;
+use solana_dex_parser::core::program_tables::{ProgramTables, SharedProgramTables};
+use solana_dex_parser::protocols::registry::{self, DiscriminatorKind};
+use solana_dex_parser::{BlockParseResult, DexParser, ParseConfig, SolanaTransaction};
+
+mod common;
+
+const NEW_VENUE: &str = "NewVenue11111111111111111111111111111111111";
+const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+const USDC_MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+
+fn transfer(idx: &str, source: &str, destination: &str, mint: &str) -> Value {
+    common::transfer(NEW_VENUE, idx, source, destination, mint, 1_000_000, 6)
+}
+
+fn swap() -> Result<SolanaTransaction> {
+    Ok(serde_json::from_value(json!({
+        "slot": 1_900,
+        "signature": "new-venue",
+        "blockTime": 1_700_000_000,
+        "signers": ["user"],
+        "instructions": [{ "programId": NEW_VENUE, "accounts": ["user"], "data": "" }],
+        "transfers": [
+            transfer("0-0", "user-sol", "vault-sol", SOL_MINT),
+            transfer("0-1", "vault-usdc", "user-usdc", USDC_MINT),
+        ],
+    }))?)
+}
+
+fn tables() -> Result<ProgramTables> {
+    Ok(ProgramTables::from_json_str(
+        &json!({
+            "programs": [{
+                "programId": NEW_VENUE,
+                "name": "NewVenue",
+                "discriminators": [
+                    { "kind": "instruction", "name": "swap", "discriminator": [1, 2, 3, 4, 5, 6, 7, 8] },
+                ],
+            }],
+        })
+        .to_string(),
+    )?)
+}
+
+#[test]
+fn tables_name_new_venues_of_the_configs_they_are_set_on() -> Result<()> {
+    let config = ParseConfig {
+        program_tables: SharedProgramTables::new(tables()?),
+        ..ParseConfig::default()
+    };
+
+    assert_eq!(config.program_name(NEW_VENUE), "NewVenue");
+    let trades = DexParser::new().parse_trades(swap()?, Some(config.clone()));
+    assert_eq!(trades[0].amm.as_deref(), Some("NewVenue"));
+    // Bundled programs keep their own name.
+    assert_eq!(
+        config.program_name("JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4"),
+        "Jupiter"
+    );
+
+    let default = ParseConfig::default();
+    assert_eq!(default.program_name(NEW_VENUE), "Unknown DEX");
+    assert_eq!(dex_program_names::name(NEW_VENUE), "Unknown DEX");
+    let trades = DexParser::new().parse_trades(swap()?, Some(default));
+    assert_eq!(trades[0].amm.as_deref(), Some("Unknown DEX"));
+    Ok(())
+}
+
+#[test]
+fn tables_name_new_venues_in_invocations_and_block_stats() -> Result<()> {
+    let config = ParseConfig {
+        program_tables: SharedProgramTables::new(tables()?),
+        ..ParseConfig::default()
+    };
+
+    let result = DexParser::new().parse_all(swap()?, Some(config.clone()));
+    let invoked = result
+        .programs_invoked
+        .iter()
+        .find(|program| program.program_id == NEW_VENUE)
+        .expect("new venue invoked");
+    assert_eq!(invoked.name, "NewVenue");
+
+    let mut trade = result.trades[0].clone();
+    trade.amm = None;
+    let mut unnamed = result.clone();
+    unnamed.trades = vec![trade];
+    let block = BlockParseResult {
+        slot: 1_900,
+        timestamp: None,
+        transactions: vec![unnamed],
+    };
+    assert_eq!(block.stats(&config).volume_by_amm["NewVenue"].trades, 1);
+    assert!(block
+        .stats(&ParseConfig::default())
+        .volume_by_amm
+        .contains_key("Unknown DEX"));
+    Ok(())
+}
+
+#[test]
+fn tables_extend_the_discriminator_registry() -> Result<()> {
+    assert!(registry::discriminators_for(NEW_VENUE).is_empty());
+
+    let entries: Vec<_> = registry::discriminators_with(&tables()?)
+        .into_iter()
+        .filter(|entry| entry.program_id == NEW_VENUE)
+        .collect();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].program_name, "NewVenue");
+    assert_eq!(entries[0].kind, DiscriminatorKind::Instruction);
+    assert_eq!(*entries[0].discriminator, [1, 2, 3, 4, 5, 6, 7, 8]);
+    Ok(())
+}
+
+#[test]
+fn storing_new_tables_reloads_every_clone_of_the_config() -> Result<()> {
+    let config = ParseConfig {
+        program_tables: SharedProgramTables::new(tables()?),
+        ..ParseConfig::default()
+    };
+    let pooled = config.clone();
+
+    config
+        .program_tables
+        .store(ProgramTables::from_toml_str(&format!(
+            r#"
+[[programs]]
+programId = "{NEW_VENUE}"
+name = "NewVenueV2"
+"#
+        ))?);
+
+    assert_eq!(pooled.program_name(NEW_VENUE), "NewVenueV2");
+    assert!(pooled.program_tables.tables().programs[0]
+        .discriminators
+        .is_empty());
+    config.program_tables.store(ProgramTables::default());
+    assert_eq!(pooled.program_name(NEW_VENUE), "Unknown DEX");
+    assert!(ProgramTables::from_json_str("{\"programs\": 1}").is_err());
+    Ok(())
+}
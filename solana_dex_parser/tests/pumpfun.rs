@@ -1,14 +1,13 @@
 use std::fs;
 
 use anyhow::Result;
-use serde_json::to_string_pretty;
+use serde_json::{json, to_string_pretty};
 use solana_dex_parser::types::TradeType;
 use solana_dex_parser::{DexParser, SolanaTransaction};
 
-#[path = "common/mod.rs"]
-mod rpc_helpers;
+mod common;
 
-use rpc_helpers::fetch_transaction_with_fallback;
+use common::fetch_transaction_with_fallback;
 
 const PUMP_FUN_PROGRAM: &str = "6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P";
 const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
@@ -17,6 +16,13 @@ const TEST_USER: &str = "5Pk716N113awdSaUDZEPZVi9Zs6hJmG5KCJtp5qQK3LB";
 const DEFAULT_PUMPFUN_SIGNATURE: &str =
     "b15toBqDHKvVy7KQeAMDiEfinqg4Y8tDorUNHBd4FVojvqGyvZMELVkAz5BrNrc9AiA1zvRAZ9FfWM7qjWUQW9u";
 
+const EVENT_PREFIX: [u8; 8] = [228, 69, 165, 46, 81, 203, 154, 29];
+const SET_PARAMS_EVENT: [u8; 8] = [223, 195, 159, 246, 62, 48, 143, 131];
+const ADMIN_SET_CREATOR_EVENT: [u8; 8] = [64, 69, 192, 104, 29, 30, 25, 107];
+const UPDATE_GLOBAL_AUTHORITY_EVENT: [u8; 8] = [182, 195, 137, 42, 35, 206, 207, 247];
+const TRADE_EVENT: [u8; 8] = [189, 219, 127, 211, 78, 230, 97, 238];
+const COLLECT_CREATOR_FEE_EVENT: [u8; 8] = [122, 2, 127, 1, 14, 191, 12, 175];
+
 fn approx_eq(actual: f64, expected: f64) {
     let diff = (actual - expected).abs();
     assert!(diff < 1e-6, "expected {expected}, got {actual}");
@@ -44,8 +50,14 @@ fn pumpfun_buy_trade_is_parsed() -> Result<()> {
     approx_eq(trade.input_token.amount, 0.5);
     assert_eq!(trade.output_token.mint, TEST_MINT);
     approx_eq(trade.output_token.amount, 12_345.6);
-    assert_eq!(trade.signature, "pumpfun-signature");
+    assert_eq!(&*trade.signature, "pumpfun-signature");
     assert_eq!(trade.idx, "0-0");
+    // Virtual reserves after the buy: 1 SOL against 20,000 tokens.
+    let reserves = trade.pool_reserves.as_ref().expect("curve reserves");
+    assert_eq!(reserves.base_mint, TEST_MINT);
+    assert_eq!(reserves.base_reserve, "20000000000");
+    assert_eq!(reserves.quote_reserve, "1000000000");
+    approx_eq(trade.price_after.expect("price after the buy"), 0.00005);
 
     assert_eq!(result.meme_events.len(), 1);
     let event = &result.meme_events[0];
@@ -98,3 +110,194 @@ fn pumpfun_real_transaction_is_parsed() -> Result<()> {
 
     Ok(())
 }
+
+fn pubkey(byte: u8) -> (Vec<u8>, String) {
+    let bytes = vec![byte; 32];
+    let encoded = bs58::encode(&bytes).into_string();
+    (bytes, encoded)
+}
+
+fn event_transaction(events: &[Vec<u8>]) -> Result<SolanaTransaction> {
+    let instructions: Vec<_> = events
+        .iter()
+        .map(|data| {
+            json!({
+                "programId": PUMP_FUN_PROGRAM,
+                "accounts": [],
+                "data": bs58::encode(data).into_string(),
+            })
+        })
+        .collect();
+    Ok(serde_json::from_value(json!({
+        "slot": 350_000_000,
+        "signature": "pumpfun-admin",
+        "blockTime": 1_750_000_000,
+        "signers": ["pumpfun-admin"],
+        "instructions": instructions,
+    }))?)
+}
+
+#[test]
+fn pumpfun_set_params_reports_fee_rates() -> Result<()> {
+    let mut data = [EVENT_PREFIX, SET_PARAMS_EVENT].concat();
+    for value in [
+        1_073_000_000_000_000u64,
+        30_000_000_000,
+        793_100_000_000_000,
+        85_000_000_000,
+        1_000_000_000_000_000,
+        95,
+    ] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    data.extend_from_slice(&pubkey(1).0);
+    data.push(1);
+    data.extend_from_slice(&15_000_000u64.to_le_bytes());
+    data.extend_from_slice(&5u64.to_le_bytes());
+    data.extend_from_slice(&[2; 32 * 8]);
+    data.extend_from_slice(&1_749_999_999i64.to_le_bytes());
+    data.extend_from_slice(&[3; 64]);
+    // Older programs logged the event without the creator fee and what follows it.
+    let legacy = data[..16 + 6 * 8].to_vec();
+
+    let result = DexParser::new().parse_all(event_transaction(&[data, legacy])?, None);
+
+    assert!(result.trades.is_empty());
+    assert_eq!(result.meme_events.len(), 2);
+    let event = &result.meme_events[0];
+    assert_eq!(event.event_type, TradeType::SetParams);
+    assert_eq!(event.user, "pumpfun-admin");
+    assert_eq!(event.fee_basis_points, Some(95));
+    assert_eq!(event.creator_fee_basis_points, Some(5));
+    assert_eq!(event.total_supply, Some(1_000_000_000_000_000));
+    let legacy = &result.meme_events[1];
+    assert_eq!(legacy.fee_basis_points, Some(95));
+    assert_eq!(legacy.creator_fee_basis_points, None);
+    Ok(())
+}
+
+#[test]
+fn pumpfun_creator_and_authority_changes_are_meme_events() -> Result<()> {
+    let (authority, authority_key) = pubkey(4);
+    let (mint, mint_key) = pubkey(5);
+    let (curve, curve_key) = pubkey(6);
+    let (old_creator, old_creator_key) = pubkey(7);
+    let (new_creator, new_creator_key) = pubkey(8);
+    let set_creator = [
+        &EVENT_PREFIX[..],
+        &ADMIN_SET_CREATOR_EVENT,
+        &1_750_000_000i64.to_le_bytes(),
+        &authority,
+        &mint,
+        &curve,
+        &old_creator,
+        &new_creator,
+    ]
+    .concat();
+    let update_authority = [
+        &EVENT_PREFIX[..],
+        &UPDATE_GLOBAL_AUTHORITY_EVENT,
+        &pubkey(9).0,
+        &new_creator,
+        &1_750_000_000i64.to_le_bytes(),
+    ]
+    .concat();
+
+    let result =
+        DexParser::new().parse_all(event_transaction(&[set_creator, update_authority])?, None);
+
+    let event = &result.meme_events[0];
+    assert_eq!(event.event_type, TradeType::SetCreator);
+    assert_eq!(event.user, authority_key);
+    assert_eq!(event.base_mint, mint_key);
+    assert_eq!(event.bonding_curve.as_deref(), Some(curve_key.as_str()));
+    assert_eq!(
+        event.previous_creator.as_deref(),
+        Some(old_creator_key.as_str())
+    );
+    assert_eq!(event.creator.as_deref(), Some(new_creator_key.as_str()));
+
+    let event = &result.meme_events[1];
+    assert_eq!(event.event_type, TradeType::UpdateGlobalAuthority);
+    assert_eq!(event.idx, "1-0");
+    assert_eq!(
+        event.new_authority.as_deref(),
+        Some(new_creator_key.as_str())
+    );
+    assert_eq!(
+        serde_json::to_value(event)?["type"],
+        json!("UPDATE_GLOBAL_AUTHORITY")
+    );
+    Ok(())
+}
+
+#[test]
+fn pumpfun_creator_fee_collection_is_a_fee_claim() -> Result<()> {
+    let (creator, creator_key) = pubkey(10);
+    let data = [
+        &EVENT_PREFIX[..],
+        &COLLECT_CREATOR_FEE_EVENT,
+        &1_750_000_000i64.to_le_bytes(),
+        &creator,
+        &123_456_789u64.to_le_bytes(),
+    ]
+    .concat();
+
+    let result = DexParser::new().parse_all(event_transaction(&[data])?, None);
+
+    assert!(result.trades.is_empty());
+    assert_eq!(result.meme_events.len(), 1);
+    let claim = &result.meme_events[0];
+    assert_eq!(claim.event_type, TradeType::FeeClaim);
+    assert_eq!(claim.protocol.as_deref(), Some("Pumpfun"));
+    assert_eq!(claim.user, creator_key);
+    assert_eq!(claim.creator.as_deref(), Some(creator_key.as_str()));
+    let token = claim.output_token.as_ref().expect("claimed amount");
+    assert_eq!(token.mint, SOL_MINT);
+    assert_eq!(token.amount_raw, "123456789");
+    approx_eq(token.amount, 0.123456789);
+    Ok(())
+}
+
+fn trade_event(virtual_token_reserves: u64, real_token_reserves: Option<u64>) -> Vec<u8> {
+    let mut data = [&EVENT_PREFIX[..], &TRADE_EVENT, &pubkey(12).0].concat();
+    data.extend_from_slice(&1_000_000_000u64.to_le_bytes());
+    data.extend_from_slice(&35_000_000_000u64.to_le_bytes());
+    data.push(1);
+    data.extend_from_slice(&pubkey(13).0);
+    data.extend_from_slice(&1_750_000_000i64.to_le_bytes());
+    data.extend_from_slice(&60_000_000_000u64.to_le_bytes());
+    data.extend_from_slice(&virtual_token_reserves.to_le_bytes());
+    if let Some(real_token_reserves) = real_token_reserves {
+        data.extend_from_slice(&30_000_000_000u64.to_le_bytes());
+        data.extend_from_slice(&real_token_reserves.to_le_bytes());
+        data.extend_from_slice(&pubkey(14).0);
+        data.extend_from_slice(&95u16.to_le_bytes());
+        data.extend_from_slice(&9_500_000u64.to_le_bytes());
+        data.extend_from_slice(&pubkey(15).0);
+        data.extend_from_slice(&5u16.to_le_bytes());
+        data.extend_from_slice(&500_000u64.to_le_bytes());
+    }
+    data
+}
+
+#[test]
+fn pumpfun_trades_report_bonding_curve_progress() -> Result<()> {
+    let events = [
+        trade_event(358_960_000_000_000, Some(79_310_000_000_000)),
+        // Legacy events without real reserves: virtual reserves less the never-sold part.
+        trade_event(676_450_000_000_000, None),
+    ];
+
+    let result = DexParser::new().parse_all(event_transaction(&events)?, None);
+
+    let progress: Vec<Option<f64>> = result
+        .meme_events
+        .iter()
+        .map(|event| event.bonding_curve_progress)
+        .collect();
+    assert_eq!(progress.len(), 2);
+    approx_eq(progress[0].expect("progress from real reserves"), 90.0);
+    approx_eq(progress[1].expect("progress from virtual reserves"), 50.0);
+    Ok(())
+}
@@ -0,0 +1,93 @@
+use anyhow::Result;
+use serde_json::{json, Value};
+use solana_dex_parser::types::TradeType;
+use solana_dex_parser::{DexParser, SolanaTransaction};
+
+const PUMP_SWAP: &str = "pAMMBay6oceH9fJKBRHGP5D4bD4sWpmSwMn52FMfXEA";
+const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+const TOKEN_MINT: &str = "4wBqpZM9xaSheZzJSMawUKKwhdpChKbZ5eu5ky4Vigw";
+const SELL_EVENT: [u8; 16] = [
+    228, 69, 165, 46, 81, 203, 154, 29, 62, 47, 55, 10, 165, 3, 220, 42,
+];
+
+fn pubkey(byte: u8) -> (Vec<u8>, String) {
+    let bytes = vec![byte; 32];
+    let encoded = bs58::encode(&bytes).into_string();
+    (bytes, encoded)
+}
+
+fn balance(account: &str, mint: &str, decimals: u8) -> Value {
+    json!({
+        "account": account,
+        "mint": mint,
+        "owner": "user",
+        "uiTokenAmount": { "amount": "0", "uiAmount": 0.0, "decimals": decimals },
+    })
+}
+
+/// Sell of 1 token for 0.1 SOL with a 0.099 SOL minimum.
+fn sell_transaction() -> Result<SolanaTransaction> {
+    let (user_base, user_base_key) = pubkey(1);
+    let (user_quote, user_quote_key) = pubkey(2);
+    let (fee_account, fee_account_key) = pubkey(3);
+    let mut data = SELL_EVENT.to_vec();
+    data.extend_from_slice(&1_745_000_000i64.to_le_bytes());
+    for amount in [
+        1_000_000u64, // base_amount_in
+        99_000_000,   // min_quote_amount_out
+        0,
+        0,
+        1_000_000_000_000, // pool_base_token_reserves
+        100_000_000_000,   // pool_quote_token_reserves
+        100_350_000,       // quote_amount_out
+        20,
+        200_000,
+        5,
+        50_000,
+        100_150_000,
+        100_000_000, // user_quote_amount_out
+    ] {
+        data.extend_from_slice(&amount.to_le_bytes());
+    }
+    for account in [
+        pubkey(4).0,
+        pubkey(5).0,
+        user_base,
+        user_quote,
+        pubkey(6).0,
+        fee_account,
+    ] {
+        data.extend_from_slice(&account);
+    }
+
+    Ok(serde_json::from_value(json!({
+        "slot": 330_000_000,
+        "signature": "pumpswap-sell",
+        "blockTime": 1_745_000_000,
+        "signers": ["user"],
+        "instructions": [{
+            "programId": PUMP_SWAP,
+            "accounts": [],
+            "data": bs58::encode(data).into_string(),
+        }],
+        "postTokenBalances": [
+            balance(&user_base_key, TOKEN_MINT, 6),
+            balance(&user_quote_key, SOL_MINT, 9),
+            balance(&fee_account_key, SOL_MINT, 9),
+        ],
+    }))?)
+}
+
+#[test]
+fn sell_reports_slippage_against_its_minimum_output() -> Result<()> {
+    let trades = DexParser::new().parse_trades(sell_transaction()?, None);
+
+    assert_eq!(trades.len(), 1);
+    let trade = &trades[0];
+    assert_eq!(trade.trade_type, TradeType::Sell);
+    assert_eq!(trade.input_token.mint, TOKEN_MINT);
+    assert_eq!(trade.output_token.amount_raw, "100000000");
+    // 0.1 SOL received against a 0.099 minimum.
+    assert_eq!(trade.slippage_bps, Some(100));
+    Ok(())
+}
@@ -0,0 +1,137 @@
+use anyhow::Result;
+use serde_json::{json, Value};
+use solana_dex_parser::types::TradeType;
+use solana_dex_parser::{DexParser, SolanaTransaction};
+
+mod common;
+
+const PUMP_SWAP: &str = "pAMMBay6oceH9fJKBRHGP5D4bD4sWpmSwMn52FMfXEA";
+const EVENT_PREFIX: [u8; 8] = [228, 69, 165, 46, 81, 203, 154, 29];
+const CREATE_POOL_EVENT: [u8; 8] = [177, 49, 12, 210, 160, 118, 167, 116];
+const DEPOSIT_EVENT: [u8; 8] = [120, 248, 61, 83, 31, 142, 107, 144];
+
+fn pubkey(byte: u8) -> (Vec<u8>, String) {
+    let bytes = vec![byte; 32];
+    let encoded = bs58::encode(&bytes).into_string();
+    (bytes, encoded)
+}
+
+fn balance(account: &str, mint: &str, decimals: u8) -> Value {
+    json!({
+        "account": account,
+        "mint": mint,
+        "owner": "user",
+        "uiTokenAmount": { "amount": "0", "uiAmount": 0.0, "decimals": decimals },
+    })
+}
+
+fn transaction(event: Vec<u8>, balances: Vec<Value>) -> Result<SolanaTransaction> {
+    common::transaction(
+        "pumpswap-liquidity",
+        json!({
+            "blockTime": 1_745_000_000,
+            "instructions": [{
+                "programId": PUMP_SWAP,
+                "accounts": [],
+                "data": bs58::encode(event).into_string(),
+            }],
+            "postTokenBalances": balances,
+        }),
+    )
+}
+
+#[test]
+fn create_pool_reports_the_initial_lp_supply() -> Result<()> {
+    let (base_mint, base_key) = pubkey(1);
+    let (quote_mint, quote_key) = pubkey(2);
+    let (pool, pool_key) = pubkey(3);
+    let (lp_mint, lp_key) = pubkey(4);
+    let mut data = [&EVENT_PREFIX[..], &CREATE_POOL_EVENT].concat();
+    data.extend_from_slice(&1_745_000_000i64.to_le_bytes());
+    data.extend_from_slice(&0u16.to_le_bytes());
+    data.extend_from_slice(&pubkey(5).0);
+    data.extend_from_slice(&base_mint);
+    data.extend_from_slice(&quote_mint);
+    data.extend_from_slice(&[6, 9]);
+    for amount in [
+        200_000_000_000_000u64,
+        80_000_000_000,
+        200_000_000_000_000,
+        80_000_000_000,
+        100,
+        4_000_000_000_000,
+        3_999_999_999_900,
+    ] {
+        data.extend_from_slice(&amount.to_le_bytes());
+    }
+    data.push(255);
+    data.extend_from_slice(&pool);
+    data.extend_from_slice(&lp_mint);
+    data.extend_from_slice(&pubkey(6).0);
+    data.extend_from_slice(&pubkey(7).0);
+
+    let tx = transaction(data, vec![balance("user-lp", &lp_key, 9)])?;
+    let result = DexParser::new().parse_all(tx, None);
+
+    assert_eq!(result.liquidities.len(), 1);
+    let event = &result.liquidities[0];
+    assert_eq!(event.event_type, TradeType::Create);
+    assert_eq!(event.pool_id, pool_key);
+    assert_eq!(event.pool_lp_mint.as_deref(), Some(lp_key.as_str()));
+    assert_eq!(event.token0_mint.as_deref(), Some(base_key.as_str()));
+    assert_eq!(event.token1_mint.as_deref(), Some(quote_key.as_str()));
+    assert_eq!(event.token1_amount, Some(80.0));
+    assert_eq!(event.lp_amount_raw.as_deref(), Some("3999999999900"));
+    assert_eq!(event.lp_supply_raw.as_deref(), Some("4000000000000"));
+    assert_eq!(event.lp_supply, Some(4_000.0));
+    Ok(())
+}
+
+#[test]
+fn deposit_reports_amounts_and_lp_supply() -> Result<()> {
+    let (pool, pool_key) = pubkey(3);
+    let mut data = [&EVENT_PREFIX[..], &DEPOSIT_EVENT].concat();
+    data.extend_from_slice(&1_745_000_000i64.to_le_bytes());
+    for amount in [
+        10_000_000_000u64,
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+        500_000_000_000,
+        200_000_000,
+        4_010_000_000_000,
+    ] {
+        data.extend_from_slice(&amount.to_le_bytes());
+    }
+    data.extend_from_slice(&pool);
+    data.extend_from_slice(&pubkey(8).0);
+    let accounts = [pubkey(9), pubkey(10), pubkey(11)];
+    for (bytes, _) in &accounts {
+        data.extend_from_slice(bytes);
+    }
+    let balances = vec![
+        balance(&accounts[0].1, "base-mint", 6),
+        balance(
+            &accounts[1].1,
+            "So11111111111111111111111111111111111111112",
+            9,
+        ),
+        balance(&accounts[2].1, "lp-mint", 9),
+    ];
+
+    let result = DexParser::new().parse_all(transaction(data, balances)?, None);
+
+    assert_eq!(result.liquidities.len(), 1);
+    let event = &result.liquidities[0];
+    assert_eq!(event.event_type, TradeType::Add);
+    assert_eq!(event.pool_id, pool_key);
+    assert_eq!(event.pool_lp_mint.as_deref(), Some("lp-mint"));
+    assert_eq!(event.token0_amount, Some(500_000.0));
+    assert_eq!(event.token1_amount, Some(0.2));
+    assert_eq!(event.lp_amount, Some(10.0));
+    assert_eq!(event.lp_supply, Some(4_010.0));
+    Ok(())
+}
@@ -0,0 +1,230 @@
+use anyhow::Result;
+use base64::{engine::general_purpose::STANDARD as BASE64_STANDARD, Engine as _};
+use serde_json::{json, Value};
+use solana_dex_parser::types::TradeType;
+use solana_dex_parser::{DexParser, SolanaTransaction};
+
+mod common;
+
+const RAYDIUM_CLMM: &str = "CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK";
+const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+const USDC_MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+
+const SWAP: [u8; 8] = [248, 198, 158, 145, 225, 117, 135, 200];
+const OPEN_POSITION_V2: [u8; 8] = [77, 184, 74, 214, 112, 86, 241, 199];
+const CLOSE_POSITION: [u8; 8] = [123, 134, 81, 0, 49, 68, 98, 98];
+const SWAP_EVENT: [u8; 8] = [64, 198, 205, 232, 38, 8, 113, 226];
+const LIQUIDITY_CHANGE_EVENT: [u8; 8] = [126, 240, 175, 206, 158, 88, 153, 107];
+
+fn instruction_data(discriminator: [u8; 8], args: &[&[u8]]) -> String {
+    let mut data = discriminator.to_vec();
+    args.iter().for_each(|arg| data.extend_from_slice(arg));
+    bs58::encode(data).into_string()
+}
+
+fn transfer(
+    idx: &str,
+    source: &str,
+    destination: &str,
+    mint: &str,
+    amount: u64,
+    decimals: u8,
+) -> Value {
+    common::transfer(
+        RAYDIUM_CLMM,
+        idx,
+        source,
+        destination,
+        mint,
+        amount,
+        decimals,
+    )
+}
+
+fn transaction(instructions: Value, transfers: Value) -> Result<SolanaTransaction> {
+    common::transaction(
+        "clmm",
+        json!({
+            "instructions": instructions,
+            "transfers": transfers,
+        }),
+    )
+}
+
+fn swap_transaction() -> Result<SolanaTransaction> {
+    let accounts = [
+        "user",
+        "amm-config",
+        "pool",
+        "user-sol",
+        "user-usdc",
+        "vault-sol",
+        "vault-usdc",
+        "observation",
+        "token-program",
+        "tick-array",
+    ];
+    let data = instruction_data(
+        SWAP,
+        &[
+            &1_000_000_000u64.to_le_bytes(),
+            &148_500_000u64.to_le_bytes(),
+            &0u128.to_le_bytes(),
+            &[1],
+        ],
+    );
+    transaction(
+        json!([{ "programId": RAYDIUM_CLMM, "accounts": accounts, "data": data }]),
+        json!([
+            transfer("0-0", "user-sol", "vault-sol", SOL_MINT, 1_000_000_000, 9),
+            transfer("0-1", "vault-usdc", "user-usdc", USDC_MINT, 150_000_000, 6),
+        ]),
+    )
+}
+
+fn program_data(event: [u8; 8]) -> String {
+    let mut data = event.to_vec();
+    data.extend_from_slice(&[0; 32]);
+    format!("Program data: {}", BASE64_STANDARD.encode(data))
+}
+
+#[test]
+fn clmm_swap_is_parsed_from_vault_transfers() -> Result<()> {
+    let trades = DexParser::new().parse_trades(swap_transaction()?, None);
+
+    assert_eq!(trades.len(), 1);
+    let trade = &trades[0];
+    assert_eq!(trade.trade_type, TradeType::Buy);
+    assert_eq!(trade.pool, ["pool"]);
+    assert_eq!(trade.amm.as_deref(), Some("RaydiumCLMM"));
+    assert_eq!(trade.input_token.mint, SOL_MINT);
+    assert_eq!(trade.output_token.mint, USDC_MINT);
+    assert_eq!(trade.output_token.amount_raw, "150000000");
+    // 150 USDC received against a 148.5 minimum.
+    assert_eq!(trade.slippage_bps, Some(100));
+    assert_eq!(trade.ticks_crossed, None);
+    Ok(())
+}
+
+#[test]
+fn clmm_swap_counts_the_ticks_it_crossed_from_its_logs() -> Result<()> {
+    let mut tx = swap_transaction()?;
+    tx.meta.log_messages = vec![
+        format!("Program {RAYDIUM_CLMM} invoke [1]"),
+        program_data(LIQUIDITY_CHANGE_EVENT),
+        program_data(LIQUIDITY_CHANGE_EVENT),
+        program_data(LIQUIDITY_CHANGE_EVENT),
+        program_data(SWAP_EVENT),
+        format!("Program {RAYDIUM_CLMM} success"),
+    ];
+
+    let trades = DexParser::new().parse_trades(tx.clone(), None);
+    assert_eq!(trades[0].ticks_crossed, Some(3));
+
+    tx.meta.log_messages = vec![
+        format!("Program {RAYDIUM_CLMM} invoke [1]"),
+        program_data(SWAP_EVENT),
+        format!("Program {RAYDIUM_CLMM} success"),
+    ];
+    let trades = DexParser::new().parse_trades(tx, None);
+    assert_eq!(trades[0].ticks_crossed, Some(0));
+    Ok(())
+}
+
+#[test]
+fn clmm_swap_reports_the_price_from_its_swap_event() -> Result<()> {
+    let mut event = SWAP_EVENT.to_vec();
+    event.extend_from_slice(&[0; 4 * 32 + 4 * 8]);
+    event.push(1); // zero_for_one
+                   // sqrt(0.15) in Q64.64: 150 USDC per SOL once decimals are applied.
+    event.extend_from_slice(&7_144_393_258_922_745_856u128.to_le_bytes());
+    event.extend_from_slice(&0u128.to_le_bytes());
+    event.extend_from_slice(&0i32.to_le_bytes());
+    let mut tx = swap_transaction()?;
+    tx.meta.log_messages = vec![
+        format!("Program {RAYDIUM_CLMM} invoke [1]"),
+        format!("Program data: {}", BASE64_STANDARD.encode(event)),
+        format!("Program {RAYDIUM_CLMM} success"),
+    ];
+
+    let trades = DexParser::new().parse_trades(tx, None);
+
+    let price_after = trades[0].price_after.expect("price after the swap");
+    assert!((price_after - 150.0).abs() < 1e-6, "{price_after}");
+    assert_eq!(trades[0].pool_reserves, None);
+    Ok(())
+}
+
+#[test]
+fn clmm_open_and_close_position_emit_pool_events() -> Result<()> {
+    let mut open_accounts: Vec<String> = (0..22).map(|i| format!("account-{i}")).collect();
+    for (index, name) in [
+        (1, "user"),
+        (2, "nft-mint"),
+        (5, "pool"),
+        (7, "tick-lower"),
+        (8, "tick-upper"),
+        (9, "position"),
+        (12, "vault-0"),
+        (13, "vault-1"),
+    ] {
+        open_accounts[index] = name.to_string();
+    }
+    let open_data = instruction_data(
+        OPEN_POSITION_V2,
+        &[
+            &(-120i32).to_le_bytes(),
+            &360i32.to_le_bytes(),
+            &(-600i32).to_le_bytes(),
+            &0i32.to_le_bytes(),
+            &5_000u128.to_le_bytes(),
+            &2_000_000_000u64.to_le_bytes(),
+            &300_000_000u64.to_le_bytes(),
+            &[1, 0],
+        ],
+    );
+    let close_accounts = [
+        "user",
+        "nft-mint",
+        "nft-account",
+        "position",
+        "system",
+        "token",
+    ];
+    let tx = transaction(
+        json!([
+            { "programId": RAYDIUM_CLMM, "accounts": open_accounts, "data": open_data },
+            {
+                "programId": RAYDIUM_CLMM,
+                "accounts": close_accounts,
+                "data": instruction_data(CLOSE_POSITION, &[]),
+            },
+        ]),
+        json!([
+            transfer("0-0", "user-sol", "vault-0", SOL_MINT, 1_000_000_000, 9),
+            transfer("0-1", "user-usdc", "vault-1", USDC_MINT, 150_000_000, 6),
+        ]),
+    )?;
+
+    let events = DexParser::new().parse_liquidity(tx, None);
+
+    assert_eq!(events.len(), 2);
+    let open = &events[0];
+    assert_eq!(open.event_type, TradeType::Add);
+    assert_eq!(open.pool_id, "pool");
+    assert_eq!(open.token0_mint.as_deref(), Some(SOL_MINT));
+    assert_eq!(open.token1_amount_raw.as_deref(), Some("150000000"));
+    let position = open.position.as_ref().expect("position info");
+    assert_eq!(position.position, "position");
+    assert_eq!(position.nft_mint.as_deref(), Some("nft-mint"));
+    assert_eq!(position.tick_lower_index, Some(-120));
+    assert_eq!(position.tick_upper_index, Some(360));
+    assert_eq!(position.tick_array_lower.as_deref(), Some("tick-lower"));
+    assert_eq!(position.liquidity.as_deref(), Some("5000"));
+
+    let close = &events[1];
+    assert_eq!(close.event_type, TradeType::Remove);
+    assert_eq!(close.pool_id, "pool");
+    assert_eq!(close.token0_amount, None);
+    Ok(())
+}
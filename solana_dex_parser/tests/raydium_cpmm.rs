@@ -0,0 +1,246 @@
+use anyhow::Result;
+use serde_json::{json, Value};
+use solana_dex_parser::types::TradeType;
+use solana_dex_parser::{DexParser, NetworkProfile, ParseConfig, SolanaTransaction};
+
+mod common;
+
+const RAYDIUM_CPMM: &str = "CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C";
+const RAYDIUM_CPMM_DEVNET: &str = "CPMDWBwJDtYax9qW7AyRuVC19Cc4L4Vcy4n2BHAbHkCW";
+const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+const TOKEN_MINT: &str = "4wBqpZM9xaSheZzJSMawUKKwhdpChKbZ5eu5ky4Vigw";
+
+const INITIALIZE: [u8; 8] = [175, 175, 109, 31, 13, 152, 155, 237];
+const WITHDRAW: [u8; 8] = [183, 18, 70, 156, 148, 109, 161, 34];
+const SWAP_BASE_INPUT: [u8; 8] = [143, 190, 90, 218, 196, 30, 51, 222];
+const SWAP_BASE_OUTPUT: [u8; 8] = [55, 217, 98, 86, 163, 74, 180, 173];
+
+fn instruction_data(discriminator: [u8; 8], args: &[u64]) -> String {
+    let mut data = discriminator.to_vec();
+    args.iter()
+        .for_each(|arg| data.extend_from_slice(&arg.to_le_bytes()));
+    bs58::encode(data).into_string()
+}
+
+fn accounts(named: &[(usize, &str)], len: usize) -> Vec<String> {
+    (0..len)
+        .map(|i| {
+            named
+                .iter()
+                .find(|(index, _)| *index == i)
+                .map(|(_, name)| name.to_string())
+                .unwrap_or_else(|| format!("account-{i}"))
+        })
+        .collect()
+}
+
+fn transfer(
+    idx: &str,
+    source: &str,
+    destination: &str,
+    mint: &str,
+    amount: u64,
+    decimals: u8,
+) -> Value {
+    common::transfer(
+        RAYDIUM_CPMM,
+        idx,
+        source,
+        destination,
+        mint,
+        amount,
+        decimals,
+    )
+}
+
+fn transaction(instructions: Value, transfers: Value) -> Result<SolanaTransaction> {
+    common::transaction(
+        "cpmm",
+        json!({
+            "instructions": instructions,
+            "transfers": transfers,
+        }),
+    )
+}
+
+#[test]
+fn cpmm_swap_base_input_is_parsed() -> Result<()> {
+    let swap_accounts = accounts(
+        &[
+            (0, "user"),
+            (3, "pool"),
+            (4, "user-token"),
+            (5, "user-sol"),
+            (6, "vault-token"),
+            (7, "vault-sol"),
+            (10, TOKEN_MINT),
+            (11, SOL_MINT),
+        ],
+        13,
+    );
+    let tx = transaction(
+        json!([{
+            "programId": RAYDIUM_CPMM,
+            "accounts": swap_accounts,
+            "data": instruction_data(SWAP_BASE_INPUT, &[5_000_000, 247_500_000]),
+        }]),
+        json!([
+            transfer("0-0", "user-token", "vault-token", TOKEN_MINT, 5_000_000, 6),
+            transfer("0-1", "vault-sol", "user-sol", SOL_MINT, 250_000_000, 9),
+        ]),
+    )?;
+
+    let result = DexParser::new().parse_all(tx, None);
+
+    assert_eq!(result.trades.len(), 1);
+    let trade = &result.trades[0];
+    assert_eq!(trade.trade_type, TradeType::Sell);
+    assert_eq!(trade.amm.as_deref(), Some("RaydiumCPMM"));
+    assert_eq!(trade.pool, ["pool"]);
+    assert_eq!(trade.input_token.amount_raw, "5000000");
+    assert_eq!(trade.output_token.mint, SOL_MINT);
+    assert_eq!(trade.volume_sol, Some(0.25));
+    // 0.25 SOL received against a 0.2475 minimum.
+    assert_eq!(trade.slippage_bps, Some(100));
+    Ok(())
+}
+
+#[test]
+fn cpmm_swap_base_output_measures_slippage_on_the_input() -> Result<()> {
+    let swap_accounts = accounts(
+        &[
+            (0, "user"),
+            (3, "pool"),
+            (4, "user-token"),
+            (5, "user-sol"),
+            (6, "vault-token"),
+            (7, "vault-sol"),
+            (10, TOKEN_MINT),
+            (11, SOL_MINT),
+        ],
+        13,
+    );
+    let tx = transaction(
+        json!([{
+            "programId": RAYDIUM_CPMM,
+            "accounts": swap_accounts,
+            "data": instruction_data(SWAP_BASE_OUTPUT, &[5_050_000, 250_000_000]),
+        }]),
+        json!([
+            transfer("0-0", "user-token", "vault-token", TOKEN_MINT, 5_000_000, 6),
+            transfer("0-1", "vault-sol", "user-sol", SOL_MINT, 250_000_000, 9),
+        ]),
+    )?;
+
+    let trades = DexParser::new().parse_trades(tx, None);
+
+    assert_eq!(trades.len(), 1);
+    // 5 tokens paid against a 5.05 maximum.
+    assert_eq!(trades[0].slippage_bps, Some(100));
+    Ok(())
+}
+
+#[test]
+fn cpmm_devnet_swap_is_parsed_with_devnet_profile() -> Result<()> {
+    let swap_accounts = accounts(
+        &[
+            (0, "user"),
+            (3, "pool"),
+            (4, "user-token"),
+            (5, "user-sol"),
+            (6, "vault-token"),
+            (7, "vault-sol"),
+        ],
+        13,
+    );
+    let tx = transaction(
+        json!([{
+            "programId": RAYDIUM_CPMM_DEVNET,
+            "accounts": swap_accounts,
+            "data": instruction_data(SWAP_BASE_INPUT, &[5_000_000, 1]),
+        }]),
+        json!([
+            transfer("0-0", "user-token", "vault-token", TOKEN_MINT, 5_000_000, 6),
+            transfer("0-1", "vault-sol", "user-sol", SOL_MINT, 250_000_000, 9),
+        ]),
+    )?;
+    let parser = DexParser::new();
+    assert!(parser.parse_trades(tx.clone(), None).is_empty());
+
+    let config = ParseConfig {
+        network: NetworkProfile::Devnet,
+        ..ParseConfig::default()
+    };
+    let trades = parser.parse_trades(tx, Some(config));
+
+    assert_eq!(trades.len(), 1);
+    assert_eq!(trades[0].program_id.as_deref(), Some(RAYDIUM_CPMM_DEVNET));
+    assert_eq!(trades[0].amm.as_deref(), Some("RaydiumCPMM"));
+    assert_eq!(trades[0].output_token.amount_raw, "250000000");
+    Ok(())
+}
+
+#[test]
+fn cpmm_initialize_and_withdraw_emit_pool_events() -> Result<()> {
+    let initialize_accounts = accounts(
+        &[
+            (0, "creator"),
+            (3, "pool"),
+            (4, SOL_MINT),
+            (5, TOKEN_MINT),
+            (6, "lp-mint"),
+            (10, "vault-0"),
+            (11, "vault-1"),
+        ],
+        20,
+    );
+    let withdraw_accounts = accounts(
+        &[
+            (0, "creator"),
+            (2, "pool"),
+            (6, "vault-0"),
+            (7, "vault-1"),
+            (10, SOL_MINT),
+            (11, TOKEN_MINT),
+            (12, "lp-mint"),
+        ],
+        14,
+    );
+    let tx = transaction(
+        json!([
+            {
+                "programId": RAYDIUM_CPMM,
+                "accounts": initialize_accounts,
+                "data": instruction_data(INITIALIZE, &[2_000_000_000, 80_000_000, 0]),
+            },
+            {
+                "programId": RAYDIUM_CPMM,
+                "accounts": withdraw_accounts,
+                "data": instruction_data(WITHDRAW, &[1_000_000_000, 0, 0]),
+            },
+        ]),
+        json!([
+            transfer("0-0", "creator-sol", "vault-0", SOL_MINT, 2_000_000_000, 9),
+            transfer("0-1", "creator-token", "vault-1", TOKEN_MINT, 80_000_000, 6),
+            transfer("1-0", "vault-0", "creator-sol", SOL_MINT, 500_000_000, 9),
+            transfer("1-1", "vault-1", "creator-token", TOKEN_MINT, 20_000_000, 6),
+        ]),
+    )?;
+
+    let events = DexParser::new().parse_liquidity(tx, None);
+
+    assert_eq!(events.len(), 2);
+    let create = &events[0];
+    assert_eq!(create.event_type, TradeType::Create);
+    assert_eq!(create.pool_id, "pool");
+    assert_eq!(create.pool_lp_mint.as_deref(), Some("lp-mint"));
+    assert_eq!(create.token0_amount, Some(2.0));
+    assert_eq!(create.token1_amount_raw.as_deref(), Some("80000000"));
+
+    let withdraw = &events[1];
+    assert_eq!(withdraw.event_type, TradeType::Remove);
+    assert_eq!(withdraw.token0_amount, Some(0.5));
+    assert_eq!(withdraw.token1_decimals, Some(6));
+    assert_eq!(withdraw.lp_amount, Some(1.0));
+    Ok(())
+}
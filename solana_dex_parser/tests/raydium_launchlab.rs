@@ -0,0 +1,239 @@
+use anyhow::Result;
+use serde_json::json;
+use solana_dex_parser::types::TradeType;
+use solana_dex_parser::{DexParser, SolanaTransaction};
+
+mod common;
+
+const LAUNCHLAB: &str = "LanMV9sAd7wArD4vJFi2qDdfnVhFxYSUg6eADduJ3uj";
+const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+const TOKEN_MINT: &str = "4wBqpZM9xaSheZzJSMawUKKwhdpChKbZ5eu5ky4Vigw";
+
+const BUY_EXACT_IN: [u8; 8] = [250, 234, 13, 123, 213, 156, 19, 236];
+const MIGRATE_TO_CPSWAP: [u8; 8] = [136, 92, 200, 103, 28, 218, 144, 140];
+const INITIALIZE: [u8; 8] = [175, 175, 109, 31, 13, 152, 155, 237];
+const TRADE_EVENT: [u8; 16] = [
+    228, 69, 165, 46, 81, 203, 154, 29, 189, 219, 127, 211, 78, 230, 97, 238,
+];
+const POOL_CREATE_EVENT: [u8; 16] = [
+    228, 69, 165, 46, 81, 203, 154, 29, 151, 215, 226, 9, 118, 161, 115, 174,
+];
+
+fn accounts(named: &[(usize, &str)], len: usize) -> Vec<String> {
+    (0..len)
+        .map(|i| {
+            named
+                .iter()
+                .find(|(index, _)| *index == i)
+                .map(|(_, name)| name.to_string())
+                .unwrap_or_else(|| format!("account-{i}"))
+        })
+        .collect()
+}
+
+fn pubkey(byte: u8) -> [u8; 32] {
+    [byte; 32]
+}
+
+fn borsh_string(value: &str) -> Vec<u8> {
+    let mut bytes = (value.len() as u32).to_le_bytes().to_vec();
+    bytes.extend_from_slice(value.as_bytes());
+    bytes
+}
+
+fn encode(parts: &[&[u8]]) -> String {
+    bs58::encode(parts.concat()).into_string()
+}
+
+fn transaction(
+    instructions: serde_json::Value,
+    inner: serde_json::Value,
+) -> Result<SolanaTransaction> {
+    common::transaction(
+        "launchlab",
+        json!({
+            "instructions": instructions,
+            "innerInstructions": [{ "index": 0, "instructions": inner }],
+        }),
+    )
+}
+
+fn trade_event_fields() -> Vec<u8> {
+    [
+        793_100_000_000_000u64, // total_base_sell
+        1_073_025_605_596_382,  // virtual_base
+        30_000_852_951,         // virtual_quote
+        0,                      // real_base_before
+        0,                      // real_quote_before
+        35_000_000_000,         // real_base_after
+        1_000_000_000,          // real_quote_after
+        1_000_000_000,          // amount_in
+        35_000_000_000,         // amount_out
+        2_500_000,              // protocol_fee
+        10_000_000,             // platform_fee
+        500_000,                // creator_fee
+        0,                      // share_fee
+    ]
+    .iter()
+    .flat_map(|value| value.to_le_bytes())
+    .collect()
+}
+
+#[test]
+fn launchlab_trade_event_becomes_buy_with_reserves_and_fees() -> Result<()> {
+    let event = encode(&[&TRADE_EVENT, &pubkey(7), &trade_event_fields(), &[0, 0]]);
+    let buy_accounts = accounts(
+        &[
+            (0, "user"),
+            (3, "bonk-platform"),
+            (4, "pool-state"),
+            (9, TOKEN_MINT),
+            (10, SOL_MINT),
+        ],
+        15,
+    );
+    let tx = transaction(
+        json!([{
+            "programId": LAUNCHLAB,
+            "accounts": buy_accounts,
+            "data": encode(&[&BUY_EXACT_IN, &1_000_000_000u64.to_le_bytes()]),
+        }]),
+        json!([{ "programId": LAUNCHLAB, "accounts": [], "data": event }]),
+    )?;
+
+    let result = DexParser::new().parse_all(tx, None);
+
+    assert_eq!(result.meme_events.len(), 1);
+    let buy = &result.meme_events[0];
+    assert_eq!(buy.event_type, TradeType::Buy);
+    assert_eq!(buy.protocol.as_deref(), Some("RaydiumLaunchLab"));
+    assert_eq!(buy.user, "user");
+    assert_eq!(buy.base_mint, TOKEN_MINT);
+    assert_eq!(buy.idx, "0-0");
+    assert_eq!(
+        buy.bonding_curve.as_deref(),
+        Some(bs58::encode(pubkey(7)).into_string().as_str())
+    );
+    assert_eq!(buy.platform_config.as_deref(), Some("bonk-platform"));
+    let input = buy.input_token.as_ref().expect("input token");
+    assert_eq!(input.mint, SOL_MINT);
+    assert_eq!(input.amount, 1.0);
+    let output = buy.output_token.as_ref().expect("output token");
+    assert_eq!(output.amount_raw, "35000000000");
+    assert_eq!(output.decimals, 6);
+    assert_eq!(buy.protocol_fee, Some(2_500_000.0));
+    assert_eq!(buy.platform_fee, Some(10_000_000.0));
+    assert_eq!(buy.creator_fee, Some(500_000.0));
+    assert_eq!(buy.pool_a_reserve, Some(1_072_990_605_596_382.0));
+    assert_eq!(buy.pool_b_reserve, Some(31_000_852_951.0));
+    Ok(())
+}
+
+#[test]
+fn trade_events_read_the_instruction_that_touched_their_pool() -> Result<()> {
+    let pool = bs58::encode(pubkey(7)).into_string();
+    let event = encode(&[&TRADE_EVENT, &pubkey(7), &trade_event_fields(), &[0, 0]]);
+    let buy_accounts = accounts(
+        &[
+            (0, "user"),
+            (3, "bonk-platform"),
+            (4, pool.as_str()),
+            (9, TOKEN_MINT),
+            (10, SOL_MINT),
+        ],
+        15,
+    );
+    // The second event directly follows the first event's self-CPI, not the buy.
+    let tx = transaction(
+        json!([{
+            "programId": LAUNCHLAB,
+            "accounts": buy_accounts,
+            "data": encode(&[&BUY_EXACT_IN, &1_000_000_000u64.to_le_bytes()]),
+        }]),
+        json!([
+            { "programId": LAUNCHLAB, "accounts": ["event-authority"], "data": event },
+            { "programId": LAUNCHLAB, "accounts": ["event-authority"], "data": event },
+        ]),
+    )?;
+
+    let result = DexParser::new().parse_all(tx, None);
+
+    assert_eq!(result.meme_events.len(), 2);
+    assert!(result
+        .meme_events
+        .iter()
+        .all(|event| event.user == "user" && event.base_mint == TOKEN_MINT));
+    Ok(())
+}
+
+#[test]
+fn launchlab_create_and_migrate_are_parsed() -> Result<()> {
+    let create_event = encode(&[
+        &POOL_CREATE_EVENT,
+        &pubkey(1),
+        &pubkey(2),
+        &pubkey(3),
+        &[6],
+        &borsh_string("Bonk Test"),
+        &borsh_string("BTEST"),
+        &borsh_string("https://example.com/btest.json"),
+        &[0],
+        &1_000_000_000_000_000u64.to_le_bytes(),
+    ]);
+    let initialize_accounts = accounts(
+        &[
+            (1, "creator"),
+            (3, "bonk-platform"),
+            (6, TOKEN_MINT),
+            (7, SOL_MINT),
+        ],
+        18,
+    );
+    let migrate_accounts = accounts(
+        &[
+            (0, "migrator"),
+            (1, TOKEN_MINT),
+            (2, SOL_MINT),
+            (5, "cpmm-pool"),
+            (17, "pool-state"),
+        ],
+        30,
+    );
+    let tx = transaction(
+        json!([
+            {
+                "programId": LAUNCHLAB,
+                "accounts": initialize_accounts,
+                "data": encode(&[&INITIALIZE]),
+            },
+            {
+                "programId": LAUNCHLAB,
+                "accounts": migrate_accounts,
+                "data": encode(&[&MIGRATE_TO_CPSWAP]),
+            },
+        ]),
+        json!([{ "programId": LAUNCHLAB, "accounts": [], "data": create_event }]),
+    )?;
+
+    let result = DexParser::new().parse_all(tx, None);
+
+    assert_eq!(result.meme_events.len(), 2);
+    let create = &result.meme_events[0];
+    assert_eq!(create.event_type, TradeType::Create);
+    assert_eq!(create.base_mint, TOKEN_MINT);
+    assert_eq!(create.symbol.as_deref(), Some("BTEST"));
+    assert_eq!(create.decimals, Some(6));
+    assert_eq!(create.total_supply, Some(1_000_000_000_000_000));
+    assert_eq!(create.platform_config.as_deref(), Some("bonk-platform"));
+
+    let migrate = &result.meme_events[1];
+    assert_eq!(migrate.event_type, TradeType::Migrate);
+    assert_eq!(migrate.idx, "1-0");
+    assert_eq!(migrate.bonding_curve.as_deref(), Some("pool-state"));
+    assert_eq!(migrate.pool.as_deref(), Some("cpmm-pool"));
+    assert_eq!(migrate.pool_dex.as_deref(), Some("RaydiumCPMM"));
+
+    assert_eq!(result.migrations.len(), 1);
+    assert_eq!(result.migrations[0].pool.as_deref(), Some("cpmm-pool"));
+    Ok(())
+}
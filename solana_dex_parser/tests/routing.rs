@@ -0,0 +1,111 @@
+use anyhow::Result;
+use serde_json::{json, Value};
+use solana_dex_parser::{DexParser, SolanaTransaction};
+
+mod common;
+
+const RAYDIUM_CPMM: &str = "CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C";
+const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+const TOKEN_MINT: &str = "4wBqpZM9xaSheZzJSMawUKKwhdpChKbZ5eu5ky4Vigw";
+
+const SWAP_BASE_INPUT: [u8; 8] = [143, 190, 90, 218, 196, 30, 51, 222];
+
+fn swap_instruction(pool: &str) -> Value {
+    let accounts: Vec<String> = (0..13)
+        .map(|i| match i {
+            0 => "user".to_string(),
+            3 => pool.to_string(),
+            4 => "user-sol".to_string(),
+            5 => "user-token".to_string(),
+            6 => format!("{pool}-sol"),
+            7 => format!("{pool}-token"),
+            10 => SOL_MINT.to_string(),
+            11 => TOKEN_MINT.to_string(),
+            _ => format!("account-{i}"),
+        })
+        .collect();
+    let mut data = SWAP_BASE_INPUT.to_vec();
+    data.extend_from_slice(&1_000_000_000u64.to_le_bytes());
+    data.extend_from_slice(&1u64.to_le_bytes());
+    json!({
+        "programId": RAYDIUM_CPMM,
+        "accounts": accounts,
+        "data": bs58::encode(data).into_string(),
+    })
+}
+
+fn transfer(idx: &str, source: &str, destination: &str, mint: &str, amount: u64) -> Value {
+    common::transfer(RAYDIUM_CPMM, idx, source, destination, mint, amount, 9)
+}
+
+fn balance(account: &str, mint: &str, amount: u64) -> Value {
+    json!({
+        "account": account,
+        "mint": mint,
+        "uiTokenAmount": { "amount": amount.to_string(), "decimals": 9 },
+    })
+}
+
+/// One SOL into each pool; outputs follow the constant-product curve exactly.
+fn split_route(pools: &[(&str, u64, u64, u64)]) -> Result<SolanaTransaction> {
+    let mut instructions = Vec::new();
+    let mut transfers = Vec::new();
+    let mut balances = Vec::new();
+    for (index, (pool, sol_reserve, token_reserve, token_out)) in pools.iter().enumerate() {
+        instructions.push(swap_instruction(pool));
+        let sol_vault = format!("{pool}-sol");
+        let token_vault = format!("{pool}-token");
+        transfers.push(transfer(
+            &format!("{index}-0"),
+            "user-sol",
+            &sol_vault,
+            SOL_MINT,
+            1_000_000_000,
+        ));
+        transfers.push(transfer(
+            &format!("{index}-1"),
+            &token_vault,
+            "user-token",
+            TOKEN_MINT,
+            *token_out,
+        ));
+        balances.push(balance(&sol_vault, SOL_MINT, *sol_reserve));
+        balances.push(balance(&token_vault, TOKEN_MINT, *token_reserve));
+    }
+    Ok(serde_json::from_value(json!({
+        "slot": 800,
+        "signature": "routing",
+        "blockTime": 1_700_000_000,
+        "signers": ["user"],
+        "instructions": instructions,
+        "transfers": transfers,
+        "preTokenBalances": balances,
+    }))?)
+}
+
+#[test]
+fn split_route_is_compared_against_best_single_pool() -> Result<()> {
+    let tx = split_route(&[
+        ("pool-a", 100_000_000_000, 700_000_000_000, 6_930_693_069),
+        ("pool-b", 50_000_000_000, 350_000_000_000, 6_862_745_098),
+    ])?;
+
+    let result = DexParser::new().parse_all(tx, None);
+
+    assert_eq!(result.trades.len(), 2);
+    let aggregate = result.aggregate_trade.expect("aggregate trade");
+    // Two SOL through pool A alone would have returned 13.725 tokens instead of 13.793.
+    assert_eq!(aggregate.routing_improvement_bps, Some(50));
+    Ok(())
+}
+
+#[test]
+fn single_hop_has_no_routing_improvement() -> Result<()> {
+    let tx = split_route(&[("pool-a", 100_000_000_000, 700_000_000_000, 6_930_693_069)])?;
+
+    let result = DexParser::new().parse_all(tx, None);
+
+    let aggregate = result.aggregate_trade.expect("aggregate trade");
+    assert_eq!(aggregate.routing_improvement_bps, None);
+    Ok(())
+}
@@ -0,0 +1,371 @@
+use std::fs;
+use std::str::FromStr;
+
+use anyhow::Result;
+use base64::{engine::general_purpose::STANDARD as BASE64_STANDARD, Engine as _};
+use serde_json::{json, Value};
+use solana_account_decoder::UiAccount;
+use solana_client::rpc_response::RpcSimulateTransactionResult;
+use solana_dex_parser::core::transaction_adapter::TransactionAdapter;
+use solana_dex_parser::rpc;
+use solana_dex_parser::types::SolanaTransaction;
+use solana_dex_parser::{DexParser, ParseConfig, SimulationContext};
+use solana_sdk::hash::Hash;
+use solana_sdk::instruction::CompiledInstruction;
+use solana_sdk::message::v0::{self, LoadedAddresses, MessageAddressTableLookup};
+use solana_sdk::message::{MessageHeader, VersionedMessage};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_sdk::transaction::VersionedTransaction;
+use solana_transaction_status::{
+    InnerInstruction, InnerInstructions, TransactionStatusMeta, TransactionTokenBalance,
+};
+
+#[test]
+fn normalized_transaction_survives_rpc_round_trip() -> Result<()> {
+    let tx_data = fs::read_to_string("tests/fixtures/sample_tx.json")?;
+    let tx: SolanaTransaction = serde_json::from_str(&tx_data)?;
+
+    let encoded = serde_json::to_value(rpc::transaction_to_rpc(&tx)?)?;
+    let decoded = rpc::transaction_from_rpc_json(encoded)?;
+
+    assert_eq!(decoded.signature, tx.signature);
+    assert_eq!(decoded.slot, tx.slot);
+    assert_eq!(decoded.block_time, tx.block_time);
+    assert_eq!(decoded.signers, tx.signers);
+    assert_eq!(decoded.instructions, tx.instructions);
+    assert_eq!(decoded.inner_instructions, tx.inner_instructions);
+    assert_eq!(decoded.pre_token_balances, tx.pre_token_balances);
+    assert_eq!(decoded.post_token_balances, tx.post_token_balances);
+    assert_eq!(decoded.meta.fee, tx.meta.fee);
+    assert_eq!(decoded.meta.compute_units, tx.meta.compute_units);
+    assert_eq!(decoded.meta.status, tx.meta.status);
+    assert_eq!(
+        decoded.meta.sol_balance_changes,
+        tx.meta.sol_balance_changes
+    );
+    Ok(())
+}
+
+/// Raydium CLMM swap from mainnet slot 344556402, a v0 transaction loading the rent sysvar
+/// and Token-2022 program from a lookup table.
+const V0_LOOKUP: &str = "tests/fixtures/raydium_clmm_v0_lookup.json";
+const CLMM_PROGRAM: &str = "CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK";
+const CLMM_POOL: &str = "9iFER3bpjf1PTTCQCfTRu17EJgvsxo9pVyA9QWwEuX4x";
+const TOKEN_2022: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+const RENT_SYSVAR: &str = "SysvarRent111111111111111111111111111111111";
+
+#[test]
+fn v0_lookup_table_accounts_resolve_in_combined_key_order() -> Result<()> {
+    let payload: Value = serde_json::from_str(&fs::read_to_string(V0_LOOKUP)?)?;
+    let tx = rpc::transaction_from_rpc_json(payload)?;
+
+    let swap = &tx.instructions[4];
+    assert_eq!(swap.program_id, CLMM_PROGRAM);
+    assert_eq!(swap.accounts.len(), 15);
+    assert_eq!(swap.accounts[1], CLMM_POOL);
+    assert_eq!(swap.accounts[9], TOKEN_2022);
+    assert_eq!(tx.instructions[3].accounts[3], RENT_SYSVAR);
+
+    let transfers = &tx.inner_instructions[0];
+    assert_eq!(transfers.index, 4);
+    assert_eq!(transfers.instructions.len(), 2);
+    assert_eq!(transfers.instructions[0].stack_height, Some(2));
+
+    let result = DexParser::new().parse_all(tx, None);
+    assert!(result
+        .programs_invoked
+        .iter()
+        .any(|program| program.program_id == CLMM_PROGRAM));
+    Ok(())
+}
+
+#[test]
+fn unresolved_lookup_accounts_are_an_error() -> Result<()> {
+    let mut payload: Value = serde_json::from_str(&fs::read_to_string(V0_LOOKUP)?)?;
+    payload["meta"]
+        .as_object_mut()
+        .expect("meta object")
+        .remove("loadedAddresses");
+
+    let err = rpc::transaction_from_rpc_json(payload).unwrap_err();
+    assert!(format!("{err:#}").contains("account index 18"), "{err:#}");
+    Ok(())
+}
+
+fn pubkey(value: &Value) -> Pubkey {
+    Pubkey::from_str(value.as_str().unwrap()).unwrap()
+}
+
+fn pubkeys(value: &Value) -> Vec<Pubkey> {
+    value.as_array().unwrap().iter().map(pubkey).collect()
+}
+
+fn u8s(value: &Value) -> Vec<u8> {
+    value
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item.as_u64().unwrap() as u8)
+        .collect()
+}
+
+fn u64s(value: &Value) -> Vec<u64> {
+    value
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item.as_u64().unwrap())
+        .collect()
+}
+
+fn compiled(ix: &Value) -> CompiledInstruction {
+    CompiledInstruction {
+        program_id_index: ix["programIdIndex"].as_u64().unwrap() as u8,
+        accounts: u8s(&ix["accounts"]),
+        data: bs58::decode(ix["data"].as_str().unwrap())
+            .into_vec()
+            .unwrap(),
+    }
+}
+
+fn token_balances(value: &Value) -> Option<Vec<TransactionTokenBalance>> {
+    Some(
+        value
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|balance| TransactionTokenBalance {
+                account_index: balance["accountIndex"].as_u64().unwrap() as u8,
+                mint: balance["mint"].as_str().unwrap().to_string(),
+                ui_token_amount: serde_json::from_value(balance["uiTokenAmount"].clone()).unwrap(),
+                owner: balance["owner"].as_str().unwrap().to_string(),
+                program_id: balance["programId"].as_str().unwrap().to_string(),
+            })
+            .collect(),
+    )
+}
+
+/// Wire bytes and status meta of a `getTransaction` JSON payload of a v0 transaction.
+fn wire_from_rpc_json(payload: &Value) -> (Vec<u8>, TransactionStatusMeta) {
+    let message = &payload["transaction"]["message"];
+    let header = &message["header"];
+    let meta = &payload["meta"];
+    let transaction = VersionedTransaction {
+        signatures: payload["transaction"]["signatures"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|signature| Signature::from_str(signature.as_str().unwrap()).unwrap())
+            .collect(),
+        message: VersionedMessage::V0(v0::Message {
+            header: MessageHeader {
+                num_required_signatures: header["numRequiredSignatures"].as_u64().unwrap() as u8,
+                num_readonly_signed_accounts: header["numReadonlySignedAccounts"].as_u64().unwrap()
+                    as u8,
+                num_readonly_unsigned_accounts: header["numReadonlyUnsignedAccounts"]
+                    .as_u64()
+                    .unwrap() as u8,
+            },
+            account_keys: pubkeys(&message["accountKeys"]),
+            recent_blockhash: Hash::from_str(message["recentBlockhash"].as_str().unwrap()).unwrap(),
+            instructions: message["instructions"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(compiled)
+                .collect(),
+            address_table_lookups: message["addressTableLookups"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|lookup| MessageAddressTableLookup {
+                    account_key: pubkey(&lookup["accountKey"]),
+                    writable_indexes: u8s(&lookup["writableIndexes"]),
+                    readonly_indexes: u8s(&lookup["readonlyIndexes"]),
+                })
+                .collect(),
+        }),
+    };
+    let meta = TransactionStatusMeta {
+        status: Ok(()),
+        fee: meta["fee"].as_u64().unwrap(),
+        pre_balances: u64s(&meta["preBalances"]),
+        post_balances: u64s(&meta["postBalances"]),
+        inner_instructions: Some(
+            meta["innerInstructions"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|set| InnerInstructions {
+                    index: set["index"].as_u64().unwrap() as u8,
+                    instructions: set["instructions"]
+                        .as_array()
+                        .unwrap()
+                        .iter()
+                        .map(|ix| InnerInstruction {
+                            instruction: compiled(ix),
+                            stack_height: ix["stackHeight"].as_u64().map(|h| h as u32),
+                        })
+                        .collect(),
+                })
+                .collect(),
+        ),
+        log_messages: serde_json::from_value(meta["logMessages"].clone()).unwrap(),
+        pre_token_balances: token_balances(&meta["preTokenBalances"]),
+        post_token_balances: token_balances(&meta["postTokenBalances"]),
+        rewards: None,
+        loaded_addresses: LoadedAddresses {
+            writable: pubkeys(&meta["loadedAddresses"]["writable"]),
+            readonly: pubkeys(&meta["loadedAddresses"]["readonly"]),
+        },
+        return_data: None,
+        compute_units_consumed: meta["computeUnitsConsumed"].as_u64(),
+    };
+    (bincode::serialize(&transaction).unwrap(), meta)
+}
+
+#[test]
+fn wire_transaction_decodes_like_the_rpc_payload() -> Result<()> {
+    let payload: Value = serde_json::from_str(&fs::read_to_string(V0_LOOKUP)?)?;
+    let expected = rpc::transaction_from_rpc_json(payload.clone())?;
+    let (bytes, meta) = wire_from_rpc_json(&payload);
+    let slot = payload["slot"].as_u64().unwrap();
+    let block_time = payload["blockTime"].as_i64();
+
+    let tx = rpc::transaction_from_base64(
+        &BASE64_STANDARD.encode(&bytes),
+        meta.clone(),
+        slot,
+        block_time,
+    )?;
+
+    assert_eq!(tx, expected);
+    assert_eq!(tx.instructions[4].accounts[9], TOKEN_2022);
+
+    let adapter =
+        TransactionAdapter::from_wire(&bytes, meta, slot, block_time, ParseConfig::default())?;
+    assert_eq!(adapter.signature(), expected.signature);
+    assert_eq!(adapter.instructions(), expected.instructions.as_slice());
+    Ok(())
+}
+
+#[test]
+fn truncated_wire_transaction_is_an_error() {
+    let payload: Value = serde_json::from_str(&fs::read_to_string(V0_LOOKUP).unwrap()).unwrap();
+    let (bytes, meta) = wire_from_rpc_json(&payload);
+
+    assert!(rpc::transaction_from_wire(&bytes[..bytes.len() / 2], meta, 0, None).is_err());
+}
+
+/// jsonParsed account state of the token account at `index` in `balances`, as
+/// `simulateTransaction` and `getMultipleAccounts` return it.
+fn parsed_token_account(balances: &Value, index: u64, lamports: u64) -> Option<UiAccount> {
+    let balance = balances
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|balance| balance["accountIndex"] == index)?;
+    Some(
+        serde_json::from_value(json!({
+            "lamports": lamports,
+            "data": {
+                "program": "spl-token",
+                "parsed": {
+                    "type": "account",
+                    "info": {
+                        "mint": balance["mint"],
+                        "owner": balance["owner"],
+                        "tokenAmount": balance["uiTokenAmount"],
+                        "state": "initialized",
+                        "isNative": false,
+                    },
+                },
+                "space": 165,
+            },
+            "owner": balance["programId"],
+            "executable": false,
+            "rentEpoch": 0,
+            "space": 165,
+        }))
+        .unwrap(),
+    )
+}
+
+#[test]
+fn simulation_parses_like_the_landed_transaction() -> Result<()> {
+    let payload: Value = serde_json::from_str(&fs::read_to_string(V0_LOOKUP)?)?;
+    let expected = rpc::transaction_from_rpc_json(payload.clone())?;
+    let (bytes, landed_meta) = wire_from_rpc_json(&payload);
+    let transaction: VersionedTransaction = bincode::deserialize(&bytes)?;
+    let meta = &payload["meta"];
+
+    // Ask for the fee payer and every token account the transaction touches.
+    let mut account_keys = pubkeys(&payload["transaction"]["message"]["accountKeys"]);
+    account_keys.extend(&landed_meta.loaded_addresses.writable);
+    account_keys.extend(&landed_meta.loaded_addresses.readonly);
+    let mut indexes = vec![0];
+    for balance in meta["postTokenBalances"].as_array().unwrap() {
+        indexes.push(balance["accountIndex"].as_u64().unwrap());
+    }
+    let state = |balances: &str, lamports: &str, index: u64| {
+        let lamports = meta[lamports][index as usize].as_u64().unwrap();
+        parsed_token_account(&meta[balances], index, lamports).or_else(|| {
+            serde_json::from_value(json!({
+                "lamports": lamports,
+                "data": ["", "base64"],
+                "owner": "11111111111111111111111111111111",
+                "executable": false,
+                "rentEpoch": 0,
+            }))
+            .ok()
+        })
+    };
+    let context = SimulationContext {
+        addresses: indexes
+            .iter()
+            .map(|index| account_keys[*index as usize].to_string())
+            .collect(),
+        pre_accounts: indexes
+            .iter()
+            .map(|index| state("preTokenBalances", "preBalances", *index))
+            .collect(),
+        loaded_addresses: landed_meta.loaded_addresses.clone(),
+        slot: expected.slot,
+    };
+    let simulation = RpcSimulateTransactionResult {
+        err: None,
+        logs: serde_json::from_value(meta["logMessages"].clone())?,
+        accounts: Some(
+            indexes
+                .iter()
+                .map(|index| state("postTokenBalances", "postBalances", *index))
+                .collect(),
+        ),
+        units_consumed: meta["computeUnitsConsumed"].as_u64(),
+        return_data: None,
+        inner_instructions: serde_json::from_value(meta["innerInstructions"].clone())?,
+    };
+
+    let tx = rpc::transaction_from_simulation(transaction.clone(), &simulation, &context)?;
+
+    assert_eq!(tx.signature, expected.signature);
+    assert_eq!(tx.instructions, expected.instructions);
+    assert_eq!(tx.inner_instructions, expected.inner_instructions);
+    assert_eq!(tx.pre_token_balances, expected.pre_token_balances);
+    assert_eq!(tx.post_token_balances, expected.post_token_balances);
+    assert_eq!(tx.meta.fee, expected.meta.fee);
+    assert_eq!(tx.meta.compute_units, expected.meta.compute_units);
+    for (address, change) in &tx.meta.sol_balance_changes {
+        assert_eq!(expected.meta.sol_balance_changes.get(address), Some(change));
+    }
+
+    let parser = DexParser::new();
+    let predicted = parser.parse_simulation(transaction, &simulation, &context, None)?;
+    let landed = parser.parse_all(expected, None);
+    assert_eq!(predicted.programs_invoked, landed.programs_invoked);
+    assert_eq!(predicted.trades, landed.trades);
+    assert_eq!(predicted.transfers, landed.transfers);
+    assert_eq!(predicted.fee, landed.fee);
+    Ok(())
+}
@@ -2,10 +2,9 @@ use anyhow::Result;
 use serde_json::Value;
 use solana_dex_parser::{DexParser, ParseConfig};
 
-#[path = "common/mod.rs"]
-mod rpc_helpers;
+mod common;
 
-use rpc_helpers::fetch_transaction_with_fallback;
+use common::fetch_transaction_with_fallback;
 
 const DEFAULT_SIGNATURE: &str =
     "b15toBqDHKvVy7KQeAMDiEfinqg4Y8tDorUNHBd4FVojvqGyvZMELVkAz5BrNrc9AiA1zvRAZ9FfWM7qjWUQW9u";
@@ -0,0 +1,246 @@
+#![cfg(feature = "rpc")]
+
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::thread;
+use std::time::Duration;
+
+use futures::StreamExt;
+use serde_json::{json, Value};
+use solana_dex_parser::{BackfillOptions, DexParser, RetryPolicy};
+
+const CLMM: &str = "CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK";
+const VOTE: &str = "Vote111111111111111111111111111111111111111";
+
+fn quick_retry(attempts: u32) -> RetryPolicy {
+    RetryPolicy {
+        attempts,
+        initial_backoff: Duration::from_millis(1),
+        max_backoff: Duration::from_millis(5),
+    }
+}
+
+/// Answer of the mock node to one request.
+enum Reply {
+    Result(Value),
+    Error(i64, &'static str),
+    Unavailable,
+}
+
+/// JSON-RPC endpoint answering requests with `handler(params)`.
+fn serve(mut handler: impl FnMut(&Value) -> Reply + Send + 'static) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                if let Some((name, value)) = line.split_once(':') {
+                    if name.eq_ignore_ascii_case("content-length") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            let request: Value = serde_json::from_slice(&body).unwrap();
+
+            let reply = match request["method"].as_str() {
+                // The client checks the node version before asking for v0 transactions.
+                Some("getVersion") => {
+                    Reply::Result(json!({"solana-core": "1.18.26", "feature-set": 0}))
+                }
+                _ => handler(&request["params"]),
+            };
+            let body = match reply {
+                Reply::Result(result) => {
+                    json!({"jsonrpc": "2.0", "id": request["id"], "result": result})
+                }
+                Reply::Error(code, message) => json!({
+                    "jsonrpc": "2.0",
+                    "id": request["id"],
+                    "error": {"code": code, "message": message},
+                }),
+                Reply::Unavailable => {
+                    let response = "HTTP/1.1 503 Service Unavailable\r\ncontent-length: 0\r\nconnection: close\r\n\r\n";
+                    stream.write_all(response.as_bytes()).unwrap();
+                    continue;
+                }
+            }
+            .to_string();
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            stream.write_all(response.as_bytes()).unwrap();
+        }
+    });
+    url
+}
+
+/// Endpoint failing its first `failures` requests with 503, then answering them with `result`.
+fn flaky(result: Value, failures: usize) -> String {
+    let mut failed = 0;
+    serve(move |_| {
+        if failed < failures {
+            failed += 1;
+            Reply::Unavailable
+        } else {
+            Reply::Result(result.clone())
+        }
+    })
+}
+
+fn fixture() -> Value {
+    serde_json::from_str(&fs::read_to_string("tests/fixtures/raydium_clmm_v0_lookup.json").unwrap())
+        .unwrap()
+}
+
+fn signature_of(payload: &Value) -> String {
+    payload["transaction"]["signatures"][0]
+        .as_str()
+        .unwrap()
+        .to_string()
+}
+
+/// `getBlock` result with a vote transaction ahead of the CLMM swap.
+fn block() -> Value {
+    let swap = fixture();
+    let mut vote = swap.clone();
+    vote["transaction"]["message"]["accountKeys"][10] = json!(VOTE);
+    vote["transaction"]["signatures"][0] = json!("1".repeat(64));
+    let entry = |payload: &Value| {
+        json!({
+            "transaction": payload["transaction"],
+            "meta": payload["meta"],
+            "version": payload["version"],
+        })
+    };
+    json!({
+        "previousBlockhash": "11111111111111111111111111111111",
+        "blockhash": "11111111111111111111111111111111",
+        "parentSlot": 99,
+        "transactions": [entry(&vote), entry(&swap)],
+        "blockTime": 1_700_000_000,
+        "blockHeight": 90,
+    })
+}
+
+#[tokio::test]
+async fn parse_signature_retries_failed_requests() {
+    let payload = fixture();
+    let signature = signature_of(&payload);
+    let url = flaky(payload, 2);
+
+    let result = DexParser::new()
+        .parse_signature_with(&url, &signature, None, &quick_retry(3))
+        .await
+        .unwrap();
+
+    assert_eq!(result.signature, signature);
+    assert!(result
+        .programs_invoked
+        .iter()
+        .any(|program| program.program_id == CLMM));
+}
+
+#[tokio::test]
+async fn parse_signature_gives_up_after_the_last_attempt() {
+    let payload = fixture();
+    let signature = signature_of(&payload);
+    let url = flaky(payload, 2);
+
+    let err = DexParser::new()
+        .parse_signature_with(&url, &signature, None, &quick_retry(2))
+        .await
+        .unwrap_err();
+
+    assert!(err.to_string().contains("after 2 attempts"), "{err}");
+}
+
+#[tokio::test]
+async fn invalid_signature_is_not_fetched() {
+    let err = DexParser::new()
+        .parse_signature("http://127.0.0.1:9", "not-a-signature")
+        .await
+        .unwrap_err();
+
+    assert_eq!(err.to_string(), "invalid signature");
+}
+
+#[test]
+fn backoff_doubles_up_to_the_cap() {
+    let retry = RetryPolicy::default();
+
+    assert_eq!(retry.backoff(0), Duration::from_millis(500));
+    assert_eq!(retry.backoff(2), Duration::from_secs(2));
+    assert_eq!(retry.backoff(10), Duration::from_secs(4));
+}
+
+#[tokio::test]
+async fn fetched_block_leaves_out_vote_transactions() {
+    let url = flaky(block(), 1);
+
+    let result = DexParser::new()
+        .fetch_and_parse_block(&url, 100)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(result.slot, 100);
+    assert_eq!(result.transactions.len(), 1);
+    let swap = &result.transactions[0];
+    assert_eq!(swap.signature, signature_of(&fixture()));
+    assert_eq!(swap.block_context.as_ref().unwrap().transaction_index, 1);
+}
+
+#[tokio::test]
+async fn skipped_slot_is_not_retried() {
+    let mut requests = 0;
+    let url = serve(move |_| {
+        requests += 1;
+        assert_eq!(requests, 1, "skipped slot was requested again");
+        Reply::Error(-32007, "Slot 100 was skipped")
+    });
+
+    let result = DexParser::new()
+        .fetch_and_parse_block(&url, 100)
+        .await
+        .unwrap();
+
+    assert!(result.is_none());
+}
+
+#[tokio::test]
+async fn backfill_streams_blocks_in_slot_order() {
+    let url = serve(|params| match params[0].as_u64() {
+        Some(101) => Reply::Error(-32007, "Slot 101 was skipped"),
+        Some(103) => Reply::Unavailable,
+        _ => Reply::Result(block()),
+    });
+    let options = BackfillOptions {
+        concurrency: 3,
+        retry: quick_retry(2),
+        config: None,
+    };
+
+    let parser = DexParser::new();
+    let results: Vec<_> = parser.backfill(&url, 100..104, &options).collect().await;
+
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0].as_ref().unwrap().slot, 100);
+    assert_eq!(results[1].as_ref().unwrap().slot, 102);
+    let err = results[2].as_ref().unwrap_err();
+    assert!(
+        err.to_string().contains("block 103 after 2 attempts"),
+        "{err}"
+    );
+}
@@ -0,0 +1,221 @@
+use anyhow::Result;
+use serde_json::{json, Value};
+use solana_dex_parser::types::TradeType;
+use solana_dex_parser::{DexParser, SolanaTransaction};
+
+mod common;
+
+const SABER: &str = "SSwpkEEcbUqx4vtoEByFjSkhKdCT5PRxhsJq6Nf1ppq";
+const DECIMAL_WRAPPER: &str = "DecZY86MU5Gj7kppfUCEmd4LbXXuyZH1yHaP2NTqdiZB";
+const USDC_MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+const USDT_MINT: &str = "Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB";
+const WRAPPED_USDC: &str = "wrapped-usdc-mint";
+const WRAPPED_USDT: &str = "wrapped-usdt-mint";
+
+const WRAPPER_DEPOSIT: [u8; 8] = [242, 35, 198, 137, 82, 225, 242, 182];
+const WRAPPER_WITHDRAW: [u8; 8] = [183, 18, 70, 156, 148, 109, 161, 34];
+
+fn transfer(
+    program_id: &str,
+    idx: &str,
+    source: &str,
+    destination: &str,
+    mint: &str,
+    amount: u64,
+    decimals: u8,
+) -> Value {
+    json!({
+        "type": "transferChecked",
+        "programId": program_id,
+        "info": {
+            "destination": destination,
+            "mint": mint,
+            "source": source,
+            "tokenAmount": {
+                "amount": amount.to_string(),
+                "uiAmount": amount as f64 / 10f64.powi(decimals as i32),
+                "decimals": decimals,
+            },
+        },
+        "idx": idx,
+        "timestamp": 1_700_000_000,
+        "signature": "saber",
+        "isFee": false,
+    })
+}
+
+fn wrapper_instruction(discriminator: [u8; 8], wrapper_mint: &str, side: &str) -> Value {
+    let mut data = discriminator.to_vec();
+    data.extend_from_slice(&0u64.to_le_bytes());
+    json!({
+        "programId": DECIMAL_WRAPPER,
+        "accounts": [
+            format!("wrapper-{side}"),
+            wrapper_mint,
+            format!("wrapper-reserve-{side}"),
+            "user",
+            format!("user-{side}"),
+            format!("user-wrapped-{side}"),
+            "token-program",
+        ],
+        "data": bs58::encode(data).into_string(),
+    })
+}
+
+fn saber_instruction(tag: u8, args: &[u64], accounts: &[&str]) -> Value {
+    let mut data = vec![tag];
+    args.iter()
+        .for_each(|arg| data.extend_from_slice(&arg.to_le_bytes()));
+    json!({
+        "programId": SABER,
+        "accounts": accounts,
+        "data": bs58::encode(data).into_string(),
+    })
+}
+
+fn transaction(instructions: Value, transfers: Value) -> Result<SolanaTransaction> {
+    common::transaction(
+        "saber",
+        json!({
+            "instructions": instructions,
+            "transfers": transfers,
+        }),
+    )
+}
+
+#[test]
+fn wrapped_swap_is_reported_in_underlying_mints() -> Result<()> {
+    let tx = transaction(
+        json!([
+            wrapper_instruction(WRAPPER_DEPOSIT, WRAPPED_USDC, "usdc"),
+            saber_instruction(
+                1,
+                &[1_000_000_000, 990_000_000],
+                &[
+                    "swap",
+                    "swap-authority",
+                    "user",
+                    "user-wrapped-usdc",
+                    "reserve-wrapped-usdc",
+                    "reserve-wrapped-usdt",
+                    "user-wrapped-usdt",
+                    "admin-fee-usdt",
+                    "token-program",
+                ],
+            ),
+            wrapper_instruction(WRAPPER_WITHDRAW, WRAPPED_USDT, "usdt"),
+        ]),
+        json!([
+            transfer(
+                DECIMAL_WRAPPER,
+                "0-0",
+                "user-usdc",
+                "wrapper-reserve-usdc",
+                USDC_MINT,
+                1_000_000,
+                6,
+            ),
+            transfer(
+                SABER,
+                "1-0",
+                "user-wrapped-usdc",
+                "reserve-wrapped-usdc",
+                WRAPPED_USDC,
+                1_000_000_000,
+                9,
+            ),
+            transfer(
+                SABER,
+                "1-1",
+                "reserve-wrapped-usdt",
+                "user-wrapped-usdt",
+                WRAPPED_USDT,
+                999_000_000,
+                9,
+            ),
+            transfer(
+                DECIMAL_WRAPPER,
+                "2-0",
+                "wrapper-reserve-usdt",
+                "user-usdt",
+                USDT_MINT,
+                999_000,
+                6,
+            ),
+        ]),
+    )?;
+
+    let trades = DexParser::new().parse_trades(tx, None);
+
+    assert_eq!(trades.len(), 1);
+    let trade = &trades[0];
+    assert_eq!(trade.trade_type, TradeType::Swap);
+    assert_eq!(trade.program_id.as_deref(), Some(SABER));
+    assert_eq!(trade.pool, ["swap"]);
+    assert_eq!(trade.user.as_deref(), Some("user"));
+    assert_eq!(trade.input_token.mint, USDC_MINT);
+    assert_eq!(trade.input_token.amount_raw, "1000000");
+    assert_eq!(trade.input_token.decimals, 6);
+    assert_eq!(trade.output_token.mint, USDT_MINT);
+    assert_eq!(trade.output_token.amount, 0.999);
+    Ok(())
+}
+
+#[test]
+fn withdraw_reports_both_reserves_and_burned_pool_tokens() -> Result<()> {
+    let tx = transaction(
+        json!([saber_instruction(
+            3,
+            &[2_000_000, 1, 1],
+            &[
+                "swap",
+                "swap-authority",
+                "user",
+                "pool-mint",
+                "user-lp",
+                "reserve-usdc",
+                "reserve-usdt",
+                "user-usdc",
+                "user-usdt",
+                "admin-fee-usdc",
+                "admin-fee-usdt",
+                "token-program",
+            ],
+        )]),
+        json!([
+            transfer(
+                SABER,
+                "0-0",
+                "reserve-usdc",
+                "user-usdc",
+                USDC_MINT,
+                1_200_000,
+                6
+            ),
+            transfer(
+                SABER,
+                "0-1",
+                "reserve-usdt",
+                "user-usdt",
+                USDT_MINT,
+                800_000,
+                6
+            ),
+        ]),
+    )?;
+
+    let result = DexParser::new().parse_all(tx, None);
+
+    assert!(result.trades.is_empty());
+    assert_eq!(result.liquidities.len(), 1);
+    let event = &result.liquidities[0];
+    assert_eq!(event.event_type, TradeType::Remove);
+    assert_eq!(event.pool_id, "swap");
+    assert_eq!(event.pool_lp_mint.as_deref(), Some("pool-mint"));
+    assert_eq!(event.token0_mint.as_deref(), Some(USDC_MINT));
+    assert_eq!(event.token0_amount, Some(1.2));
+    assert_eq!(event.token1_mint.as_deref(), Some(USDT_MINT));
+    assert_eq!(event.token1_amount_raw.as_deref(), Some("800000"));
+    assert_eq!(event.lp_amount_raw.as_deref(), Some("2000000"));
+    Ok(())
+}
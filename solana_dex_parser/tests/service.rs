@@ -0,0 +1,58 @@
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::sync::Arc;
+
+use anyhow::Result;
+use serde_json::Value;
+use solana_dex_parser::service::{AdminServer, ServiceStats};
+use solana_dex_parser::ParseResult;
+
+fn get(server: &AdminServer, path: &str) -> Result<(String, String)> {
+    let mut stream = TcpStream::connect(server.local_addr())?;
+    write!(stream, "GET {path} HTTP/1.1\r\nHost: localhost\r\n\r\n")?;
+    let mut response = String::new();
+    stream.read_to_string(&mut response)?;
+    let (head, body) = response.split_once("\r\n\r\n").unwrap_or((&response, ""));
+    let status = head.lines().next().unwrap_or_default().to_string();
+    Ok((status, body.to_string()))
+}
+
+#[test]
+fn admin_endpoint_reports_readiness_and_status() -> Result<()> {
+    let stats = Arc::new(ServiceStats::new());
+    let server = AdminServer::start("127.0.0.1:0", stats.clone())?;
+
+    assert_eq!(get(&server, "/healthz")?.0, "HTTP/1.1 200 OK");
+    assert_eq!(
+        get(&server, "/readyz")?.0,
+        "HTTP/1.1 503 Service Unavailable"
+    );
+
+    stats.set_ready(true);
+    stats.record(&ParseResult {
+        slot: 250_000_000,
+        ..ParseResult::new()
+    });
+    stats.record(&ParseResult {
+        slot: 249_999_999,
+        state: false,
+        ..ParseResult::new()
+    });
+    stats.record_failure();
+    stats.set_cache_size("priceCache", 12);
+
+    assert_eq!(get(&server, "/readyz")?.0, "HTTP/1.1 200 OK");
+    let (status, body) = get(&server, "/status")?;
+    assert_eq!(status, "HTTP/1.1 200 OK");
+    let status: Value = serde_json::from_str(&body)?;
+    assert_eq!(status["ready"], true);
+    assert_eq!(status["lastSlot"], 250_000_000);
+    assert_eq!(status["processed"], 3);
+    assert_eq!(status["failed"], 2);
+    assert_eq!(status["caches"]["priceCache"], 12);
+    assert!((status["errorRate"].as_f64().unwrap_or_default() - 2.0 / 3.0).abs() < 1e-9);
+
+    assert_eq!(get(&server, "/metrics")?.0, "HTTP/1.1 404 Not Found");
+    server.shutdown();
+    Ok(())
+}
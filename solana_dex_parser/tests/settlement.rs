@@ -0,0 +1,94 @@
+use std::fs;
+
+use anyhow::Result;
+use serde_json::json;
+use solana_dex_parser::core::settlement::SettlementFlows;
+use solana_dex_parser::core::transaction_adapter::TransactionAdapter;
+use solana_dex_parser::{rpc, DexParser, ParseConfig, SolanaTransaction};
+
+const UNKNOWN_DEX: &str = "MMkrTkeLwWm1SYq5ZmxafstTpYx9Gm6WNW9SW8kYSAc";
+const OPENBOOK_V1: &str = "srmqPvymJeFKQ4zGQed1GFppgkRHL9kaELCbyksJtPX";
+const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+const USDC_MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+
+/// Hand-built crank and settle transactions, under `tests/fixtures`: no recorded mainnet
+/// crank is available yet, so these only mirror the instruction layout of one.
+fn fixture(name: &str) -> Result<SolanaTransaction> {
+    let data = fs::read_to_string(format!("tests/fixtures/{name}"))?;
+    Ok(serde_json::from_str(&data)?)
+}
+
+#[test]
+fn market_maker_settle_funds_is_not_a_swap() -> Result<()> {
+    let result = DexParser::new().parse_all(fixture("openbook_v1_mm_settle_funds.json")?, None);
+
+    assert!(result.trades.is_empty());
+    assert_eq!(result.transfers.len(), 2);
+    Ok(())
+}
+
+#[test]
+fn crank_and_settle_instructions_are_not_swaps() -> Result<()> {
+    let result = DexParser::new().parse_all(fixture("serum_v3_crank.json")?, None);
+
+    assert!(result.trades.is_empty());
+    assert_eq!(result.transfers.len(), 2);
+    Ok(())
+}
+
+/// Raydium v4 swap from mainnet slot 344556402, whose AMM passes its OpenBook v1 market
+/// program and market along without cranking or settling it.
+#[test]
+fn amm_swap_through_an_orderbook_market_is_not_a_settlement() -> Result<()> {
+    let payload = serde_json::from_str(&fs::read_to_string(
+        "tests/fixtures/raydium_v4_openbook_market_swap.json",
+    )?)?;
+    let tx = rpc::transaction_from_rpc_json(payload)?;
+    let swap = &tx.instructions[2];
+    assert_eq!(swap.accounts[7], OPENBOOK_V1);
+
+    let adapter = TransactionAdapter::new(tx, ParseConfig::default());
+    assert!(SettlementFlows::new(&adapter).is_empty());
+    Ok(())
+}
+
+#[test]
+fn unknown_dex_swaps_are_still_detected() -> Result<()> {
+    let transfer = |idx: &str, source: &str, destination: &str, mint: &str, amount: &str| {
+        let decimals = if mint == USDC_MINT { 6 } else { 9 };
+        json!({
+            "type": "transfer",
+            "programId": UNKNOWN_DEX,
+            "info": {
+                "authority": source,
+                "destination": destination,
+                "mint": mint,
+                "source": source,
+                "tokenAmount": { "amount": amount, "decimals": decimals },
+            },
+            "idx": idx,
+            "timestamp": 1_705_000_000,
+            "signature": "unknown-dex-swap",
+            "isFee": false,
+        })
+    };
+    let tx: SolanaTransaction = serde_json::from_value(json!({
+        "slot": 245_120_400,
+        "signature": "unknown-dex-swap",
+        "blockTime": 1_705_000_000,
+        "signers": ["trader"],
+        "instructions": [{ "programId": UNKNOWN_DEX, "accounts": ["trader"], "data": "BPFcE" }],
+        "innerInstructions": [],
+        "transfers": [
+            transfer("0-0", "trader-quote", "pool-quote", USDC_MINT, "96000000"),
+            transfer("0-1", "pool-base", "trader-base", SOL_MINT, "1000000000"),
+        ],
+    }))?;
+
+    let trades = DexParser::new().parse_trades(tx, None);
+
+    assert_eq!(trades.len(), 1);
+    assert_eq!(trades[0].input_token.mint, USDC_MINT);
+    assert_eq!(trades[0].output_token.mint, SOL_MINT);
+    Ok(())
+}
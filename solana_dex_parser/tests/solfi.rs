@@ -0,0 +1,127 @@
+use anyhow::Result;
+use serde_json::{json, Value};
+use solana_dex_parser::types::TradeType;
+use solana_dex_parser::{DexParser, SolanaTransaction};
+
+mod common;
+
+const SOLFI: &str = "SoLFiHG9TfgtdUXUjWAxi3LtvYuFyDLVhBWxdMZxyCe";
+const JUPITER: &str = "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4";
+const TOKEN_PROGRAM: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+const USDC_MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+
+const SWAP_EVENT: [u8; 16] = [
+    228, 69, 165, 46, 81, 203, 154, 29, 64, 198, 205, 232, 38, 8, 113, 226,
+];
+
+fn transfer(
+    idx: &str,
+    source: &str,
+    destination: &str,
+    mint: &str,
+    amount: u64,
+    decimals: u8,
+) -> Value {
+    common::transfer(
+        TOKEN_PROGRAM,
+        idx,
+        source,
+        destination,
+        mint,
+        amount,
+        decimals,
+    )
+}
+
+fn solfi_swap(amount_in: u64, quote_to_base: bool) -> Value {
+    let mut data = vec![7];
+    data.extend_from_slice(&amount_in.to_le_bytes());
+    data.extend_from_slice(&0u64.to_le_bytes());
+    data.push(quote_to_base as u8);
+    json!({
+        "programId": SOLFI,
+        "accounts": [
+            "user",
+            "pair",
+            "base-vault",
+            "quote-vault",
+            "user-base",
+            "user-quote",
+            TOKEN_PROGRAM,
+            "Sysvar1nstructions1111111111111111111111111",
+        ],
+        "data": bs58::encode(data).into_string(),
+    })
+}
+
+#[test]
+fn swap_buys_base_with_quote_from_the_pair_vaults() -> Result<()> {
+    let tx: SolanaTransaction = serde_json::from_value(json!({
+        "slot": 900,
+        "signature": "solfi",
+        "blockTime": 1_700_000_000,
+        "signers": ["user"],
+        "instructions": [solfi_swap(150_000_000, true)],
+        "transfers": [
+            transfer("0-0", "user-quote", "quote-vault", USDC_MINT, 150_000_000, 6),
+            transfer("0-1", "base-vault", "user-base", SOL_MINT, 1_000_000_000, 9),
+        ],
+    }))?;
+
+    let trades = DexParser::new().parse_trades(tx, None);
+
+    assert_eq!(trades.len(), 1);
+    let trade = &trades[0];
+    assert_eq!(trade.trade_type, TradeType::Sell);
+    assert_eq!(trade.program_id.as_deref(), Some(SOLFI));
+    assert_eq!(trade.amm.as_deref(), Some("SolFi"));
+    assert_eq!(trade.pool, ["pair"]);
+    assert_eq!(trade.input_token.mint, USDC_MINT);
+    assert_eq!(trade.input_token.amount, 150.0);
+    assert_eq!(trade.output_token.mint, SOL_MINT);
+    assert_eq!(trade.output_token.amount_raw, "1000000000");
+    Ok(())
+}
+
+#[test]
+fn jupiter_hop_through_solfi_is_attributed_by_name() -> Result<()> {
+    let mut event = SWAP_EVENT.to_vec();
+    event.extend(bs58::decode(SOLFI).into_vec()?);
+    event.extend(bs58::decode(SOL_MINT).into_vec()?);
+    event.extend_from_slice(&1_000_000_000u64.to_le_bytes());
+    event.extend(bs58::decode(USDC_MINT).into_vec()?);
+    event.extend_from_slice(&150_000_000u64.to_le_bytes());
+
+    let tx: SolanaTransaction = serde_json::from_value(json!({
+        "slot": 900,
+        "signature": "jupiter",
+        "blockTime": 1_700_000_000,
+        "signers": ["user"],
+        "instructions": [{ "programId": JUPITER, "accounts": [], "data": "" }],
+        "innerInstructions": [{
+            "index": 0,
+            "instructions": [
+                { "programId": SOLFI, "accounts": [], "data": "" },
+                {
+                    "programId": JUPITER,
+                    "accounts": [],
+                    "data": bs58::encode(event).into_string(),
+                },
+            ],
+        }],
+    }))?;
+
+    let trades = DexParser::new().parse_trades(tx, None);
+
+    let trade = trades
+        .iter()
+        .find(|trade| trade.program_id.as_deref() == Some(JUPITER))
+        .expect("jupiter route trade");
+    assert_eq!(trade.amm.as_deref(), Some("SolFi"));
+    assert_eq!(
+        trade.amms.as_deref(),
+        Some(["SolFi".to_string()].as_slice())
+    );
+    Ok(())
+}
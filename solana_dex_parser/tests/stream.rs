@@ -0,0 +1,50 @@
+use std::thread;
+use std::time::Duration;
+
+use solana_dex_parser::stream::{bounded, Disconnected, OverflowPolicy};
+
+#[test]
+fn drop_policies_keep_the_queue_at_capacity() {
+    let (sender, receiver) = bounded(2, OverflowPolicy::DropNewest);
+    (1..=4).for_each(|item| sender.send(item).unwrap());
+    drop(sender);
+    assert_eq!(receiver.counters().snapshot().dropped, 2);
+    assert_eq!(receiver.collect::<Vec<_>>(), [1, 2]);
+
+    let (sender, receiver) = bounded(2, OverflowPolicy::DropOldest);
+    (1..=4).for_each(|item| sender.send(item).unwrap());
+    drop(sender);
+    let metrics = receiver.counters().snapshot();
+    assert_eq!(
+        (metrics.sent, metrics.dropped, metrics.max_depth),
+        (4, 2, 2)
+    );
+    assert_eq!(receiver.collect::<Vec<_>>(), [3, 4]);
+}
+
+#[test]
+fn park_blocks_the_sender_until_the_consumer_catches_up() {
+    let (sender, receiver) = bounded(1, OverflowPolicy::Park);
+    let counters = sender.counters();
+    let producer = thread::spawn(move || (1..=3).for_each(|item| sender.send(item).unwrap()));
+
+    thread::sleep(Duration::from_millis(50));
+    assert_eq!(counters.snapshot().depth, 1);
+    assert_eq!(receiver.collect::<Vec<_>>(), [1, 2, 3]);
+    producer.join().unwrap();
+
+    let metrics = counters.snapshot();
+    assert_eq!((metrics.sent, metrics.dropped, metrics.depth), (3, 0, 0));
+    assert!(metrics.parked_ms >= 40);
+}
+
+#[test]
+fn parked_sender_is_released_when_the_receiver_goes_away() {
+    let (sender, receiver) = bounded(1, OverflowPolicy::Park);
+    sender.send(1).unwrap();
+    let producer = thread::spawn(move || sender.send(2));
+
+    thread::sleep(Duration::from_millis(20));
+    drop(receiver);
+    assert_eq!(producer.join().unwrap(), Err(Disconnected(2)));
+}
@@ -0,0 +1,57 @@
+use anyhow::Result;
+use serde_json::json;
+use solana_dex_parser::{DexParser, ParseConfig, SolanaTransaction};
+
+mod common;
+
+fn transaction() -> Result<SolanaTransaction> {
+    common::transaction(
+        "timed",
+        json!({
+            "instructions": [
+                { "programId": "MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr", "accounts": ["user"], "data": "" }
+            ],
+        }),
+    )
+}
+
+#[test]
+fn timings_are_off_by_default() -> Result<()> {
+    let result = DexParser::new().parse_all(transaction()?, None);
+
+    assert!(result.timings.is_none());
+    assert!(serde_json::to_value(&result)?.get("timings").is_none());
+    Ok(())
+}
+
+#[test]
+fn records_every_stage_that_ran() -> Result<()> {
+    let config = ParseConfig {
+        record_timings: true,
+        ..ParseConfig::default()
+    };
+
+    let result = DexParser::new().parse_all(transaction()?, Some(config));
+    let timings = result.timings.expect("timings recorded");
+    let stages: Vec<&str> = timings
+        .stages
+        .iter()
+        .map(|stage| stage.stage.as_str())
+        .collect();
+    assert_eq!(
+        stages,
+        [
+            "normalize",
+            "prepare",
+            "trades",
+            "liquidity",
+            "memeEvents",
+            "transfers",
+            "enrich",
+            "postProcess"
+        ]
+    );
+    let staged: u64 = timings.stages.iter().map(|stage| stage.micros).sum();
+    assert!(staged <= timings.total_micros);
+    Ok(())
+}
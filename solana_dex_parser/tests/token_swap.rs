@@ -0,0 +1,134 @@
+use anyhow::Result;
+use serde_json::{json, Value};
+use solana_dex_parser::types::TradeType;
+use solana_dex_parser::{DexParser, SolanaTransaction};
+
+mod common;
+
+const SPL_TOKEN_SWAP: &str = "SwaPpA9LAaLfeLi3a68M4DjnLqgtticKg6CnyNwgAC8";
+const STEP: &str = "SSwpMgqNDsyV7mAgN9ady4bDVu5ySjmmXejXvy2vLt1";
+const PENGUIN: &str = "PSwapMdSai8tjrEXcxFeQth87xC4rRsa4VA5mhGhXkP";
+const SAROS: &str = "SSwapUtytfBdBn1b9NUGG6foMVPtcWgpRU32HToDUZr";
+const TOKEN_PROGRAM: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+const USDC_MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+const STEP_MINT: &str = "StepAscQoEioFxxWGnh2sLBDFp9d8rvKz2Yp39iDpyT";
+
+fn transfer(idx: &str, source: &str, destination: &str, mint: &str, amount: u64) -> Value {
+    let decimals = if mint == USDC_MINT { 6 } else { 9 };
+    common::transfer(
+        TOKEN_PROGRAM,
+        idx,
+        source,
+        destination,
+        mint,
+        amount,
+        decimals,
+    )
+}
+
+fn transaction(
+    program_id: &str,
+    tag: u8,
+    args: &[u64],
+    accounts: &[&str],
+    transfers: Vec<Value>,
+) -> Result<SolanaTransaction> {
+    let mut data = vec![tag];
+    for arg in args {
+        data.extend_from_slice(&arg.to_le_bytes());
+    }
+    common::transaction(
+        "token-swap",
+        json!({
+            "blockTime": 1_620_000_000,
+            "instructions": [{
+                "programId": program_id,
+                "accounts": accounts,
+                "data": bs58::encode(data).into_string(),
+            }],
+            "transfers": transfers,
+        }),
+    )
+}
+
+#[test]
+fn legacy_swaps_are_named_after_their_fork() -> Result<()> {
+    for (program_id, venue) in [
+        (SPL_TOKEN_SWAP, "SplTokenSwap"),
+        (STEP, "Step"),
+        (PENGUIN, "Penguin"),
+        (SAROS, "Saros"),
+    ] {
+        let tx = transaction(
+            program_id,
+            1,
+            &[2_000_000, 0],
+            &[
+                "pool",
+                "authority",
+                "user",
+                "user-usdc",
+                "vault-usdc",
+                "vault-step",
+                "user-step",
+                "lp-mint",
+                "fee-account",
+                TOKEN_PROGRAM,
+            ],
+            vec![
+                transfer("0-0", "user-usdc", "vault-usdc", USDC_MINT, 2_000_000),
+                transfer("0-1", "vault-step", "user-step", STEP_MINT, 12_500_000_000),
+            ],
+        )?;
+
+        let trades = DexParser::new().parse_trades(tx, None);
+
+        assert_eq!(trades.len(), 1, "{venue}");
+        let trade = &trades[0];
+        assert_eq!(trade.amm.as_deref(), Some(venue));
+        assert_eq!(trade.program_id.as_deref(), Some(program_id));
+        assert_eq!(trade.trade_type, TradeType::Swap);
+        assert_eq!(trade.pool, ["pool"]);
+        assert_eq!(trade.input_token.amount, 2.0);
+        assert_eq!(trade.output_token.mint, STEP_MINT);
+        assert_eq!(trade.output_token.amount, 12.5);
+    }
+    Ok(())
+}
+
+#[test]
+fn saros_withdrawal_is_a_liquidity_event() -> Result<()> {
+    let tx = transaction(
+        SAROS,
+        3,
+        &[1_000, 0, 0],
+        &[
+            "pool",
+            "authority",
+            "user",
+            "lp-mint",
+            "user-lp",
+            "vault-usdc",
+            "vault-step",
+            "user-usdc",
+            "user-step",
+        ],
+        vec![
+            transfer("0-0", "vault-usdc", "user-usdc", USDC_MINT, 4_000_000),
+            transfer("0-1", "vault-step", "user-step", STEP_MINT, 25_000_000_000),
+        ],
+    )?;
+
+    let result = DexParser::new().parse_all(tx, None);
+
+    assert!(result.trades.is_empty());
+    assert_eq!(result.liquidities.len(), 1);
+    let event = &result.liquidities[0];
+    assert_eq!(event.event_type, TradeType::Remove);
+    assert_eq!(event.amm.as_deref(), Some("Saros"));
+    assert_eq!(event.pool_id, "pool");
+    assert_eq!(event.token0_mint.as_deref(), Some(USDC_MINT));
+    assert_eq!(event.token1_amount, Some(25.0));
+    assert_eq!(event.lp_amount_raw.as_deref(), Some("1000"));
+    Ok(())
+}
@@ -0,0 +1,123 @@
+use anyhow::Result;
+use serde_json::{json, Value};
+use solana_dex_parser::{DexParser, ParseConfig, SolanaTransaction, TradingBot};
+
+mod common;
+
+const STEP: &str = "SSwpMgqNDsyV7mAgN9ady4bDVu5ySjmmXejXvy2vLt1";
+const SYSTEM_PROGRAM: &str = "11111111111111111111111111111111";
+const TOKEN_PROGRAM: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+const USDC_MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+const PHOTON_FEE_WALLET: &str = "AVUCZyuT35YSuj4RH7fwiyPu82Djn2Hfg7y2ND2XcnZH";
+const BOT_ROUTER: &str = "BotRouter1111111111111111111111111111111111";
+
+fn transfer(program_id: &str, idx: &str, source: &str, destination: &str, mint: &str) -> Value {
+    let (amount, decimals) = if mint == USDC_MINT {
+        (5_000_000, 6)
+    } else {
+        (1_000_000_000, 9)
+    };
+    common::transfer(program_id, idx, source, destination, mint, amount, decimals)
+}
+
+fn step_swap() -> Value {
+    let mut data = vec![1u8];
+    data.extend_from_slice(&1_000_000_000u64.to_le_bytes());
+    data.extend_from_slice(&0u64.to_le_bytes());
+    json!({
+        "programId": STEP,
+        "accounts": [
+            "pool", "authority", "user", "user-sol", "vault-sol", "vault-usdc", "user-usdc",
+        ],
+        "data": bs58::encode(data).into_string(),
+    })
+}
+
+fn transaction(instructions: Value, inner: Value, transfers: Value) -> Result<SolanaTransaction> {
+    common::transaction(
+        "bot",
+        json!({
+            "instructions": instructions,
+            "innerInstructions": inner,
+            "transfers": transfers,
+        }),
+    )
+}
+
+#[test]
+fn photon_fee_paid_in_its_own_instruction_is_a_bot_fee() -> Result<()> {
+    let mut fee = vec![2u8, 0, 0, 0];
+    fee.extend_from_slice(&10_000_000u64.to_le_bytes());
+    let tx = transaction(
+        json!([
+            step_swap(),
+            {
+                "programId": SYSTEM_PROGRAM,
+                "accounts": ["user", PHOTON_FEE_WALLET],
+                "data": bs58::encode(fee).into_string(),
+            },
+        ]),
+        json!([]),
+        json!([
+            transfer(STEP, "0-0", "user-sol", "vault-sol", SOL_MINT),
+            transfer(STEP, "0-1", "vault-usdc", "user-usdc", USDC_MINT),
+        ]),
+    )?;
+
+    let trades = DexParser::new().parse_trades(tx, None);
+
+    assert_eq!(trades.len(), 1);
+    let fee = &trades[0].fees[0];
+    assert_eq!(fee.fee_type.as_deref(), Some("bot"));
+    assert_eq!(fee.dex.as_deref(), Some("Photon"));
+    assert_eq!(fee.recipient.as_deref(), Some(PHOTON_FEE_WALLET));
+    assert_eq!(fee.mint, SOL_MINT);
+    assert_eq!(fee.amount, 0.01);
+    // The swap stays as executed; the bot fee is on top of it.
+    assert_eq!(trades[0].input_token.amount, 1.0);
+    Ok(())
+}
+
+#[test]
+fn configured_bot_router_is_not_a_venue_and_its_token_fee_is_reported() -> Result<()> {
+    let config = ParseConfig {
+        trading_bots: vec![TradingBot {
+            name: "Custom".to_string(),
+            program_ids: vec![BOT_ROUTER.to_string()],
+            fee_wallets: vec!["custom-fee".to_string()],
+        }],
+        ..ParseConfig::default()
+    };
+    let mut fee = transfer(BOT_ROUTER, "0-3", "user-usdc", "custom-fee-usdc", USDC_MINT);
+    fee["info"]["destinationOwner"] = json!("custom-fee");
+    let tx = transaction(
+        json!([{ "programId": BOT_ROUTER, "accounts": ["user"], "data": "" }]),
+        json!([{
+            "index": 0,
+            "instructions": [
+                step_swap(),
+                { "programId": TOKEN_PROGRAM, "accounts": [], "data": "" },
+                { "programId": TOKEN_PROGRAM, "accounts": [], "data": "" },
+                { "programId": TOKEN_PROGRAM, "accounts": [], "data": "" },
+            ],
+        }]),
+        json!([
+            transfer(BOT_ROUTER, "0-1", "user-sol", "vault-sol", SOL_MINT),
+            transfer(BOT_ROUTER, "0-2", "vault-usdc", "user-usdc", USDC_MINT),
+            fee,
+        ]),
+    )?;
+
+    let trades = DexParser::new().parse_trades(tx, Some(config));
+
+    assert_eq!(trades.len(), 1);
+    assert_eq!(trades[0].amm.as_deref(), Some("Step"));
+    let fee = &trades[0].fees[0];
+    assert_eq!(fee.fee_type.as_deref(), Some("bot"));
+    assert_eq!(fee.dex.as_deref(), Some("Custom"));
+    assert_eq!(fee.recipient.as_deref(), Some("custom-fee"));
+    assert_eq!(fee.mint, USDC_MINT);
+    assert_eq!(fee.amount_raw, "5000000");
+    Ok(())
+}
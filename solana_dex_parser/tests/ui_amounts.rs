@@ -0,0 +1,65 @@
+use anyhow::Result;
+use serde_json::json;
+use solana_dex_parser::format::to_json_value;
+use solana_dex_parser::types::{TokenAmount, TokenInfo};
+use solana_dex_parser::{ParseResult, PoolEvent, TradeInfo, UiAmountFormat};
+
+fn result() -> ParseResult {
+    ParseResult {
+        signature: "sig".to_string(),
+        fee: TokenAmount::new("5000", 9, Some(0.000005)),
+        trades: vec![TradeInfo {
+            signature: "sig".into(),
+            input_token: TokenInfo {
+                mint: "usdc".to_string(),
+                amount: 0.1 + 0.2,
+                amount_raw: "300000".to_string(),
+                decimals: 6,
+                ..TokenInfo::default()
+            },
+            output_token: TokenInfo {
+                mint: "sol".to_string(),
+                amount: 0.002,
+                amount_raw: "2000000".to_string(),
+                decimals: 9,
+                ..TokenInfo::default()
+            },
+            ..TradeInfo::default()
+        }],
+        liquidities: vec![PoolEvent {
+            signature: "sig".into(),
+            token0_amount: Some(1.2),
+            token0_amount_raw: Some("1200000".to_string()),
+            token0_decimals: Some(6),
+            lp_amount: Some(2.5),
+            ..PoolEvent::default()
+        }],
+        ..ParseResult::new()
+    }
+}
+
+#[test]
+fn string_format_renders_exact_decimals_across_result_types() -> Result<()> {
+    let value = to_json_value(&result(), UiAmountFormat::String)?;
+
+    assert_eq!(value["fee"]["uiAmount"], json!("0.000005"));
+    assert_eq!(value["fee"]["amount"], json!("5000"));
+    let trade = &value["trades"][0];
+    assert_eq!(trade["inputToken"]["amount"], json!("0.3"));
+    assert_eq!(trade["inputToken"]["amountRaw"], json!("300000"));
+    assert_eq!(trade["outputToken"]["amount"], json!("0.002"));
+    let event = &value["liquidities"][0];
+    assert_eq!(event["token0Amount"], json!("1.2"));
+    assert_eq!(event["lpAmount"], json!("2.5"));
+    Ok(())
+}
+
+#[test]
+fn number_format_matches_plain_serialization() -> Result<()> {
+    let result = result();
+    assert_eq!(
+        to_json_value(&result, UiAmountFormat::Number)?,
+        serde_json::to_value(&result)?
+    );
+    Ok(())
+}
@@ -0,0 +1,104 @@
+use solana_dex_parser::core::price::fill_usd_values;
+use solana_dex_parser::types::{TokenInfo, TradeType};
+use solana_dex_parser::{MemeEvent, PriceProvider, StablePairPrice, TradeInfo};
+
+mod common;
+
+const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+const USDC_MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+const USDT_MINT: &str = "Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB";
+const TOKEN_MINT: &str = "4wBqpZM9xaSheZzJSMawUKKwhdpChKbZ5eu5ky4Vigw";
+
+fn trade(input: TokenInfo, output: TokenInfo) -> TradeInfo {
+    let volume_sol = [&input, &output]
+        .into_iter()
+        .find(|token| token.mint == SOL_MINT)
+        .map(|token| token.amount);
+    TradeInfo {
+        trade_type: TradeType::Swap,
+        input_token: input,
+        output_token: output,
+        volume_sol,
+        ..TradeInfo::default()
+    }
+}
+
+#[test]
+fn stable_pairs_set_the_sol_price_weighted_by_sol_traded() {
+    let trades = [
+        trade(
+            common::ui_token(SOL_MINT, 1.0, 9),
+            common::ui_token(USDC_MINT, 150.0, 6),
+        ),
+        trade(
+            common::ui_token(USDT_MINT, 460.0, 6),
+            common::ui_token(SOL_MINT, 3.0, 9),
+        ),
+        trade(
+            common::ui_token(SOL_MINT, 10.0, 9),
+            common::ui_token(TOKEN_MINT, 1_000.0, 9),
+        ),
+    ];
+
+    let price = StablePairPrice::from_trades(&trades);
+
+    // 610 USD for 4 SOL.
+    assert_eq!(price.sol_usd(), Some(152.5));
+    assert_eq!(price.sol_price_usd(0), Some(152.5));
+    assert_eq!(price.price_in_sol(USDC_MINT, 0), Some(1.0 / 152.5));
+    assert_eq!(price.price_in_sol(TOKEN_MINT, 0), None);
+    assert_eq!(StablePairPrice::from_trades(&trades[2..]).sol_usd(), None);
+}
+
+#[test]
+fn fills_trades_and_meme_events_from_the_sol_price() {
+    let mut trades = vec![
+        trade(
+            common::ui_token(SOL_MINT, 2.0, 9),
+            common::ui_token(TOKEN_MINT, 1_000.0, 9),
+        ),
+        trade(
+            common::ui_token(TOKEN_MINT, 500.0, 9),
+            common::ui_token(USDC_MINT, 160.0, 6),
+        ),
+        trade(
+            common::ui_token(SOL_MINT, 1.0, 9),
+            common::ui_token(USDT_MINT, 150.0, 6),
+        ),
+    ];
+    let mut events = vec![MemeEvent {
+        event_type: TradeType::Buy,
+        base_mint: TOKEN_MINT.to_string(),
+        quote_mint: SOL_MINT.to_string(),
+        input_token: Some(common::ui_token(SOL_MINT, 2.0, 9)),
+        output_token: Some(common::ui_token(TOKEN_MINT, 1_000.0, 9)),
+        ..MemeEvent::default()
+    }];
+
+    fill_usd_values(&mut trades, &mut events, Some(150.0));
+
+    assert_eq!(trades[0].volume_usd, Some(300.0));
+    assert_eq!(trades[0].price_usd, Some(0.3));
+    // A stablecoin leg is taken at face value.
+    assert_eq!(trades[1].volume_usd, Some(160.0));
+    assert_eq!(trades[1].price_usd, Some(0.32));
+    // SOL itself is the priced token of a stable pair.
+    assert_eq!(trades[2].price_usd, Some(150.0));
+    assert_eq!(events[0].volume_usd, Some(300.0));
+    assert_eq!(events[0].price_usd, Some(0.3));
+}
+
+#[test]
+fn leaves_sol_trades_unpriced_without_a_sol_price() {
+    let mut trades = vec![trade(
+        common::ui_token(SOL_MINT, 2.0, 9),
+        common::ui_token(TOKEN_MINT, 1_000.0, 9),
+    )];
+
+    fill_usd_values(&mut trades, &mut [], None);
+
+    assert_eq!(trades[0].volume_usd, None);
+    assert_eq!(trades[0].price_usd, None);
+    let json = serde_json::to_value(&trades[0]).expect("serialize trade");
+    assert!(json.get("volumeUsd").is_none());
+}
@@ -0,0 +1,196 @@
+use anyhow::Result;
+use serde_json::{json, Value};
+use solana_dex_parser::types::TradeType;
+use solana_dex_parser::{DexParser, SolanaTransaction};
+
+mod common;
+
+const VIRTUALS: &str = "5U3EU2ubXtK84QcRjWVmYt9RaDyA8gKxdUrPFXmZyaki";
+const METEORA_DAMM_V2: &str = "cpamdpZCGKUy5JxQXB4dcpGPiikHawvSWAd6mEn1sGG";
+const VIRTUAL_MINT: &str = "3iQL8BFS2vE7mww4ehAqQHAsbmRNCrPxizWAT2Zfyr9y";
+const AGENT_MINT: &str = "4wBqpZM9xaSheZzJSMawUKKwhdpChKbZ5eu5ky4Vigw";
+
+const LAUNCH: [u8; 8] = [153, 241, 93, 225, 22, 69, 74, 61];
+const BUY: [u8; 8] = [102, 6, 61, 18, 1, 218, 235, 234];
+const SELL: [u8; 8] = [51, 230, 133, 164, 1, 127, 131, 173];
+const GRADUATE: [u8; 8] = [45, 235, 225, 181, 17, 218, 64, 130];
+
+fn borsh_string(value: &str) -> Vec<u8> {
+    let mut bytes = (value.len() as u32).to_le_bytes().to_vec();
+    bytes.extend_from_slice(value.as_bytes());
+    bytes
+}
+
+fn encode(parts: &[&[u8]]) -> String {
+    bs58::encode(parts.concat()).into_string()
+}
+
+fn transfer(idx: &str, source: &str, destination: &str, mint: &str, amount: u64) -> Value {
+    common::transfer(VIRTUALS, idx, source, destination, mint, amount, 9)
+}
+
+fn swap(discriminator: [u8; 8], amount: u64) -> Value {
+    json!({
+        "programId": VIRTUALS,
+        "accounts": [
+            "user",
+            AGENT_MINT,
+            VIRTUAL_MINT,
+            "curve",
+            "token-vault",
+            "virtual-vault",
+            "user-agent",
+            "user-virtual",
+        ],
+        "data": encode(&[&discriminator, &amount.to_le_bytes(), &0u64.to_le_bytes()]),
+    })
+}
+
+fn transaction(
+    instructions: Value,
+    inner_instructions: Value,
+    transfers: Value,
+) -> Result<SolanaTransaction> {
+    common::transaction(
+        "virtuals",
+        json!({
+            "instructions": instructions,
+            "innerInstructions": inner_instructions,
+            "transfers": transfers,
+        }),
+    )
+}
+
+#[test]
+fn launch_and_buy_emit_a_create_event_and_a_trade() -> Result<()> {
+    let launch_data = encode(&[
+        &LAUNCH,
+        &borsh_string("Agent Test"),
+        &borsh_string("AGENT"),
+        &borsh_string("https://example.com/agent.json"),
+    ]);
+    let tx = transaction(
+        json!([
+            {
+                "programId": VIRTUALS,
+                "accounts": ["user", AGENT_MINT, "curve", "virtuals-config"],
+                "data": launch_data,
+            },
+            swap(BUY, 500_000_000_000),
+        ]),
+        json!([]),
+        json!([
+            transfer(
+                "1-0",
+                "user-virtual",
+                "virtual-vault",
+                VIRTUAL_MINT,
+                500_000_000_000
+            ),
+            transfer(
+                "1-1",
+                "token-vault",
+                "user-agent",
+                AGENT_MINT,
+                12_000_000_000_000
+            ),
+        ]),
+    )?;
+
+    let result = DexParser::new().parse_all(tx, None);
+
+    let create = &result.meme_events[0];
+    assert_eq!(create.event_type, TradeType::Create);
+    assert_eq!(create.protocol.as_deref(), Some("Virtuals"));
+    assert_eq!(create.name.as_deref(), Some("Agent Test"));
+    assert_eq!(create.symbol.as_deref(), Some("AGENT"));
+    assert_eq!(create.creator.as_deref(), Some("user"));
+    assert_eq!(create.base_mint, AGENT_MINT);
+    assert_eq!(create.bonding_curve.as_deref(), Some("curve"));
+    assert_eq!(create.platform_config.as_deref(), Some("virtuals-config"));
+    assert_eq!(result.meme_events[1].event_type, TradeType::Buy);
+
+    assert_eq!(result.trades.len(), 1);
+    let trade = &result.trades[0];
+    assert_eq!(trade.trade_type, TradeType::Buy);
+    assert_eq!(trade.program_id.as_deref(), Some(VIRTUALS));
+    assert_eq!(trade.amm.as_deref(), Some("Virtuals"));
+    assert_eq!(trade.pool, ["curve"]);
+    assert_eq!(trade.user.as_deref(), Some("user"));
+    assert_eq!(trade.idx, "1-0");
+    assert_eq!(trade.input_token.mint, VIRTUAL_MINT);
+    assert_eq!(trade.input_token.amount_raw, "500000000000");
+    assert_eq!(trade.output_token.mint, AGENT_MINT);
+    assert_eq!(trade.output_token.amount_raw, "12000000000000");
+    Ok(())
+}
+
+#[test]
+fn sell_pays_out_of_the_virtual_vault() -> Result<()> {
+    let tx = transaction(
+        json!([swap(SELL, 1_000_000_000_000)]),
+        json!([]),
+        json!([
+            transfer(
+                "0-0",
+                "user-agent",
+                "token-vault",
+                AGENT_MINT,
+                1_000_000_000_000
+            ),
+            transfer(
+                "0-1",
+                "virtual-vault",
+                "user-virtual",
+                VIRTUAL_MINT,
+                40_000_000_000
+            ),
+        ]),
+    )?;
+
+    let trades = DexParser::new().parse_trades(tx, None);
+
+    assert_eq!(trades.len(), 1);
+    assert_eq!(trades[0].trade_type, TradeType::Sell);
+    assert_eq!(trades[0].input_token.mint, AGENT_MINT);
+    assert_eq!(trades[0].output_token.mint, VIRTUAL_MINT);
+    assert_eq!(trades[0].output_token.amount_raw, "40000000000");
+    Ok(())
+}
+
+#[test]
+fn graduation_is_reported_as_a_migration_into_damm_v2() -> Result<()> {
+    let tx = transaction(
+        json!([{
+            "programId": VIRTUALS,
+            "accounts": ["user", AGENT_MINT, VIRTUAL_MINT, "curve", "damm-pool"],
+            "data": encode(&[&GRADUATE]),
+        }]),
+        json!([{
+            "index": 0,
+            "instructions": [{ "programId": METEORA_DAMM_V2, "accounts": [], "data": "" }],
+        }]),
+        json!([]),
+    )?;
+
+    let result = DexParser::new().parse_all(tx, None);
+
+    assert!(result.trades.is_empty());
+    assert_eq!(result.meme_events.len(), 1);
+    let migrate = &result.meme_events[0];
+    assert_eq!(migrate.event_type, TradeType::Migrate);
+    assert_eq!(migrate.pool.as_deref(), Some("damm-pool"));
+    assert_eq!(migrate.pool_dex.as_deref(), Some("MeteoraDAMMV2"));
+
+    assert_eq!(result.migrations.len(), 1);
+    let migration = &result.migrations[0];
+    assert_eq!(migration.source_protocol, "Virtuals");
+    assert_eq!(migration.mint, AGENT_MINT);
+    assert_eq!(migration.bonding_curve, "curve");
+    assert_eq!(migration.pool.as_deref(), Some("damm-pool"));
+    assert_eq!(
+        migration.destination_program.as_deref(),
+        Some(METEORA_DAMM_V2)
+    );
+    Ok(())
+}
@@ -0,0 +1,103 @@
+use anyhow::Result;
+use serde_json::{json, Value};
+use solana_dex_parser::{DexParser, ParseConfig, SolanaTransaction};
+
+const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+const TOKEN_MINT: &str = "4wBqpZM9xaSheZzJSMawUKKwhdpChKbZ5eu5ky4Vigw";
+
+fn balance(account: &str, mint: &str, owner: &str, amount: u64) -> Value {
+    json!({
+        "account": account,
+        "mint": mint,
+        "owner": owner,
+        "uiTokenAmount": { "amount": amount.to_string(), "decimals": 6 },
+    })
+}
+
+/// The user spends 0.5 wrapped SOL from their own wrapped account on 100 tokens of the pool.
+fn swap_transaction() -> Result<SolanaTransaction> {
+    Ok(serde_json::from_value(json!({
+        "slot": 1,
+        "signature": "wallet-flows",
+        "blockTime": 1_700_000_000,
+        "signers": ["user"],
+        "instructions": [],
+        "meta": {
+            "fee": 5_000,
+            "computeUnits": 0,
+            "status": "SUCCESS",
+            "solBalanceChanges": {
+                "user": { "pre": 10_000_000, "post": 9_995_000, "change": -5_000 },
+                "user-wsol": { "pre": 502_039_280, "post": 2_039_280, "change": -500_000_000 },
+                "pool-wsol": { "pre": 2_039_280, "post": 502_039_280, "change": 500_000_000 },
+            },
+        },
+        "preTokenBalances": [
+            balance("user-wsol", SOL_MINT, "user", 500_000_000),
+            balance("user-token", TOKEN_MINT, "user", 0),
+            balance("pool-wsol", SOL_MINT, "pool", 0),
+            balance("pool-token", TOKEN_MINT, "pool", 1_000_000_000),
+        ],
+        "postTokenBalances": [
+            balance("user-wsol", SOL_MINT, "user", 0),
+            balance("user-token", TOKEN_MINT, "user", 100_000_000),
+            balance("pool-wsol", SOL_MINT, "pool", 500_000_000),
+            balance("pool-token", TOKEN_MINT, "pool", 900_000_000),
+        ],
+    }))?)
+}
+
+#[test]
+fn wallet_flows_are_only_embedded_when_enabled() -> Result<()> {
+    let result = DexParser::new().parse_all(swap_transaction()?, None);
+    assert_eq!(result.wallet_flows, None);
+
+    let serialized = serde_json::to_value(&result)?;
+    assert!(serialized.get("walletFlows").is_none());
+    assert_eq!(serialized["signerSolChange"], -500_005_000);
+    assert_eq!(serialized["signerTokenChanges"][TOKEN_MINT], 100_000_000);
+    Ok(())
+}
+
+#[test]
+fn wallet_flows_net_token_accounts_into_their_owner() -> Result<()> {
+    let config = ParseConfig {
+        include_wallet_flows: true,
+        ..ParseConfig::default()
+    };
+    let result = DexParser::new().parse_all(swap_transaction()?, Some(config));
+
+    let flows = result.wallet_flows.expect("wallet flows");
+    assert_eq!(flows.len(), 2);
+    // Wrapped SOL counts once, through the lamports of the wrapped account.
+    let user = &flows["user"];
+    assert_eq!(user.sol.as_ref().map(|sol| sol.change), Some(-500_005_000));
+    assert_eq!(user.tokens.len(), 1);
+    assert_eq!(user.tokens[TOKEN_MINT].change, 100_000_000);
+    let pool = &flows["pool"];
+    assert_eq!(pool.sol.as_ref().map(|sol| sol.change), Some(500_000_000));
+    assert_eq!(pool.tokens[TOKEN_MINT].change, -100_000_000);
+    Ok(())
+}
+
+#[test]
+fn signer_changes_are_the_fee_payer_wallet_flow() -> Result<()> {
+    let config = ParseConfig {
+        include_wallet_flows: true,
+        ..ParseConfig::default()
+    };
+    let result = DexParser::new().parse_all(swap_transaction()?, Some(config));
+    let flows = result.wallet_flows.expect("wallet flows");
+    let user = &flows["user"];
+
+    assert_eq!(
+        Some(result.signer_sol_change),
+        user.sol.as_ref().map(|sol| sol.change)
+    );
+    assert_eq!(result.signer_token_changes.len(), 1);
+    assert_eq!(
+        result.signer_token_changes[TOKEN_MINT],
+        user.tokens[TOKEN_MINT].change
+    );
+    Ok(())
+}
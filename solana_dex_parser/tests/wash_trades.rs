@@ -0,0 +1,146 @@
+use solana_dex_parser::core::wash::flag_wash_trades;
+use solana_dex_parser::types::TransferInfo;
+use solana_dex_parser::{ParseResult, TradeInfo, TransferData};
+
+mod common;
+
+const POOL: &str = "58oQChx4yWmvKdwLLZzBi4ChoCc2fqCUWBkwMihLYQo2";
+const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+const TOKEN_MINT: &str = "4wBqpZM9xaSheZzJSMawUKKwhdpChKbZ5eu5ky4Vigw";
+const SYSTEM_PROGRAM: &str = "11111111111111111111111111111111";
+const TOKEN_PROGRAM: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+/// Raydium AMM v4 authority, a PDA shared by every pool of the program.
+const RAYDIUM_AUTHORITY: &str = "5Q544fKrFoe6tsEbD7S8EmxGTJYAKtTVhAW5Q5pge4j1";
+const BINANCE_HOT_WALLET: &str = "5tzFkiKscXHK5ZXCGbXZxdw7gTjjD1mBwuoFbhUvuAi9";
+
+/// Buy of the token with SOL on the pool, or a sell when `buy` is unset.
+fn trade(user: &str, buy: bool) -> ParseResult {
+    let token = common::token(TOKEN_MINT, 1_000_000_000, 9);
+    let sol = common::token(SOL_MINT, 1_000_000_000, 9);
+    ParseResult {
+        signer: vec![user.to_string()],
+        trades: vec![TradeInfo {
+            pool: vec![POOL.to_string()],
+            user: Some(user.to_string()),
+            ..common::swap(token, sol, buy)
+        }],
+        ..ParseResult::new()
+    }
+}
+
+fn funding(parent: &str, child: &str) -> ParseResult {
+    funding_by(SYSTEM_PROGRAM, parent, child)
+}
+
+fn funding_by(program_id: &str, parent: &str, child: &str) -> ParseResult {
+    ParseResult {
+        signer: vec![parent.to_string()],
+        transfers: vec![TransferData {
+            transfer_type: "transfer".to_string(),
+            program_id: program_id.to_string(),
+            info: TransferInfo {
+                authority: Some(parent.to_string()),
+                source: parent.to_string(),
+                destination: child.to_string(),
+                mint: SOL_MINT.to_string(),
+                ..TransferInfo::default()
+            },
+            idx: "0-0".to_string(),
+            timestamp: 0,
+            signature: "funding".into(),
+            is_fee: false,
+        }],
+        ..ParseResult::new()
+    }
+}
+
+fn flags(results: &[ParseResult]) -> Vec<bool> {
+    results
+        .iter()
+        .flat_map(|result| &result.trades)
+        .map(|trade| trade.suspected_wash)
+        .collect()
+}
+
+#[test]
+fn round_trip_of_one_wallet_is_suspected_wash() {
+    let mut block = vec![
+        trade("alice", true),
+        trade("bob", true),
+        trade("alice", false),
+    ];
+
+    flag_wash_trades(&mut block);
+
+    assert_eq!(flags(&block), [true, false, true]);
+}
+
+#[test]
+fn wallets_funded_by_the_same_parent_are_one_owner() {
+    let mut block = vec![
+        funding("parent", "alice"),
+        funding("parent", "bob"),
+        trade("alice", true),
+        trade("bob", false),
+        trade("carol", false),
+    ];
+
+    flag_wash_trades(&mut block);
+
+    assert_eq!(flags(&block), [true, true, false]);
+}
+
+#[test]
+fn same_direction_trades_are_not_wash() {
+    let mut block = vec![trade("alice", true), trade("alice", true)];
+
+    flag_wash_trades(&mut block);
+
+    assert_eq!(flags(&block), [false, false]);
+}
+
+#[test]
+fn wrapped_sol_paid_out_of_pool_vaults_is_not_funding() {
+    let mut block = vec![
+        funding_by(TOKEN_PROGRAM, "pool-vault-owner", "alice"),
+        funding_by(TOKEN_PROGRAM, "pool-vault-owner", "bob"),
+        trade("alice", true),
+        trade("bob", false),
+    ];
+
+    flag_wash_trades(&mut block);
+
+    assert_eq!(flags(&block), [false, false]);
+}
+
+#[test]
+fn program_derived_and_exchange_funders_are_not_parents() {
+    let mut block = vec![
+        funding(RAYDIUM_AUTHORITY, "alice"),
+        funding(RAYDIUM_AUTHORITY, "bob"),
+        funding(BINANCE_HOT_WALLET, "carol"),
+        funding(BINANCE_HOT_WALLET, "dave"),
+        trade("alice", true),
+        trade("bob", false),
+        trade("carol", true),
+        trade("dave", false),
+    ];
+
+    flag_wash_trades(&mut block);
+
+    assert_eq!(flags(&block), [false, false, false, false]);
+}
+
+#[test]
+fn funding_after_the_trade_does_not_link_wallets() {
+    let mut block = vec![
+        trade("alice", true),
+        funding("parent", "alice"),
+        funding("parent", "bob"),
+        trade("bob", false),
+    ];
+
+    flag_wash_trades(&mut block);
+
+    assert_eq!(flags(&block), [false, false]);
+}
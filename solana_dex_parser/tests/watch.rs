@@ -0,0 +1,149 @@
+#[cfg(feature = "webhooks")]
+use std::io::{BufRead, BufReader, Read, Write};
+#[cfg(feature = "webhooks")]
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+#[cfg(feature = "webhooks")]
+use std::thread;
+
+#[cfg(feature = "webhooks")]
+use anyhow::Result;
+use solana_dex_parser::types::TokenInfo;
+#[cfg(feature = "webhooks")]
+use solana_dex_parser::WatchSpec;
+use solana_dex_parser::{AlertTarget, ParseResult, TradeAlert, TradeInfo, Watch, WatchList};
+
+const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+const MEME_MINT: &str = "4wBqpZM9xaSheZzJSMawUKKwhdpChKbZ5eu5ky4Vigw";
+
+fn trade(user: &str, idx: &str) -> TradeInfo {
+    TradeInfo {
+        user: Some(user.to_string()),
+        input_token: TokenInfo {
+            mint: SOL_MINT.to_string(),
+            ..TokenInfo::default()
+        },
+        output_token: TokenInfo {
+            mint: MEME_MINT.to_string(),
+            ..TokenInfo::default()
+        },
+        signature: "watched".into(),
+        idx: idx.to_string(),
+        ..TradeInfo::default()
+    }
+}
+
+/// Callback target recording the watch and trade of every alert in `seen`.
+fn recorder(seen: &Arc<Mutex<Vec<(String, String)>>>) -> AlertTarget {
+    let sink = seen.clone();
+    AlertTarget::Callback(Arc::new(move |alert: &TradeAlert| {
+        sink.lock()
+            .unwrap()
+            .push((alert.watch.clone(), alert.trade.idx.clone()));
+    }))
+}
+
+fn result(trades: Vec<TradeInfo>) -> ParseResult {
+    ParseResult {
+        trades,
+        ..ParseResult::new()
+    }
+}
+
+#[test]
+fn callbacks_receive_trades_of_watched_wallets_and_mints() {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let callback = recorder(&seen);
+    let watches = WatchList::new()
+        .with_watch(Watch::new("whale", callback.clone()).wallet("whale"))
+        .with_watch(Watch::new("meme", callback.clone()).mint(MEME_MINT))
+        .with_watch(Watch::new("other", callback).wallet("nobody"));
+
+    let delivered = watches.notify(&result(vec![trade("whale", "0-0"), trade("retail", "1-0")]));
+
+    assert_eq!(delivered, 3);
+    assert_eq!(
+        *seen.lock().unwrap(),
+        [
+            ("whale".to_string(), "0-0".to_string()),
+            ("meme".to_string(), "0-0".to_string()),
+            ("meme".to_string(), "1-0".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn watches_sharing_a_name_deliver_to_their_own_target() {
+    let (first, second) = (
+        Arc::new(Mutex::new(Vec::new())),
+        Arc::new(Mutex::new(Vec::new())),
+    );
+    let watches = WatchList::new()
+        .with_watch(Watch::new("desk", recorder(&first)).wallet("whale"))
+        .with_watch(Watch::new("desk", recorder(&second)).mint(MEME_MINT));
+
+    let delivered = watches.notify(&result(vec![trade("retail", "0-0")]));
+
+    assert_eq!(delivered, 1);
+    assert!(first.lock().unwrap().is_empty());
+    assert_eq!(
+        *second.lock().unwrap(),
+        [("desk".to_string(), "0-0".to_string())]
+    );
+}
+
+#[cfg(feature = "webhooks")]
+#[test]
+fn webhooks_receive_alerts_as_json_posts() -> Result<()> {
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let url = format!("http://{}/alerts", listener.local_addr()?);
+    let server = thread::spawn(move || -> Result<(String, TradeAlert)> {
+        let (stream, _) = listener.accept()?;
+        let mut reader = BufReader::new(stream);
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line)?;
+        let mut length = 0;
+        loop {
+            let mut header = String::new();
+            reader.read_line(&mut header)?;
+            if header.trim().is_empty() {
+                break;
+            }
+            if let Some((name, value)) = header.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    length = value.trim().parse()?;
+                }
+            }
+        }
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body)?;
+        reader
+            .get_mut()
+            .write_all(b"HTTP/1.1 204 No Content\r\nConnection: close\r\n\r\n")?;
+        Ok((request_line, serde_json::from_slice(&body)?))
+    });
+
+    let watches = WatchList::from_specs([WatchSpec {
+        name: "whale".to_string(),
+        wallets: vec!["whale".to_string()],
+        webhook: url,
+        ..WatchSpec::default()
+    }]);
+    let delivered = watches.notify(&result(vec![trade("whale", "0-0")]));
+
+    let (request_line, alert) = server.join().expect("webhook thread panicked")?;
+    assert_eq!(delivered, 1);
+    assert!(request_line.starts_with("POST /alerts "));
+    assert_eq!(alert.watch, "whale");
+    assert_eq!(alert.trade, trade("whale", "0-0"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn callback_lists_live_inside_an_async_runtime() {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let watches = WatchList::new().with_watch(Watch::new("whale", recorder(&seen)).wallet("whale"));
+
+    assert_eq!(watches.notify(&result(vec![trade("whale", "0-0")])), 1);
+    drop(watches);
+}
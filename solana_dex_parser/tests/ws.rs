@@ -0,0 +1,140 @@
+#![cfg(feature = "ws")]
+
+use std::fs;
+use std::sync::Arc;
+
+use futures::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use solana_dex_parser::ws::{self, SubscribeOptions, Subscription};
+use solana_dex_parser::DexParser;
+use tokio::net::TcpListener;
+use tokio_tungstenite::tungstenite::Message;
+
+const CLMM: &str = "CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK";
+
+fn fixture() -> Value {
+    serde_json::from_str(&fs::read_to_string("tests/fixtures/raydium_clmm_v0_lookup.json").unwrap())
+        .unwrap()
+}
+
+/// `transactionNotification` of an enhanced provider carrying `payload`.
+fn transaction_notification(payload: &Value) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "method": "transactionNotification",
+        "params": {
+            "subscription": 7,
+            "result": {
+                "transaction": {
+                    "transaction": payload["transaction"],
+                    "meta": payload["meta"],
+                    "version": payload["version"],
+                },
+                "signature": payload["transaction"]["signatures"][0],
+                "slot": payload["slot"],
+            },
+        },
+    })
+}
+
+/// Websocket node answering the subscribe request with `reply`, then sending `notifications`
+/// and closing. Hands the subscribe request back through the join handle.
+async fn serve(
+    reply: Value,
+    notifications: Vec<Value>,
+) -> (String, tokio::task::JoinHandle<Value>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("ws://{}", listener.local_addr().unwrap());
+    let node = tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut socket = tokio_tungstenite::accept_async(stream).await.unwrap();
+        let request = match socket.next().await.unwrap().unwrap() {
+            Message::Text(text) => serde_json::from_str(&text).unwrap(),
+            other => panic!("unexpected message {other:?}"),
+        };
+        socket.send(Message::Text(reply.to_string())).await.unwrap();
+        for notification in notifications {
+            socket
+                .send(Message::Text(notification.to_string()))
+                .await
+                .unwrap();
+        }
+        socket.close(None).await.unwrap();
+        request
+    });
+    (url, node)
+}
+
+#[tokio::test]
+async fn transaction_feed_yields_parsed_transactions() {
+    let payload = fixture();
+    let mut failed = payload.clone();
+    failed["meta"]["err"] = json!({"InstructionError": [0, "InvalidAccountData"]});
+    let (url, node) = serve(
+        json!({"jsonrpc": "2.0", "id": 1, "result": 7}),
+        vec![
+            json!({"jsonrpc": "2.0", "method": "slotNotification", "params": {}}),
+            transaction_notification(&failed),
+            transaction_notification(&payload),
+        ],
+    )
+    .await;
+
+    let subscription = Subscription::Transactions {
+        accounts: vec![CLMM.to_string()],
+    };
+    let mut feed = ws::subscribe(
+        &url,
+        subscription.clone(),
+        Arc::new(DexParser::new()),
+        SubscribeOptions::default(),
+    )
+    .await
+    .unwrap();
+
+    let result = feed.recv().await.unwrap().unwrap();
+    assert_eq!(result.signature, payload["transaction"]["signatures"][0]);
+    assert_eq!(result.slot, payload["slot"].as_u64().unwrap());
+    assert!(result
+        .programs_invoked
+        .iter()
+        .any(|program| program.program_id == CLMM));
+    assert!(feed.recv().await.is_none());
+    assert_eq!(node.await.unwrap(), subscription.request());
+}
+
+#[tokio::test]
+async fn rejected_subscription_is_an_error() {
+    let (url, _node) = serve(
+        json!({"jsonrpc": "2.0", "id": 1, "error": {"code": -32601, "message": "Method not found"}}),
+        Vec::new(),
+    )
+    .await;
+
+    let err = ws::subscribe(
+        &url,
+        Subscription::Transactions {
+            accounts: vec![CLMM.to_string()],
+        },
+        Arc::new(DexParser::new()),
+        SubscribeOptions::default(),
+    )
+    .await
+    .unwrap_err();
+
+    assert!(err.to_string().contains("subscription rejected"), "{err}");
+}
+
+#[test]
+fn logs_subscription_mentions_the_account() {
+    let subscription = Subscription::Logs {
+        account: CLMM.to_string(),
+        rpc_url: "http://127.0.0.1:8899".to_string(),
+    };
+
+    let request = subscription.request();
+
+    assert_eq!(request["method"], "logsSubscribe");
+    assert_eq!(request["params"][0]["mentions"], json!([CLMM]));
+    assert_eq!(request["params"][1]["commitment"], "confirmed");
+}
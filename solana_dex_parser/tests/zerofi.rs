@@ -0,0 +1,78 @@
+use anyhow::Result;
+use serde_json::{json, Value};
+use solana_dex_parser::{DexParser, SolanaTransaction};
+
+mod common;
+
+const ZEROFI: &str = "ZERor4xhbUycZ6gb9ntrhqscUcZmAbQDjEAtCf4hbZY";
+const TOKEN_PROGRAM: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+const USDC_MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+
+fn transfer(
+    idx: &str,
+    source: &str,
+    destination: &str,
+    mint: &str,
+    amount: u64,
+    decimals: u8,
+) -> Value {
+    common::transfer(
+        TOKEN_PROGRAM,
+        idx,
+        source,
+        destination,
+        mint,
+        amount,
+        decimals,
+    )
+}
+
+fn zerofi_swap(amount_in: u64) -> Value {
+    let mut data = vec![6];
+    data.extend_from_slice(&amount_in.to_le_bytes());
+    data.extend_from_slice(&0u64.to_le_bytes());
+    json!({
+        "programId": ZEROFI,
+        "accounts": [
+            "pair",
+            "vault-info-in",
+            "vault-in",
+            "vault-info-out",
+            "vault-out",
+            "user-in",
+            "user-out",
+            "user",
+            TOKEN_PROGRAM,
+            "Sysvar1nstructions1111111111111111111111111",
+        ],
+        "data": bs58::encode(data).into_string(),
+    })
+}
+
+#[test]
+fn swap_reports_the_oracle_quoted_fill_price() -> Result<()> {
+    let tx: SolanaTransaction = serde_json::from_value(json!({
+        "slot": 900,
+        "signature": "zerofi",
+        "blockTime": 1_700_000_000,
+        "signers": ["user"],
+        "instructions": [zerofi_swap(2_000_000_000)],
+        "transfers": [
+            transfer("0-0", "user-in", "vault-in", SOL_MINT, 2_000_000_000, 9),
+            transfer("0-1", "vault-out", "user-out", USDC_MINT, 301_000_000, 6),
+        ],
+    }))?;
+
+    let trades = DexParser::new().parse_trades(tx, None);
+
+    assert_eq!(trades.len(), 1);
+    let trade = &trades[0];
+    assert_eq!(trade.program_id.as_deref(), Some(ZEROFI));
+    assert_eq!(trade.amm.as_deref(), Some("ZeroFi"));
+    assert_eq!(trade.pool, ["pair"]);
+    assert_eq!(trade.input_token.mint, SOL_MINT);
+    assert_eq!(trade.output_token.amount, 301.0);
+    assert_eq!(trade.oracle_price, Some(150.5));
+    Ok(())
+}